@@ -1990,6 +1990,10 @@ impl Panel for AgentPanel {
         self.zoomed = zoomed;
         cx.notify();
     }
+
+    fn can_detach(&self, _cx: &App) -> bool {
+        true
+    }
 }
 
 impl AgentPanel {
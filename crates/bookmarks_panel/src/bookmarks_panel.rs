@@ -0,0 +1,631 @@
+use std::{path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use db::kvp::KEY_VALUE_STORE;
+use editor::Editor;
+use gpui::{
+    Action, App, AsyncWindowContext, Entity, EventEmitter, FocusHandle, Focusable, Pixels,
+    Subscription, Task, WeakEntity, actions, px,
+};
+use project::{
+    Fs, Project,
+    bookmark_store::{BookmarkStore, SerializedBookmark},
+};
+use serde::{Deserialize, Serialize};
+use settings::{RegisterSetting, Settings, SettingsStore};
+use text::Point;
+use ui::{IconButton, IconName, Label, ListItem, Tab, Tooltip, prelude::*};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+const BOOKMARKS_PANEL_KEY: &str = "BookmarksPanel";
+
+actions!(
+    bookmarks_panel,
+    [
+        /// Toggles focus on the bookmarks panel.
+        ToggleFocus,
+        /// Removes every bookmark in the workspace.
+        ClearAllBookmarks,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<BookmarksPanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Debug, RegisterSetting)]
+pub struct BookmarksPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+impl Settings for BookmarksPanelSettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let panel = content.bookmarks_panel.as_ref().unwrap();
+        Self {
+            button: panel.button.unwrap(),
+            dock: panel.dock.unwrap().into(),
+            default_width: panel.default_width.map(px).unwrap(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedBookmarksPanel {
+    width: Option<Pixels>,
+}
+
+/// The bookmark whose label is being edited.
+struct LabelEditor {
+    abs_path: Arc<Path>,
+    row: u32,
+    editor: Entity<Editor>,
+    _subscription: Subscription,
+}
+
+pub struct BookmarksPanel {
+    fs: Arc<dyn Fs>,
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    bookmark_store: Entity<BookmarkStore>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    label_editor: Option<LabelEditor>,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl BookmarksPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        let serialized_panel = match workspace
+            .read_with(&cx, |workspace, _| {
+                BookmarksPanel::serialization_key(workspace)
+            })
+            .ok()
+            .flatten()
+        {
+            Some(serialization_key) => cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(&serialization_key) })
+                .await
+                .context("loading bookmarks panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedBookmarksPanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten(),
+            None => None,
+        };
+
+        workspace.update(&mut cx, |workspace, cx| {
+            let panel = Self::new(workspace, cx);
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|width| width.round());
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let project = workspace.project().clone();
+        let bookmark_store = project.read(cx).bookmark_store();
+        let workspace_handle = workspace.weak_handle();
+        cx.new(|cx| {
+            let subscriptions = vec![
+                cx.observe(&bookmark_store, |_, _, cx| cx.notify()),
+                cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+            ];
+            Self {
+                fs,
+                workspace: workspace_handle,
+                project,
+                bookmark_store,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                label_editor: None,
+                pending_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            }
+        })
+    }
+
+    fn serialization_key(workspace: &Workspace) -> Option<String> {
+        workspace
+            .database_id()
+            .map(|id| i64::from(id).to_string())
+            .or(workspace.session_id())
+            .map(|id| format!("{}-{:?}", BOOKMARKS_PANEL_KEY, id))
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let Some(serialization_key) = self
+            .workspace
+            .read_with(cx, |workspace, _| {
+                BookmarksPanel::serialization_key(workspace)
+            })
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        serialization_key,
+                        serde_json::to_string(&SerializedBookmarksPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn clear_all_bookmarks(
+        &mut self,
+        _: &ClearAllBookmarks,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.label_editor = None;
+        self.bookmark_store
+            .update(cx, |bookmark_store, cx| bookmark_store.clear_bookmarks(cx));
+    }
+
+    fn open_bookmark(
+        &mut self,
+        abs_path: &Arc<Path>,
+        row: u32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project_path) = self.bookmark_store.read(cx).project_path(abs_path, cx) else {
+            return;
+        };
+        let Some(item) = self
+            .workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_path(project_path, None, true, window, cx)
+            })
+            .log_err()
+        else {
+            return;
+        };
+        window
+            .spawn(cx, async move |cx| {
+                let Some(editor) = item.await?.downcast::<Editor>() else {
+                    return Ok(());
+                };
+                editor.update_in(cx, |editor, window, cx| {
+                    editor.go_to_singleton_buffer_point(Point::new(row, 0), window, cx);
+                })?;
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn edit_label(
+        &mut self,
+        abs_path: Arc<Path>,
+        bookmark: &SerializedBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Bookmark label…", window, cx);
+            if let Some(label) = &bookmark.label {
+                editor.set_text(label.to_string(), window, cx);
+                editor.select_all(&editor::actions::SelectAll, window, cx);
+            }
+            editor
+        });
+        let subscription = cx.subscribe_in(
+            &editor,
+            window,
+            |this, _, event: &editor::EditorEvent, _, cx| {
+                if let editor::EditorEvent::Blurred = event {
+                    this.label_editor = None;
+                    cx.notify();
+                }
+            },
+        );
+        editor.focus_handle(cx).focus(window, cx);
+        self.label_editor = Some(LabelEditor {
+            abs_path,
+            row: bookmark.row,
+            editor,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(label_editor) = self.label_editor.take() else {
+            return;
+        };
+        let label = label_editor.editor.read(cx).text(cx);
+        self.bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.set_bookmark_label(
+                &label_editor.abs_path,
+                label_editor.row,
+                Some(label.into()),
+                cx,
+            );
+        });
+        self.focus_handle.focus(window, cx);
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.label_editor.take().is_some() {
+            self.focus_handle.focus(window, cx);
+            cx.notify();
+        }
+    }
+
+    fn render_file_header(&self, abs_path: &Arc<Path>, index: usize, cx: &App) -> impl IntoElement {
+        let path = self
+            .bookmark_store
+            .read(cx)
+            .project_path(abs_path, cx)
+            .and_then(|project_path| {
+                self.project
+                    .read(cx)
+                    .short_full_path_for_project_path(&project_path, cx)
+            })
+            .unwrap_or_else(|| abs_path.to_string_lossy().to_string());
+        h_flex()
+            .id(("bookmarks-file", index))
+            .px_2()
+            .pt_2()
+            .pb_1()
+            .gap_1()
+            .child(
+                Icon::new(IconName::File)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(Label::new(path).size(LabelSize::Small).truncate())
+    }
+
+    fn render_bookmark(
+        &self,
+        abs_path: &Arc<Path>,
+        bookmark: &SerializedBookmark,
+        id: usize,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let row = bookmark.row;
+        if let Some(label_editor) = &self.label_editor
+            && label_editor.abs_path == *abs_path
+            && label_editor.row == row
+        {
+            return h_flex()
+                .pl_6()
+                .pr_2()
+                .py_0p5()
+                .child(label_editor.editor.clone())
+                .into_any_element();
+        }
+
+        let line = Label::new(format!("{}", row + 1))
+            .size(LabelSize::Small)
+            .color(Color::Muted)
+            .buffer_font(cx);
+        let label = match &bookmark.label {
+            Some(label) => Label::new(label.clone()).truncate(),
+            None => Label::new(format!("Line {}", row + 1))
+                .color(Color::Muted)
+                .truncate(),
+        };
+        ListItem::new(("bookmark", id))
+            .indent_level(1)
+            .start_slot(line)
+            .child(label)
+            .end_hover_slot(
+                h_flex()
+                    .gap_0p5()
+                    .child(
+                        IconButton::new(("edit-bookmark-label", id), IconName::Pencil)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Edit Label"))
+                            .on_click({
+                                let abs_path = abs_path.clone();
+                                let bookmark = bookmark.clone();
+                                cx.listener(move |this, _, window, cx| {
+                                    this.edit_label(abs_path.clone(), &bookmark, window, cx);
+                                })
+                            }),
+                    )
+                    .child(
+                        IconButton::new(("remove-bookmark", id), IconName::Close)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Remove Bookmark"))
+                            .on_click({
+                                let abs_path = abs_path.clone();
+                                cx.listener(move |this, _, _, cx| {
+                                    this.bookmark_store.update(cx, |bookmark_store, cx| {
+                                        bookmark_store.remove_bookmark(&abs_path, row, cx);
+                                    });
+                                })
+                            }),
+                    ),
+            )
+            .on_click({
+                let abs_path = abs_path.clone();
+                cx.listener(move |this, _, window, cx| {
+                    this.open_bookmark(&abs_path, row, window, cx);
+                })
+            })
+            .into_any_element()
+    }
+}
+
+impl Render for BookmarksPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let bookmarks = self.bookmark_store.read(cx).all_bookmarks(cx);
+        let has_bookmarks = !bookmarks.is_empty();
+        let mut bookmark_index = 0;
+        v_flex()
+            .key_context("BookmarksPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::clear_all_bookmarks))
+            .size_full()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .px_2()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new("Bookmarks"))
+                    .when(has_bookmarks, |this| {
+                        this.child(
+                            IconButton::new("clear-bookmarks", IconName::Trash)
+                                .icon_size(IconSize::Small)
+                                .tooltip(Tooltip::text("Clear All Bookmarks"))
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(ClearAllBookmarks.boxed_clone(), cx)
+                                }),
+                        )
+                    }),
+            )
+            .map(|this| {
+                if !has_bookmarks {
+                    return this.child(
+                        v_flex()
+                            .flex_1()
+                            .justify_center()
+                            .items_center()
+                            .gap_1()
+                            .p_4()
+                            .child(Label::new("No bookmarks"))
+                            .child(
+                                Label::new("Toggle a bookmark on a line from the editor")
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    );
+                }
+                this.child(
+                    v_flex()
+                        .id("bookmarks")
+                        .flex_1()
+                        .overflow_y_scroll()
+                        .children(bookmarks.iter().enumerate().map(
+                            |(file_index, (abs_path, bookmarks))| {
+                                v_flex()
+                                    .child(self.render_file_header(abs_path, file_index, cx))
+                                    .children(bookmarks.iter().map(|bookmark| {
+                                        bookmark_index += 1;
+                                        self.render_bookmark(abs_path, bookmark, bookmark_index, cx)
+                                    }))
+                            },
+                        )),
+                )
+            })
+    }
+}
+
+impl Focusable for BookmarksPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for BookmarksPanel {}
+
+impl Panel for BookmarksPanel {
+    fn persistent_name() -> &'static str {
+        "BookmarksPanel"
+    }
+
+    fn panel_key() -> &'static str {
+        BOOKMARKS_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        BookmarksPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.bookmarks_panel.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| BookmarksPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        BookmarksPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::Bookmark)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Bookmarks Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        10
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    use super::*;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_bookmarks_panel(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "a.rs": "one\ntwo\nthree\n",
+                "b.rs": "four\nfive\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace =
+            multi_workspace.read_with(cx, |multi_workspace, _| multi_workspace.workspace().clone());
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = BookmarksPanel::new(workspace, cx);
+            workspace.add_panel(panel.clone(), window, cx);
+            panel
+        });
+
+        let bookmark_store = project.read_with(cx, |project, _| project.bookmark_store());
+        for (path, row) in [
+            (path!("/project/b.rs"), 1),
+            (path!("/project/a.rs"), 2),
+            (path!("/project/a.rs"), 0),
+        ] {
+            let buffer = project
+                .update(cx, |project, cx| project.open_local_buffer(path, cx))
+                .await
+                .unwrap();
+            let position =
+                buffer.read_with(cx, |buffer, _| buffer.anchor_before(Point::new(row, 0)));
+            bookmark_store.update(cx, |bookmark_store, cx| {
+                bookmark_store.toggle_bookmark(buffer, position, cx)
+            });
+        }
+        let a_path: Arc<Path> = Arc::from(Path::new(path!("/project/a.rs")));
+        let b_path: Arc<Path> = Arc::from(Path::new(path!("/project/b.rs")));
+        let unlabeled = |row| SerializedBookmark { row, label: None };
+        assert_eq!(
+            bookmark_store.read_with(cx, |bookmark_store, cx| bookmark_store.all_bookmarks(cx)),
+            [
+                (a_path.clone(), vec![unlabeled(0), unlabeled(2)]),
+                (b_path.clone(), vec![unlabeled(1)]),
+            ]
+            .into_iter()
+            .collect()
+        );
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.open_bookmark(&a_path, 2, window, cx)
+        });
+        cx.run_until_parked();
+        let editor = workspace
+            .read_with(cx, |workspace, cx| workspace.active_item_as::<Editor>(cx))
+            .expect("opening a bookmark opens its file");
+        let cursor = editor.update(cx, |editor, cx| {
+            editor
+                .selections
+                .newest::<Point>(&editor.display_snapshot(cx))
+                .head()
+        });
+        assert_eq!(cursor, Point::new(2, 0));
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.edit_label(a_path.clone(), &unlabeled(2), window, cx);
+            let label_editor = panel.label_editor.as_ref().unwrap().editor.clone();
+            label_editor.update(cx, |editor, cx| editor.set_text("Setup", window, cx));
+            panel.confirm(&menu::Confirm, window, cx);
+        });
+        assert_eq!(
+            bookmark_store.read_with(cx, |bookmark_store, cx| {
+                bookmark_store.all_bookmarks(cx).remove(&a_path)
+            }),
+            Some(vec![
+                unlabeled(0),
+                SerializedBookmark {
+                    row: 2,
+                    label: Some("Setup".into()),
+                },
+            ])
+        );
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.clear_all_bookmarks(&ClearAllBookmarks, window, cx)
+        });
+        assert!(
+            bookmark_store
+                .read_with(cx, |bookmark_store, cx| bookmark_store.all_bookmarks(cx))
+                .is_empty()
+        );
+    }
+}
@@ -891,6 +891,41 @@ impl BufferDiffInner<Entity<language::Buffer>> {
     }
 }
 
+/// The rows of the buffer a hunk spans, which is empty for deletions.
+fn hunk_rows(hunk: &DiffHunk) -> Range<u32> {
+    let end = hunk.range.end;
+    hunk.range.start.row..if end.column > 0 { end.row + 1 } else { end.row }
+}
+
+/// Whether `is_selected` covers all of a hunk's rows, or `None` when it
+/// covers none of them. Deletions count as selected when the row they were
+/// removed before is.
+fn hunk_selection(hunk: &DiffHunk, is_selected: impl Fn(u32) -> bool) -> Option<bool> {
+    let mut rows = hunk_rows(hunk);
+    if rows.is_empty() {
+        return is_selected(rows.start).then_some(true);
+    }
+    if !rows.clone().any(&is_selected) {
+        return None;
+    }
+    Some(rows.all(is_selected))
+}
+
+/// The buffer text of the lines of a hunk for which `include` holds.
+fn hunk_lines(
+    hunk: &DiffHunk,
+    buffer: &text::BufferSnapshot,
+    include: impl Fn(u32) -> bool,
+) -> String {
+    let mut text = String::new();
+    for row in hunk_rows(hunk).filter(|row| include(*row)) {
+        let start = Point::new(row, 0).max(hunk.range.start);
+        let end = Point::new(row + 1, 0).min(hunk.range.end);
+        text.extend(buffer.text_for_range(start..end));
+    }
+    text
+}
+
 impl BufferDiffInner<language::BufferSnapshot> {
     fn hunks_intersecting_range<'a>(
         &'a self,
@@ -1605,6 +1640,119 @@ impl BufferDiff {
         new_index_text
     }
 
+    /// Stages or unstages the lines within `rows` that the buffer added,
+    /// leaving the rest of their hunks as they were. The lines a hunk
+    /// removed are only staged or unstaged along with the whole hunk, once
+    /// `rows` covers all of its lines.
+    ///
+    /// Returns the new index text.
+    pub fn stage_or_unstage_lines(
+        &mut self,
+        stage: bool,
+        rows: &[Range<u32>],
+        buffer: &text::BufferSnapshot,
+        cx: &mut Context<Self>,
+    ) -> Option<Rope> {
+        let secondary_diff = self.secondary_diff.as_ref()?.read(cx);
+        if !secondary_diff.inner.base_text_exists || (!stage && !self.inner.base_text_exists) {
+            return None;
+        }
+        let index_text = secondary_diff.inner.base_text.read(cx).as_rope().clone();
+        let unstaged_hunks = secondary_diff
+            .snapshot(cx)
+            .hunks(buffer)
+            .collect::<Vec<_>>();
+        let is_selected = |row: u32| rows.iter().any(|range| range.contains(&row));
+
+        let mut edits = Vec::<(Range<usize>, String)>::new();
+        if stage {
+            for hunk in &unstaged_hunks {
+                let Some(covered) = hunk_selection(hunk, is_selected) else {
+                    continue;
+                };
+                let replacement = if covered {
+                    buffer.text_for_range(hunk.range.clone()).collect()
+                } else {
+                    let mut text = index_text
+                        .chunks_in_range(hunk.diff_base_byte_range.clone())
+                        .collect::<String>();
+                    if !text.is_empty() && !text.ends_with('\n') {
+                        text.push('\n');
+                    }
+                    text.push_str(&hunk_lines(hunk, buffer, is_selected));
+                    text
+                };
+                edits.push((hunk.diff_base_byte_range.clone(), replacement));
+            }
+        } else {
+            let head_text = self.inner.base_text.read(cx).as_rope().clone();
+            let uncommitted_hunks = self.snapshot(cx).hunks(buffer).collect::<Vec<_>>();
+            for hunk in &uncommitted_hunks {
+                let Some(covered) = hunk_selection(hunk, is_selected) else {
+                    continue;
+                };
+                let buffer_range = hunk.buffer_range.to_offset(buffer);
+
+                // Only lines whose staged and unstaged contents agree can be
+                // unstaged line by line, so skip hunks with unstaged changes.
+                let mut previous_unstaged_hunk_end = None;
+                let mut overlaps_unstaged_hunk = false;
+                for unstaged_hunk in &unstaged_hunks {
+                    let unstaged_range = unstaged_hunk.buffer_range.to_offset(buffer);
+                    if unstaged_range == buffer_range {
+                        overlaps_unstaged_hunk = true;
+                        break;
+                    } else if unstaged_range.end <= buffer_range.start {
+                        previous_unstaged_hunk_end =
+                            Some((unstaged_range.end, unstaged_hunk.diff_base_byte_range.end));
+                    } else if unstaged_range.start >= buffer_range.end {
+                        break;
+                    } else {
+                        overlaps_unstaged_hunk = true;
+                        break;
+                    }
+                }
+                if overlaps_unstaged_hunk {
+                    continue;
+                }
+                let index_offset = |offset: usize| match previous_unstaged_hunk_end {
+                    Some((buffer_end, index_end)) => index_end + offset - buffer_end,
+                    None => offset,
+                };
+
+                let replacement = if covered {
+                    head_text
+                        .chunks_in_range(hunk.diff_base_byte_range.clone())
+                        .collect()
+                } else {
+                    hunk_lines(hunk, buffer, |row| !is_selected(row))
+                };
+                edits.push((
+                    index_offset(buffer_range.start)..index_offset(buffer_range.end),
+                    replacement,
+                ));
+            }
+        }
+        if edits.is_empty() {
+            return None;
+        }
+        edits.sort_by_key(|(range, _)| range.start);
+
+        let mut new_index_text = Rope::new();
+        let mut index_cursor = index_text.cursor(0);
+        for (old_range, replacement_text) in edits {
+            new_index_text.append(index_cursor.slice(old_range.start));
+            index_cursor.seek_forward(old_range.end);
+            new_index_text.push(&replacement_text);
+        }
+        new_index_text.append(index_cursor.suffix());
+
+        cx.emit(BufferDiffEvent::HunksStagedOrUnstaged(Some(
+            new_index_text.clone(),
+        )));
+        Some(new_index_text)
+    }
+
     pub fn stage_or_unstage_all_hunks(
         &mut self,
         stage: bool,
@@ -2733,6 +2881,71 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_stage_and_unstage_lines(cx: &mut TestAppContext) {
+        let examples = [
+            (
+                "stage added line",
+                true,
+                "one\nfive\n",
+                "one\nfive\n",
+                1..2,
+                "one\nthree\nfive\n",
+            ),
+            (
+                "stage all lines",
+                true,
+                "one\nfive\n",
+                "one\nfive\n",
+                0..4,
+                "one\nthree\nfour\nfive\n",
+            ),
+            (
+                "stage line of modification",
+                true,
+                "one\ntwo\nfive\n",
+                "one\ntwo\nfive\n",
+                2..3,
+                "one\ntwo\nfour\nfive\n",
+            ),
+            (
+                "unstage added line",
+                false,
+                "one\nfive\n",
+                "one\nthree\nfour\nfive\n",
+                2..3,
+                "one\nthree\nfive\n",
+            ),
+            (
+                "unstage all lines",
+                false,
+                "one\nfive\n",
+                "one\nthree\nfour\nfive\n",
+                1..3,
+                "one\nfive\n",
+            ),
+        ];
+        let buffer_text = "one\nthree\nfour\nfive\n";
+
+        for (name, stage, head_text, index_text, rows, final_index_text) in examples {
+            let buffer = Buffer::new(ReplicaId::LOCAL, BufferId::new(1).unwrap(), buffer_text);
+            let unstaged_diff =
+                cx.new(|cx| BufferDiff::new_with_base_text(index_text, &buffer, cx));
+            let uncommitted_diff = cx.new(|cx| {
+                let mut diff = BufferDiff::new_with_base_text(head_text, &buffer, cx);
+                diff.set_secondary_diff(unstaged_diff);
+                diff
+            });
+
+            let new_index_text = uncommitted_diff.update(cx, |diff, cx| {
+                diff.stage_or_unstage_lines(stage, &[rows], &buffer, cx)
+                    .unwrap()
+                    .to_string()
+            });
+            assert_eq!(new_index_text, final_index_text, "example: {name}");
+        }
+    }
+
     #[gpui::test]
     async fn test_buffer_diff_compare(cx: &mut TestAppContext) {
         let base_text = "
@@ -2228,6 +2228,7 @@ async fn test_inlay_hint_refresh_is_forwarded(
                         show_type_hints: Some(false),
                         show_parameter_hints: Some(false),
                         show_other_hints: Some(false),
+                        show_chaining_hints: Some(false),
                         show_background: Some(false),
                         toggle_on_modifiers_press: None,
                     })
@@ -2246,6 +2247,7 @@ async fn test_inlay_hint_refresh_is_forwarded(
                         show_type_hints: Some(true),
                         show_parameter_hints: Some(true),
                         show_other_hints: Some(true),
+                        show_chaining_hints: Some(true),
                         show_background: Some(false),
                         toggle_on_modifiers_press: None,
                     })
@@ -6,7 +6,7 @@ use gpui::{App, WindowHandle, prelude::*};
 use std::sync::{Arc, Weak};
 use ui::{CollabNotification, prelude::*};
 use util::ResultExt;
-use workspace::AppState;
+use workspace::{AppState, MultiWorkspace};
 
 pub fn init(app_state: &Arc<AppState>, cx: &mut App) {
     let app_state = Arc::downgrade(app_state);
@@ -40,6 +40,18 @@ pub fn init(app_state: &Arc<AppState>, cx: &mut App) {
                         notification_windows.push(window);
                     }
                 }
+
+                cx.update(|cx| {
+                    if let Some(window) = cx
+                        .windows()
+                        .into_iter()
+                        .find_map(|window| window.downcast::<MultiWorkspace>())
+                    {
+                        window
+                            .update(cx, |_, window, _| window.request_attention())
+                            .log_err();
+                    }
+                });
             }
         }
     })
@@ -9,8 +9,8 @@ use std::{
 
 use client::parse_zed_link;
 use command_palette_hooks::{
-    CommandInterceptItem, CommandInterceptResult, CommandPaletteFilter,
-    GlobalCommandPaletteInterceptor,
+    CommandInterceptItem, CommandInterceptResult, CommandPaletteEvent, CommandPaletteFilter,
+    GlobalCommandPaletteInterceptor, GlobalCommandPaletteObserver,
 };
 
 use fuzzy::{StringMatch, StringMatchCandidate};
@@ -96,6 +96,7 @@ impl CommandPalette {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
+        GlobalCommandPaletteObserver::notify(CommandPaletteEvent::Opened, cx);
         let filter = CommandPaletteFilter::try_global(cx);
 
         let commands = window
@@ -588,6 +589,7 @@ impl PickerDelegate for CommandPaletteDelegate {
         })
         .detach_and_log_err(cx);
         let action = command.action;
+        GlobalCommandPaletteObserver::notify(CommandPaletteEvent::Confirmed(action.as_ref()), cx);
         window.focus(&self.previous_focus_handle, cx);
         self.dismissed(window, cx);
         window.dispatch_action(action, cx);
@@ -151,3 +151,42 @@ impl GlobalCommandPaletteInterceptor {
         Some(handler(query, workspace, cx))
     }
 }
+
+/// What the command palette reports to its observer.
+pub enum CommandPaletteEvent<'a> {
+    /// The command palette opened.
+    Opened,
+    /// The command palette is about to run the given action.
+    Confirmed(&'a dyn Action),
+}
+
+/// An observer of the command palette.
+#[derive(Clone)]
+pub struct GlobalCommandPaletteObserver(Rc<dyn Fn(CommandPaletteEvent<'_>, &mut App)>);
+
+impl Global for GlobalCommandPaletteObserver {}
+
+impl GlobalCommandPaletteObserver {
+    /// Sets the global observer.
+    ///
+    /// This will override the previous observer, if it exists.
+    pub fn set(cx: &mut App, observer: impl Fn(CommandPaletteEvent<'_>, &mut App) + 'static) {
+        cx.set_global(Self(Rc::new(observer)));
+    }
+
+    /// Clears the global observer.
+    pub fn clear(cx: &mut App) {
+        if cx.has_global::<Self>() {
+            cx.remove_global::<Self>();
+        }
+    }
+
+    /// Reports the given event to the observer, if there is one.
+    pub fn notify(event: CommandPaletteEvent<'_>, cx: &mut App) {
+        let Some(observer) = cx.try_global::<Self>() else {
+            return;
+        };
+        let handler = observer.0.clone();
+        handler(event, cx);
+    }
+}
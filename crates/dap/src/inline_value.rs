@@ -2,6 +2,8 @@
 pub enum VariableLookupKind {
     Variable,
     Expression,
+    /// Text to show as is, held in the location's `variable_name`.
+    Text,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -10,7 +12,7 @@ pub enum VariableScope {
     Global,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InlineValueLocation {
     pub variable_name: String,
     pub scope: VariableScope,
@@ -3,6 +3,8 @@ mod toolbar_controls;
 
 mod buffer_diagnostics;
 mod diagnostic_renderer;
+mod diagnostics_filter;
+mod diagnostics_panel;
 
 #[cfg(test)]
 mod diagnostics_tests;
@@ -10,7 +12,10 @@ mod diagnostics_tests;
 use anyhow::Result;
 use buffer_diagnostics::BufferDiagnosticsEditor;
 use collections::{BTreeSet, HashMap, HashSet};
+use db::kvp::KEY_VALUE_STORE;
 use diagnostic_renderer::DiagnosticBlock;
+use diagnostics_filter::{DiagnosticsFilter, diagnostic_source};
+pub use diagnostics_panel::DiagnosticsPanel;
 use editor::{
     Editor, EditorEvent, ExcerptRange, MultiBuffer, PathKey,
     display_map::{BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
@@ -27,7 +32,7 @@ use language::{
     ToTreeSitterPoint,
 };
 use project::{
-    DiagnosticSummary, Project, ProjectPath,
+    DiagnosticSummary, Project, ProjectItem as _, ProjectPath, WorktreeId,
     project_settings::{DiagnosticSeverity, ProjectSettings},
 };
 use settings::Settings;
@@ -42,8 +47,8 @@ use text::{BufferId, OffsetRangeExt};
 use theme::ActiveTheme;
 use toolbar_controls::DiagnosticsToolbarEditor;
 pub use toolbar_controls::ToolbarControls;
-use ui::{Icon, IconName, Label, h_flex, prelude::*};
-use util::ResultExt;
+use ui::{ContextMenu, Icon, IconName, Label, PopoverMenu, Tooltip, h_flex, prelude::*};
+use util::{ResultExt, rel_path::RelPath};
 use workspace::{
     ItemNavHistory, Workspace,
     item::{Item, ItemEvent, ItemHandle, SaveOptions, TabContentParams},
@@ -58,18 +63,36 @@ actions!(
         /// Toggles the display of warning-level diagnostics.
         ToggleWarnings,
         /// Toggles automatic refresh of diagnostics.
-        ToggleDiagnosticsRefresh
+        ToggleDiagnosticsRefresh,
+        /// Toggles the display of information and hint diagnostics.
+        ToggleInformation,
+        /// Toggles listing the files of each directory together.
+        ToggleGroupByDirectory,
+        /// Toggles showing only the diagnostics of the last active file.
+        ToggleCurrentFileOnly,
+        /// Focuses the text filter of the project diagnostics view.
+        FocusFilter
     ]
 );
 
+const FILTER_SERIALIZATION_KEY: &str = "ProjectDiagnosticsFilter";
+
 #[derive(Default)]
 pub(crate) struct IncludeWarnings(bool);
 impl Global for IncludeWarnings {}
 
+fn include_warnings(cx: &App) -> bool {
+    match cx.try_global::<IncludeWarnings>() {
+        Some(include_warnings) => include_warnings.0,
+        None => ProjectSettings::get_global(cx).diagnostics.include_warnings,
+    }
+}
+
 pub fn init(cx: &mut App) {
     editor::set_diagnostic_renderer(diagnostic_renderer::DiagnosticRenderer {}, cx);
     cx.observe_new(ProjectDiagnosticsEditor::register).detach();
     cx.observe_new(BufferDiagnosticsEditor::register).detach();
+    diagnostics_panel::init(cx);
 }
 
 pub(crate) struct ProjectDiagnosticsEditor {
@@ -83,15 +106,25 @@ pub(crate) struct ProjectDiagnosticsEditor {
     multibuffer: Entity<MultiBuffer>,
     paths_to_update: BTreeSet<ProjectPath>,
     include_warnings: bool,
+    filter: DiagnosticsFilter,
+    filter_editor: Entity<Editor>,
+    /// The last active file other than this view, for the "current file only" filter.
+    current_file: Option<ProjectPath>,
+    /// The directories of the files with diagnostics, in the order their
+    /// files are listed when grouping by directory.
+    directories: Vec<(WorktreeId, Arc<RelPath>)>,
     update_excerpts_task: Option<Task<Result<()>>>,
     diagnostic_summary_update: Task<()>,
-    _subscription: Subscription,
+    filter_update: Task<()>,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl EventEmitter<EditorEvent> for ProjectDiagnosticsEditor {}
 
 const DIAGNOSTICS_UPDATE_DEBOUNCE: Duration = Duration::from_millis(50);
 const DIAGNOSTICS_SUMMARY_UPDATE_DEBOUNCE: Duration = Duration::from_millis(30);
+const FILTER_UPDATE_DEBOUNCE: Duration = Duration::from_millis(100);
 
 impl Render for ProjectDiagnosticsEditor {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
@@ -101,23 +134,29 @@ impl Render for ProjectDiagnosticsEditor {
             0
         };
 
-        let child =
-            if warning_count + self.summary.error_count == 0 && self.editor.read(cx).is_empty(cx) {
-                let label = if self.summary.warning_count == 0 {
-                    SharedString::new_static("No problems in workspace")
-                } else {
-                    SharedString::new_static("No errors in workspace")
-                };
-                v_flex()
-                    .key_context("EmptyPane")
-                    .size_full()
-                    .gap_1()
-                    .justify_center()
-                    .items_center()
-                    .text_center()
-                    .bg(cx.theme().colors().editor_background)
-                    .child(Label::new(label).color(Color::Muted))
-                    .when(self.summary.warning_count > 0, |this| {
+        let child = if (warning_count + self.summary.error_count == 0
+            || self.filter.hides_diagnostics())
+            && self.editor.read(cx).is_empty(cx)
+        {
+            let label = if self.filter.hides_diagnostics() {
+                SharedString::new_static("No diagnostics match the filters")
+            } else if self.summary.warning_count == 0 {
+                SharedString::new_static("No problems in workspace")
+            } else {
+                SharedString::new_static("No errors in workspace")
+            };
+            v_flex()
+                .key_context("EmptyPane")
+                .size_full()
+                .gap_1()
+                .justify_center()
+                .items_center()
+                .text_center()
+                .bg(cx.theme().colors().editor_background)
+                .child(Label::new(label).color(Color::Muted))
+                .when(
+                    !self.include_warnings && self.summary.warning_count > 0,
+                    |this| {
                         let plural_suffix = if self.summary.warning_count > 1 {
                             "s"
                         } else {
@@ -135,17 +174,23 @@ impl Render for ProjectDiagnosticsEditor {
                                 }),
                             ),
                         )
-                    })
-            } else {
-                div().size_full().child(self.editor.clone())
-            };
+                    },
+                )
+        } else {
+            div().size_full().child(self.editor.clone())
+        };
 
-        div()
+        v_flex()
             .key_context("Diagnostics")
             .track_focus(&self.focus_handle(cx))
             .size_full()
             .on_action(cx.listener(Self::toggle_warnings))
             .on_action(cx.listener(Self::toggle_diagnostics_refresh))
+            .on_action(cx.listener(Self::toggle_information))
+            .on_action(cx.listener(Self::toggle_group_by_directory))
+            .on_action(cx.listener(Self::toggle_current_file_only))
+            .on_action(cx.listener(Self::focus_filter))
+            .child(self.render_filter_bar(cx))
             .child(child)
     }
 }
@@ -257,22 +302,73 @@ impl ProjectDiagnosticsEditor {
         )
         .detach();
         cx.observe_global_in::<IncludeWarnings>(window, |this, window, cx| {
-            let include_warnings = cx.global::<IncludeWarnings>().0;
-            this.include_warnings = include_warnings;
-            this.editor.update(cx, |editor, cx| {
-                editor.set_max_diagnostics_severity(
-                    if include_warnings {
-                        DiagnosticSeverity::Warning
-                    } else {
-                        DiagnosticSeverity::Error
-                    },
-                    cx,
-                )
-            });
+            this.include_warnings = cx.global::<IncludeWarnings>().0;
+            this.update_max_diagnostics_severity(cx);
             this.refresh(window, cx);
         })
         .detach();
 
+        let filter_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Filter diagnostics…", window, cx);
+            editor
+        });
+        let filter_editor_subscription = cx.subscribe_in(
+            &filter_editor,
+            window,
+            |this: &mut Self, filter_editor, event, window, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    let query = filter_editor.read(cx).text(cx);
+                    if query != this.filter.query {
+                        this.filter.query = query;
+                        this.filter_update = cx.spawn_in(window, async move |this, cx| {
+                            cx.background_executor().timer(FILTER_UPDATE_DEBOUNCE).await;
+                            this.update_in(cx, |this, window, cx| this.filter_changed(window, cx))
+                                .log_err();
+                        });
+                    }
+                }
+            },
+        );
+        let mut subscriptions = vec![project_event_subscription, filter_editor_subscription];
+        if let Some(workspace) = workspace.upgrade() {
+            subscriptions.push(cx.subscribe_in(
+                &workspace,
+                window,
+                |this, workspace, event, window, cx| {
+                    if let workspace::Event::ActiveItemChanged = event {
+                        let active_item = workspace.read(cx).active_item(cx);
+                        this.active_item_changed(active_item, window, cx);
+                    }
+                },
+            ));
+        }
+
+        // The workspace may be mid-update while this view is created, so it
+        // is only read once that update is done.
+        cx.spawn_in(window, async move |this, cx| {
+            let serialization_key = this.update_in(cx, |this, window, cx| {
+                let workspace = this.workspace.upgrade()?;
+                let active_item = workspace.read(cx).active_item(cx);
+                this.active_item_changed(active_item, window, cx);
+                Self::filter_serialization_key(workspace.read(cx))
+            })?;
+            let Some(serialization_key) = serialization_key else {
+                return anyhow::Ok(());
+            };
+            let serialized_filter = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(&serialization_key) })
+                .await?;
+            if let Some(serialized_filter) = serialized_filter {
+                let filter = serde_json::from_str::<DiagnosticsFilter>(&serialized_filter)?;
+                this.update_in(cx, |this, window, cx| {
+                    this.restore_filter(filter, window, cx)
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+
         let project = project_handle.read(cx);
         let mut this = Self {
             project: project_handle.clone(),
@@ -280,6 +376,10 @@ impl ProjectDiagnosticsEditor {
             diagnostics: Default::default(),
             blocks: Default::default(),
             include_warnings,
+            filter: DiagnosticsFilter::default(),
+            filter_editor,
+            current_file: None,
+            directories: Vec::new(),
             workspace,
             multibuffer: excerpts,
             focus_handle,
@@ -287,12 +387,136 @@ impl ProjectDiagnosticsEditor {
             paths_to_update: Default::default(),
             update_excerpts_task: None,
             diagnostic_summary_update: Task::ready(()),
-            _subscription: project_event_subscription,
+            filter_update: Task::ready(()),
+            pending_serialization: Task::ready(None),
+            _subscriptions: subscriptions,
         };
         this.refresh(window, cx);
         this
     }
 
+    fn filter_serialization_key(workspace: &Workspace) -> Option<String> {
+        workspace
+            .database_id()
+            .map(|id| i64::from(id).to_string())
+            .or(workspace.session_id())
+            .map(|id| format!("{}-{:?}", FILTER_SERIALIZATION_KEY, id))
+    }
+
+    fn serialize_filter(&mut self, cx: &mut Context<Self>) {
+        let Some(serialization_key) = self
+            .workspace
+            .read_with(cx, |workspace, _| Self::filter_serialization_key(workspace))
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        let filter = self.filter.clone();
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(serialization_key, serde_json::to_string(&filter)?)
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn restore_filter(
+        &mut self,
+        filter: DiagnosticsFilter,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if filter == self.filter {
+            return;
+        }
+        self.filter_editor.update(cx, |filter_editor, cx| {
+            if filter_editor.text(cx) != filter.query {
+                filter_editor.set_text(filter.query.clone(), window, cx);
+            }
+        });
+        self.filter = filter;
+        self.filter_update = Task::ready(());
+        self.update_max_diagnostics_severity(cx);
+        self.apply_filter(window, cx);
+    }
+
+    /// Lists the diagnostics matching the filter anew.
+    fn apply_filter(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.multibuffer
+            .update(cx, |multibuffer, cx| multibuffer.clear(cx));
+        self.refresh(window, cx);
+        cx.notify();
+    }
+
+    fn filter_changed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.apply_filter(window, cx);
+        self.serialize_filter(cx);
+    }
+
+    fn active_item_changed(
+        &mut self,
+        active_item: Option<Box<dyn ItemHandle>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project_path) = active_item
+            .filter(|item| item.item_id() != cx.entity_id())
+            .and_then(|item| item.project_path(cx))
+        else {
+            return;
+        };
+        if self.current_file.as_ref() == Some(&project_path) {
+            return;
+        }
+        self.current_file = Some(project_path);
+        if self.filter.current_file_only {
+            self.apply_filter(window, cx);
+        }
+    }
+
+    fn update_max_diagnostics_severity(&mut self, cx: &mut Context<Self>) {
+        let max_severity = if self.filter.include_information {
+            DiagnosticSeverity::Hint
+        } else if self.include_warnings {
+            DiagnosticSeverity::Warning
+        } else {
+            DiagnosticSeverity::Error
+        };
+        self.editor.update(cx, |editor, cx| {
+            editor.set_max_diagnostics_severity(max_severity, cx)
+        });
+    }
+
+    fn shows_path(&self, project_path: &ProjectPath) -> bool {
+        !self.filter.current_file_only || self.current_file.as_ref() == Some(project_path)
+    }
+
+    /// The key the excerpts of a buffer are listed by, which orders them by
+    /// directory first when grouping by directory.
+    ///
+    /// Returns `None` when the buffer is in a directory that had no
+    /// diagnostics when the view was last refreshed, as the order of all
+    /// directories must then be recomputed.
+    fn path_key(&self, buffer: &Entity<Buffer>, cx: &App) -> Option<PathKey> {
+        let path_key = PathKey::for_buffer(buffer, cx);
+        if !self.filter.group_by_directory {
+            return Some(path_key);
+        }
+        let Some(file) = buffer.read(cx).file() else {
+            return Some(path_key);
+        };
+        let directory = (
+            file.worktree_id(cx),
+            file.path().parent().unwrap_or(RelPath::empty()).into_arc(),
+        );
+        let index = self.directories.binary_search(&directory).ok()?;
+        Some(PathKey::with_sort_prefix(index as u64, path_key.path))
+    }
+
     /// Closes all excerpts of buffers that:
     ///  - have no diagnostics anymore
     ///  - are saved (not dirty)
@@ -396,14 +620,9 @@ impl ProjectDiagnosticsEditor {
         } else {
             let workspace_handle = cx.entity().downgrade();
 
-            let include_warnings = match cx.try_global::<IncludeWarnings>() {
-                Some(include_warnings) => include_warnings.0,
-                None => ProjectSettings::get_global(cx).diagnostics.include_warnings,
-            };
-
             let diagnostics = cx.new(|cx| {
                 ProjectDiagnosticsEditor::new(
-                    include_warnings,
+                    include_warnings(cx),
                     workspace.project().clone(),
                     workspace_handle,
                     window,
@@ -418,6 +637,156 @@ impl ProjectDiagnosticsEditor {
         cx.set_global(IncludeWarnings(!self.include_warnings));
     }
 
+    fn toggle_information(
+        &mut self,
+        _: &ToggleInformation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.filter.include_information = !self.filter.include_information;
+        self.update_max_diagnostics_severity(cx);
+        self.filter_changed(window, cx);
+    }
+
+    fn toggle_group_by_directory(
+        &mut self,
+        _: &ToggleGroupByDirectory,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.filter.group_by_directory = !self.filter.group_by_directory;
+        self.filter_changed(window, cx);
+    }
+
+    fn toggle_current_file_only(
+        &mut self,
+        _: &ToggleCurrentFileOnly,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.filter.current_file_only = !self.filter.current_file_only;
+        self.filter_changed(window, cx);
+    }
+
+    fn toggle_source(&mut self, source: String, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.filter.hidden_sources.remove(&source) {
+            self.filter.hidden_sources.insert(source);
+        }
+        self.filter_changed(window, cx);
+    }
+
+    fn focus_filter(&mut self, _: &FocusFilter, window: &mut Window, cx: &mut Context<Self>) {
+        self.filter_editor.focus_handle(cx).focus(window, cx);
+    }
+
+    /// The sources of the diagnostics in the view, whether shown or not,
+    /// along with those currently hidden.
+    fn diagnostic_sources(&self) -> BTreeSet<String> {
+        let mut sources = self.filter.hidden_sources.clone();
+        sources.extend(
+            self.diagnostics
+                .values()
+                .flatten()
+                .map(|entry| diagnostic_source(&entry.diagnostic).to_string()),
+        );
+        sources
+    }
+
+    fn render_filter_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let this = cx.entity().downgrade();
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                Icon::new(IconName::MagnifyingGlass)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(div().flex_1().child(self.filter_editor.clone()))
+            .child(
+                IconButton::new("toggle-information", IconName::Info)
+                    .icon_size(IconSize::Small)
+                    .toggle_state(self.filter.include_information)
+                    .tooltip(Tooltip::for_action_title(
+                        if self.filter.include_information {
+                            "Exclude Information and Hints"
+                        } else {
+                            "Include Information and Hints"
+                        },
+                        &ToggleInformation,
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.toggle_information(&ToggleInformation, window, cx)
+                    })),
+            )
+            .child(
+                PopoverMenu::new("diagnostic-sources")
+                    .trigger_with_tooltip(
+                        IconButton::new("diagnostic-sources-trigger", IconName::Filter)
+                            .icon_size(IconSize::Small)
+                            .toggle_state(!self.filter.hidden_sources.is_empty()),
+                        Tooltip::text("Filter Sources"),
+                    )
+                    .anchor(gpui::Corner::TopRight)
+                    .menu(move |window, cx| {
+                        let this = this.upgrade()?;
+                        let sources = this.read(cx).diagnostic_sources();
+                        let hidden_sources = this.read(cx).filter.hidden_sources.clone();
+                        Some(ContextMenu::build(window, cx, |mut menu, _, _| {
+                            if sources.is_empty() {
+                                return menu.header("No Diagnostic Sources");
+                            }
+                            menu = menu.header("Sources");
+                            for source in sources {
+                                let this = this.downgrade();
+                                let shown = !hidden_sources.contains(&source);
+                                menu = menu.toggleable_entry(
+                                    source.clone(),
+                                    shown,
+                                    IconPosition::Start,
+                                    None,
+                                    move |window, cx| {
+                                        this.update(cx, |this, cx| {
+                                            this.toggle_source(source.clone(), window, cx)
+                                        })
+                                        .log_err();
+                                    },
+                                );
+                            }
+                            menu
+                        }))
+                    }),
+            )
+            .child(
+                IconButton::new("toggle-group-by-directory", IconName::FileTree)
+                    .icon_size(IconSize::Small)
+                    .toggle_state(self.filter.group_by_directory)
+                    .tooltip(Tooltip::for_action_title(
+                        "Group by Directory",
+                        &ToggleGroupByDirectory,
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.toggle_group_by_directory(&ToggleGroupByDirectory, window, cx)
+                    })),
+            )
+            .child(
+                IconButton::new("toggle-current-file-only", IconName::File)
+                    .icon_size(IconSize::Small)
+                    .toggle_state(self.filter.current_file_only)
+                    .tooltip(Tooltip::for_action_title(
+                        "Current File Only",
+                        &ToggleCurrentFileOnly,
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.toggle_current_file_only(&ToggleCurrentFileOnly, window, cx)
+                    })),
+            )
+    }
+
     fn toggle_diagnostics_refresh(
         &mut self,
         _: &ToggleDiagnosticsRefresh,
@@ -462,6 +831,22 @@ impl ProjectDiagnosticsEditor {
                 .map(|(project_path, _, _)| project_path)
                 .collect::<BTreeSet<_>>();
         });
+        self.directories = self
+            .paths_to_update
+            .iter()
+            .map(|project_path| {
+                (
+                    project_path.worktree_id,
+                    project_path
+                        .path
+                        .parent()
+                        .unwrap_or(RelPath::empty())
+                        .into_arc(),
+                )
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
 
         self.update_stale_excerpts(window, cx);
     }
@@ -494,11 +879,12 @@ impl ProjectDiagnosticsEditor {
         let buffer_snapshot = buffer.read(cx).snapshot();
         let buffer_id = buffer_snapshot.remote_id();
 
-        let max_severity = if self.include_warnings {
-            lsp::DiagnosticSeverity::WARNING
-        } else {
-            lsp::DiagnosticSeverity::ERROR
-        };
+        let include_warnings = self.include_warnings;
+        let filter = self.filter.clone();
+        let shows_buffer = buffer
+            .read(cx)
+            .project_path(cx)
+            .is_none_or(|project_path| self.shows_path(&project_path));
 
         cx.spawn_in(window, async move |this, cx| {
             let diagnostics = buffer_snapshot
@@ -541,8 +927,7 @@ impl ProjectDiagnosticsEditor {
 
             let diagnostics_toolbar_editor = Arc::new(this.clone());
             for (_, group) in grouped {
-                let group_severity = group.iter().map(|d| d.diagnostic.severity).min();
-                if group_severity.is_none_or(|s| s > max_severity) {
+                if !shows_buffer || !filter.matches_group(&group, include_warnings) {
                     continue;
                 }
                 let languages = this
@@ -616,6 +1001,10 @@ impl ProjectDiagnosticsEditor {
             }
 
             this.update_in(cx, |this, window, cx| {
+                let Some(path_key) = this.path_key(&buffer, cx) else {
+                    this.apply_filter(window, cx);
+                    return;
+                };
                 if let Some(block_ids) = this.blocks.remove(&buffer_id) {
                     this.editor.update(cx, |editor, cx| {
                         editor.display_map.update(cx, |display_map, cx| {
@@ -632,7 +1021,7 @@ impl ProjectDiagnosticsEditor {
                         })
                         .collect();
                     multi_buffer.set_excerpt_ranges_for_path(
-                        PathKey::for_buffer(&buffer, cx),
+                        path_key,
                         buffer.clone(),
                         &buffer_snapshot,
                         excerpt_ranges,
@@ -820,13 +1209,15 @@ impl Item for ProjectDiagnosticsEditor {
         Self: Sized,
     {
         Task::ready(Some(cx.new(|cx| {
-            ProjectDiagnosticsEditor::new(
+            let mut diagnostics = ProjectDiagnosticsEditor::new(
                 self.include_warnings,
                 self.project.clone(),
                 self.workspace.clone(),
                 window,
                 cx,
-            )
+            );
+            diagnostics.restore_filter(self.filter.clone(), window, cx);
+            diagnostics
         })))
     }
 
@@ -0,0 +1,146 @@
+use std::collections::BTreeSet;
+
+use language::{Diagnostic, DiagnosticEntryRef};
+use lsp::{DiagnosticSeverity, NumberOrString};
+use serde::{Deserialize, Serialize};
+use text::Point;
+
+/// The label of diagnostics whose language server didn't name their source.
+pub(crate) const UNKNOWN_SOURCE: &str = "Other";
+
+/// Narrows the diagnostics shown in the project diagnostics view, and
+/// chooses how their files are ordered.
+///
+/// Whether warnings are shown is controlled separately, by the
+/// `ToggleWarnings` action.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct DiagnosticsFilter {
+    /// Whether information and hint diagnostics are shown.
+    pub include_information: bool,
+    /// The sources, like `rustc` or `clippy`, whose diagnostics are hidden.
+    pub hidden_sources: BTreeSet<String>,
+    /// Text that the message, source or code of a diagnostic must contain.
+    pub query: String,
+    /// Whether the files of each directory are listed together, before
+    /// those of its subdirectories.
+    pub group_by_directory: bool,
+    /// Whether only the diagnostics of the last active file are shown.
+    pub current_file_only: bool,
+}
+
+impl DiagnosticsFilter {
+    /// Whether any diagnostic that would otherwise be shown is hidden.
+    pub fn hides_diagnostics(&self) -> bool {
+        !self.hidden_sources.is_empty() || !self.query.is_empty() || self.current_file_only
+    }
+
+    /// Whether a diagnostic group is shown, judging by its primary
+    /// diagnostic for severity and source, and by any of its diagnostics for
+    /// the query.
+    pub fn matches_group(
+        &self,
+        group: &[DiagnosticEntryRef<'_, Point>],
+        include_warnings: bool,
+    ) -> bool {
+        let Some(primary) = group
+            .iter()
+            .find(|entry| entry.diagnostic.is_primary)
+            .or(group.first())
+        else {
+            return false;
+        };
+        let severity = primary.diagnostic.severity;
+        let severity_included = if severity == DiagnosticSeverity::WARNING {
+            include_warnings
+        } else if severity > DiagnosticSeverity::WARNING {
+            self.include_information
+        } else {
+            true
+        };
+        if !severity_included
+            || self
+                .hidden_sources
+                .contains(diagnostic_source(primary.diagnostic))
+        {
+            return false;
+        }
+
+        let query = self.query.trim().to_lowercase();
+        query.is_empty()
+            || group
+                .iter()
+                .any(|entry| diagnostic_matches_query(entry.diagnostic, &query))
+    }
+}
+
+pub(crate) fn diagnostic_source(diagnostic: &Diagnostic) -> &str {
+    diagnostic.source.as_deref().unwrap_or(UNKNOWN_SOURCE)
+}
+
+fn diagnostic_matches_query(diagnostic: &Diagnostic, query: &str) -> bool {
+    let code = diagnostic.code.as_ref().map(|code| match code {
+        NumberOrString::Number(number) => number.to_string(),
+        NumberOrString::String(string) => string.clone(),
+    });
+    diagnostic.message.to_lowercase().contains(query)
+        || diagnostic_source(diagnostic).to_lowercase().contains(query)
+        || code.is_some_and(|code| code.to_lowercase().contains(query))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(diagnostic: &Diagnostic) -> DiagnosticEntryRef<'_, Point> {
+        DiagnosticEntryRef {
+            range: Point::zero()..Point::zero(),
+            diagnostic,
+        }
+    }
+
+    #[test]
+    fn test_matches_group() {
+        let unused = Diagnostic {
+            source: Some("rustc".to_string()),
+            code: Some(NumberOrString::String("unused_variables".to_string())),
+            severity: DiagnosticSeverity::WARNING,
+            message: "unused variable: `x`".to_string(),
+            is_primary: true,
+            ..Default::default()
+        };
+        let lint = Diagnostic {
+            source: Some("clippy".to_string()),
+            severity: DiagnosticSeverity::ERROR,
+            message: "this loop never actually loops".to_string(),
+            is_primary: true,
+            ..Default::default()
+        };
+        let hint = Diagnostic {
+            severity: DiagnosticSeverity::HINT,
+            message: "consider borrowing here".to_string(),
+            is_primary: true,
+            ..Default::default()
+        };
+
+        let mut filter = DiagnosticsFilter::default();
+        assert!(filter.matches_group(&[entry(&unused)], true));
+        assert!(!filter.matches_group(&[entry(&unused)], false));
+        assert!(filter.matches_group(&[entry(&lint)], false));
+        assert!(!filter.matches_group(&[entry(&hint)], true));
+
+        filter.include_information = true;
+        assert!(filter.matches_group(&[entry(&hint)], true));
+
+        filter.hidden_sources.insert("clippy".to_string());
+        filter.hidden_sources.insert(UNKNOWN_SOURCE.to_string());
+        assert!(!filter.matches_group(&[entry(&lint)], true));
+        assert!(!filter.matches_group(&[entry(&hint)], true));
+        assert!(filter.matches_group(&[entry(&unused)], true));
+
+        filter.query = "Unused_Var".to_string();
+        assert!(filter.matches_group(&[entry(&unused)], true));
+        filter.query = "loop".to_string();
+        assert!(!filter.matches_group(&[entry(&unused)], true));
+    }
+}
@@ -0,0 +1,127 @@
+use gpui::{
+    Action, App, AsyncWindowContext, Entity, EventEmitter, FocusHandle, Focusable, Pixels,
+    WeakEntity, actions, px,
+};
+use ui::{IconName, prelude::*};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+use crate::{ProjectDiagnosticsEditor, include_warnings};
+
+const DIAGNOSTICS_PANEL_KEY: &str = "DiagnosticsPanel";
+
+actions!(
+    diagnostics_panel,
+    [
+        /// Toggles focus on the diagnostics panel.
+        ToggleFocus,
+    ]
+);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<DiagnosticsPanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+/// The project diagnostics in the bottom dock, where they can be moved into a
+/// window of their own.
+pub struct DiagnosticsPanel {
+    diagnostics: Entity<ProjectDiagnosticsEditor>,
+    height: Option<Pixels>,
+}
+
+impl DiagnosticsPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        workspace.update_in(&mut cx, |workspace, window, cx| {
+            let project = workspace.project().clone();
+            let workspace = cx.entity().downgrade();
+            cx.new(|cx| {
+                let diagnostics = cx.new(|cx| {
+                    ProjectDiagnosticsEditor::new(
+                        include_warnings(cx),
+                        project,
+                        workspace,
+                        window,
+                        cx,
+                    )
+                });
+                Self {
+                    diagnostics,
+                    height: None,
+                }
+            })
+        })
+    }
+}
+
+impl Render for DiagnosticsPanel {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex().size_full().child(self.diagnostics.clone())
+    }
+}
+
+impl Focusable for DiagnosticsPanel {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.diagnostics.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<PanelEvent> for DiagnosticsPanel {}
+
+impl Panel for DiagnosticsPanel {
+    fn persistent_name() -> &'static str {
+        "DiagnosticsPanel"
+    }
+
+    fn panel_key() -> &'static str {
+        DIAGNOSTICS_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, _: &App) -> DockPosition {
+        DockPosition::Bottom
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        position == DockPosition::Bottom
+    }
+
+    fn set_position(&mut self, _: DockPosition, _: &mut Window, _: &mut Context<Self>) {}
+
+    fn size(&self, _: &Window, _: &App) -> Pixels {
+        self.height.unwrap_or(px(320.))
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.height = size;
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, _: &App) -> Option<IconName> {
+        Some(IconName::Warning)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Diagnostics Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        13
+    }
+
+    fn can_detach(&self, _: &App) -> bool {
+        true
+    }
+}
@@ -150,6 +150,16 @@ pub struct ToggleComments {
     pub ignore_indent: bool,
 }
 
+/// Inserts padding so that every cursor, or the first `delimiter` on each
+/// selected line, lines up in the same column.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct AlignSelections {
+    #[serde(default)]
+    pub delimiter: Option<String>,
+}
+
 /// Moves the cursor up by a specified number of lines.
 #[derive(PartialEq, Clone, Deserialize, Default, JsonSchema, Action)]
 #[action(namespace = editor)]
@@ -416,6 +426,8 @@ actions!(
         ClearFlycheck,
         /// Confirms the rename operation.
         ConfirmRename,
+        /// Previews the edits of the rename, to choose which to apply.
+        ConfirmRenameWithPreview,
         /// Confirms completion by inserting at cursor.
         ConfirmCompletionInsert,
         /// Confirms completion by replacing existing text.
@@ -562,12 +574,20 @@ actions!(
         GoToImplementation,
         /// Goes to implementation in a split pane.
         GoToImplementationSplit,
+        /// Goes to the next bookmark in the workspace.
+        GoToNextBookmark,
         /// Goes to the next change in the file.
         GoToNextChange,
+        /// Goes to the next edited location in the workspace's location history.
+        GoToNextEditLocation,
         /// Goes to the parent module of the current file.
         GoToParentModule,
+        /// Goes to the previous bookmark in the workspace.
+        GoToPreviousBookmark,
         /// Goes to the previous change in the file.
         GoToPreviousChange,
+        /// Goes to the previous edited location in the workspace's location history.
+        GoToPreviousEditLocation,
         /// Goes to the next reference to the symbol under the cursor.
         GoToNextReference,
         /// Goes to the previous reference to the symbol under the cursor.
@@ -598,6 +618,8 @@ actions!(
         KillRingCut,
         /// Yanks from kill ring (Emacs-style).
         KillRingYank,
+        /// Replaces the text just yanked with the kill before it (Emacs-style).
+        KillRingYankPop,
         /// Moves cursor down one line.
         LineDown,
         /// Moves cursor up one line.
@@ -683,6 +705,15 @@ actions!(
         PageUp,
         /// Pastes from clipboard.
         Paste,
+        /// Shows the definition of the symbol at cursor in an editor below the
+        /// cursor, instead of navigating to it.
+        PeekDefinition,
+        /// Shows the implementations of the symbol at cursor in an editor below
+        /// the cursor, instead of navigating to them.
+        PeekImplementation,
+        /// Shows the references to the symbol at cursor in an editor below the
+        /// cursor, instead of navigating to them.
+        PeekReferences,
         /// Navigates to the previous edit prediction.
         PreviousEditPrediction,
         /// Goes to the previous snippet tabstop if one exists.
@@ -773,14 +804,27 @@ actions!(
         SelectToStartOfParagraph,
         /// Extends selection up.
         SelectUp,
+        /// Writes the whole buffer to the active terminal.
+        SendBufferToTerminal,
+        /// Writes the selected text, or the lines of empty selections, to the
+        /// active terminal.
+        SendSelectionToTerminal,
+        /// Shows the callers and callees of the function at the cursor in the
+        /// hierarchy panel.
+        ShowCallHierarchy,
         /// Shows code completion suggestions at the cursor position.
         ShowCompletions,
         /// Shows the system character palette.
         ShowCharacterPalette,
         /// Shows edit prediction at cursor.
         ShowEditPrediction,
+        /// Shows the workspace's recently edited and navigated to locations.
+        ShowEditLocationHistory,
         /// Shows signature help for the current function.
         ShowSignatureHelp,
+        /// Shows the supertypes and subtypes of the type at the cursor in the
+        /// hierarchy panel.
+        ShowTypeHierarchy,
         /// Shows word completions.
         ShowWordCompletions,
         /// Randomly shuffles selected lines.
@@ -795,6 +839,11 @@ actions!(
         SortLinesCaseInsensitive,
         /// Sorts selected lines case-sensitively.
         SortLinesCaseSensitive,
+        /// Sorts selected lines case-sensitively, in descending order.
+        SortLinesDescending,
+        /// Sorts selected lines in natural order, comparing runs of digits by
+        /// their numeric value.
+        SortLinesNaturally,
         /// Stops the language server for the current file.
         StopLanguageServer,
         /// Switches between source and header files.
@@ -803,6 +852,8 @@ actions!(
         Tab,
         /// Removes a tab character or outdents.
         Backtab,
+        /// Toggles a bookmark at the current line.
+        ToggleBookmark,
         /// Toggles a breakpoint at the current line.
         ToggleBreakpoint,
         /// Toggles the case of selected text.
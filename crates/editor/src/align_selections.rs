@@ -0,0 +1,185 @@
+use collections::BTreeMap;
+use gpui::{Context, Window};
+use multi_buffer::{MultiBufferRow, MultiBufferSnapshot};
+use text::Point;
+
+use crate::{Editor, SelectionEffects, actions::AlignSelections};
+
+/// A position to line up with the positions at the same index on other lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct AlignmentPoint {
+    point: Point,
+    /// The column in characters, as padding is counted in characters.
+    column: u32,
+}
+
+impl Editor {
+    pub fn align_selections(
+        &mut self,
+        action: &AlignSelections,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.read_only(cx) {
+            return;
+        }
+        let delimiter = action
+            .delimiter
+            .as_deref()
+            .filter(|delimiter| !delimiter.is_empty());
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let selections = self.selections.all::<Point>(&self.display_snapshot(cx));
+
+        let mut points_by_row = BTreeMap::<u32, Vec<AlignmentPoint>>::new();
+        for selection in &selections {
+            match delimiter {
+                Some(delimiter) => {
+                    let (start_row, mut end_row) = (selection.start.row, selection.end.row);
+                    if end_row > start_row && selection.end.column == 0 {
+                        end_row -= 1;
+                    }
+                    for row in start_row..=end_row {
+                        if points_by_row.contains_key(&row) {
+                            continue;
+                        }
+                        let line_start = if row == start_row && !selection.is_empty() {
+                            selection.start
+                        } else {
+                            Point::new(row, 0)
+                        };
+                        let line_end = if row == selection.end.row && !selection.is_empty() {
+                            selection.end
+                        } else {
+                            Point::new(row, snapshot.line_len(MultiBufferRow(row)))
+                        };
+                        let line = snapshot
+                            .text_for_range(line_start..line_end)
+                            .collect::<String>();
+                        if let Some(index) = line.find(delimiter) {
+                            let point = Point::new(row, line_start.column + index as u32);
+                            points_by_row
+                                .entry(row)
+                                .or_default()
+                                .push(alignment_point(&snapshot, point));
+                        }
+                    }
+                }
+                None => points_by_row
+                    .entry(selection.start.row)
+                    .or_default()
+                    .push(alignment_point(&snapshot, selection.start)),
+            }
+        }
+        if points_by_row.len() < 2 {
+            return;
+        }
+
+        let edits = alignment_padding(points_by_row.into_values().collect())
+            .into_iter()
+            .map(|(point, padding)| (point..point, " ".repeat(padding as usize)))
+            .collect::<Vec<_>>();
+        if edits.is_empty() {
+            return;
+        }
+
+        // Padding is inserted at the cursors, which would otherwise stay in
+        // front of it.
+        let new_selections = delimiter.is_none().then(|| {
+            selections
+                .iter()
+                .map(|selection| {
+                    snapshot.anchor_after(selection.start)..snapshot.anchor_after(selection.end)
+                })
+                .collect::<Vec<_>>()
+        });
+        self.transact(window, cx, |editor, window, cx| {
+            editor
+                .buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            if let Some(new_selections) = new_selections {
+                editor.change_selections(SelectionEffects::default(), window, cx, |selections| {
+                    selections.select_anchor_ranges(new_selections)
+                });
+            }
+        });
+    }
+}
+
+fn alignment_point(snapshot: &MultiBufferSnapshot, point: Point) -> AlignmentPoint {
+    let column = snapshot
+        .text_for_range(Point::new(point.row, 0)..point)
+        .flat_map(str::chars)
+        .count() as u32;
+    AlignmentPoint { point, column }
+}
+
+/// The padding to insert before each point so that the points at the same
+/// index on every line end up in the same column, given the points of each
+/// line in order.
+fn alignment_padding(points_by_line: Vec<Vec<AlignmentPoint>>) -> Vec<(Point, u32)> {
+    let mut padding = Vec::new();
+    // How far each line's remaining points are moved by the padding before them.
+    let mut shifts = vec![0; points_by_line.len()];
+    let point_count = points_by_line.iter().map(Vec::len).max().unwrap_or(0);
+    for index in 0..point_count {
+        let lines = points_by_line
+            .iter()
+            .zip(shifts.iter_mut())
+            .filter_map(|(points, shift)| Some((points.get(index)?, shift)))
+            .collect::<Vec<_>>();
+        // A point with nothing to line up with stays where it is.
+        if lines.len() < 2 {
+            break;
+        }
+        let Some(target_column) = lines
+            .iter()
+            .map(|(point, shift)| point.column + **shift)
+            .max()
+        else {
+            break;
+        };
+        for (point, shift) in lines {
+            let point_padding = target_column - (point.column + *shift);
+            if point_padding > 0 {
+                padding.push((point.point, point_padding));
+                *shift += point_padding;
+            }
+        }
+    }
+    padding
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(row: u32, columns: &[u32]) -> Vec<AlignmentPoint> {
+        columns
+            .iter()
+            .map(|&column| AlignmentPoint {
+                point: Point::new(row, column),
+                column,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_alignment_padding() {
+        assert_eq!(
+            alignment_padding(vec![
+                points(0, &[2, 5]),
+                points(1, &[4, 6]),
+                points(2, &[3])
+            ]),
+            [
+                (Point::new(0, 2), 2),
+                (Point::new(2, 3), 1),
+                (Point::new(1, 6), 1)
+            ]
+        );
+        assert_eq!(
+            alignment_padding(vec![points(0, &[1]), points(1, &[1, 3])]),
+            Vec::new()
+        );
+    }
+}
@@ -3,8 +3,12 @@ use settings::SettingsStore;
 use std::time::Duration;
 use ui::App;
 
+/// Shorter blink intervals are raised to this, so that a tiny setting doesn't
+/// make the cursor flicker and repaint the window constantly.
+const MIN_BLINK_INTERVAL: Duration = Duration::from_millis(100);
+
 pub struct BlinkManager {
-    blink_interval: Duration,
+    blink_interval: fn(&App) -> Duration,
     blink_epoch: usize,
     /// Whether the blinking is paused.
     blinking_paused: bool,
@@ -18,7 +22,7 @@ pub struct BlinkManager {
 
 impl BlinkManager {
     pub fn new(
-        blink_interval: Duration,
+        blink_interval: fn(&App) -> Duration,
         blink_enabled_in_settings: fn(&App) -> bool,
         cx: &mut Context<Self>,
     ) -> Self {
@@ -69,7 +73,7 @@ impl BlinkManager {
                 cx.notify();
 
                 let epoch = self.next_blink_epoch();
-                let interval = self.blink_interval;
+                let interval = (self.blink_interval)(cx).max(MIN_BLINK_INTERVAL);
                 cx.spawn(async move |this, cx| {
                     cx.background_executor().timer(interval).await;
                     if let Some(this) = this.upgrade() {
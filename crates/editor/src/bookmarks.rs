@@ -0,0 +1,240 @@
+use std::{ops::Range, path::Path, sync::Arc};
+
+use collections::{HashMap, HashSet};
+use gpui::{ClickEvent, Entity, Task};
+use multi_buffer::{Anchor, MultiBufferRow, ToPoint as _};
+use project::bookmark_store::{Bookmark, BookmarkStore};
+use text::{Bias, Point, ToPoint as _};
+use ui::{ButtonStyle, IconButton, IconName, IconSize, Tooltip, prelude::*};
+
+use crate::{
+    DisplayPoint, DisplayRow, Editor, SelectionEffects,
+    actions::{GoToNextBookmark, GoToPreviousBookmark, ToggleBookmark},
+    display_map::ToDisplayPoint as _,
+    scroll::Autoscroll,
+};
+
+impl Editor {
+    pub(crate) fn active_bookmarks(
+        &self,
+        range: Range<DisplayRow>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> HashMap<DisplayRow, Bookmark> {
+        let mut bookmark_rows = HashMap::default();
+        let Some(bookmark_store) = self.bookmark_store.clone() else {
+            return bookmark_rows;
+        };
+        let Some(project) = self.project() else {
+            return bookmark_rows;
+        };
+
+        let snapshot = self.snapshot(window, cx);
+        let multi_buffer_snapshot = snapshot.buffer_snapshot();
+        let range = snapshot.display_point_to_point(DisplayPoint::new(range.start, 0), Bias::Left)
+            ..snapshot.display_point_to_point(DisplayPoint::new(range.end, 0), Bias::Right);
+        for (buffer_snapshot, range, excerpt_id) in
+            multi_buffer_snapshot.range_to_buffer_ranges(range.start..=range.end)
+        {
+            let Some(buffer) = project
+                .read(cx)
+                .buffer_for_id(buffer_snapshot.remote_id(), cx)
+            else {
+                continue;
+            };
+            let bookmarks = bookmark_store.read(cx).bookmarks(
+                &buffer,
+                Some(
+                    buffer_snapshot.anchor_before(range.start)
+                        ..buffer_snapshot.anchor_after(range.end),
+                ),
+                buffer_snapshot,
+                cx,
+            );
+            for bookmark in bookmarks {
+                let display_row = Anchor::in_buffer(excerpt_id, bookmark.position)
+                    .to_point(&multi_buffer_snapshot)
+                    .to_display_point(&snapshot)
+                    .row();
+                bookmark_rows.insert(display_row, bookmark.clone());
+            }
+        }
+        bookmark_rows
+    }
+
+    pub(crate) fn render_bookmark(
+        &self,
+        row: DisplayRow,
+        bookmark: &Bookmark,
+        cx: &mut Context<Self>,
+    ) -> IconButton {
+        let tooltip = match &bookmark.label {
+            Some(label) => SharedString::from(format!("{label}\nClick to remove bookmark")),
+            None => SharedString::from("Remove bookmark"),
+        };
+        let position = bookmark.position;
+        IconButton::new(("bookmark_indicator", row.0 as usize), IconName::Bookmark)
+            .icon_size(IconSize::XSmall)
+            .size(ui::ButtonSize::None)
+            .icon_color(Color::Accent)
+            .style(ButtonStyle::Transparent)
+            .on_click(cx.listener(move |editor, _: &ClickEvent, _, cx| {
+                let Some(bookmark_store) = editor.bookmark_store.clone() else {
+                    return;
+                };
+                let Some(buffer) = position
+                    .buffer_id
+                    .and_then(|buffer_id| editor.buffer.read(cx).buffer(buffer_id))
+                else {
+                    return;
+                };
+                bookmark_store.update(cx, |bookmark_store, cx| {
+                    bookmark_store.toggle_bookmark(buffer, position, cx);
+                });
+            }))
+            .tooltip(Tooltip::text(tooltip))
+    }
+
+    pub fn toggle_bookmark(
+        &mut self,
+        _: &ToggleBookmark,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bookmark_store) = self.bookmark_store.clone() else {
+            return;
+        };
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut toggled_rows = HashSet::default();
+        let heads = self
+            .selections
+            .disjoint_anchors()
+            .iter()
+            .map(|selection| selection.head())
+            .filter(|head| toggled_rows.insert(MultiBufferRow(head.to_point(&snapshot).row)))
+            .collect::<Vec<_>>();
+        for head in heads {
+            let Some(buffer) = self.buffer.read(cx).buffer_for_anchor(head, cx) else {
+                continue;
+            };
+            bookmark_store.update(cx, |bookmark_store, cx| {
+                bookmark_store.toggle_bookmark(buffer, head.text_anchor, cx);
+            });
+        }
+    }
+
+    pub fn go_to_next_bookmark(
+        &mut self,
+        _: &GoToNextBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_adjacent_bookmark(true, window, cx);
+    }
+
+    pub fn go_to_previous_bookmark(
+        &mut self,
+        _: &GoToPreviousBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_adjacent_bookmark(false, window, cx);
+    }
+
+    /// Moves to the bookmark after or before the newest cursor, in any file of
+    /// the workspace, wrapping around at the ends.
+    fn go_to_adjacent_bookmark(
+        &mut self,
+        forward: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bookmark_store) = self.bookmark_store.clone() else {
+            return;
+        };
+        let bookmarks = bookmark_store
+            .read(cx)
+            .all_bookmarks(cx)
+            .into_iter()
+            .flat_map(|(abs_path, bookmarks)| {
+                bookmarks
+                    .into_iter()
+                    .map(move |bookmark| (abs_path.clone(), bookmark.row))
+            })
+            .collect::<Vec<_>>();
+
+        let head = self.selections.newest_anchor().head();
+        let buffer = self.buffer.read(cx).buffer_for_anchor(head, cx);
+        let current = buffer.as_ref().and_then(|buffer| {
+            let abs_path = BookmarkStore::abs_path_from_buffer(buffer, cx)?;
+            let row = head.text_anchor.to_point(&buffer.read(cx).snapshot()).row;
+            Some((abs_path, row))
+        });
+        let target = match &current {
+            Some(current) if forward => bookmarks
+                .iter()
+                .find(|bookmark| *bookmark > current)
+                .or(bookmarks.first()),
+            Some(current) => bookmarks
+                .iter()
+                .rev()
+                .find(|bookmark| *bookmark < current)
+                .or(bookmarks.last()),
+            None if forward => bookmarks.first(),
+            None => bookmarks.last(),
+        };
+        let Some((abs_path, row)) = target.cloned() else {
+            return;
+        };
+
+        let point = Point::new(row, 0);
+        if let Some(buffer) = buffer
+            && current.is_some_and(|(current_path, _)| current_path == abs_path)
+            && let Some(anchor) = self
+                .buffer
+                .read(cx)
+                .buffer_point_to_anchor(&buffer, point, cx)
+        {
+            self.change_selections(
+                SelectionEffects::scroll(Autoscroll::center()),
+                window,
+                cx,
+                |selections| selections.select_anchor_ranges([anchor..anchor]),
+            );
+            return;
+        }
+
+        self.open_bookmark(&bookmark_store, abs_path, point, window, cx)
+            .detach_and_log_err(cx);
+    }
+
+    fn open_bookmark(
+        &self,
+        bookmark_store: &Entity<BookmarkStore>,
+        abs_path: Arc<Path>,
+        point: Point,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let Some(workspace) = self.workspace() else {
+            return Task::ready(Ok(()));
+        };
+        let Some(project_path) = bookmark_store.read(cx).project_path(&abs_path, cx) else {
+            return Task::ready(Err(anyhow::anyhow!("Project path not found")));
+        };
+        let item = workspace.update(cx, |workspace, cx| {
+            workspace.open_path(project_path, None, true, window, cx)
+        });
+        window.spawn(cx, async move |cx| {
+            let Some(editor) = item.await?.downcast::<Editor>() else {
+                return Ok(());
+            };
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    editor.go_to_singleton_buffer_point(point, window, cx);
+                })
+                .ok();
+            anyhow::Ok(())
+        })
+    }
+}
@@ -0,0 +1,721 @@
+//! Code lenses: annotations shown above the symbols they belong to, like the
+//! number of references to a function or a button to run a test.
+//!
+//! Lenses come from [`CodeLensProvider`]s: the project's language servers, and
+//! any other provider an editor was given, like a test runner. Lenses a
+//! provider returned without a command are resolved once they are scrolled
+//! into view.
+
+use std::{rc::Rc, sync::Arc};
+
+use anyhow::Result;
+use collections::{BTreeMap, HashMap, HashSet};
+use futures::future::join_all;
+use gpui::{Entity, Task, WeakEntity};
+use itertools::Itertools as _;
+use language::{Buffer, language_settings::language_settings};
+use project::{CodeAction, LspAction, Project, ProjectTransaction};
+use text::{BufferId, ToOffset as _, ToPoint as _};
+use ui::{Button, ButtonStyle, LabelSize, prelude::*};
+
+use crate::{
+    Editor, LSP_REQUEST_DEBOUNCE_TIMEOUT,
+    display_map::{
+        BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId, RenderBlock,
+    },
+};
+
+/// A source of code lenses, given as code actions with an
+/// [`LspAction::CodeLens`], whose command title is shown above the line the
+/// action's range starts on.
+pub trait CodeLensProvider {
+    fn id(&self) -> Arc<str>;
+
+    fn code_lenses(&self, buffer: &Entity<Buffer>, cx: &mut App) -> Task<Result<Vec<CodeAction>>>;
+
+    /// Fills in the command of a lens that was returned without one.
+    fn resolve_code_lens(
+        &self,
+        buffer: &Entity<Buffer>,
+        code_lens: CodeAction,
+        cx: &mut App,
+    ) -> Task<Result<CodeAction>>;
+
+    fn run_code_lens(
+        &self,
+        buffer: Entity<Buffer>,
+        code_lens: CodeAction,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<ProjectTransaction>>;
+}
+
+impl CodeLensProvider for Entity<Project> {
+    fn id(&self) -> Arc<str> {
+        "project".into()
+    }
+
+    fn code_lenses(&self, buffer: &Entity<Buffer>, cx: &mut App) -> Task<Result<Vec<CodeAction>>> {
+        let buffer_len = buffer.read(cx).len();
+        let code_lens_actions = self.update(cx, |project, cx| {
+            project.code_lens_actions(buffer, 0..buffer_len, cx)
+        });
+        cx.background_spawn(async move { Ok(code_lens_actions.await?.unwrap_or_default()) })
+    }
+
+    fn resolve_code_lens(
+        &self,
+        buffer: &Entity<Buffer>,
+        code_lens: CodeAction,
+        cx: &mut App,
+    ) -> Task<Result<CodeAction>> {
+        self.update(cx, |project, cx| {
+            project.resolve_code_lens(buffer, code_lens, cx)
+        })
+    }
+
+    fn run_code_lens(
+        &self,
+        buffer: Entity<Buffer>,
+        code_lens: CodeAction,
+        _window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<ProjectTransaction>> {
+        self.update(cx, |project, cx| {
+            project.apply_code_action(buffer, code_lens, true, cx)
+        })
+    }
+}
+
+#[derive(Clone)]
+struct ShownCodeLens {
+    id: usize,
+    provider: Rc<dyn CodeLensProvider>,
+    code_lens: CodeAction,
+    resolving: bool,
+}
+
+impl ShownCodeLens {
+    fn title(&self) -> Option<SharedString> {
+        match &self.code_lens.lsp_action {
+            LspAction::CodeLens(lens) => lens
+                .command
+                .as_ref()
+                .map(|command| SharedString::from(command.title.clone())),
+            lsp_action => Some(SharedString::from(lsp_action.title().to_owned())),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct CodeLensState {
+    lenses: HashMap<BufferId, Vec<ShownCodeLens>>,
+    /// One block per row with lenses, placed at the start of the row's first
+    /// lens.
+    blocks: HashMap<BufferId, Vec<(text::Anchor, CustomBlockId)>>,
+    next_lens_id: usize,
+}
+
+impl CodeLensState {
+    fn shown_lens(
+        &mut self,
+        provider: Rc<dyn CodeLensProvider>,
+        code_lens: CodeAction,
+    ) -> ShownCodeLens {
+        self.next_lens_id += 1;
+        ShownCodeLens {
+            id: self.next_lens_id,
+            provider,
+            code_lens,
+            resolving: false,
+        }
+    }
+}
+
+impl Editor {
+    pub fn add_code_lens_provider(
+        &mut self,
+        provider: Rc<dyn CodeLensProvider>,
+        cx: &mut Context<Self>,
+    ) {
+        if self
+            .code_lens_providers
+            .iter()
+            .any(|existing_provider| existing_provider.id() == provider.id())
+        {
+            return;
+        }
+
+        self.code_lens_providers.push(provider);
+        self.refresh_code_lenses(None, cx);
+    }
+
+    pub fn remove_code_lens_provider(&mut self, id: Arc<str>, cx: &mut Context<Self>) {
+        self.code_lens_providers
+            .retain(|provider| provider.id() != id);
+        self.refresh_code_lenses(None, cx);
+    }
+
+    pub(super) fn refresh_code_lenses(
+        &mut self,
+        for_buffer: Option<BufferId>,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode().is_full() {
+            return;
+        }
+
+        let disabled_buffers = self
+            .code_lens
+            .lenses
+            .keys()
+            .copied()
+            .filter(|buffer_id| {
+                self.code_lens_providers.is_empty()
+                    || self
+                        .buffer
+                        .read(cx)
+                        .buffer(*buffer_id)
+                        .is_none_or(|buffer| !code_lens_enabled(&buffer, cx))
+            })
+            .collect::<Vec<_>>();
+        for buffer_id in disabled_buffers {
+            self.clear_code_lenses(buffer_id, cx);
+        }
+        if self.code_lens_providers.is_empty() {
+            return;
+        }
+
+        let buffers_to_query = self
+            .visible_excerpts(false, cx)
+            .into_values()
+            .map(|(buffer, ..)| buffer)
+            .chain(for_buffer.and_then(|id| self.buffer.read(cx).buffer(id)))
+            .filter(|buffer| {
+                for_buffer.is_none_or(|target| target == buffer.read(cx).remote_id())
+                    && code_lens_enabled(buffer, cx)
+            })
+            .unique_by(|buffer| buffer.read(cx).remote_id())
+            .collect::<Vec<_>>();
+        if buffers_to_query.is_empty() {
+            return;
+        }
+
+        let providers = self.code_lens_providers.clone();
+        self.refresh_code_lens_task = cx.spawn(async move |editor, cx| {
+            cx.background_executor()
+                .timer(LSP_REQUEST_DEBOUNCE_TIMEOUT)
+                .await;
+
+            let Some(tasks) = editor
+                .update(cx, |_, cx| {
+                    buffers_to_query
+                        .iter()
+                        .map(|buffer| {
+                            let buffer_id = buffer.read(cx).remote_id();
+                            let fetches = providers
+                                .iter()
+                                .map(|provider| {
+                                    let fetch = provider.code_lenses(buffer, cx);
+                                    let provider = provider.clone();
+                                    async move { (provider, fetch.await) }
+                                })
+                                .collect::<Vec<_>>();
+                            async move { (buffer_id, join_all(fetches).await) }
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .ok()
+            else {
+                return;
+            };
+
+            let results = join_all(tasks).await;
+            editor
+                .update(cx, |editor, cx| {
+                    for (buffer_id, fetched) in results {
+                        let lenses = fetched
+                            .into_iter()
+                            .flat_map(|(provider, code_lenses)| {
+                                let code_lenses = code_lenses.unwrap_or_else(|error| {
+                                    log::error!(
+                                        "Failed to fetch code lenses from {}: {error:#}",
+                                        provider.id()
+                                    );
+                                    Vec::new()
+                                });
+                                code_lenses
+                                    .into_iter()
+                                    .map(move |code_lens| (provider.clone(), code_lens))
+                            })
+                            .collect::<Vec<_>>()
+                            .into_iter()
+                            .map(|(provider, code_lens)| {
+                                editor.code_lens.shown_lens(provider, code_lens)
+                            })
+                            .collect();
+                        editor.code_lens.lenses.insert(buffer_id, lenses);
+                        editor.update_code_lens_blocks(buffer_id, cx);
+                    }
+                    editor.resolve_visible_code_lenses(cx);
+                })
+                .ok();
+        });
+    }
+
+    /// Resolves the lenses in the visible part of each buffer that came
+    /// without a command, so that their titles can be shown.
+    pub(super) fn resolve_visible_code_lenses(&mut self, cx: &mut Context<Self>) {
+        if self.code_lens.lenses.is_empty() {
+            return;
+        }
+
+        let mut resolve_tasks = Vec::new();
+        for (buffer, _, visible_range) in self.visible_excerpts(false, cx).into_values() {
+            let buffer_id = buffer.read(cx).remote_id();
+            let Some(lenses) = self.code_lens.lenses.get_mut(&buffer_id) else {
+                continue;
+            };
+            let snapshot = buffer.read(cx).snapshot();
+            for lens in lenses.iter_mut() {
+                if lens.code_lens.resolved
+                    || lens.resolving
+                    || !visible_range.contains(&lens.code_lens.range.start.to_offset(&snapshot))
+                {
+                    continue;
+                }
+                lens.resolving = true;
+                let resolve_task =
+                    lens.provider
+                        .resolve_code_lens(&buffer, lens.code_lens.clone(), cx);
+                resolve_tasks.push((buffer_id, lens.id, resolve_task));
+            }
+        }
+
+        for (buffer_id, lens_id, resolve_task) in resolve_tasks {
+            cx.spawn(async move |editor, cx| {
+                let resolved = resolve_task.await;
+                editor
+                    .update(cx, |editor, cx| {
+                        // The lenses may have been fetched again in the meantime.
+                        let Some(lens) = editor
+                            .code_lens
+                            .lenses
+                            .get_mut(&buffer_id)
+                            .and_then(|lenses| lenses.iter_mut().find(|lens| lens.id == lens_id))
+                        else {
+                            return;
+                        };
+                        lens.resolving = false;
+                        match resolved {
+                            Ok(code_lens) => lens.code_lens = code_lens,
+                            Err(error) => log::error!("Failed to resolve a code lens: {error:#}"),
+                        }
+                        lens.code_lens.resolved = true;
+                        let position = lens.code_lens.range.start;
+                        editor.update_code_lens_block(buffer_id, position, cx);
+                    })
+                    .ok();
+            })
+            .detach();
+        }
+    }
+
+    pub(super) fn clear_code_lenses(&mut self, buffer_id: BufferId, cx: &mut Context<Self>) {
+        self.code_lens.lenses.remove(&buffer_id);
+        if let Some(blocks) = self.code_lens.blocks.remove(&buffer_id) {
+            self.remove_blocks(
+                blocks
+                    .into_iter()
+                    .map(|(_, block_id)| block_id)
+                    .collect::<HashSet<_>>(),
+                None,
+                cx,
+            );
+        }
+    }
+
+    /// Shows the lenses of a buffer in a block above each line they start on.
+    /// Unresolved lenses keep their line's block in place, to not move the
+    /// text around once they are resolved.
+    fn update_code_lens_blocks(&mut self, buffer_id: BufferId, cx: &mut Context<Self>) {
+        if let Some(blocks) = self.code_lens.blocks.remove(&buffer_id) {
+            self.remove_blocks(
+                blocks.into_iter().map(|(_, block_id)| block_id).collect(),
+                None,
+                cx,
+            );
+        }
+        let Some(buffer) = self.buffer.read(cx).buffer(buffer_id) else {
+            return;
+        };
+        let Some(lenses) = self.code_lens.lenses.get(&buffer_id) else {
+            return;
+        };
+
+        let snapshot = buffer.read(cx).snapshot();
+        let mut lenses_by_row = BTreeMap::<u32, Vec<&ShownCodeLens>>::new();
+        for lens in lenses {
+            let row = lens.code_lens.range.start.to_point(&snapshot).row;
+            lenses_by_row.entry(row).or_default().push(lens);
+        }
+
+        let editor = cx.weak_entity();
+        let multi_buffer = self.buffer.read(cx);
+        let (positions, blocks): (Vec<_>, Vec<_>) = lenses_by_row
+            .into_values()
+            .filter_map(|lenses| {
+                let render = code_lens_renderer(editor.clone(), buffer_id, &lenses)?;
+                let buffer_position = lenses.first()?.code_lens.range.start;
+                let position =
+                    multi_buffer.buffer_anchor_to_anchor(&buffer, buffer_position, cx)?;
+                Some((
+                    buffer_position,
+                    BlockProperties {
+                        style: BlockStyle::Flex,
+                        placement: BlockPlacement::Above(position),
+                        height: Some(1),
+                        render,
+                        priority: 0,
+                    },
+                ))
+            })
+            .unzip();
+        let block_ids = self.insert_blocks(blocks, None, cx);
+        self.code_lens
+            .blocks
+            .insert(buffer_id, positions.into_iter().zip(block_ids).collect());
+    }
+
+    /// Re-renders the block of the row a lens starts on, after the lens got
+    /// resolved, leaving the other rows' blocks untouched.
+    fn update_code_lens_block(
+        &mut self,
+        buffer_id: BufferId,
+        lens_position: text::Anchor,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(buffer) = self.buffer.read(cx).buffer(buffer_id) else {
+            return;
+        };
+        let Some(lenses) = self.code_lens.lenses.get(&buffer_id) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).snapshot();
+        let row = lens_position.to_point(&snapshot).row;
+        let row_lenses = lenses
+            .iter()
+            .filter(|lens| lens.code_lens.range.start.to_point(&snapshot).row == row)
+            .collect::<Vec<_>>();
+        let render = code_lens_renderer(cx.weak_entity(), buffer_id, &row_lenses);
+
+        let blocks = self.code_lens.blocks.entry(buffer_id).or_default();
+        let existing_block = blocks
+            .iter()
+            .position(|(position, _)| position.to_point(&snapshot).row == row);
+        match (existing_block, render) {
+            (Some(index), Some(render)) => {
+                let block_id = blocks[index].1;
+                self.replace_blocks(HashMap::from_iter([(block_id, render)]), None, cx);
+            }
+            (Some(index), None) => {
+                let (_, block_id) = blocks.remove(index);
+                self.remove_blocks(HashSet::from_iter([block_id]), None, cx);
+            }
+            (None, Some(render)) => {
+                let Some(position) =
+                    self.buffer
+                        .read(cx)
+                        .buffer_anchor_to_anchor(&buffer, lens_position, cx)
+                else {
+                    return;
+                };
+                let block_ids = self.insert_blocks(
+                    [BlockProperties {
+                        style: BlockStyle::Flex,
+                        placement: BlockPlacement::Above(position),
+                        height: Some(1),
+                        render,
+                        priority: 0,
+                    }],
+                    None,
+                    cx,
+                );
+                let blocks = self.code_lens.blocks.entry(buffer_id).or_default();
+                blocks.extend(
+                    block_ids
+                        .into_iter()
+                        .map(|block_id| (lens_position, block_id)),
+                );
+            }
+            (None, None) => {}
+        }
+    }
+
+    fn run_code_lens(
+        &mut self,
+        buffer_id: BufferId,
+        lens_id: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(lens) = self
+            .code_lens
+            .lenses
+            .get(&buffer_id)
+            .and_then(|lenses| lenses.iter().find(|lens| lens.id == lens_id))
+            .cloned()
+        else {
+            return;
+        };
+        let Some(buffer) = self.buffer.read(cx).buffer(buffer_id) else {
+            return;
+        };
+        let title = lens.code_lens.lsp_action.title().to_owned();
+        let run_code_lens = lens
+            .provider
+            .run_code_lens(buffer, lens.code_lens, window, cx);
+        let Some(workspace) = self.workspace() else {
+            run_code_lens.detach_and_log_err(cx);
+            return;
+        };
+        let workspace = workspace.downgrade();
+        cx.spawn_in(window, async move |editor, cx| {
+            let project_transaction = run_code_lens.await?;
+            Self::open_project_transaction(&editor, workspace, project_transaction, title, cx).await
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+fn code_lens_enabled(buffer: &Entity<Buffer>, cx: &App) -> bool {
+    let buffer = buffer.read(cx);
+    language_settings(
+        buffer.language().map(|language| language.name()),
+        buffer.file(),
+        cx,
+    )
+    .code_lens
+    .enabled()
+}
+
+/// Renders the titles of the lenses starting on one row, or nothing when none
+/// of them has a title and none is still waiting to be resolved.
+fn code_lens_renderer(
+    editor: WeakEntity<Editor>,
+    buffer_id: BufferId,
+    lenses: &[&ShownCodeLens],
+) -> Option<RenderBlock> {
+    let titles = lenses
+        .iter()
+        .filter_map(|lens| Some((lens.id, lens.title()?)))
+        .collect::<Vec<_>>();
+    let pending = lenses.iter().any(|lens| !lens.code_lens.resolved);
+    if titles.is_empty() && !pending {
+        return None;
+    }
+    Some(Arc::new(move |cx| {
+        render_code_lenses(editor.clone(), buffer_id, &titles, cx)
+    }))
+}
+
+fn render_code_lenses(
+    editor: WeakEntity<Editor>,
+    buffer_id: BufferId,
+    titles: &[(usize, SharedString)],
+    cx: &mut BlockContext,
+) -> AnyElement {
+    h_flex()
+        .id(cx.block_id)
+        .block_mouse_except_scroll()
+        .h(cx.line_height)
+        .pl(cx.anchor_x)
+        .gap_2()
+        .children(titles.iter().map(|(lens_id, title)| {
+            let editor = editor.clone();
+            let lens_id = *lens_id;
+            Button::new(("code-lens", lens_id), title.clone())
+                .label_size(LabelSize::Small)
+                .color(Color::Muted)
+                .style(ButtonStyle::Transparent)
+                .on_click(move |_, window, cx| {
+                    editor
+                        .update(cx, |editor, cx| {
+                            editor.run_code_lens(buffer_id, lens_id, window, cx)
+                        })
+                        .ok();
+                })
+        }))
+        .into_any_element()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc, Mutex,
+        atomic::{self, AtomicUsize},
+    };
+
+    use futures::{StreamExt as _, channel::oneshot};
+    use gpui::TestAppContext;
+    use settings::CodeLens;
+
+    use crate::{
+        Editor,
+        editor_tests::{init_test, update_test_language_settings},
+        test::editor_lsp_test_context::EditorLspTestContext,
+    };
+
+    fn code_lens_titles(editor: &Editor) -> Vec<Option<String>> {
+        editor
+            .code_lens
+            .lenses
+            .values()
+            .flatten()
+            .map(|lens| lens.title().map(|title| title.to_string()))
+            .collect()
+    }
+
+    fn code_lens_block_ids(editor: &Editor) -> Vec<super::CustomBlockId> {
+        editor
+            .code_lens
+            .blocks
+            .values()
+            .flatten()
+            .map(|(_, block_id)| *block_id)
+            .collect()
+    }
+
+    #[gpui::test]
+    async fn test_code_lenses_fetch_resolve_and_run(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        update_test_language_settings(cx, |settings| {
+            settings.defaults.code_lens = Some(CodeLens::On);
+        });
+
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                code_lens_provider: Some(lsp::CodeLensOptions {
+                    resolve_provider: Some(true),
+                }),
+                execute_command_provider: Some(lsp::ExecuteCommandOptions {
+                    commands: vec!["run_main".to_string()],
+                    ..lsp::ExecuteCommandOptions::default()
+                }),
+                ..lsp::ServerCapabilities::default()
+            },
+            cx,
+        )
+        .await;
+
+        let mut code_lens_request = cx.set_request_handler::<lsp::request::CodeLensRequest, _, _>(
+            move |_, _, _| async move {
+                Ok(Some(vec![
+                    lsp::CodeLens {
+                        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 7)),
+                        command: Some(lsp::Command {
+                            title: "Run".to_owned(),
+                            command: "run_main".to_owned(),
+                            arguments: None,
+                        }),
+                        data: None,
+                    },
+                    lsp::CodeLens {
+                        range: lsp::Range::new(lsp::Position::new(2, 0), lsp::Position::new(2, 8)),
+                        command: None,
+                        data: Some(serde_json::json!("references")),
+                    },
+                ]))
+            },
+        );
+        // Resolve requests are held until the test lets them through, to
+        // observe the blocks shown for unresolved lenses.
+        let pending_resolves = Arc::new(Mutex::new(Vec::<oneshot::Sender<()>>::new()));
+        let _resolve_requests = cx.set_request_handler::<lsp::request::CodeLensResolve, _, _>({
+            let pending_resolves = pending_resolves.clone();
+            move |_, mut lens, _| {
+                let (resolved_tx, resolved_rx) = oneshot::channel();
+                pending_resolves.lock().unwrap().push(resolved_tx);
+                async move {
+                    resolved_rx.await.ok();
+                    if lens.command.is_none() {
+                        lens.command = Some(lsp::Command {
+                            title: "2 references".to_owned(),
+                            command: "show_references".to_owned(),
+                            arguments: None,
+                        });
+                    }
+                    Ok(lens)
+                }
+            }
+        });
+        let executed_commands = Arc::new(AtomicUsize::new(0));
+        let _execute_requests = cx.set_request_handler::<lsp::request::ExecuteCommand, _, _>({
+            let executed_commands = executed_commands.clone();
+            move |_, params, _| {
+                assert_eq!(params.command, "run_main");
+                executed_commands.fetch_add(1, atomic::Ordering::Release);
+                async move { Ok(None) }
+            }
+        });
+
+        cx.set_state("ˇfn main() {}\n\nfn other() {}\n");
+        assert!(code_lens_request.next().await.is_some());
+        cx.run_until_parked();
+
+        let block_ids_before_resolve = cx.update_editor(|editor, _, _| {
+            let mut titles = code_lens_titles(editor);
+            titles.sort();
+            assert_eq!(titles, [None, Some("Run".to_owned())]);
+            code_lens_block_ids(editor)
+        });
+        assert_eq!(
+            block_ids_before_resolve.len(),
+            2,
+            "Unresolved lenses should keep a block for their row"
+        );
+        assert_eq!(pending_resolves.lock().unwrap().len(), 2);
+
+        for resolved_tx in pending_resolves.lock().unwrap().drain(..) {
+            resolved_tx.send(()).ok();
+        }
+        cx.run_until_parked();
+
+        let run_lens_id = cx.update_editor(|editor, _, _| {
+            let mut titles = code_lens_titles(editor);
+            titles.sort();
+            assert_eq!(
+                titles,
+                [Some("2 references".to_owned()), Some("Run".to_owned())]
+            );
+            let mut block_ids = code_lens_block_ids(editor);
+            block_ids.sort();
+            let mut block_ids_before_resolve = block_ids_before_resolve.clone();
+            block_ids_before_resolve.sort();
+            assert_eq!(
+                block_ids, block_ids_before_resolve,
+                "Resolving a lens should only re-render its own row's block"
+            );
+            editor
+                .code_lens
+                .lenses
+                .values()
+                .flatten()
+                .find(|lens| lens.title().as_deref() == Some("Run"))
+                .map(|lens| lens.id)
+                .expect("the Run lens should be shown")
+        });
+
+        cx.update_editor(|editor, window, cx| {
+            let buffer_id = *editor
+                .code_lens
+                .lenses
+                .keys()
+                .next()
+                .expect("lenses for the open buffer");
+            editor.run_code_lens(buffer_id, run_lens_id, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(executed_commands.load(atomic::Ordering::Acquire), 1);
+    }
+}
@@ -99,8 +99,9 @@ use gpui::{
 };
 use language::{Point, Subscription as BufferSubscription, language_settings::language_settings};
 use multi_buffer::{
-    Anchor, AnchorRangeExt, ExcerptId, MultiBuffer, MultiBufferOffset, MultiBufferOffsetUtf16,
-    MultiBufferPoint, MultiBufferRow, MultiBufferSnapshot, RowInfo, ToOffset, ToPoint,
+    Anchor, AnchorRangeExt, BufferOffset, ExcerptId, MultiBuffer, MultiBufferOffset,
+    MultiBufferOffsetUtf16, MultiBufferPoint, MultiBufferRow, MultiBufferSnapshot, RowInfo,
+    ToOffset, ToPoint,
 };
 use project::project_settings::DiagnosticSeverity;
 use project::{InlayId, lsp_store::LspFoldingRange, lsp_store::TokenType};
@@ -161,12 +162,14 @@ pub enum HighlightKey {
     HoveredLinkState,
     InlineAssist,
     InputComposition,
+    LinkedEditingRanges,
     MatchingBracket,
     PendingInput,
     ProjectSearchView,
     Rename,
     SearchWithinRange,
     SelectedTextHighlight,
+    SpellCheck,
     SyntaxTreeView(usize),
     VimExchange,
 }
@@ -2277,6 +2280,46 @@ impl DisplaySnapshot {
             .unwrap_or(false)
     }
 
+    /// Returns the range folded by the region marker or folds query fold
+    /// starting at `buffer_row`, unless the language server provides the
+    /// folds.
+    pub fn syntax_fold_range_for_buffer_row(
+        &self,
+        buffer_row: MultiBufferRow,
+    ) -> Option<Range<Point>> {
+        if self.use_lsp_folding_ranges {
+            return None;
+        }
+        let buffer_snapshot = self.buffer_snapshot();
+        let row_start = MultiBufferPoint::new(buffer_row.0, 0);
+        let mut excerpt = buffer_snapshot.excerpt_containing(row_start..row_start)?;
+        let buffer = excerpt.buffer();
+        let buffer_row_start =
+            excerpt.map_offset_to_buffer(buffer_snapshot.point_to_offset(row_start));
+        let fold_range =
+            buffer.fold_range_for_row(buffer.offset_to_point(buffer_row_start.0).row)?;
+        let fold_range = BufferOffset(buffer.point_to_offset(fold_range.start))
+            ..BufferOffset(buffer.point_to_offset(fold_range.end));
+        if !excerpt.contains_buffer_range(fold_range.clone()) {
+            return None;
+        }
+        let fold_range = excerpt.map_range_from_buffer(fold_range);
+        Some(
+            buffer_snapshot.offset_to_point(fold_range.start)
+                ..buffer_snapshot.offset_to_point(fold_range.end),
+        )
+    }
+
+    /// Whether the language at `buffer_row` defines its folds with a folds
+    /// query, so that indentation doesn't make rows foldable.
+    pub fn folds_by_syntax(&self, buffer_row: MultiBufferRow) -> bool {
+        let buffer_snapshot = self.buffer_snapshot();
+        let row_start = MultiBufferPoint::new(buffer_row.0, 0);
+        buffer_snapshot
+            .point_to_buffer_point(row_start)
+            .is_some_and(|(buffer, point, _)| buffer.folds_by_syntax(point.row))
+    }
+
     #[instrument(skip_all)]
     pub fn crease_for_buffer_row(&self, buffer_row: MultiBufferRow) -> Option<Crease<Point>> {
         let start =
@@ -2315,7 +2358,18 @@ impl DisplaySnapshot {
                     render_toggle: render_toggle.clone(),
                 }),
             }
+        } else if let Some(range) = self.syntax_fold_range_for_buffer_row(buffer_row)
+            && !self.is_line_folded(buffer_row)
+        {
+            Some(Crease::Inline {
+                range,
+                placeholder: self.fold_placeholder.clone(),
+                render_toggle: None,
+                render_trailer: None,
+                metadata: None,
+            })
         } else if !self.use_lsp_folding_ranges
+            && !self.folds_by_syntax(buffer_row)
             && self.starts_indent(MultiBufferRow(start.row))
             && !self.is_line_folded(MultiBufferRow(start.row))
         {
@@ -12,10 +12,13 @@
 //!
 //! If you're looking to improve Vim mode, you should check out Vim crate that wraps Editor and overrides its behavior.
 pub mod actions;
+mod align_selections;
 pub mod blink_manager;
+mod bookmarks;
 mod bracket_colorization;
 mod clangd_ext;
 pub mod code_context_menus;
+mod code_lens;
 pub mod display_map;
 mod document_colors;
 mod document_symbols;
@@ -31,14 +34,19 @@ mod inlays;
 pub mod items;
 mod jsx_tag_auto_close;
 mod linked_editing_ranges;
+mod location_history;
 mod lsp_ext;
 mod mouse_context_menu;
 pub mod movement;
+mod peek;
 mod persistence;
+mod persistent_undo;
+mod rename_preview;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
 pub mod semantic_tokens;
+mod snippet_variables;
 mod split;
 pub mod split_editor_view;
 pub mod tasks;
@@ -54,6 +62,7 @@ mod signature_help;
 pub mod test;
 
 pub(crate) use actions::*;
+pub use code_lens::CodeLensProvider;
 pub use display_map::{
     ChunkRenderer, ChunkRendererContext, DisplayPoint, FoldPlaceholder, HighlightKey,
     SemanticTokenHighlight,
@@ -65,8 +74,8 @@ pub use editor_settings::{
     ShowMinimap,
 };
 pub use element::{
-    CursorLayout, EditorElement, HighlightedRange, HighlightedRangeLine, PointForPosition,
-    render_breadcrumb_text,
+    CaretAnimation, CursorLayout, EditorElement, HighlightedRange, HighlightedRangeLine,
+    PointForPosition, render_breadcrumb_text,
 };
 pub use git::blame::BlameRenderer;
 pub use hover_popover::hover_markdown_style;
@@ -94,6 +103,7 @@ use code_context_menus::{
     AvailableCodeAction, CodeActionContents, CodeActionsItem, CodeActionsMenu, CodeContextMenu,
     CompletionsMenu, ContextMenuOrigin,
 };
+use code_lens::CodeLensState;
 use collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use convert_case::{Case, Casing};
 use dap::TelemetrySpawnLocation;
@@ -115,7 +125,7 @@ use gpui::{
     Action, Animation, AnimationExt, AnyElement, App, AppContext, AsyncWindowContext,
     AvailableSpace, Background, Bounds, ClickEvent, ClipboardEntry, ClipboardItem, Context,
     DispatchPhase, Edges, Entity, EntityId, EntityInputHandler, EventEmitter, FocusHandle,
-    FocusOutEvent, Focusable, FontId, FontStyle, FontWeight, Global, HighlightStyle, Hsla,
+    FocusOutEvent, Focusable, FontId, FontStyle, FontWeight, Global, HighlightStyle, Hsla, Image,
     KeyContext, Modifiers, MouseButton, MouseDownEvent, MouseMoveEvent, PaintQuad, ParentElement,
     Pixels, PressureStage, Render, ScrollHandle, SharedString, SharedUri, Size, Stateful, Styled,
     Subscription, Task, TextRun, TextStyle, TextStyleRefinement, UTF16Selection, UnderlineStyle,
@@ -152,12 +162,15 @@ use multi_buffer::{
     ExcerptInfo, ExpandExcerptDirection, MultiBufferDiffHunk, MultiBufferPoint, MultiBufferRow,
 };
 use parking_lot::Mutex;
+use peek::PeekState;
 use persistence::DB;
 use project::{
     BreakpointWithPosition, CodeAction, Completion, CompletionDisplayOptions, CompletionIntent,
     CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, InlayHint, InlayId,
     InvalidationStrategy, Location, LocationLink, LspAction, PrepareRenameResponse, Project,
-    ProjectItem, ProjectPath, ProjectTransaction, TaskSourceKind,
+    ProjectItem, ProjectPath, ProjectTransaction, RenameBufferEdits, TaskSourceKind,
+    WorktreeSettings,
+    bookmark_store::BookmarkStore,
     debugger::{
         breakpoint_store::{
             Breakpoint, BreakpointEditAction, BreakpointSessionState, BreakpointState,
@@ -184,6 +197,7 @@ use settings::{
 };
 use smallvec::{SmallVec, smallvec};
 use snippet::Snippet;
+use snippet_variables::SnippetVariables;
 use std::{
     any::{Any, TypeId},
     borrow::Cow,
@@ -216,6 +230,7 @@ use workspace::{
     OpenTerminal, Pane, RestoreOnStartupBehavior, SERIALIZATION_THROTTLE_TIME, SplitDirection,
     TabBarSettings, Toast, ViewId, Workspace, WorkspaceId, WorkspaceSettings,
     item::{BreadcrumbText, ItemBufferKind, ItemHandle, PreviewTabsSettings, SaveOptions},
+    location_history::LocationKind,
     notifications::{DetachAndPromptErr, NotificationId, NotifyTaskExt},
     searchable::SearchEvent,
 };
@@ -237,10 +252,10 @@ use crate::{
 
 pub const FILE_HEADER_HEIGHT: u32 = 2;
 pub const MULTI_BUFFER_EXCERPT_HEADER_HEIGHT: u32 = 1;
-const CURSOR_BLINK_INTERVAL: Duration = Duration::from_millis(500);
 const MAX_LINE_LEN: usize = 1024;
 const MIN_NAVIGATION_HISTORY_ROW_DELTA: i64 = 10;
 const MAX_SELECTION_HISTORY_LEN: usize = 1024;
+const SELECTION_DRAG_AUTOSCROLL_INTERVAL: Duration = Duration::from_millis(16);
 pub(crate) const CURSORS_VISIBLE_FOR: Duration = Duration::from_millis(2000);
 #[doc(hidden)]
 pub const CODE_ACTIONS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
@@ -1012,6 +1027,9 @@ enum SelectionDragState {
         selection: Selection<Anchor>,
         drop_cursor: Selection<Anchor>,
         hide_drop_cursor: bool,
+        /// Keeps scrolling while the mouse is held near the edge of the text
+        /// area, where it stops producing move events.
+        autoscroll_task: Option<Task<()>>,
     },
 }
 
@@ -1242,6 +1260,12 @@ pub struct Editor {
     hovered_link_state: Option<HoveredLinkState>,
     edit_prediction_provider: Option<RegisteredEditPredictionDelegate>,
     code_action_providers: Vec<Rc<dyn CodeActionProvider>>,
+    code_lens_providers: Vec<Rc<dyn CodeLensProvider>>,
+    code_lens: CodeLensState,
+    refresh_code_lens_task: Task<()>,
+    peek: Option<PeekState>,
+    /// The editor whose peek this editor is shown in.
+    peek_host: Option<WeakEntity<Editor>>,
     active_edit_prediction: Option<EditPredictionState>,
     /// Used to prevent flickering as the user types while the menu is open
     stale_edit_prediction_in_menu: Option<EditPredictionState>,
@@ -1257,9 +1281,15 @@ pub struct Editor {
     next_color_inlay_id: usize,
     _subscriptions: Vec<Subscription>,
     pixel_position_of_newest_cursor: Option<gpui::Point<Pixels>>,
+    caret_animation: Option<CaretAnimation>,
     gutter_dimensions: GutterDimensions,
     style: Option<EditorStyle>,
     text_style_refinement: Option<TextStyleRefinement>,
+    /// How much larger or smaller than the global buffer font size this
+    /// editor shows it, following the font size of its pane.
+    buffer_font_size_delta: Pixels,
+    /// Whether the gutter is hidden because the editor's pane is in zen mode.
+    zen_mode: bool,
     next_editor_action_id: EditorActionId,
     editor_actions: Rc<
         RefCell<BTreeMap<EditorActionId, Box<dyn Fn(&Editor, &mut Window, &mut Context<Self>)>>>,
@@ -1294,6 +1324,7 @@ pub struct Editor {
     tasks: BTreeMap<(BufferId, BufferRow), RunnableTasks>,
     tasks_update_task: Option<Task<()>>,
     breakpoint_store: Option<Entity<BreakpointStore>>,
+    bookmark_store: Option<Entity<BookmarkStore>>,
     gutter_breakpoint_indicator: (Option<PhantomBreakpointIndicator>, Option<Task<()>>),
     pub(crate) gutter_diff_review_indicator: (Option<PhantomDiffReviewIndicator>, Option<Task<()>>),
     pub(crate) diff_review_drag_state: Option<DiffReviewDragState>,
@@ -1319,10 +1350,13 @@ pub struct Editor {
     /// Whether we are temporarily displaying a diff other than git's
     temporary_diff_override: bool,
     selection_mark_mode: bool,
+    kill_ring_state: Option<KillRingState>,
     toggle_fold_multiple_buffers: Task<()>,
     _scroll_cursor_center_top_bottom_task: Task<()>,
     serialize_selections: Task<()>,
     serialize_folds: Task<()>,
+    serialize_undo_history: Task<()>,
+    restored_undo_history: Option<persistent_undo::RestoredUndoHistory>,
     mouse_cursor_hidden: bool,
     minimap: Option<Entity<Self>>,
     hide_mouse_mode: HideMouseMode,
@@ -2115,7 +2149,7 @@ impl Editor {
 
         let blink_manager = cx.new(|cx| {
             let mut blink_manager = BlinkManager::new(
-                CURSOR_BLINK_INTERVAL,
+                |cx| Duration::from_millis(EditorSettings::get_global(cx).cursor_blink_interval.0),
                 |cx| EditorSettings::get_global(cx).cursor_blink,
                 cx,
             );
@@ -2135,7 +2169,7 @@ impl Editor {
                 window,
                 |editor, _, event, window, cx| match event {
                     project::Event::RefreshCodeLens => {
-                        // we always query lens with actions, without storing them, always refreshing them
+                        editor.refresh_code_lenses(None, cx);
                     }
                     project::Event::RefreshInlayHints {
                         server_id,
@@ -2351,8 +2385,13 @@ impl Editor {
             (EditorMode::Full { .. }, Some(project)) => Some(project.read(cx).breakpoint_store()),
             _ => None,
         };
+        let bookmark_store = match (&mode, project.as_ref()) {
+            (EditorMode::Full { .. }, Some(project)) => Some(project.read(cx).bookmark_store()),
+            _ => None,
+        };
 
         let mut code_action_providers = Vec::new();
+        let mut code_lens_providers = Vec::new();
         let mut load_uncommitted_diff = None;
         if let Some(project) = project.clone() {
             load_uncommitted_diff = Some(
@@ -2365,6 +2404,7 @@ impl Editor {
                 )
                 .shared(),
             );
+            code_lens_providers.push(Rc::new(project.clone()) as Rc<_>);
             code_action_providers.push(Rc::new(project) as Rc<_>);
         }
 
@@ -2444,6 +2484,11 @@ impl Editor {
             next_completion_id: 0,
             next_inlay_id: 0,
             code_action_providers,
+            code_lens_providers,
+            code_lens: CodeLensState::default(),
+            refresh_code_lens_task: Task::ready(()),
+            peek: None,
+            peek_host: None,
             available_code_actions: None,
             code_actions_task: None,
             quick_selection_highlight_task: None,
@@ -2486,6 +2531,7 @@ impl Editor {
             inline_value_cache: InlineValueCache::new(inlay_hint_settings.show_value_hints),
             gutter_hovered: false,
             pixel_position_of_newest_cursor: None,
+            caret_animation: None,
             last_bounds: None,
             last_position_map: None,
             expect_bounds_change: None,
@@ -2522,6 +2568,7 @@ impl Editor {
             tasks: BTreeMap::default(),
 
             breakpoint_store,
+            bookmark_store,
             gutter_breakpoint_indicator: (None, None),
             gutter_diff_review_indicator: (None, None),
             diff_review_drag_state: None,
@@ -2574,10 +2621,15 @@ impl Editor {
             registered_buffers: HashMap::default(),
             _scroll_cursor_center_top_bottom_task: Task::ready(()),
             selection_mark_mode: false,
+            kill_ring_state: None,
             toggle_fold_multiple_buffers: Task::ready(()),
             serialize_selections: Task::ready(()),
             serialize_folds: Task::ready(()),
+            serialize_undo_history: Task::ready(()),
+            restored_undo_history: None,
             text_style_refinement: None,
+            buffer_font_size_delta: Pixels::ZERO,
+            zen_mode: false,
             load_diff_task: load_uncommitted_diff,
             temporary_diff_override: false,
             mouse_cursor_hidden: false,
@@ -2621,6 +2673,13 @@ impl Editor {
                     cx.notify();
                 }));
         }
+        if let Some(bookmarks) = editor.bookmark_store.as_ref() {
+            editor
+                ._subscriptions
+                .push(cx.observe(bookmarks, |_, _, cx| {
+                    cx.notify();
+                }));
+        }
         editor.tasks_update_task = Some(editor.refresh_runnables(window, cx));
         editor._subscriptions.extend(project_subscriptions);
 
@@ -2655,6 +2714,7 @@ impl Editor {
                                         InlayHintRefreshReason::NewLinesShown,
                                         cx,
                                     );
+                                    editor.resolve_visible_code_lenses(cx);
                                     if !editor.buffer().read(cx).is_singleton() {
                                         editor.update_lsp_data(None, window, cx);
                                     }
@@ -2690,6 +2750,8 @@ impl Editor {
                             .change_list
                             .push_to_change_list(pop_state, new_positions);
                     }
+                    let head = editor.selections.newest_anchor().head();
+                    editor.push_to_location_history(head, LocationKind::Edit, cx);
                 }
                 _ => (),
             },
@@ -3162,7 +3224,7 @@ impl Editor {
 
         EditorSnapshot {
             mode: self.mode.clone(),
-            show_gutter: self.show_gutter,
+            show_gutter: self.show_gutter && !self.zen_mode,
             offset_content: self.offset_content,
             show_line_numbers: self.show_line_numbers,
             number_deleted_lines: self.number_deleted_lines,
@@ -3503,25 +3565,37 @@ impl Editor {
         // Copy selections to primary selection buffer
         #[cfg(any(target_os = "linux", target_os = "freebsd"))]
         if local {
-            let selections = self
-                .selections
-                .all::<MultiBufferOffset>(&self.display_snapshot(cx));
-            let buffer_handle = self.buffer.read(cx).read(cx);
+            let selections = self.selections.all::<Point>(&self.display_snapshot(cx));
+            let buffer = self.buffer.read(cx).read(cx);
 
+            // Record each selection's length so that a middle-click paste
+            // into the same number of cursors distributes them like `paste`.
             let mut text = String::new();
-            for (index, selection) in selections.iter().enumerate() {
-                let text_for_selection = buffer_handle
-                    .text_for_range(selection.start..selection.end)
-                    .collect::<String>();
-
-                text.push_str(&text_for_selection);
-                if index != selections.len() - 1 {
+            let mut clipboard_selections = Vec::new();
+            for selection in selections.iter().filter(|selection| !selection.is_empty()) {
+                if !clipboard_selections.is_empty() {
                     text.push('\n');
                 }
+                let mut len = 0;
+                for chunk in buffer.text_for_range(selection.start..selection.end) {
+                    text.push_str(chunk);
+                    len += chunk.len();
+                }
+                clipboard_selections.push(ClipboardSelection::for_buffer(
+                    len,
+                    false,
+                    selection.start..selection.end,
+                    &buffer,
+                    self.project.as_ref(),
+                    cx,
+                ));
             }
 
             if !text.is_empty() {
-                cx.write_to_primary(ClipboardItem::new_string(text));
+                cx.write_to_primary(ClipboardItem::new_string_with_json_metadata(
+                    text,
+                    clipboard_selections,
+                ));
             }
         }
 
@@ -4463,6 +4537,9 @@ impl Editor {
             cx.notify();
             return;
         }
+        if self.close_peek(window, cx) {
+            return;
+        }
 
         if self.mode.is_full()
             && self.change_selections(Default::default(), window, cx, |s| s.try_cancel())
@@ -4911,6 +4988,7 @@ impl Editor {
         self.transact(window, cx, |this, window, cx| {
             if clear_linked_edit_ranges {
                 this.linked_edit_ranges.clear();
+                this.clear_background_highlights(HighlightKey::LinkedEditingRanges, cx);
             }
             let initial_buffer_versions =
                 jsx_tag_auto_close::construct_initial_buffer_versions_map(this, &edits, cx);
@@ -6378,6 +6456,7 @@ impl Editor {
         cx.stop_propagation();
 
         let buffer_handle = completions_menu.buffer.clone();
+        let snippet_variables = self.snippet_variables(cx);
 
         let CompletionEdit {
             new_text,
@@ -6388,6 +6467,7 @@ impl Editor {
             intent,
             &buffer_handle,
             &completions_menu.initial_position.text_anchor,
+            &snippet_variables,
             cx,
         );
 
@@ -6553,13 +6633,23 @@ impl Editor {
         });
 
         drop(completion);
-        let apply_edits = provider.apply_additional_edits_for_completion(
-            buffer_handle.clone(),
-            completions_menu.completions.clone(),
-            candidate_id,
-            true,
-            cx,
-        );
+        let auto_import = {
+            let buffer = buffer_handle.read(cx);
+            language_settings(buffer.language().map(|l| l.name()), buffer.file(), cx)
+                .completions
+                .auto_import
+        };
+        let apply_edits = if auto_import {
+            provider.apply_additional_edits_for_completion(
+                buffer_handle.clone(),
+                completions_menu.completions.clone(),
+                candidate_id,
+                true,
+                cx,
+            )
+        } else {
+            Task::ready(Ok(None))
+        };
 
         let editor_settings = EditorSettings::get_global(cx);
         if editor_settings.show_signature_help_after_edits || editor_settings.auto_signature_help {
@@ -6569,7 +6659,11 @@ impl Editor {
         }
 
         Some(cx.spawn_in(window, async move |editor, cx| {
-            apply_edits.await?;
+            if let Some(transaction) = apply_edits.await? {
+                editor.update(cx, |editor, cx| {
+                    editor.show_auto_import_toast(buffer_handle.clone(), transaction, cx)
+                })?;
+            }
 
             if let Some((lsp_store, command)) = lsp_store.zip(command) {
                 let title = command.lsp_action.title().to_owned();
@@ -6595,6 +6689,57 @@ impl Editor {
         }))
     }
 
+    /// Tells which lines accepting a completion added elsewhere in the
+    /// buffer, usually an import, and offers to undo them.
+    fn show_auto_import_toast(
+        &self,
+        buffer: Entity<Buffer>,
+        transaction: language::Transaction,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        let added_lines = {
+            let buffer = buffer.read(cx);
+            buffer
+                .edited_ranges_for_transaction::<usize>(&transaction)
+                .flat_map(|range| {
+                    buffer
+                        .text_for_range(range)
+                        .collect::<String>()
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty())
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+        let Some(first_line) = added_lines.first() else {
+            return;
+        };
+        let message = match added_lines.len() {
+            1 => format!("Added `{first_line}`"),
+            2 => format!("Added `{first_line}` and 1 more line"),
+            count => format!("Added `{first_line}` and {} more lines", count - 1),
+        };
+
+        struct AutoImport;
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(NotificationId::unique::<AutoImport>(), message)
+                    .on_click("Undo", move |_, cx| {
+                        buffer.update(cx, |buffer, cx| {
+                            buffer.undo_transaction(transaction.id, cx);
+                        });
+                    })
+                    .autohide(),
+                cx,
+            )
+        });
+    }
+
     pub fn toggle_code_actions(
         &mut self,
         action: &ToggleCodeActions,
@@ -7452,37 +7597,66 @@ impl Editor {
         None
     }
 
+    /// The text to highlight the occurrences of, its range, and whether only
+    /// whole words match, which is the case for the word under an empty cursor.
     fn prepare_highlight_query_from_selection(
         &mut self,
         window: &Window,
         cx: &mut Context<Editor>,
-    ) -> Option<(String, Range<Anchor>)> {
+    ) -> Option<(String, Range<Anchor>, bool)> {
         if matches!(self.mode, EditorMode::SingleLine) {
             return None;
         }
-        if !EditorSettings::get_global(cx).selection_highlight {
+        let editor_settings = EditorSettings::get_global(cx);
+        if !editor_settings.selection_highlight {
             return None;
         }
+        let min_length = editor_settings.selection_highlight_min_length;
         if self.selections.count() != 1 || self.selections.line_mode() {
             return None;
         }
         let snapshot = self.snapshot(window, cx);
         let selection = self.selections.newest::<Point>(&snapshot);
-        // If the selection spans multiple rows OR it is empty
-        if selection.start.row != selection.end.row
-            || selection.start.column == selection.end.column
-        {
+        if selection.start.row != selection.end.row {
             return None;
         }
-        let selection_anchor_range = selection.range().to_anchors(snapshot.buffer_snapshot());
+        let (selection_anchor_range, whole_word) = if selection.is_empty() {
+            let (word_range, kind) = snapshot
+                .buffer_snapshot()
+                .surrounding_word(selection.head(), None);
+            if kind != Some(CharKind::Word)
+                || word_range.is_empty()
+                || self.has_lsp_document_highlights(selection.head(), cx)
+            {
+                return None;
+            }
+            (word_range.to_anchors(snapshot.buffer_snapshot()), true)
+        } else {
+            (
+                selection.range().to_anchors(snapshot.buffer_snapshot()),
+                false,
+            )
+        };
         let query = snapshot
             .buffer_snapshot()
             .text_for_range(selection_anchor_range.clone())
             .collect::<String>();
-        if query.trim().is_empty() {
+        if query.trim().is_empty() || query.chars().count() < min_length {
             return None;
         }
-        Some((query, selection_anchor_range))
+        Some((query, selection_anchor_range, whole_word))
+    }
+
+    /// Whether a language server highlights the occurrences of the symbol at
+    /// `position`, which the word under the cursor then shouldn't duplicate.
+    fn has_lsp_document_highlights(&self, position: Point, cx: &mut Context<Editor>) -> bool {
+        let Some(provider) = self.semantics_provider.clone() else {
+            return false;
+        };
+        let Some((buffer, _)) = self.buffer.read(cx).point_to_buffer_offset(position, cx) else {
+            return false;
+        };
+        provider.supports_document_highlights(&buffer, cx)
     }
 
     #[ztracing::instrument(skip_all)]
@@ -7490,6 +7664,7 @@ impl Editor {
         &mut self,
         query_text: String,
         query_range: Range<Anchor>,
+        whole_word: bool,
         multi_buffer_range_to_query: Range<Point>,
         use_debounce: bool,
         window: &mut Window,
@@ -7512,7 +7687,7 @@ impl Editor {
                 let mut match_ranges = Vec::new();
                 let Ok(regex) = project::search::SearchQuery::text(
                     query_text.clone(),
-                    false,
+                    whole_word,
                     false,
                     false,
                     Default::default(),
@@ -7677,7 +7852,7 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) {
-        let Some((query_text, query_range)) =
+        let Some((query_text, query_range, whole_word)) =
             self.prepare_highlight_query_from_selection(window, cx)
         else {
             self.clear_background_highlights(HighlightKey::SelectedTextHighlight, cx);
@@ -7712,6 +7887,7 @@ impl Editor {
                 self.update_selection_occurrence_highlights(
                     query_text.clone(),
                     query_range.clone(),
+                    whole_word,
                     multi_buffer_visible_range,
                     false,
                     window,
@@ -7737,6 +7913,7 @@ impl Editor {
                 self.update_selection_occurrence_highlights(
                     query_text,
                     query_range,
+                    whole_word,
                     multi_buffer_full_range,
                     true,
                     window,
@@ -11420,6 +11597,26 @@ impl Editor {
         self.manipulate_immutable_lines(window, cx, |lines| lines.sort())
     }
 
+    pub fn sort_lines_descending(
+        &mut self,
+        _: &SortLinesDescending,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.manipulate_immutable_lines(window, cx, |lines| lines.sort_by(|a, b| b.cmp(a)))
+    }
+
+    pub fn sort_lines_naturally(
+        &mut self,
+        _: &SortLinesNaturally,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.manipulate_immutable_lines(window, cx, |lines| {
+            lines.sort_by(|a, b| util::paths::natural_sort(a, b))
+        })
+    }
+
     pub fn sort_lines_by_length(
         &mut self,
         _: &SortLinesByLength,
@@ -12252,8 +12449,12 @@ impl Editor {
         });
     }
 
+    /// Replaces the lines of each selection with `manipulate`'s result. With
+    /// `whole_buffer_without_selection`, a lone cursor in a singleton buffer
+    /// manipulates every line of the buffer.
     fn manipulate_lines<M>(
         &mut self,
+        whole_buffer_without_selection: bool,
         window: &mut Window,
         cx: &mut Context<Self>,
         mut manipulate: M,
@@ -12267,7 +12468,16 @@ impl Editor {
 
         let mut edits = Vec::new();
 
-        let selections = self.selections.all::<Point>(&display_map);
+        let mut selections = self.selections.all::<Point>(&display_map);
+        if whole_buffer_without_selection
+            && buffer.is_singleton()
+            && let [selection] = selections.as_mut_slice()
+            && selection.is_empty()
+        {
+            selection.start = Point::zero();
+            selection.end = buffer.max_point();
+            selection.reversed = false;
+        }
         let mut selections = selections.iter().peekable();
         let mut contiguous_row_selections = Vec::new();
         let mut new_selections = Vec::new();
@@ -12356,7 +12566,7 @@ impl Editor {
     ) where
         Fn: FnMut(&mut Vec<&str>),
     {
-        self.manipulate_lines(window, cx, |text| {
+        self.manipulate_lines(true, window, cx, |text| {
             let mut lines: Vec<&str> = text.split('\n').collect();
             let line_count_before = lines.len();
 
@@ -12378,7 +12588,7 @@ impl Editor {
     ) where
         Fn: FnMut(&mut Vec<Cow<'_, str>>),
     {
-        self.manipulate_lines(window, cx, |text| {
+        self.manipulate_lines(false, window, cx, |text| {
             let mut lines: Vec<Cow<str>> = text.split('\n').map(Cow::from).collect();
             let line_count_before = lines.len();
 
@@ -12714,6 +12924,57 @@ impl Editor {
         });
     }
 
+    pub(crate) fn autoscroll_selection_drag(
+        &mut self,
+        mouse_position: gpui::Point<Pixels>,
+        scroll_delta: gpui::Point<f32>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let SelectionDragState::Dragging {
+            autoscroll_task, ..
+        } = &mut self.selection_drag_state
+        else {
+            return;
+        };
+        if scroll_delta == gpui::Point::default() {
+            *autoscroll_task = None;
+            return;
+        }
+        *autoscroll_task = Some(cx.spawn_in(window, async move |editor, cx| {
+            loop {
+                cx.background_executor()
+                    .timer(SELECTION_DRAG_AUTOSCROLL_INTERVAL)
+                    .await;
+                let still_dragging = editor.update_in(cx, |editor, window, cx| {
+                    if !matches!(
+                        editor.selection_drag_state,
+                        SelectionDragState::Dragging { .. }
+                    ) {
+                        return false;
+                    }
+                    editor.apply_scroll_delta(scroll_delta, window, cx);
+                    if let Some(position_map) = editor.last_position_map.clone()
+                        && let SelectionDragState::Dragging { drop_cursor, .. } =
+                            &mut editor.selection_drag_state
+                    {
+                        let point = position_map.point_for_position(mouse_position);
+                        let drop_anchor = position_map
+                            .snapshot
+                            .display_point_to_anchor(point.previous_valid, Bias::Left);
+                        drop_cursor.start = drop_anchor;
+                        drop_cursor.end = drop_anchor;
+                    }
+                    cx.notify();
+                    true
+                });
+                if !still_dragging.unwrap_or(false) {
+                    break;
+                }
+            }
+        }));
+    }
+
     pub fn move_selection_on_drop(
         &mut self,
         selection: &Selection<Anchor>,
@@ -13626,8 +13887,27 @@ impl Editor {
                 }
             });
         });
+        let follows_kill = matches!(
+            &self.kill_ring_state,
+            Some(KillRingState::Killed { edit_count, cursors })
+                if *edit_count == self.buffer.read(cx).snapshot(cx).edit_count()
+                    && *cursors == self.kill_ring_cursors(cx)
+        );
         let item = self.cut_common(false, window, cx);
-        cx.set_global(KillRing(item))
+        let kill_ring = cx.default_global::<KillRing>();
+        match kill_ring.entries.back_mut() {
+            Some(latest) if follows_kill => *latest = append_kill(latest, item),
+            _ => {
+                kill_ring.entries.push_back(item);
+                if kill_ring.entries.len() > KILL_RING_MAX_ENTRIES {
+                    kill_ring.entries.pop_front();
+                }
+            }
+        }
+        self.kill_ring_state = Some(KillRingState::Killed {
+            edit_count: self.buffer.read(cx).snapshot(cx).edit_count(),
+            cursors: self.kill_ring_cursors(cx),
+        });
     }
 
     pub fn kill_ring_yank(
@@ -13637,16 +13917,99 @@ impl Editor {
         cx: &mut Context<Self>,
     ) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
-        let (text, metadata) = if let Some(KillRing(item)) = cx.try_global() {
-            if let Some(ClipboardEntry::String(kill_ring)) = item.entries().first() {
-                (kill_ring.text().to_string(), kill_ring.metadata_json())
-            } else {
-                return;
-            }
-        } else {
+        self.yank_kill(0, window, cx);
+    }
+
+    pub fn kill_ring_yank_pop(
+        &mut self,
+        _: &KillRingYankPop,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(KillRingState::Yanked {
+            edit_count,
+            ranges,
+            index,
+        }) = self.kill_ring_state.clone()
+        else {
             return;
         };
+        let cursors = self.kill_ring_cursors(cx);
+        if edit_count != self.buffer.read(cx).snapshot(cx).edit_count()
+            || cursors.len() != ranges.len()
+            || cursors
+                .iter()
+                .zip(&ranges)
+                .any(|(cursor, range)| *cursor != range.end)
+        {
+            return;
+        }
+        let Some(entry_count) = cx
+            .try_global::<KillRing>()
+            .map(|kill_ring| kill_ring.entries.len())
+            .filter(|entry_count| *entry_count > 0)
+        else {
+            return;
+        };
+
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+        self.transact(window, cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges(ranges);
+            });
+            editor.yank_kill((index + 1) % entry_count, window, cx);
+        });
+    }
+
+    /// Pastes the kill ring entry `index` kills before the latest one,
+    /// remembering the pasted ranges so that a yank pop can replace them.
+    fn yank_kill(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((text, metadata)) = cx
+            .try_global::<KillRing>()
+            .and_then(|kill_ring| kill_ring.entries.iter().rev().nth(index))
+            .and_then(|item| match item.entries().first() {
+                Some(ClipboardEntry::String(kill)) => {
+                    Some((kill.text().to_string(), kill.metadata_json()))
+                }
+                _ => None,
+            })
+        else {
+            return;
+        };
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let starts = self
+            .selections
+            .all::<MultiBufferOffset>(&self.display_snapshot(cx))
+            .into_iter()
+            .map(|selection| snapshot.anchor_before(selection.start))
+            .collect::<Vec<_>>();
         self.do_paste(&text, metadata, false, window, cx);
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let cursors = self.kill_ring_cursors(cx);
+        if starts.len() == cursors.len() {
+            let ranges = starts
+                .iter()
+                .zip(cursors)
+                .map(|(start, cursor)| start.to_offset(&snapshot)..cursor)
+                .collect();
+            self.kill_ring_state = Some(KillRingState::Yanked {
+                edit_count: snapshot.edit_count(),
+                ranges,
+                index,
+            });
+        } else {
+            self.kill_ring_state = None;
+        }
+    }
+
+    fn kill_ring_cursors(&self, cx: &mut Context<Self>) -> Vec<MultiBufferOffset> {
+        self.selections
+            .all::<MultiBufferOffset>(&self.display_snapshot(cx))
+            .into_iter()
+            .map(|selection| selection.head())
+            .collect()
     }
 
     pub fn copy_and_trim(&mut self, _: &CopyAndTrim, _: &mut Window, cx: &mut Context<Self>) {
@@ -13757,6 +14120,46 @@ impl Editor {
         ));
     }
 
+    /// When pasting into a block of selections, one per row, text with a line
+    /// for each of them, splits the text so that each selection gets its line
+    /// rather than all of the text.
+    fn split_for_block_paste(
+        &self,
+        text: &str,
+        clipboard_selections: Option<&[ClipboardSelection]>,
+        cx: &mut Context<Self>,
+    ) -> Option<(String, Vec<ClipboardSelection>)> {
+        let selections = self.selections.all::<Point>(&self.display_snapshot(cx));
+        if selections.len() < 2
+            || clipboard_selections
+                .is_some_and(|clipboard_selections| clipboard_selections.len() == selections.len())
+        {
+            return None;
+        }
+        let is_block = selections
+            .iter()
+            .all(|selection| selection.start.row == selection.end.row)
+            && selections
+                .windows(2)
+                .all(|pair| pair[0].start.row < pair[1].start.row);
+        let lines = text.lines().collect::<Vec<_>>();
+        if !is_block || lines.len() != selections.len() {
+            return None;
+        }
+
+        let clipboard_selections = lines
+            .iter()
+            .map(|line| ClipboardSelection {
+                len: line.len(),
+                is_entire_line: false,
+                first_line_indent: (line.len() - line.trim_start().len()) as u32,
+                file_path: None,
+                line_range: None,
+            })
+            .collect();
+        Some((lines.join("\n"), clipboard_selections))
+    }
+
     pub fn do_paste(
         &mut self,
         text: &String,
@@ -13769,7 +14172,11 @@ impl Editor {
             return;
         }
 
-        let clipboard_text = Cow::Borrowed(text.as_str());
+        let (clipboard_text, clipboard_selections) =
+            match self.split_for_block_paste(text, clipboard_selections.as_deref(), cx) {
+                Some((text, selections)) => (Cow::Owned(text), Some(selections)),
+                None => (Cow::Borrowed(text.as_str()), clipboard_selections),
+            };
 
         self.transact(window, cx, |this, window, cx| {
             let had_active_edit_prediction = this.has_active_edit_prediction();
@@ -13968,6 +14375,13 @@ impl Editor {
     pub fn paste(&mut self, _: &Paste, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
         if let Some(item) = cx.read_from_clipboard() {
+            if item.text().is_none()
+                && let Some(image) = item.image()
+                && self.paste_image_into_markdown(image.clone(), window, cx)
+            {
+                return;
+            }
+
             let entries = item.entries();
 
             match entries.first() {
@@ -13986,6 +14400,48 @@ impl Editor {
         }
     }
 
+    /// Saves a pasted image next to the Markdown file being edited and inserts
+    /// a link to it. Returns false when this editor isn't editing a local
+    /// Markdown file.
+    fn paste_image_into_markdown(
+        &mut self,
+        image: Image,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if self.read_only(cx) {
+            return false;
+        }
+        let Some((directory, fs)) = maybe!({
+            let buffer = self.buffer.read(cx).as_singleton()?;
+            let buffer = buffer.read(cx);
+            if buffer.language()?.name() != "Markdown".into() {
+                return None;
+            }
+            let markdown_path = buffer.file()?.as_local()?.abs_path(cx);
+            let fs = self.project.as_ref()?.read(cx).fs().clone();
+            Some((markdown_path.parent()?.to_path_buf(), fs))
+        }) else {
+            return false;
+        };
+
+        cx.spawn_in(window, async move |editor, cx| {
+            let extension = image.format.extension();
+            let mut file_name = format!("image.{extension}");
+            let mut index = 1;
+            while fs.is_file(&directory.join(&file_name)).await {
+                file_name = format!("image-{index}.{extension}");
+                index += 1;
+            }
+            fs.write(&directory.join(&file_name), &image.bytes).await?;
+            editor.update_in(cx, |editor, window, cx| {
+                editor.insert(&format!("![]({file_name})"), window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+        true
+    }
+
     pub fn undo(&mut self, _: &Undo, window: &mut Window, cx: &mut Context<Self>) {
         if self.read_only(cx) {
             return;
@@ -14013,6 +14469,8 @@ impl Editor {
             self.refresh_edit_prediction(true, false, window, cx);
             cx.emit(EditorEvent::Edited { transaction_id });
             cx.emit(EditorEvent::TransactionUndone { transaction_id });
+        } else {
+            self.undo_restored_history(window, cx);
         }
     }
 
@@ -15304,7 +15762,8 @@ impl Editor {
             cx.emit(EditorEvent::PushedToNavHistory {
                 anchor: cursor_anchor,
                 is_deactivate,
-            })
+            });
+            self.push_to_location_history(cursor_anchor, LocationKind::Navigation, cx);
         }
     }
 
@@ -15633,9 +16092,11 @@ impl Editor {
             .map(|selection| selection.range())
             .collect_vec();
 
+        let snippet_variables = self.snippet_variables(cx);
+        let resolve_variable = |name: &str| snippet_variables.resolve(name);
         let snippet = if let Some(snippet_body) = &action.snippet {
             if action.language.is_none() && action.name.is_none() {
-                Snippet::parse(snippet_body)?
+                Snippet::parse_with_variables(snippet_body, &resolve_variable)?
             } else {
                 bail!("`snippet` is mutually exclusive with `language` and `name`")
             }
@@ -15647,7 +16108,7 @@ impl Editor {
                 .into_iter()
                 .find(|snippet| snippet.name == *name)
                 .context("snippet not found")?;
-            Snippet::parse(&snippet.body)?
+            Snippet::parse_with_variables(&snippet.body, &resolve_variable)?
         } else {
             // todo(andrew): open modal to select snippet
             bail!("`name` or `snippet` is required")
@@ -18994,22 +19455,8 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<Task<Result<()>>> {
-        let rename = self.take_rename(false, window, cx)?;
+        let (buffer, start, old_name, new_name) = self.take_rename_target(window, cx)?;
         let workspace = self.workspace()?.downgrade();
-        let (buffer, start) = self
-            .buffer
-            .read(cx)
-            .text_anchor_for_position(rename.range.start, cx)?;
-        let (end_buffer, _) = self
-            .buffer
-            .read(cx)
-            .text_anchor_for_position(rename.range.end, cx)?;
-        if buffer != end_buffer {
-            return None;
-        }
-
-        let old_name = rename.old_name;
-        let new_name = rename.editor.read(cx).text(cx);
 
         let rename = self.semantics_provider.as_ref()?.perform_rename(
             &buffer,
@@ -19036,6 +19483,29 @@ impl Editor {
         }))
     }
 
+    /// Ends the pending rename, returning the buffer and position of the
+    /// symbol it renames, with the symbol's old and new names.
+    fn take_rename_target(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<(Entity<Buffer>, text::Anchor, Arc<str>, String)> {
+        let rename = self.take_rename(false, window, cx)?;
+        let (buffer, start) = self
+            .buffer
+            .read(cx)
+            .text_anchor_for_position(rename.range.start, cx)?;
+        let (end_buffer, _) = self
+            .buffer
+            .read(cx)
+            .text_anchor_for_position(rename.range.end, cx)?;
+        if buffer != end_buffer {
+            return None;
+        }
+        let new_name = rename.editor.read(cx).text(cx);
+        Some((buffer, start, rename.old_name, new_name))
+    }
+
     fn take_rename(
         &mut self,
         moving_cursor: bool,
@@ -20597,7 +21067,88 @@ impl Editor {
             .map(|s| s.range())
             .collect();
         let stage = self.has_stageable_diff_hunks_in_ranges(&ranges, &snapshot);
-        self.stage_or_unstage_diff_hunks(stage, ranges, cx);
+        self.stage_or_unstage_selections(stage, ranges, cx);
+    }
+
+    /// Stages or unstages the hunks under empty selections, and only the
+    /// selected lines of the hunks under the rest.
+    fn stage_or_unstage_selections(
+        &mut self,
+        stage: bool,
+        ranges: Vec<Range<Anchor>>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.delegate_stage_and_restore {
+            self.stage_or_unstage_diff_hunks(stage, ranges, cx);
+            return;
+        }
+        let (line_ranges, hunk_ranges): (Vec<_>, Vec<_>) = ranges
+            .iter()
+            .cloned()
+            .partition(|range| range.start != range.end);
+        if line_ranges.is_empty() {
+            self.stage_or_unstage_diff_hunks(stage, hunk_ranges, cx);
+            return;
+        }
+
+        let task = self.save_buffers_for_ranges_if_needed(&ranges, cx);
+        cx.spawn(async move |this, cx| {
+            task.await?;
+            this.update(cx, |this, cx| {
+                let snapshot = this.buffer.read(cx).snapshot(cx);
+                let mut rows_by_buffer = HashMap::<BufferId, (&BufferSnapshot, Vec<_>)>::default();
+                let rows = |start: Point, end: Point| {
+                    let end_row = if end.column > 0 || end.row == start.row {
+                        end.row + 1
+                    } else {
+                        end.row
+                    };
+                    start.row..end_row
+                };
+                for range in &line_ranges {
+                    for (buffer, buffer_range, _) in snapshot.range_to_buffer_ranges(range.clone())
+                    {
+                        let start = buffer.offset_to_point(buffer_range.start.0);
+                        let end = buffer.offset_to_point(buffer_range.end.0);
+                        rows_by_buffer
+                            .entry(buffer.remote_id())
+                            .or_insert_with(|| (buffer, Vec::new()))
+                            .1
+                            .push(rows(start, end));
+                    }
+                }
+
+                // The hunks of buffers that also have lines selected are
+                // staged along with those lines, as one change of the index.
+                // Staging them separately would have the second change start
+                // from an index that doesn't have the first one yet.
+                let mut separate_hunks = Vec::new();
+                for hunk in this.diff_hunks_in_ranges(&hunk_ranges, &snapshot) {
+                    match rows_by_buffer.get_mut(&hunk.buffer_id) {
+                        Some((buffer, buffer_rows)) => {
+                            let start = hunk.buffer_range.start.to_point(*buffer);
+                            let end = hunk.buffer_range.end.to_point(*buffer);
+                            buffer_rows.push(rows(start, end));
+                        }
+                        None => separate_hunks.push(hunk),
+                    }
+                }
+                for (buffer_id, hunks) in
+                    &separate_hunks.into_iter().chunk_by(|hunk| hunk.buffer_id)
+                {
+                    this.do_stage_or_unstage(stage, buffer_id, hunks, cx);
+                }
+
+                for (buffer_id, (buffer, rows)) in rows_by_buffer {
+                    if let Some(diff) = this.buffer.read(cx).diff_for(buffer_id) {
+                        diff.update(cx, |diff, cx| {
+                            diff.stage_or_unstage_lines(stage, &rows, buffer, cx)
+                        });
+                    }
+                }
+            })
+        })
+        .detach_and_log_err(cx);
     }
 
     pub fn set_render_diff_hunk_controls(
@@ -20697,7 +21248,7 @@ impl Editor {
         let ranges = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
 
         if ranges.iter().any(|range| range.start != range.end) {
-            self.stage_or_unstage_diff_hunks(stage, ranges, cx);
+            self.stage_or_unstage_selections(stage, ranges, cx);
             return;
         }
 
@@ -21139,6 +21690,9 @@ impl Editor {
     }
 
     pub fn soft_wrap_mode(&self, cx: &App) -> SoftWrap {
+        if self.soft_wrap_mode_override.is_none() && self.is_large_file_without_soft_wrap(cx) {
+            return SoftWrap::None;
+        }
         let settings = self.buffer.read(cx).language_settings(cx);
         let mode = self.soft_wrap_mode_override.unwrap_or(settings.soft_wrap);
         match mode {
@@ -21155,6 +21709,23 @@ impl Editor {
         }
     }
 
+    fn is_large_file_without_soft_wrap(&self, cx: &App) -> bool {
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return false;
+        };
+        let buffer = buffer.read(cx);
+        if !buffer.is_large_file() {
+            return false;
+        }
+        let settings_location = buffer.file().map(|file| SettingsLocation {
+            worktree_id: file.worktree_id(cx),
+            path: file.path(),
+        });
+        !WorktreeSettings::get(settings_location, cx)
+            .large_file_mode
+            .soft_wrap
+    }
+
     pub fn set_soft_wrap_mode(
         &mut self,
         mode: language_settings::SoftWrap,
@@ -23701,6 +24272,37 @@ impl Editor {
             .is_some_and(|(_, highlights)| !highlights.is_empty())
     }
 
+    /// Returns the display rows covered by the background highlights for `key`
+    /// that intersect the given range.
+    pub fn background_highlight_rows_in_range(
+        &self,
+        key: HighlightKey,
+        search_range: Range<Anchor>,
+        display_snapshot: &DisplaySnapshot,
+    ) -> Vec<Range<DisplayRow>> {
+        let Some((_, ranges)) = self.background_highlights.get(&key) else {
+            return Vec::new();
+        };
+        let buffer_snapshot = display_snapshot.buffer_snapshot();
+        let start_ix = match ranges.binary_search_by(|probe| {
+            if probe.end.cmp(&search_range.start, buffer_snapshot).is_gt() {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            }
+        }) {
+            Ok(i) | Err(i) => i,
+        };
+        ranges[start_ix..]
+            .iter()
+            .take_while(|range| range.start.cmp(&search_range.end, buffer_snapshot).is_lt())
+            .map(|range| {
+                range.start.to_display_point(display_snapshot).row()
+                    ..range.end.to_display_point(display_snapshot).row()
+            })
+            .collect()
+    }
+
     /// Returns all background highlights for a given range.
     ///
     /// The order of highlights is not deterministic, do sort the ranges if needed for the logic.
@@ -24006,6 +24608,7 @@ impl Editor {
 
                 // Clean up orphaned review comments after edits
                 self.cleanup_orphaned_review_comments(cx);
+                self.serialize_undo_history(cx);
 
                 if let Some(buffer) = edited_buffer {
                     if buffer.read(cx).file().is_none() {
@@ -24083,6 +24686,7 @@ impl Editor {
                         display_map.invalidate_semantic_highlights(*buffer_id);
                         display_map.clear_lsp_folding_ranges(*buffer_id, cx);
                     });
+                    self.clear_code_lenses(*buffer_id, cx);
                 }
                 jsx_tag_auto_close::refresh_enabled_in_any_buffer(self, multibuffer, cx);
                 cx.emit(EditorEvent::ExcerptsRemoved {
@@ -24136,10 +24740,19 @@ impl Editor {
                 cx.notify();
             }
             multi_buffer::Event::DirtyChanged => cx.emit(EditorEvent::DirtyChanged),
-            multi_buffer::Event::Saved => cx.emit(EditorEvent::Saved),
-            multi_buffer::Event::FileHandleChanged
-            | multi_buffer::Event::Reloaded
-            | multi_buffer::Event::BufferDiffChanged => cx.emit(EditorEvent::TitleChanged),
+            multi_buffer::Event::Saved => {
+                // The saved history is only restored while the file's mtime
+                // matches, which saving changes.
+                self.serialize_undo_history(cx);
+                cx.emit(EditorEvent::Saved);
+            }
+            multi_buffer::Event::Reloaded => {
+                self.serialize_undo_history(cx);
+                cx.emit(EditorEvent::TitleChanged);
+            }
+            multi_buffer::Event::FileHandleChanged | multi_buffer::Event::BufferDiffChanged => {
+                cx.emit(EditorEvent::TitleChanged)
+            }
             multi_buffer::Event::DiagnosticsUpdated => {
                 self.update_diagnostics_state(window, cx);
             }
@@ -24331,6 +24944,7 @@ impl Editor {
             if language_settings_changed {
                 self.clear_disabled_lsp_folding_ranges(window, cx);
                 self.refresh_document_symbols(None, cx);
+                self.refresh_code_lenses(None, cx);
             }
 
             if let Some(inlay_splice) = self.colors.as_mut().and_then(|colors| {
@@ -25371,6 +25985,7 @@ impl Editor {
         self.refresh_document_colors(for_buffer, window, cx);
         self.refresh_folding_ranges(for_buffer, window, cx);
         self.refresh_document_symbols(for_buffer, cx);
+        self.refresh_code_lenses(for_buffer, cx);
     }
 
     fn register_visible_buffers(&mut self, cx: &mut Context<Self>) {
@@ -25422,12 +26037,40 @@ impl Editor {
                 font_family: settings.buffer_font.family.clone(),
                 font_features: settings.buffer_font.features.clone(),
                 font_fallbacks: settings.buffer_font.fallbacks.clone(),
-                font_size: settings.buffer_font_size(cx).into(),
+                font_size: theme::clamp_font_size(
+                    settings.buffer_font_size(cx) + self.buffer_font_size_delta,
+                )
+                .into(),
                 font_weight: settings.buffer_font.weight,
                 line_height: relative(settings.buffer_line_height.value()),
                 ..Default::default()
             },
         };
+        if self.mode.is_full()
+            && let Some(buffer) = self.buffer.read(cx).as_singleton()
+        {
+            let buffer = buffer.read(cx);
+            let language_settings = language_settings(
+                buffer.language().map(|language| language.name()),
+                buffer.file(),
+                cx,
+            );
+            if let Some(font_family) = &language_settings.buffer_font_family {
+                text_style.font_family = font_family.clone();
+            }
+            if let Some(font_size) = language_settings.buffer_font_size {
+                // Keep the zoom of the buffer font size applied to the language's size.
+                let zoom = settings.buffer_font_size(cx) - settings.buffer_font_size_settings();
+                text_style.font_size =
+                    theme::clamp_font_size(font_size + zoom + self.buffer_font_size_delta).into();
+            }
+            if let Some(font_weight) = language_settings.buffer_font_weight {
+                text_style.font_weight = font_weight;
+            }
+            if let Some(font_features) = &language_settings.buffer_font_features {
+                text_style.font_features = font_features.clone();
+            }
+        }
         if let Some(text_style_refinement) = &self.text_style_refinement {
             text_style.refine(text_style_refinement)
         }
@@ -25454,6 +26097,13 @@ impl Editor {
         }
     }
 
+    /// The symbols containing the newest cursor, outermost first, as shown in the breadcrumbs.
+    pub fn outline_symbols_at_cursor(&self) -> &[OutlineItem<Anchor>] {
+        self.outline_symbols_at_cursor
+            .as_ref()
+            .map_or(&[], |(_, symbols)| symbols.as_slice())
+    }
+
     fn breadcrumbs_inner(&self, cx: &App) -> Option<Vec<BreadcrumbText>> {
         let multibuffer = self.buffer().read(cx);
         let is_singleton = multibuffer.is_singleton();
@@ -25533,6 +26183,7 @@ fn process_completion_for_edit(
     intent: CompletionIntent,
     buffer: &Entity<Buffer>,
     cursor_position: &text::Anchor,
+    snippet_variables: &SnippetVariables,
     cx: &mut Context<Editor>,
 ) -> CompletionEdit {
     let buffer = buffer.read(cx);
@@ -25558,7 +26209,11 @@ fn process_completion_for_edit(
         {
             snippet_source = label;
         }
-        match Snippet::parse(&snippet_source).log_err() {
+        match Snippet::parse_with_variables(&snippet_source, &|name| {
+            snippet_variables.resolve(name)
+        })
+        .log_err()
+        {
             Some(parsed_snippet) => (Some(parsed_snippet.clone()), parsed_snippet.text),
             None => (None, completion.new_text.clone()),
         }
@@ -26618,6 +27273,8 @@ pub trait SemanticsProvider {
 
     fn supports_semantic_tokens(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool;
 
+    fn supports_document_highlights(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool;
+
     fn document_highlights(
         &self,
         buffer: &Entity<Buffer>,
@@ -26647,6 +27304,14 @@ pub trait SemanticsProvider {
         new_name: String,
         cx: &mut App,
     ) -> Option<Task<Result<ProjectTransaction>>>;
+
+    fn rename_edits(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        new_name: String,
+        cx: &mut App,
+    ) -> Option<Task<Result<Option<Vec<RenameBufferEdits>>>>>;
 }
 
 pub trait CompletionProvider {
@@ -27153,6 +27818,14 @@ impl SemanticsProvider for Entity<Project> {
         })
     }
 
+    fn supports_document_highlights(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
+        self.update(cx, |project, cx| {
+            buffer.update(cx, |buffer, cx| {
+                project.any_language_server_supports_document_highlights(buffer, cx)
+            })
+        })
+    }
+
     fn inline_values(
         &self,
         buffer_handle: Entity<Buffer>,
@@ -27250,6 +27923,18 @@ impl SemanticsProvider for Entity<Project> {
             project.perform_rename(buffer.clone(), position, new_name, cx)
         }))
     }
+
+    fn rename_edits(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        new_name: String,
+        cx: &mut App,
+    ) -> Option<Task<Result<Option<Vec<RenameBufferEdits>>>>> {
+        Some(self.update(cx, |project, cx| {
+            project.rename_edits(buffer.clone(), position, new_name, cx)
+        }))
+    }
 }
 
 fn consume_contiguous_rows(
@@ -27588,7 +28273,10 @@ impl EditorSnapshot {
             }
         }
 
-        is_foldable |= !self.use_lsp_folding_ranges && self.starts_indent(buffer_row);
+        is_foldable |= self.syntax_fold_range_for_buffer_row(buffer_row).is_some()
+            || (!self.use_lsp_folding_ranges
+                && !self.folds_by_syntax(buffer_row)
+                && self.starts_indent(buffer_row));
 
         if folded || (is_foldable && (row_contains_cursor || self.gutter_hovered)) {
             Some(
@@ -28599,9 +29287,85 @@ fn collapse_multiline_range(range: Range<Point>) -> Range<Point> {
         range.start..range.start
     }
 }
-pub struct KillRing(ClipboardItem);
+/// The number of kills the kill ring keeps, like Emacs' `kill-ring-max`.
+const KILL_RING_MAX_ENTRIES: usize = 120;
+
+/// The kills of `KillRingCut`, oldest first, shared by all editors.
+#[derive(Default)]
+pub struct KillRing {
+    entries: VecDeque<ClipboardItem>,
+}
 impl Global for KillRing {}
 
+/// What an editor's last kill ring action was, so that a kill right after a
+/// kill can append to it, and a yank pop right after a yank can replace it.
+#[derive(Clone, Debug)]
+enum KillRingState {
+    Killed {
+        edit_count: usize,
+        cursors: Vec<MultiBufferOffset>,
+    },
+    Yanked {
+        edit_count: usize,
+        ranges: Vec<Range<MultiBufferOffset>>,
+        index: usize,
+    },
+}
+
+/// Joins a kill to the latest one, selection by selection, falling back to
+/// the new kill when their selections don't line up.
+fn append_kill(latest: &ClipboardItem, kill: ClipboardItem) -> ClipboardItem {
+    let (Some(ClipboardEntry::String(latest)), Some(ClipboardEntry::String(new))) =
+        (latest.entries().first(), kill.entries().first())
+    else {
+        return kill;
+    };
+    let (Some(latest_selections), Some(new_selections)) = (
+        latest.metadata_json::<Vec<ClipboardSelection>>(),
+        new.metadata_json::<Vec<ClipboardSelection>>(),
+    ) else {
+        return kill;
+    };
+    if latest_selections.len() != new_selections.len()
+        || latest_selections
+            .iter()
+            .chain(&new_selections)
+            .any(|selection| selection.is_entire_line)
+    {
+        return kill;
+    }
+
+    let mut latest_texts = latest.text().split('\n');
+    let mut new_texts = new.text().split('\n');
+    let mut text = String::new();
+    let mut selections = Vec::with_capacity(latest_selections.len());
+    for (latest_selection, new_selection) in latest_selections.into_iter().zip(new_selections) {
+        if !selections.is_empty() {
+            text.push('\n');
+        }
+        let start = text.len();
+        for (texts, selection) in [
+            (&mut latest_texts, &latest_selection),
+            (&mut new_texts, &new_selection),
+        ] {
+            let mut remaining = selection.len;
+            for part in texts {
+                text.push_str(part);
+                if remaining <= part.len() {
+                    break;
+                }
+                text.push('\n');
+                remaining -= part.len() + 1;
+            }
+        }
+        selections.push(ClipboardSelection {
+            len: text.len() - start,
+            ..latest_selection
+        });
+    }
+    ClipboardItem::new_string_with_json_metadata(text, selections)
+}
+
 const UPDATE_DEBOUNCE: Duration = Duration::from_millis(50);
 
 enum BreakpointPromptEditAction {
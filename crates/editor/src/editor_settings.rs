@@ -17,9 +17,12 @@ use ui::scrollbars::{ScrollbarVisibility, ShowScrollbar};
 #[derive(Clone, RegisterSetting)]
 pub struct EditorSettings {
     pub cursor_blink: bool,
+    pub cursor_blink_interval: DelayMs,
+    pub smooth_caret: bool,
     pub cursor_shape: Option<CursorShape>,
     pub current_line_highlight: CurrentLineHighlight,
     pub selection_highlight: bool,
+    pub selection_highlight_min_length: usize,
     pub rounded_selection: bool,
     pub lsp_highlight_debounce: DelayMs,
     pub hover_popover_enabled: bool,
@@ -72,6 +75,7 @@ pub struct Jupyter {
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct StickyScroll {
     pub enabled: bool,
+    pub max_lines: usize,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -129,6 +133,7 @@ pub struct Gutter {
     pub line_numbers: bool,
     pub runnables: bool,
     pub breakpoints: bool,
+    pub bookmarks: bool,
     pub folds: bool,
 }
 
@@ -204,9 +209,12 @@ impl Settings for EditorSettings {
         let sticky_scroll = editor.sticky_scroll.unwrap();
         Self {
             cursor_blink: editor.cursor_blink.unwrap(),
+            cursor_blink_interval: editor.cursor_blink_interval.unwrap(),
+            smooth_caret: editor.smooth_caret.unwrap(),
             cursor_shape: editor.cursor_shape.map(Into::into),
             current_line_highlight: editor.current_line_highlight.unwrap(),
             selection_highlight: editor.selection_highlight.unwrap(),
+            selection_highlight_min_length: editor.selection_highlight_min_length.unwrap(),
             rounded_selection: editor.rounded_selection.unwrap(),
             lsp_highlight_debounce: editor.lsp_highlight_debounce.unwrap(),
             hover_popover_enabled: editor.hover_popover_enabled.unwrap(),
@@ -251,6 +259,7 @@ impl Settings for EditorSettings {
                 line_numbers: gutter.line_numbers.unwrap(),
                 runnables: gutter.runnables.unwrap(),
                 breakpoints: gutter.breakpoints.unwrap(),
+                bookmarks: gutter.bookmarks.unwrap(),
                 folds: gutter.folds.unwrap(),
             },
             scroll_beyond_last_line: editor.scroll_beyond_last_line.unwrap(),
@@ -261,6 +270,7 @@ impl Settings for EditorSettings {
             fast_scroll_sensitivity: editor.fast_scroll_sensitivity.unwrap(),
             sticky_scroll: StickyScroll {
                 enabled: sticky_scroll.enabled.unwrap(),
+                max_lines: sticky_scroll.max_lines.unwrap() as usize,
             },
             relative_line_numbers: editor.relative_line_numbers.unwrap(),
             seed_search_query_from_cursor: editor.seed_search_query_from_cursor.unwrap(),
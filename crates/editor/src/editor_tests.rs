@@ -17,7 +17,7 @@ use buffer_diff::{BufferDiff, DiffHunkSecondaryStatus, DiffHunkStatus, DiffHunkS
 use collections::HashMap;
 use futures::{StreamExt, channel::oneshot};
 use gpui::{
-    BackgroundExecutor, DismissEvent, TestAppContext, UpdateGlobal, VisualTestContext,
+    BackgroundExecutor, DismissEvent, ImageFormat, TestAppContext, UpdateGlobal, VisualTestContext,
     WindowBounds, WindowOptions, div,
 };
 use indoc::indoc;
@@ -4958,6 +4958,46 @@ fn test_join_lines_with_multi_selection(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_align_selections(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state(indoc! {"
+        let aˇ = 1;
+        let bbbˇ = 2;
+        let ccˇ = 3;
+    "});
+    cx.update_editor(|editor, window, cx| {
+        editor.align_selections(&AlignSelections::default(), window, cx);
+    });
+    cx.assert_editor_state(indoc! {"
+        let a  ˇ = 1;
+        let bbbˇ = 2;
+        let cc ˇ = 3;
+    "});
+
+    cx.set_state(indoc! {"
+        «a = 1,
+        bbb = 22,
+        cc = 3,ˇ»
+    "});
+    cx.update_editor(|editor, window, cx| {
+        editor.align_selections(
+            &AlignSelections {
+                delimiter: Some("=".to_string()),
+            },
+            window,
+            cx,
+        );
+    });
+    cx.assert_editor_state(indoc! {"
+        «a   = 1,
+        bbb = 22,
+        cc  = 3,ˇ»
+    "});
+}
+
 #[gpui::test]
 async fn test_join_lines_with_git_diff_base(executor: BackgroundExecutor, cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -5245,6 +5285,36 @@ async fn test_manipulate_immutable_lines_with_single_selection(cx: &mut TestAppC
         123ˇ»
     "});
 
+    // Test sort_lines_descending()
+    cx.set_state(indoc! {"
+        «b
+        c
+        a
+        Cˇ»
+    "});
+    cx.update_editor(|e, window, cx| e.sort_lines_descending(&SortLinesDescending, window, cx));
+    cx.assert_editor_state(indoc! {"
+        «c
+        b
+        a
+        Cˇ»
+    "});
+
+    // Test sort_lines_naturally()
+    cx.set_state(indoc! {"
+        «item10
+        item2
+        Item1
+        item1ˇ»
+    "});
+    cx.update_editor(|e, window, cx| e.sort_lines_naturally(&SortLinesNaturally, window, cx));
+    cx.assert_editor_state(indoc! {"
+        «item1
+        Item1
+        item2
+        item10ˇ»
+    "});
+
     // Test reverse_lines()
     cx.set_state(indoc! {"
         «5
@@ -5267,7 +5337,7 @@ async fn test_manipulate_immutable_lines_with_single_selection(cx: &mut TestAppC
     // From here on out, test more complex cases of manipulate_immutable_lines() with a single driver method: sort_lines_case_sensitive()
     // Since all methods calling manipulate_immutable_lines() are doing the exact same general thing (reordering lines)
 
-    // Don't manipulate when cursor is on single line, but expand the selection
+    // Manipulate the whole buffer when there is only a cursor, selecting it
     cx.set_state(indoc! {"
         ddˇdd
         ccc
@@ -5277,10 +5347,27 @@ async fn test_manipulate_immutable_lines_with_single_selection(cx: &mut TestAppC
     cx.update_editor(|e, window, cx| {
         e.sort_lines_case_sensitive(&SortLinesCaseSensitive, window, cx)
     });
+    cx.assert_editor_state(indoc! {"
+        «a
+        bb
+        ccc
+        ddddˇ»
+    "});
+
+    // Don't manipulate the whole buffer when there are several cursors
+    cx.set_state(indoc! {"
+        ddˇdd
+        ccc
+        bˇb
+        a
+    "});
+    cx.update_editor(|e, window, cx| {
+        e.sort_lines_case_sensitive(&SortLinesCaseSensitive, window, cx)
+    });
     cx.assert_editor_state(indoc! {"
         «ddddˇ»
         ccc
-        bb
+        «bbˇ»
         a
     "});
 
@@ -7688,6 +7775,70 @@ async fn test_cut_line_ends(cx: &mut TestAppContext) {
         fox jumps overˇthe lazy dog"});
 }
 
+#[gpui::test]
+async fn test_kill_ring(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    // Consecutive kills append to the same kill ring entry.
+    cx.set_state(indoc! {"
+        ˇone
+        two
+        three"});
+    cx.update_editor(|e, window, cx| {
+        e.kill_ring_cut(&KillRingCut, window, cx);
+        e.kill_ring_cut(&KillRingCut, window, cx);
+        e.kill_ring_cut(&KillRingCut, window, cx);
+        e.kill_ring_cut(&KillRingCut, window, cx);
+    });
+    cx.assert_editor_state("ˇthree");
+    cx.update_editor(|e, window, cx| e.kill_ring_yank(&KillRingYank, window, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        two
+        ˇthree"});
+
+    // A kill after moving starts a new entry, and yank pop cycles through them.
+    cx.update_editor(|e, window, cx| {
+        e.move_to_end_of_line(&Default::default(), window, cx);
+        e.handle_input(" four", window, cx);
+        e.move_to_beginning_of_line(&Default::default(), window, cx);
+        e.kill_ring_cut(&KillRingCut, window, cx);
+    });
+    cx.assert_editor_state(indoc! {"
+        one
+        two
+        ˇ"});
+    cx.update_editor(|e, window, cx| e.kill_ring_yank(&KillRingYank, window, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        two
+        three fourˇ"});
+    cx.update_editor(|e, window, cx| e.kill_ring_yank_pop(&KillRingYankPop, window, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        two
+        one
+        two
+        ˇ"});
+    cx.update_editor(|e, window, cx| e.kill_ring_yank_pop(&KillRingYankPop, window, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        two
+        three fourˇ"});
+
+    // Yank pop does nothing unless it follows a yank.
+    cx.update_editor(|e, window, cx| {
+        e.handle_input("!", window, cx);
+        e.kill_ring_yank_pop(&KillRingYankPop, window, cx);
+    });
+    cx.assert_editor_state(indoc! {"
+        one
+        two
+        three four!ˇ"});
+}
+
 #[gpui::test]
 async fn test_clipboard(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -8056,6 +8207,44 @@ async fn test_clipboard_line_numbers_from_multibuffer(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_paste_into_block_selection(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.write_to_clipboard(ClipboardItem::new_string("one\ntwo\nthree\n".into()));
+
+    // Each row of the block gets one line of the clipboard text.
+    cx.set_state(indoc! {"
+        aˇa
+        b«bˇ»b
+        cˇc
+    "});
+    cx.update_editor(|e, window, cx| e.paste(&Paste, window, cx));
+    cx.assert_editor_state(indoc! {"
+        aoneˇa
+        btwoˇb
+        cthreeˇc
+    "});
+
+    // With more rows than lines, the entire clipboard text is pasted on every row.
+    cx.write_to_clipboard(ClipboardItem::new_string("x\ny".into()));
+    cx.set_state(indoc! {"
+        ˇa
+        ˇb
+        ˇc
+    "});
+    cx.update_editor(|e, window, cx| e.paste(&Paste, window, cx));
+    cx.assert_editor_state(indoc! {"
+        x
+        yˇa
+        x
+        yˇb
+        x
+        yˇc
+    "});
+}
+
 #[gpui::test]
 async fn test_paste_multiline(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -18040,6 +18229,46 @@ fn test_highlighted_ranges(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_word_under_cursor_occurrence_highlights(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state("let ˇfoo = foo + food + Foo;\nfoo();");
+    cx.executor()
+        .advance_clock(SELECTION_HIGHLIGHT_DEBOUNCE_TIMEOUT);
+    cx.run_until_parked();
+    cx.assert_editor_background_highlights(
+        HighlightKey::SelectedTextHighlight,
+        "let foo = «foo» + food + «Foo»;\n«foo»();",
+    );
+
+    cx.set_state("let foo = foo + food + Foo;ˇ\nfoo();");
+    cx.executor()
+        .advance_clock(SELECTION_HIGHLIGHT_DEBOUNCE_TIMEOUT);
+    cx.run_until_parked();
+    cx.assert_editor_background_highlights(
+        HighlightKey::SelectedTextHighlight,
+        "let foo = foo + food + Foo;\nfoo();",
+    );
+
+    cx.update(|_, cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings.editor.selection_highlight_min_length = Some(4);
+            });
+        });
+    });
+    cx.set_state("let ˇfoo = foo + food + Foo;\nfoo();");
+    cx.executor()
+        .advance_clock(SELECTION_HIGHLIGHT_DEBOUNCE_TIMEOUT);
+    cx.run_until_parked();
+    cx.assert_editor_background_highlights(
+        HighlightKey::SelectedTextHighlight,
+        "let foo = foo + food + Foo;\nfoo();",
+    );
+}
+
 #[gpui::test]
 async fn test_following(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -19139,6 +19368,8 @@ async fn test_language_server_restart_due_to_settings_change(cx: &mut TestAppCon
                 })),
                 enable_lsp_tasks: false,
                 fetch: None,
+                languages: None,
+                workspace_folders: None,
             },
         );
     });
@@ -19160,6 +19391,8 @@ async fn test_language_server_restart_due_to_settings_change(cx: &mut TestAppCon
                 })),
                 enable_lsp_tasks: false,
                 fetch: None,
+                languages: None,
+                workspace_folders: None,
             },
         );
     });
@@ -19181,6 +19414,8 @@ async fn test_language_server_restart_due_to_settings_change(cx: &mut TestAppCon
                 })),
                 enable_lsp_tasks: false,
                 fetch: None,
+                languages: None,
+                workspace_folders: None,
             },
         );
     });
@@ -19200,6 +19435,8 @@ async fn test_language_server_restart_due_to_settings_change(cx: &mut TestAppCon
                 initialization_options: None,
                 enable_lsp_tasks: false,
                 fetch: None,
+                languages: None,
+                workspace_folders: None,
             },
         );
     });
@@ -19303,6 +19540,62 @@ async fn test_completions_with_additional_edits(cx: &mut TestAppContext) {
     cx.assert_editor_state("fn main() { let a = Some(2)ˇ; }");
 }
 
+#[gpui::test]
+async fn test_completions_without_auto_import(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.completions = Some(CompletionSettingsContent {
+            auto_import: Some(false),
+            ..Default::default()
+        });
+    });
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            completion_provider: Some(lsp::CompletionOptions {
+                trigger_characters: Some(vec![".".to_string()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    cx.set_state("fn main() { let a = 2ˇ; }");
+    cx.simulate_keystroke(".");
+    let mut request =
+        cx.set_request_handler::<lsp::request::Completion, _, _>(move |_, _, _| async move {
+            Ok(Some(lsp::CompletionResponse::Array(vec![
+                lsp::CompletionItem {
+                    label: "some".into(),
+                    text_edit: Some(lsp::CompletionTextEdit::Edit(lsp::TextEdit {
+                        range: lsp::Range::new(
+                            lsp::Position::new(0, 22),
+                            lsp::Position::new(0, 22),
+                        ),
+                        new_text: "Some(2)".to_string(),
+                    })),
+                    additional_text_edits: Some(vec![lsp::TextEdit {
+                        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 0)),
+                        new_text: "use std::option::Option::Some;\n".to_string(),
+                    }]),
+                    ..Default::default()
+                },
+            ])))
+        });
+
+    request.next().await;
+    cx.condition(|editor, _| editor.context_menu_visible())
+        .await;
+    let apply_additional_edits = cx.update_editor(|editor, window, cx| {
+        editor
+            .confirm_completion(&ConfirmCompletion::default(), window, cx)
+            .unwrap()
+    });
+    apply_additional_edits.await.unwrap();
+    cx.assert_editor_state("fn main() { let a = 2.Some(2)ˇ; }");
+}
+
 #[gpui::test]
 async fn test_completions_resolve_updates_labels_if_filter_text_matches(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -22150,6 +22443,87 @@ async fn test_stage_and_unstage_added_file_hunk(
     cx.assert_index_text(None);
 }
 
+#[gpui::test]
+async fn test_stage_and_unstage_selected_lines(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let head_text = indoc! {"
+        one
+        four
+    "};
+    cx.set_head_text(head_text);
+    cx.set_index_text(head_text);
+    cx.set_state(indoc! {"
+        one
+        «twoˇ»
+        three
+        four
+    "});
+    cx.run_until_parked();
+
+    cx.update_editor(|editor, window, cx| {
+        editor.toggle_staged_selected_diff_hunks(&Default::default(), window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_index_text(Some(indoc! {"
+        one
+        two
+        four
+    "}));
+
+    cx.update_editor(|editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(1, 0)..Point::new(3, 0)])
+        });
+        editor.toggle_staged_selected_diff_hunks(&Default::default(), window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_index_text(Some(indoc! {"
+        one
+        two
+        three
+        four
+    "}));
+}
+
+#[gpui::test]
+async fn test_stage_selected_lines_and_hunks_together(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let head_text = indoc! {"
+        one
+        four
+        seven
+    "};
+    cx.set_head_text(head_text);
+    cx.set_index_text(head_text);
+    cx.set_state(indoc! {"
+        one
+        «twoˇ»
+        three
+        four
+        fiˇve
+        six
+        seven
+    "});
+    cx.run_until_parked();
+
+    cx.update_editor(|editor, window, cx| {
+        editor.toggle_staged_selected_diff_hunks(&Default::default(), window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_index_text(Some(indoc! {"
+        one
+        two
+        four
+        five
+        six
+        seven
+    "}));
+}
+
 async fn setup_indent_guides_editor(
     text: &str,
     cx: &mut TestAppContext,
@@ -22794,6 +23168,58 @@ async fn test_active_indent_guide_non_matching_indent(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_active_indent_guide_follows_syntax_scope(cx: &mut TestAppContext) {
+    let (buffer_id, mut cx) = setup_indent_guides_editor(
+        &"
+    fn main() {
+        if 1 == 2 {
+            let a = 1;
+        }
+    }"
+        .unindent(),
+        cx,
+    )
+    .await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
+    cx.run_until_parked();
+
+    // By indentation alone, the closing brace would be in the function's scope.
+    cx.update_editor(|editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(3, 0)..Point::new(3, 0)])
+        });
+    });
+    cx.run_until_parked();
+
+    assert_indent_guides(
+        0..4,
+        vec![
+            indent_guide(buffer_id, 1, 3, 0),
+            indent_guide(buffer_id, 2, 2, 1),
+        ],
+        Some(vec![1]),
+        &mut cx,
+    );
+
+    cx.update_editor(|editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(4, 0)..Point::new(4, 0)])
+        });
+    });
+    cx.run_until_parked();
+
+    assert_indent_guides(
+        0..4,
+        vec![
+            indent_guide(buffer_id, 1, 3, 0),
+            indent_guide(buffer_id, 2, 2, 1),
+        ],
+        Some(vec![0]),
+        &mut cx,
+    );
+}
+
 #[gpui::test]
 async fn test_indent_guide_with_expanded_diff_hunks(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -23615,6 +24041,56 @@ async fn test_goto_definition_with_find_all_references_fallback(cx: &mut TestApp
     });
 }
 
+#[gpui::test]
+async fn test_peek_definition(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            definition_provider: Some(lsp::OneOf::Left(true)),
+            ..lsp::ServerCapabilities::default()
+        },
+        cx,
+    )
+    .await;
+    let original_state = r#"fn one() {
+        let mut a = ˇtwo();
+    }
+
+    fn two() {}"#
+        .unindent();
+    cx.set_state(&original_state);
+    cx.lsp
+        .set_request_handler::<lsp::request::GotoDefinition, _, _>(move |params, _| async move {
+            Ok(Some(lsp::GotoDefinitionResponse::Scalar(lsp::Location {
+                uri: params.text_document_position_params.text_document.uri,
+                range: lsp::Range::new(lsp::Position::new(4, 3), lsp::Position::new(4, 6)),
+            })))
+        });
+
+    cx.update_editor(|editor, window, cx| editor.peek_definition(&PeekDefinition, window, cx));
+    cx.run_until_parked();
+
+    // The definition is shown below the cursor, which doesn't move.
+    cx.assert_editor_state(&original_state);
+    let peek_text = cx
+        .update_editor(|editor, _, cx| {
+            let peek_editor = editor.peek_editor()?;
+            Some(peek_editor.read(cx).text(cx))
+        })
+        .expect("Should have peeked the definition");
+    assert!(peek_text.contains("fn two() {}"));
+    let editors = cx.update_workspace(|workspace, _, cx| {
+        workspace.items_of_type::<Editor>(cx).collect::<Vec<_>>()
+    });
+    assert_eq!(editors.len(), 1, "Peeking should not open another editor");
+
+    // The peek editor is focused, so cancelling in it closes the peek.
+    cx.dispatch_action(Cancel);
+    cx.update_editor(|editor, _, _| {
+        assert!(editor.peek_editor().is_none());
+    });
+}
+
 #[gpui::test]
 async fn test_goto_definition_no_fallback(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -28322,6 +28798,29 @@ async fn test_insert_snippet(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_insert_snippet_with_variables(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state("one\n«twoˇ» three");
+    cx.update_editor(|editor, window, cx| {
+        cx.write_to_clipboard(ClipboardItem::new_string("pasted".to_string()));
+        editor.insert_snippet_at_selections(
+            &InsertSnippet {
+                language: None,
+                name: None,
+                snippet: Some(
+                    "${TM_SELECTED_TEXT/(.*)/${1:/upcase}/} $TM_LINE_NUMBER $CLIPBOARD".to_string(),
+                ),
+            },
+            window,
+            cx,
+        );
+    });
+    cx.assert_editor_state("one\nTWO 2 pastedˇ three");
+}
+
 #[gpui::test]
 async fn test_inlay_hints_request_timeout(cx: &mut TestAppContext) {
     use crate::inlays::inlay_hints::InlayHintRefreshReason;
@@ -28773,6 +29272,39 @@ async fn test_paste_url_from_other_app_creates_markdown_link_over_selected_text(
     ));
 }
 
+#[gpui::test]
+async fn test_paste_image_into_markdown_saves_it_next_to_the_file(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let markdown_language = Arc::new(Language::new(
+        LanguageConfig {
+            name: "Markdown".into(),
+            ..LanguageConfig::default()
+        },
+        None,
+    ));
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(markdown_language), cx));
+    cx.set_state("See: ˇ");
+
+    let image = Image::from_bytes(ImageFormat::Png, vec![1, 2, 3]);
+    cx.update_editor(|editor, window, cx| {
+        cx.write_to_clipboard(ClipboardItem::new_image(&image));
+        editor.paste(&Paste, window, cx);
+    });
+    cx.run_until_parked();
+
+    cx.assert_editor_state("See: ![](image.png)ˇ");
+    let fs = cx.update_editor(|editor, _, cx| editor.project().unwrap().read(cx).fs().clone());
+    assert_eq!(
+        fs.load_bytes(path!("/root/image.png").as_ref())
+            .await
+            .unwrap(),
+        vec![1, 2, 3]
+    );
+}
+
 #[gpui::test]
 async fn test_markdown_indents(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -29368,7 +29900,7 @@ async fn test_sticky_scroll(cx: &mut TestAppContext) {
         });
         cx.run_until_parked();
         cx.update_editor(|e, window, cx| {
-            EditorElement::sticky_headers(&e, &e.snapshot(window, cx))
+            EditorElement::sticky_headers(&e, &e.snapshot(window, cx), usize::MAX)
                 .into_iter()
                 .map(
                     |StickyHeader {
@@ -29408,6 +29940,49 @@ async fn test_sticky_scroll(cx: &mut TestAppContext) {
     assert_eq!(sticky_headers(10.0), vec![]);
 }
 
+#[gpui::test]
+async fn test_sticky_scroll_max_lines(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state(indoc! {"
+        ˇimpl Bar {
+            fn new() -> Self {
+                Self
+            }
+        }
+    "});
+    cx.update_editor(|e, _, cx| {
+        e.buffer()
+            .read(cx)
+            .as_singleton()
+            .unwrap()
+            .update(cx, |buffer, cx| {
+                buffer.set_language(Some(rust_lang()), cx);
+            })
+    });
+
+    let mut sticky_headers = |max_lines: usize| {
+        cx.update_editor(|e, window, cx| {
+            e.scroll(gpui::Point { x: 0., y: 1.5 }, None, window, cx);
+        });
+        cx.run_until_parked();
+        cx.update_editor(|e, window, cx| {
+            EditorElement::sticky_headers(&e, &e.snapshot(window, cx), max_lines)
+                .into_iter()
+                .map(|StickyHeader { start_point, .. }| start_point)
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let impl_bar = Point { row: 0, column: 0 };
+    let fn_new = Point { row: 1, column: 4 };
+    assert_eq!(sticky_headers(2), vec![impl_bar, fn_new]);
+    // Only the outermost scopes are kept.
+    assert_eq!(sticky_headers(1), vec![impl_bar]);
+    assert_eq!(sticky_headers(0), vec![]);
+}
+
 #[gpui::test]
 async fn test_sticky_scroll_with_expanded_deleted_diff_hunks(
     executor: BackgroundExecutor,
@@ -29471,7 +30046,7 @@ async fn test_sticky_scroll_with_expanded_deleted_diff_hunks(
         });
         cx.run_until_parked();
         cx.update_editor(|e, window, cx| {
-            EditorElement::sticky_headers(&e, &e.snapshot(window, cx))
+            EditorElement::sticky_headers(&e, &e.snapshot(window, cx), usize::MAX)
                 .into_iter()
                 .map(
                     |StickyHeader {
@@ -29630,6 +30205,7 @@ async fn test_scroll_by_clicking_sticky_header(cx: &mut TestAppContext) {
             store.update_user_settings(cx, |settings| {
                 settings.editor.sticky_scroll = Some(settings::StickyScrollContent {
                     enabled: Some(true),
+                    max_lines: None,
                 })
             });
         });
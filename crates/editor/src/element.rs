@@ -34,7 +34,7 @@ use crate::{
     },
 };
 use buffer_diff::{DiffHunkStatus, DiffHunkStatusKind};
-use collections::{BTreeMap, HashMap};
+use collections::{BTreeMap, HashMap, HashSet};
 use feature_flags::{DiffReviewFeatureFlag, FeatureFlagAppExt as _};
 use file_icons::FileIcons;
 use git::{Oid, blame::BlameEntry, commit::ParsedCommitMessage, status::FileStatus};
@@ -45,11 +45,11 @@ use gpui::{
     GlobalElementId, Hitbox, HitboxBehavior, Hsla, InteractiveElement, IntoElement, IsZero,
     KeybindingKeystroke, Length, Modifiers, ModifiersChangedEvent, MouseButton, MouseClickEvent,
     MouseDownEvent, MouseMoveEvent, MousePressureEvent, MouseUpEvent, PaintQuad, ParentElement,
-    Pixels, PressureStage, ScrollDelta, ScrollHandle, ScrollWheelEvent, ShapedLine, SharedString,
-    Size, StatefulInteractiveElement, Style, Styled, StyledText, TextAlign, TextRun,
-    TextStyleRefinement, WeakEntity, Window, anchored, checkerboard, deferred, div, fill,
-    linear_color_stop, linear_gradient, outline, point, px, quad, relative, size, solid_background,
-    transparent_black,
+    PinchEvent, Pixels, PressureStage, ScrollDelta, ScrollHandle, ScrollWheelEvent, ShapedLine,
+    SharedString, Size, StatefulInteractiveElement, Style, Styled, StyledText, TextAlign, TextRun,
+    TextStyleRefinement, WeakEntity, Window, accesskit, anchored, checkerboard, deferred, div,
+    fill, linear_color_stop, linear_gradient, outline, point, px, quad, relative, size,
+    solid_background, transparent_black,
 };
 use itertools::Itertools;
 use language::{IndentGuideSettings, language_settings::ShowWhitespaceSetting};
@@ -62,7 +62,7 @@ use multi_buffer::{
 use edit_prediction_types::EditPredictionGranularity;
 
 use project::{
-    DisableAiSettings, Entry, ProjectPath,
+    DisableAiSettings, Entry, Project, ProjectPath, Worktree,
     debugger::breakpoint_store::{Breakpoint, BreakpointSessionState},
     project_settings::ProjectSettings,
 };
@@ -89,12 +89,12 @@ use text::{BufferId, SelectionGoal};
 use theme::{ActiveTheme, Appearance, BufferLineHeight, PlayerColor};
 use ui::utils::ensure_minimum_contrast;
 use ui::{
-    ButtonLike, ContextMenu, Indicator, KeyBinding, POPOVER_Y_PADDING, Tooltip, prelude::*,
-    right_click_menu, scrollbars::ShowScrollbar, text_for_keystroke,
+    ButtonLike, ContextMenu, Indicator, KeyBinding, POPOVER_Y_PADDING, PopoverMenu, Tooltip,
+    prelude::*, right_click_menu, scrollbars::ShowScrollbar, text_for_keystroke,
 };
 use unicode_segmentation::UnicodeSegmentation;
 use util::post_inc;
-use util::{RangeExt, ResultExt, debug_panic};
+use util::{RangeExt, ResultExt, debug_panic, rel_path::RelPath};
 use workspace::{
     CollaboratorId, ItemHandle, ItemSettings, OpenInTerminal, OpenTerminal, RevealInProjectPanel,
     Workspace,
@@ -263,6 +263,8 @@ impl EditorElement {
         register_action(editor, window, Editor::sort_lines_by_length);
         register_action(editor, window, Editor::sort_lines_case_sensitive);
         register_action(editor, window, Editor::sort_lines_case_insensitive);
+        register_action(editor, window, Editor::sort_lines_descending);
+        register_action(editor, window, Editor::sort_lines_naturally);
         register_action(editor, window, Editor::reverse_lines);
         register_action(editor, window, Editor::shuffle_lines);
         register_action(editor, window, Editor::rotate_selections_forward);
@@ -298,6 +300,7 @@ impl EditorElement {
         register_action(editor, window, Editor::cut);
         register_action(editor, window, Editor::kill_ring_cut);
         register_action(editor, window, Editor::kill_ring_yank);
+        register_action(editor, window, Editor::kill_ring_yank_pop);
         register_action(editor, window, Editor::copy);
         register_action(editor, window, Editor::copy_and_trim);
         register_action(editor, window, Editor::diff_clipboard_with_selection);
@@ -535,6 +538,12 @@ impl EditorElement {
         register_action(editor, window, Editor::cancel_edit_review_comment_action);
         register_action(editor, window, Editor::go_to_previous_change);
         register_action(editor, window, Editor::go_to_next_change);
+        register_action(editor, window, Editor::go_to_previous_edit_location);
+        register_action(editor, window, Editor::go_to_next_edit_location);
+        register_action(editor, window, Editor::show_edit_location_history);
+        register_action(editor, window, Editor::peek_definition);
+        register_action(editor, window, Editor::peek_implementation);
+        register_action(editor, window, Editor::peek_references);
         register_action(editor, window, Editor::go_to_prev_reference);
         register_action(editor, window, Editor::go_to_next_reference);
 
@@ -611,6 +620,13 @@ impl EditorElement {
                 cx.propagate();
             }
         });
+        register_action(editor, window, |editor, action, window, cx| {
+            if let Some(task) = editor.confirm_rename_with_preview(action, window, cx) {
+                editor.detach_and_notify_err(task, window, cx);
+            } else {
+                cx.propagate();
+            }
+        });
         register_action(editor, window, |editor, action, window, cx| {
             if let Some(task) = editor.find_all_references(action, window, cx) {
                 task.detach_and_log_err(cx);
@@ -641,8 +657,12 @@ impl EditorElement {
         register_action(editor, window, Editor::spawn_nearest_task);
         register_action(editor, window, Editor::insert_uuid_v4);
         register_action(editor, window, Editor::insert_uuid_v7);
+        register_action(editor, window, Editor::align_selections);
         register_action(editor, window, Editor::open_selections_in_multibuffer);
         register_action(editor, window, Editor::toggle_breakpoint);
+        register_action(editor, window, Editor::toggle_bookmark);
+        register_action(editor, window, Editor::go_to_next_bookmark);
+        register_action(editor, window, Editor::go_to_previous_bookmark);
         register_action(editor, window, Editor::edit_log_breakpoint);
         register_action(editor, window, Editor::enable_breakpoint);
         register_action(editor, window, Editor::disable_breakpoint);
@@ -1019,7 +1039,14 @@ impl EditorElement {
 
             #[cfg(any(target_os = "linux", target_os = "freebsd"))]
             if EditorSettings::get_global(cx).middle_click_paste {
-                if let Some(text) = cx.read_from_primary().and_then(|item| item.text()) {
+                if let Some(item) = cx.read_from_primary() {
+                    let (text, clipboard_selections) = match item.entries() {
+                        [gpui::ClipboardEntry::String(clipboard_string)] => (
+                            clipboard_string.text().clone(),
+                            clipboard_string.metadata_json::<Vec<crate::ClipboardSelection>>(),
+                        ),
+                        _ => (item.text().unwrap_or_default(), None),
+                    };
                     let point_for_position = position_map.point_for_position(event.position);
                     let position = point_for_position.previous_valid;
 
@@ -1032,7 +1059,7 @@ impl EditorElement {
                         window,
                         cx,
                     );
-                    editor.insert(&text, window, cx);
+                    editor.do_paste(&text, clipboard_selections, false, window, cx);
                 }
                 cx.stop_propagation()
             }
@@ -1161,6 +1188,7 @@ impl EditorElement {
                     drop_cursor.end = drop_anchor;
                     *hide_drop_cursor = !text_hitbox.is_hovered(window);
                     editor.apply_scroll_delta(scroll_delta, window, cx);
+                    editor.autoscroll_selection_drag(event.position, scroll_delta, window, cx);
                     cx.notify();
                 }
                 SelectionDragState::ReadyToDrag {
@@ -1186,8 +1214,10 @@ impl EditorElement {
                             selection: selection.clone(),
                             drop_cursor,
                             hide_drop_cursor: false,
+                            autoscroll_task: None,
                         };
                         editor.apply_scroll_delta(scroll_delta, window, cx);
+                        editor.autoscroll_selection_drag(event.position, scroll_delta, window, cx);
                         cx.notify();
                     } else {
                         let click_point = position_map.point_for_position(*click_position);
@@ -1606,6 +1636,7 @@ impl EditorElement {
                     ref selection,
                     ref drop_cursor,
                     ref hide_drop_cursor,
+                    ..
                 } = editor.selection_drag_state
                     && !hide_drop_cursor
                     && (drop_cursor
@@ -1797,6 +1828,11 @@ impl EditorElement {
             let mut cursors = Vec::new();
 
             let show_local_cursors = editor.show_local_cursors(window, cx);
+            let smooth_caret = EditorSettings::get_global(cx).smooth_caret
+                && !cx.accessibility_preferences().reduce_motion;
+            if !smooth_caret {
+                editor.caret_animation = None;
+            }
 
             for (player_color, selections) in selections {
                 for selection in selections {
@@ -1889,10 +1925,28 @@ impl EditorElement {
                         }
                     }
 
-                    let x = cursor_character_x - scroll_pixel_position.x.into();
-                    let y = ((cursor_position.row().as_f64() - scroll_position.y)
+                    let mut x = cursor_character_x - scroll_pixel_position.x.into();
+                    let mut y = ((cursor_position.row().as_f64() - scroll_position.y)
                         * ScrollPixelOffset::from(line_height))
                     .into();
+                    if smooth_caret && selection.is_newest && selection.is_local {
+                        let target = point(
+                            f64::from(cursor_character_x),
+                            cursor_position.row().as_f64() * ScrollPixelOffset::from(line_height),
+                        );
+                        let (position, animating) = CaretAnimation::advance(
+                            &mut editor.caret_animation,
+                            target,
+                            f64::from(text_hitbox.size.height),
+                            Instant::now(),
+                        );
+                        if animating {
+                            window.request_animation_frame();
+                        }
+                        x = (position.x - scroll_pixel_position.x).into();
+                        y = (position.y - scroll_position.y * ScrollPixelOffset::from(line_height))
+                            .into();
+                    }
                     if selection.is_newest {
                         editor.pixel_position_of_newest_cursor = Some(point(
                             text_hitbox.origin.x + x + block_width / 2.,
@@ -2150,6 +2204,14 @@ impl EditorElement {
         minimap_editor.update(cx, |editor, cx| {
             editor.set_scroll_position(point(0., minimap_scroll_top), window, cx)
         });
+        let markers = self.layout_minimap_markers(
+            snapshot,
+            minimap_bounds,
+            minimap_line_height,
+            minimap_scroll_top,
+            minimap_lines,
+            cx,
+        );
 
         // Required for the drop shadow to be visible
         const PADDING_OFFSET: Pixels = px(4.);
@@ -2173,6 +2235,7 @@ impl EditorElement {
 
         Some(MinimapLayout {
             minimap,
+            markers,
             thumb_layout: layout,
             thumb_border_style: minimap_settings.thumb_border,
             minimap_line_height,
@@ -2181,6 +2244,79 @@ impl EditorElement {
         })
     }
 
+    /// Marks the search matches and diagnostics among the rows the minimap shows.
+    fn layout_minimap_markers(
+        &self,
+        snapshot: &EditorSnapshot,
+        minimap_bounds: Bounds<Pixels>,
+        minimap_line_height: Pixels,
+        minimap_scroll_top: ScrollOffset,
+        minimap_lines: f64,
+        cx: &App,
+    ) -> Vec<PaintQuad> {
+        let display_snapshot = &snapshot.display_snapshot;
+        let buffer_snapshot = snapshot.buffer_snapshot();
+        let max_row = snapshot.max_point().row();
+        let start_row = DisplayRow(minimap_scroll_top.floor() as u32).min(max_row);
+        let end_row = DisplayRow((minimap_scroll_top + minimap_lines).ceil() as u32).min(max_row);
+        let start = DisplayPoint::new(start_row, 0).to_point(display_snapshot);
+        let end = DisplayPoint::new(end_row, snapshot.line_len(end_row)).to_point(display_snapshot);
+
+        let rows_bounds = |rows: Range<DisplayRow>, left: Pixels| {
+            let row_top = |row: DisplayRow| {
+                minimap_bounds.top()
+                    + minimap_line_height * (row.as_f64() - minimap_scroll_top) as f32
+            };
+            Bounds::from_corners(
+                point(left, row_top(rows.start)),
+                point(minimap_bounds.right(), row_top(rows.end.next_row())),
+            )
+        };
+
+        let mut markers = Vec::new();
+        let mut search_color = cx.theme().status().info;
+        search_color.fade_out(0.6);
+        let search_rows = self.editor.read(cx).background_highlight_rows_in_range(
+            HighlightKey::BufferSearchHighlights,
+            buffer_snapshot.anchor_before(start)..buffer_snapshot.anchor_after(end),
+            display_snapshot,
+        );
+        for rows in search_rows {
+            markers.push(fill(rows_bounds(rows, minimap_bounds.left()), search_color));
+        }
+
+        let diagnostics = buffer_snapshot
+            .diagnostics_in_range::<Point>(start..end)
+            // Paint the most severe diagnostics last.
+            .sorted_by_key(|diagnostic| std::cmp::Reverse(diagnostic.diagnostic.severity));
+        for diagnostic in diagnostics {
+            let color = match diagnostic.diagnostic.severity {
+                lsp::DiagnosticSeverity::ERROR => cx.theme().status().error,
+                lsp::DiagnosticSeverity::WARNING => cx.theme().status().warning,
+                lsp::DiagnosticSeverity::INFORMATION => cx.theme().status().info,
+                _ => cx.theme().status().hint,
+            };
+            let rows = diagnostic
+                .range
+                .start
+                .to_display_point(display_snapshot)
+                .row()
+                ..diagnostic
+                    .range
+                    .end
+                    .to_display_point(display_snapshot)
+                    .row();
+            markers.push(fill(
+                rows_bounds(
+                    rows,
+                    minimap_bounds.right() - MinimapLayout::DIAGNOSTIC_MARKER_WIDTH,
+                ),
+                color,
+            ));
+        }
+        markers
+    }
+
     fn get_minimap_line_height(
         &self,
         font_size: AbsoluteLength,
@@ -2206,6 +2342,16 @@ impl EditorElement {
         if minimap_settings.show == ShowMinimap::Auto && !scrollbars_shown {
             return None;
         }
+        if !self
+            .editor
+            .read(cx)
+            .buffer
+            .read(cx)
+            .language_settings(cx)
+            .show_minimap
+        {
+            return None;
+        }
 
         let minimap_font_size = self.editor.read_with(cx, |editor, cx| {
             editor.minimap().map(|minimap_editor| {
@@ -3159,6 +3305,80 @@ impl EditorElement {
         })
     }
 
+    fn layout_bookmarks(
+        &self,
+        line_height: Pixels,
+        range: Range<DisplayRow>,
+        scroll_position: gpui::Point<ScrollOffset>,
+        gutter_dimensions: &GutterDimensions,
+        gutter_hitbox: &Hitbox,
+        snapshot: &EditorSnapshot,
+        breakpoint_rows: &HashMap<DisplayRow, (Anchor, Breakpoint, Option<BreakpointSessionState>)>,
+        row_infos: &[RowInfo],
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<AnyElement> {
+        if self.split_side == Some(SplitSide::Left) {
+            return Vec::new();
+        }
+
+        self.editor.update(cx, |editor, cx| {
+            // Breakpoints and runnables take the bookmark's place in the gutter.
+            let runnable_rows = editor
+                .tasks
+                .values()
+                .map(|tasks| {
+                    tasks
+                        .offset
+                        .to_point(&snapshot.buffer_snapshot())
+                        .to_display_point(snapshot)
+                        .row()
+                })
+                .collect::<HashSet<_>>();
+            editor
+                .active_bookmarks(range.clone(), window, cx)
+                .into_iter()
+                .filter_map(|(display_row, bookmark)| {
+                    if range.start > display_row
+                        || range.end < display_row
+                        || breakpoint_rows.contains_key(&display_row)
+                        || runnable_rows.contains(&display_row)
+                    {
+                        return None;
+                    }
+                    if row_infos
+                        .get((display_row.0.saturating_sub(range.start.0)) as usize)
+                        .is_some_and(|row_info| {
+                            row_info.expand_info.is_some()
+                                || row_info
+                                    .diff_status
+                                    .is_some_and(|status| status.is_deleted())
+                        })
+                    {
+                        return None;
+                    }
+                    let row =
+                        MultiBufferRow(DisplayPoint::new(display_row, 0).to_point(snapshot).row);
+                    if snapshot.is_line_folded(row) {
+                        return None;
+                    }
+
+                    let button = editor.render_bookmark(display_row, &bookmark, cx);
+                    Some(prepaint_gutter_button(
+                        button.into_any_element(),
+                        display_row,
+                        line_height,
+                        gutter_dimensions,
+                        scroll_position,
+                        gutter_hitbox,
+                        window,
+                        cx,
+                    ))
+                })
+                .collect_vec()
+        })
+    }
+
     fn should_render_diff_review_button(
         &self,
         range: Range<DisplayRow>,
@@ -4488,7 +4708,8 @@ impl EditorElement {
             .show_line_numbers
             .unwrap_or_else(|| EditorSettings::get_global(cx).gutter.line_numbers);
 
-        let rows = Self::sticky_headers(self.editor.read(cx), snapshot);
+        let max_lines = EditorSettings::get_global(cx).sticky_scroll.max_lines;
+        let rows = Self::sticky_headers(self.editor.read(cx), snapshot, max_lines);
 
         let mut lines = Vec::<StickyHeaderLine>::new();
 
@@ -4557,7 +4778,11 @@ impl EditorElement {
         })
     }
 
-    pub(crate) fn sticky_headers(editor: &Editor, snapshot: &EditorSnapshot) -> Vec<StickyHeader> {
+    pub(crate) fn sticky_headers(
+        editor: &Editor,
+        snapshot: &EditorSnapshot,
+        max_lines: usize,
+    ) -> Vec<StickyHeader> {
         let scroll_top = snapshot.scroll_position().y;
 
         let mut end_rows = Vec::<DisplayRow>::new();
@@ -4587,6 +4812,9 @@ impl EditorElement {
                 end_rows.pop();
             }
             let depth = end_rows.len();
+            if depth >= max_lines {
+                continue;
+            }
             let adjusted_scroll_top = scroll_top + depth as f64;
 
             if sticky_row.as_f64() >= adjusted_scroll_top || end_row.as_f64() <= adjusted_scroll_top
@@ -6313,6 +6541,10 @@ impl EditorElement {
                 }
             });
 
+            for bookmark in layout.bookmarks.iter_mut() {
+                bookmark.paint(window, cx);
+            }
+
             for breakpoint in layout.breakpoints.iter_mut() {
                 breakpoint.paint(window, cx);
             }
@@ -7280,6 +7512,9 @@ impl EditorElement {
             window.paint_layer(layout.thumb_layout.hitbox.bounds, |window| {
                 window.with_element_namespace("minimap", |window| {
                     layout.minimap.paint(window, cx);
+                    for marker in layout.markers.drain(..) {
+                        window.paint_quad(marker);
+                    }
                     if let Some(thumb_bounds) = layout.thumb_layout.thumb_bounds {
                         let minimap_thumb_color = match layout.thumb_layout.thumb_state {
                             ScrollbarThumbState::Idle => {
@@ -7588,6 +7823,18 @@ impl EditorElement {
 
         self.paint_scroll_wheel_listener(layout, window, cx);
 
+        if layout.mode.is_full() {
+            window.on_mouse_event({
+                let hitbox = layout.hitbox.clone();
+                move |event: &PinchEvent, phase, window, cx| {
+                    if phase == DispatchPhase::Bubble && hitbox.is_hovered(window) {
+                        theme::adjust_buffer_font_size(cx, |size| size * (1. + event.delta));
+                        cx.stop_propagation();
+                    }
+                }
+            });
+        }
+
         window.on_mouse_event({
             let position_map = layout.position_map.clone();
             let editor = self.editor.clone();
@@ -7800,6 +8047,43 @@ pub fn render_breadcrumb_text(
 
     let element = h_flex().flex_grow().text_ui(cx);
 
+    let editor = active_item
+        .downcast::<Editor>()
+        .map(|editor| editor.downgrade());
+    // Multibuffer headers keep a single button toggling the outline, while each segment of the
+    // toolbar's breadcrumbs navigates on its own.
+    let navigable_editor = editor
+        .as_ref()
+        .filter(|_| !multibuffer_header)
+        .and_then(|editor| editor.upgrade());
+    let first_symbol_index = navigable_editor.as_ref().map_or(0, |editor| {
+        usize::from(editor.read(cx).buffer().read(cx).is_singleton())
+    });
+    let navigable_path = navigable_editor
+        .as_ref()
+        .filter(|_| first_symbol_index == 1)
+        .and_then(|editor| {
+            let project = editor.read(cx).project()?.clone();
+            let workspace = editor.read(cx).workspace()?.downgrade();
+            let project_path = active_item.project_path(cx)?;
+            let displayed_path = project_path
+                .path
+                .display(project.read(cx).path_style(cx))
+                .into_owned();
+            let root_prefix_len = segments
+                .first()?
+                .text
+                .strip_suffix(displayed_path.as_str())?
+                .len();
+            Some(BreadcrumbPath {
+                project,
+                workspace,
+                project_path,
+                root_prefix_len,
+            })
+        });
+
+    let mut segment_indices = (0..segments.len()).map(Some).collect::<Vec<_>>();
     let prefix_end_ix = cmp::min(segments.len(), MAX_SEGMENTS / 2);
     let suffix_start_ix = cmp::max(
         prefix_end_ix,
@@ -7815,30 +8099,73 @@ pub fn render_breadcrumb_text(
                 font: None,
             }),
         );
+        segment_indices.splice(prefix_end_ix..suffix_start_ix, Some(None));
     }
 
-    let highlighted_segments = segments.into_iter().enumerate().map(|(index, segment)| {
-        let mut text_style = window.text_style();
-        if let Some(ref font) = segment.font {
-            text_style.font_family = font.family.clone();
-            text_style.font_features = font.features.clone();
-            text_style.font_style = font.style;
-            text_style.font_weight = font.weight;
-        }
-        text_style.color = Color::Muted.color(cx);
+    let highlighted_segments = segments
+        .into_iter()
+        .zip(segment_indices)
+        .map(|(segment, index)| {
+            let mut text_style = window.text_style();
+            if let Some(ref font) = segment.font {
+                text_style.font_family = font.family.clone();
+                text_style.font_features = font.features.clone();
+                text_style.font_style = font.style;
+                text_style.font_weight = font.weight;
+            }
+            text_style.color = Color::Muted.color(cx);
+
+            let show_dirty_filename = index == Some(0)
+                && !workspace::TabBarSettings::get_global(cx).show
+                && active_item.is_dirty(cx);
+
+            if index == Some(0)
+                && let Some(breadcrumb_path) = navigable_path.as_ref()
+            {
+                return breadcrumb_path.render(&segment.text, &text_style, show_dirty_filename, cx);
+            }
 
-        if index == 0
-            && !workspace::TabBarSettings::get_global(cx).show
-            && active_item.is_dirty(cx)
-            && let Some(styled_element) = apply_dirty_filename_style(&segment, &text_style, cx)
-        {
-            return styled_element;
-        }
+            let styled_segment = if show_dirty_filename
+                && let Some(styled_element) = apply_dirty_filename_style(&segment, &text_style, cx)
+            {
+                styled_element
+            } else {
+                StyledText::new(segment.text.replace('\n', "⏎"))
+                    .with_default_highlights(&text_style, segment.highlights.unwrap_or_default())
+                    .into_any()
+            };
 
-        StyledText::new(segment.text.replace('\n', "⏎"))
-            .with_default_highlights(&text_style, segment.highlights.unwrap_or_default())
-            .into_any()
-    });
+            let Some(editor) = navigable_editor.as_ref() else {
+                return styled_segment;
+            };
+            let editor = editor.downgrade();
+            match index.and_then(|index| index.checked_sub(first_symbol_index)) {
+                Some(symbol_index) => ButtonLike::new(("breadcrumb-symbol", symbol_index))
+                    .child(styled_segment)
+                    .tooltip(Tooltip::text("Show Symbols in Scope"))
+                    .on_click(move |_, window, cx| {
+                        if let Some((editor, callback)) = editor
+                            .upgrade()
+                            .zip(zed_actions::outline::TOGGLE_OUTLINE_IN_SCOPE.get())
+                        {
+                            callback(editor.to_any_view(), symbol_index, window, cx);
+                        }
+                    })
+                    .into_any_element(),
+                None => ButtonLike::new(("breadcrumb-segment", index.unwrap_or(usize::MAX)))
+                    .child(styled_segment)
+                    .tooltip(Tooltip::text("Show Symbol Outline"))
+                    .on_click(move |_, window, cx| {
+                        if let Some((editor, callback)) = editor
+                            .upgrade()
+                            .zip(zed_actions::outline::TOGGLE_OUTLINE.get())
+                        {
+                            callback(editor.to_any_view(), window, cx);
+                        }
+                    })
+                    .into_any_element(),
+            }
+        });
 
     let breadcrumbs = Itertools::intersperse_with(highlighted_segments, || {
         Label::new("›").color(Color::Placeholder).into_any_element()
@@ -7859,81 +8186,38 @@ pub fn render_breadcrumb_text(
         breadcrumbs_stack
     };
 
-    let editor = active_item
-        .downcast::<Editor>()
-        .map(|editor| editor.downgrade());
-
     let has_project_path = active_item.project_path(cx).is_some();
 
     match editor {
+        Some(editor) if !multibuffer_header => element
+            .id("breadcrumb_container")
+            .overflow_x_scroll()
+            .child(breadcrumbs)
+            .when(has_project_path, |this| {
+                this.on_mouse_down(MouseButton::Right, move |_, _, cx| {
+                    copy_breadcrumb_path(&editor, cx);
+                })
+            })
+            .into_any_element(),
         Some(editor) => element
             .id("breadcrumb_container")
-            .when(!multibuffer_header, |this| this.overflow_x_scroll())
             .child(
                 ButtonLike::new("toggle outline view")
                     .child(breadcrumbs)
-                    .when(multibuffer_header, |this| {
-                        this.style(ButtonStyle::Transparent)
-                    })
-                    .when(!multibuffer_header, |this| {
-                        let focus_handle = editor.upgrade().unwrap().focus_handle(&cx);
-
-                        this.tooltip(Tooltip::element(move |_window, cx| {
-                            v_flex()
-                                .gap_1()
-                                .child(
-                                    h_flex()
-                                        .gap_1()
-                                        .justify_between()
-                                        .child(Label::new("Show Symbol Outline"))
-                                        .child(ui::KeyBinding::for_action_in(
-                                            &zed_actions::outline::ToggleOutline,
-                                            &focus_handle,
-                                            cx,
-                                        )),
-                                )
-                                .when(has_project_path, |this| {
-                                    this.child(
-                                        h_flex()
-                                            .gap_1()
-                                            .justify_between()
-                                            .pt_1()
-                                            .border_t_1()
-                                            .border_color(cx.theme().colors().border_variant)
-                                            .child(Label::new("Right-Click to Copy Path")),
-                                    )
-                                })
-                                .into_any_element()
-                        }))
-                        .on_click({
-                            let editor = editor.clone();
-                            move |_, window, cx| {
-                                if let Some((editor, callback)) = editor
-                                    .upgrade()
-                                    .zip(zed_actions::outline::TOGGLE_OUTLINE.get())
-                                {
-                                    callback(editor.to_any_view(), window, cx);
-                                }
+                    .style(ButtonStyle::Transparent)
+                    .on_click({
+                        let editor = editor.clone();
+                        move |_, window, cx| {
+                            if let Some((editor, callback)) = editor
+                                .upgrade()
+                                .zip(zed_actions::outline::TOGGLE_OUTLINE.get())
+                            {
+                                callback(editor.to_any_view(), window, cx);
                             }
-                        })
-                        .when(has_project_path, |this| {
-                            this.on_right_click({
-                                let editor = editor.clone();
-                                move |_, _, cx| {
-                                    if let Some(abs_path) = editor.upgrade().and_then(|editor| {
-                                        editor.update(cx, |editor, cx| {
-                                            editor.target_file_abs_path(cx)
-                                        })
-                                    }) {
-                                        if let Some(path_str) = abs_path.to_str() {
-                                            cx.write_to_clipboard(ClipboardItem::new_string(
-                                                path_str.to_string(),
-                                            ));
-                                        }
-                                    }
-                                }
-                            })
-                        })
+                        }
+                    })
+                    .when(has_project_path, |this| {
+                        this.on_right_click(move |_, _, cx| copy_breadcrumb_path(&editor, cx))
                     }),
             )
             .into_any_element(),
@@ -7945,6 +8229,152 @@ pub fn render_breadcrumb_text(
     }
 }
 
+fn copy_breadcrumb_path(editor: &WeakEntity<Editor>, cx: &mut App) {
+    if let Some(abs_path) = editor
+        .upgrade()
+        .and_then(|editor| editor.update(cx, |editor, cx| editor.target_file_abs_path(cx)))
+        && let Some(path_str) = abs_path.to_str()
+    {
+        cx.write_to_clipboard(ClipboardItem::new_string(path_str.to_string()));
+    }
+}
+
+/// The file path shown as the first breadcrumb, whose every segment lists the entries of the
+/// directory containing it.
+struct BreadcrumbPath {
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    project_path: ProjectPath,
+    /// The length of the worktree root name shown before the path, if any.
+    root_prefix_len: usize,
+}
+
+impl BreadcrumbPath {
+    fn render(
+        &self,
+        text: &str,
+        text_style: &gpui::TextStyle,
+        show_dirty_filename: bool,
+        cx: &App,
+    ) -> AnyElement {
+        let Some(worktree) = self
+            .project
+            .read(cx)
+            .worktree_for_id(self.project_path.worktree_id, cx)
+        else {
+            return StyledText::new(text.to_string())
+                .with_default_highlights(text_style, [])
+                .into_any();
+        };
+
+        let separator = self.project.read(cx).path_style(cx).primary_separator();
+        let mut directories = self
+            .project_path
+            .path
+            .ancestors()
+            .skip(1)
+            .map(|directory| directory.into_arc())
+            .collect::<Vec<_>>();
+        directories.reverse();
+        let file_index = directories.len().saturating_sub(1);
+
+        let mut file_name_style = text_style.clone();
+        if show_dirty_filename {
+            file_name_style.font_weight = FontWeight::BOLD;
+            file_name_style.color = Color::Default.color(cx);
+        }
+
+        let root_prefix = &text[..self.root_prefix_len];
+        let segments = self
+            .project_path
+            .path
+            .components()
+            .zip(directories)
+            .enumerate()
+            .map(|(index, (name, directory))| {
+                let style = if index == file_index {
+                    &file_name_style
+                } else {
+                    text_style
+                };
+                let label = StyledText::new(name.to_string()).with_default_highlights(style, []);
+                let worktree = worktree.downgrade();
+                let workspace = self.workspace.clone();
+                PopoverMenu::new(("breadcrumb-path", index))
+                    .trigger(ButtonLike::new(("breadcrumb-path-trigger", index)).child(label))
+                    .menu(move |window, cx| {
+                        let worktree = worktree.upgrade()?;
+                        let directory = directory.clone();
+                        let workspace = workspace.clone();
+                        Some(ContextMenu::build(window, cx, move |menu, _, cx| {
+                            directory_entries_menu(menu, directory, &worktree, &workspace, cx)
+                        }))
+                    })
+                    .into_any_element()
+            });
+
+        h_flex()
+            .when(!root_prefix.is_empty(), |this| {
+                this.child(
+                    StyledText::new(root_prefix.to_string())
+                        .with_default_highlights(text_style, []),
+                )
+            })
+            .children(Itertools::intersperse_with(segments, || {
+                StyledText::new(separator)
+                    .with_default_highlights(text_style, [])
+                    .into_any_element()
+            }))
+            .into_any_element()
+    }
+}
+
+/// Lists the entries of a worktree directory, opening files when clicked and listing the
+/// entries of subdirectories in submenus.
+fn directory_entries_menu(
+    mut menu: ContextMenu,
+    directory: Arc<RelPath>,
+    worktree: &Entity<Worktree>,
+    workspace: &WeakEntity<Workspace>,
+    cx: &App,
+) -> ContextMenu {
+    let worktree_id = worktree.read(cx).id();
+    for entry in worktree.read(cx).child_entries(&directory) {
+        let Some(name) = entry.path.file_name() else {
+            continue;
+        };
+        if entry.is_dir() {
+            let path = entry.path.clone();
+            let worktree = worktree.downgrade();
+            let workspace = workspace.clone();
+            menu = menu.submenu(name.to_string(), move |menu, _, cx| {
+                match worktree.upgrade() {
+                    Some(worktree) => {
+                        directory_entries_menu(menu, path.clone(), &worktree, &workspace, cx)
+                    }
+                    None => menu,
+                }
+            });
+        } else {
+            let project_path = ProjectPath {
+                worktree_id,
+                path: entry.path.clone(),
+            };
+            let workspace = workspace.clone();
+            menu = menu.entry(name.to_string(), None, move |window, cx| {
+                workspace
+                    .update(cx, |workspace, cx| {
+                        workspace
+                            .open_path(project_path.clone(), None, true, window, cx)
+                            .detach_and_log_err(cx);
+                    })
+                    .ok();
+            });
+        }
+    }
+    menu
+}
+
 fn apply_dirty_filename_style(
     segment: &BreadcrumbText,
     text_style: &gpui::TextStyle,
@@ -10553,6 +10983,23 @@ impl Element for EditorElement {
                         cx,
                     );
 
+                    let bookmarks = if gutter_settings.bookmarks {
+                        self.layout_bookmarks(
+                            line_height,
+                            start_row..end_row,
+                            scroll_position,
+                            &gutter_dimensions,
+                            &gutter_hitbox,
+                            &snapshot,
+                            &breakpoint_rows,
+                            &row_infos,
+                            window,
+                            cx,
+                        )
+                    } else {
+                        Vec::new()
+                    };
+
                     let test_indicators = if gutter_settings.runnables {
                         self.layout_run_indicators(
                             line_height,
@@ -10742,7 +11189,7 @@ impl Element for EditorElement {
                         &[TextRun {
                             len: tab_len,
                             font: self.style.text.font(),
-                            color: cx.theme().colors().editor_invisible,
+                            color: cx.theme().colors().editor_invisible_tab,
                             ..Default::default()
                         }],
                         None,
@@ -10756,7 +11203,7 @@ impl Element for EditorElement {
                         &[TextRun {
                             len: space_len,
                             font: self.style.text.font(),
-                            color: cx.theme().colors().editor_invisible,
+                            color: cx.theme().colors().editor_invisible_space,
                             ..Default::default()
                         }],
                         None,
@@ -10874,6 +11321,7 @@ impl Element for EditorElement {
                         mouse_context_menu,
                         test_indicators,
                         breakpoints,
+                        bookmarks,
                         diff_review_button,
                         crease_toggles,
                         crease_trailers,
@@ -10914,6 +11362,7 @@ impl Element for EditorElement {
             );
             self.register_actions(window, cx);
             self.register_key_listeners(window, cx, layout);
+            self.paint_accessibility_node(&focus_handle, bounds, layout, window, cx);
         }
 
         let text_style = TextStyleRefinement {
@@ -10964,6 +11413,96 @@ impl Element for EditorElement {
     }
 }
 
+impl EditorElement {
+    /// Exposes the visible lines to assistive technologies, one text run per
+    /// display row, along with the position of the newest cursor.
+    fn paint_accessibility_node(
+        &self,
+        focus_handle: &FocusHandle,
+        bounds: Bounds<Pixels>,
+        layout: &EditorLayout,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let snapshot = &layout.position_map.snapshot;
+        let role = if layout.mode.is_single_line() {
+            accesskit::Role::TextInput
+        } else {
+            accesskit::Role::MultilineTextInput
+        };
+        let editor_node_id = focus_handle.accessibility_node_id();
+        let mut editor_node = accesskit::Node::new(role);
+        editor_node.set_bounds(accesskit_rect(bounds));
+        if let Some(placeholder) = snapshot.placeholder_text() {
+            editor_node.set_placeholder(placeholder);
+        }
+
+        let visible_rows = layout.visible_display_row_range.clone();
+        let cursor = self
+            .editor
+            .read(cx)
+            .selections
+            .newest_anchor()
+            .head()
+            .to_display_point(snapshot);
+        let line_height = layout.position_map.line_height;
+        let scroll_top = layout.position_map.scroll_position.y;
+
+        let mut runs = Vec::new();
+        for row in visible_rows.start.0..visible_rows.end.0 {
+            let run_id = gpui::accessibility_node_id(&(editor_node_id, row));
+            let mut text = snapshot.line(DisplayRow(row));
+            let cursor_index = (cursor.row().0 == row).then(|| {
+                let column = (cursor.column() as usize).min(text.len());
+                text.get(..column)
+                    .map_or(0, |prefix| prefix.chars().count())
+            });
+            if row + 1 < visible_rows.end.0 {
+                text.push('\n');
+            }
+
+            let top = layout.content_origin.y + line_height * (row as f64 - scroll_top) as f32;
+            let mut run = accesskit::Node::new(accesskit::Role::TextRun);
+            run.set_bounds(accesskit_rect(Bounds::new(
+                point(layout.content_origin.x, top),
+                size(bounds.right() - layout.content_origin.x, line_height),
+            )));
+            run.set_character_lengths(
+                text.chars()
+                    .map(|character| character.len_utf8() as u8)
+                    .collect::<Vec<_>>(),
+            );
+            run.set_value(text);
+            if let Some(character_index) = cursor_index {
+                let position = accesskit::TextPosition {
+                    node: run_id,
+                    character_index,
+                };
+                editor_node.set_text_selection(accesskit::TextSelection {
+                    anchor: position,
+                    focus: position,
+                });
+            }
+            runs.push((run_id, run));
+        }
+
+        window.with_accessibility_node(editor_node_id, editor_node, |window| {
+            for (run_id, run) in runs {
+                window.with_accessibility_node(run_id, run, |_| {});
+            }
+        });
+    }
+}
+
+fn accesskit_rect(bounds: Bounds<Pixels>) -> accesskit::Rect {
+    accesskit::Rect {
+        x0: f64::from(bounds.left()),
+        y0: f64::from(bounds.top()),
+        x1: f64::from(bounds.right()),
+        y1: f64::from(bounds.bottom()),
+    }
+}
+
 pub(super) fn gutter_bounds(
     editor_bounds: Bounds<Pixels>,
     gutter_dimensions: GutterDimensions,
@@ -11053,6 +11592,7 @@ pub struct EditorLayout {
     selections: Vec<(PlayerColor, Vec<SelectionLayout>)>,
     test_indicators: Vec<AnyElement>,
     breakpoints: Vec<AnyElement>,
+    bookmarks: Vec<AnyElement>,
     diff_review_button: Option<AnyElement>,
     crease_toggles: Vec<Option<AnyElement>>,
     expand_toggles: Vec<Option<(AnyElement, gpui::Point<Pixels>)>>,
@@ -11629,6 +12169,7 @@ impl ScrollbarLayout {
 
 struct MinimapLayout {
     pub minimap: AnyElement,
+    pub markers: Vec<PaintQuad>,
     pub thumb_layout: ScrollbarLayout,
     pub minimap_scroll_top: ScrollOffset,
     pub minimap_line_height: Pixels,
@@ -11641,6 +12182,8 @@ impl MinimapLayout {
     const MINIMAP_MIN_WIDTH_COLUMNS: f32 = 20.;
     /// The minimap width as a percentage of the editor width.
     const MINIMAP_WIDTH_PCT: f32 = 0.15;
+    /// The width of the diagnostic markers along the right edge of the minimap.
+    const DIAGNOSTIC_MARKER_WIDTH: Pixels = px(3.);
     /// Calculates the scroll top offset the minimap editor has to have based on the
     /// current scroll progress.
     fn calculate_minimap_top_offset(
@@ -11819,6 +12362,65 @@ pub struct IndentGuideLayout {
     settings: IndentGuideSettings,
 }
 
+/// How long the newest cursor takes to glide to its new position when
+/// `smooth_caret` is enabled.
+const CARET_ANIMATION_DURATION: Duration = Duration::from_millis(80);
+
+/// The glide of the newest cursor between two positions, measured in pixels
+/// from the start of the document so that scrolling doesn't animate it.
+pub struct CaretAnimation {
+    from: gpui::Point<ScrollPixelOffset>,
+    to: gpui::Point<ScrollPixelOffset>,
+    started_at: Instant,
+}
+
+impl CaretAnimation {
+    /// Returns where the caret is drawn on its way to `target`, and whether
+    /// it is still moving, restarting the glide when the target changes.
+    ///
+    /// Moves further than `max_distance` vertically jump instead, since
+    /// gliding across a whole screen is more distracting than helpful.
+    pub fn advance(
+        animation: &mut Option<Self>,
+        target: gpui::Point<ScrollPixelOffset>,
+        max_distance: ScrollPixelOffset,
+        now: Instant,
+    ) -> (gpui::Point<ScrollPixelOffset>, bool) {
+        let current = animation.get_or_insert(Self {
+            from: target,
+            to: target,
+            started_at: now,
+        });
+        if current.to != target {
+            let from = current.position_at(now);
+            *current = Self {
+                from: if (target.y - from.y).abs() > max_distance {
+                    target
+                } else {
+                    from
+                },
+                to: target,
+                started_at: now,
+            };
+        }
+        (
+            current.position_at(now),
+            now < current.started_at + CARET_ANIMATION_DURATION,
+        )
+    }
+
+    fn position_at(&self, now: Instant) -> gpui::Point<ScrollPixelOffset> {
+        let progress = (now.duration_since(self.started_at).as_secs_f64()
+            / CARET_ANIMATION_DURATION.as_secs_f64())
+        .min(1.);
+        let eased = 1. - (1. - progress).powi(3);
+        point(
+            self.from.x + (self.to.x - self.from.x) * eased,
+            self.from.y + (self.to.y - self.from.y) * eased,
+        )
+    }
+}
+
 pub struct CursorLayout {
     origin: gpui::Point<Pixels>,
     block_width: Pixels,
@@ -12264,6 +12866,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_caret_animation() {
+        let start = Instant::now();
+        let mut animation = None;
+
+        assert_eq!(
+            CaretAnimation::advance(&mut animation, point(10., 20.), 100., start),
+            (point(10., 20.), false),
+            "The first position is drawn without gliding"
+        );
+
+        assert_eq!(
+            CaretAnimation::advance(&mut animation, point(50., 20.), 100., start),
+            (point(10., 20.), true)
+        );
+        assert_eq!(
+            CaretAnimation::advance(
+                &mut animation,
+                point(50., 20.),
+                100.,
+                start + CARET_ANIMATION_DURATION / 2
+            ),
+            (point(45., 20.), true),
+            "The glide eases out"
+        );
+        let end = start + CARET_ANIMATION_DURATION;
+        assert_eq!(
+            CaretAnimation::advance(&mut animation, point(50., 20.), 100., end),
+            (point(50., 20.), false)
+        );
+
+        assert_eq!(
+            CaretAnimation::advance(&mut animation, point(50., 40.), 100., end),
+            (point(50., 20.), true)
+        );
+        assert_eq!(
+            CaretAnimation::advance(
+                &mut animation,
+                point(50., 500.),
+                100.,
+                end + CARET_ANIMATION_DURATION / 2
+            ),
+            (point(50., 500.), true),
+            "Moves further than the maximum distance jump"
+        );
+    }
+
     #[gpui::test]
     async fn test_soft_wrap_editor_width_full_editor(cx: &mut TestAppContext) {
         init_test(cx, |_| {});
@@ -1188,6 +1188,7 @@ mod tests {
                 show_type_hints: Some(true),
                 show_parameter_hints: Some(true),
                 show_other_hints: Some(true),
+                show_chaining_hints: Some(true),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
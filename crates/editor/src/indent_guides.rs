@@ -3,7 +3,7 @@ use std::{cmp::Ordering, ops::Range, time::Duration};
 use collections::HashSet;
 use gpui::{App, AppContext as _, Context, Task, Window};
 use language::language_settings::language_settings;
-use multi_buffer::{IndentGuide, MultiBufferRow, ToPoint};
+use multi_buffer::{IndentGuide, MultiBufferRow, MultiBufferSnapshot, ToPoint};
 use text::{LineIndent, Point};
 use util::ResultExt;
 
@@ -210,6 +210,9 @@ async fn resolve_indented_range(
     snapshot: DisplaySnapshot,
     buffer_row: MultiBufferRow,
 ) -> Option<ActiveIndentedRange> {
+    if let Some(indented_range) = syntax_indented_range(snapshot.buffer_snapshot(), buffer_row) {
+        return Some(indented_range);
+    }
     snapshot
         .buffer_snapshot()
         .enclosing_indent(buffer_row)
@@ -217,6 +220,64 @@ async fn resolve_indented_range(
         .map(|(row_range, indent)| ActiveIndentedRange { row_range, indent })
 }
 
+/// Finds the innermost syntax node around the start of the row's text that
+/// spans several lines and indents its contents, like the body of a block.
+/// Returns `None` for blank rows or languages without a syntax tree, where
+/// the enclosing indentation is used instead.
+fn syntax_indented_range(
+    buffer: &MultiBufferSnapshot,
+    buffer_row: MultiBufferRow,
+) -> Option<ActiveIndentedRange> {
+    let line_indent = buffer.line_indent_for_row(buffer_row);
+    if line_indent.is_line_empty() {
+        return None;
+    }
+    let position = buffer.point_to_offset(Point::new(buffer_row.0, line_indent.raw_len()));
+    let mut range = position..position;
+    loop {
+        let (node, node_range) = buffer.syntax_ancestor(range)?;
+        // The root node spans the whole file, whose top-level items aren't a scope.
+        node.parent()?;
+        let start_row = node_range.start.to_point(buffer).row;
+        let end_row = node_range.end.to_point(buffer).row;
+        if let Some(indented_range) = indented_rows(buffer, start_row, end_row) {
+            return Some(indented_range);
+        }
+        range = node_range;
+    }
+}
+
+fn indented_rows(
+    buffer: &MultiBufferSnapshot,
+    start_row: u32,
+    end_row: u32,
+) -> Option<ActiveIndentedRange> {
+    if end_row <= start_row {
+        return None;
+    }
+    let indent = buffer.line_indent_for_row(MultiBufferRow(start_row));
+    let (_, body_indent, _) = buffer
+        .line_indents(MultiBufferRow(start_row + 1), |_| true)
+        .take_while(|(row, _, _)| row.0 <= end_row)
+        .find(|(_, line_indent, _)| !line_indent.is_line_empty())?;
+    if body_indent.raw_len() <= indent.raw_len() {
+        return None;
+    }
+
+    // A closing line, like `}`, is outside of the indented contents.
+    let last_line_indent = buffer.line_indent_for_row(MultiBufferRow(end_row));
+    let last_row =
+        if !last_line_indent.is_line_empty() && last_line_indent.raw_len() <= indent.raw_len() {
+            end_row - 1
+        } else {
+            end_row
+        };
+    Some(ActiveIndentedRange {
+        row_range: MultiBufferRow(start_row)..MultiBufferRow(last_row),
+        indent,
+    })
+}
+
 fn should_recalculate_indented_range(
     prev_row: MultiBufferRow,
     new_row: MultiBufferRow,
@@ -1000,6 +1000,9 @@ pub mod tests {
                     allowed_hint_kinds.contains(&Some(InlayHintKind::Parameter)),
                 ),
                 show_other_hints: Some(allowed_hint_kinds.contains(&None)),
+                show_chaining_hints: Some(
+                    allowed_hint_kinds.contains(&Some(InlayHintKind::Chaining)),
+                ),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -1194,6 +1197,7 @@ pub mod tests {
                 show_type_hints: Some(true),
                 show_parameter_hints: Some(true),
                 show_other_hints: Some(true),
+                show_chaining_hints: Some(true),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -1304,6 +1308,7 @@ pub mod tests {
                 show_type_hints: Some(true),
                 show_parameter_hints: Some(true),
                 show_other_hints: Some(true),
+                show_chaining_hints: Some(true),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -1553,6 +1558,9 @@ pub mod tests {
                     allowed_hint_kinds.contains(&Some(InlayHintKind::Parameter)),
                 ),
                 show_other_hints: Some(allowed_hint_kinds.contains(&None)),
+                show_chaining_hints: Some(
+                    allowed_hint_kinds.contains(&Some(InlayHintKind::Chaining)),
+                ),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -1718,6 +1726,9 @@ pub mod tests {
                         new_allowed_hint_kinds.contains(&Some(InlayHintKind::Parameter)),
                     ),
                     show_other_hints: Some(new_allowed_hint_kinds.contains(&None)),
+                    show_chaining_hints: Some(
+                        new_allowed_hint_kinds.contains(&Some(InlayHintKind::Chaining)),
+                    ),
                     show_background: Some(false),
                     toggle_on_modifiers_press: None,
                 })
@@ -1765,6 +1776,9 @@ pub mod tests {
                     another_allowed_hint_kinds.contains(&Some(InlayHintKind::Parameter)),
                 ),
                 show_other_hints: Some(another_allowed_hint_kinds.contains(&None)),
+                show_chaining_hints: Some(
+                    another_allowed_hint_kinds.contains(&Some(InlayHintKind::Chaining)),
+                ),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -1838,6 +1852,9 @@ pub mod tests {
                     final_allowed_hint_kinds.contains(&Some(InlayHintKind::Parameter)),
                 ),
                 show_other_hints: Some(final_allowed_hint_kinds.contains(&None)),
+                show_chaining_hints: Some(
+                    final_allowed_hint_kinds.contains(&Some(InlayHintKind::Chaining)),
+                ),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -1912,6 +1929,7 @@ pub mod tests {
                 show_type_hints: Some(true),
                 show_parameter_hints: Some(true),
                 show_other_hints: Some(true),
+                show_chaining_hints: Some(true),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -2276,6 +2294,7 @@ pub mod tests {
                 show_type_hints: Some(true),
                 show_parameter_hints: Some(true),
                 show_other_hints: Some(true),
+                show_chaining_hints: Some(true),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -2886,6 +2905,7 @@ let c = 3;"#
                 show_type_hints: Some(false),
                 show_parameter_hints: Some(false),
                 show_other_hints: Some(false),
+                show_chaining_hints: Some(false),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -3079,6 +3099,7 @@ let c = 3;"#
                 show_type_hints: Some(true),
                 show_parameter_hints: Some(true),
                 show_other_hints: Some(true),
+                show_chaining_hints: Some(true),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -3118,6 +3139,7 @@ let c = 3;"#
                 show_type_hints: Some(true),
                 show_parameter_hints: Some(true),
                 show_other_hints: Some(true),
+                show_chaining_hints: Some(true),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -3229,6 +3251,7 @@ let c = 3;"#
                 show_type_hints: Some(true),
                 show_parameter_hints: Some(true),
                 show_other_hints: Some(true),
+                show_chaining_hints: Some(true),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -3311,6 +3334,7 @@ let c = 3;"#
                 show_type_hints: Some(true),
                 show_parameter_hints: Some(true),
                 show_other_hints: Some(true),
+                show_chaining_hints: Some(true),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -3377,6 +3401,7 @@ let c = 3;"#
                 show_type_hints: Some(true),
                 show_parameter_hints: Some(true),
                 show_other_hints: Some(true),
+                show_chaining_hints: Some(true),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
@@ -3603,6 +3628,7 @@ let c = 3;"#
                 show_type_hints: Some(true),
                 show_parameter_hints: Some(true),
                 show_other_hints: Some(true),
+                show_chaining_hints: Some(true),
                 show_background: Some(false),
                 toggle_on_modifiers_press: None,
             })
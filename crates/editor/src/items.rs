@@ -18,8 +18,8 @@ use gpui::{
     ParentElement, Pixels, SharedString, Styled, Task, WeakEntity, Window, point,
 };
 use language::{
-    Bias, Buffer, BufferRow, CharKind, CharScopeContext, LocalFile, Point, SelectionGoal,
-    proto::serialize_anchor as serialize_text_anchor,
+    Bias, Buffer, BufferRow, CharKind, CharScopeContext, LanguageName, LocalFile, Point,
+    SelectionGoal, proto::serialize_anchor as serialize_text_anchor,
 };
 use lsp::DiagnosticSeverity;
 use multi_buffer::MultiBufferOffset;
@@ -51,8 +51,9 @@ use workspace::{
     },
 };
 use workspace::{
-    OpenOptions,
+    OpenOptions, Toast,
     item::{Dedup, ItemSettings, SerializableItem, TabContentParams},
+    notifications::NotificationId,
 };
 use workspace::{
     OpenVisible, Pane, WorkspaceSettings,
@@ -678,8 +679,19 @@ impl Item for Editor {
         ItemSettings::get_global(cx)
             .file_icons
             .then(|| {
-                path_for_buffer(&self.buffer, 0, true, cx)
-                    .and_then(|path| FileIcons::get_icon(Path::new(&*path), cx))
+                let language_name = self
+                    .buffer
+                    .read(cx)
+                    .as_singleton()
+                    .and_then(|buffer| buffer.read(cx).language())
+                    .map(|language| language.name());
+                path_for_buffer(&self.buffer, 0, true, cx).and_then(|path| {
+                    FileIcons::get_icon_for_language(
+                        language_name.as_ref().map(AsRef::as_ref),
+                        Path::new(&*path),
+                        cx,
+                    )
+                })
             })
             .flatten()
             .map(Icon::from_path)
@@ -767,6 +779,11 @@ impl Item for Editor {
         }
     }
 
+    fn language_name(&self, cx: &App) -> Option<LanguageName> {
+        let buffer = self.buffer.read(cx).as_singleton()?;
+        buffer.read(cx).language().map(|language| language.name())
+    }
+
     fn can_save_as(&self, cx: &App) -> bool {
         self.buffer.read(cx).is_singleton()
     }
@@ -796,6 +813,23 @@ impl Item for Editor {
         self.nav_history = Some(history);
     }
 
+    fn set_buffer_font_size_delta(
+        &mut self,
+        delta: Pixels,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.buffer_font_size_delta = delta;
+        cx.notify();
+    }
+
+    fn set_zen_mode(&mut self, zen_mode: bool, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.zen_mode != zen_mode {
+            self.zen_mode = zen_mode;
+            cx.notify();
+        }
+    }
+
     fn on_removed(&self, cx: &mut Context<Self>) {
         self.report_editor_event(ReportEditorEvent::Closed, None, cx);
     }
@@ -992,7 +1026,13 @@ impl Item for Editor {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        let first_added = self.workspace.is_none();
         self.workspace = Some((workspace.weak_handle(), workspace.database_id()));
+        // Restored here rather than on deserialization, so that files opened
+        // by path get their history back too.
+        if first_added && let Some(workspace_id) = workspace.database_id() {
+            self.restore_undo_history(workspace_id, cx);
+        }
         if let Some(workspace) = &workspace.weak_handle().upgrade() {
             cx.subscribe(workspace, |editor, _, event: &workspace::Event, _cx| {
                 if let workspace::Event::ModalOpened = event {
@@ -1211,9 +1251,12 @@ impl SerializableItem for Editor {
                             .context("Failed to open path in project")?;
 
                         if let Some(contents) = contents {
-                            buffer.update(cx, |buffer, cx| {
-                                restore_serialized_buffer_contents(buffer, contents, mtime, cx);
+                            let has_conflict = buffer.update(cx, |buffer, cx| {
+                                restore_serialized_buffer_contents(buffer, contents, mtime, cx)
                             });
+                            if has_conflict {
+                                notify_restored_buffer_conflict(&workspace, &abs_path, cx);
+                            }
                         }
 
                         cx.update(|window, cx| {
@@ -1248,15 +1291,21 @@ impl SerializableItem for Editor {
                                 )?;
 
                             if let Some(contents) = contents {
-                                editor.update_in(cx, |editor, _window, cx| {
-                                    if let Some(buffer) = editor.buffer().read(cx).as_singleton() {
-                                        buffer.update(cx, |buffer, cx| {
-                                            restore_serialized_buffer_contents(
-                                                buffer, contents, mtime, cx,
-                                            );
-                                        });
-                                    }
-                                })?;
+                                let has_conflict =
+                                    editor.update_in(cx, |editor, _window, cx| {
+                                        editor.buffer().read(cx).as_singleton().is_some_and(
+                                            |buffer| {
+                                                buffer.update(cx, |buffer, cx| {
+                                                    restore_serialized_buffer_contents(
+                                                        buffer, contents, mtime, cx,
+                                                    )
+                                                })
+                                            },
+                                        )
+                                    })?;
+                                if has_conflict {
+                                    notify_restored_buffer_conflict(&workspace, &abs_path, cx);
+                                }
                             }
 
                             editor.update_in(cx, |editor, window, cx| {
@@ -1996,12 +2045,14 @@ fn path_for_file<'a>(
 /// This is somewhat wasteful since we load the whole buffer from disk then overwrite it,
 /// but keeps implementation simple as we don't need to persist all metadata from loading
 /// (git diff base, etc.).
+///
+/// Returns whether the file changed on disk since the contents were serialized.
 fn restore_serialized_buffer_contents(
     buffer: &mut Buffer,
     contents: String,
     mtime: Option<MTime>,
     cx: &mut Context<Buffer>,
-) {
+) -> bool {
     // If we did restore an mtime, store it on the buffer so that
     // the next edit will mark the buffer as dirty/conflicted.
     if mtime.is_some() {
@@ -2011,6 +2062,34 @@ fn restore_serialized_buffer_contents(
     if let Some(entry) = buffer.peek_undo_stack() {
         buffer.forget_transaction(entry.transaction_id());
     }
+    buffer.has_conflict()
+}
+
+/// Warns that the unsaved changes restored for a file were made to an older
+/// version of it, so that they aren't saved over the file's new contents unnoticed.
+fn notify_restored_buffer_conflict(
+    workspace: &WeakEntity<Workspace>,
+    abs_path: &Path,
+    cx: &mut AsyncWindowContext,
+) {
+    struct RestoredBufferConflict;
+
+    workspace
+        .update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::composite::<RestoredBufferConflict>(
+                        abs_path.to_string_lossy().into_owned(),
+                    ),
+                    format!(
+                        "{} changed on disk while Zed was closed. Its unsaved changes were restored on top of the older version.",
+                        abs_path.display()
+                    ),
+                ),
+                cx,
+            )
+        })
+        .log_err();
 }
 
 #[cfg(test)]
@@ -2025,7 +2104,10 @@ mod tests {
     use language::TestFile;
     use project::FakeFs;
     use std::path::{Path, PathBuf};
-    use util::{path, rel_path::RelPath};
+    use util::{
+        path,
+        rel_path::{RelPath, rel_path},
+    };
 
     #[gpui::test]
     fn test_path_for_file(cx: &mut App) {
@@ -2210,12 +2292,19 @@ mod tests {
                 .unwrap();
 
             let deserialized =
-                deserialize_editor(item_id, workspace_id, workspace, project, cx).await;
+                deserialize_editor(item_id, workspace_id, workspace.clone(), project, cx).await;
 
             deserialized.update(cx, |editor, cx| {
                 assert_eq!(editor.text(cx), "fn main() {}");
                 assert!(editor.has_conflict(cx)); // The editor should have a conflict
             });
+            workspace.read_with(cx, |workspace, _| {
+                assert_eq!(
+                    workspace.notification_ids().len(),
+                    1,
+                    "the conflict with the file on disk should be reported"
+                );
+            });
         }
 
         // Test case 5: Deserialize with no path, no content, no language, and no old mtime (new, empty, unsaved buffer)
@@ -2304,4 +2393,90 @@ mod tests {
             });
         }
     }
+
+    #[gpui::test]
+    async fn test_undo_history_persists_across_sessions(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), serde_json::json!({ "file.txt": "one\n" }))
+            .await;
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+
+        // Edit and save the file in a first session.
+        {
+            let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+            let (multi_workspace, cx) = cx.add_window_view(|window, cx| {
+                MultiWorkspace::test_new(project.clone(), window, cx)
+            });
+            let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+            let editor = open_file_in_workspace(&workspace, workspace_id, cx).await;
+
+            editor.update_in(cx, |editor, window, cx| {
+                editor.move_to_end(&crate::actions::MoveToEnd, window, cx);
+                editor.insert("two\n", window, cx);
+            });
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    editor.save(
+                        SaveOptions {
+                            format: false,
+                            autosave: false,
+                        },
+                        project.clone(),
+                        window,
+                        cx,
+                    )
+                })
+                .await
+                .unwrap();
+            cx.run_until_parked();
+            cx.executor()
+                .advance_clock(workspace::SERIALIZATION_THROTTLE_TIME);
+            let serialize_undo_history = editor.update(cx, |editor, _| {
+                std::mem::replace(&mut editor.serialize_undo_history, Task::ready(()))
+            });
+            serialize_undo_history.await;
+        }
+
+        // Reopen it in a new session, whose buffer has no history of its own.
+        {
+            let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+            let (multi_workspace, cx) = cx.add_window_view(|window, cx| {
+                MultiWorkspace::test_new(project.clone(), window, cx)
+            });
+            let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+            let editor = open_file_in_workspace(&workspace, workspace_id, cx).await;
+
+            editor.update_in(cx, |editor, window, cx| {
+                assert_eq!(editor.text(cx), "one\ntwo\n");
+                editor.undo(&crate::actions::Undo, window, cx);
+                assert_eq!(editor.text(cx), "one\n");
+                assert!(editor.is_dirty(cx));
+            });
+        }
+    }
+
+    async fn open_file_in_workspace(
+        workspace: &Entity<Workspace>,
+        workspace_id: WorkspaceId,
+        cx: &mut VisualTestContext,
+    ) -> Entity<Editor> {
+        workspace.update(cx, |workspace, _| {
+            workspace.set_database_id_for_test(workspace_id)
+        });
+        let worktree_id = workspace.update(cx, |workspace, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees(cx).next().unwrap().read(cx).id()
+            })
+        });
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, rel_path("file.txt")), None, true, window, cx)
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap()
+    }
 }
@@ -6,7 +6,7 @@ use std::{ops::Range, time::Duration};
 use text::{AnchorRangeExt, BufferId, ToPoint};
 use util::ResultExt;
 
-use crate::Editor;
+use crate::{Editor, HighlightKey};
 
 #[derive(Clone, Default)]
 pub(super) struct LinkedEditingRanges(
@@ -164,6 +164,7 @@ pub(super) fn refresh_linked_ranges(
                     };
                     values.sort_by(|lhs, rhs| lhs.0.cmp(&rhs.0, &snapshot));
                 }
+                this.highlight_linked_edit_ranges(cx);
 
                 cx.notify();
             })
@@ -173,3 +174,35 @@ pub(super) fn refresh_linked_ranges(
     }));
     None
 }
+
+impl Editor {
+    /// Marks the ranges that are edited together, so that it's visible
+    /// which other ranges an edit will change.
+    fn highlight_linked_edit_ranges(&mut self, cx: &mut Context<Self>) {
+        let multi_buffer = self.buffer.read(cx);
+        let mut highlighted_ranges = Vec::new();
+        for (buffer_id, ranges) in &self.linked_edit_ranges.0 {
+            let Some(buffer) = multi_buffer.buffer(*buffer_id) else {
+                continue;
+            };
+            for (range, _) in ranges {
+                if let Some(start) = multi_buffer.buffer_anchor_to_anchor(&buffer, range.start, cx)
+                    && let Some(end) = multi_buffer.buffer_anchor_to_anchor(&buffer, range.end, cx)
+                {
+                    highlighted_ranges.push(start..end);
+                }
+            }
+        }
+
+        if highlighted_ranges.is_empty() {
+            self.clear_background_highlights(HighlightKey::LinkedEditingRanges, cx);
+        } else {
+            self.highlight_background(
+                HighlightKey::LinkedEditingRanges,
+                &highlighted_ranges,
+                |_, theme| theme.colors().editor_document_highlight_bracket_background,
+                cx,
+            );
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use multi_buffer::Anchor;
+use project::ProjectPath;
+use text::ToPoint as _;
+use ui::{ContextMenu, ContextMenuEntry, IconName, IconPosition, prelude::*};
+use workspace::location_history::LocationKind;
+
+use crate::{
+    Editor,
+    actions::{GoToNextEditLocation, GoToPreviousEditLocation, ShowEditLocationHistory},
+    mouse_context_menu::{MenuPosition, MouseContextMenu},
+};
+
+/// How many of the most recent locations the history menu lists.
+const MAX_LISTED_LOCATIONS: usize = 25;
+
+impl Editor {
+    /// Records `position` in the workspace's location history.
+    pub(crate) fn push_to_location_history(
+        &mut self,
+        position: Anchor,
+        kind: LocationKind,
+        cx: &mut Context<Self>,
+    ) {
+        if self.nav_history.is_none() {
+            return;
+        }
+        let Some((project_path, row)) = self.location_of(position, cx) else {
+            return;
+        };
+        let data = self.navigation_data(position, cx);
+        if let Some(nav_history) = self.nav_history.as_ref() {
+            nav_history.push_location(data, project_path, row, kind);
+        }
+    }
+
+    fn location_of(&self, position: Anchor, cx: &App) -> Option<(ProjectPath, u32)> {
+        let buffer = self.buffer.read(cx).buffer_for_anchor(position, cx)?;
+        let buffer = buffer.read(cx);
+        let project_path = buffer.project_path(cx)?;
+        let row = position.text_anchor.to_point(&buffer.snapshot()).row;
+        Some((project_path, row))
+    }
+
+    pub fn go_to_previous_edit_location(
+        &mut self,
+        _: &GoToPreviousEditLocation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_adjacent_location(false, window, cx);
+    }
+
+    pub fn go_to_next_edit_location(
+        &mut self,
+        _: &GoToNextEditLocation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_adjacent_location(true, window, cx);
+    }
+
+    fn go_to_adjacent_location(
+        &mut self,
+        forward: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        let current = self.location_of(self.selections.newest_anchor().head(), cx);
+        // Going to the location can update this editor.
+        window.defer(cx, move |window, cx| {
+            workspace.update(cx, |workspace, cx| {
+                let current = current
+                    .as_ref()
+                    .map(|(project_path, row)| (project_path, *row));
+                workspace
+                    .go_to_adjacent_location(forward, LocationKind::Edit, current, window, cx)
+                    .detach_and_log_err(cx);
+            });
+        });
+    }
+
+    pub fn show_edit_location_history(
+        &mut self,
+        _: &ShowEditLocationHistory,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        let location_history = workspace.read(cx).location_history().clone();
+        let locations = location_history.locations();
+        if locations.is_empty() {
+            return;
+        }
+        let position = location_history.position();
+        let project = workspace.read(cx).project().clone();
+        let path_style = project.read(cx).path_style(cx);
+        let weak_workspace = workspace.downgrade();
+        let focus_handle = self.focus_handle.clone();
+
+        let context_menu = ContextMenu::build(window, cx, move |mut menu, _, cx| {
+            menu = menu.header("Recent Locations");
+            for (index, location) in locations
+                .iter()
+                .enumerate()
+                .rev()
+                .take(MAX_LISTED_LOCATIONS)
+            {
+                let path = project
+                    .read(cx)
+                    .short_full_path_for_project_path(&location.project_path, cx)
+                    .unwrap_or_else(|| location.project_path.path.display(path_style).to_string());
+                let icon = match location.kind {
+                    LocationKind::Edit => IconName::Pencil,
+                    LocationKind::Navigation => IconName::ArrowRight,
+                };
+                let workspace = weak_workspace.clone();
+                menu = menu.item(
+                    ContextMenuEntry::new(format!("{path}:{}", location.row + 1))
+                        .icon(icon)
+                        .icon_color(Color::Muted)
+                        .toggleable(IconPosition::End, position == Some(index))
+                        .handler(move |window, cx| {
+                            workspace
+                                .update(cx, |workspace, cx| {
+                                    workspace
+                                        .go_to_location_at(index, window, cx)
+                                        .detach_and_log_err(cx);
+                                })
+                                .ok();
+                        }),
+                );
+            }
+            menu.context(focus_handle)
+        });
+
+        let character_size = self.character_dimensions(window, cx);
+        let menu_position = MenuPosition::PinnedToEditor {
+            source: self.selections.newest_anchor().head(),
+            offset: gpui::point(character_size.em_width, character_size.line_height),
+        };
+        self.mouse_context_menu = Some(MouseContextMenu::new(
+            self,
+            menu_position,
+            context_menu,
+            window,
+            cx,
+        ));
+        cx.notify();
+    }
+}
@@ -0,0 +1,445 @@
+//! Peeking: showing the definitions, implementations or references of a symbol
+//! in an editor embedded below the cursor, instead of navigating to them.
+//!
+//! Peeking from within a peek stacks the new peek on top of the previous one in
+//! the same block, so that going back returns to the previous peek.
+
+use std::{cmp::Reverse, sync::Arc};
+
+use anyhow::Result;
+use collections::{HashMap, HashSet};
+use gpui::{AnyElement, Entity, SharedString, Task, WeakEntity};
+use itertools::Itertools as _;
+use language::{Buffer, Location, Point};
+use multi_buffer::{MultiBuffer, PathKey};
+use text::{ToOffset as _, ToPoint as _};
+use ui::{Disclosure, IconButton, IconButtonShape, Tooltip, prelude::*};
+
+use crate::{
+    Editor, GotoDefinitionKind, SelectionEffects,
+    actions::{PeekDefinition, PeekImplementation, PeekReferences},
+    display_map::{
+        BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId, HighlightKey,
+    },
+    hover_links, multibuffer_context_lines,
+    scroll::Autoscroll,
+};
+
+/// How many lines of the peeked locations are visible at once.
+const PEEK_EDITOR_LINES: u32 = 12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PeekKind {
+    Definition,
+    Implementation,
+    References,
+}
+
+impl PeekKind {
+    fn title(self, symbol: &str) -> String {
+        match self {
+            PeekKind::Definition => format!("Definition of {symbol}"),
+            PeekKind::Implementation => format!("Implementations of {symbol}"),
+            PeekKind::References => format!("References to {symbol}"),
+        }
+    }
+}
+
+pub(crate) struct PeekState {
+    block_id: CustomBlockId,
+    /// The peek opened from this editor, followed by the peeks opened from
+    /// within it. Only the last one is shown.
+    stack: Vec<PeekEntry>,
+    collapsed: bool,
+}
+
+struct PeekEntry {
+    title: SharedString,
+    location_count: usize,
+    editor: Entity<Editor>,
+}
+
+impl Editor {
+    pub fn peek_definition(
+        &mut self,
+        _: &PeekDefinition,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.peek(PeekKind::Definition, window, cx)
+            .detach_and_log_err(cx);
+    }
+
+    pub fn peek_implementation(
+        &mut self,
+        _: &PeekImplementation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.peek(PeekKind::Implementation, window, cx)
+            .detach_and_log_err(cx);
+    }
+
+    pub fn peek_references(
+        &mut self,
+        _: &PeekReferences,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.peek(PeekKind::References, window, cx)
+            .detach_and_log_err(cx);
+    }
+
+    fn peek(
+        &mut self,
+        kind: PeekKind,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let head = self.selections.newest_anchor().head();
+        let Some((buffer, buffer_position)) =
+            self.buffer.read(cx).text_anchor_for_position(head, cx)
+        else {
+            return Task::ready(Ok(()));
+        };
+        let symbol = {
+            let snapshot = buffer.read(cx).snapshot();
+            let (word_range, _) =
+                snapshot.surrounding_word(buffer_position.to_offset(&snapshot), None);
+            snapshot.text_for_range(word_range).collect::<String>()
+        };
+
+        let locations = match kind {
+            PeekKind::Definition | PeekKind::Implementation => {
+                let definition_kind = if kind == PeekKind::Definition {
+                    GotoDefinitionKind::Symbol
+                } else {
+                    GotoDefinitionKind::Implementation
+                };
+                let Some(definitions) = self.semantics_provider.as_ref().and_then(|provider| {
+                    provider.definitions(&buffer, buffer_position, definition_kind, cx)
+                }) else {
+                    return Task::ready(Ok(()));
+                };
+                let buffer = buffer.clone();
+                cx.spawn(async move |_, cx| {
+                    let definitions = definitions.await?.unwrap_or_default();
+                    Ok(cx.update(|cx| {
+                        definitions
+                            .into_iter()
+                            .filter(|link| {
+                                hover_links::exclude_link_to_position(
+                                    &buffer,
+                                    &buffer_position,
+                                    link,
+                                    cx,
+                                )
+                            })
+                            .map(|link| link.target)
+                            .collect::<Vec<_>>()
+                    }))
+                })
+            }
+            PeekKind::References => {
+                let Some(project) = self.project.clone() else {
+                    return Task::ready(Ok(()));
+                };
+                let references = project.update(cx, |project, cx| {
+                    project.references(&buffer, buffer_position, cx)
+                });
+                cx.background_spawn(async move { Ok(references.await?.unwrap_or_default()) })
+            }
+        };
+
+        cx.spawn_in(window, async move |editor, cx| {
+            let locations = locations.await?;
+            if locations.is_empty() {
+                return Ok(());
+            }
+            editor.update_in(cx, |editor, window, cx| {
+                let title = kind.title(&symbol);
+                if let Some(host) = editor.peek_host.as_ref().and_then(WeakEntity::upgrade) {
+                    host.update(cx, |host, cx| {
+                        host.show_peek(title, locations, true, window, cx)
+                    });
+                } else {
+                    editor.show_peek(title, locations, false, window, cx);
+                }
+            })
+        })
+    }
+
+    fn show_peek(
+        &mut self,
+        title: String,
+        locations: Vec<Location>,
+        stacked: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let location_count = locations.len();
+        let mut locations = locations
+            .into_iter()
+            .map(|location| {
+                let snapshot = location.buffer.read(cx).snapshot();
+                let range = location.range.start.to_point(&snapshot)
+                    ..location.range.end.to_point(&snapshot);
+                (location.buffer, range)
+            })
+            .into_group_map();
+        for ranges in locations.values_mut() {
+            ranges.sort_by_key(|range| (range.start, Reverse(range.end)));
+            ranges.dedup();
+        }
+
+        let peek_editor = self.new_peek_editor(&title, locations, project, window, cx);
+        let entry = PeekEntry {
+            title: title.into(),
+            location_count,
+            editor: peek_editor.clone(),
+        };
+
+        if stacked && let Some(peek) = self.peek.as_mut() {
+            peek.stack.push(entry);
+            if peek.collapsed {
+                peek.collapsed = false;
+                let block_id = peek.block_id;
+                self.resize_blocks(
+                    HashMap::from_iter([(block_id, peek_block_height(false))]),
+                    None,
+                    cx,
+                );
+            }
+        } else {
+            self.close_peek(window, cx);
+            let host = cx.entity().downgrade();
+            let placement = BlockPlacement::Below(self.selections.newest_anchor().head());
+            let block_id = self.insert_blocks(
+                [BlockProperties {
+                    style: BlockStyle::Sticky,
+                    placement,
+                    height: Some(peek_block_height(false)),
+                    render: Arc::new(move |cx| render_peek(&host, cx)),
+                    priority: 0,
+                }],
+                Some(Autoscroll::fit()),
+                cx,
+            )[0];
+            self.peek = Some(PeekState {
+                block_id,
+                stack: vec![entry],
+                collapsed: false,
+            });
+        }
+
+        window.focus(&peek_editor.focus_handle(cx), cx);
+        cx.notify();
+    }
+
+    fn new_peek_editor(
+        &self,
+        title: &str,
+        locations: std::collections::HashMap<Entity<Buffer>, Vec<std::ops::Range<Point>>>,
+        project: Entity<project::Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Editor> {
+        let capability = project.read(cx).capability();
+        let mut ranges = Vec::new();
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::new(capability);
+            for (buffer, ranges_for_buffer) in locations {
+                let (new_ranges, _) = multibuffer.set_excerpts_for_path(
+                    PathKey::for_buffer(&buffer, cx),
+                    buffer,
+                    ranges_for_buffer,
+                    multibuffer_context_lines(cx),
+                    cx,
+                );
+                ranges.extend(new_ranges);
+            }
+            multibuffer.with_title(title.to_string())
+        });
+
+        let host = cx.entity().downgrade();
+        let workspace = self.workspace.clone();
+        cx.new(|cx| {
+            let mut editor = Editor::for_multibuffer(multibuffer, Some(project), window, cx);
+            editor.peek_host = Some(host);
+            editor.workspace = workspace;
+            if let Some(first_range) = ranges.first() {
+                let start = first_range.start;
+                editor.change_selections(
+                    SelectionEffects::scroll(Autoscroll::center()),
+                    window,
+                    cx,
+                    |selections| selections.select_anchor_ranges([start..start]),
+                );
+            }
+            editor.highlight_background(
+                HighlightKey::Editor,
+                &ranges,
+                |_, theme| theme.colors().editor_highlighted_line_background,
+                cx,
+            );
+            editor
+        })
+    }
+
+    #[cfg(test)]
+    pub(crate) fn peek_editor(&self) -> Option<Entity<Editor>> {
+        let entry = self.peek.as_ref()?.stack.last()?;
+        Some(entry.editor.clone())
+    }
+
+    /// Closes the peek shown in this editor, or the one this editor is shown
+    /// in, returning whether there was one.
+    pub(crate) fn close_peek(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        if let Some(host) = self.peek_host.as_ref().and_then(WeakEntity::upgrade) {
+            return host.update(cx, |host, cx| host.close_peek(window, cx));
+        }
+        let Some(peek) = self.peek.take() else {
+            return false;
+        };
+        self.remove_blocks(HashSet::from_iter([peek.block_id]), None, cx);
+        window.focus(&self.focus_handle, cx);
+        cx.notify();
+        true
+    }
+
+    fn pop_peek(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(peek) = self.peek.as_mut() else {
+            return;
+        };
+        peek.stack.pop();
+        match peek.stack.last() {
+            Some(entry) => {
+                let focus_handle = entry.editor.focus_handle(cx);
+                window.focus(&focus_handle, cx);
+                cx.notify();
+            }
+            None => {
+                self.close_peek(window, cx);
+            }
+        }
+    }
+
+    fn toggle_peek_collapsed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(peek) = self.peek.as_mut() else {
+            return;
+        };
+        peek.collapsed = !peek.collapsed;
+        let (block_id, collapsed) = (peek.block_id, peek.collapsed);
+        self.resize_blocks(
+            HashMap::from_iter([(block_id, peek_block_height(collapsed))]),
+            None,
+            cx,
+        );
+        if collapsed {
+            window.focus(&self.focus_handle, cx);
+        }
+        cx.notify();
+    }
+}
+
+fn peek_block_height(collapsed: bool) -> u32 {
+    if collapsed { 1 } else { PEEK_EDITOR_LINES + 1 }
+}
+
+fn render_peek(host: &WeakEntity<Editor>, cx: &mut BlockContext) -> AnyElement {
+    let Some(host_editor) = host.upgrade() else {
+        return div().into_any_element();
+    };
+    let Some(peek) = host_editor.read(cx).peek.as_ref() else {
+        return div().into_any_element();
+    };
+    let Some(entry) = peek.stack.last() else {
+        return div().into_any_element();
+    };
+    let title = entry.title.clone();
+    let location_count = entry.location_count;
+    let peek_editor = entry.editor.clone();
+    let stack_depth = peek.stack.len();
+    let collapsed = peek.collapsed;
+    let colors = cx.theme().colors();
+
+    let header = h_flex()
+        .h(cx.line_height)
+        .px_2()
+        .gap_1()
+        .justify_between()
+        .bg(colors.editor_subheader_background)
+        .child(
+            h_flex()
+                .gap_1()
+                .child(
+                    Disclosure::new("peek-disclosure", !collapsed).on_toggle_expanded({
+                        let host = host.clone();
+                        Arc::new(move |_, window, cx| {
+                            host.update(cx, |editor, cx| editor.toggle_peek_collapsed(window, cx))
+                                .ok();
+                        })
+                    }),
+                )
+                .child(Label::new(title).size(LabelSize::Small))
+                .when(location_count > 1, |this| {
+                    this.child(
+                        Label::new(format!("{location_count} results"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                }),
+        )
+        .child(
+            h_flex()
+                .gap_1()
+                .when(stack_depth > 1, |this| {
+                    this.child(
+                        IconButton::new("peek-back", IconName::ArrowLeft)
+                            .shape(IconButtonShape::Square)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Back to Previous Peek"))
+                            .on_click({
+                                let host = host.clone();
+                                move |_, window, cx| {
+                                    host.update(cx, |editor, cx| editor.pop_peek(window, cx))
+                                        .ok();
+                                }
+                            }),
+                    )
+                })
+                .child(
+                    IconButton::new("peek-close", IconName::Close)
+                        .shape(IconButtonShape::Square)
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("Close Peek"))
+                        .on_click({
+                            let host = host.clone();
+                            move |_, window, cx| {
+                                host.update(cx, |editor, cx| editor.close_peek(window, cx))
+                                    .ok();
+                            }
+                        }),
+                ),
+        );
+
+    v_flex()
+        .id("peek")
+        .occlude()
+        .w_full()
+        .border_y_1()
+        .border_color(colors.border)
+        .bg(colors.editor_background)
+        .child(header)
+        .when(!collapsed, |this| {
+            this.child(
+                div()
+                    .h(cx.line_height * PEEK_EDITOR_LINES as f32)
+                    .child(peek_editor),
+            )
+        })
+        .into_any_element()
+}
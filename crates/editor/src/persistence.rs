@@ -10,7 +10,8 @@ use db::{
 };
 use fs::MTime;
 use itertools::Itertools as _;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::{ops::Range, path::PathBuf};
 
 use workspace::{ItemId, WorkspaceDb, WorkspaceId};
 
@@ -87,6 +88,28 @@ impl Column for SerializedEditor {
     }
 }
 
+/// The undo history of a file, kept so that edits from previous sessions
+/// can still be undone.
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub(crate) struct SerializedUndoHistory {
+    /// The modification time of the file when the history was saved, as
+    /// seconds and nanoseconds.
+    pub(crate) mtime: Option<(u64, u32)>,
+    /// The length and checksum of the text the most recent step applies to,
+    /// for noticing when the text was restored differently.
+    pub(crate) len: usize,
+    pub(crate) checksum: u64,
+    /// The steps to undo, from the oldest to the most recent.
+    pub(crate) steps: Vec<SerializedUndoStep>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub(crate) struct SerializedUndoStep {
+    /// The edits reverting the step, as byte ranges in the text after it and
+    /// the text to replace them with.
+    pub(crate) edits: Vec<(Range<usize>, String)>,
+}
+
 pub struct EditorDb(db::sqlez::thread_safe_connection::ThreadSafeConnection);
 
 impl Domain for EditorDb {
@@ -123,6 +146,12 @@ impl Domain for EditorDb {
     //   start_fingerprint: Option<String>,
     //   end_fingerprint: Option<String>,
     // )
+    //
+    // editor_undo_history(
+    //   workspace_id: usize,
+    //   path: PathBuf,
+    //   history: String,
+    // )
 
     const MIGRATIONS: &[&str] = &[
         sql! (
@@ -203,6 +232,18 @@ impl Domain for EditorDb {
             ALTER TABLE editor_folds ADD COLUMN start_fingerprint TEXT;
             ALTER TABLE editor_folds ADD COLUMN end_fingerprint TEXT;
         ),
+        sql! (
+            // Keyed by path rather than by editor, so that the history outlives
+            // the editors showing the file.
+            CREATE TABLE editor_undo_history (
+                workspace_id INTEGER NOT NULL,
+                path BLOB NOT NULL,
+                history TEXT NOT NULL,
+                PRIMARY KEY(workspace_id, path),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+            ) STRICT;
+        ),
     ];
 }
 
@@ -287,6 +328,59 @@ impl EditorDb {
         }
     }
 
+    query! {
+        fn get_editor_undo_history_json(
+            workspace_id: WorkspaceId,
+            path: PathBuf
+        ) -> Result<Option<String>> {
+            SELECT history
+            FROM editor_undo_history
+            WHERE workspace_id = ?1 AND path = ?2
+        }
+    }
+
+    query! {
+        async fn save_editor_undo_history_json(
+            workspace_id: WorkspaceId,
+            path: PathBuf,
+            history: String
+        ) -> Result<()> {
+            INSERT OR REPLACE INTO editor_undo_history (workspace_id, path, history)
+            VALUES (?1, ?2, ?3)
+        }
+    }
+
+    query! {
+        pub async fn delete_editor_undo_history(
+            workspace_id: WorkspaceId,
+            path: PathBuf
+        ) -> Result<()> {
+            DELETE FROM editor_undo_history
+            WHERE workspace_id = ?1 AND path = ?2
+        }
+    }
+
+    pub(crate) fn get_editor_undo_history(
+        &self,
+        workspace_id: WorkspaceId,
+        path: PathBuf,
+    ) -> Result<Option<SerializedUndoHistory>> {
+        self.get_editor_undo_history_json(workspace_id, path)?
+            .map(|history| Ok(serde_json::from_str(&history)?))
+            .transpose()
+    }
+
+    pub(crate) async fn save_editor_undo_history(
+        &self,
+        workspace_id: WorkspaceId,
+        path: PathBuf,
+        history: SerializedUndoHistory,
+    ) -> Result<()> {
+        log::debug!("Saving undo history for {path:?} in workspace {workspace_id:?}");
+        self.save_editor_undo_history_json(workspace_id, path, serde_json::to_string(&history)?)
+            .await
+    }
+
     // Migrate folds from an old editor_id to a new one.
     // This is needed because entity IDs change between sessions, but workspace
     // cleanup deletes old editor rows (cascading to folds) before the new
@@ -0,0 +1,269 @@
+//! Keeps the undo history of files across sessions, so that edits made before
+//! a restart can still be undone.
+//!
+//! Only the text changes are kept: each step stores the edits that revert it,
+//! computed by diffing the buffer versions before and after its transaction.
+//! Steps restored from a previous session are undone once the buffer's own
+//! undo stack is exhausted.
+
+use std::hash::Hasher as _;
+
+use anyhow::Context as _;
+use collections::FxHasher;
+use gpui::Task;
+use multi_buffer::MultiBufferOffset;
+use settings::Settings as _;
+use text::Rope;
+use ui::{Context, Window};
+use util::ResultExt as _;
+use workspace::{
+    RestoreOnStartupBehavior, SERIALIZATION_THROTTLE_TIME, WorkspaceId, WorkspaceSettings,
+    item::ItemBufferKind,
+};
+
+use crate::{
+    Autoscroll, Editor, SelectionEffects,
+    persistence::{DB, SerializedUndoHistory, SerializedUndoStep},
+};
+
+/// How many steps are kept per file.
+const MAX_PERSISTED_UNDO_STEPS: usize = 100;
+
+/// How much text the kept steps may insert in total, in bytes.
+const MAX_PERSISTED_UNDO_TEXT_LEN: usize = 1024 * 1024;
+
+/// Files larger than this, in bytes, don't keep their history, since each step
+/// is computed by diffing whole versions of the file.
+const MAX_PERSISTED_UNDO_BUFFER_LEN: usize = 4 * 1024 * 1024;
+
+/// Undo history from a previous session that is yet to be undone.
+pub(crate) struct RestoredUndoHistory {
+    /// The text the most recent step applies to.
+    base_text: Rope,
+    /// The steps to undo, from the oldest to the most recent.
+    steps: Vec<SerializedUndoStep>,
+}
+
+impl Editor {
+    fn persists_undo_history(&self, cx: &Context<Self>) -> bool {
+        self.buffer_kind(cx) == ItemBufferKind::Singleton
+            && !self.mode.is_minimap()
+            && WorkspaceSettings::get(None, cx).restore_on_startup
+                != RestoreOnStartupBehavior::EmptyTab
+    }
+
+    /// Saves the undo history once the edits pause, like the selections, so
+    /// that typing doesn't collect and diff the buffer's versions on every
+    /// keystroke.
+    pub(crate) fn serialize_undo_history(&mut self, cx: &mut Context<Self>) {
+        if !self.persists_undo_history(cx) {
+            return;
+        }
+        self.serialize_undo_history = cx.spawn(async move |editor, cx| {
+            cx.background_executor()
+                .timer(SERIALIZATION_THROTTLE_TIME)
+                .await;
+            let Ok(Some(save_undo_history)) =
+                editor.update(cx, |editor, cx| editor.save_undo_history(cx))
+            else {
+                return;
+            };
+            save_undo_history.await;
+        });
+    }
+
+    /// Diffs the versions of the buffer's undo stack on the background
+    /// executor, and saves the resulting steps.
+    fn save_undo_history(&self, cx: &mut Context<Self>) -> Option<Task<()>> {
+        let workspace_id = self.workspace_serialization_id(cx)?;
+        let buffer = self.buffer.read(cx).as_singleton()?;
+        let buffer = buffer.read(cx);
+        let path = buffer.file()?.full_path(cx);
+        if buffer.len() > MAX_PERSISTED_UNDO_BUFFER_LEN {
+            return Some(cx.background_spawn(async move {
+                DB.delete_editor_undo_history(workspace_id, path)
+                    .await
+                    .log_err();
+            }));
+        }
+
+        let snapshot = buffer.text_snapshot();
+        let start_versions = buffer.undo_stack_start_versions();
+        let mtime = buffer
+            .saved_mtime()
+            .and_then(|mtime| mtime.to_seconds_and_nanos_for_persistence());
+        let restored_history = self
+            .restored_undo_history
+            .as_ref()
+            .map(|history| (history.base_text.clone(), history.steps.clone()));
+        Some(cx.background_spawn(async move {
+            let text = snapshot.as_rope();
+            let mut steps = Vec::new();
+            let mut inserted_len = 0;
+            let mut push_step = |step: SerializedUndoStep| {
+                inserted_len += step.edits.iter().map(|(_, text)| text.len()).sum::<usize>();
+                if steps.len() == MAX_PERSISTED_UNDO_STEPS
+                    || inserted_len > MAX_PERSISTED_UNDO_TEXT_LEN
+                {
+                    return false;
+                }
+                steps.push(step);
+                true
+            };
+
+            let mut text_after = text.to_string();
+            let mut reached_session_start = true;
+            for version in start_versions.iter().rev() {
+                let text_before = snapshot.rope_for_version(version).to_string();
+                let step = SerializedUndoStep {
+                    edits: language::text_diff(&text_after, &text_before)
+                        .into_iter()
+                        .map(|(range, new_text)| (range, new_text.to_string()))
+                        .collect(),
+                };
+                if !push_step(step) {
+                    reached_session_start = false;
+                    break;
+                }
+                text_after = text_before;
+            }
+            if reached_session_start
+                && let Some((base_text, restored_steps)) = restored_history
+                && base_text.equals_str(&text_after)
+            {
+                for step in restored_steps.into_iter().rev() {
+                    if !push_step(step) {
+                        break;
+                    }
+                }
+            }
+            steps.reverse();
+
+            let history = SerializedUndoHistory {
+                mtime,
+                len: text.len(),
+                checksum: text_checksum(text),
+                steps,
+            };
+            DB.save_editor_undo_history(workspace_id, path.clone(), history)
+                .await
+                .with_context(|| {
+                    format!("persisting undo history for {path:?}, workspace {workspace_id:?}")
+                })
+                .log_err();
+        }))
+    }
+
+    /// Loads the history saved for the editor's file, unless the file changed
+    /// since.
+    pub(crate) fn restore_undo_history(&mut self, workspace_id: WorkspaceId, cx: &Context<Self>) {
+        if !self.persists_undo_history(cx) {
+            return;
+        }
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+        let buffer = buffer.read(cx);
+        let Some(file) = buffer.file() else {
+            return;
+        };
+        let Some(history) = DB
+            .get_editor_undo_history(workspace_id, file.full_path(cx))
+            .log_err()
+            .flatten()
+        else {
+            return;
+        };
+
+        let text = buffer.as_rope();
+        let mtime = buffer
+            .saved_mtime()
+            .and_then(|mtime| mtime.to_seconds_and_nanos_for_persistence());
+        if history.steps.is_empty()
+            || history.mtime != mtime
+            || history.len != text.len()
+            || history.checksum != text_checksum(text)
+        {
+            return;
+        }
+        self.restored_undo_history = Some(RestoredUndoHistory {
+            base_text: text.clone(),
+            steps: history.steps,
+        });
+    }
+
+    /// Undoes the most recent step restored from a previous session. Called
+    /// once the buffer has nothing left to undo.
+    pub(crate) fn undo_restored_history(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+        let Some(history) = self.restored_undo_history.as_mut() else {
+            return;
+        };
+        let text = buffer.read(cx).as_rope().clone();
+        let step = history.steps.pop();
+        // The buffer only returns to the text the steps apply to when everything
+        // done in this session was undone.
+        let Some(step) = step.filter(|step| {
+            history.base_text.len() == text.len()
+                && history.base_text.chars().eq(text.chars())
+                && edits_are_valid(step, &text)
+        }) else {
+            self.restored_undo_history = None;
+            return;
+        };
+
+        let mut edited_ranges = Vec::with_capacity(step.edits.len());
+        let mut delta = 0isize;
+        for (range, new_text) in &step.edits {
+            let start = range.start.saturating_add_signed(delta);
+            edited_ranges.push(MultiBufferOffset(start)..MultiBufferOffset(start + new_text.len()));
+            delta += new_text.len() as isize - range.len() as isize;
+        }
+        let new_text = buffer.update(cx, |buffer, cx| {
+            buffer.start_transaction();
+            buffer.edit(step.edits, None, cx);
+            // Undoing the step's transaction would redo the step.
+            if let Some(transaction_id) = buffer.end_transaction(cx) {
+                buffer.forget_transaction(transaction_id);
+            }
+            buffer.as_rope().clone()
+        });
+        if let Some(history) = self.restored_undo_history.as_mut() {
+            history.base_text = new_text;
+        }
+
+        self.change_selections(SelectionEffects::no_scroll(), window, cx, |selections| {
+            selections.select_ranges(edited_ranges);
+        });
+        self.request_autoscroll(Autoscroll::fit(), cx);
+    }
+}
+
+/// Whether the step's edits are sorted char ranges within `text`, which they
+/// are unless the saved history was damaged.
+fn edits_are_valid(step: &SerializedUndoStep, text: &Rope) -> bool {
+    let mut previous_end = 0;
+    step.edits.iter().all(|(range, _)| {
+        let valid = previous_end <= range.start
+            && range.start <= range.end
+            && range.end <= text.len()
+            && text.is_char_boundary(range.start)
+            && text.is_char_boundary(range.end);
+        previous_end = range.end;
+        valid
+    })
+}
+
+fn text_checksum(text: &Rope) -> u64 {
+    let mut hasher = FxHasher::default();
+    // Written byte by byte, so that the checksum doesn't depend on how the
+    // text is split into chunks.
+    for chunk in text.bytes_in_range(0..text.len()) {
+        for byte in chunk {
+            hasher.write_u8(*byte);
+        }
+    }
+    hasher.finish()
+}
@@ -0,0 +1,573 @@
+//! Previewing a rename: every edit the language server would make, grouped by
+//! file, with checkboxes to leave out occurrences or whole files before the
+//! rest are applied.
+
+use std::{ops::Range, sync::Arc};
+
+use gpui::{
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ScrollHandle, Task, WeakEntity,
+};
+use language::{Buffer, Point};
+use project::{ProjectTransaction, RenameBufferEdits};
+use text::{ToOffset as _, ToPoint as _};
+use ui::{Checkbox, HighlightedLabel, KeyBinding, prelude::*};
+use workspace::{ModalView, Workspace};
+
+use crate::{Editor, actions::ConfirmRenameWithPreview};
+
+pub struct RenamePreview {
+    title: SharedString,
+    files: Vec<PreviewFile>,
+    editor: WeakEntity<Editor>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+}
+
+struct PreviewFile {
+    buffer: Entity<Buffer>,
+    path: SharedString,
+    occurrences: Vec<PreviewOccurrence>,
+}
+
+struct PreviewOccurrence {
+    range: Range<text::Anchor>,
+    new_text: Arc<str>,
+    row: u32,
+    /// The line of the occurrence as it reads after the rename.
+    line: SharedString,
+    /// The range of the new text in `line`.
+    new_text_range: Range<usize>,
+    included: bool,
+}
+
+impl PreviewFile {
+    fn toggle_state(&self) -> ToggleState {
+        let included_count = self
+            .occurrences
+            .iter()
+            .filter(|occurrence| occurrence.included)
+            .count();
+        if included_count == 0 {
+            ToggleState::Unselected
+        } else if included_count == self.occurrences.len() {
+            ToggleState::Selected
+        } else {
+            ToggleState::Indeterminate
+        }
+    }
+}
+
+impl Editor {
+    pub fn confirm_rename_with_preview(
+        &mut self,
+        _: &ConfirmRenameWithPreview,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<anyhow::Result<()>>> {
+        let (buffer, position, old_name, new_name) = self.take_rename_target(window, cx)?;
+        let workspace = self.workspace()?.downgrade();
+        let semantics_provider = self.semantics_provider.clone()?;
+        let rename_edits =
+            semantics_provider.rename_edits(&buffer, position, new_name.clone(), cx)?;
+
+        Some(cx.spawn_in(window, async move |editor, cx| {
+            let title = format!("Rename: {} → {}", old_name, new_name);
+            let Some(buffer_edits) = rename_edits.await? else {
+                // The rename also creates, renames or deletes files, which
+                // can't be left out, so it's applied as a whole.
+                let Some(rename) = editor.update(cx, |_, cx| {
+                    semantics_provider.perform_rename(&buffer, position, new_name, cx)
+                })?
+                else {
+                    return Ok(());
+                };
+                let project_transaction = rename.await?;
+                return Self::open_project_transaction(
+                    &editor,
+                    workspace,
+                    project_transaction,
+                    title,
+                    cx,
+                )
+                .await;
+            };
+            if buffer_edits
+                .iter()
+                .all(|buffer_edits| buffer_edits.edits.is_empty())
+            {
+                return Ok(());
+            }
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let workspace_handle = cx.entity().downgrade();
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    RenamePreview::new(title, buffer_edits, editor, workspace_handle, window, cx)
+                });
+            })?;
+            Ok(())
+        }))
+    }
+}
+
+impl RenamePreview {
+    fn new(
+        title: String,
+        buffer_edits: Vec<RenameBufferEdits>,
+        editor: WeakEntity<Editor>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut files = buffer_edits
+            .into_iter()
+            .filter(|buffer_edits| !buffer_edits.edits.is_empty())
+            .map(|buffer_edits| preview_file(buffer_edits, cx))
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let focus_handle = cx.focus_handle();
+        window.focus(&focus_handle, cx);
+        Self {
+            title: title.into(),
+            files,
+            editor,
+            workspace,
+            focus_handle,
+            scroll_handle: ScrollHandle::new(),
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let mut project_transaction = ProjectTransaction::default();
+        for file in &self.files {
+            let edits = file
+                .occurrences
+                .iter()
+                .filter(|occurrence| occurrence.included)
+                .map(|occurrence| (occurrence.range.clone(), occurrence.new_text.clone()))
+                .collect::<Vec<_>>();
+            if edits.is_empty() {
+                continue;
+            }
+            let transaction = file.buffer.update(cx, |buffer, cx| {
+                buffer.finalize_last_transaction();
+                buffer.start_transaction();
+                buffer.edit(edits, None, cx);
+                buffer.end_transaction(cx).and_then(|transaction_id| {
+                    buffer.finalize_last_transaction();
+                    buffer.get_transaction(transaction_id).cloned()
+                })
+            });
+            if let Some(transaction) = transaction {
+                project_transaction
+                    .0
+                    .insert(file.buffer.clone(), transaction);
+            }
+        }
+
+        let editor = self.editor.clone();
+        let workspace = self.workspace.clone();
+        let title = self.title.to_string();
+        cx.spawn_in(window, async move |_, cx| {
+            Editor::open_project_transaction(&editor, workspace, project_transaction, title, cx)
+                .await?;
+            editor.update(cx, |editor, cx| {
+                editor.refresh_document_highlights(cx);
+            })
+        })
+        .detach_and_log_err(cx);
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn toggle_file(&mut self, file_index: usize, cx: &mut Context<Self>) {
+        if let Some(file) = self.files.get_mut(file_index) {
+            let included = file.toggle_state() != ToggleState::Selected;
+            for occurrence in &mut file.occurrences {
+                occurrence.included = included;
+            }
+            cx.notify();
+        }
+    }
+
+    fn toggle_occurrence(
+        &mut self,
+        file_index: usize,
+        occurrence_index: usize,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(occurrence) = self
+            .files
+            .get_mut(file_index)
+            .and_then(|file| file.occurrences.get_mut(occurrence_index))
+        {
+            occurrence.included = !occurrence.included;
+            cx.notify();
+        }
+    }
+
+    fn render_file(
+        &self,
+        file_index: usize,
+        file: &PreviewFile,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        v_flex()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .py_1()
+                    .child(
+                        Checkbox::new(("rename-preview-file", file_index), file.toggle_state())
+                            .on_click(
+                                cx.listener(move |this, _, _, cx| this.toggle_file(file_index, cx)),
+                            ),
+                    )
+                    .child(Label::new(file.path.clone()).size(LabelSize::Small))
+                    .child(
+                        Label::new(file.occurrences.len().to_string())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .children(
+                file.occurrences
+                    .iter()
+                    .enumerate()
+                    .map(|(occurrence_index, occurrence)| {
+                        let highlight_indices = occurrence
+                            .line
+                            .char_indices()
+                            .map(|(index, _)| index)
+                            .filter(|index| occurrence.new_text_range.contains(index))
+                            .collect();
+                        h_flex()
+                            .pl_6()
+                            .gap_2()
+                            .child(
+                                Checkbox::new(
+                                    SharedString::from(format!(
+                                        "rename-preview-occurrence-{file_index}-{occurrence_index}"
+                                    )),
+                                    ToggleState::from(occurrence.included),
+                                )
+                                .on_click(cx.listener(
+                                    move |this, _, _, cx| {
+                                        this.toggle_occurrence(file_index, occurrence_index, cx)
+                                    },
+                                )),
+                            )
+                            .child(
+                                Label::new(format!("{}", occurrence.row + 1))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(
+                                HighlightedLabel::new(occurrence.line.clone(), highlight_indices)
+                                    .size(LabelSize::Small)
+                                    .truncate(),
+                            )
+                    }),
+            )
+    }
+}
+
+fn preview_file(buffer_edits: RenameBufferEdits, cx: &App) -> PreviewFile {
+    let buffer = buffer_edits.buffer.read(cx);
+    let snapshot = buffer.snapshot();
+    let path = buffer
+        .file()
+        .map(|file| file.full_path(cx).to_string_lossy().into_owned())
+        .unwrap_or_else(|| "untitled".to_string());
+
+    let mut edits = buffer_edits.edits;
+    edits.sort_by_key(|(range, _)| range.start.to_offset(&snapshot));
+    let occurrences = edits
+        .into_iter()
+        .map(|(range, new_text)| {
+            let start = range.start.to_point(&snapshot);
+            let end = range.end.to_point(&snapshot);
+            let prefix = snapshot
+                .text_for_range(Point::new(start.row, 0)..start)
+                .collect::<String>();
+            let suffix = snapshot
+                .text_for_range(end..Point::new(end.row, snapshot.line_len(end.row)))
+                .collect::<String>();
+            let prefix = prefix.trim_start();
+            let new_text_start = prefix.len();
+            let new_text_end = new_text_start + new_text.len();
+            PreviewOccurrence {
+                line: format!("{prefix}{new_text}{}", suffix.trim_end()).into(),
+                new_text_range: new_text_start..new_text_end,
+                row: start.row,
+                range,
+                new_text,
+                included: true,
+            }
+        })
+        .collect();
+
+    PreviewFile {
+        buffer: buffer_edits.buffer.clone(),
+        path: path.into(),
+        occurrences,
+    }
+}
+
+impl EventEmitter<DismissEvent> for RenamePreview {}
+
+impl ModalView for RenamePreview {}
+
+impl Focusable for RenamePreview {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for RenamePreview {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let occurrence_count = self
+            .files
+            .iter()
+            .map(|file| file.occurrences.len())
+            .sum::<usize>();
+        let included_count = self
+            .files
+            .iter()
+            .flat_map(|file| &file.occurrences)
+            .filter(|occurrence| occurrence.included)
+            .count();
+
+        v_flex()
+            .key_context("RenamePreview")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(40.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .child(Headline::new(self.title.clone()).size(HeadlineSize::XSmall)),
+            )
+            .child(
+                v_flex()
+                    .id("rename-preview-files")
+                    .px_3()
+                    .max_h(rems(24.))
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .children(
+                        self.files
+                            .iter()
+                            .enumerate()
+                            .map(|(file_index, file)| self.render_file(file_index, file, cx)),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .px_3()
+                    .py_2()
+                    .gap_1()
+                    .justify_between()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new(format!(
+                            "{included_count} of {occurrence_count} occurrences in {} files",
+                            self.files.len()
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Button::new("rename-preview-cancel", "Cancel")
+                                    .key_binding(
+                                        KeyBinding::for_action_in(
+                                            &menu::Cancel,
+                                            &self.focus_handle,
+                                            cx,
+                                        )
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                    )
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.cancel(&menu::Cancel, window, cx)
+                                    })),
+                            )
+                            .child(
+                                Button::new("rename-preview-apply", "Apply")
+                                    .disabled(included_count == 0)
+                                    .key_binding(
+                                        KeyBinding::for_action_in(
+                                            &menu::Confirm,
+                                            &self.focus_handle,
+                                            cx,
+                                        )
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                    )
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.confirm(&menu::Confirm, window, cx)
+                                    })),
+                            ),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt as _;
+    use gpui::TestAppContext;
+    use indoc::indoc;
+    use util::path;
+
+    use super::*;
+    use crate::{
+        actions::Rename, editor_tests::init_test,
+        test::editor_lsp_test_context::EditorLspTestContext,
+    };
+
+    fn occurrence_lines(preview: &RenamePreview) -> Vec<(&str, Vec<(u32, &str, &str)>)> {
+        preview
+            .files
+            .iter()
+            .map(|file| {
+                let occurrences = file
+                    .occurrences
+                    .iter()
+                    .map(|occurrence| {
+                        (
+                            occurrence.row,
+                            occurrence.line.as_ref(),
+                            &occurrence.line[occurrence.new_text_range.clone()],
+                        )
+                    })
+                    .collect();
+                (file.path.as_ref(), occurrences)
+            })
+            .collect()
+    }
+
+    #[gpui::test]
+    async fn test_rename_preview(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let capabilities = lsp::ServerCapabilities {
+            rename_provider: Some(lsp::OneOf::Left(true)),
+            ..Default::default()
+        };
+        let mut cx = EditorLspTestContext::new_rust(capabilities, cx).await;
+        let fs = cx.update_workspace(|workspace, _, cx| workspace.project().read(cx).fs().clone());
+        fs.as_fake()
+            .insert_file(
+                path!("/root/dir/other.rs"),
+                "fn other(foo: Foo) -> Foo {\n    foo\n}\n".into(),
+            )
+            .await;
+        cx.run_until_parked();
+
+        cx.set_state(indoc! {"
+            struct Fˇoo {}
+        "});
+        cx.update_editor(|editor, window, cx| editor.rename(&Rename, window, cx))
+            .expect("Prepare rename was not started")
+            .await
+            .expect("Prepare rename failed");
+        cx.update_editor(|editor, window, cx| {
+            let rename_editor = editor.pending_rename.as_ref().unwrap().editor.clone();
+            rename_editor.update(cx, |rename_editor, cx| {
+                rename_editor.set_text("Bar", window, cx)
+            });
+        });
+
+        let mut rename_handler = cx.set_request_handler::<lsp::request::Rename, _, _>(
+            move |url, params, _| async move {
+                assert_eq!(params.new_name, "Bar");
+                let edit = |line, start, end| {
+                    lsp::TextEdit::new(
+                        lsp::Range::new(
+                            lsp::Position::new(line, start),
+                            lsp::Position::new(line, end),
+                        ),
+                        "Bar".to_string(),
+                    )
+                };
+                Ok(Some(lsp::WorkspaceEdit::new(
+                    std::collections::HashMap::from_iter([
+                        (url, vec![edit(0, 7, 10)]),
+                        (
+                            lsp::Uri::from_file_path(path!("/root/dir/other.rs")).unwrap(),
+                            vec![edit(0, 22, 25), edit(0, 14, 17)],
+                        ),
+                    ]),
+                )))
+            },
+        );
+        let preview_task = cx
+            .update_editor(|editor, window, cx| {
+                editor.confirm_rename_with_preview(&ConfirmRenameWithPreview, window, cx)
+            })
+            .expect("Rename preview was not started");
+        rename_handler.next().await.unwrap();
+        preview_task.await.expect("Rename preview failed");
+        cx.run_until_parked();
+
+        // Nothing is renamed until the preview is confirmed.
+        cx.assert_editor_state(indoc! {"
+            struct Fˇoo {}
+        "});
+        let preview = cx
+            .update_workspace(|workspace, _, cx| workspace.active_modal::<RenamePreview>(cx))
+            .expect("Rename preview was not shown");
+        let other_buffer = preview.update(&mut cx.cx.cx, |preview, _| {
+            assert_eq!(preview.title.as_ref(), "Rename: Foo → Bar");
+            assert_eq!(
+                occurrence_lines(preview),
+                [
+                    (path!("root/dir/file.rs"), vec![(0, "struct Bar {}", "Bar")],),
+                    (
+                        path!("root/dir/other.rs"),
+                        vec![
+                            (0, "fn other(foo: Bar) -> Foo {", "Bar"),
+                            (0, "fn other(foo: Foo) -> Bar {", "Bar"),
+                        ],
+                    ),
+                ]
+            );
+            preview.files[1].buffer.clone()
+        });
+
+        preview.update(&mut cx.cx.cx, |preview, cx| {
+            preview.toggle_occurrence(1, 0, cx);
+            assert_eq!(preview.files[1].toggle_state(), ToggleState::Indeterminate);
+            preview.toggle_file(1, cx);
+            assert_eq!(preview.files[1].toggle_state(), ToggleState::Selected);
+            preview.toggle_file(1, cx);
+            assert_eq!(preview.files[1].toggle_state(), ToggleState::Unselected);
+            assert_eq!(preview.files[0].toggle_state(), ToggleState::Selected);
+        });
+        preview.update_in(&mut cx.cx.cx, |preview, window, cx| {
+            preview.confirm(&menu::Confirm, window, cx)
+        });
+        cx.run_until_parked();
+
+        // Only the edits of the file left checked are applied.
+        cx.update_editor(|editor, _, cx| {
+            assert_eq!(editor.text(cx), "struct Bar {}\n");
+        });
+        other_buffer.read_with(&cx.cx.cx, |buffer, _| {
+            assert_eq!(buffer.text(), "fn other(foo: Foo) -> Foo {\n    foo\n}\n");
+        });
+        cx.update_workspace(|workspace, _, cx| {
+            assert!(workspace.active_modal::<RenamePreview>(cx).is_none());
+        });
+    }
+}
@@ -0,0 +1,169 @@
+//! Values of the variables snippets can refer to, like `$TM_FILENAME` or
+//! `$CURRENT_YEAR`, as listed in the LSP and TextMate snippet syntax.
+
+use std::path::PathBuf;
+
+use gpui::Context;
+use multi_buffer::MultiBufferRow;
+use rand::Rng as _;
+use text::Point;
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::Editor;
+
+/// The context of the newest selection, captured when a snippet is about to
+/// be inserted.
+pub(crate) struct SnippetVariables {
+    selected_text: String,
+    current_line: String,
+    current_word: String,
+    line_index: u32,
+    file_path: Option<PathBuf>,
+    relative_file_path: Option<String>,
+    workspace_name: Option<String>,
+    workspace_folder: Option<PathBuf>,
+    clipboard: Option<String>,
+    line_comment: Option<String>,
+    block_comment: Option<(String, String)>,
+}
+
+impl SnippetVariables {
+    pub(crate) fn resolve(&self, name: &str) -> Option<String> {
+        let now = || {
+            let local_offset = UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC);
+            OffsetDateTime::now_utc().to_offset(local_offset)
+        };
+        let value = match name {
+            "TM_SELECTED_TEXT" => self.selected_text.clone(),
+            "TM_CURRENT_LINE" => self.current_line.clone(),
+            "TM_CURRENT_WORD" => self.current_word.clone(),
+            "TM_LINE_INDEX" => self.line_index.to_string(),
+            "TM_LINE_NUMBER" => (self.line_index + 1).to_string(),
+            "TM_FILENAME" => self
+                .file_path
+                .as_ref()?
+                .file_name()?
+                .to_string_lossy()
+                .into(),
+            "TM_FILENAME_BASE" => self
+                .file_path
+                .as_ref()?
+                .file_stem()?
+                .to_string_lossy()
+                .into(),
+            "TM_DIRECTORY" => self.file_path.as_ref()?.parent()?.to_string_lossy().into(),
+            "TM_FILEPATH" => self.file_path.as_ref()?.to_string_lossy().into(),
+            "RELATIVE_FILEPATH" => self.relative_file_path.clone()?,
+            "WORKSPACE_NAME" => self.workspace_name.clone()?,
+            "WORKSPACE_FOLDER" => self.workspace_folder.as_ref()?.to_string_lossy().into(),
+            "CLIPBOARD" => self.clipboard.clone()?,
+            "CURRENT_YEAR" => now().year().to_string(),
+            "CURRENT_YEAR_SHORT" => format!("{:02}", now().year() % 100),
+            "CURRENT_MONTH" => format!("{:02}", u8::from(now().month())),
+            "CURRENT_MONTH_NAME" => now().month().to_string(),
+            "CURRENT_MONTH_NAME_SHORT" => now().month().to_string().chars().take(3).collect(),
+            "CURRENT_DATE" => format!("{:02}", now().day()),
+            "CURRENT_DAY_NAME" => now().weekday().to_string(),
+            "CURRENT_DAY_NAME_SHORT" => now().weekday().to_string().chars().take(3).collect(),
+            "CURRENT_HOUR" => format!("{:02}", now().hour()),
+            "CURRENT_MINUTE" => format!("{:02}", now().minute()),
+            "CURRENT_SECOND" => format!("{:02}", now().second()),
+            "CURRENT_SECONDS_UNIX" => OffsetDateTime::now_utc().unix_timestamp().to_string(),
+            "CURRENT_TIMEZONE_OFFSET" => {
+                let (hours, minutes, _) = now().offset().as_hms();
+                format!("{hours:+03}:{:02}", minutes.abs())
+            }
+            "RANDOM" => format!("{:06}", rand::rng().random_range(0..1_000_000)),
+            "RANDOM_HEX" => format!("{:06x}", rand::rng().random_range(0..0x1000000)),
+            "UUID" => uuid::Uuid::new_v4().to_string(),
+            "LINE_COMMENT" => self.line_comment.clone()?,
+            "BLOCK_COMMENT_START" => self.block_comment.as_ref()?.0.clone(),
+            "BLOCK_COMMENT_END" => self.block_comment.as_ref()?.1.clone(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl Editor {
+    pub(crate) fn snippet_variables(&self, cx: &mut Context<Self>) -> SnippetVariables {
+        let display_snapshot = self.display_snapshot(cx);
+        let selection = self.selections.newest::<Point>(&display_snapshot);
+        let snapshot = display_snapshot.buffer_snapshot();
+        let selected_text = snapshot
+            .text_for_range(selection.range())
+            .collect::<String>();
+        let row = selection.head().row;
+        let current_line = snapshot
+            .text_for_range(
+                Point::new(row, 0)..Point::new(row, snapshot.line_len(MultiBufferRow(row))),
+            )
+            .collect::<String>();
+
+        let mut variables = SnippetVariables {
+            selected_text,
+            current_line,
+            current_word: String::new(),
+            line_index: row,
+            file_path: None,
+            relative_file_path: None,
+            workspace_name: None,
+            workspace_folder: None,
+            clipboard: cx
+                .read_from_clipboard()
+                .and_then(|clipboard_item| clipboard_item.text()),
+            line_comment: None,
+            block_comment: None,
+        };
+
+        let Some((buffer, buffer_point, _)) = self
+            .buffer
+            .read(cx)
+            .point_to_buffer_point(selection.head(), cx)
+        else {
+            return variables;
+        };
+        let buffer = buffer.read(cx);
+        let buffer_snapshot = buffer.snapshot();
+        let offset = buffer_snapshot.point_to_offset(buffer_point);
+        let (word_range, _) = buffer_snapshot.surrounding_word(offset, None);
+        variables.current_word = buffer_snapshot.text_for_range(word_range).collect();
+        variables.line_index = buffer_point.row;
+
+        if let Some(scope) = buffer_snapshot.language_scope_at(offset) {
+            variables.line_comment = scope
+                .line_comment_prefixes()
+                .first()
+                .map(|prefix| prefix.trim_end().to_string());
+            variables.block_comment = scope.block_comment().map(|block_comment| {
+                (
+                    block_comment.start.trim_end().to_string(),
+                    block_comment.end.trim_start().to_string(),
+                )
+            });
+        }
+
+        if let Some(file) = buffer.file() {
+            let relative_path = file.path();
+            variables.relative_file_path =
+                Some(relative_path.display(file.path_style(cx)).into_owned());
+            variables.workspace_name = file
+                .full_path(cx)
+                .components()
+                .next()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned());
+            if let Some(local_file) = file.as_local() {
+                let abs_path = local_file.abs_path(cx);
+                variables.workspace_folder = abs_path
+                    .ancestors()
+                    .nth(relative_path.components().count())
+                    .map(|folder| folder.to_path_buf());
+                variables.file_path = Some(abs_path);
+            } else {
+                variables.file_path = Some(file.full_path(cx));
+            }
+        }
+
+        variables
+    }
+}
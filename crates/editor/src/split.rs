@@ -6,9 +6,11 @@ use std::{
 use buffer_diff::{BufferDiff, BufferDiffSnapshot};
 use collections::HashMap;
 
-use gpui::{Action, AppContext as _, Entity, EventEmitter, Focusable, Subscription, WeakEntity};
+use gpui::{
+    Action, AppContext as _, Entity, EventEmitter, Focusable, Pixels, Subscription, WeakEntity,
+};
 use itertools::Itertools;
-use language::{Buffer, Capability};
+use language::{Buffer, Capability, LanguageName};
 use multi_buffer::{
     Anchor, BufferOffset, ExcerptId, ExcerptRange, ExpandExcerptDirection, MultiBuffer,
     MultiBufferDiffHunk, MultiBufferPoint, MultiBufferSnapshot, PathKey,
@@ -1634,6 +1636,10 @@ impl Item for SplittableEditor {
         self.rhs_editor.read(cx).buffer_kind(cx)
     }
 
+    fn language_name(&self, cx: &App) -> Option<LanguageName> {
+        self.rhs_editor.read(cx).language_name(cx)
+    }
+
     fn is_dirty(&self, cx: &App) -> bool {
         self.rhs_editor.read(cx).is_dirty(cx)
     }
@@ -1706,6 +1712,29 @@ impl Item for SplittableEditor {
         });
     }
 
+    fn set_buffer_font_size_delta(
+        &mut self,
+        delta: Pixels,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let editors =
+            std::iter::once(&self.rhs_editor).chain(self.lhs.as_ref().map(|lhs| &lhs.editor));
+        for editor in editors {
+            editor.update(cx, |editor, cx| {
+                editor.set_buffer_font_size_delta(delta, window, cx);
+            });
+        }
+    }
+
+    fn set_zen_mode(&mut self, zen_mode: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let editors =
+            std::iter::once(&self.rhs_editor).chain(self.lhs.as_ref().map(|lhs| &lhs.editor));
+        for editor in editors {
+            editor.update(cx, |editor, cx| editor.set_zen_mode(zen_mode, window, cx));
+        }
+    }
+
     fn added_to_workspace(
         &mut self,
         workspace: &mut Workspace,
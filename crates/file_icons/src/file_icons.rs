@@ -27,9 +27,6 @@ impl FileIcons {
                 .or_else(|| this.icon_theme.file_suffixes.get(suffix))
                 .and_then(|typ| this.get_icon_for_type(typ, cx))
         };
-        // TODO: Associate a type with the languages and have the file's language
-        //       override these associations
-
         if let Some(mut typ) = path.file_name().and_then(|typ| typ.to_str()) {
             // check if file name is in suffixes
             // e.g. catch file named `eslint.config.js` instead of `.eslint.config.js`
@@ -81,6 +78,27 @@ impl FileIcons {
         this.get_icon_for_type("default", cx)
     }
 
+    /// Returns the icon that the current icon theme associates with a language.
+    pub fn get_language_icon(language_name: &str, cx: &App) -> Option<SharedString> {
+        let this = Self::get(cx);
+        this.icon_theme
+            .file_languages
+            .get(language_name)
+            .and_then(|typ| this.get_icon_for_type(typ, cx))
+    }
+
+    /// Returns the icon for a file of the given language, falling back to the
+    /// icon for its path when the icon theme has none for the language.
+    pub fn get_icon_for_language(
+        language_name: Option<&str>,
+        path: &Path,
+        cx: &App,
+    ) -> Option<SharedString> {
+        language_name
+            .and_then(|language_name| Self::get_language_icon(language_name, cx))
+            .or_else(|| Self::get_icon(path, cx))
+    }
+
     fn default_icon_theme(cx: &App) -> Option<Arc<IconTheme>> {
         let theme_registry = ThemeRegistry::global(cx);
         theme_registry.default_icon_theme().ok()
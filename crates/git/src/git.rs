@@ -87,6 +87,14 @@ actions!(
         Amend,
         /// Enable the --signoff option.
         Signoff,
+        /// Enable the --gpg-sign option.
+        GpgSign,
+        /// Shows the previous message from the commit message history.
+        PreviousCommitMessage,
+        /// Shows the next message from the commit message history.
+        NextCommitMessage,
+        /// Opens a multibuffer with the staged changes, as they'll be committed.
+        ReviewStagedChanges,
         /// Cancels the current git operation.
         Cancel,
         /// Expands the commit message editor.
@@ -101,6 +109,12 @@ actions!(
         Clone,
         /// Adds a file to .gitignore.
         AddToGitignore,
+        /// Moves the cursor to the next merge conflict.
+        GoToNextConflict,
+        /// Moves the cursor to the previous merge conflict.
+        GoToPreviousConflict,
+        /// Opens the file's merge conflicts in a three-way merge view.
+        OpenMergeView,
     ]
 );
 
@@ -1,7 +1,10 @@
 use crate::branch_picker::{self, BranchList};
 use crate::git_panel::{GitPanel, commit_message_editor, panel_editor_style};
 use git::repository::CommitOptions;
-use git::{Amend, Commit, GenerateCommitMessage, Signoff};
+use git::{
+    Amend, Commit, GenerateCommitMessage, GpgSign, NextCommitMessage, PreviousCommitMessage,
+    ReviewStagedChanges, Signoff,
+};
 use panel::panel_button;
 use project::DisableAiSettings;
 use settings::Settings;
@@ -281,7 +284,9 @@ impl CommitModal {
                     let git_panel = git_panel_entity.read(cx);
                     let amend_enabled = git_panel.amend_pending();
                     let signoff_enabled = git_panel.signoff_enabled();
+                    let gpg_sign_enabled = git_panel.gpg_sign_enabled();
                     let has_previous_commit = git_panel.head_commit(cx).is_some();
+                    let has_staged_changes = git_panel.has_staged_changes();
 
                     Some(ContextMenu::build(window, cx, |context_menu, _, _| {
                         context_menu
@@ -320,6 +325,26 @@ impl CommitModal {
                                     }
                                 },
                             )
+                            .toggleable_entry(
+                                "GPG Sign",
+                                gpg_sign_enabled,
+                                IconPosition::Start,
+                                Some(Box::new(GpgSign)),
+                                {
+                                    let git_panel = git_panel_entity.clone();
+                                    move |window, cx| {
+                                        git_panel.update(cx, |git_panel, cx| {
+                                            git_panel.toggle_gpg_sign_enabled(&GpgSign, window, cx);
+                                        })
+                                    }
+                                },
+                            )
+                            .separator()
+                            .action_disabled_when(
+                                !has_staged_changes,
+                                "Review Staged Changes",
+                                Box::new(ReviewStagedChanges),
+                            )
                     }))
                 }
             })
@@ -333,28 +358,34 @@ impl CommitModal {
             tooltip,
             commit_label,
             co_authors,
+            commit_message_warning,
             generate_commit_message,
             active_repo,
             is_amend_pending,
             is_signoff_enabled,
+            is_gpg_sign_enabled,
             workspace,
         ) = self.git_panel.update(cx, |git_panel, cx| {
             let (can_commit, tooltip) = git_panel.configure_commit_button(cx);
             let title = git_panel.commit_button_title();
             let co_authors = git_panel.render_co_authors(cx);
+            let commit_message_warning = git_panel.render_commit_message_warning(cx);
             let generate_commit_message = git_panel.render_generate_commit_message_button(cx);
             let active_repo = git_panel.active_repository.clone();
             let is_amend_pending = git_panel.amend_pending();
             let is_signoff_enabled = git_panel.signoff_enabled();
+            let is_gpg_sign_enabled = git_panel.gpg_sign_enabled();
             (
                 can_commit,
                 tooltip,
                 title,
                 co_authors,
+                commit_message_warning,
                 generate_commit_message,
                 active_repo,
                 is_amend_pending,
                 is_signoff_enabled,
+                is_gpg_sign_enabled,
                 git_panel.workspace.clone(),
             )
         });
@@ -423,7 +454,8 @@ impl CommitModal {
                             .child(branch_picker),
                     )
                     .children(generate_commit_message)
-                    .children(co_authors),
+                    .children(co_authors)
+                    .children(commit_message_warning),
             )
             .child(div().flex_1())
             .child(
@@ -452,6 +484,7 @@ impl CommitModal {
                                     CommitOptions {
                                         amend: is_amend_pending,
                                         signoff: is_signoff_enabled,
+                                        gpg_sign: is_gpg_sign_enabled,
                                     },
                                     window,
                                     cx,
@@ -472,9 +505,14 @@ impl CommitModal {
                                             &git::Commit
                                         }),
                                         format!(
-                                            "git commit{}{}",
+                                            "git commit{}{}{}",
                                             if is_amend_pending { " --amend" } else { "" },
-                                            if is_signoff_enabled { " --signoff" } else { "" }
+                                            if is_signoff_enabled { " --signoff" } else { "" },
+                                            if is_gpg_sign_enabled {
+                                                " --gpg-sign"
+                                            } else {
+                                                ""
+                                            }
                                         ),
                                         &focus_handle.clone(),
                                         cx,
@@ -543,6 +581,18 @@ impl Render for CommitModal {
             .on_action(cx.listener(Self::dismiss))
             .on_action(cx.listener(Self::on_commit))
             .on_action(cx.listener(Self::on_amend))
+            .on_action(
+                cx.listener(|this, action: &PreviousCommitMessage, window, cx| {
+                    this.git_panel.update(cx, |git_panel, cx| {
+                        git_panel.show_previous_commit_message(action, window, cx);
+                    })
+                }),
+            )
+            .on_action(cx.listener(|this, action: &NextCommitMessage, window, cx| {
+                this.git_panel.update(cx, |git_panel, cx| {
+                    git_panel.show_next_commit_message(action, window, cx);
+                })
+            }))
             .when(!DisableAiSettings::get_global(cx).disable_ai, |this| {
                 this.on_action(cx.listener(|this, _: &GenerateCommitMessage, _, cx| {
                     this.git_panel.update(cx, |panel, cx| {
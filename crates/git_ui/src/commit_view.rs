@@ -3,7 +3,7 @@ use buffer_diff::BufferDiff;
 use collections::HashMap;
 use editor::display_map::{BlockPlacement, BlockProperties, BlockStyle};
 use editor::{Addon, Editor, EditorEvent, ExcerptRange, MultiBuffer, multibuffer_context_lines};
-use git::repository::{CommitDetails, CommitDiff, RepoPath, is_binary_content};
+use git::repository::{CommitDetails, CommitDiff, CommitFile, RepoPath, is_binary_content};
 use git::status::{FileStatus, StatusCode, TrackedStatus};
 use git::{
     BuildCommitPermalinkParams, GitHostingProviderRegistry, GitRemote, ParsedGitRemote,
@@ -76,8 +76,8 @@ struct GitBlob {
     display_name: String,
 }
 
-struct CommitDiffAddon {
-    file_statuses: HashMap<language::BufferId, FileStatus>,
+pub(crate) struct CommitDiffAddon {
+    pub(crate) file_statuses: HashMap<language::BufferId, FileStatus>,
 }
 
 impl Addon for CommitDiffAddon {
@@ -248,113 +248,19 @@ impl CommitView {
 
         let repository_clone = repository.clone();
 
+        let multibuffer_handle = multibuffer.downgrade();
         cx.spawn(async move |this, cx| {
-            let mut binary_buffer_ids: HashSet<language::BufferId> = HashSet::default();
-            let mut file_statuses: HashMap<language::BufferId, FileStatus> = HashMap::default();
-
-            for file in commit_diff.files {
-                let is_created = file.old_text.is_none();
-                let is_deleted = file.new_text.is_none();
-                let raw_new_text = file.new_text.unwrap_or_default();
-                let raw_old_text = file.old_text;
-
-                let is_binary = file.is_binary
-                    || is_binary_content(raw_new_text.as_bytes())
-                    || raw_old_text
-                        .as_ref()
-                        .is_some_and(|text| is_binary_content(text.as_bytes()));
-
-                let new_text = if is_binary {
-                    "(binary file not shown)".to_string()
-                } else {
-                    raw_new_text
-                };
-                let old_text = if is_binary { None } else { raw_old_text };
-                let worktree_id = repository_clone
-                    .update(cx, |repository, cx| {
-                        repository
-                            .repo_path_to_project_path(&file.path, cx)
-                            .map(|path| path.worktree_id)
-                            .or(first_worktree_id)
-                    })
-                    .context("project has no worktrees")?;
-                let short_sha = commit_sha.get(0..7).unwrap_or(&commit_sha);
-                let file_name = file
-                    .path
-                    .file_name()
-                    .map(|name| name.to_string())
-                    .unwrap_or_else(|| file.path.display(PathStyle::local()).to_string());
-                let display_name = format!("{short_sha} - {file_name}");
-
-                let file = Arc::new(GitBlob {
-                    path: file.path.clone(),
-                    is_deleted,
-                    is_binary,
-                    worktree_id,
-                    display_name,
-                }) as Arc<dyn language::File>;
-
-                let buffer = build_buffer(new_text, file, &language_registry, cx).await?;
-                let buffer_id = cx.update(|cx| buffer.read(cx).remote_id());
-
-                let status_code = if is_created {
-                    StatusCode::Added
-                } else if is_deleted {
-                    StatusCode::Deleted
-                } else {
-                    StatusCode::Modified
-                };
-                file_statuses.insert(
-                    buffer_id,
-                    FileStatus::Tracked(TrackedStatus {
-                        index_status: status_code,
-                        worktree_status: StatusCode::Unmodified,
-                    }),
-                );
-
-                if is_binary {
-                    binary_buffer_ids.insert(buffer_id);
-                }
-
-                let buffer_diff = if is_binary {
-                    None
-                } else {
-                    Some(build_buffer_diff(old_text, &buffer, &language_registry, cx).await?)
-                };
-
-                this.update(cx, |this, cx| {
-                    this.multibuffer.update(cx, |multibuffer, cx| {
-                        let snapshot = buffer.read(cx).snapshot();
-                        let path = snapshot.file().unwrap().path().clone();
-                        let excerpt_ranges = if is_binary {
-                            vec![language::Point::zero()..snapshot.max_point()]
-                        } else if let Some(buffer_diff) = &buffer_diff {
-                            let diff_snapshot = buffer_diff.read(cx).snapshot(cx);
-                            let mut hunks = diff_snapshot.hunks(&snapshot).peekable();
-                            if hunks.peek().is_none() {
-                                vec![language::Point::zero()..snapshot.max_point()]
-                            } else {
-                                hunks
-                                    .map(|hunk| hunk.buffer_range.to_point(&snapshot))
-                                    .collect::<Vec<_>>()
-                            }
-                        } else {
-                            vec![language::Point::zero()..snapshot.max_point()]
-                        };
-
-                        let _is_newly_added = multibuffer.set_excerpts_for_path(
-                            PathKey::with_sort_prefix(FILE_NAMESPACE_SORT_PREFIX, path),
-                            buffer,
-                            excerpt_ranges,
-                            multibuffer_context_lines(cx),
-                            cx,
-                        );
-                        if let Some(buffer_diff) = buffer_diff {
-                            multibuffer.add_diff(buffer_diff, cx);
-                        }
-                    });
-                })?;
-            }
+            let short_sha = commit_sha.get(0..7).unwrap_or(&commit_sha);
+            let (binary_buffer_ids, file_statuses) = add_commit_files_to_multibuffer(
+                commit_diff.files,
+                short_sha,
+                multibuffer_handle,
+                repository_clone,
+                first_worktree_id,
+                language_registry,
+                cx,
+            )
+            .await?;
 
             this.update(cx, |this, cx| {
                 this.editor.update(cx, |editor, _cx| {
@@ -906,6 +812,129 @@ async fn build_buffer_diff(
     Ok(diff)
 }
 
+/// Adds an excerpt per file to the multibuffer, returning the buffers that hold
+/// binary files (to be folded) and the status to show for each buffer.
+pub(crate) async fn add_commit_files_to_multibuffer(
+    files: Vec<CommitFile>,
+    display_prefix: &str,
+    multibuffer: WeakEntity<MultiBuffer>,
+    repository: Entity<Repository>,
+    first_worktree_id: Option<WorktreeId>,
+    language_registry: Arc<LanguageRegistry>,
+    cx: &mut AsyncApp,
+) -> Result<(
+    HashSet<language::BufferId>,
+    HashMap<language::BufferId, FileStatus>,
+)> {
+    let mut binary_buffer_ids: HashSet<language::BufferId> = HashSet::default();
+    let mut file_statuses: HashMap<language::BufferId, FileStatus> = HashMap::default();
+
+    for file in files {
+        let is_created = file.old_text.is_none();
+        let is_deleted = file.new_text.is_none();
+        let raw_new_text = file.new_text.unwrap_or_default();
+        let raw_old_text = file.old_text;
+
+        let is_binary = file.is_binary
+            || is_binary_content(raw_new_text.as_bytes())
+            || raw_old_text
+                .as_ref()
+                .is_some_and(|text| is_binary_content(text.as_bytes()));
+
+        let new_text = if is_binary {
+            "(binary file not shown)".to_string()
+        } else {
+            raw_new_text
+        };
+        let old_text = if is_binary { None } else { raw_old_text };
+        let worktree_id = repository
+            .update(cx, |repository, cx| {
+                repository
+                    .repo_path_to_project_path(&file.path, cx)
+                    .map(|path| path.worktree_id)
+                    .or(first_worktree_id)
+            })
+            .context("project has no worktrees")?;
+        let file_name = file
+            .path
+            .file_name()
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| file.path.display(PathStyle::local()).to_string());
+        let display_name = format!("{display_prefix} - {file_name}");
+
+        let file = Arc::new(GitBlob {
+            path: file.path.clone(),
+            is_deleted,
+            is_binary,
+            worktree_id,
+            display_name,
+        }) as Arc<dyn language::File>;
+
+        let buffer = build_buffer(new_text, file, &language_registry, cx).await?;
+        let buffer_id = cx.update(|cx| buffer.read(cx).remote_id());
+
+        let status_code = if is_created {
+            StatusCode::Added
+        } else if is_deleted {
+            StatusCode::Deleted
+        } else {
+            StatusCode::Modified
+        };
+        file_statuses.insert(
+            buffer_id,
+            FileStatus::Tracked(TrackedStatus {
+                index_status: status_code,
+                worktree_status: StatusCode::Unmodified,
+            }),
+        );
+
+        if is_binary {
+            binary_buffer_ids.insert(buffer_id);
+        }
+
+        let buffer_diff = if is_binary {
+            None
+        } else {
+            Some(build_buffer_diff(old_text, &buffer, &language_registry, cx).await?)
+        };
+
+        multibuffer.update(cx, |multibuffer, cx| {
+            let snapshot = buffer.read(cx).snapshot();
+            let Some(path) = snapshot.file().map(|file| file.path().clone()) else {
+                return;
+            };
+            let excerpt_ranges = if is_binary {
+                vec![language::Point::zero()..snapshot.max_point()]
+            } else if let Some(buffer_diff) = &buffer_diff {
+                let diff_snapshot = buffer_diff.read(cx).snapshot(cx);
+                let mut hunks = diff_snapshot.hunks(&snapshot).peekable();
+                if hunks.peek().is_none() {
+                    vec![language::Point::zero()..snapshot.max_point()]
+                } else {
+                    hunks
+                        .map(|hunk| hunk.buffer_range.to_point(&snapshot))
+                        .collect::<Vec<_>>()
+                }
+            } else {
+                vec![language::Point::zero()..snapshot.max_point()]
+            };
+
+            let _is_newly_added = multibuffer.set_excerpts_for_path(
+                PathKey::with_sort_prefix(FILE_NAMESPACE_SORT_PREFIX, path),
+                buffer,
+                excerpt_ranges,
+                multibuffer_context_lines(cx),
+                cx,
+            );
+            if let Some(buffer_diff) = buffer_diff {
+                multibuffer.add_diff(buffer_diff, cx);
+            }
+        })?;
+    }
+
+    Ok((binary_buffer_ids, file_statuses))
+}
+
 impl EventEmitter<EditorEvent> for CommitView {}
 
 impl Focusable for CommitView {
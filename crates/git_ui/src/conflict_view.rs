@@ -1,14 +1,17 @@
 use collections::{HashMap, HashSet};
 use editor::{
     ConflictsOurs, ConflictsOursMarker, ConflictsOuter, ConflictsTheirs, ConflictsTheirsMarker,
-    Editor, EditorEvent, ExcerptId, MultiBuffer, RowHighlightOptions,
+    Editor, EditorEvent, ExcerptId, MultiBuffer, RowHighlightOptions, SelectionEffects,
+    ToPoint as _,
     display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+    scroll::Autoscroll,
 };
+use git::{GoToNextConflict, GoToPreviousConflict};
 use gpui::{
     App, Context, Entity, InteractiveElement as _, ParentElement as _, Subscription, Task,
     WeakEntity,
 };
-use language::{Anchor, Buffer, BufferId};
+use language::{Anchor, Buffer, BufferId, Point};
 use project::{ConflictRegion, ConflictSet, ConflictSetUpdate, ProjectItem as _};
 use std::{ops::Range, sync::Arc};
 use ui::{ActiveTheme, Element as _, Styled, Window, prelude::*};
@@ -55,6 +58,26 @@ pub fn register_editor(editor: &mut Editor, buffer: Entity<MultiBuffer>, cx: &mu
     editor.register_addon(ConflictAddon {
         buffers: Default::default(),
     });
+    editor
+        .register_action({
+            let editor = cx.weak_entity();
+            move |_: &GoToNextConflict, window, cx| {
+                editor
+                    .update(cx, |editor, cx| go_to_conflict(editor, true, window, cx))
+                    .ok();
+            }
+        })
+        .detach();
+    editor
+        .register_action({
+            let editor = cx.weak_entity();
+            move |_: &GoToPreviousConflict, window, cx| {
+                editor
+                    .update(cx, |editor, cx| go_to_conflict(editor, false, window, cx))
+                    .ok();
+            }
+        })
+        .detach();
 
     let buffers = buffer.read(cx).all_buffers();
     for buffer in buffers {
@@ -314,6 +337,73 @@ fn conflicts_updated(
     }
 }
 
+/// Moves the cursor to the start of the next or previous conflict, wrapping
+/// around at the ends of the multibuffer.
+fn go_to_conflict(
+    editor: &mut Editor,
+    forward: bool,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    let Some(conflict_addon) = editor.addon::<ConflictAddon>() else {
+        return;
+    };
+    let multibuffer = editor.buffer().read(cx);
+    let snapshot = multibuffer.snapshot(cx);
+    let mut conflict_starts = Vec::new();
+    for (buffer_id, buffer_conflicts) in &conflict_addon.buffers {
+        let conflict_set = buffer_conflicts.conflict_set.read(cx).snapshot();
+        for (excerpt_id, range) in multibuffer.excerpts_for_buffer(*buffer_id, cx) {
+            let Some(buffer_snapshot) = snapshot.buffer_for_excerpt(excerpt_id) else {
+                continue;
+            };
+            for conflict in conflict_set.conflicts.iter() {
+                let in_excerpt = range
+                    .context
+                    .start
+                    .cmp(&conflict.range.start, buffer_snapshot)
+                    .is_le()
+                    && range
+                        .context
+                        .end
+                        .cmp(&conflict.range.start, buffer_snapshot)
+                        .is_ge();
+                if in_excerpt
+                    && let Some(start) =
+                        snapshot.anchor_in_excerpt(excerpt_id, conflict.range.start)
+                {
+                    conflict_starts.push(start.to_point(&snapshot));
+                }
+            }
+        }
+    }
+    conflict_starts.sort();
+
+    let cursor = editor.selections.newest_anchor().head().to_point(&snapshot);
+    let target = if forward {
+        conflict_starts
+            .iter()
+            .find(|start| start.row > cursor.row)
+            .or(conflict_starts.first())
+    } else {
+        conflict_starts
+            .iter()
+            .rev()
+            .find(|start| start.row < cursor.row)
+            .or(conflict_starts.last())
+    };
+    let Some(&target) = target else {
+        return;
+    };
+    let target = Point::new(target.row, 0);
+    editor.change_selections(
+        SelectionEffects::scroll(Autoscroll::center()),
+        window,
+        cx,
+        |selections| selections.select_ranges([target..target]),
+    );
+}
+
 #[ztracing::instrument(skip_all)]
 fn update_conflict_highlighting(
     editor: &mut Editor,
@@ -419,6 +509,7 @@ fn render_conflict_buttons(
             Button::new("both", "Use Both")
                 .label_size(LabelSize::Small)
                 .on_click({
+                    let editor = editor.clone();
                     let conflict = conflict.clone();
                     let ours = conflict.ours.clone();
                     let theirs = conflict.theirs.clone();
@@ -435,6 +526,26 @@ fn render_conflict_buttons(
                     }
                 }),
         )
+        .when_some(conflict.base.clone(), |this, base| {
+            this.child(
+                Button::new("base", "Use Base")
+                    .label_size(LabelSize::Small)
+                    .on_click({
+                        let conflict = conflict.clone();
+                        move |_, window, cx| {
+                            resolve_conflict(
+                                editor.clone(),
+                                excerpt_id,
+                                conflict.clone(),
+                                vec![base.clone()],
+                                window,
+                                cx,
+                            )
+                            .detach()
+                        }
+                    }),
+            )
+        })
         .into_any()
 }
 
@@ -0,0 +1,573 @@
+//! EditorDiffView compares two open buffers side by side, or a buffer against the clipboard.
+
+use anyhow::Result;
+use buffer_diff::BufferDiff;
+use editor::{Editor, EditorEvent, MultiBuffer, PathKey, SplittableEditor};
+use futures::{FutureExt, select_biased};
+use gpui::{
+    AnyElement, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, Render, Subscription, Task, Window,
+};
+use language::{Buffer, Capability, Point};
+use project::Project;
+use settings::DiffViewStyle;
+use std::{
+    any::{Any, TypeId},
+    pin::pin,
+    sync::Arc,
+    time::Duration,
+};
+use ui::{Color, Icon, IconName, Label, LabelCommon as _, SharedString};
+use util::paths::PathExt as _;
+use workspace::{
+    Item, ItemHandle as _, ItemNavHistory, ToolbarItemLocation, Workspace,
+    item::{BreadcrumbText, ItemEvent, SaveOptions, TabContentParams},
+    searchable::SearchableItemHandle,
+};
+
+use crate::file_diff_view::build_buffer_diff;
+
+pub struct EditorDiffView {
+    editor: Entity<SplittableEditor>,
+    old_buffer: Entity<Buffer>,
+    new_buffer: Entity<Buffer>,
+    /// Set when the old side is the clipboard rather than an open buffer.
+    old_title: Option<SharedString>,
+    buffer_changes_tx: watch::Sender<()>,
+    _editor_subscription: Subscription,
+    _recalculate_diff_task: Task<Result<()>>,
+}
+
+const RECALCULATE_DIFF_DEBOUNCE: Duration = Duration::from_millis(250);
+
+impl EditorDiffView {
+    /// Compares the active editor's buffer, on the right, with the buffer of
+    /// the active editor in another pane, or with the clipboard when no other
+    /// pane shows an editor.
+    pub fn open_for_active_editors(
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        let active_pane = workspace.active_pane().clone();
+        let singleton_buffer = |pane: &Entity<workspace::Pane>, cx: &App| {
+            let editor = pane.read(cx).active_item()?.downcast::<Editor>()?;
+            editor.read(cx).buffer().read(cx).as_singleton()
+        };
+
+        let Some(new_buffer) = singleton_buffer(&active_pane, cx) else {
+            log::warn!("Comparing editors requires an active editor with a single buffer.");
+            return None;
+        };
+        let other_buffer = workspace
+            .panes()
+            .iter()
+            .filter(|pane| **pane != active_pane)
+            .filter_map(|pane| singleton_buffer(pane, cx))
+            .find(|buffer| *buffer != new_buffer);
+
+        let (old_buffer, old_title) = match other_buffer {
+            Some(buffer) => (buffer, None),
+            None => {
+                let Some(clipboard_text) = cx
+                    .read_from_clipboard()
+                    .and_then(|clipboard_item| clipboard_item.text())
+                else {
+                    log::warn!("There is no other editor or clipboard text to compare with.");
+                    return None;
+                };
+                let language = new_buffer.read(cx).language().cloned();
+                let clipboard_buffer = cx.new(|cx| {
+                    let mut buffer = Buffer::local(clipboard_text, cx);
+                    buffer.set_language(language, cx);
+                    buffer.set_capability(Capability::ReadOnly, cx);
+                    buffer
+                });
+                (clipboard_buffer, Some(SharedString::from("Clipboard")))
+            }
+        };
+
+        let workspace = workspace.weak_handle();
+        Some(window.spawn(cx, async move |cx| {
+            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+            let languages = project.update(cx, |project, _| project.languages().clone());
+            let buffer_diff = build_buffer_diff(&old_buffer, &new_buffer, languages, cx).await?;
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let workspace_entity = cx.entity();
+                let diff_view = cx.new(|cx| {
+                    EditorDiffView::new(
+                        old_buffer,
+                        old_title,
+                        new_buffer,
+                        buffer_diff,
+                        project,
+                        workspace_entity,
+                        window,
+                        cx,
+                    )
+                });
+
+                let pane = workspace.active_pane();
+                pane.update(cx, |pane, cx| {
+                    pane.add_item(Box::new(diff_view.clone()), true, true, None, window, cx);
+                });
+
+                diff_view
+            })
+        }))
+    }
+
+    pub fn new(
+        old_buffer: Entity<Buffer>,
+        old_title: Option<SharedString>,
+        new_buffer: Entity<Buffer>,
+        diff: Entity<BufferDiff>,
+        project: Entity<Project>,
+        workspace: Entity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::new(Capability::ReadWrite);
+            multibuffer.set_all_diff_hunks_expanded(cx);
+            multibuffer
+        });
+        let editor = cx.new(|cx| {
+            let editor = SplittableEditor::new(
+                DiffViewStyle::Split,
+                multibuffer,
+                project,
+                workspace,
+                window,
+                cx,
+            );
+            editor.set_render_diff_hunk_controls(
+                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                cx,
+            );
+            editor.rhs_editor().update(cx, |editor, cx| {
+                editor.start_temporary_diff_override();
+                editor.disable_diagnostics(cx);
+            });
+            editor
+        });
+        let editor_subscription = cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+            cx.emit(event.clone())
+        });
+
+        let (buffer_changes_tx, mut buffer_changes_rx) = watch::channel(());
+
+        for buffer in [&old_buffer, &new_buffer] {
+            cx.subscribe(buffer, move |this, _, event, _| match event {
+                language::BufferEvent::Edited
+                | language::BufferEvent::LanguageChanged(_)
+                | language::BufferEvent::Reparsed => {
+                    this.buffer_changes_tx.send(()).ok();
+                }
+                _ => {}
+            })
+            .detach();
+        }
+
+        let mut this = Self {
+            editor,
+            old_buffer,
+            new_buffer,
+            old_title,
+            buffer_changes_tx,
+            _editor_subscription: editor_subscription,
+            _recalculate_diff_task: cx.spawn({
+                let diff = diff.clone();
+                async move |this, cx| {
+                    while buffer_changes_rx.recv().await.is_ok() {
+                        loop {
+                            let mut timer = cx
+                                .background_executor()
+                                .timer(RECALCULATE_DIFF_DEBOUNCE)
+                                .fuse();
+                            let mut recv = pin!(buffer_changes_rx.recv().fuse());
+                            select_biased! {
+                                _ = timer => break,
+                                _ = recv => continue,
+                            }
+                        }
+
+                        let (old_snapshot, new_snapshot) = this.update(cx, |this, cx| {
+                            (
+                                this.old_buffer.read(cx).snapshot(),
+                                this.new_buffer.read(cx).snapshot(),
+                            )
+                        })?;
+                        diff.update(cx, |diff, cx| {
+                            diff.set_base_text(
+                                Some(old_snapshot.text().as_str().into()),
+                                old_snapshot.language().cloned(),
+                                new_snapshot.text.clone(),
+                                cx,
+                            )
+                        })
+                        .await
+                        .ok();
+                        this.update(cx, |this, cx| this.update_excerpts(&diff, cx))?;
+                    }
+                    Ok(())
+                }
+            }),
+        };
+        this.update_excerpts(&diff, cx);
+        this
+    }
+
+    /// Keeps the whole of the new buffer in view, including lines added at
+    /// its end since the view was opened.
+    fn update_excerpts(&mut self, diff: &Entity<BufferDiff>, cx: &mut Context<Self>) {
+        let path = PathKey::for_buffer(&self.new_buffer, cx);
+        let max_point = self.new_buffer.read(cx).max_point();
+        let new_buffer = self.new_buffer.clone();
+        self.editor.update(cx, |editor, cx| {
+            editor.set_excerpts_for_path(
+                path,
+                new_buffer,
+                [Point::zero()..max_point],
+                0,
+                diff.clone(),
+                cx,
+            );
+        });
+    }
+
+    fn buffer_title(buffer: &Entity<Buffer>, cx: &App) -> String {
+        buffer
+            .read(cx)
+            .file()
+            .and_then(|file| {
+                Some(
+                    file.full_path(cx)
+                        .file_name()?
+                        .to_string_lossy()
+                        .to_string(),
+                )
+            })
+            .unwrap_or_else(|| "untitled".into())
+    }
+
+    fn buffer_path(buffer: &Entity<Buffer>, cx: &App) -> String {
+        buffer
+            .read(cx)
+            .file()
+            .map(|file| file.full_path(cx).compact().to_string_lossy().into_owned())
+            .unwrap_or_else(|| "untitled".into())
+    }
+}
+
+impl EventEmitter<EditorEvent> for EditorDiffView {}
+
+impl Focusable for EditorDiffView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for EditorDiffView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Diff).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
+        let old_title = match &self.old_title {
+            Some(title) => title.to_string(),
+            None => Self::buffer_title(&self.old_buffer, cx),
+        };
+        let new_title = Self::buffer_title(&self.new_buffer, cx);
+        format!("{old_title} ↔ {new_title}").into()
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let old_path = match &self.old_title {
+            Some(title) => title.to_string(),
+            None => Self::buffer_path(&self.old_buffer, cx),
+        };
+        let new_path = Self::buffer_path(&self.new_buffer, cx);
+        Some(format!("{old_path} ↔ {new_path}").into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Editor Diff View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        cx: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<SplittableEditor>() {
+            Some(self.editor.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.read(cx).rhs_editor().clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.editor.for_each_project_item(cx, f)
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.new_buffer.read(cx).is_dirty()
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.rhs_editor().update(cx, |editor, _| {
+                editor.set_nav_history(Some(nav_history));
+            });
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Arc<dyn Any + Send>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.editor.breadcrumbs(cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+
+    fn can_save(&self, cx: &App) -> bool {
+        // Edits only ever land in the new buffer, on the right.
+        self.editor.read(cx).can_save(cx)
+    }
+
+    fn save(
+        &mut self,
+        options: SaveOptions,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.editor
+            .update(cx, |editor, cx| editor.save(options, project, window, cx))
+    }
+}
+
+impl Render for EditorDiffView {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        self.editor.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{ClipboardItem, TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use settings::SettingsStore;
+    use util::{path, rel_path::rel_path};
+    use workspace::{MultiWorkspace, SplitDirection};
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+        });
+    }
+
+    fn hunk_count(diff_view: &Entity<EditorDiffView>, cx: &mut VisualTestContext) -> usize {
+        diff_view.read_with(cx, |diff_view, cx| {
+            let editor = diff_view.editor.read(cx).rhs_editor().read(cx);
+            editor.buffer().read(cx).snapshot(cx).diff_hunks().count()
+        })
+    }
+
+    #[gpui::test]
+    async fn test_compare_editors_in_two_panes(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "old_file.txt": "old line 1\nline 2\nold line 3\nline 4\n",
+                "new_file.txt": "new line 1\nline 2\nnew line 3\nline 4\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path(
+                    (worktree_id, rel_path("old_file.txt")),
+                    None,
+                    true,
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.split_pane(
+                workspace.active_pane().clone(),
+                SplitDirection::Right,
+                window,
+                cx,
+            );
+        });
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path(
+                    (worktree_id, rel_path("new_file.txt")),
+                    None,
+                    true,
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                EditorDiffView::open_for_active_editors(workspace, window, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(hunk_count(&diff_view, cx), 2);
+        diff_view.read_with(cx, |diff_view, cx| {
+            assert_eq!(
+                diff_view.tab_content_text(0, cx),
+                "old_file.txt ↔ new_file.txt"
+            );
+        });
+
+        // Edits on the right side apply to the new buffer and update the diff.
+        diff_view.update_in(cx, |diff_view, window, cx| {
+            let editor = diff_view.editor.read(cx).rhs_editor().clone();
+            editor.update(cx, |editor, cx| {
+                editor.select_all(&Default::default(), window, cx);
+                editor.insert("old line 1\nline 2\nold line 3\nline 4\n", window, cx);
+            });
+        });
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(hunk_count(&diff_view, cx), 0);
+        diff_view.read_with(cx, |diff_view, cx| {
+            assert!(diff_view.new_buffer.read(cx).is_dirty());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_compare_editor_with_clipboard(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "file.txt": "line 1\nline 2\nline 3\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, rel_path("file.txt")), None, true, window, cx)
+            })
+            .await
+            .unwrap();
+        cx.write_to_clipboard(ClipboardItem::new_string(
+            "line 1\nchanged line 2\nline 3\n".to_string(),
+        ));
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                EditorDiffView::open_for_active_editors(workspace, window, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(hunk_count(&diff_view, cx), 1);
+        diff_view.read_with(cx, |diff_view, cx| {
+            assert_eq!(diff_view.tab_content_text(0, cx), "Clipboard ↔ file.txt");
+        });
+    }
+}
@@ -29,7 +29,10 @@ use git::repository::{
 };
 use git::stash::GitStash;
 use git::status::StageStatus;
-use git::{Amend, Signoff, ToggleStaged, repository::RepoPath, status::FileStatus};
+use git::{
+    Amend, GpgSign, NextCommitMessage, PreviousCommitMessage, ReviewStagedChanges, Signoff,
+    ToggleStaged, repository::RepoPath, status::FileStatus,
+};
 use git::{
     ExpandCommitEditor, GitHostingProviderRegistry, RestoreTrackedFiles, StageAll, StashAll,
     StashApply, StashPop, TrashUntrackedFiles, UnstageAll,
@@ -254,6 +257,10 @@ struct SerializedGitPanel {
     amend_pending: bool,
     #[serde(default)]
     signoff_enabled: bool,
+    #[serde(default)]
+    gpg_sign_enabled: bool,
+    #[serde(default)]
+    commit_message_history: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
@@ -626,6 +633,10 @@ pub struct GitPanel {
     amend_pending: bool,
     original_commit_message: Option<String>,
     signoff_enabled: bool,
+    gpg_sign_enabled: bool,
+    /// Messages of earlier commits, oldest first.
+    commit_message_history: Vec<String>,
+    commit_message_history_position: Option<CommitMessageHistoryPosition>,
     pending_serialization: Task<()>,
     pub(crate) project: Entity<Project>,
     scroll_handle: UniformListScrollHandle,
@@ -647,6 +658,14 @@ pub struct GitPanel {
     _settings_subscription: Subscription,
 }
 
+/// Where the commit editor is in the commit message history, along with the
+/// message it had before it started showing the history.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct CommitMessageHistoryPosition {
+    index: usize,
+    draft: String,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct BulkStaging {
     repo_id: RepositoryId,
@@ -655,6 +674,29 @@ struct BulkStaging {
 
 const MAX_PANEL_EDITOR_LINES: usize = 6;
 
+const MAX_COMMIT_MESSAGE_HISTORY_LEN: usize = 50;
+
+/// The length past which git tools start truncating a commit's subject.
+const MAX_COMMIT_SUBJECT_LENGTH: usize = 72;
+
+/// Describes what's off about the shape of a commit message, if anything:
+/// an overlong subject, or a body that isn't separated from it by a blank
+/// line. Leading blank lines and comments are ignored, as git strips them.
+fn commit_message_issue(message: &str) -> Option<&'static str> {
+    let mut lines = message
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .skip_while(|line| line.trim().is_empty());
+    let subject = lines.next()?;
+    if subject.chars().count() > MAX_COMMIT_SUBJECT_LENGTH {
+        Some("The subject line is longer than 72 characters")
+    } else if lines.next().is_some_and(|line| !line.trim().is_empty()) {
+        Some("Separate the subject from the body with a blank line")
+    } else {
+        None
+    }
+}
+
 pub(crate) fn commit_message_editor(
     commit_message_buffer: Entity<Buffer>,
     placeholder: Option<SharedString>,
@@ -789,6 +831,9 @@ impl GitPanel {
                 amend_pending: false,
                 original_commit_message: None,
                 signoff_enabled: false,
+                gpg_sign_enabled: false,
+                commit_message_history: Vec::new(),
+                commit_message_history_position: None,
                 pending_serialization: Task::ready(()),
                 single_staged_entry: None,
                 single_tracked_entry: None,
@@ -896,6 +941,8 @@ impl GitPanel {
         let width = self.width;
         let amend_pending = self.amend_pending;
         let signoff_enabled = self.signoff_enabled;
+        let gpg_sign_enabled = self.gpg_sign_enabled;
+        let commit_message_history = self.commit_message_history.clone();
 
         self.pending_serialization = cx.spawn(async move |git_panel, cx| {
             cx.background_executor()
@@ -923,6 +970,8 @@ impl GitPanel {
                                 width,
                                 amend_pending,
                                 signoff_enabled,
+                                gpg_sign_enabled,
+                                commit_message_history,
                             })?,
                         )
                         .await?;
@@ -2116,6 +2165,7 @@ impl GitPanel {
                 CommitOptions {
                     amend: false,
                     signoff: self.signoff_enabled,
+                    gpg_sign: self.gpg_sign_enabled,
                 },
                 window,
                 cx,
@@ -2156,6 +2206,7 @@ impl GitPanel {
                         CommitOptions {
                             amend: true,
                             signoff: self.signoff_enabled,
+                            gpg_sign: self.gpg_sign_enabled,
                         },
                         window,
                         cx,
@@ -2288,6 +2339,7 @@ impl GitPanel {
             return;
         };
 
+        let typed_message = message.clone();
         if self.add_coauthors {
             self.fill_co_authors(&mut message, cx);
         }
@@ -2329,6 +2381,7 @@ impl GitPanel {
 
                 match result {
                     Ok(()) => {
+                        this.push_commit_message_history(typed_message, cx);
                         if options.amend {
                             this.set_amend_pending(false, cx);
                         } else {
@@ -4078,6 +4131,8 @@ impl GitPanel {
                 let has_previous_commit = self.head_commit(cx).is_some();
                 let amend = self.amend_pending();
                 let signoff = self.signoff_enabled;
+                let gpg_sign = self.gpg_sign_enabled;
+                let has_staged_changes = self.has_staged_changes();
 
                 move |window, cx| {
                     Some(ContextMenu::build(window, cx, |context_menu, _, _| {
@@ -4110,6 +4165,19 @@ impl GitPanel {
                                 Some(Box::new(Signoff)),
                                 move |window, cx| window.dispatch_action(Box::new(Signoff), cx),
                             )
+                            .toggleable_entry(
+                                "GPG Sign",
+                                gpg_sign,
+                                IconPosition::Start,
+                                Some(Box::new(GpgSign)),
+                                move |window, cx| window.dispatch_action(Box::new(GpgSign), cx),
+                            )
+                            .separator()
+                            .action_disabled_when(
+                                !has_staged_changes,
+                                "Review Staged Changes",
+                                Box::new(ReviewStagedChanges),
+                            )
                     }))
                 }
             })
@@ -4132,6 +4200,21 @@ impl GitPanel {
         }
     }
 
+    pub(crate) fn render_commit_message_warning(&self, cx: &App) -> Option<AnyElement> {
+        let issue = commit_message_issue(&self.commit_editor.read(cx).text(cx))?;
+        Some(
+            div()
+                .id("commit-message-warning")
+                .child(
+                    Icon::new(IconName::Warning)
+                        .size(IconSize::Small)
+                        .color(Color::Warning),
+                )
+                .tooltip(Tooltip::text(issue))
+                .into_any_element(),
+        )
+    }
+
     pub fn commit_button_title(&self) -> &'static str {
         if self.amend_pending {
             if self.has_staged_changes() {
@@ -4327,6 +4410,7 @@ impl GitPanel {
                             .child(
                                 h_flex()
                                     .gap_0p5()
+                                    .children(self.render_commit_message_warning(cx))
                                     .children(enable_coauthors)
                                     .child(self.render_commit_button(cx)),
                             ),
@@ -4382,6 +4466,7 @@ impl GitPanel {
         let commit_tooltip_focus_handle = self.commit_editor.focus_handle(cx);
         let amend = self.amend_pending();
         let signoff = self.signoff_enabled;
+        let gpg_sign = self.gpg_sign_enabled;
 
         let label_color = if self.pending_commit.is_some() {
             Color::Disabled
@@ -4415,7 +4500,11 @@ impl GitPanel {
                         git_panel
                             .update(cx, |git_panel, cx| {
                                 git_panel.commit_changes(
-                                    CommitOptions { amend, signoff },
+                                    CommitOptions {
+                                        amend,
+                                        signoff,
+                                        gpg_sign,
+                                    },
                                     window,
                                     cx,
                                 );
@@ -4432,9 +4521,10 @@ impl GitPanel {
                                 tooltip,
                                 Some(if amend { &git::Amend } else { &git::Commit }),
                                 format!(
-                                    "git commit{}{}",
+                                    "git commit{}{}{}",
                                     if amend { " --amend" } else { "" },
-                                    if signoff { " --signoff" } else { "" }
+                                    if signoff { " --signoff" } else { "" },
+                                    if gpg_sign { " --gpg-sign" } else { "" }
                                 ),
                                 &handle.clone(),
                                 cx,
@@ -5391,6 +5481,78 @@ impl GitPanel {
         self.amend_pending
     }
 
+    fn push_commit_message_history(&mut self, message: String, cx: &mut Context<Self>) {
+        self.commit_message_history_position = None;
+        let message = message.trim().to_string();
+        if message.is_empty() {
+            return;
+        }
+        self.commit_message_history
+            .retain(|earlier_message| *earlier_message != message);
+        self.commit_message_history.push(message);
+        if self.commit_message_history.len() > MAX_COMMIT_MESSAGE_HISTORY_LEN {
+            let excess = self.commit_message_history.len() - MAX_COMMIT_MESSAGE_HISTORY_LEN;
+            self.commit_message_history.drain(..excess);
+        }
+        self.serialize(cx);
+    }
+
+    pub(crate) fn show_previous_commit_message(
+        &mut self,
+        _: &PreviousCommitMessage,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let position = match self.commit_message_history_position.take() {
+            Some(position) if position.index == 0 => position,
+            Some(position) => CommitMessageHistoryPosition {
+                index: position.index - 1,
+                draft: position.draft,
+            },
+            None => {
+                let Some(index) = self.commit_message_history.len().checked_sub(1) else {
+                    return;
+                };
+                CommitMessageHistoryPosition {
+                    index,
+                    draft: self.commit_editor.read(cx).text(cx),
+                }
+            }
+        };
+        let message = self.commit_message_history[position.index].clone();
+        self.commit_message_history_position = Some(position);
+        self.set_commit_message(message, window, cx);
+    }
+
+    pub(crate) fn show_next_commit_message(
+        &mut self,
+        _: &NextCommitMessage,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(position) = self.commit_message_history_position.take() else {
+            return;
+        };
+        let message = if position.index + 1 < self.commit_message_history.len() {
+            let index = position.index + 1;
+            self.commit_message_history_position = Some(CommitMessageHistoryPosition {
+                index,
+                draft: position.draft,
+            });
+            self.commit_message_history[index].clone()
+        } else {
+            position.draft
+        };
+        self.set_commit_message(message, window, cx);
+    }
+
+    fn set_commit_message(&mut self, message: String, window: &mut Window, cx: &mut Context<Self>) {
+        self.commit_editor.update(cx, |editor, cx| {
+            editor.set_text(message, window, cx);
+            editor.move_to_end(&editor::actions::MoveToEnd, window, cx);
+        });
+    }
+
     /// Sets the pending amend state, ensuring that the original commit message
     /// is either saved, when `value` is `true` and there's no pending amend, or
     /// restored, when `value` is `false` and there's a pending amend.
@@ -5435,6 +5597,25 @@ impl GitPanel {
         self.set_signoff_enabled(!self.signoff_enabled, cx);
     }
 
+    pub fn gpg_sign_enabled(&self) -> bool {
+        self.gpg_sign_enabled
+    }
+
+    pub fn set_gpg_sign_enabled(&mut self, value: bool, cx: &mut Context<Self>) {
+        self.gpg_sign_enabled = value;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    pub fn toggle_gpg_sign_enabled(
+        &mut self,
+        _: &GpgSign,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_gpg_sign_enabled(!self.gpg_sign_enabled, cx);
+    }
+
     pub async fn load(
         workspace: WeakEntity<Workspace>,
         mut cx: AsyncWindowContext,
@@ -5465,6 +5646,8 @@ impl GitPanel {
                     panel.width = serialized_panel.width;
                     panel.amend_pending = serialized_panel.amend_pending;
                     panel.signoff_enabled = serialized_panel.signoff_enabled;
+                    panel.gpg_sign_enabled = serialized_panel.gpg_sign_enabled;
+                    panel.commit_message_history = serialized_panel.commit_message_history;
                     cx.notify();
                 })
             }
@@ -5545,6 +5728,9 @@ impl Render for GitPanel {
                     .on_action(cx.listener(GitPanel::on_commit))
                     .on_action(cx.listener(GitPanel::on_amend))
                     .on_action(cx.listener(GitPanel::toggle_signoff_enabled))
+                    .on_action(cx.listener(GitPanel::toggle_gpg_sign_enabled))
+                    .on_action(cx.listener(GitPanel::show_previous_commit_message))
+                    .on_action(cx.listener(GitPanel::show_next_commit_message))
                     .on_action(cx.listener(Self::stage_all))
                     .on_action(cx.listener(Self::unstage_all))
                     .on_action(cx.listener(Self::stage_selected))
@@ -6980,6 +7166,59 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_commit_message_history(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "src": {
+                        "main.rs": "fn main() {}"
+                    }
+                }
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+
+        let panel = workspace.update_in(cx, GitPanel::new);
+
+        panel.update(cx, |panel, cx| {
+            panel.push_commit_message_history("First".into(), cx);
+            panel.push_commit_message_history("Second\n".into(), cx);
+            panel.push_commit_message_history("First".into(), cx);
+            panel.push_commit_message_history("  ".into(), cx);
+            assert_eq!(panel.commit_message_history, ["Second", "First"]);
+        });
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.set_commit_message("Draft".into(), window, cx);
+
+            panel.show_previous_commit_message(&PreviousCommitMessage, window, cx);
+            assert_eq!(panel.commit_message_buffer(cx).read(cx).text(), "First");
+            panel.show_previous_commit_message(&PreviousCommitMessage, window, cx);
+            assert_eq!(panel.commit_message_buffer(cx).read(cx).text(), "Second");
+            panel.show_previous_commit_message(&PreviousCommitMessage, window, cx);
+            assert_eq!(panel.commit_message_buffer(cx).read(cx).text(), "Second");
+
+            panel.show_next_commit_message(&NextCommitMessage, window, cx);
+            assert_eq!(panel.commit_message_buffer(cx).read(cx).text(), "First");
+            panel.show_next_commit_message(&NextCommitMessage, window, cx);
+            assert_eq!(panel.commit_message_buffer(cx).read(cx).text(), "Draft");
+            assert!(panel.commit_message_history_position.is_none());
+        });
+    }
+
     #[gpui::test]
     async fn test_amend(cx: &mut TestAppContext) {
         init_test(cx);
@@ -7415,6 +7654,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_commit_message_issue() {
+        assert_eq!(commit_message_issue(""), None);
+        assert_eq!(commit_message_issue("Fix the build"), None);
+        assert_eq!(
+            commit_message_issue("\nFix the build\n\nThe linker flags changed.\n"),
+            None
+        );
+        assert_eq!(
+            commit_message_issue(&"a".repeat(73)),
+            Some("The subject line is longer than 72 characters")
+        );
+        assert_eq!(
+            commit_message_issue("Fix the build\nThe linker flags changed."),
+            Some("Separate the subject from the body with a blank line")
+        );
+        assert_eq!(
+            commit_message_issue("Fix the build\n# Please enter the commit message\n"),
+            None
+        );
+    }
+
     #[test]
     fn test_compress_diff_no_truncation() {
         let diff = indoc! {"
@@ -25,7 +25,7 @@ use ui::prelude::*;
 use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
 use zed_actions;
 
-use crate::{git_panel::GitPanel, text_diff_view::TextDiffView};
+use crate::{editor_diff_view::EditorDiffView, git_panel::GitPanel, text_diff_view::TextDiffView};
 
 mod askpass_modal;
 pub mod branch_picker;
@@ -33,16 +33,19 @@ mod commit_modal;
 pub mod commit_tooltip;
 pub mod commit_view;
 mod conflict_view;
+pub mod editor_diff_view;
 pub mod file_diff_view;
 pub mod file_history_view;
 pub mod git_panel;
 mod git_panel_settings;
 pub mod git_picker;
+pub mod merge_view;
 pub mod multi_diff_view;
 pub mod picker_prompt;
 pub mod project_diff;
 pub(crate) mod remote_output;
 pub mod repository_selector;
+mod staged_changes_view;
 pub mod stash_picker;
 pub mod text_diff_view;
 pub mod worktree_picker;
@@ -50,6 +53,7 @@ pub mod worktree_picker;
 pub fn init(cx: &mut App) {
     editor::set_blame_renderer(blame_ui::GitBlameRenderer, cx);
     commit_view::init(cx);
+    staged_changes_view::init(cx);
     file_history_view::init(cx);
 
     cx.observe_new(|editor: &mut Editor, _, cx| {
@@ -200,6 +204,9 @@ pub fn init(cx: &mut App) {
                 GitCloneModal::show(panel, window, cx)
             });
         });
+        workspace.register_action(|workspace, _: &git::OpenMergeView, window, cx| {
+            merge_view::MergeView::open_for_active_editor(workspace, window, cx);
+        });
         workspace.register_action(|workspace, _: &git::OpenModifiedFiles, window, cx| {
             open_modified_files(workspace, window, cx);
         });
@@ -213,6 +220,13 @@ pub fn init(cx: &mut App) {
                 };
             },
         );
+        workspace.register_action(
+            |workspace, _: &workspace::CompareActiveEditors, window, cx| {
+                if let Some(task) = EditorDiffView::open_for_active_editors(workspace, window, cx) {
+                    task.detach_and_log_err(cx);
+                }
+            },
+        );
         workspace.register_action(|workspace, _: &git::FileHistory, window, cx| {
             let Some(active_item) = workspace.active_item(cx) else {
                 return;
@@ -0,0 +1,594 @@
+//! MergeView shows a file with merge conflicts as three read-only panes, with each side of its
+//! conflicts and their common ancestor, above a result editor in which the conflicts are resolved.
+
+use anyhow::Result;
+use editor::{Editor, EditorEvent, SelectionEffects, scroll::Autoscroll};
+use gpui::{
+    AnyElement, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, Render, SharedString, Subscription, Task, Window,
+};
+use language::{Buffer, Point, TextBufferSnapshot, ToOffset as _, ToPoint as _};
+use project::{ConflictRegion, ConflictSet, ConflictSetUpdate, Project};
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+use ui::{ActiveTheme as _, Color, Icon, IconName, Label, LabelCommon as _, LabelSize, prelude::*};
+use util::paths::PathExt;
+use workspace::{
+    Item, ItemHandle as _, ItemNavHistory, Workspace,
+    item::{BreadcrumbText, ItemEvent, SaveOptions, TabContentParams},
+    searchable::SearchableItemHandle,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MergeSide {
+    Ours,
+    Base,
+    Theirs,
+}
+
+/// A read-only pane showing the file with every conflict replaced by one of its sides.
+struct MergePane {
+    side: MergeSide,
+    buffer: Entity<Buffer>,
+    editor: Entity<Editor>,
+    /// The row each conflict starts at in this pane, in the order of the conflicts.
+    conflict_rows: Vec<u32>,
+}
+
+pub struct MergeView {
+    buffer: Entity<Buffer>,
+    result_editor: Entity<Editor>,
+    conflict_set: Entity<ConflictSet>,
+    panes: Vec<MergePane>,
+    ours_branch_name: Option<SharedString>,
+    theirs_branch_name: Option<SharedString>,
+    /// Whether any conflict recorded its base, which only happens with the
+    /// `diff3` and `zdiff3` conflict styles.
+    show_base: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl MergeView {
+    /// Opens the active editor's file in a merge view, in the active pane.
+    pub fn open_for_active_editor(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Option<Entity<Self>> {
+        let editor = workspace.active_item_as::<Editor>(cx)?;
+        let buffer = editor.read(cx).buffer().read(cx).as_singleton()?;
+        let project = workspace.project().clone();
+        let merge_view = cx.new(|cx| MergeView::new(buffer, project, window, cx));
+        workspace.active_pane().update(cx, |pane, cx| {
+            pane.add_item(Box::new(merge_view.clone()), true, true, None, window, cx);
+        });
+        Some(merge_view)
+    }
+
+    pub fn new(
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let conflict_set = project.read(cx).git_store().update(cx, |git_store, cx| {
+            git_store.open_conflict_set(buffer.clone(), cx)
+        });
+        let result_editor =
+            cx.new(|cx| Editor::for_buffer(buffer.clone(), Some(project), window, cx));
+        let language = buffer.read(cx).language().cloned();
+        let panes = [MergeSide::Ours, MergeSide::Base, MergeSide::Theirs]
+            .into_iter()
+            .map(|side| {
+                let pane_buffer = cx.new(|cx| {
+                    let mut buffer = Buffer::local("", cx);
+                    buffer.set_language(language.clone(), cx);
+                    buffer
+                });
+                let editor = cx.new(|cx| {
+                    let mut editor = Editor::for_buffer(pane_buffer.clone(), None, window, cx);
+                    editor.set_read_only(true);
+                    editor.disable_diagnostics(cx);
+                    editor
+                });
+                MergePane {
+                    side,
+                    buffer: pane_buffer,
+                    editor,
+                    conflict_rows: Vec::new(),
+                }
+            })
+            .collect();
+
+        let subscriptions = vec![
+            cx.subscribe(&result_editor, |_, _, event: &EditorEvent, cx| {
+                cx.emit(event.clone())
+            }),
+            cx.subscribe_in(
+                &result_editor,
+                window,
+                |this, _, event: &EditorEvent, window, cx| {
+                    if let EditorEvent::SelectionsChanged { local: true } = event {
+                        this.reveal_conflict_in_panes(window, cx);
+                    }
+                },
+            ),
+            cx.subscribe(&conflict_set, |this, _, _: &ConflictSetUpdate, cx| {
+                this.update_panes(cx)
+            }),
+        ];
+
+        let mut this = Self {
+            buffer,
+            result_editor,
+            conflict_set,
+            panes,
+            ours_branch_name: None,
+            theirs_branch_name: None,
+            show_base: false,
+            _subscriptions: subscriptions,
+        };
+        this.update_panes(cx);
+        this
+    }
+
+    fn update_panes(&mut self, cx: &mut Context<Self>) {
+        let conflicts = self.conflict_set.read(cx).snapshot().conflicts;
+        let buffer = self.buffer.read(cx).text_snapshot();
+        if let Some(conflict) = conflicts.first() {
+            self.ours_branch_name = Some(conflict.ours_branch_name.clone());
+            self.theirs_branch_name = Some(conflict.theirs_branch_name.clone());
+        }
+        self.show_base |= conflicts.iter().any(|conflict| conflict.base.is_some());
+
+        for pane in &mut self.panes {
+            let (text, conflict_rows) = side_text(&buffer, &conflicts, pane.side);
+            pane.conflict_rows = conflict_rows;
+            pane.buffer.update(cx, |buffer, cx| {
+                if buffer.text() != text {
+                    buffer.set_text(text, cx);
+                }
+            });
+        }
+        cx.notify();
+    }
+
+    /// Scrolls the panes to the conflict at or above the result editor's cursor.
+    fn reveal_conflict_in_panes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let cursor = self.result_editor.update(cx, |editor, cx| {
+            editor
+                .selections
+                .newest::<Point>(&editor.display_snapshot(cx))
+                .head()
+        });
+        let buffer = self.buffer.read(cx).text_snapshot();
+        let conflicts = self.conflict_set.read(cx).snapshot().conflicts;
+        let Some(conflict_index) = conflicts
+            .iter()
+            .rposition(|conflict| conflict.range.start.to_point(&buffer).row <= cursor.row)
+        else {
+            return;
+        };
+
+        for pane in &self.panes {
+            let Some(&row) = pane.conflict_rows.get(conflict_index) else {
+                continue;
+            };
+            let target = Point::new(row, 0);
+            pane.editor.update(cx, |editor, cx| {
+                editor.change_selections(
+                    SelectionEffects::scroll(Autoscroll::center()),
+                    window,
+                    cx,
+                    |selections| selections.select_ranges([target..target]),
+                );
+            });
+        }
+    }
+
+    fn pane_title(&self, side: MergeSide) -> SharedString {
+        let (side_name, branch_name) = match side {
+            MergeSide::Ours => ("Ours", self.ours_branch_name.as_ref()),
+            MergeSide::Base => ("Base", None),
+            MergeSide::Theirs => ("Theirs", self.theirs_branch_name.as_ref()),
+        };
+        match branch_name {
+            Some(branch_name) => format!("{side_name} ({branch_name})").into(),
+            None => side_name.into(),
+        }
+    }
+
+    fn result_title(&self, cx: &App) -> SharedString {
+        match self.conflict_set.read(cx).snapshot().conflicts.len() {
+            0 => "Result (all conflicts resolved)".into(),
+            1 => "Result (1 conflict left)".into(),
+            count => format!("Result ({count} conflicts left)").into(),
+        }
+    }
+
+    fn buffer_title(&self, cx: &App) -> String {
+        self.buffer
+            .read(cx)
+            .file()
+            .and_then(|file| {
+                Some(
+                    file.full_path(cx)
+                        .file_name()?
+                        .to_string_lossy()
+                        .to_string(),
+                )
+            })
+            .unwrap_or_else(|| "untitled".into())
+    }
+}
+
+/// The buffer's text with every conflict replaced by one of its sides, along
+/// with the row each conflict starts at in that text. Conflicts that didn't
+/// record their base are left empty on the base side.
+fn side_text(
+    buffer: &TextBufferSnapshot,
+    conflicts: &[ConflictRegion],
+    side: MergeSide,
+) -> (String, Vec<u32>) {
+    let mut text = String::new();
+    let mut conflict_rows = Vec::with_capacity(conflicts.len());
+    let mut row = 0;
+    let mut push = |text: &mut String, range: std::ops::Range<usize>| {
+        for chunk in buffer.text_for_range(range) {
+            row += chunk.matches('\n').count() as u32;
+            text.push_str(chunk);
+        }
+    };
+
+    let mut offset = 0;
+    for conflict in conflicts {
+        let range = conflict.range.to_offset(buffer);
+        push(&mut text, offset..range.start);
+        conflict_rows.push(row);
+        let side_range = match side {
+            MergeSide::Ours => Some(&conflict.ours),
+            MergeSide::Base => conflict.base.as_ref(),
+            MergeSide::Theirs => Some(&conflict.theirs),
+        };
+        if let Some(side_range) = side_range {
+            push(&mut text, side_range.to_offset(buffer));
+        }
+        offset = range.end;
+    }
+    push(&mut text, offset..buffer.len());
+    (text, conflict_rows)
+}
+
+fn pane_header(title: SharedString, cx: &App) -> impl IntoElement {
+    h_flex()
+        .flex_none()
+        .px_2()
+        .py_1()
+        .border_b_1()
+        .border_color(cx.theme().colors().border_variant)
+        .bg(cx.theme().colors().editor_subheader_background)
+        .child(Label::new(title).size(LabelSize::Small).color(Color::Muted))
+}
+
+impl EventEmitter<EditorEvent> for MergeView {}
+
+impl Focusable for MergeView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.result_editor.focus_handle(cx)
+    }
+}
+
+impl Item for MergeView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
+        format!("Merge {}", self.buffer_title(cx)).into()
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let path = self
+            .buffer
+            .read(cx)
+            .file()
+            .map(|file| file.full_path(cx).compact().to_string_lossy().into_owned())
+            .unwrap_or_else(|| "untitled".into());
+        Some(format!("Merge {path}").into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Merge View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.result_editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.result_editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.result_editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.result_editor.for_each_project_item(cx, f)
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.buffer.read(cx).is_dirty()
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.buffer.read(cx).has_conflict()
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.result_editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Arc<dyn Any + Send>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.result_editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumbs(&self, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.result_editor.breadcrumbs(cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.result_editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+
+    fn can_save(&self, cx: &App) -> bool {
+        self.result_editor.read(cx).can_save(cx)
+    }
+
+    fn save(
+        &mut self,
+        options: SaveOptions,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.result_editor
+            .update(cx, |editor, cx| editor.save(options, project, window, cx))
+    }
+}
+
+impl Render for MergeView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let border_color = cx.theme().colors().border;
+
+        v_flex()
+            .size_full()
+            .child(
+                h_flex()
+                    .h_1_2()
+                    .w_full()
+                    .border_b_1()
+                    .border_color(border_color)
+                    .children(
+                        self.panes
+                            .iter()
+                            .filter(|pane| pane.side != MergeSide::Base || self.show_base)
+                            .enumerate()
+                            .map(|(index, pane)| {
+                                v_flex()
+                                    .flex_1()
+                                    .min_w_0()
+                                    .h_full()
+                                    .when(index > 0, |this| {
+                                        this.border_l_1().border_color(border_color)
+                                    })
+                                    .child(pane_header(self.pane_title(pane.side), cx))
+                                    .child(pane.editor.clone())
+                            }),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .child(pane_header(self.result_title(cx), cx))
+                    .child(self.result_editor.clone()),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::status::{UnmergedStatus, UnmergedStatusCode};
+    use gpui::TestAppContext;
+    use project::{FakeFs, Fs};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::{path, rel_path::rel_path};
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    fn pane_text(merge_view: &Entity<MergeView>, side: MergeSide, cx: &App) -> String {
+        merge_view
+            .read(cx)
+            .panes
+            .iter()
+            .find(|pane| pane.side == side)
+            .map(|pane| pane.buffer.read(cx).text())
+            .unwrap_or_default()
+    }
+
+    #[gpui::test]
+    async fn test_merge_view(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "foo": "<<<<<<< x\nours\n=======\ntheirs\n>>>>>>> y\nmiddle\n<<<<<<< x\nmore ours\n||||||| base\nbase\n=======\nmore theirs\n>>>>>>> y\nend\n",
+            }),
+        )
+        .await;
+        fs.set_status_for_repo(
+            Path::new(path!("/project/.git")),
+            &[(
+                "foo",
+                UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                }
+                .into(),
+            )],
+        );
+        let project = project::Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, rel_path("foo")), None, true, window, cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        let merge_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                MergeView::open_for_active_editor(workspace, window, cx)
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        cx.update(|_, cx| {
+            assert_eq!(
+                pane_text(&merge_view, MergeSide::Ours, cx),
+                "ours\nmiddle\nmore ours\nend\n"
+            );
+            assert_eq!(
+                pane_text(&merge_view, MergeSide::Base, cx),
+                "middle\nbase\nend\n"
+            );
+            assert_eq!(
+                pane_text(&merge_view, MergeSide::Theirs, cx),
+                "theirs\nmiddle\nmore theirs\nend\n"
+            );
+            let merge_view = merge_view.read(cx);
+            assert!(merge_view.show_base);
+            assert_eq!(merge_view.panes[0].conflict_rows, vec![0, 2]);
+            assert_eq!(merge_view.pane_title(MergeSide::Ours), "Ours (x)");
+            assert_eq!(merge_view.result_title(cx), "Result (2 conflicts left)");
+        });
+
+        // Resolving a conflict in the result editor removes its markers, and
+        // every pane then shows the resolution.
+        cx.update(|_, cx| {
+            let merge_view = merge_view.read(cx);
+            let conflict = merge_view.conflict_set.read(cx).snapshot().conflicts[0].clone();
+            conflict.resolve(merge_view.buffer.clone(), &[conflict.theirs.clone()], cx);
+        });
+        cx.run_until_parked();
+
+        cx.update(|_, cx| {
+            assert_eq!(
+                merge_view.read(cx).buffer.read(cx).text(),
+                "theirs\nmiddle\n<<<<<<< x\nmore ours\n||||||| base\nbase\n=======\nmore theirs\n>>>>>>> y\nend\n"
+            );
+            assert_eq!(
+                pane_text(&merge_view, MergeSide::Ours, cx),
+                "theirs\nmiddle\nmore ours\nend\n"
+            );
+            assert_eq!(
+                pane_text(&merge_view, MergeSide::Base, cx),
+                "theirs\nmiddle\nbase\nend\n"
+            );
+            assert_eq!(
+                merge_view.read(cx).result_title(cx),
+                "Result (1 conflict left)"
+            );
+        });
+
+        // Moving to a conflict in the result editor reveals it in the panes.
+        cx.dispatch_action(git::GoToNextConflict);
+        cx.run_until_parked();
+        cx.update(|_, cx| {
+            let pane = &merge_view.read(cx).panes[0];
+            let cursor = pane.editor.update(cx, |editor, cx| {
+                editor
+                    .selections
+                    .newest::<Point>(&editor.display_snapshot(cx))
+                    .head()
+            });
+            assert_eq!(cursor, Point::new(2, 0));
+        });
+    }
+}
@@ -1963,9 +1963,12 @@ impl Addon for BranchDiffAddon {
 mod tests {
     use collections::HashMap;
     use db::indoc;
-    use editor::test::editor_test_context::{EditorTestContext, assert_state_with_diff};
+    use editor::{
+        ToPoint as _,
+        test::editor_test_context::{EditorTestContext, assert_state_with_diff},
+    };
     use git::status::{TrackedStatus, UnmergedStatus, UnmergedStatusCode};
-    use gpui::TestAppContext;
+    use gpui::{TestAppContext, VisualTestContext};
     use project::FakeFs;
     use serde_json::json;
     use settings::{DiffViewStyle, SettingsStore};
@@ -2501,6 +2504,96 @@ mod tests {
         assert_eq!(contents, "ours\n");
     }
 
+    #[gpui::test]
+    async fn test_go_to_conflicts(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "foo": "<<<<<<< x\nours\n=======\ntheirs\n>>>>>>> y\nmiddle\n<<<<<<< x\nmore ours\n||||||| base\nbase\n=======\nmore theirs\n>>>>>>> y\n",
+            }),
+        )
+        .await;
+        fs.set_status_for_repo(
+            Path::new(path!("/project/.git")),
+            &[(
+                "foo",
+                UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                }
+                .into(),
+            )],
+        );
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        cx.run_until_parked();
+
+        cx.focus(&workspace);
+        cx.update(|window, cx| {
+            window.dispatch_action(project_diff::Diff.boxed_clone(), cx);
+        });
+        cx.run_until_parked();
+
+        let item = workspace.update(cx, |workspace, cx| {
+            workspace.active_item_as::<ProjectDiff>(cx).unwrap()
+        });
+        cx.focus(&item);
+        let editor = item.read_with(cx, |item, cx| item.editor.read(cx).rhs_editor().clone());
+        let cursor_buffer_row = |cx: &mut VisualTestContext| {
+            editor.update(cx, |editor, cx| {
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let head = editor.selections.newest_anchor().head().to_point(&snapshot);
+                snapshot
+                    .point_to_buffer_point(head)
+                    .map(|(_, point, _)| point.row)
+            })
+        };
+
+        cx.dispatch_action(git::GoToNextConflict);
+        assert_eq!(cursor_buffer_row(cx), Some(6));
+        cx.dispatch_action(git::GoToNextConflict);
+        assert_eq!(cursor_buffer_row(cx), Some(0));
+        cx.dispatch_action(git::GoToPreviousConflict);
+        assert_eq!(cursor_buffer_row(cx), Some(6));
+
+        cx.update(|window, cx| {
+            let excerpt_id = editor.read(cx).buffer().read(cx).excerpt_ids()[0];
+            let buffer_id = editor.read(cx).buffer().read(cx).all_buffers()[0]
+                .read(cx)
+                .remote_id();
+            let conflict_set = editor
+                .read(cx)
+                .addon::<ConflictAddon>()
+                .unwrap()
+                .conflict_set(buffer_id)
+                .unwrap();
+            let conflict = conflict_set.read(cx).snapshot().conflicts[1].clone();
+            let base = conflict.base.clone().unwrap();
+            resolve_conflict(
+                editor.downgrade(),
+                excerpt_id,
+                conflict,
+                vec![base],
+                window,
+                cx,
+            )
+        })
+        .await;
+
+        let contents = fs.read_file_sync(path!("/project/foo")).unwrap();
+        let contents = String::from_utf8(contents).unwrap();
+        assert_eq!(
+            contents,
+            "<<<<<<< x\nours\n=======\ntheirs\n>>>>>>> y\nmiddle\nbase\n"
+        );
+    }
+
     #[gpui::test]
     async fn test_new_hunk_in_modified_file(cx: &mut TestAppContext) {
         init_test(cx);
@@ -0,0 +1,339 @@
+use anyhow::Result;
+use editor::{Editor, EditorEvent, MultiBuffer};
+use git::ReviewStagedChanges;
+use gpui::{
+    AnyElement, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, Render, Task, Window,
+};
+use language::Capability;
+use project::{Project, git_store::Repository};
+use std::any::{Any, TypeId};
+use std::sync::Arc;
+use ui::prelude::*;
+use util::ResultExt as _;
+use workspace::{
+    Item, ItemNavHistory, Workspace,
+    item::{ItemEvent, TabContentParams},
+    searchable::SearchableItemHandle,
+};
+
+use crate::commit_view::{CommitDiffAddon, add_commit_files_to_multibuffer};
+use crate::git_panel::GitPanel;
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
+        workspace.register_action(|workspace, _: &ReviewStagedChanges, window, cx| {
+            StagedChangesView::deploy(workspace, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// A read-only multibuffer showing what the next commit would contain: the
+/// index compared against `HEAD`, independent of unstaged worktree edits.
+pub struct StagedChangesView {
+    editor: Entity<Editor>,
+    multibuffer: Entity<MultiBuffer>,
+    project: Entity<Project>,
+    _load: Task<()>,
+}
+
+impl StagedChangesView {
+    fn deploy(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let Some(repository) = workspace
+            .panel::<GitPanel>(cx)
+            .and_then(|panel| panel.read(cx).active_repository.clone())
+        else {
+            return;
+        };
+        workspace.hide_modal(window, cx);
+
+        if let Some(existing) = workspace.item_of_type::<Self>(cx) {
+            existing.update(cx, |view, cx| view.reload(repository, cx));
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let project = workspace.project().clone();
+        let view = cx.new(|cx| Self::new(repository, project, window, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let multibuffer = cx.new(|_| MultiBuffer::new(Capability::ReadOnly));
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer.clone(), Some(project.clone()), window, cx);
+            editor.disable_inline_diagnostics();
+            editor.set_show_breakpoints(false, cx);
+            editor.set_expand_all_diff_hunks(cx);
+            editor
+        });
+
+        let mut this = Self {
+            editor,
+            multibuffer,
+            project,
+            _load: Task::ready(()),
+        };
+        this.reload(repository, cx);
+        this
+    }
+
+    fn reload(&mut self, repository: Entity<Repository>, cx: &mut Context<Self>) {
+        self.multibuffer
+            .update(cx, |multibuffer, cx| multibuffer.clear(cx));
+
+        let staged_changes =
+            repository.update(cx, |repository, _| repository.load_staged_changes());
+        let language_registry = self.project.read(cx).languages().clone();
+        let first_worktree_id = self
+            .project
+            .read(cx)
+            .worktrees(cx)
+            .next()
+            .map(|worktree| worktree.read(cx).id());
+        let multibuffer = self.multibuffer.downgrade();
+
+        self._load = cx.spawn(async move |this, cx| {
+            let result: Result<()> = async {
+                let staged_changes = staged_changes.await??;
+                let (binary_buffer_ids, file_statuses) = add_commit_files_to_multibuffer(
+                    staged_changes.files,
+                    "Staged",
+                    multibuffer,
+                    repository,
+                    first_worktree_id,
+                    language_registry,
+                    cx,
+                )
+                .await?;
+
+                this.update(cx, |this, cx| {
+                    this.editor.update(cx, |editor, cx| {
+                        editor.register_addon(CommitDiffAddon { file_statuses });
+                        if !binary_buffer_ids.is_empty() {
+                            editor.fold_buffers(binary_buffer_ids, cx);
+                        }
+                    });
+                })
+            }
+            .await;
+            result.log_err();
+        });
+    }
+}
+
+impl EventEmitter<EditorEvent> for StagedChangesView {}
+
+impl Focusable for StagedChangesView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for StagedChangesView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        "Staged Changes".into()
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Staged Changes View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.editor.for_each_project_item(cx, f)
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Arc<dyn Any + Send>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for StagedChangesView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("StagedChangesView")
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(div().flex_grow().child(self.editor.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    use super::*;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_staged_changes_view(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "image.bin": "\0binary",
+                "src.rs": "staged\n",
+                "unstaged.rs": "unstaged\n",
+            }),
+        )
+        .await;
+        fs.set_head_for_repo(
+            path!("/project/.git").as_ref(),
+            &[
+                ("src.rs", "committed\n".into()),
+                ("unstaged.rs", "committed\n".into()),
+            ],
+            "deadbeef",
+        );
+        fs.set_index_for_repo(
+            path!("/project/.git").as_ref(),
+            &[
+                ("image.bin", "\0binary".into()),
+                ("src.rs", "staged\n".into()),
+                ("unstaged.rs", "committed\n".into()),
+            ],
+        );
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (_multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+
+        let repository = project
+            .read_with(cx, |project, cx| project.active_repository(cx))
+            .unwrap();
+        let view = cx.new_window_entity(|window, cx| {
+            StagedChangesView::new(repository, project.clone(), window, cx)
+        });
+        cx.run_until_parked();
+
+        let mut files = view.read_with(cx, |view, cx| {
+            let editor = view.editor.read(cx);
+            view.multibuffer
+                .read(cx)
+                .all_buffers()
+                .into_iter()
+                .map(|buffer| {
+                    let buffer = buffer.read(cx);
+                    (
+                        buffer.file().unwrap().path().as_unix_str().to_string(),
+                        buffer.text(),
+                        editor.is_buffer_folded(buffer.remote_id(), cx),
+                    )
+                })
+                .collect::<Vec<_>>()
+        });
+        files.sort();
+        assert_eq!(
+            files,
+            [
+                (
+                    "image.bin".to_string(),
+                    "(binary file not shown)".to_string(),
+                    true
+                ),
+                ("src.rs".to_string(), "staged\n".to_string(), false),
+            ],
+            "Only staged files are shown, with binary ones folded"
+        );
+    }
+}
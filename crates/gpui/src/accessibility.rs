@@ -0,0 +1,181 @@
+//! Exposes the contents of windows to assistive technologies such as screen
+//! readers, through [AccessKit](https://accesskit.dev).
+//!
+//! Elements describe themselves by inserting [`accesskit::Node`]s while they
+//! paint, see [`Window::with_accessibility_node`]. At the end of each frame the
+//! window assembles those nodes into a tree and, when it changed, hands it to
+//! the platform window.
+
+use std::hash::Hash;
+
+pub use accesskit;
+use accesskit::{Affine, Node, NodeId, Role, Tree, TreeUpdate};
+use collections::FxHashMap;
+
+use crate::{FocusHandle, FocusId, SharedString, hash};
+
+/// The id of the node representing the window itself, which all other nodes
+/// descend from.
+pub(crate) const ROOT_ACCESSIBILITY_NODE_ID: NodeId = NodeId(0);
+
+/// Returns a node id derived from `value`, for elements that need a stable
+/// id across frames.
+pub fn accessibility_node_id(value: &impl Hash) -> NodeId {
+    NodeId(hash(value))
+}
+
+impl FocusId {
+    /// The id of the accessibility node of the element tracking this focus
+    /// handle, which lets assistive technologies follow keyboard focus.
+    pub fn accessibility_node_id(&self) -> NodeId {
+        accessibility_node_id(self)
+    }
+}
+
+impl FocusHandle {
+    /// The id of the accessibility node of the element tracking this focus
+    /// handle.
+    pub fn accessibility_node_id(&self) -> NodeId {
+        self.id.accessibility_node_id()
+    }
+}
+
+/// How an interactive element describes itself to assistive technologies.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityProperties {
+    /// What kind of control the element is.
+    pub role: Role,
+    /// The text a screen reader announces for the element.
+    pub label: Option<SharedString>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct AccessibilityNodeRecord {
+    pub(crate) id: NodeId,
+    pub(crate) parent: NodeId,
+    pub(crate) node: Node,
+}
+
+/// Builds a full tree update from the nodes recorded during a frame.
+pub(crate) fn accessibility_tree_update(
+    records: &[AccessibilityNodeRecord],
+    focus: Option<FocusId>,
+    scale_factor: f32,
+) -> TreeUpdate {
+    let mut root = Node::new(Role::Window);
+    root.set_transform(Affine::scale(scale_factor as f64));
+
+    let mut nodes = Vec::with_capacity(records.len() + 1);
+    nodes.push((ROOT_ACCESSIBILITY_NODE_ID, root));
+    let mut indices = FxHashMap::default();
+    indices.insert(ROOT_ACCESSIBILITY_NODE_ID, 0);
+    for record in records {
+        // An element painted twice in one frame keeps its first node.
+        if indices.contains_key(&record.id) {
+            continue;
+        }
+        let parent_index = indices.get(&record.parent).copied().unwrap_or_default();
+        nodes[parent_index].1.push_child(record.id);
+        indices.insert(record.id, nodes.len());
+        nodes.push((record.id, record.node.clone()));
+    }
+
+    let focus = focus
+        .map(|focus_id| focus_id.accessibility_node_id())
+        .filter(|node_id| indices.contains_key(node_id))
+        .unwrap_or(ROOT_ACCESSIBILITY_NODE_ID);
+
+    let mut tree = Tree::new(ROOT_ACCESSIBILITY_NODE_ID);
+    tree.toolkit_name = Some("GPUI".to_string());
+    tree.toolkit_version = Some(env!("CARGO_PKG_VERSION").to_string());
+    TreeUpdate {
+        nodes,
+        tree: Some(tree),
+        focus,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as gpui, Context, InteractiveElement as _, IntoElement, Render, TestAppContext,
+        Window, div,
+    };
+
+    use super::*;
+
+    struct AccessibleView {
+        label: SharedString,
+    }
+
+    impl Render for AccessibleView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .id("save")
+                .accessibility_role(Role::Button)
+                .accessibility_label(self.label.clone())
+        }
+    }
+
+    #[gpui::test]
+    fn test_tree_is_only_updated_when_it_changes(cx: &mut TestAppContext) {
+        let window = cx.add_window(|_, _| AccessibleView {
+            label: "Save".into(),
+        });
+        let test_window = cx.test_window(window.into());
+        let button_labels = || {
+            test_window
+                .0
+                .lock()
+                .accessibility_updates
+                .iter()
+                .map(|update| {
+                    update
+                        .nodes
+                        .iter()
+                        .find(|(_, node)| node.role() == Role::Button)
+                        .and_then(|(_, node)| node.label().map(str::to_string))
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(button_labels(), [Some("Save".to_string())]);
+
+        window.update(cx, |_, window, _| window.refresh()).unwrap();
+        assert_eq!(button_labels(), [Some("Save".to_string())]);
+
+        window
+            .update(cx, |view, _, cx| {
+                view.label = "Save All".into();
+                cx.notify();
+            })
+            .unwrap();
+        assert_eq!(
+            button_labels(),
+            [Some("Save".to_string()), Some("Save All".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_tree_update() {
+        let record = |id, parent, role| AccessibilityNodeRecord {
+            id: NodeId(id),
+            parent: NodeId(parent),
+            node: Node::new(role),
+        };
+        let update = accessibility_tree_update(
+            &[
+                record(1, 0, Role::Group),
+                record(2, 1, Role::Button),
+                record(2, 1, Role::TextInput),
+            ],
+            None,
+            2.,
+        );
+        let ids = update.nodes.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        assert_eq!(ids, [ROOT_ACCESSIBILITY_NODE_ID, NodeId(1), NodeId(2)]);
+        assert_eq!(update.nodes[0].1.children(), [NodeId(1)]);
+        assert_eq!(update.nodes[1].1.children(), [NodeId(2)]);
+        assert_eq!(update.nodes[2].1.role(), Role::Button);
+        assert_eq!(update.focus, ROOT_ACCESSIBILITY_NODE_ID);
+    }
+}
@@ -36,15 +36,16 @@ pub use visual_test_context::*;
 #[cfg(any(feature = "inspector", debug_assertions))]
 use crate::InspectorElementRegistry;
 use crate::{
-    Action, ActionBuildError, ActionRegistry, Any, AnyView, AnyWindowHandle, AppContext, Arena,
-    ArenaBox, Asset, AssetSource, BackgroundExecutor, Bounds, ClipboardItem, CursorStyle,
-    DispatchPhase, DisplayId, EventEmitter, FocusHandle, FocusMap, ForegroundExecutor, Global,
-    KeyBinding, KeyContext, Keymap, Keystroke, LayoutId, Menu, MenuItem, OwnedMenu,
-    PathPromptOptions, Pixels, Platform, PlatformDisplay, PlatformKeyboardLayout,
-    PlatformKeyboardMapper, Point, Priority, PromptBuilder, PromptButton, PromptHandle,
-    PromptLevel, Render, RenderImage, RenderablePromptHandle, Reservation, ScreenCaptureSource,
-    SharedString, SubscriberSet, Subscription, SvgRenderer, Task, TextRenderingMode, TextSystem,
-    ThermalState, Window, WindowAppearance, WindowHandle, WindowId, WindowInvalidator,
+    AccessibilityPreferences, Action, ActionBuildError, ActionRegistry, Any, AnyView,
+    AnyWindowHandle, AppContext, Arena, ArenaBox, Asset, AssetSource, BackgroundExecutor, Bounds,
+    ClipboardItem, CursorStyle, DispatchPhase, DisplayId, EventEmitter, FocusHandle, FocusMap,
+    ForegroundExecutor, Global, KeyBinding, KeyContext, Keymap, Keystroke, LayoutId, Menu,
+    MenuItem, OwnedMenu, PathPromptOptions, Pixels, Platform, PlatformDisplay,
+    PlatformKeyboardLayout, PlatformKeyboardMapper, Point, PresentationMode, Priority,
+    PromptBuilder, PromptButton, PromptHandle, PromptLevel, Render, RenderImage,
+    RenderablePromptHandle, Reservation, ScreenCaptureSource, SharedString, SubscriberSet,
+    Subscription, SvgRenderer, Task, TextRenderingMode, TextSystem, ThermalState, Window,
+    WindowAppearance, WindowHandle, WindowId, WindowInvalidator,
     colors::{Colors, GlobalColors},
     current_platform, hash, init_app_menus,
 };
@@ -619,6 +620,9 @@ pub struct App {
     pub(crate) keystroke_interceptors: SubscriberSet<(), KeystrokeObserver>,
     pub(crate) keyboard_layout_observers: SubscriberSet<(), Handler>,
     pub(crate) thermal_state_observers: SubscriberSet<(), Handler>,
+    pub(crate) accessibility_preferences_observers: SubscriberSet<(), Handler>,
+    pub(crate) global_hotkey_handlers: SubscriberSet<u32, Handler>,
+    next_global_hotkey_id: u32,
     pub(crate) release_listeners: SubscriberSet<EntityId, ReleaseListener>,
     pub(crate) global_observers: SubscriberSet<TypeId, Handler>,
     pub(crate) quit_observers: SubscriberSet<(), QuitHandler>,
@@ -638,6 +642,7 @@ pub struct App {
     #[cfg(any(test, feature = "test-support", debug_assertions))]
     pub(crate) name: Option<&'static str>,
     pub(crate) text_rendering_mode: Rc<Cell<TextRenderingMode>>,
+    pub(crate) presentation_mode: Rc<Cell<PresentationMode>>,
     quit_mode: QuitMode,
     quitting: bool,
     /// Per-App element arena. This isolates element allocations between different
@@ -672,6 +677,7 @@ impl App {
                 platform: platform.clone(),
                 text_system,
                 text_rendering_mode: Rc::new(Cell::new(TextRenderingMode::default())),
+                presentation_mode: Rc::new(Cell::new(PresentationMode::default())),
                 mode: GpuiMode::Production,
                 actions: Rc::new(ActionRegistry::default()),
                 flushing_effects: false,
@@ -706,6 +712,9 @@ impl App {
                 keystroke_interceptors: SubscriberSet::new(),
                 keyboard_layout_observers: SubscriberSet::new(),
                 thermal_state_observers: SubscriberSet::new(),
+                accessibility_preferences_observers: SubscriberSet::new(),
+                global_hotkey_handlers: SubscriberSet::new(),
+                next_global_hotkey_id: 0,
                 global_observers: SubscriberSet::new(),
                 quit_observers: SubscriberSet::new(),
                 restart_observers: SubscriberSet::new(),
@@ -757,6 +766,31 @@ impl App {
             }
         }));
 
+        platform.on_accessibility_preferences_change(Box::new({
+            let app = Rc::downgrade(&app);
+            move || {
+                if let Some(app) = app.upgrade() {
+                    let cx = &mut app.borrow_mut();
+                    cx.accessibility_preferences_observers
+                        .clone()
+                        .retain(&(), move |callback| (callback)(cx));
+                    cx.refresh_windows();
+                }
+            }
+        }));
+
+        platform.on_global_hotkey(Box::new({
+            let app = Rc::downgrade(&app);
+            move |id| {
+                if let Some(app) = app.upgrade() {
+                    let cx = &mut app.borrow_mut();
+                    cx.global_hotkey_handlers
+                        .clone()
+                        .retain(&id, move |callback| (callback)(cx));
+                }
+            }
+        }));
+
         platform.on_quit(Box::new({
             let cx = app.clone();
             move || {
@@ -819,6 +853,42 @@ impl App {
         subscription
     }
 
+    /// Registers a system-wide hotkey, which invokes `callback` even while none of
+    /// the application's windows are focused. The hotkey is released when the
+    /// returned subscription is dropped.
+    ///
+    /// The `description` is shown by desktops that let users review and rebind
+    /// global shortcuts, such as the XDG desktop portal on Wayland.
+    pub fn register_global_hotkey<F>(
+        &mut self,
+        keystroke: &Keystroke,
+        description: &str,
+        mut callback: F,
+    ) -> Result<Subscription>
+    where
+        F: 'static + FnMut(&mut App),
+    {
+        let id = self.next_global_hotkey_id;
+        self.next_global_hotkey_id += 1;
+        self.platform
+            .register_global_hotkey(id, keystroke, description)
+            .with_context(|| format!("registering global hotkey {}", keystroke.unparse()))?;
+
+        let (subscription, activate) = self.global_hotkey_handlers.insert(
+            id,
+            Box::new(move |cx| {
+                callback(cx);
+                true
+            }),
+        );
+        activate();
+        let platform = self.platform.clone();
+        Ok(Subscription::join(
+            subscription,
+            Subscription::new(move || platform.unregister_global_hotkey(id)),
+        ))
+    }
+
     /// Gracefully quit the application via the platform's standard routine.
     pub fn quit(&self) {
         self.platform.quit();
@@ -1120,6 +1190,27 @@ impl App {
         subscription
     }
 
+    /// Returns the accessibility preferences of the desktop.
+    pub fn accessibility_preferences(&self) -> AccessibilityPreferences {
+        self.platform.accessibility_preferences()
+    }
+
+    /// Invokes a handler when the accessibility preferences of the desktop change
+    pub fn on_accessibility_preferences_change<F>(&self, mut callback: F) -> Subscription
+    where
+        F: 'static + FnMut(&mut App),
+    {
+        let (subscription, activate) = self.accessibility_preferences_observers.insert(
+            (),
+            Box::new(move |cx| {
+                callback(cx);
+                true
+            }),
+        );
+        activate();
+        subscription
+    }
+
     /// Returns the appearance of the application's windows.
     pub fn window_appearance(&self) -> WindowAppearance {
         self.platform.window_appearance()
@@ -1140,6 +1231,16 @@ impl App {
         self.text_rendering_mode.get()
     }
 
+    /// Sets how the application's windows schedule drawing.
+    pub fn set_presentation_mode(&mut self, mode: PresentationMode) {
+        self.presentation_mode.set(mode);
+    }
+
+    /// Returns how the application's windows schedule drawing.
+    pub fn presentation_mode(&self) -> PresentationMode {
+        self.presentation_mode.get()
+    }
+
     /// Writes data to the platform clipboard.
     pub fn write_to_clipboard(&self, item: ClipboardItem) {
         self.platform.write_to_clipboard(item)
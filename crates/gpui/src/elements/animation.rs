@@ -4,7 +4,8 @@ use std::{
 };
 
 use crate::{
-    AnyElement, App, Element, ElementId, GlobalElementId, InspectorElementId, IntoElement, Window,
+    AnyElement, App, Element, ElementId, GlobalElementId, Hsla, InspectorElementId, IntoElement,
+    Pixels, Point, Rgba, Size, Window, point, px, size,
 };
 
 pub use easing::*;
@@ -143,13 +144,19 @@ impl<E: IntoElement + 'static> Element for AnimationElement<E> {
                 start: Instant::now(),
                 animation_ix: 0,
             });
-            let animation_ix = state.animation_ix;
+            let mut animation_ix = state.animation_ix;
 
             let mut delta = state.start.elapsed().as_secs_f32()
                 / self.animations[animation_ix].duration.as_secs_f32();
 
             let mut done = false;
-            if delta > 1.0 {
+            if cx.accessibility_preferences().reduce_motion {
+                // Skip straight to the end, and hold repeating animations still.
+                animation_ix = self.animations.len() - 1;
+                state.animation_ix = animation_ix;
+                delta = 1.0;
+                done = true;
+            } else if delta > 1.0 {
                 if self.animations[animation_ix].oneshot {
                     if animation_ix >= self.animations.len() - 1 {
                         done = true;
@@ -206,6 +213,298 @@ impl<E: IntoElement + 'static> Element for AnimationElement<E> {
     }
 }
 
+/// A value that can be animated by interpolating between two of its states.
+pub trait Animatable: Copy + PartialEq + 'static {
+    /// Returns the state `progress` of the way from `self` to `target`.
+    /// Springs overshoot, so `progress` may fall slightly outside of 0 to 1.
+    fn lerp(&self, target: &Self, progress: f32) -> Self;
+}
+
+impl Animatable for f32 {
+    fn lerp(&self, target: &Self, progress: f32) -> Self {
+        self + (target - self) * progress
+    }
+}
+
+impl Animatable for Pixels {
+    fn lerp(&self, target: &Self, progress: f32) -> Self {
+        px(f32::from(*self).lerp(&f32::from(*target), progress))
+    }
+}
+
+impl<T: Animatable + Clone + std::fmt::Debug + Default> Animatable for Point<T> {
+    fn lerp(&self, target: &Self, progress: f32) -> Self {
+        point(
+            self.x.lerp(&target.x, progress),
+            self.y.lerp(&target.y, progress),
+        )
+    }
+}
+
+impl<T: Animatable + Clone + std::fmt::Debug + Default> Animatable for Size<T> {
+    fn lerp(&self, target: &Self, progress: f32) -> Self {
+        size(
+            self.width.lerp(&target.width, progress),
+            self.height.lerp(&target.height, progress),
+        )
+    }
+}
+
+impl Animatable for Hsla {
+    /// Colors are mixed in RGB space, which avoids sweeping through unrelated
+    /// hues on the way.
+    fn lerp(&self, target: &Self, progress: f32) -> Self {
+        let progress = progress.clamp(0., 1.);
+        let from = Rgba::from(*self);
+        let to = Rgba::from(*target);
+        Hsla::from(Rgba {
+            r: from.r.lerp(&to.r, progress),
+            g: from.g.lerp(&to.g, progress),
+            b: from.b.lerp(&to.b, progress),
+            a: from.a.lerp(&to.a, progress),
+        })
+    }
+}
+
+/// A damped spring, which settles on its target at a speed and with an
+/// amount of bounce set by its parameters rather than after a fixed duration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spring {
+    /// How strongly the spring pulls toward its target.
+    pub stiffness: f32,
+    /// How strongly the spring resists moving, which limits the overshoot.
+    pub damping: f32,
+    /// The mass on the spring, which slows down both pulling and resisting.
+    pub mass: f32,
+}
+
+impl Spring {
+    /// A spring that settles quickly without overshooting, suited to small
+    /// transitions such as hover states.
+    pub const fn snappy() -> Self {
+        Self {
+            stiffness: 700.,
+            damping: 55.,
+            mass: 1.,
+        }
+    }
+
+    /// A spring that overshoots slightly, suited to moving content around.
+    pub const fn bouncy() -> Self {
+        Self {
+            stiffness: 400.,
+            damping: 28.,
+            mass: 1.,
+        }
+    }
+
+    /// A slower spring that settles without overshooting, suited to panels
+    /// sliding in.
+    pub const fn gentle() -> Self {
+        Self {
+            stiffness: 200.,
+            damping: 30.,
+            mass: 1.,
+        }
+    }
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self::snappy()
+    }
+}
+
+/// How an [`AnimatedValue`] moves toward a new target.
+#[derive(Clone)]
+pub enum Transition {
+    /// Moves to the target over a fixed duration, using an easing function
+    /// like [`Animation`].
+    Eased {
+        /// How long it takes to reach the target.
+        duration: Duration,
+        /// Maps the elapsed fraction of the duration to the fraction of the
+        /// distance covered.
+        easing: Rc<dyn Fn(f32) -> f32>,
+    },
+    /// Moves to the target with a spring.
+    Spring(Spring),
+}
+
+impl Transition {
+    /// A transition over the given duration, with the given easing function.
+    pub fn eased(duration: Duration, easing: impl Fn(f32) -> f32 + 'static) -> Self {
+        Self::Eased {
+            duration,
+            easing: Rc::new(easing),
+        }
+    }
+}
+
+impl From<Spring> for Transition {
+    fn from(spring: Spring) -> Self {
+        Self::Spring(spring)
+    }
+}
+
+/// Springs are stepped in increments of at most this long, which keeps the
+/// simulation stable when frames are dropped.
+const SPRING_STEP: Duration = Duration::from_millis(4);
+
+/// A spring within this distance of its target, as a fraction of the
+/// distance it travels, and moving slower than this, has settled.
+const SPRING_REST_THRESHOLD: f32 = 0.001;
+
+/// A value that moves toward its target over time rather than jumping to it.
+///
+/// Elements usually animate values with [`Window::use_animated_value`],
+/// which advances the value as frames are drawn.
+#[derive(Clone)]
+pub struct AnimatedValue<T> {
+    from: T,
+    target: T,
+    transition: Transition,
+    /// How far the value is from `from` to `target`, between 0 and 1 except
+    /// while a spring overshoots.
+    progress: f32,
+    /// For springs, the rate at which `progress` changes per second.
+    velocity: f32,
+    started_at: Instant,
+    updated_at: Instant,
+}
+
+impl<T: Animatable> AnimatedValue<T> {
+    /// Creates a value that's at rest.
+    pub fn new(value: T, transition: impl Into<Transition>) -> Self {
+        let now = Instant::now();
+        Self {
+            from: value,
+            target: value,
+            transition: transition.into(),
+            progress: 1.,
+            velocity: 0.,
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// The value this is moving toward.
+    pub fn target(&self) -> T {
+        self.target
+    }
+
+    /// The current value, as of the last call to [`Self::advance`].
+    pub fn value(&self) -> T {
+        self.from.lerp(&self.target, self.progress)
+    }
+
+    /// Whether the value has yet to settle on its target.
+    pub fn is_animating(&self) -> bool {
+        self.progress != 1. || self.velocity != 0.
+    }
+
+    /// Starts moving toward `target` from the current value.
+    pub fn animate_to(&mut self, target: T) {
+        self.animate_to_at(target, Instant::now());
+    }
+
+    /// Moves straight to `value`, stopping any ongoing animation.
+    pub fn jump_to(&mut self, value: T) {
+        self.from = value;
+        self.target = value;
+        self.progress = 1.;
+        self.velocity = 0.;
+    }
+
+    /// Advances the value to now, returning it.
+    pub fn advance(&mut self) -> T {
+        self.advance_to(Instant::now())
+    }
+
+    fn animate_to_at(&mut self, target: T, now: Instant) {
+        if target == self.target {
+            return;
+        }
+        self.from = self.value();
+        self.target = target;
+        self.progress = 0.;
+        // The spring's velocity is relative to the distance it travels, so it
+        // doesn't carry over to a new target.
+        self.velocity = 0.;
+        self.started_at = now;
+        self.updated_at = now;
+    }
+
+    fn advance_to(&mut self, now: Instant) -> T {
+        match &self.transition {
+            Transition::Eased { duration, easing } => {
+                let elapsed = now.saturating_duration_since(self.started_at);
+                self.progress = if elapsed >= *duration {
+                    1.
+                } else {
+                    easing(elapsed.as_secs_f32() / duration.as_secs_f32())
+                };
+            }
+            Transition::Spring(spring) => {
+                let spring = *spring;
+                let mut remaining = now.saturating_duration_since(self.updated_at);
+                while self.is_animating() && !remaining.is_zero() {
+                    let step = remaining.min(SPRING_STEP);
+                    remaining -= step;
+                    self.step_spring(spring, step.as_secs_f32());
+                }
+            }
+        }
+        self.updated_at = now;
+        self.value()
+    }
+
+    fn step_spring(&mut self, spring: Spring, seconds: f32) {
+        let displacement = self.progress - 1.;
+        let force = -spring.stiffness * displacement - spring.damping * self.velocity;
+        self.velocity += force / spring.mass * seconds;
+        self.progress += self.velocity * seconds;
+        if (self.progress - 1.).abs() < SPRING_REST_THRESHOLD
+            && self.velocity.abs() < SPRING_REST_THRESHOLD
+        {
+            self.progress = 1.;
+            self.velocity = 0.;
+        }
+    }
+}
+
+impl Window {
+    /// Animates a value belonging to the element with the given key toward
+    /// `target`, returning its value for the current frame and redrawing the
+    /// current view until it settles.
+    ///
+    /// The first time the key is rendered, the value starts at `initial`,
+    /// which lets elements animate in as they appear. Later frames animate
+    /// toward each new `target` from wherever the value is at the time.
+    pub fn use_animated_value<T: Animatable>(
+        &mut self,
+        key: impl Into<ElementId>,
+        initial: T,
+        target: T,
+        transition: impl Into<Transition>,
+    ) -> T {
+        let transition = transition.into();
+        self.with_global_id(key.into(), |global_id, window| {
+            window.with_element_state(global_id, |state: Option<AnimatedValue<T>>, window| {
+                let mut state =
+                    state.unwrap_or_else(|| AnimatedValue::new(initial, transition.clone()));
+                state.transition = transition;
+                state.animate_to(target);
+                let value = state.advance();
+                if state.is_animating() {
+                    window.request_animation_frame();
+                }
+                (value, state)
+            })
+        })
+    }
+}
+
 mod easing {
     use std::f32::consts::PI;
 
@@ -261,3 +560,49 @@ mod easing {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eased_value_reaches_target_after_duration() {
+        let start = Instant::now();
+        let mut value =
+            AnimatedValue::new(0., Transition::eased(Duration::from_millis(128), linear));
+        value.animate_to_at(10., start);
+
+        assert_eq!(value.advance_to(start + Duration::from_millis(64)), 5.);
+        assert!(value.is_animating());
+        assert_eq!(value.advance_to(start + Duration::from_millis(200)), 10.);
+        assert!(!value.is_animating());
+    }
+
+    #[test]
+    fn test_spring_overshoots_and_settles() {
+        let start = Instant::now();
+        let mut value = AnimatedValue::new(0., Spring::bouncy());
+        value.animate_to_at(1., start);
+
+        let mut max = 0f32;
+        for frame in 1..=120 {
+            max = max.max(value.advance_to(start + Duration::from_millis(frame * 16)));
+        }
+        assert!(max > 1.);
+        assert!(!value.is_animating());
+        assert_eq!(value.value(), 1.);
+    }
+
+    #[test]
+    fn test_retargeting_starts_from_current_value() {
+        let start = Instant::now();
+        let mut value =
+            AnimatedValue::new(0., Transition::eased(Duration::from_millis(128), linear));
+        value.animate_to_at(10., start);
+        value.advance_to(start + Duration::from_millis(64));
+
+        value.animate_to_at(0., start + Duration::from_millis(64));
+        assert_eq!(value.value(), 5.);
+        assert_eq!(value.advance_to(start + Duration::from_millis(96)), 3.75);
+    }
+}
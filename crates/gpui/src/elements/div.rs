@@ -16,14 +16,14 @@
 //! constructed by combining these two systems into an all-in-one element.
 
 use crate::{
-    AbsoluteLength, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, App, Bounds, ClickEvent,
-    DispatchPhase, Display, Element, ElementId, Entity, FocusHandle, Global, GlobalElementId,
-    Hitbox, HitboxBehavior, HitboxId, InspectorElementId, IntoElement, IsZero, KeyContext,
-    KeyDownEvent, KeyUpEvent, KeyboardButton, KeyboardClickEvent, LayoutId, ModifiersChangedEvent,
-    MouseButton, MouseClickEvent, MouseDownEvent, MouseMoveEvent, MousePressureEvent, MouseUpEvent,
-    Overflow, ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size, Style,
-    StyleRefinement, Styled, Task, TooltipId, Visibility, Window, WindowControlArea, point, px,
-    size,
+    AbsoluteLength, AccessibilityProperties, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, App,
+    Bounds, ClickEvent, DispatchPhase, Display, Element, ElementId, Entity, FocusHandle, Global,
+    GlobalElementId, Hitbox, HitboxBehavior, HitboxId, InspectorElementId, IntoElement, IsZero,
+    KeyContext, KeyDownEvent, KeyUpEvent, KeyboardButton, KeyboardClickEvent, LayoutId,
+    ModifiersChangedEvent, MouseButton, MouseClickEvent, MouseDownEvent, MouseMoveEvent,
+    MousePressureEvent, MouseUpEvent, Overflow, ParentElement, PinchEvent, Pixels, Point, Render,
+    ScrollWheelEvent, SharedString, Size, Style, StyleRefinement, Styled, SwipeEvent, Task,
+    TooltipId, Visibility, Window, WindowControlArea, point, px, size,
 };
 use collections::HashMap;
 use refineable::Refineable;
@@ -353,6 +353,32 @@ impl Interactivity {
             }));
     }
 
+    /// Bind the given callback to pinch gestures during the bubble phase.
+    /// The imperative API equivalent to [`InteractiveElement::on_pinch`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    pub fn on_pinch(&mut self, listener: impl Fn(&PinchEvent, &mut Window, &mut App) + 'static) {
+        self.pinch_listeners
+            .push(Box::new(move |event, phase, hitbox, window, cx| {
+                if phase == DispatchPhase::Bubble && hitbox.is_hovered(window) {
+                    (listener)(event, window, cx);
+                }
+            }));
+    }
+
+    /// Bind the given callback to multi-finger swipe gestures during the bubble phase.
+    /// The imperative API equivalent to [`InteractiveElement::on_swipe`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    pub fn on_swipe(&mut self, listener: impl Fn(&SwipeEvent, &mut Window, &mut App) + 'static) {
+        self.swipe_listeners
+            .push(Box::new(move |event, phase, hitbox, window, cx| {
+                if phase == DispatchPhase::Bubble && hitbox.is_hovered(window) {
+                    (listener)(event, window, cx);
+                }
+            }));
+    }
+
     /// Bind the given callback to an action dispatch during the capture phase.
     /// The imperative API equivalent to [`InteractiveElement::capture_action`].
     ///
@@ -628,6 +654,27 @@ impl Interactivity {
         self.window_control = Some(area);
     }
 
+    /// Expose this element to assistive technologies as a control of the given role.
+    /// The imperative API equivalent to [`InteractiveElement::accessibility_role`].
+    pub fn accessibility_role(&mut self, role: accesskit::Role) {
+        self.accessibility_properties().role = role;
+    }
+
+    /// Set the text assistive technologies announce for this element.
+    /// The imperative API equivalent to [`InteractiveElement::accessibility_label`].
+    pub fn accessibility_label(&mut self, label: impl Into<SharedString>) {
+        self.accessibility_properties().label = Some(label.into());
+    }
+
+    fn accessibility_properties(&mut self) -> &mut AccessibilityProperties {
+        self.accessibility.get_or_insert_with(|| {
+            Box::new(AccessibilityProperties {
+                role: accesskit::Role::GenericContainer,
+                label: None,
+            })
+        })
+    }
+
     /// Block non-scroll mouse interactions with elements behind this element's hitbox.
     /// The imperative API equivalent to [`InteractiveElement::block_mouse_except_scroll`].
     ///
@@ -905,6 +952,24 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Bind the given callback to pinch gestures during the bubble phase.
+    /// The fluent API equivalent to [`Interactivity::on_pinch`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    fn on_pinch(mut self, listener: impl Fn(&PinchEvent, &mut Window, &mut App) + 'static) -> Self {
+        self.interactivity().on_pinch(listener);
+        self
+    }
+
+    /// Bind the given callback to multi-finger swipe gestures during the bubble phase.
+    /// The fluent API equivalent to [`Interactivity::on_swipe`].
+    ///
+    /// See [`Context::listener`](crate::Context::listener) to get access to a view's state from this callback.
+    fn on_swipe(mut self, listener: impl Fn(&SwipeEvent, &mut Window, &mut App) + 'static) -> Self {
+        self.interactivity().on_swipe(listener);
+        self
+    }
+
     /// Capture the given action, before normal action dispatch can fire.
     /// The fluent API equivalent to [`Interactivity::capture_action`].
     ///
@@ -1076,6 +1141,21 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Expose this element to assistive technologies as a control of the given role.
+    /// Only elements with an id or a tracked focus handle are exposed.
+    /// The fluent API equivalent to [`Interactivity::accessibility_role`].
+    fn accessibility_role(mut self, role: accesskit::Role) -> Self {
+        self.interactivity().accessibility_role(role);
+        self
+    }
+
+    /// Set the text assistive technologies announce for this element.
+    /// The fluent API equivalent to [`Interactivity::accessibility_label`].
+    fn accessibility_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.interactivity().accessibility_label(label);
+        self
+    }
+
     /// Block non-scroll mouse interactions with elements behind this element's hitbox.
     /// The fluent API equivalent to [`Interactivity::block_mouse_except_scroll`].
     ///
@@ -1290,6 +1370,12 @@ pub(crate) type MouseMoveListener =
 pub(crate) type ScrollWheelListener =
     Box<dyn Fn(&ScrollWheelEvent, DispatchPhase, &Hitbox, &mut Window, &mut App) + 'static>;
 
+pub(crate) type PinchListener =
+    Box<dyn Fn(&PinchEvent, DispatchPhase, &Hitbox, &mut Window, &mut App) + 'static>;
+
+pub(crate) type SwipeListener =
+    Box<dyn Fn(&SwipeEvent, DispatchPhase, &Hitbox, &mut Window, &mut App) + 'static>;
+
 pub(crate) type ClickListener = Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
 
 pub(crate) type DragListener =
@@ -1644,6 +1730,8 @@ pub struct Interactivity {
     pub(crate) mouse_pressure_listeners: Vec<MousePressureListener>,
     pub(crate) mouse_move_listeners: Vec<MouseMoveListener>,
     pub(crate) scroll_wheel_listeners: Vec<ScrollWheelListener>,
+    pub(crate) pinch_listeners: Vec<PinchListener>,
+    pub(crate) swipe_listeners: Vec<SwipeListener>,
     pub(crate) key_down_listeners: Vec<KeyDownListener>,
     pub(crate) key_up_listeners: Vec<KeyUpListener>,
     pub(crate) modifiers_changed_listeners: Vec<ModifiersChangedListener>,
@@ -1660,6 +1748,7 @@ pub struct Interactivity {
     pub(crate) tab_index: Option<isize>,
     pub(crate) tab_group: bool,
     pub(crate) tab_stop: bool,
+    pub(crate) accessibility: Option<Box<AccessibilityProperties>>,
 
     #[cfg(any(feature = "inspector", debug_assertions))]
     pub(crate) source_location: Option<&'static core::panic::Location<'static>>,
@@ -1847,6 +1936,8 @@ impl Interactivity {
             || !self.click_listeners.is_empty()
             || !self.aux_click_listeners.is_empty()
             || !self.scroll_wheel_listeners.is_empty()
+            || !self.pinch_listeners.is_empty()
+            || !self.swipe_listeners.is_empty()
             || self.drag_listener.is_some()
             || !self.drop_listeners.is_empty()
             || self.tooltip_builder.is_some()
@@ -1911,6 +2002,31 @@ impl Interactivity {
         }
     }
 
+    fn accessibility_node(
+        &self,
+        global_id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+    ) -> Option<(accesskit::NodeId, accesskit::Node)> {
+        let properties = self.accessibility.as_ref()?;
+        let node_id = match (&self.tracked_focus_handle, global_id) {
+            (Some(focus_handle), _) => focus_handle.id.accessibility_node_id(),
+            (None, Some(global_id)) => crate::accessibility_node_id(global_id),
+            (None, None) => return None,
+        };
+
+        let mut node = accesskit::Node::new(properties.role);
+        if let Some(label) = &properties.label {
+            node.set_label(label.to_string());
+        }
+        node.set_bounds(accesskit::Rect {
+            x0: bounds.left().0 as f64,
+            y0: bounds.top().0 as f64,
+            x1: bounds.right().0 as f64,
+            y1: bounds.bottom().0 as f64,
+        });
+        Some((node_id, node))
+    }
+
     /// Paint this element according to this interactivity state's configured styles
     /// and bind the element's mouse and keyboard events.
     ///
@@ -2004,7 +2120,13 @@ impl Interactivity {
                                         }
 
                                         self.paint_keyboard_listeners(window, cx);
-                                        f(&style, window, cx);
+                                        match self.accessibility_node(global_id, bounds) {
+                                            Some((node_id, node)) => window
+                                                .with_accessibility_node(node_id, node, |window| {
+                                                    f(&style, window, cx)
+                                                }),
+                                            None => f(&style, window, cx),
+                                        }
 
                                         if let Some(_hitbox) = hitbox {
                                             #[cfg(any(feature = "inspector", debug_assertions))]
@@ -2213,6 +2335,20 @@ impl Interactivity {
             })
         }
 
+        for listener in self.pinch_listeners.drain(..) {
+            let hitbox = hitbox.clone();
+            window.on_mouse_event(move |event: &PinchEvent, phase, window, cx| {
+                listener(event, phase, &hitbox, window, cx);
+            })
+        }
+
+        for listener in self.swipe_listeners.drain(..) {
+            let hitbox = hitbox.clone();
+            window.on_mouse_event(move |event: &SwipeEvent, phase, window, cx| {
+                listener(event, phase, &hitbox, window, cx);
+            })
+        }
+
         if self.hover_style.is_some()
             || self.base_style.mouse_cursor.is_some()
             || cx.active_drag.is_some() && !self.drag_over_styles.is_empty()
@@ -452,6 +452,74 @@ impl Deref for ScrollWheelEvent {
     }
 }
 
+/// A pinch gesture on a touchpad or touchscreen.
+#[derive(Clone, Debug, Default)]
+pub struct PinchEvent {
+    /// The position of the center of the gesture on the window.
+    pub position: Point<Pixels>,
+
+    /// How much the fingers spread apart since the previous event of the
+    /// gesture, relative to their distance then. Positive when zooming in.
+    pub delta: f32,
+
+    /// The modifiers that were held down during the gesture.
+    pub modifiers: Modifiers,
+
+    /// The phase of the gesture.
+    pub touch_phase: TouchPhase,
+}
+
+impl Sealed for PinchEvent {}
+impl InputEvent for PinchEvent {
+    fn to_platform_input(self) -> PlatformInput {
+        PlatformInput::Pinch(self)
+    }
+}
+impl MouseEvent for PinchEvent {}
+
+impl Deref for PinchEvent {
+    type Target = Modifiers;
+
+    fn deref(&self) -> &Self::Target {
+        &self.modifiers
+    }
+}
+
+/// A swipe of three or more fingers on a touchpad.
+#[derive(Clone, Debug, Default)]
+pub struct SwipeEvent {
+    /// The position of the mouse on the window.
+    pub position: Point<Pixels>,
+
+    /// How far the fingers moved since the previous event of the gesture.
+    pub delta: Point<Pixels>,
+
+    /// The number of fingers making the gesture.
+    pub finger_count: u32,
+
+    /// The modifiers that were held down during the gesture.
+    pub modifiers: Modifiers,
+
+    /// The phase of the gesture.
+    pub touch_phase: TouchPhase,
+}
+
+impl Sealed for SwipeEvent {}
+impl InputEvent for SwipeEvent {
+    fn to_platform_input(self) -> PlatformInput {
+        PlatformInput::Swipe(self)
+    }
+}
+impl MouseEvent for SwipeEvent {}
+
+impl Deref for SwipeEvent {
+    type Target = Modifiers;
+
+    fn deref(&self) -> &Self::Target {
+        &self.modifiers
+    }
+}
+
 /// The scroll delta for a scroll wheel event.
 #[derive(Clone, Copy, Debug)]
 pub enum ScrollDelta {
@@ -626,6 +694,10 @@ pub enum PlatformInput {
     MouseExited(MouseExitEvent),
     /// The scroll wheel was used.
     ScrollWheel(ScrollWheelEvent),
+    /// A pinch gesture was made.
+    Pinch(PinchEvent),
+    /// A multi-finger swipe gesture was made.
+    Swipe(SwipeEvent),
     /// Files were dragged and dropped onto the window.
     FileDrop(FileDropEvent),
 }
@@ -642,6 +714,8 @@ impl PlatformInput {
             PlatformInput::MousePressure(event) => Some(event),
             PlatformInput::MouseExited(event) => Some(event),
             PlatformInput::ScrollWheel(event) => Some(event),
+            PlatformInput::Pinch(event) => Some(event),
+            PlatformInput::Swipe(event) => Some(event),
             PlatformInput::FileDrop(event) => Some(event),
         }
     }
@@ -657,6 +731,8 @@ impl PlatformInput {
             PlatformInput::MousePressure(_) => None,
             PlatformInput::MouseExited(_) => None,
             PlatformInput::ScrollWheel(_) => None,
+            PlatformInput::Pinch(_) => None,
+            PlatformInput::Swipe(_) => None,
             PlatformInput::FileDrop(_) => None,
         }
     }
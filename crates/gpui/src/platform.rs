@@ -40,7 +40,6 @@ use crate::{
 use anyhow::Result;
 use async_task::Runnable;
 use futures::channel::oneshot;
-#[cfg(any(test, feature = "test-support"))]
 use image::RgbaImage;
 use image::codecs::gif::GifDecoder;
 use image::{AnimationDecoder as _, Frame};
@@ -255,6 +254,11 @@ pub(crate) trait Platform: 'static {
     fn thermal_state(&self) -> ThermalState;
     fn on_thermal_state_change(&self, callback: Box<dyn FnMut()>);
 
+    fn accessibility_preferences(&self) -> AccessibilityPreferences {
+        AccessibilityPreferences::default()
+    }
+    fn on_accessibility_preferences_change(&self, _callback: Box<dyn FnMut()>) {}
+
     fn compositor_name(&self) -> &'static str {
         ""
     }
@@ -284,6 +288,19 @@ pub(crate) trait Platform: 'static {
     fn keyboard_layout(&self) -> Box<dyn PlatformKeyboardLayout>;
     fn keyboard_mapper(&self) -> Rc<dyn PlatformKeyboardMapper>;
     fn on_keyboard_layout_change(&self, callback: Box<dyn FnMut()>);
+
+    fn register_global_hotkey(
+        &self,
+        _id: u32,
+        _keystroke: &Keystroke,
+        _description: &str,
+    ) -> Result<()> {
+        Err(anyhow::anyhow!(
+            "global hotkeys are not supported on this platform"
+        ))
+    }
+    fn unregister_global_hotkey(&self, _id: u32) {}
+    fn on_global_hotkey(&self, _callback: Box<dyn FnMut(u32)>) {}
 }
 
 /// A handle to a platform's display, e.g. a monitor or laptop screen.
@@ -330,6 +347,15 @@ pub enum ThermalState {
     Critical,
 }
 
+/// The accessibility preferences of the desktop
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AccessibilityPreferences {
+    /// Whether the user prefers interfaces with higher contrast
+    pub high_contrast: bool,
+    /// Whether the user prefers interfaces with less motion, like animations
+    pub reduce_motion: bool,
+}
+
 /// Metadata for a given [ScreenCaptureSource]
 #[derive(Clone)]
 pub struct SourceMetadata {
@@ -437,6 +463,10 @@ pub struct WindowControls {
     pub minimize: bool,
     /// Whether this platform supports a window menu
     pub window_menu: bool,
+    /// Whether this platform supports keeping the window above other windows
+    pub keep_above: bool,
+    /// Whether this platform supports snapping the window to a [`WindowSnapLayout`]
+    pub snap: bool,
 }
 
 impl Default for WindowControls {
@@ -447,10 +477,60 @@ impl Default for WindowControls {
             maximize: true,
             minimize: true,
             window_menu: true,
+            // There is no portable protocol for these, so platforms have to opt in.
+            keep_above: false,
+            snap: false,
         }
     }
 }
 
+/// A region of the display a window can be snapped to
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum WindowSnapLayout {
+    /// The left half of the display
+    LeftHalf,
+    /// The right half of the display
+    RightHalf,
+    /// The top left quarter of the display
+    TopLeftQuarter,
+    /// The top right quarter of the display
+    TopRightQuarter,
+    /// The bottom left quarter of the display
+    BottomLeftQuarter,
+    /// The bottom right quarter of the display
+    BottomRightQuarter,
+}
+
+impl WindowSnapLayout {
+    /// The region this layout covers, as fractions of the display's size
+    pub fn region(&self) -> Bounds<f32> {
+        let (x, y, width, height) = match self {
+            WindowSnapLayout::LeftHalf => (0., 0., 0.5, 1.),
+            WindowSnapLayout::RightHalf => (0.5, 0., 0.5, 1.),
+            WindowSnapLayout::TopLeftQuarter => (0., 0., 0.5, 0.5),
+            WindowSnapLayout::TopRightQuarter => (0.5, 0., 0.5, 0.5),
+            WindowSnapLayout::BottomLeftQuarter => (0., 0.5, 0.5, 0.5),
+            WindowSnapLayout::BottomRightQuarter => (0.5, 0.5, 0.5, 0.5),
+        };
+        Bounds::new(point(x, y), size(width, height))
+    }
+
+    /// The bounds this layout covers within the given work area
+    pub fn bounds(&self, work_area: Bounds<Pixels>) -> Bounds<Pixels> {
+        let region = self.region();
+        Bounds::new(
+            point(
+                work_area.origin.x + work_area.size.width * region.origin.x,
+                work_area.origin.y + work_area.size.height * region.origin.y,
+            ),
+            size(
+                work_area.size.width * region.size.width,
+                work_area.size.height * region.size.height,
+            ),
+        )
+    }
+}
+
 /// A type to describe which sides of the window are currently tiled in some way
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
 pub struct Tiling {
@@ -531,6 +611,19 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
     fn on_appearance_changed(&self, callback: Box<dyn FnMut()>);
     fn draw(&self, scene: &Scene);
     fn completed_frame(&self) {}
+    /// Calls `callback` for each completed frame, in order, with the time it
+    /// reached the screen, or `None` if it never did. Platforms only track
+    /// presentation while a callback is set.
+    fn on_frame_presented(&self, _callback: Option<Box<dyn FnMut(Option<Instant>)>>) {}
+    /// Whether this window reports presentation times to the
+    /// `on_frame_presented` callback.
+    fn reports_frame_presentation(&self) -> bool {
+        false
+    }
+    /// Called when the window's accessibility tree changed. Platforms that
+    /// expose it to assistive technologies call `build_update` for the new
+    /// tree, which they can skip while none are listening.
+    fn update_accessibility_tree(&self, _build_update: &mut dyn FnMut() -> accesskit::TreeUpdate) {}
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas>;
     fn is_subpixel_rendering_supported(&self) -> bool;
 
@@ -566,6 +659,13 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
     }
     fn request_decorations(&self, _decorations: WindowDecorations) {}
     fn show_window_menu(&self, _position: Point<Pixels>) {}
+    fn lower(&self) {}
+    fn set_keep_above(&self, _keep_above: bool) {}
+    fn is_keep_above(&self) -> bool {
+        false
+    }
+    fn snap(&self, _layout: WindowSnapLayout) {}
+    fn request_attention(&self) {}
     fn start_window_move(&self) {}
     fn start_window_resize(&self, _edge: ResizeEdge) {}
     fn window_decorations(&self) -> Decorations {
@@ -589,9 +689,8 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
     }
 
     /// Renders the given scene to a texture and returns the pixel data as an RGBA image.
-    /// This does not present the frame to screen - useful for visual testing where we want
-    /// to capture what would be rendered without displaying it or requiring the window to be visible.
-    #[cfg(any(test, feature = "test-support"))]
+    /// This does not present the frame to screen - useful for screenshots and visual testing,
+    /// capturing what would be rendered without requiring the window to be visible.
     fn render_to_image(&self, _scene: &Scene) -> Result<RgbaImage> {
         anyhow::bail!("render_to_image not implemented for this platform")
     }
@@ -1470,6 +1569,19 @@ pub enum TextRenderingMode {
     Grayscale,
 }
 
+/// How windows schedule drawing in response to input.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PresentationMode {
+    /// Draw at most once per display refresh, when the platform asks for a
+    /// frame.
+    #[default]
+    VSync,
+    /// Also draw as soon as input changes a window's contents, rather than
+    /// waiting for the next refresh, which lowers input latency at the cost
+    /// of some extra frames.
+    LowLatency,
+}
+
 /// The options that can be configured for a file dialog prompt
 #[derive(Clone, Debug)]
 pub struct PathPromptOptions {
@@ -1719,6 +1831,14 @@ impl ClipboardItem {
         }
     }
 
+    /// Returns the first ClipboardEntry::Image in the item, if there is one.
+    pub fn image(&self) -> Option<&Image> {
+        self.entries.iter().find_map(|entry| match entry {
+            ClipboardEntry::Image(image) => Some(image),
+            _ => None,
+        })
+    }
+
     /// If this item is one ClipboardEntry::String, returns its metadata.
     #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
     pub fn metadata(&self) -> Option<&String> {
@@ -1818,6 +1938,20 @@ impl ImageFormat {
         }
     }
 
+    /// Returns the file extension conventionally used for the ImageFormat
+    pub const fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Webp => "webp",
+            ImageFormat::Gif => "gif",
+            ImageFormat::Svg => "svg",
+            ImageFormat::Bmp => "bmp",
+            ImageFormat::Tiff => "tiff",
+            ImageFormat::Ico => "ico",
+        }
+    }
+
     /// Returns the ImageFormat for the given mime type
     pub fn from_mime_type(mime_type: &str) -> Option<Self> {
         match mime_type {
@@ -1866,6 +2000,13 @@ impl Image {
         }
     }
 
+    /// Create a PNG image from RGBA pixels, such as a window screenshot
+    pub fn from_rgba(pixels: &RgbaImage) -> Result<Self> {
+        let mut bytes = Vec::new();
+        pixels.write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)?;
+        Ok(Self::from_bytes(ImageFormat::Png, bytes))
+    }
+
     /// Get this image's ID
     pub fn id(&self) -> u64 {
         self.id
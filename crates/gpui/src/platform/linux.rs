@@ -1,6 +1,10 @@
+#[cfg(any(feature = "wayland", feature = "x11"))]
+mod accessibility;
 mod dispatcher;
 mod headless;
 mod keyboard;
+#[cfg(feature = "wayland")]
+mod kinetic_scroll;
 mod platform;
 #[cfg(any(feature = "wayland", feature = "x11"))]
 mod text_system;
@@ -12,9 +16,13 @@ mod x11;
 #[cfg(any(feature = "wayland", feature = "x11"))]
 mod xdg_desktop_portal;
 
+#[cfg(any(feature = "wayland", feature = "x11"))]
+pub(crate) use accessibility::*;
 pub(crate) use dispatcher::*;
 pub(crate) use headless::*;
 pub(crate) use keyboard::*;
+#[cfg(feature = "wayland")]
+pub(crate) use kinetic_scroll::*;
 pub(crate) use platform::*;
 #[cfg(any(feature = "wayland", feature = "x11"))]
 pub(crate) use text_system::*;
@@ -0,0 +1,171 @@
+//! Exposes window accessibility trees over AT-SPI, the D-Bus protocol Linux
+//! screen readers such as Orca use.
+
+use std::sync::Arc;
+
+use accesskit::{
+    ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler, Node, NodeId, TreeUpdate,
+};
+use collections::FxHashMap;
+use parking_lot::Mutex;
+
+use crate::{Bounds, ScaledPixels};
+
+/// Serves one window's accessibility tree.
+pub(crate) struct AccessibilityAdapter {
+    adapter: accesskit_unix::Adapter,
+    latest_update: Arc<Mutex<Option<TreeUpdate>>>,
+    sent_tree: Option<SentTree>,
+}
+
+/// The tree last handed to the adapter, which later updates only send the
+/// changes to.
+struct SentTree {
+    nodes: FxHashMap<NodeId, Node>,
+    focus: NodeId,
+}
+
+impl AccessibilityAdapter {
+    pub fn new() -> Self {
+        let latest_update = Arc::new(Mutex::new(None));
+        let adapter = accesskit_unix::Adapter::new(
+            InitialTree(latest_update.clone()),
+            IgnoredActions,
+            IgnoredDeactivation,
+        );
+        Self {
+            adapter,
+            latest_update,
+            sent_tree: None,
+        }
+    }
+
+    pub fn update(&mut self, build_update: &mut dyn FnMut() -> TreeUpdate) {
+        let update = build_update();
+        let Some(changes) = tree_changes(self.sent_tree.as_ref(), &update) else {
+            return;
+        };
+        self.sent_tree = Some(SentTree {
+            nodes: update.nodes.iter().cloned().collect(),
+            focus: update.focus,
+        });
+        // A screen reader starting later requests the full tree from
+        // `InitialTree`, on the adapter's thread. It's stored first so that
+        // one starting during `update_if_active` doesn't miss the changes.
+        *self.latest_update.lock() = Some(update);
+        self.adapter.update_if_active(|| changes);
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.adapter.update_window_focus_state(focused);
+    }
+
+    /// Positions the tree on screen, given the window's bounds with and
+    /// without its decorations.
+    pub fn set_bounds(&mut self, outer: Bounds<ScaledPixels>, inner: Bounds<ScaledPixels>) {
+        self.adapter
+            .set_root_window_bounds(accesskit_rect(outer), accesskit_rect(inner));
+    }
+}
+
+/// The nodes of `update` that are new or differ from the sent tree, or `None`
+/// when the tree and its focus are unchanged. Nodes that are no longer
+/// referenced are dropped by AccessKit, as their parents are sent too.
+fn tree_changes(sent_tree: Option<&SentTree>, update: &TreeUpdate) -> Option<TreeUpdate> {
+    let Some(sent_tree) = sent_tree else {
+        return Some(update.clone());
+    };
+    let nodes = update
+        .nodes
+        .iter()
+        .filter(|(id, node)| sent_tree.nodes.get(id) != Some(node))
+        .cloned()
+        .collect::<Vec<_>>();
+    if nodes.is_empty() && sent_tree.focus == update.focus {
+        return None;
+    }
+    Some(TreeUpdate {
+        nodes,
+        tree: None,
+        focus: update.focus,
+    })
+}
+
+fn accesskit_rect(bounds: Bounds<ScaledPixels>) -> accesskit::Rect {
+    accesskit::Rect {
+        x0: bounds.left().0 as f64,
+        y0: bounds.top().0 as f64,
+        x1: bounds.right().0 as f64,
+        y1: bounds.bottom().0 as f64,
+    }
+}
+
+struct InitialTree(Arc<Mutex<Option<TreeUpdate>>>);
+
+impl ActivationHandler for InitialTree {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        self.0.lock().clone()
+    }
+}
+
+/// Screen readers can read the tree, but can't yet act on it.
+struct IgnoredActions;
+
+impl ActionHandler for IgnoredActions {
+    fn do_action(&mut self, _request: ActionRequest) {}
+}
+
+struct IgnoredDeactivation;
+
+impl DeactivationHandler for IgnoredDeactivation {
+    fn deactivate_accessibility(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{Role, Tree};
+
+    use super::*;
+
+    fn tree_update(button_label: &str, focus: NodeId) -> TreeUpdate {
+        let mut root = Node::new(Role::Window);
+        root.set_children(vec![NodeId(1), NodeId(2)]);
+        let mut button = Node::new(Role::Button);
+        button.set_label(button_label);
+        TreeUpdate {
+            nodes: vec![
+                (NodeId(0), root),
+                (NodeId(1), button),
+                (NodeId(2), Node::new(Role::TextInput)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus,
+        }
+    }
+
+    fn sent_tree(update: &TreeUpdate) -> SentTree {
+        SentTree {
+            nodes: update.nodes.iter().cloned().collect(),
+            focus: update.focus,
+        }
+    }
+
+    #[test]
+    fn test_tree_changes() {
+        let first = tree_update("Save", NodeId(0));
+        assert_eq!(tree_changes(None, &first), Some(first.clone()));
+        let sent = sent_tree(&first);
+
+        assert_eq!(tree_changes(Some(&sent), &first), None);
+
+        let relabeled = tree_update("Save All", NodeId(0));
+        let changes = tree_changes(Some(&sent), &relabeled).unwrap();
+        assert_eq!(changes.nodes, [relabeled.nodes[1].clone()]);
+        assert_eq!(changes.tree, None);
+
+        let refocused = tree_update("Save", NodeId(2));
+        let changes = tree_changes(Some(&sent), &refocused).unwrap();
+        assert!(changes.nodes.is_empty());
+        assert_eq!(changes.focus, NodeId(2));
+    }
+}
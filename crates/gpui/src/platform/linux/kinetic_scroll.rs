@@ -0,0 +1,154 @@
+//! Momentum for touchpad and touchscreen scrolling. Unlike macOS, Linux
+//! compositors stop sending scroll events as soon as the fingers lift, so
+//! clients keep the content moving and decelerating themselves.
+
+use std::{collections::VecDeque, time::Duration};
+
+use crate::{Pixels, Point, point, px};
+
+/// How often momentum scroll events are sent.
+pub(crate) const KINETIC_SCROLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Only the movement over this much time before the fingers lift counts
+/// toward the momentum.
+const VELOCITY_WINDOW_MS: u32 = 100;
+
+/// The fraction of its velocity the content keeps after moving for a second.
+const FRICTION_PER_SECOND: f32 = 0.03;
+
+/// The speed, in pixels per second, below which the content stops.
+const MIN_VELOCITY: f32 = 30.;
+
+#[derive(Default)]
+pub(crate) struct KineticScroll {
+    /// Scroll deltas made while the fingers are down, by the millisecond
+    /// timestamp of the input event.
+    samples: VecDeque<(u32, Point<Pixels>)>,
+    /// Pixels per second.
+    velocity: Point<f32>,
+    /// Incremented whenever momentum starts or is cancelled, so that timers
+    /// driving an older momentum stop.
+    generation: u64,
+}
+
+impl KineticScroll {
+    /// Records a scroll made while the fingers are down, stopping any
+    /// ongoing momentum.
+    pub fn track(&mut self, delta: Point<Pixels>, time: u32) {
+        if self.velocity != Point::default() {
+            self.cancel();
+        }
+        while self
+            .samples
+            .front()
+            .is_some_and(|(sample_time, _)| time.wrapping_sub(*sample_time) > VELOCITY_WINDOW_MS)
+        {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((time, delta));
+    }
+
+    /// Called when the fingers lift. Returns the generation of the momentum
+    /// to drive with [`Self::step`], if the fingers were moving fast enough.
+    pub fn release(&mut self, time: u32) -> Option<u64> {
+        let samples = std::mem::take(&mut self.samples);
+        let (first_time, _) = *samples.front()?;
+        let (last_time, _) = *samples.back()?;
+        // Fingers that rest before lifting don't fling the content.
+        if time.wrapping_sub(last_time) > VELOCITY_WINDOW_MS / 2 {
+            return None;
+        }
+
+        let total = samples
+            .iter()
+            .fold(Point::<Pixels>::default(), |total, (_, delta)| {
+                total + *delta
+            });
+        let elapsed_seconds = last_time
+            .wrapping_sub(first_time)
+            .max(KINETIC_SCROLL_INTERVAL.as_millis() as u32) as f32
+            / 1000.;
+        let velocity = point(
+            f32::from(total.x) / elapsed_seconds,
+            f32::from(total.y) / elapsed_seconds,
+        );
+        if velocity.x.hypot(velocity.y) < MIN_VELOCITY {
+            return None;
+        }
+
+        self.velocity = velocity;
+        self.generation += 1;
+        Some(self.generation)
+    }
+
+    /// Advances the momentum started as `generation` by `elapsed`, returning
+    /// how far to scroll, or `None` once it stopped or was cancelled.
+    pub fn step(&mut self, generation: u64, elapsed: Duration) -> Option<Point<Pixels>> {
+        if generation != self.generation {
+            return None;
+        }
+        let seconds = elapsed.as_secs_f32();
+        self.velocity = self.velocity * FRICTION_PER_SECOND.powf(seconds);
+        if self.velocity.x.hypot(self.velocity.y) < MIN_VELOCITY {
+            self.cancel();
+            return None;
+        }
+        Some(point(
+            px(self.velocity.x * seconds),
+            px(self.velocity.y * seconds),
+        ))
+    }
+
+    /// Stops any ongoing momentum.
+    pub fn cancel(&mut self) {
+        self.velocity = Point::default();
+        self.generation += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fling_decelerates_and_stops() {
+        let mut kinetic_scroll = KineticScroll::default();
+        for frame in 0..6 {
+            kinetic_scroll.track(point(px(0.), px(20.)), frame * 16);
+        }
+        let generation = kinetic_scroll
+            .release(5 * 16 + 8)
+            .expect("a fast scroll should fling the content");
+
+        let mut previous = f32::INFINITY;
+        let mut steps = 0;
+        while let Some(delta) = kinetic_scroll.step(generation, KINETIC_SCROLL_INTERVAL) {
+            let delta = f32::from(delta.y);
+            assert!(delta > 0. && delta < previous);
+            previous = delta;
+            steps += 1;
+        }
+        assert!(steps > 10);
+    }
+
+    #[test]
+    fn test_resting_fingers_do_not_fling() {
+        let mut kinetic_scroll = KineticScroll::default();
+        kinetic_scroll.track(point(px(0.), px(20.)), 0);
+        kinetic_scroll.track(point(px(0.), px(20.)), 16);
+        assert_eq!(kinetic_scroll.release(400), None);
+    }
+
+    #[test]
+    fn test_scrolling_again_stops_momentum() {
+        let mut kinetic_scroll = KineticScroll::default();
+        kinetic_scroll.track(point(px(0.), px(30.)), 0);
+        kinetic_scroll.track(point(px(0.), px(30.)), 16);
+        let generation = kinetic_scroll.release(20).unwrap();
+        kinetic_scroll.track(point(px(0.), px(1.)), 100);
+        assert_eq!(
+            kinetic_scroll.step(generation, KINETIC_SCROLL_INTERVAL),
+            None
+        );
+    }
+}
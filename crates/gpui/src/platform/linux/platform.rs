@@ -22,11 +22,11 @@ use util::command::{new_command, new_std_command};
 use xkbcommon::xkb::{self, Keycode, Keysym, State};
 
 use crate::{
-    Action, AnyWindowHandle, BackgroundExecutor, ClipboardItem, CursorStyle, DisplayId,
-    ForegroundExecutor, Keymap, LinuxDispatcher, Menu, MenuItem, OwnedMenu, PathPromptOptions,
-    Platform, PlatformDisplay, PlatformKeyboardLayout, PlatformKeyboardMapper, PlatformTextSystem,
-    PlatformWindow, PriorityQueueCalloopReceiver, Result, RunnableVariant, Task, ThermalState,
-    WindowAppearance, WindowParams,
+    AccessibilityPreferences, Action, AnyWindowHandle, BackgroundExecutor, ClipboardItem,
+    CursorStyle, DisplayId, ForegroundExecutor, Keymap, Keystroke, LinuxDispatcher, Menu, MenuItem,
+    OwnedMenu, PathPromptOptions, Platform, PlatformDisplay, PlatformKeyboardLayout,
+    PlatformKeyboardMapper, PlatformTextSystem, PlatformWindow, PriorityQueueCalloopReceiver,
+    Result, RunnableVariant, Task, ThermalState, WindowAppearance, WindowParams,
 };
 #[cfg(any(feature = "wayland", feature = "x11"))]
 use crate::{Pixels, Point, px};
@@ -36,6 +36,7 @@ pub(crate) const SCROLL_LINES: f32 = 3.0;
 
 // Values match the defaults on GTK.
 // Taken from https://github.com/GNOME/gtk/blob/main/gtk/gtksettings.c#L320
+// The interval is replaced by the desktop's setting when the settings portal reports one.
 #[cfg(any(feature = "wayland", feature = "x11"))]
 pub(crate) const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
 #[cfg(any(feature = "wayland", feature = "x11"))]
@@ -121,6 +122,19 @@ pub trait LinuxClient {
     fn window_stack(&self) -> Option<Vec<AnyWindowHandle>>;
     fn run(&self);
 
+    fn register_global_hotkey(
+        &self,
+        _id: u32,
+        _keystroke: &Keystroke,
+        _description: &str,
+    ) -> anyhow::Result<()> {
+        Err(anyhow!(
+            "global hotkeys are not supported by {}",
+            self.compositor_name()
+        ))
+    }
+    fn unregister_global_hotkey(&self, _id: u32) {}
+
     #[cfg(any(feature = "wayland", feature = "x11"))]
     fn window_identifier(
         &self,
@@ -138,6 +152,8 @@ pub(crate) struct PlatformHandlers {
     pub(crate) will_open_app_menu: Option<Box<dyn FnMut()>>,
     pub(crate) validate_app_menu_command: Option<Box<dyn FnMut(&dyn Action) -> bool>>,
     pub(crate) keyboard_layout_change: Option<Box<dyn FnMut()>>,
+    pub(crate) accessibility_preferences_change: Option<Box<dyn FnMut()>>,
+    pub(crate) global_hotkey: Option<Box<dyn FnMut(u32)>>,
 }
 
 pub(crate) struct LinuxCommon {
@@ -146,9 +162,12 @@ pub(crate) struct LinuxCommon {
     pub(crate) text_system: Arc<dyn PlatformTextSystem>,
     pub(crate) appearance: WindowAppearance,
     pub(crate) auto_hide_scrollbars: bool,
+    pub(crate) accessibility_preferences: AccessibilityPreferences,
     pub(crate) callbacks: PlatformHandlers,
     pub(crate) signal: LoopSignal,
     pub(crate) menus: Vec<OwnedMenu>,
+    #[cfg(any(feature = "wayland", feature = "x11"))]
+    pub(crate) double_click_interval: Duration,
 }
 
 impl LinuxCommon {
@@ -172,13 +191,35 @@ impl LinuxCommon {
             text_system,
             appearance: WindowAppearance::Light,
             auto_hide_scrollbars: false,
+            accessibility_preferences: AccessibilityPreferences::default(),
             callbacks,
             signal,
             menus: Vec::new(),
+            #[cfg(any(feature = "wayland", feature = "x11"))]
+            double_click_interval: DOUBLE_CLICK_INTERVAL,
         };
 
         (common, main_receiver)
     }
+
+    /// Changes the accessibility preferences, returning the handler to call
+    /// when that changed them.
+    ///
+    /// The handler is taken so that it can run without the client borrowed,
+    /// and must be put back afterwards.
+    #[cfg(any(feature = "wayland", feature = "x11"))]
+    pub(crate) fn update_accessibility_preferences(
+        &mut self,
+        update: impl FnOnce(&mut AccessibilityPreferences),
+    ) -> Option<Box<dyn FnMut()>> {
+        let previous_preferences = self.accessibility_preferences;
+        update(&mut self.accessibility_preferences);
+        if self.accessibility_preferences == previous_preferences {
+            None
+        } else {
+            self.callbacks.accessibility_preferences_change.take()
+        }
+    }
 }
 
 impl<P: LinuxClient + 'static> Platform for P {
@@ -208,6 +249,16 @@ impl<P: LinuxClient + 'static> Platform for P {
 
     fn on_thermal_state_change(&self, _callback: Box<dyn FnMut()>) {}
 
+    fn accessibility_preferences(&self) -> AccessibilityPreferences {
+        self.with_common(|common| common.accessibility_preferences)
+    }
+
+    fn on_accessibility_preferences_change(&self, callback: Box<dyn FnMut()>) {
+        self.with_common(|common| {
+            common.callbacks.accessibility_preferences_change = Some(callback)
+        });
+    }
+
     fn thermal_state(&self) -> ThermalState {
         ThermalState::Nominal
     }
@@ -341,6 +392,23 @@ impl<P: LinuxClient + 'static> Platform for P {
         self.with_common(|common| common.callbacks.open_urls = Some(callback));
     }
 
+    fn register_global_hotkey(
+        &self,
+        id: u32,
+        keystroke: &Keystroke,
+        description: &str,
+    ) -> Result<()> {
+        self.register_global_hotkey(id, keystroke, description)
+    }
+
+    fn unregister_global_hotkey(&self, id: u32) {
+        self.unregister_global_hotkey(id)
+    }
+
+    fn on_global_hotkey(&self, callback: Box<dyn FnMut(u32)>) {
+        self.with_common(|common| common.callbacks.global_hotkey = Some(callback));
+    }
+
     fn prompt_for_paths(
         &self,
         options: PathPromptOptions,
@@ -981,6 +1049,25 @@ impl crate::Keystroke {
         }
     }
 
+    /// Returns the keysym for a key name produced by [`Keystroke::from_xkb`].
+    /// Global hotkeys are bound by keysym, as the desktop rather than Zed
+    /// decides which keys trigger them.
+    pub(super) fn keysym(&self) -> Option<Keysym> {
+        let keysym = match self.key.as_str() {
+            "enter" => Keysym::Return,
+            "pageup" => Keysym::Prior,
+            "pagedown" => Keysym::Next,
+            key => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(character), None) => xkb::utf32_to_keysym(character as u32),
+                    _ => xkb::keysym_from_name(key, xkb::KEYSYM_CASE_INSENSITIVE),
+                }
+            }
+        };
+        (keysym != Keysym::NoSymbol).then_some(keysym)
+    }
+
     /**
      * Returns which symbol the dead key represents
      * <https://developer.mozilla.org/en-US/docs/Web/API/UI_Events/Keyboard_event_key_values#dead_keycodes_for_linux>
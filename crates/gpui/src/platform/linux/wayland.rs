@@ -2,6 +2,7 @@ mod client;
 mod clipboard;
 mod cursor;
 mod display;
+mod global_shortcuts;
 mod serial;
 mod window;
 
@@ -1,6 +1,8 @@
 use std::{
     cell::{RefCell, RefMut},
+    collections::BTreeMap,
     hash::Hash,
+    ops::Range,
     os::fd::{AsRawFd, BorrowedFd},
     path::PathBuf,
     rc::{Rc, Weak},
@@ -33,9 +35,13 @@ use wayland_client::{
     Connection, Dispatch, Proxy, QueueHandle, delegate_noop,
     protocol::{
         wl_buffer, wl_compositor, wl_keyboard, wl_pointer, wl_registry, wl_seat, wl_shm,
-        wl_shm_pool, wl_surface,
+        wl_shm_pool, wl_surface, wl_touch,
     },
 };
+use wayland_protocols::wp::pointer_gestures::zv1::client::{
+    zwp_pointer_gesture_pinch_v1, zwp_pointer_gesture_swipe_v1, zwp_pointer_gestures_v1,
+};
+use wayland_protocols::wp::presentation_time::client::{wp_presentation, wp_presentation_feedback};
 use wayland_protocols::wp::primary_selection::zv1::client::zwp_primary_selection_offer_v1::{
     self, ZwpPrimarySelectionOfferV1,
 };
@@ -74,21 +80,22 @@ use super::{
 };
 
 use crate::{
-    AnyWindowHandle, Bounds, Capslock, CursorStyle, DOUBLE_CLICK_INTERVAL, DevicePixels, DisplayId,
-    FileDropEvent, ForegroundExecutor, KeyDownEvent, KeyUpEvent, Keystroke, LinuxCommon,
-    LinuxKeyboardLayout, Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent,
-    MouseExitEvent, MouseMoveEvent, MouseUpEvent, NavigationDirection, Pixels, PlatformDisplay,
-    PlatformInput, PlatformKeyboardLayout, Point, ResultExt as _, SCROLL_LINES, ScrollDelta,
-    ScrollWheelEvent, Size, TouchPhase, WindowParams, point, profiler, px, size,
+    AnyWindowHandle, Bounds, Capslock, CursorStyle, DevicePixels, DisplayId, FileDropEvent,
+    ForegroundExecutor, KeyDownEvent, KeyUpEvent, Keystroke, LinuxCommon, LinuxKeyboardLayout,
+    Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseExitEvent, MouseMoveEvent,
+    MouseUpEvent, NavigationDirection, PinchEvent, Pixels, PlatformDisplay, PlatformInput,
+    PlatformKeyboardLayout, Point, ResultExt as _, SCROLL_LINES, ScrollDelta, ScrollWheelEvent,
+    Size, SwipeEvent, TouchPhase, WindowParams, point, profiler, px, size,
 };
 use crate::{
     SharedString,
     platform::linux::{
-        LinuxClient, get_xkb_compose_state, is_within_click_distance, open_uri_internal, read_fd,
-        reveal_path_internal,
+        KINETIC_SCROLL_INTERVAL, KineticScroll, LinuxClient, get_xkb_compose_state,
+        is_within_click_distance, open_uri_internal, read_fd, reveal_path_internal,
         wayland::{
             clipboard::{Clipboard, DataOffer, FILE_LIST_MIME_TYPE, TEXT_MIME_TYPES},
             cursor::Cursor,
+            global_shortcuts::PortalGlobalShortcuts,
             serial::{SerialKind, SerialTracker},
             window::WaylandWindow,
         },
@@ -124,6 +131,8 @@ pub struct Globals {
     pub layer_shell: Option<zwlr_layer_shell_v1::ZwlrLayerShellV1>,
     pub blur_manager: Option<org_kde_kwin_blur_manager::OrgKdeKwinBlurManager>,
     pub text_input_manager: Option<zwp_text_input_manager_v3::ZwpTextInputManagerV3>,
+    pub pointer_gestures: Option<zwp_pointer_gestures_v1::ZwpPointerGesturesV1>,
+    pub presentation: Option<wp_presentation::WpPresentation>,
     pub dialog: Option<xdg_wm_dialog_v1::XdgWmDialogV1>,
     pub executor: ForegroundExecutor,
 }
@@ -164,6 +173,8 @@ impl Globals {
             layer_shell: globals.bind(&qh, 1..=5, ()).ok(),
             blur_manager: globals.bind(&qh, 1..=1, ()).ok(),
             text_input_manager: globals.bind(&qh, 1..=1, ()).ok(),
+            pointer_gestures: globals.bind(&qh, 1..=1, ()).ok(),
+            presentation: globals.bind(&qh, 1..=1, ()).ok(),
             dialog: globals.bind(&qh, dialog_v..=dialog_v, ()).ok(),
             executor,
             qh,
@@ -208,12 +219,19 @@ pub(crate) struct WaylandClientState {
     wl_seat: wl_seat::WlSeat, // TODO: Multi seat support
     wl_pointer: Option<wl_pointer::WlPointer>,
     wl_keyboard: Option<wl_keyboard::WlKeyboard>,
+    wl_touch: Option<wl_touch::WlTouch>,
+    pointer_gestures: Option<(
+        zwp_pointer_gesture_swipe_v1::ZwpPointerGestureSwipeV1,
+        zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1,
+    )>,
     cursor_shape_device: Option<wp_cursor_shape_device_v1::WpCursorShapeDeviceV1>,
     data_device: Option<wl_data_device::WlDataDevice>,
     primary_selection: Option<zwp_primary_selection_device_v1::ZwpPrimarySelectionDeviceV1>,
     text_input: Option<zwp_text_input_v3::ZwpTextInputV3>,
     pre_edit_text: Option<String>,
     ime_pre_edit: Option<String>,
+    /// The input method's cursor within `ime_pre_edit`, in UTF-16 offsets.
+    ime_pre_edit_cursor: Option<Range<usize>>,
     composing: bool,
     // Surface to Window mapping
     windows: HashMap<ObjectId, WaylandWindowStatePtr>,
@@ -235,6 +253,15 @@ pub(crate) struct WaylandClientState {
     vertical_modifier: f32,
     horizontal_modifier: f32,
     scroll_event_received: bool,
+    /// The timestamp of the latest axis event, in milliseconds.
+    scroll_time: u32,
+    kinetic_scroll: KineticScroll,
+    swipe_finger_count: u32,
+    /// The scale of the ongoing pinch gesture, relative to when it began.
+    pinch_scale: f64,
+    touch: TouchState,
+    /// The clock that presentation feedback timestamps are measured with.
+    presentation_clock: libc::clockid_t,
     enter_token: Option<()>,
     button_pressed: Option<MouseButton>,
     mouse_focused_window: Option<WaylandWindowStatePtr>,
@@ -247,6 +274,7 @@ pub(crate) struct WaylandClientState {
     cursor: Cursor,
     pending_activation: Option<PendingActivation>,
     event_loop: Option<EventLoop<'static, WaylandClientStatePtr>>,
+    global_shortcuts: PortalGlobalShortcuts,
     pub common: LinuxCommon,
 }
 
@@ -256,6 +284,26 @@ pub struct DragState {
     position: Point<Pixels>,
 }
 
+/// Fingers on a touchscreen. One finger scrolls, or clicks when tapped, and
+/// two fingers pinch.
+#[derive(Default)]
+pub struct TouchState {
+    window: Option<WaylandWindowStatePtr>,
+    points: BTreeMap<i32, Point<Pixels>>,
+    /// Where the finger went down, while its touch may still be a tap.
+    tap_location: Option<Point<Pixels>>,
+}
+
+impl TouchState {
+    fn pinch_center_and_distance(&self) -> Option<(Point<Pixels>, f32)> {
+        let mut points = self.points.values();
+        let (first, second) = (*points.next()?, *points.next()?);
+        let center = point((first.x + second.x) / 2., (first.y + second.y) / 2.);
+        let offset = second - first;
+        Some((center, f32::from(offset.x).hypot(f32::from(offset.y))))
+    }
+}
+
 pub struct ClickState {
     last_mouse_button: Option<MouseButton>,
     last_click: Instant,
@@ -568,6 +616,58 @@ impl WaylandClient {
                             client.cursor.set_size(size);
                         }
                     }
+                    XDPEvent::DoubleClickInterval(interval) => {
+                        if let Some(client) = client.0.upgrade() {
+                            client.borrow_mut().common.double_click_interval = interval;
+                        }
+                    }
+                    XDPEvent::HighContrast(high_contrast) => {
+                        if let Some(client) = client.0.upgrade() {
+                            let callback =
+                                client.borrow_mut().common.update_accessibility_preferences(
+                                    |preferences| preferences.high_contrast = high_contrast,
+                                );
+                            if let Some(mut callback) = callback {
+                                callback();
+                                client
+                                    .borrow_mut()
+                                    .common
+                                    .callbacks
+                                    .accessibility_preferences_change = Some(callback);
+                            }
+                        }
+                    }
+                    XDPEvent::ReduceMotion(reduce_motion) => {
+                        if let Some(client) = client.0.upgrade() {
+                            let callback =
+                                client.borrow_mut().common.update_accessibility_preferences(
+                                    |preferences| preferences.reduce_motion = reduce_motion,
+                                );
+                            if let Some(mut callback) = callback {
+                                callback();
+                                client
+                                    .borrow_mut()
+                                    .common
+                                    .callbacks
+                                    .accessibility_preferences_change = Some(callback);
+                            }
+                        }
+                    }
+                }
+            })
+            .unwrap();
+
+        let (global_hotkey_sender, global_hotkey_channel) = calloop::channel::channel();
+        handle
+            .insert_source(global_hotkey_channel, |event, _, client| {
+                if let calloop::channel::Event::Msg(hotkey_id) = event
+                    && let Some(client) = client.0.upgrade()
+                {
+                    let callback = client.borrow_mut().common.callbacks.global_hotkey.take();
+                    if let Some(mut callback) = callback {
+                        callback(hotkey_id);
+                        client.borrow_mut().common.callbacks.global_hotkey = Some(callback);
+                    }
                 }
             })
             .unwrap();
@@ -579,12 +679,15 @@ impl WaylandClient {
             wl_seat: seat,
             wl_pointer: None,
             wl_keyboard: None,
+            wl_touch: None,
+            pointer_gestures: None,
             cursor_shape_device: None,
             data_device,
             primary_selection,
             text_input: None,
             pre_edit_text: None,
             ime_pre_edit: None,
+            ime_pre_edit_cursor: None,
             composing: false,
             outputs: HashMap::default(),
             in_progress_outputs,
@@ -619,6 +722,12 @@ impl WaylandClient {
             },
             capslock: Capslock { on: false },
             scroll_event_received: false,
+            scroll_time: 0,
+            kinetic_scroll: KineticScroll::default(),
+            swipe_finger_count: 0,
+            pinch_scale: 1.,
+            touch: TouchState::default(),
+            presentation_clock: libc::CLOCK_MONOTONIC,
             axis_source: AxisSource::Wheel,
             mouse_location: None,
             continuous_scroll_delta: None,
@@ -637,6 +746,7 @@ impl WaylandClient {
             cursor,
             pending_activation: None,
             event_loop: Some(event_loop),
+            global_shortcuts: PortalGlobalShortcuts::new(global_hotkey_sender),
         }));
 
         WaylandSource::new(conn, event_queue)
@@ -886,6 +996,25 @@ impl LinuxClient for WaylandClient {
         None
     }
 
+    fn register_global_hotkey(
+        &self,
+        id: u32,
+        keystroke: &Keystroke,
+        description: &str,
+    ) -> anyhow::Result<()> {
+        let mut state = self.0.borrow_mut();
+        let executor = state.common.background_executor.clone();
+        state
+            .global_shortcuts
+            .register(id, keystroke, description, &executor)
+    }
+
+    fn unregister_global_hotkey(&self, id: u32) {
+        let mut state = self.0.borrow_mut();
+        let executor = state.common.background_executor.clone();
+        state.global_shortcuts.unregister(id, &executor);
+    }
+
     fn compositor_name(&self) -> &'static str {
         "Wayland"
     }
@@ -975,6 +1104,7 @@ delegate_noop!(WaylandClientStatePtr: ignore zxdg_decoration_manager_v1::ZxdgDec
 delegate_noop!(WaylandClientStatePtr: ignore zwlr_layer_shell_v1::ZwlrLayerShellV1);
 delegate_noop!(WaylandClientStatePtr: ignore org_kde_kwin_blur_manager::OrgKdeKwinBlurManager);
 delegate_noop!(WaylandClientStatePtr: ignore zwp_text_input_manager_v3::ZwpTextInputManagerV3);
+delegate_noop!(WaylandClientStatePtr: ignore zwp_pointer_gestures_v1::ZwpPointerGesturesV1);
 delegate_noop!(WaylandClientStatePtr: ignore org_kde_kwin_blur::OrgKdeKwinBlur);
 delegate_noop!(WaylandClientStatePtr: ignore wp_viewporter::WpViewporter);
 delegate_noop!(WaylandClientStatePtr: ignore wp_viewport::WpViewport);
@@ -1001,6 +1131,72 @@ impl Dispatch<WlCallback, ObjectId> for WaylandClientStatePtr {
     }
 }
 
+impl Dispatch<wp_presentation::WpPresentation, ()> for WaylandClientStatePtr {
+    fn event(
+        this: &mut Self,
+        _: &wp_presentation::WpPresentation,
+        event: wp_presentation::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let wp_presentation::Event::ClockId { clk_id } = event {
+            this.get_client().borrow_mut().presentation_clock = clk_id as libc::clockid_t;
+        }
+    }
+}
+
+impl Dispatch<wp_presentation_feedback::WpPresentationFeedback, ObjectId>
+    for WaylandClientStatePtr
+{
+    fn event(
+        this: &mut Self,
+        _: &wp_presentation_feedback::WpPresentationFeedback,
+        event: wp_presentation_feedback::Event,
+        surface_id: &ObjectId,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let client = this.get_client();
+        let mut state = client.borrow_mut();
+        let Some(window) = get_window(&mut state, surface_id) else {
+            return;
+        };
+        let presentation_clock = state.presentation_clock;
+        drop(state);
+
+        match event {
+            wp_presentation_feedback::Event::Presented {
+                tv_sec_hi,
+                tv_sec_lo,
+                tv_nsec,
+                ..
+            } => {
+                let seconds = (u64::from(tv_sec_hi) << 32) | u64::from(tv_sec_lo);
+                let presented_at =
+                    presentation_instant(presentation_clock, Duration::new(seconds, tv_nsec));
+                window.frame_presented(presented_at);
+            }
+            wp_presentation_feedback::Event::Discarded => window.frame_presented(None),
+            _ => {}
+        }
+    }
+}
+
+/// Converts a timestamp of the compositor's presentation clock to an
+/// [`Instant`], which can't be constructed from a raw clock reading.
+fn presentation_instant(clock: libc::clockid_t, timestamp: Duration) -> Option<Instant> {
+    let mut now = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    if unsafe { libc::clock_gettime(clock, &mut now) } != 0 {
+        return None;
+    }
+    let now_on_clock = Duration::new(now.tv_sec as u64, now.tv_nsec as u32);
+    Instant::now().checked_sub(now_on_clock.saturating_sub(timestamp))
+}
+
 pub(crate) fn get_window(
     mut state: &mut RefMut<WaylandClientState>,
     surface_id: &ObjectId,
@@ -1210,6 +1406,7 @@ impl Dispatch<wl_seat::WlSeat, ()> for WaylandClientStatePtr {
                 if let Some(text_input) = state.text_input.take() {
                     text_input.destroy();
                     state.ime_pre_edit = None;
+                    state.ime_pre_edit_cursor = None;
                     state.composing = false;
                 }
 
@@ -1238,12 +1435,39 @@ impl Dispatch<wl_seat::WlSeat, ()> for WaylandClientStatePtr {
                     .as_ref()
                     .map(|cursor_shape_manager| cursor_shape_manager.get_pointer(&pointer, qh, ()));
 
+                if let Some((swipe, pinch)) = state.pointer_gestures.take() {
+                    swipe.destroy();
+                    pinch.destroy();
+                }
+
+                state.pointer_gestures =
+                    state
+                        .globals
+                        .pointer_gestures
+                        .as_ref()
+                        .map(|pointer_gestures| {
+                            (
+                                pointer_gestures.get_swipe_gesture(&pointer, qh, ()),
+                                pointer_gestures.get_pinch_gesture(&pointer, qh, ()),
+                            )
+                        });
+
                 if let Some(wl_pointer) = &state.wl_pointer {
                     wl_pointer.release();
                 }
 
                 state.wl_pointer = Some(pointer);
             }
+            if capabilities.contains(wl_seat::Capability::Touch) {
+                let touch = seat.get_touch(qh, ());
+
+                if let Some(wl_touch) = &state.wl_touch {
+                    wl_touch.release();
+                }
+
+                state.touch = TouchState::default();
+                state.wl_touch = Some(touch);
+            }
         }
     }
 }
@@ -1381,7 +1605,10 @@ impl Dispatch<wl_keyboard::WlKeyboard, ()> for WaylandClientStatePtr {
                                     let pre_edit =
                                         state.pre_edit_text.clone().unwrap_or(String::default());
                                     drop(state);
-                                    focused_window.handle_ime(ImeInput::SetMarkedText(pre_edit));
+                                    focused_window.handle_ime(ImeInput::SetMarkedText {
+                                        text: pre_edit,
+                                        selected_range: None,
+                                    });
                                     state = client.borrow_mut();
                                 }
 
@@ -1401,8 +1628,10 @@ impl Dispatch<wl_keyboard::WlKeyboard, ()> for WaylandClientStatePtr {
                                         focused_window.handle_ime(ImeInput::InsertText(pre_edit));
                                     }
                                     if let Some(current_key) = new_pre_edit {
-                                        focused_window
-                                            .handle_ime(ImeInput::SetMarkedText(current_key));
+                                        focused_window.handle_ime(ImeInput::SetMarkedText {
+                                            text: current_key,
+                                            selected_range: None,
+                                        });
                                     }
                                     compose.feed(keysym);
                                     state = client.borrow_mut();
@@ -1522,8 +1751,15 @@ impl Dispatch<zwp_text_input_v3::ZwpTextInputV3, ()> for WaylandClientStatePtr {
                     }
                 }
             }
-            zwp_text_input_v3::Event::PreeditString { text, .. } => {
+            zwp_text_input_v3::Event::PreeditString {
+                text,
+                cursor_begin,
+                cursor_end,
+            } => {
                 state.composing = true;
+                state.ime_pre_edit_cursor = text
+                    .as_deref()
+                    .and_then(|text| preedit_cursor_range(text, cursor_begin, cursor_end));
                 state.ime_pre_edit = text;
             }
             zwp_text_input_v3::Event::Done { serial } => {
@@ -1533,9 +1769,13 @@ impl Dispatch<zwp_text_input_v3::ZwpTextInputV3, ()> for WaylandClientStatePtr {
                     return;
                 };
 
+                let selected_range = state.ime_pre_edit_cursor.take();
                 if let Some(text) = state.ime_pre_edit.take() {
                     drop(state);
-                    window.handle_ime(ImeInput::SetMarkedText(text));
+                    window.handle_ime(ImeInput::SetMarkedText {
+                        text,
+                        selected_range,
+                    });
                     if let Some(area) = window.get_ime_area() {
                         text_input.set_cursor_rectangle(
                             area.origin.x.0 as i32,
@@ -1558,6 +1798,16 @@ impl Dispatch<zwp_text_input_v3::ZwpTextInputV3, ()> for WaylandClientStatePtr {
     }
 }
 
+/// Converts the byte offsets text-input-v3 gives for the preedit cursor into
+/// UTF-16 offsets. Negative offsets mean the input method hid the cursor.
+fn preedit_cursor_range(text: &str, cursor_begin: i32, cursor_end: i32) -> Option<Range<usize>> {
+    let utf16_offset = |offset: i32| {
+        let prefix = text.get(..usize::try_from(offset).ok()?)?;
+        Some(prefix.encode_utf16().count())
+    };
+    Some(utf16_offset(cursor_begin)?..utf16_offset(cursor_end)?)
+}
+
 fn linux_button_to_gpui(button: u32) -> Option<MouseButton> {
     // These values are coming from <linux/input-event-codes.h>.
     const BTN_LEFT: u32 = 0x110;
@@ -1629,6 +1879,7 @@ impl Dispatch<wl_pointer::WlPointer, ()> for WaylandClientStatePtr {
                 }
             }
             wl_pointer::Event::Leave { .. } => {
+                state.kinetic_scroll.cancel();
                 if let Some(focused_window) = state.mouse_focused_window.clone() {
                     let input = PlatformInput::MouseExited(MouseExitEvent {
                         position: state.mouse_location.unwrap(),
@@ -1709,6 +1960,7 @@ impl Dispatch<wl_pointer::WlPointer, ()> for WaylandClientStatePtr {
                 }
                 match button_state {
                     wl_pointer::ButtonState::Pressed => {
+                        state.kinetic_scroll.cancel();
                         if let Some(window) = state.keyboard_focused_window.clone() {
                             if state.composing && state.text_input.is_some() {
                                 drop(state);
@@ -1728,7 +1980,7 @@ impl Dispatch<wl_pointer::WlPointer, ()> for WaylandClientStatePtr {
                         }
                         let click_elapsed = state.click.last_click.elapsed();
 
-                        if click_elapsed < DOUBLE_CLICK_INTERVAL
+                        if click_elapsed < state.common.double_click_interval
                             && state
                                 .click
                                 .last_mouse_button
@@ -1786,13 +2038,14 @@ impl Dispatch<wl_pointer::WlPointer, ()> for WaylandClientStatePtr {
                 state.axis_source = axis_source;
             }
             wl_pointer::Event::Axis {
+                time,
                 axis: WEnum::Value(axis),
                 value,
-                ..
             } => {
                 if state.axis_source == AxisSource::Wheel {
                     return;
                 }
+                state.scroll_time = time;
                 let axis = if state.modifiers.shift {
                     wl_pointer::Axis::HorizontalScroll
                 } else {
@@ -1879,6 +2132,10 @@ impl Dispatch<wl_pointer::WlPointer, ()> for WaylandClientStatePtr {
                     let continuous = state.continuous_scroll_delta.take();
                     let discrete = state.discrete_scroll_delta.take();
                     if let Some(continuous) = continuous {
+                        if state.axis_source == AxisSource::Finger {
+                            let time = state.scroll_time;
+                            state.kinetic_scroll.track(continuous, time);
+                        }
                         if let Some(window) = state.mouse_focused_window.clone() {
                             let input = PlatformInput::ScrollWheel(ScrollWheelEvent {
                                 position: state.mouse_location.unwrap(),
@@ -1903,6 +2160,275 @@ impl Dispatch<wl_pointer::WlPointer, ()> for WaylandClientStatePtr {
                     }
                 }
             }
+            wl_pointer::Event::AxisStop { time, .. } => {
+                if state.axis_source == AxisSource::Finger
+                    && let Some(window) = state.mouse_focused_window.clone()
+                    && let Some(position) = state.mouse_location
+                    && let Some(generation) = state.kinetic_scroll.release(time)
+                {
+                    start_kinetic_scroll(&state, generation, window, position);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Keeps scrolling `window` after the fingers lift, until the momentum
+/// started as `generation` runs out or is cancelled.
+fn start_kinetic_scroll(
+    state: &WaylandClientState,
+    generation: u64,
+    window: WaylandWindowStatePtr,
+    position: Point<Pixels>,
+) {
+    let result = state.loop_handle.insert_source(
+        Timer::from_duration(KINETIC_SCROLL_INTERVAL),
+        move |event_timestamp, _metadata, this| {
+            let client = this.get_client();
+            let mut state = client.borrow_mut();
+            let Some(delta) = state
+                .kinetic_scroll
+                .step(generation, KINETIC_SCROLL_INTERVAL)
+            else {
+                return TimeoutAction::Drop;
+            };
+            let input = PlatformInput::ScrollWheel(ScrollWheelEvent {
+                position,
+                delta: ScrollDelta::Pixels(delta),
+                modifiers: state.modifiers,
+                touch_phase: TouchPhase::Moved,
+            });
+            drop(state);
+            window.handle_input(input);
+            TimeoutAction::ToInstant(event_timestamp + KINETIC_SCROLL_INTERVAL)
+        },
+    );
+    if let Err(error) = result {
+        log::error!("failed to start kinetic scrolling: {}", error.error);
+    }
+}
+
+impl Dispatch<zwp_pointer_gesture_swipe_v1::ZwpPointerGestureSwipeV1, ()>
+    for WaylandClientStatePtr
+{
+    fn event(
+        this: &mut Self,
+        _: &zwp_pointer_gesture_swipe_v1::ZwpPointerGestureSwipeV1,
+        event: zwp_pointer_gesture_swipe_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let client = this.get_client();
+        let mut state = client.borrow_mut();
+        let (delta, touch_phase) = match event {
+            zwp_pointer_gesture_swipe_v1::Event::Begin { fingers, .. } => {
+                state.swipe_finger_count = fingers;
+                (Point::default(), TouchPhase::Started)
+            }
+            zwp_pointer_gesture_swipe_v1::Event::Update { dx, dy, .. } => {
+                (point(px(dx as f32), px(dy as f32)), TouchPhase::Moved)
+            }
+            zwp_pointer_gesture_swipe_v1::Event::End { .. } => {
+                (Point::default(), TouchPhase::Ended)
+            }
+            _ => return,
+        };
+        let (Some(window), Some(position)) =
+            (state.mouse_focused_window.clone(), state.mouse_location)
+        else {
+            return;
+        };
+        let input = PlatformInput::Swipe(SwipeEvent {
+            position,
+            delta,
+            finger_count: state.swipe_finger_count,
+            modifiers: state.modifiers,
+            touch_phase,
+        });
+        drop(state);
+        window.handle_input(input);
+    }
+}
+
+impl Dispatch<zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1, ()>
+    for WaylandClientStatePtr
+{
+    fn event(
+        this: &mut Self,
+        _: &zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1,
+        event: zwp_pointer_gesture_pinch_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let client = this.get_client();
+        let mut state = client.borrow_mut();
+        let (delta, touch_phase) = match event {
+            zwp_pointer_gesture_pinch_v1::Event::Begin { .. } => {
+                state.pinch_scale = 1.;
+                (0., TouchPhase::Started)
+            }
+            zwp_pointer_gesture_pinch_v1::Event::Update { scale, .. } => {
+                let delta = scale / state.pinch_scale - 1.;
+                state.pinch_scale = scale;
+                (delta as f32, TouchPhase::Moved)
+            }
+            zwp_pointer_gesture_pinch_v1::Event::End { .. } => (0., TouchPhase::Ended),
+            _ => return,
+        };
+        let (Some(window), Some(position)) =
+            (state.mouse_focused_window.clone(), state.mouse_location)
+        else {
+            return;
+        };
+        let input = PlatformInput::Pinch(PinchEvent {
+            position,
+            delta,
+            modifiers: state.modifiers,
+            touch_phase,
+        });
+        drop(state);
+        window.handle_input(input);
+    }
+}
+
+impl Dispatch<wl_touch::WlTouch, ()> for WaylandClientStatePtr {
+    fn event(
+        this: &mut Self,
+        _: &wl_touch::WlTouch,
+        event: wl_touch::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let client = this.get_client();
+        let mut state = client.borrow_mut();
+        match event {
+            wl_touch::Event::Down {
+                surface, id, x, y, ..
+            } => {
+                let position = point(px(x as f32), px(y as f32));
+                if state.touch.points.is_empty() {
+                    state.kinetic_scroll.cancel();
+                    let window = get_window(&mut state, &surface.id());
+                    state.touch.window = window;
+                    state.touch.tap_location = Some(position);
+                } else {
+                    state.touch.tap_location = None;
+                }
+                state.touch.points.insert(id, position);
+
+                if state.touch.points.len() == 2
+                    && let Some(window) = state.touch.window.clone()
+                    && let Some((center, _)) = state.touch.pinch_center_and_distance()
+                {
+                    let input = PlatformInput::Pinch(PinchEvent {
+                        position: center,
+                        delta: 0.,
+                        modifiers: state.modifiers,
+                        touch_phase: TouchPhase::Started,
+                    });
+                    drop(state);
+                    window.handle_input(input);
+                }
+            }
+            wl_touch::Event::Motion { time, id, x, y } => {
+                let position = point(px(x as f32), px(y as f32));
+                let Some(window) = state.touch.window.clone() else {
+                    return;
+                };
+                let previous_pinch = state.touch.pinch_center_and_distance();
+                let Some(previous_position) = state.touch.points.insert(id, position) else {
+                    return;
+                };
+
+                let input = match state.touch.points.len() {
+                    1 => {
+                        if state.touch.tap_location.is_some_and(|tap_location| {
+                            is_within_click_distance(tap_location, position)
+                        }) {
+                            return;
+                        }
+                        state.touch.tap_location = None;
+                        let delta = position - previous_position;
+                        state.kinetic_scroll.track(delta, time);
+                        PlatformInput::ScrollWheel(ScrollWheelEvent {
+                            position,
+                            delta: ScrollDelta::Pixels(delta),
+                            modifiers: state.modifiers,
+                            touch_phase: TouchPhase::Moved,
+                        })
+                    }
+                    2 => {
+                        let (Some((_, previous_distance)), Some((center, distance))) =
+                            (previous_pinch, state.touch.pinch_center_and_distance())
+                        else {
+                            return;
+                        };
+                        if previous_distance <= 0. {
+                            return;
+                        }
+                        PlatformInput::Pinch(PinchEvent {
+                            position: center,
+                            delta: distance / previous_distance - 1.,
+                            modifiers: state.modifiers,
+                            touch_phase: TouchPhase::Moved,
+                        })
+                    }
+                    _ => return,
+                };
+                drop(state);
+                window.handle_input(input);
+            }
+            wl_touch::Event::Up { time, id, .. } => {
+                let Some(window) = state.touch.window.clone() else {
+                    return;
+                };
+                let pinch = state.touch.pinch_center_and_distance();
+                let Some(position) = state.touch.points.remove(&id) else {
+                    return;
+                };
+
+                if let Some((center, _)) = pinch {
+                    if state.touch.points.len() == 1 {
+                        let input = PlatformInput::Pinch(PinchEvent {
+                            position: center,
+                            delta: 0.,
+                            modifiers: state.modifiers,
+                            touch_phase: TouchPhase::Ended,
+                        });
+                        drop(state);
+                        window.handle_input(input);
+                    }
+                    return;
+                }
+
+                state.touch.window = None;
+                if state.touch.tap_location.take().is_some() {
+                    let modifiers = state.modifiers;
+                    drop(state);
+                    window.handle_input(PlatformInput::MouseDown(MouseDownEvent {
+                        button: MouseButton::Left,
+                        position,
+                        modifiers,
+                        click_count: 1,
+                        first_mouse: false,
+                    }));
+                    window.handle_input(PlatformInput::MouseUp(MouseUpEvent {
+                        button: MouseButton::Left,
+                        position,
+                        modifiers,
+                        click_count: 1,
+                    }));
+                } else if let Some(generation) = state.kinetic_scroll.release(time) {
+                    start_kinetic_scroll(&state, generation, window, position);
+                }
+            }
+            wl_touch::Event::Cancel => {
+                state.touch = TouchState::default();
+            }
             _ => {}
         }
     }
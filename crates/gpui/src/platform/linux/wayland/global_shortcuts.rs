@@ -0,0 +1,131 @@
+//! Global hotkeys on Wayland. Compositors don't let clients grab keys, so the
+//! hotkeys are bound through the XDG desktop portal's GlobalShortcuts interface.
+
+use std::collections::BTreeMap;
+
+use anyhow::Context as _;
+use ashpd::desktop::global_shortcuts::{GlobalShortcuts, NewShortcut};
+use calloop::channel::Sender;
+use smol::stream::StreamExt as _;
+use xkbcommon::xkb;
+
+use crate::{BackgroundExecutor, Keystroke, Task};
+
+#[derive(Clone)]
+struct Shortcut {
+    trigger: String,
+    description: String,
+}
+
+pub(crate) struct PortalGlobalShortcuts {
+    shortcuts: BTreeMap<u32, Shortcut>,
+    activations: Sender<u32>,
+    session: Option<Task<()>>,
+}
+
+impl PortalGlobalShortcuts {
+    /// Creates the hotkey registry, which sends the id of each hotkey the user
+    /// presses to `activations`.
+    pub fn new(activations: Sender<u32>) -> Self {
+        Self {
+            shortcuts: BTreeMap::new(),
+            activations,
+            session: None,
+        }
+    }
+
+    pub fn register(
+        &mut self,
+        id: u32,
+        keystroke: &Keystroke,
+        description: &str,
+        executor: &BackgroundExecutor,
+    ) -> anyhow::Result<()> {
+        let trigger = portal_trigger(keystroke)?;
+        self.shortcuts.insert(
+            id,
+            Shortcut {
+                trigger,
+                description: description.to_string(),
+            },
+        );
+        self.rebind(executor);
+        Ok(())
+    }
+
+    pub fn unregister(&mut self, id: u32, executor: &BackgroundExecutor) {
+        if self.shortcuts.remove(&id).is_some() {
+            self.rebind(executor);
+        }
+    }
+
+    /// The portal binds all of a session's shortcuts at once, so every change
+    /// replaces the session with one that binds the full set.
+    fn rebind(&mut self, executor: &BackgroundExecutor) {
+        self.session = None;
+        if self.shortcuts.is_empty() {
+            return;
+        }
+
+        let shortcuts = self
+            .shortcuts
+            .iter()
+            .map(|(id, shortcut)| (id.to_string(), shortcut.clone()))
+            .collect::<Vec<_>>();
+        let activations = self.activations.clone();
+        self.session = Some(executor.spawn(async move {
+            if let Err(error) = run_session(shortcuts, activations).await {
+                log::error!("failed to bind global hotkeys through the desktop portal: {error:#}");
+            }
+        }));
+    }
+}
+
+async fn run_session(
+    shortcuts: Vec<(String, Shortcut)>,
+    activations: Sender<u32>,
+) -> anyhow::Result<()> {
+    let portal = GlobalShortcuts::new().await?;
+    let session = portal.create_session().await?;
+    let new_shortcuts = shortcuts
+        .iter()
+        .map(|(id, shortcut)| {
+            NewShortcut::new(id, &shortcut.description).preferred_trigger(shortcut.trigger.as_str())
+        })
+        .collect::<Vec<_>>();
+    portal
+        .bind_shortcuts(&session, &new_shortcuts, None)
+        .await?
+        .response()
+        .context("the desktop declined to bind the shortcuts")?;
+
+    let mut activated = portal.receive_activated().await?;
+    while let Some(activation) = activated.next().await {
+        if let Ok(id) = activation.shortcut_id().parse() {
+            activations.send(id)?;
+        }
+    }
+    Ok(())
+}
+
+/// Formats a keystroke as a trigger in the XDG shortcuts format, such as
+/// `CTRL+ALT+z`.
+fn portal_trigger(keystroke: &Keystroke) -> anyhow::Result<String> {
+    let keysym = keystroke
+        .keysym()
+        .with_context(|| format!("unknown key {:?}", keystroke.key))?;
+    let modifiers = [
+        (keystroke.modifiers.control, "CTRL"),
+        (keystroke.modifiers.alt, "ALT"),
+        (keystroke.modifiers.shift, "SHIFT"),
+        (keystroke.modifiers.platform, "LOGO"),
+    ];
+
+    let mut trigger = String::new();
+    for (_, name) in modifiers.iter().filter(|(pressed, _)| *pressed) {
+        trigger.push_str(name);
+        trigger.push('+');
+    }
+    trigger.push_str(&xkb::keysym_get_name(keysym));
+    Ok(trigger)
+}
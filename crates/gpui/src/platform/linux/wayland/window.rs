@@ -1,9 +1,11 @@
 use std::{
     cell::{Ref, RefCell, RefMut},
     ffi::c_void,
+    ops::Range,
     ptr::NonNull,
     rc::Rc,
     sync::Arc,
+    time::Instant,
 };
 
 use collections::{FxHashSet, HashMap};
@@ -25,20 +27,20 @@ use wayland_protocols_plasma::blur::client::org_kde_kwin_blur;
 use wayland_protocols_wlr::layer_shell::v1::client::zwlr_layer_surface_v1;
 
 use crate::{
-    AnyWindowHandle, Bounds, Decorations, DevicePixels, Globals, GpuSpecs, Modifiers, Output,
-    Pixels, PlatformDisplay, PlatformInput, Point, PromptButton, PromptLevel, RequestFrameOptions,
-    ResizeEdge, Size, Tiling, WaylandClientStatePtr, WindowAppearance, WindowBackgroundAppearance,
-    WindowBounds, WindowControlArea, WindowControls, WindowDecorations, WindowParams, get_window,
-    layer_shell::LayerShellNotSupportedError, px, size,
-};
-use crate::{
-    Capslock,
+    AccessibilityAdapter, Capslock,
     platform::{
         PlatformAtlas, PlatformInputHandler, PlatformWindow,
         linux::wayland::{display::WaylandDisplay, serial::SerialKind},
         wgpu::{WgpuContext, WgpuRenderer, WgpuSurfaceConfig},
     },
 };
+use crate::{
+    AnyWindowHandle, Bounds, Decorations, DevicePixels, Globals, GpuSpecs, Modifiers, Output,
+    Pixels, PlatformDisplay, PlatformInput, Point, PromptButton, PromptLevel, RequestFrameOptions,
+    ResizeEdge, Size, Tiling, WaylandClientStatePtr, WindowAppearance, WindowBackgroundAppearance,
+    WindowBounds, WindowControlArea, WindowControls, WindowDecorations, WindowParams, get_window,
+    layer_shell::LayerShellNotSupportedError, px, size,
+};
 use crate::{WindowKind, scene::Scene};
 
 #[derive(Default)]
@@ -52,6 +54,7 @@ pub(crate) struct Callbacks {
     should_close: Option<Box<dyn FnMut() -> bool>>,
     close: Option<Box<dyn FnOnce()>>,
     appearance_changed: Option<Box<dyn FnMut()>>,
+    frame_presented: Option<Box<dyn FnMut(Option<Instant>)>>,
 }
 
 struct RawWindow {
@@ -99,6 +102,9 @@ pub struct WaylandWindowState {
     appearance: WindowAppearance,
     blur: Option<org_kde_kwin_blur::OrgKdeKwinBlur>,
     viewport: Option<wp_viewport::WpViewport>,
+    /// Only created alongside a viewport, since a fractionally scaled buffer
+    /// is mapped onto the surface through the viewport's destination size.
+    fractional_scale: Option<wp_fractional_scale_v1::WpFractionalScaleV1>,
     outputs: HashMap<ObjectId, Output>,
     display: Option<(ObjectId, Output)>,
     globals: Globals,
@@ -121,6 +127,7 @@ pub struct WaylandWindowState {
     in_progress_window_controls: Option<WindowControls>,
     window_controls: WindowControls,
     client_inset: Option<Pixels>,
+    accessibility: AccessibilityAdapter,
 }
 
 pub enum WaylandSurfaceState {
@@ -317,6 +324,7 @@ impl WaylandWindowState {
         surface_state: WaylandSurfaceState,
         appearance: WindowAppearance,
         viewport: Option<wp_viewport::WpViewport>,
+        fractional_scale: Option<wp_fractional_scale_v1::WpFractionalScaleV1>,
         client: WaylandClientStatePtr,
         globals: Globals,
         gpu_context: &WgpuContext,
@@ -358,6 +366,7 @@ impl WaylandWindowState {
             app_id: None,
             blur: None,
             viewport,
+            fractional_scale,
             globals,
             outputs: HashMap::default(),
             display: None,
@@ -381,6 +390,7 @@ impl WaylandWindowState {
             in_progress_window_controls: None,
             window_controls: WindowControls::default(),
             client_inset: None,
+            accessibility: AccessibilityAdapter::new(),
         })
     }
 
@@ -417,7 +427,12 @@ impl WaylandWindowState {
 pub(crate) struct WaylandWindow(pub WaylandWindowStatePtr);
 pub enum ImeInput {
     InsertText(String),
-    SetMarkedText(String),
+    /// The text being composed, with the input method's cursor within it in
+    /// UTF-16 offsets.
+    SetMarkedText {
+        text: String,
+        selected_range: Option<Range<usize>>,
+    },
     UnmarkText,
     DeleteText,
 }
@@ -449,6 +464,10 @@ impl Drop for WaylandWindow {
         // decorations are gone. layer_surface has no dependencies.
         state.surface_state.destroy();
 
+        if let Some(fractional_scale) = &state.fractional_scale {
+            fractional_scale.destroy();
+        }
+
         // Viewport must be destroyed before the wl_surface.
         // See https://wayland.app/protocols/viewporter#wp_viewport
         if let Some(viewport) = &state.viewport {
@@ -492,15 +511,19 @@ impl WaylandWindow {
         let surface = globals.compositor.create_surface(&globals.qh, ());
         let surface_state = WaylandSurfaceState::new(&surface, &globals, &params, parent.clone())?;
 
-        if let Some(fractional_scale_manager) = globals.fractional_scale_manager.as_ref() {
-            fractional_scale_manager.get_fractional_scale(&surface, &globals.qh, surface.id());
-        }
-
         let viewport = globals
             .viewporter
             .as_ref()
             .map(|viewporter| viewporter.get_viewport(&surface, &globals.qh, ()));
 
+        let fractional_scale = globals
+            .fractional_scale_manager
+            .as_ref()
+            .filter(|_| viewport.is_some())
+            .map(|fractional_scale_manager| {
+                fractional_scale_manager.get_fractional_scale(&surface, &globals.qh, surface.id())
+            });
+
         let this = Self(WaylandWindowStatePtr {
             state: Rc::new(RefCell::new(WaylandWindowState::new(
                 handle,
@@ -508,6 +531,7 @@ impl WaylandWindow {
                 surface_state,
                 appearance,
                 viewport,
+                fractional_scale,
                 client,
                 globals,
                 gpu_context,
@@ -563,6 +587,12 @@ impl WaylandWindowStatePtr {
         }
     }
 
+    pub fn frame_presented(&self, presented_at: Option<Instant>) {
+        if let Some(fun) = self.callbacks.borrow_mut().frame_presented.as_mut() {
+            fun(presented_at);
+        }
+    }
+
     pub fn handle_xdg_surface_event(&self, event: xdg_surface::Event) {
         if let xdg_surface::Event::Configure { serial } = event {
             {
@@ -836,8 +866,11 @@ impl WaylandWindowStatePtr {
 
                 let scale = state.primary_output_scale();
 
-                // We use `PreferredBufferScale` instead to set the scale if it's available
-                if state.surface.version() < wl_surface::EVT_PREFERRED_BUFFER_SCALE_SINCE {
+                // We use `PreferredBufferScale` or `WpFractionalScale` instead to set the scale
+                // if they're available
+                if state.fractional_scale.is_none()
+                    && state.surface.version() < wl_surface::EVT_PREFERRED_BUFFER_SCALE_SINCE
+                {
                     state.surface.set_buffer_scale(scale);
                     drop(state);
                     self.rescale(scale as f32);
@@ -848,8 +881,11 @@ impl WaylandWindowStatePtr {
 
                 let scale = state.primary_output_scale();
 
-                // We use `PreferredBufferScale` instead to set the scale if it's available
-                if state.surface.version() < wl_surface::EVT_PREFERRED_BUFFER_SCALE_SINCE {
+                // We use `PreferredBufferScale` or `WpFractionalScale` instead to set the scale
+                // if they're available
+                if state.fractional_scale.is_none()
+                    && state.surface.version() < wl_surface::EVT_PREFERRED_BUFFER_SCALE_SINCE
+                {
                     state.surface.set_buffer_scale(scale);
                     drop(state);
                     self.rescale(scale as f32);
@@ -857,7 +893,7 @@ impl WaylandWindowStatePtr {
             }
             wl_surface::Event::PreferredBufferScale { factor } => {
                 // We use `WpFractionalScale` instead to set the scale if it's available
-                if state.globals.fractional_scale_manager.is_none() {
+                if state.fractional_scale.is_none() {
                     state.surface.set_buffer_scale(factor);
                     drop(state);
                     self.rescale(factor as f32);
@@ -878,8 +914,11 @@ impl WaylandWindowStatePtr {
                 ImeInput::InsertText(text) => {
                     input_handler.replace_text_in_range(None, &text);
                 }
-                ImeInput::SetMarkedText(text) => {
-                    input_handler.replace_and_mark_text_in_range(None, &text, None);
+                ImeInput::SetMarkedText {
+                    text,
+                    selected_range,
+                } => {
+                    input_handler.replace_and_mark_text_in_range(None, &text, selected_range);
                 }
                 ImeInput::UnmarkText => {
                     input_handler.unmark_text();
@@ -899,8 +938,14 @@ impl WaylandWindowStatePtr {
         let mut bounds: Option<Bounds<Pixels>> = None;
         if let Some(mut input_handler) = state.input_handler.take() {
             drop(state);
-            if let Some(selection) = input_handler.marked_text_range() {
-                bounds = input_handler.bounds_for_range(selection.start..selection.start);
+            // Place the candidate window at the cursor within the composition.
+            if let Some(marked_range) = input_handler.marked_text_range() {
+                let cursor = input_handler
+                    .selected_text_range(true)
+                    .map(|selection| selection.range.start)
+                    .filter(|cursor| marked_range.contains(cursor))
+                    .unwrap_or(marked_range.start);
+                bounds = input_handler.bounds_for_range(cursor..cursor);
             }
             self.state.borrow_mut().input_handler = Some(input_handler);
         }
@@ -923,6 +968,12 @@ impl WaylandWindowStatePtr {
             }
             let device_bounds = state.bounds.to_device_pixels(state.scale);
             state.renderer.update_drawable_size(device_bounds.size);
+            // Wayland hides the window's position from clients, so the tree
+            // is placed relative to the surface's origin.
+            let physical_bounds = state.bounds.scale(state.scale);
+            state
+                .accessibility
+                .set_bounds(physical_bounds, physical_bounds);
             (state.bounds.size, state.scale)
         };
 
@@ -991,7 +1042,10 @@ impl WaylandWindowStatePtr {
     }
 
     pub fn set_focused(&self, focus: bool) {
-        self.state.borrow_mut().active = focus;
+        let mut state = self.state.borrow_mut();
+        state.active = focus;
+        state.accessibility.set_focused(focus);
+        drop(state);
         if let Some(ref mut fun) = self.callbacks.borrow_mut().active_status_change {
             fun(focus);
         }
@@ -1200,6 +1254,20 @@ impl PlatformWindow for WaylandWindow {
         self.borrow().active
     }
 
+    fn request_attention(&self) {
+        // Without an input serial the activation can't take focus, so compositors
+        // that support xdg-activation mark the window as demanding attention instead.
+        let state = self.borrow();
+        if let (Some(activation), Some(app_id)) = (&state.globals.activation, state.app_id.clone())
+        {
+            state.client.set_pending_activation(state.surface.id());
+            let token = activation.get_activation_token(&state.globals.qh, ());
+            token.set_app_id(app_id);
+            token.set_surface(&state.surface);
+            token.commit();
+        }
+    }
+
     fn is_hovered(&self) -> bool {
         self.borrow().hovered
     }
@@ -1310,11 +1378,33 @@ impl PlatformWindow for WaylandWindow {
         state.renderer.draw(scene);
     }
 
+    fn render_to_image(&self, scene: &Scene) -> anyhow::Result<image::RgbaImage> {
+        let mut state = self.borrow_mut();
+        state.renderer.render_to_image(scene)
+    }
+
     fn completed_frame(&self) {
         let state = self.borrow();
+        if let Some(presentation) = &state.globals.presentation
+            && self.0.callbacks.borrow().frame_presented.is_some()
+        {
+            presentation.feedback(&state.surface, &state.globals.qh, state.surface.id());
+        }
         state.surface.commit();
     }
 
+    fn on_frame_presented(&self, callback: Option<Box<dyn FnMut(Option<Instant>)>>) {
+        self.0.callbacks.borrow_mut().frame_presented = callback;
+    }
+
+    fn reports_frame_presentation(&self) -> bool {
+        self.borrow().globals.presentation.is_some()
+    }
+
+    fn update_accessibility_tree(&self, build_update: &mut dyn FnMut() -> accesskit::TreeUpdate) {
+        self.borrow_mut().accessibility.update(build_update);
+    }
+
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas> {
         let state = self.borrow();
         state.renderer.sprite_atlas().clone()
@@ -29,7 +29,7 @@ use x11rb::{
     protocol::xkb::ConnectionExt as _,
     protocol::xproto::{
         AtomEnum, ChangeWindowAttributesAux, ClientMessageData, ClientMessageEvent,
-        ConnectionExt as _, EventMask, ModMask, Visibility,
+        ConnectionExt as _, EventMask, GrabMode, ModMask, Visibility,
     },
     protocol::{Event, randr, render, xinput, xkb, xproto},
     resource_manager::Database,
@@ -53,7 +53,7 @@ use crate::platform::{
     linux::{
         DEFAULT_CURSOR_ICON_NAME, LinuxClient, get_xkb_compose_state, is_within_click_distance,
         log_cursor_icon_warning, open_uri_internal,
-        platform::{DOUBLE_CLICK_INTERVAL, SCROLL_LINES},
+        platform::SCROLL_LINES,
         reveal_path_internal,
         xdg_desktop_portal::{Event as XDPEvent, XDPEventSource},
     },
@@ -61,9 +61,10 @@ use crate::platform::{
 };
 use crate::{
     AnyWindowHandle, Bounds, ClipboardItem, CursorStyle, DisplayId, FileDropEvent, Keystroke,
-    LinuxKeyboardLayout, Modifiers, ModifiersChangedEvent, MouseButton, Pixels, Platform,
-    PlatformDisplay, PlatformInput, PlatformKeyboardLayout, Point, RequestFrameOptions,
-    ScrollDelta, Size, TouchPhase, WindowParams, X11Window, modifiers_from_xinput_info, point, px,
+    LinuxKeyboardLayout, Modifiers, ModifiersChangedEvent, MouseButton, PinchEvent, Pixels,
+    Platform, PlatformDisplay, PlatformInput, PlatformKeyboardLayout, Point, RequestFrameOptions,
+    ScrollDelta, Size, SwipeEvent, TouchPhase, WindowParams, X11Window, fp1616_to_f32,
+    modifiers_from_xinput_info, point, px,
 };
 
 /// Value for DeviceId parameters which selects all devices.
@@ -168,6 +169,21 @@ struct ScrollAxisState {
     scroll_value: Option<f32>,
 }
 
+/// Combinations of the lock modifiers, which shouldn't stop a global hotkey from
+/// triggering. X11 only matches grabs with the exact modifier state, so each
+/// hotkey is grabbed once for every combination.
+fn ignored_hotkey_modifiers() -> [u16; 4] {
+    let caps_lock = ModMask::LOCK.bits();
+    let num_lock = ModMask::M2.bits();
+    [0, caps_lock, num_lock, caps_lock | num_lock]
+}
+
+/// A key grabbed on the root window to implement a global hotkey.
+struct GlobalHotkeyGrab {
+    keycode: xproto::Keycode,
+    modifiers: u16,
+}
+
 pub struct X11ClientState {
     pub(crate) loop_handle: LoopHandle<'static, X11Client>,
     pub(crate) event_loop: Option<calloop::EventLoop<'static, X11Client>>,
@@ -185,6 +201,9 @@ pub struct X11ClientState {
     pub(crate) xcb_connection: Rc<XCBConnection>,
     xkb_device_id: i32,
     client_side_decorations_supported: bool,
+    gestures_supported: bool,
+    /// The scale of the ongoing pinch gesture, relative to when it began.
+    pinch_scale: f32,
     pub(crate) x_root_index: usize,
     pub(crate) _resource_database: Database,
     pub(crate) atoms: XcbAtoms,
@@ -216,6 +235,7 @@ pub struct X11ClientState {
     pub(crate) clipboard: Clipboard,
     pub(crate) clipboard_item: Option<ClipboardItem>,
     pub(crate) xdnd_state: Xdnd,
+    global_hotkeys: HashMap<u32, GlobalHotkeyGrab>,
 }
 
 #[derive(Clone)]
@@ -341,16 +361,17 @@ impl X11Client {
         xcb_connection.prefetch_extension_information(render::X11_EXTENSION_NAME)?;
         xcb_connection.prefetch_extension_information(xinput::X11_EXTENSION_NAME)?;
 
-        // Announce to X server that XInput up to 2.1 is supported. To increase this to 2.2 and
-        // beyond, support for touch events would need to be added.
+        // Announce to X server that XInput up to 2.4 is supported, for touchpad gestures.
+        // Touchscreens are left to the server's pointer emulation.
         let xinput_version = get_reply(
             || "XInput XiQueryVersion failed",
-            xcb_connection.xinput_xi_query_version(2, 1),
+            xcb_connection.xinput_xi_query_version(2, 4),
         )?;
         assert!(
             xinput_version.major_version >= 2,
             "XInput version >= 2 required."
         );
+        let gestures_supported = xinput_version.minor_version >= 4;
 
         let pointer_device_states =
             current_pointer_device_states(&xcb_connection, &BTreeMap::new()).unwrap_or_default();
@@ -473,6 +494,35 @@ impl X11Client {
                     XDPEvent::CursorTheme(_) | XDPEvent::CursorSize(_) => {
                         // noop, X11 manages this for us.
                     }
+                    XDPEvent::DoubleClickInterval(interval) => {
+                        client.with_common(|common| common.double_click_interval = interval);
+                    }
+                    XDPEvent::HighContrast(high_contrast) => {
+                        let callback = client.with_common(|common| {
+                            common.update_accessibility_preferences(|preferences| {
+                                preferences.high_contrast = high_contrast
+                            })
+                        });
+                        if let Some(mut callback) = callback {
+                            callback();
+                            client.with_common(|common| {
+                                common.callbacks.accessibility_preferences_change = Some(callback)
+                            });
+                        }
+                    }
+                    XDPEvent::ReduceMotion(reduce_motion) => {
+                        let callback = client.with_common(|common| {
+                            common.update_accessibility_preferences(|preferences| {
+                                preferences.reduce_motion = reduce_motion
+                            })
+                        });
+                        if let Some(mut callback) = callback {
+                            callback();
+                            client.with_common(|common| {
+                                common.callbacks.accessibility_preferences_change = Some(callback)
+                            });
+                        }
+                    }
                 }
             })
             .map_err(|err| anyhow!("Failed to initialize XDP event source: {err:?}"))?;
@@ -498,6 +548,8 @@ impl X11Client {
             xcb_connection,
             xkb_device_id,
             client_side_decorations_supported,
+            gestures_supported,
+            pinch_scale: 1.,
             x_root_index,
             _resource_database: resource_database,
             atoms,
@@ -523,6 +575,7 @@ impl X11Client {
             clipboard,
             clipboard_item: None,
             xdnd_state: Xdnd::default(),
+            global_hotkeys: HashMap::default(),
         }))))
     }
 
@@ -626,6 +679,10 @@ impl X11Client {
             }
 
             for event in events.into_iter() {
+                if self.handle_global_hotkey(&event) {
+                    continue;
+                }
+
                 let mut state = self.0.borrow_mut();
                 if !state.has_xim() {
                     drop(state);
@@ -1039,7 +1096,7 @@ impl X11Client {
                                 let pre_edit =
                                     state.pre_edit_text.clone().unwrap_or(String::default());
                                 drop(state);
-                                window.handle_ime_preedit(pre_edit);
+                                window.handle_ime_preedit(pre_edit, None);
                                 state = self.0.borrow_mut();
                             }
                             xkbc::Status::Cancelled => {
@@ -1049,7 +1106,7 @@ impl X11Client {
                                     window.handle_ime_commit(pre_edit);
                                 }
                                 if let Some(current_key) = Keystroke::underlying_dead_key(keysym) {
-                                    window.handle_ime_preedit(current_key);
+                                    window.handle_ime_preedit(current_key, None);
                                 }
                                 state = self.0.borrow_mut();
                                 compose_state.feed(keysym);
@@ -1124,7 +1181,7 @@ impl X11Client {
                 match button_or_scroll_from_event_detail(event.detail) {
                     Some(ButtonOrScroll::Button(button)) => {
                         let click_elapsed = state.last_click.elapsed();
-                        if click_elapsed < DOUBLE_CLICK_INTERVAL
+                        if click_elapsed < state.common.double_click_interval
                             && state
                                 .last_mouse_button
                                 .is_some_and(|prev_button| prev_button == button)
@@ -1258,6 +1315,24 @@ impl X11Client {
                     }
                 }
             }
+            Event::XinputGesturePinchBegin(event) => {
+                self.handle_pinch(event, TouchPhase::Started)?;
+            }
+            Event::XinputGesturePinchUpdate(event) => {
+                self.handle_pinch(event, TouchPhase::Moved)?;
+            }
+            Event::XinputGesturePinchEnd(event) => {
+                self.handle_pinch(event, TouchPhase::Ended)?;
+            }
+            Event::XinputGestureSwipeBegin(event) => {
+                self.handle_swipe(event, TouchPhase::Started)?;
+            }
+            Event::XinputGestureSwipeUpdate(event) => {
+                self.handle_swipe(event, TouchPhase::Moved)?;
+            }
+            Event::XinputGestureSwipeEnd(event) => {
+                self.handle_swipe(event, TouchPhase::Ended)?;
+            }
             Event::XinputEnter(event) if event.mode == xinput::NotifyMode::NORMAL => {
                 let window = self.get_window(event.event)?;
                 window.set_hovered(true);
@@ -1323,8 +1398,8 @@ impl X11Client {
             XimCallbackEvent::XimCommitEvent(window, text) => {
                 self.xim_handle_commit(window, text);
             }
-            XimCallbackEvent::XimPreeditEvent(window, text) => {
-                self.xim_handle_preedit(window, text);
+            XimCallbackEvent::XimPreeditEvent(window, text, caret) => {
+                self.xim_handle_preedit(window, text, caret);
             }
         };
     }
@@ -1360,6 +1435,63 @@ impl X11Client {
         Some(())
     }
 
+    fn handle_pinch(
+        &self,
+        event: xinput::GesturePinchBeginEvent,
+        touch_phase: TouchPhase,
+    ) -> Option<()> {
+        let window = self.get_window(event.event)?;
+        let mut state = self.0.borrow_mut();
+        let position = point(
+            px(fp1616_to_f32(event.event_x) / state.scale_factor),
+            px(fp1616_to_f32(event.event_y) / state.scale_factor),
+        );
+        let scale = fp1616_to_f32(event.scale);
+        let delta = match touch_phase {
+            TouchPhase::Moved if state.pinch_scale > 0. => scale / state.pinch_scale - 1.,
+            _ => 0.,
+        };
+        state.pinch_scale = scale;
+        let modifiers = modifiers_from_xinput_info(event.mods);
+        drop(state);
+
+        window.handle_input(PlatformInput::Pinch(PinchEvent {
+            position,
+            delta,
+            modifiers,
+            touch_phase,
+        }));
+        Some(())
+    }
+
+    fn handle_swipe(
+        &self,
+        event: xinput::GestureSwipeBeginEvent,
+        touch_phase: TouchPhase,
+    ) -> Option<()> {
+        let window = self.get_window(event.event)?;
+        let state = self.0.borrow();
+        let position = point(
+            px(fp1616_to_f32(event.event_x) / state.scale_factor),
+            px(fp1616_to_f32(event.event_y) / state.scale_factor),
+        );
+        let delta = point(
+            px(fp1616_to_f32(event.delta_x) / state.scale_factor),
+            px(fp1616_to_f32(event.delta_y) / state.scale_factor),
+        );
+        let modifiers = modifiers_from_xinput_info(event.mods);
+        drop(state);
+
+        window.handle_input(PlatformInput::Swipe(SwipeEvent {
+            position,
+            delta,
+            finger_count: event.detail,
+            modifiers,
+            touch_phase,
+        }));
+        Some(())
+    }
+
     fn xim_handle_commit(&self, window: xproto::Window, text: String) -> Option<()> {
         let Some(window) = self.get_window(window) else {
             log::error!("bug: Failed to get window for XIM commit");
@@ -1372,7 +1504,7 @@ impl X11Client {
         Some(())
     }
 
-    fn xim_handle_preedit(&self, window: xproto::Window, text: String) -> Option<()> {
+    fn xim_handle_preedit(&self, window: xproto::Window, text: String, caret: usize) -> Option<()> {
         let Some(window) = self.get_window(window) else {
             log::error!("bug: Failed to get window for XIM preedit");
             return None;
@@ -1382,7 +1514,8 @@ impl X11Client {
         let (mut ximc, mut xim_handler) = state.take_xim()?;
         state.composing = !text.is_empty();
         drop(state);
-        window.handle_ime_preedit(text);
+        let selected_range = (!text.is_empty()).then_some(caret..caret);
+        window.handle_ime_preedit(text, selected_range);
 
         if let Some(scaled_area) = window.get_ime_area() {
             let ic_attributes = ximc
@@ -1412,6 +1545,37 @@ impl X11Client {
         Some(())
     }
 
+    /// Handles key presses on the root window, which are only delivered for the
+    /// keys grabbed as global hotkeys.
+    fn handle_global_hotkey(&self, event: &Event) -> bool {
+        let Event::KeyPress(key_press) = event else {
+            return false;
+        };
+        let mut state = self.0.borrow_mut();
+        let root = state.xcb_connection.setup().roots[state.x_root_index].root;
+        if key_press.event != root {
+            return false;
+        }
+
+        let hotkey_modifiers =
+            (ModMask::CONTROL | ModMask::M1 | ModMask::SHIFT | ModMask::M4).bits();
+        let modifiers = u16::from(key_press.state) & hotkey_modifiers;
+        let hotkey_id = state
+            .global_hotkeys
+            .iter()
+            .find(|(_, grab)| grab.keycode == key_press.detail && grab.modifiers == modifiers)
+            .map(|(id, _)| *id);
+        if let Some(hotkey_id) = hotkey_id
+            && let Some(mut callback) = state.common.callbacks.global_hotkey.take()
+        {
+            drop(state);
+            callback(hotkey_id);
+            state = self.0.borrow_mut();
+            state.common.callbacks.global_hotkey = Some(callback);
+        }
+        true
+    }
+
     fn handle_keyboard_layout_change(&self) {
         let mut state = self.0.borrow_mut();
         let layout_idx = state.xkb.serialize_layout(STATE_LAYOUT_EFFECTIVE);
@@ -1515,6 +1679,7 @@ impl LinuxClient for X11Client {
             params,
             &state.xcb_connection,
             state.client_side_decorations_supported,
+            state.gestures_supported,
             state.x_root_index,
             x_window,
             &state.atoms,
@@ -1650,6 +1815,97 @@ impl LinuxClient for X11Client {
             .log_with_level(log::Level::Debug)
     }
 
+    fn register_global_hotkey(
+        &self,
+        id: u32,
+        keystroke: &Keystroke,
+        _description: &str,
+    ) -> anyhow::Result<()> {
+        let mut state = self.0.borrow_mut();
+        let keysym = keystroke
+            .keysym()
+            .with_context(|| format!("unknown key {:?}", keystroke.key))?;
+        let keymap = state.xkb.get_keymap();
+        let keycode = (keymap.min_keycode().raw()..=keymap.max_keycode().raw())
+            .map(xkbc::Keycode::new)
+            .find(|keycode| {
+                keymap
+                    .key_get_syms_by_level(*keycode, 0, 0)
+                    .contains(&keysym)
+            })
+            .with_context(|| format!("no key on the keyboard types {:?}", keystroke.key))?;
+        let keycode = xproto::Keycode::try_from(keycode.raw())?;
+
+        let mut modifiers = 0;
+        if keystroke.modifiers.control {
+            modifiers |= ModMask::CONTROL.bits();
+        }
+        if keystroke.modifiers.alt {
+            modifiers |= ModMask::M1.bits();
+        }
+        if keystroke.modifiers.shift {
+            modifiers |= ModMask::SHIFT.bits();
+        }
+        if keystroke.modifiers.platform {
+            modifiers |= ModMask::M4.bits();
+        }
+
+        let root = state.xcb_connection.setup().roots[state.x_root_index].root;
+        let mut grabbed_modifiers = Vec::new();
+        for ignored_modifiers in ignored_hotkey_modifiers() {
+            let grab_modifiers = ModMask::from(modifiers | ignored_modifiers);
+            let grabbed = check_reply(
+                || "the key combination is already used by another application",
+                state.xcb_connection.grab_key(
+                    false,
+                    root,
+                    grab_modifiers,
+                    keycode,
+                    GrabMode::ASYNC,
+                    GrabMode::ASYNC,
+                ),
+            );
+            if let Err(error) = grabbed {
+                // Leaves no partial grab behind, which would swallow the key
+                // with only some of the lock modifiers on.
+                for grab_modifiers in grabbed_modifiers {
+                    check_reply(
+                        || "failed to release global hotkey",
+                        state
+                            .xcb_connection
+                            .ungrab_key(keycode, root, grab_modifiers),
+                    )
+                    .log_err();
+                }
+                return Err(error);
+            }
+            grabbed_modifiers.push(grab_modifiers);
+        }
+        state
+            .global_hotkeys
+            .insert(id, GlobalHotkeyGrab { keycode, modifiers });
+        Ok(())
+    }
+
+    fn unregister_global_hotkey(&self, id: u32) {
+        let mut state = self.0.borrow_mut();
+        let Some(grab) = state.global_hotkeys.remove(&id) else {
+            return;
+        };
+        let root = state.xcb_connection.setup().roots[state.x_root_index].root;
+        for ignored_modifiers in ignored_hotkey_modifiers() {
+            check_reply(
+                || "failed to release global hotkey",
+                state.xcb_connection.ungrab_key(
+                    grab.keycode,
+                    root,
+                    ModMask::from(grab.modifiers | ignored_modifiers),
+                ),
+            )
+            .log_err();
+        }
+    }
+
     fn run(&self) {
         let Some(mut event_loop) = self
             .0
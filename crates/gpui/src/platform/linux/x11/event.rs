@@ -53,6 +53,11 @@ pub(crate) fn modifiers_from_xinput_info(modifier_info: xinput::ModifierInfo) ->
     }
 }
 
+/// Converts an XInput 16.16 fixed point number.
+pub(crate) fn fp1616_to_f32(value: xinput::Fp1616) -> f32 {
+    value as f32 / 65536.
+}
+
 pub(crate) fn pressed_button_from_mask(button_mask: u32) -> Option<MouseButton> {
     Some(if button_mask & 2 == 2 {
         MouseButton::Left
@@ -3,11 +3,12 @@ use x11rb::connection::RequestConnection;
 
 use crate::platform::wgpu::{WgpuContext, WgpuRenderer, WgpuSurfaceConfig};
 use crate::{
-    AnyWindowHandle, Bounds, Decorations, DevicePixels, ForegroundExecutor, GpuSpecs, Modifiers,
-    Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow,
-    Point, PromptButton, PromptLevel, RequestFrameOptions, ResizeEdge, ScaledPixels, Scene, Size,
-    Tiling, WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControlArea,
-    WindowDecorations, WindowKind, WindowParams, X11ClientStatePtr, px,
+    AccessibilityAdapter, AnyWindowHandle, Bounds, Decorations, DevicePixels, ForegroundExecutor,
+    GpuSpecs, Modifiers, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
+    PlatformInputHandler, PlatformWindow, Point, PromptButton, PromptLevel, RequestFrameOptions,
+    ResizeEdge, ScaledPixels, Scene, Size, Tiling, WindowAppearance, WindowBackgroundAppearance,
+    WindowBounds, WindowControlArea, WindowControls, WindowDecorations, WindowKind, WindowParams,
+    WindowSnapLayout, X11ClientStatePtr, px,
 };
 
 use collections::FxHashSet;
@@ -28,7 +29,8 @@ use x11rb::{
 };
 
 use std::{
-    cell::RefCell, ffi::c_void, fmt::Display, num::NonZeroU32, ptr::NonNull, rc::Rc, sync::Arc,
+    cell::RefCell, ffi::c_void, fmt::Display, num::NonZeroU32, ops::Range, ptr::NonNull, rc::Rc,
+    sync::Arc,
 };
 
 use super::{X11Display, XINPUT_ALL_DEVICE_GROUPS, XINPUT_ALL_DEVICES};
@@ -64,6 +66,8 @@ x11rb::atom_manager! {
         _NET_WM_STATE_MAXIMIZED_HORZ,
         _NET_WM_STATE_FULLSCREEN,
         _NET_WM_STATE_HIDDEN,
+        _NET_WM_STATE_ABOVE,
+        _NET_WM_STATE_DEMANDS_ATTENTION,
         _NET_WM_STATE_FOCUSED,
         _NET_ACTIVE_WINDOW,
         _NET_WM_SYNC_REQUEST,
@@ -81,6 +85,7 @@ x11rb::atom_manager! {
         _GTK_FRAME_EXTENTS,
         _GTK_EDGE_CONSTRAINTS,
         _NET_CLIENT_LIST_STACKING,
+        _KDE_NET_WM_BLUR_BEHIND_REGION,
     }
 }
 
@@ -275,11 +280,13 @@ pub struct X11WindowState {
     active: bool,
     hovered: bool,
     fullscreen: bool,
+    keep_above: bool,
     client_side_decorations_supported: bool,
     decorations: WindowDecorations,
     edge_constraints: Option<EdgeConstraints>,
     pub handle: AnyWindowHandle,
     last_insets: [u32; 4],
+    accessibility: AccessibilityAdapter,
 }
 
 impl X11WindowState {
@@ -396,6 +403,7 @@ impl X11WindowState {
         params: WindowParams,
         xcb: &Rc<XCBConnection>,
         client_side_decorations_supported: bool,
+        gestures_supported: bool,
         x_main_screen_index: usize,
         x_window: xproto::Window,
         atoms: &XcbAtoms,
@@ -644,19 +652,29 @@ impl X11WindowState {
                 ),
             )?;
 
+            let mut pointer_event_mask = xinput::XIEventMask::MOTION
+                | xinput::XIEventMask::BUTTON_PRESS
+                | xinput::XIEventMask::BUTTON_RELEASE
+                | xinput::XIEventMask::ENTER
+                | xinput::XIEventMask::LEAVE;
+            // Servers before XInput 2.4 reject gesture masks.
+            if gestures_supported {
+                pointer_event_mask = pointer_event_mask
+                    | xinput::XIEventMask::GESTURE_PINCH_BEGIN
+                    | xinput::XIEventMask::GESTURE_PINCH_UPDATE
+                    | xinput::XIEventMask::GESTURE_PINCH_END
+                    | xinput::XIEventMask::GESTURE_SWIPE_BEGIN
+                    | xinput::XIEventMask::GESTURE_SWIPE_UPDATE
+                    | xinput::XIEventMask::GESTURE_SWIPE_END;
+            }
+
             check_reply(
                 || "X11 XiSelectEvents failed.",
                 xcb.xinput_xi_select_events(
                     x_window,
                     &[xinput::EventMask {
                         deviceid: XINPUT_ALL_DEVICE_GROUPS,
-                        mask: vec![
-                            xinput::XIEventMask::MOTION
-                                | xinput::XIEventMask::BUTTON_PRESS
-                                | xinput::XIEventMask::BUTTON_RELEASE
-                                | xinput::XIEventMask::ENTER
-                                | xinput::XIEventMask::LEAVE,
-                        ],
+                        mask: vec![pointer_event_mask],
                     }],
                 ),
             )?;
@@ -715,6 +733,7 @@ impl X11WindowState {
                 active: false,
                 hovered: false,
                 fullscreen: false,
+                keep_above: false,
                 maximized_vertical: false,
                 maximized_horizontal: false,
                 hidden: false,
@@ -725,6 +744,7 @@ impl X11WindowState {
                 client_side_decorations_supported,
                 decorations: WindowDecorations::Server,
                 last_insets: [0, 0, 0, 0],
+                accessibility: AccessibilityAdapter::new(),
                 edge_constraints: None,
                 counter_id: sync_request_counter,
                 last_sync_counter: None,
@@ -789,8 +809,8 @@ impl Drop for X11Window {
 }
 
 enum WmHintPropertyState {
-    // Remove = 0,
-    // Add = 1,
+    Remove = 0,
+    Add = 1,
     Toggle = 2,
 }
 
@@ -803,6 +823,7 @@ impl X11Window {
         params: WindowParams,
         xcb: &Rc<XCBConnection>,
         client_side_decorations_supported: bool,
+        gestures_supported: bool,
         x_main_screen_index: usize,
         x_window: xproto::Window,
         atoms: &XcbAtoms,
@@ -819,6 +840,7 @@ impl X11Window {
                 params,
                 xcb,
                 client_side_decorations_supported,
+                gestures_supported,
                 x_main_screen_index,
                 x_window,
                 atoms,
@@ -993,6 +1015,7 @@ impl X11WindowStatePtr {
 
         state.active = false;
         state.fullscreen = false;
+        state.keep_above = false;
         state.maximized_vertical = false;
         state.maximized_horizontal = false;
         state.hidden = false;
@@ -1008,6 +1031,8 @@ impl X11WindowStatePtr {
                 state.maximized_horizontal = true;
             } else if atom == state.atoms._NET_WM_STATE_HIDDEN {
                 state.hidden = true;
+            } else if atom == state.atoms._NET_WM_STATE_ABOVE {
+                state.keep_above = true;
             }
         }
 
@@ -1090,14 +1115,16 @@ impl X11WindowStatePtr {
         }
     }
 
-    pub fn handle_ime_preedit(&self, text: String) {
+    /// Shows `text` as the text being composed, with `selected_range` as the
+    /// input method's cursor within it, in UTF-16 offsets.
+    pub fn handle_ime_preedit(&self, text: String, selected_range: Option<Range<usize>>) {
         if self.is_blocked() {
             return;
         }
         let mut state = self.state.borrow_mut();
         if let Some(mut input_handler) = state.input_handler.take() {
             drop(state);
-            input_handler.replace_and_mark_text_in_range(None, &text, None);
+            input_handler.replace_and_mark_text_in_range(None, &text, selected_range);
             let mut state = self.state.borrow_mut();
             state.input_handler = Some(input_handler);
         }
@@ -1163,6 +1190,10 @@ impl X11WindowStatePtr {
             } else {
                 state.bounds = bounds;
             }
+            let physical_bounds = state.bounds.scale(state.scale_factor);
+            state
+                .accessibility
+                .set_bounds(physical_bounds, physical_bounds);
 
             let gpu_size = query_render_extent(&self.xcb, self.x_window)?;
             if true {
@@ -1192,6 +1223,7 @@ impl X11WindowStatePtr {
     }
 
     pub fn set_active(&self, focus: bool) {
+        self.state.borrow_mut().accessibility.set_focused(focus);
         if let Some(ref mut fun) = self.callbacks.borrow_mut().active_status_change {
             fun(focus);
         }
@@ -1451,6 +1483,23 @@ impl PlatformWindow for X11Window {
         state.background_appearance = background_appearance;
         let transparent = state.is_transparent();
         state.renderer.update_transparency(transparent);
+
+        // KWin blurs what's behind windows with this property, within the
+        // listed rectangles or, when there are none, the whole window.
+        let blur_region = state.atoms._KDE_NET_WM_BLUR_BEHIND_REGION;
+        let result = if background_appearance == WindowBackgroundAppearance::Blurred {
+            self.0.xcb.change_property32(
+                xproto::PropMode::REPLACE,
+                self.0.x_window,
+                blur_region,
+                xproto::AtomEnum::CARDINAL,
+                &[],
+            )
+        } else {
+            self.0.xcb.delete_property(self.0.x_window, blur_region)
+        };
+        check_reply(|| "X11 ChangeProperty for blur failed.", result).log_err();
+        xcb_flush(&self.0.xcb);
     }
 
     fn background_appearance(&self) -> WindowBackgroundAppearance {
@@ -1562,11 +1611,102 @@ impl PlatformWindow for X11Window {
         inner.renderer.draw(scene);
     }
 
+    fn render_to_image(&self, scene: &Scene) -> anyhow::Result<image::RgbaImage> {
+        let mut inner = self.0.state.borrow_mut();
+        inner.renderer.render_to_image(scene)
+    }
+
+    fn update_accessibility_tree(&self, build_update: &mut dyn FnMut() -> accesskit::TreeUpdate) {
+        self.0.state.borrow_mut().accessibility.update(build_update);
+    }
+
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas> {
         let inner = self.0.state.borrow();
         inner.renderer.sprite_atlas().clone()
     }
 
+    fn lower(&self) {
+        check_reply(
+            || "X11 ConfigureWindow to lower window failed.",
+            self.0.xcb.configure_window(
+                self.0.x_window,
+                &xproto::ConfigureWindowAux::new().stack_mode(xproto::StackMode::BELOW),
+            ),
+        )
+        .log_err();
+        xcb_flush(&self.0.xcb);
+    }
+
+    fn set_keep_above(&self, keep_above: bool) {
+        let state = self.0.state.borrow();
+        self.set_wm_hints(
+            || "X11 SendEvent to keep a window above others failed.",
+            if keep_above {
+                WmHintPropertyState::Add
+            } else {
+                WmHintPropertyState::Remove
+            },
+            state.atoms._NET_WM_STATE_ABOVE,
+            xproto::AtomEnum::NONE.into(),
+        )
+        .log_err();
+    }
+
+    fn is_keep_above(&self) -> bool {
+        self.0.state.borrow().keep_above
+    }
+
+    fn request_attention(&self) {
+        // The window manager clears this state once the window is activated.
+        let state = self.0.state.borrow();
+        self.set_wm_hints(
+            || "X11 SendEvent to request attention failed.",
+            WmHintPropertyState::Add,
+            state.atoms._NET_WM_STATE_DEMANDS_ATTENTION,
+            xproto::AtomEnum::NONE.into(),
+        )
+        .log_err();
+    }
+
+    fn snap(&self, layout: WindowSnapLayout) {
+        let state = self.0.state.borrow();
+        // Window managers ignore geometry requests for maximized windows.
+        if state.maximized_vertical || state.maximized_horizontal {
+            self.set_wm_hints(
+                || "X11 SendEvent to unmaximize a window failed.",
+                WmHintPropertyState::Remove,
+                state.atoms._NET_WM_STATE_MAXIMIZED_VERT,
+                state.atoms._NET_WM_STATE_MAXIMIZED_HORZ,
+            )
+            .log_err();
+        }
+
+        let bounds = layout
+            .bounds(state.display.visible_bounds())
+            .to_device_pixels(state.scale_factor);
+        check_reply(
+            || format!("X11 ConfigureWindow to snap window to {layout:?} failed."),
+            self.0.xcb.configure_window(
+                self.0.x_window,
+                &xproto::ConfigureWindowAux::new()
+                    .x(bounds.origin.x.0)
+                    .y(bounds.origin.y.0)
+                    .width(bounds.size.width.0 as u32)
+                    .height(bounds.size.height.0 as u32),
+            ),
+        )
+        .log_err();
+        xcb_flush(&self.0.xcb);
+    }
+
+    fn window_controls(&self) -> WindowControls {
+        WindowControls {
+            keep_above: true,
+            snap: true,
+            ..WindowControls::default()
+        }
+    }
+
     fn show_window_menu(&self, position: Point<Pixels>) {
         let state = self.0.state.borrow();
 
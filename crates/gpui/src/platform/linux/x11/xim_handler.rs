@@ -5,7 +5,8 @@ use xim::{AHashMap, AttributeName, Client, ClientError, ClientHandler, InputStyl
 
 pub enum XimCallbackEvent {
     XimXEvent(x11rb::protocol::Event),
-    XimPreeditEvent(xproto::Window, String),
+    /// The full preedit text, with the caret as a UTF-16 offset into it.
+    XimPreeditEvent(xproto::Window, String, usize),
     XimCommitEvent(xproto::Window, String),
 }
 
@@ -15,6 +16,7 @@ pub struct XimHandler {
     pub connected: bool,
     pub window: xproto::Window,
     pub last_callback_event: Option<XimCallbackEvent>,
+    preedit: String,
 }
 
 impl XimHandler {
@@ -25,6 +27,7 @@ impl XimHandler {
             connected: false,
             window: Default::default(),
             last_callback_event: None,
+            preedit: String::new(),
         }
     }
 }
@@ -73,6 +76,7 @@ impl<C: Client<XEvent = xproto::KeyPressEvent>> ClientHandler<C> for XimHandler
         _input_context_id: u16,
         text: &str,
     ) -> Result<(), ClientError> {
+        self.preedit.clear();
         self.last_callback_event = Some(XimCallbackEvent::XimCommitEvent(
             self.window,
             String::from(text),
@@ -110,9 +114,9 @@ impl<C: Client<XEvent = xproto::KeyPressEvent>> ClientHandler<C> for XimHandler
         _client: &mut C,
         _input_method_id: u16,
         _input_context_id: u16,
-        _caret: i32,
-        _chg_first: i32,
-        _chg_len: i32,
+        caret: i32,
+        chg_first: i32,
+        chg_len: i32,
         _status: xim::PreeditDrawStatus,
         preedit_string: &str,
         _feedbacks: Vec<xim::Feedback>,
@@ -124,9 +128,25 @@ impl<C: Client<XEvent = xproto::KeyPressEvent>> ClientHandler<C> for XimHandler
         // XIMPrimary, XIMHighlight, XIMSecondary, XIMTertiary are not specified,
         // but interchangeable as above
         // Currently there's no way to support these.
+
+        // Each draw only carries the characters that changed since the last one.
+        let mut chars = self.preedit.chars().collect::<Vec<_>>();
+        let change_start = usize::try_from(chg_first).unwrap_or(0).min(chars.len());
+        let change_end = change_start
+            .saturating_add(usize::try_from(chg_len).unwrap_or(0))
+            .min(chars.len());
+        chars.splice(change_start..change_end, preedit_string.chars());
+        let caret_utf16 = chars
+            .iter()
+            .take(usize::try_from(caret).unwrap_or(0))
+            .map(|char| char.len_utf16())
+            .sum();
+        self.preedit = chars.into_iter().collect();
+
         self.last_callback_event = Some(XimCallbackEvent::XimPreeditEvent(
             self.window,
-            String::from(preedit_string),
+            self.preedit.clone(),
+            caret_utf16,
         ));
         Ok(())
     }
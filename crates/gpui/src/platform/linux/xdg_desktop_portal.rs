@@ -6,6 +6,7 @@ use ashpd::desktop::settings::{ColorScheme, Settings};
 use calloop::channel::Channel;
 use calloop::{EventSource, Poll, PostAction, Readiness, Token, TokenFactory};
 use smol::stream::StreamExt;
+use std::time::Duration;
 
 use crate::{BackgroundExecutor, WindowAppearance};
 
@@ -15,8 +16,13 @@ pub enum Event {
     CursorTheme(String),
     #[cfg_attr(feature = "x11", allow(dead_code))]
     CursorSize(u32),
+    DoubleClickInterval(Duration),
+    HighContrast(bool),
+    ReduceMotion(bool),
 }
 
+const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+
 pub struct XDPEventSource {
     channel: Channel<Event>,
 }
@@ -51,6 +57,15 @@ impl XDPEventSource {
                     sender.send(Event::CursorSize(initial_size as u32))?;
                 }
 
+                if let Ok(initial_interval) = settings
+                    .read::<i32>("org.gnome.desktop.peripherals.mouse", "double-click")
+                    .await
+                {
+                    sender.send(Event::DoubleClickInterval(Duration::from_millis(
+                        initial_interval.max(0) as u64,
+                    )))?;
+                }
+
                 if let Ok(mut cursor_theme_changed) = settings
                     .receive_setting_changed_with_args(
                         "org.gnome.desktop.interface",
@@ -89,6 +104,97 @@ impl XDPEventSource {
                         .detach();
                 }
 
+                if let Ok(mut double_click_changed) = settings
+                    .receive_setting_changed_with_args::<i32>(
+                        "org.gnome.desktop.peripherals.mouse",
+                        "double-click",
+                    )
+                    .await
+                {
+                    let sender = sender.clone();
+                    background
+                        .spawn(async move {
+                            while let Some(interval) = double_click_changed.next().await {
+                                let interval = interval?;
+                                sender.send(Event::DoubleClickInterval(Duration::from_millis(
+                                    interval.max(0) as u64,
+                                )))?;
+                            }
+                            anyhow::Ok(())
+                        })
+                        .detach();
+                }
+
+                // 1 means the user prefers higher contrast, 0 that they have no preference.
+                if let Ok(contrast) = settings.read::<u32>(APPEARANCE_NAMESPACE, "contrast").await {
+                    sender.send(Event::HighContrast(contrast == 1))?;
+                }
+
+                // Older portals don't have `reduced-motion`, but GNOME's animation
+                // setting means the same.
+                if let Ok(reduced_motion) = settings
+                    .read::<u32>(APPEARANCE_NAMESPACE, "reduced-motion")
+                    .await
+                {
+                    sender.send(Event::ReduceMotion(reduced_motion == 1))?;
+                } else if let Ok(enable_animations) = settings
+                    .read::<bool>("org.gnome.desktop.interface", "enable-animations")
+                    .await
+                {
+                    sender.send(Event::ReduceMotion(!enable_animations))?;
+                }
+
+                if let Ok(mut contrast_changed) = settings
+                    .receive_setting_changed_with_args::<u32>(APPEARANCE_NAMESPACE, "contrast")
+                    .await
+                {
+                    let sender = sender.clone();
+                    background
+                        .spawn(async move {
+                            while let Some(contrast) = contrast_changed.next().await {
+                                sender.send(Event::HighContrast(contrast? == 1))?;
+                            }
+                            anyhow::Ok(())
+                        })
+                        .detach();
+                }
+
+                if let Ok(mut reduced_motion_changed) = settings
+                    .receive_setting_changed_with_args::<u32>(
+                        APPEARANCE_NAMESPACE,
+                        "reduced-motion",
+                    )
+                    .await
+                {
+                    let sender = sender.clone();
+                    background
+                        .spawn(async move {
+                            while let Some(reduced_motion) = reduced_motion_changed.next().await {
+                                sender.send(Event::ReduceMotion(reduced_motion? == 1))?;
+                            }
+                            anyhow::Ok(())
+                        })
+                        .detach();
+                }
+
+                if let Ok(mut animations_changed) = settings
+                    .receive_setting_changed_with_args::<bool>(
+                        "org.gnome.desktop.interface",
+                        "enable-animations",
+                    )
+                    .await
+                {
+                    let sender = sender.clone();
+                    background
+                        .spawn(async move {
+                            while let Some(enable_animations) = animations_changed.next().await {
+                                sender.send(Event::ReduceMotion(!enable_animations?))?;
+                            }
+                            anyhow::Ok(())
+                        })
+                        .detach();
+                }
+
                 let mut appearance_changed = settings.receive_color_scheme_changed().await?;
                 while let Some(scheme) = appearance_changed.next().await {
                     sender.send(Event::WindowAppearance(WindowAppearance::from_native(
@@ -11,7 +11,6 @@ use cocoa::{
     foundation::{NSSize, NSUInteger},
     quartzcore::AutoresizingMask,
 };
-#[cfg(any(test, feature = "test-support"))]
 use image::RgbaImage;
 
 use core_foundation::base::TCFType;
@@ -434,9 +433,8 @@ impl MetalRenderer {
     }
 
     /// Renders the scene to a texture and returns the pixel data as an RGBA image.
-    /// This does not present the frame to screen - useful for visual testing
-    /// where we want to capture what would be rendered without displaying it.
-    #[cfg(any(test, feature = "test-support"))]
+    /// This does not present the frame to screen - useful for screenshots and visual
+    /// testing, where we want to capture what would be rendered without displaying it.
     pub fn render_to_image(&mut self, scene: &Scene) -> Result<RgbaImage> {
         let layer = self.layer.clone();
         let viewport_size = layer.drawable_size();
@@ -444,8 +442,14 @@ impl MetalRenderer {
             (viewport_size.width.ceil() as i32).into(),
             (viewport_size.height.ceil() as i32).into(),
         );
-        let drawable = layer
-            .next_drawable()
+        // Outside of tests, windows keep framebuffer compression, so only the
+        // drawable used for a screenshot allows reading its texture.
+        #[cfg(not(any(test, feature = "test-support")))]
+        layer.set_framebuffer_only(false);
+        let drawable = layer.next_drawable();
+        #[cfg(not(any(test, feature = "test-support")))]
+        layer.set_framebuffer_only(true);
+        let drawable = drawable
             .ok_or_else(|| anyhow::anyhow!("Failed to get drawable for render_to_image"))?;
 
         loop {
@@ -9,7 +9,6 @@ use crate::{
     dispatch_get_main_queue, dispatch_sys::dispatch_async_f, platform::PlatformInputHandler, point,
     px, size,
 };
-#[cfg(any(test, feature = "test-support"))]
 use anyhow::Result;
 use block::ConcreteBlock;
 use cocoa::{
@@ -28,7 +27,6 @@ use cocoa::{
         NSUserDefaults,
     },
 };
-#[cfg(any(test, feature = "test-support"))]
 use image::RgbaImage;
 
 use core_graphics::display::{CGDirectDisplayID, CGPoint, CGRect};
@@ -1618,7 +1616,6 @@ impl PlatformWindow for MacWindow {
         }
     }
 
-    #[cfg(any(test, feature = "test-support"))]
     fn render_to_image(&self, scene: &crate::Scene) -> Result<RgbaImage> {
         let mut this = self.0.lock();
         this.renderer.render_to_image(scene)
@@ -29,6 +29,7 @@ pub(crate) struct TestWindowState {
     moved_callback: Option<Box<dyn FnMut()>>,
     input_handler: Option<PlatformInputHandler>,
     is_fullscreen: bool,
+    pub(crate) accessibility_updates: Vec<accesskit::TreeUpdate>,
 }
 
 #[derive(Clone)]
@@ -74,6 +75,7 @@ impl TestWindow {
             moved_callback: None,
             input_handler: None,
             is_fullscreen: false,
+            accessibility_updates: Vec::new(),
         })))
     }
 
@@ -276,6 +278,10 @@ impl PlatformWindow for TestWindow {
 
     fn draw(&self, _scene: &crate::Scene) {}
 
+    fn update_accessibility_tree(&self, build_update: &mut dyn FnMut() -> accesskit::TreeUpdate) {
+        self.0.lock().accessibility_updates.push(build_update());
+    }
+
     fn sprite_atlas(&self) -> sync::Arc<dyn crate::PlatformAtlas> {
         self.0.lock().sprite_atlas.clone()
     }
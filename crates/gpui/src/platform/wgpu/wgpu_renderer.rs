@@ -4,7 +4,9 @@ use crate::{
     PolychromeSprite, PrimitiveBatch, Quad, ScaledPixels, Scene, Shadow, Size, SubpixelSprite,
     Underline, get_gamma_correction_ratios,
 };
+use anyhow::Context as _;
 use bytemuck::{Pod, Zeroable};
+use image::RgbaImage;
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use std::num::NonZeroU64;
 use std::sync::Arc;
@@ -840,6 +842,107 @@ impl WgpuRenderer {
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
 
+        if let Some(encoder) = self.encode_scene(scene, &frame_view) {
+            self.queue.submit(std::iter::once(encoder.finish()));
+        }
+        frame.present();
+    }
+
+    /// Renders the scene to an offscreen texture the size of the window and
+    /// reads it back, without presenting it.
+    pub fn render_to_image(&mut self, scene: &Scene) -> anyhow::Result<RgbaImage> {
+        self.atlas.before_frame();
+
+        let width = self.surface_config.width;
+        let height = self.surface_config.height;
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("screenshot_texture"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.surface_config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = self
+            .encode_scene(scene, &view)
+            .context("failed to encode the scene")?;
+
+        let row_size = width * 4;
+        let padded_row_size = row_size.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("screenshot_buffer"),
+            size: u64::from(padded_row_size) * u64::from(height),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_size),
+                    rows_per_image: Some(height),
+                },
+            },
+            extent,
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            sender.send(result).ok();
+        });
+        self.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .context("failed to wait for the screenshot to render")?;
+        receiver
+            .recv()
+            .context("the screenshot buffer was dropped")?
+            .context("failed to read the screenshot buffer")?;
+
+        let mut pixels = Vec::with_capacity(row_size as usize * height as usize);
+        for row in slice
+            .get_mapped_range()
+            .chunks_exact(padded_row_size as usize)
+        {
+            pixels.extend_from_slice(&row[..row_size as usize]);
+        }
+        buffer.unmap();
+
+        if matches!(
+            self.surface_config.format,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        ) {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+        RgbaImage::from_raw(width, height, pixels)
+            .context("failed to create an image from the screenshot")
+    }
+
+    /// Encodes the commands drawing `scene` to `target`, or returns `None`
+    /// if the instance buffer would need to grow too large.
+    fn encode_scene(
+        &mut self,
+        scene: &Scene,
+        target: &wgpu::TextureView,
+    ) -> Option<wgpu::CommandEncoder> {
         let gamma_params = GammaParams {
             gamma_ratios: self.rendering_params.gamma_ratios,
             grayscale_enhanced_contrast: self.rendering_params.grayscale_enhanced_contrast,
@@ -894,7 +997,7 @@ impl WgpuRenderer {
                 let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                     label: Some("main_pass"),
                     color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &frame_view,
+                        view: target,
                         resolve_target: None,
                         ops: wgpu::Operations {
                             load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
@@ -933,7 +1036,7 @@ impl WgpuRenderer {
                             pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                                 label: Some("main_pass_continued"),
                                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                                    view: &frame_view,
+                                    view: target,
                                     resolve_target: None,
                                     ops: wgpu::Operations {
                                         load: wgpu::LoadOp::Load,
@@ -1001,16 +1104,13 @@ impl WgpuRenderer {
                         "instance buffer size grew too large: {}",
                         self.instance_buffer_capacity
                     );
-                    frame.present();
-                    return;
+                    return None;
                 }
                 self.grow_instance_buffer();
                 continue;
             }
 
-            self.queue.submit(std::iter::once(encoder.finish()));
-            frame.present();
-            return;
+            return Some(encoder);
         }
     }
 
@@ -1,23 +1,25 @@
 #[cfg(any(feature = "inspector", debug_assertions))]
 use crate::Inspector;
 use crate::{
-    Action, AnyDrag, AnyElement, AnyImageCache, AnyTooltip, AnyView, App, AppContext, Arena, Asset,
-    AsyncWindowContext, AvailableSpace, Background, BorderStyle, Bounds, BoxShadow, Capslock,
-    Context, Corners, CursorStyle, Decorations, DevicePixels, DispatchActionListener,
-    DispatchNodeId, DispatchTree, DisplayId, Edges, Effect, Entity, EntityId, EventEmitter,
-    FileDropEvent, FontId, Global, GlobalElementId, GlyphId, GpuSpecs, Hsla, InputHandler, IsZero,
-    KeyBinding, KeyContext, KeyDownEvent, KeyEvent, Keystroke, KeystrokeEvent, LayoutId,
-    LineLayoutIndex, Modifiers, ModifiersChangedEvent, MonochromeSprite, MouseButton, MouseEvent,
-    MouseMoveEvent, MouseUpEvent, Path, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
-    PlatformInputHandler, PlatformWindow, Point, PolychromeSprite, Priority, PromptButton,
-    PromptLevel, Quad, Render, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams,
-    Replay, ResizeEdge, SMOOTH_SVG_SCALE_FACTOR, SUBPIXEL_VARIANTS_X, SUBPIXEL_VARIANTS_Y,
-    ScaledPixels, Scene, Shadow, SharedString, Size, StrikethroughStyle, Style, SubpixelSprite,
-    SubscriberSet, Subscription, SystemWindowTab, SystemWindowTabController, TabStopMap,
-    TaffyLayoutEngine, Task, TextRenderingMode, TextStyle, TextStyleRefinement, ThermalState,
-    TransformationMatrix, Underline, UnderlineStyle, WindowAppearance, WindowBackgroundAppearance,
-    WindowBounds, WindowControls, WindowDecorations, WindowOptions, WindowParams, WindowTextSystem,
-    point, prelude::*, px, rems, size, transparent_black,
+    AccessibilityNodeRecord, Action, AnyDrag, AnyElement, AnyImageCache, AnyTooltip, AnyView, App,
+    AppContext, Arena, Asset, AsyncWindowContext, AvailableSpace, Background, BorderStyle, Bounds,
+    BoxShadow, Capslock, Context, Corners, CursorStyle, Decorations, DevicePixels,
+    DispatchActionListener, DispatchNodeId, DispatchTree, DisplayId, Edges, Effect, Entity,
+    EntityId, EventEmitter, FileDropEvent, FontId, Global, GlobalElementId, GlyphId, GpuSpecs,
+    Hsla, InputHandler, IsZero, KeyBinding, KeyContext, KeyDownEvent, KeyEvent, Keystroke,
+    KeystrokeEvent, LayoutId, LineLayoutIndex, Modifiers, ModifiersChangedEvent, MonochromeSprite,
+    MouseButton, MouseEvent, MouseMoveEvent, MouseUpEvent, Path, Pixels, PlatformAtlas,
+    PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow, Point, PolychromeSprite,
+    PresentationMode, Priority, PromptButton, PromptLevel, Quad, ROOT_ACCESSIBILITY_NODE_ID,
+    Render, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams, Replay, ResizeEdge,
+    SMOOTH_SVG_SCALE_FACTOR, SUBPIXEL_VARIANTS_X, SUBPIXEL_VARIANTS_Y, ScaledPixels, Scene, Shadow,
+    SharedString, Size, StrikethroughStyle, Style, SubpixelSprite, SubscriberSet, Subscription,
+    SystemWindowTab, SystemWindowTabController, TabStopMap, TaffyLayoutEngine, Task,
+    TextRenderingMode, TextStyle, TextStyleRefinement, ThermalState, TransformationMatrix,
+    Underline, UnderlineStyle, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    WindowControls, WindowDecorations, WindowOptions, WindowParams, WindowSnapLayout,
+    WindowTextSystem, accessibility_tree_update, point, prelude::*, px, rems, size,
+    transparent_black,
 };
 use anyhow::{Context as _, Result, anyhow};
 use collections::{FxHashMap, FxHashSet};
@@ -54,9 +56,11 @@ use util::post_inc;
 use util::{ResultExt, measure};
 use uuid::Uuid;
 
+mod input_latency;
 mod prompts;
 
 use crate::util::atomic_incr_if_not_zero;
+pub use input_latency::*;
 pub use prompts::*;
 
 pub(crate) const DEFAULT_WINDOW_SIZE: Size<Pixels> = size(px(1536.), px(864.));
@@ -745,6 +749,7 @@ pub(crate) struct Frame {
     pub(crate) input_handlers: Vec<Option<PlatformInputHandler>>,
     pub(crate) tooltip_requests: Vec<Option<TooltipRequest>>,
     pub(crate) cursor_styles: Vec<CursorStyleRequest>,
+    pub(crate) accessibility_nodes: Vec<AccessibilityNodeRecord>,
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) debug_bounds: FxHashMap<String, Bounds<Pixels>>,
     #[cfg(any(feature = "inspector", debug_assertions))]
@@ -770,6 +775,7 @@ pub(crate) struct PaintIndex {
     mouse_listeners_index: usize,
     input_handlers_index: usize,
     cursor_styles_index: usize,
+    accessibility_nodes_index: usize,
     accessed_element_states_index: usize,
     tab_handle_index: usize,
     line_layout_index: LineLayoutIndex,
@@ -791,6 +797,7 @@ impl Frame {
             input_handlers: Vec::new(),
             tooltip_requests: Vec::new(),
             cursor_styles: Vec::new(),
+            accessibility_nodes: Vec::new(),
 
             #[cfg(any(test, feature = "test-support"))]
             debug_bounds: FxHashMap::default(),
@@ -813,6 +820,7 @@ impl Frame {
         self.input_handlers.clear();
         self.tooltip_requests.clear();
         self.cursor_styles.clear();
+        self.accessibility_nodes.clear();
         self.hitboxes.clear();
         self.window_control_hitboxes.clear();
         self.deferred_draws.clear();
@@ -898,6 +906,14 @@ pub struct Window {
     sprite_atlas: Arc<dyn PlatformAtlas>,
     text_system: Arc<WindowTextSystem>,
     text_rendering_mode: Rc<Cell<TextRenderingMode>>,
+    presentation_mode: Rc<Cell<PresentationMode>>,
+    /// Only tracked while something displays it, since measuring presentation
+    /// makes the compositor send an event for every frame.
+    input_latency: Rc<RefCell<Option<InputLatencyTracker>>>,
+    /// Whether a frame was drawn in response to input since the platform last
+    /// asked for one, in [`PresentationMode::LowLatency`].
+    drew_since_frame_request: Rc<Cell<bool>>,
+    low_latency_frame: Option<Task<()>>,
     rem_size: Pixels,
     /// The stack of override values for the window's rem size.
     ///
@@ -909,6 +925,7 @@ pub struct Window {
     pub(crate) root: Option<AnyView>,
     pub(crate) element_id_stack: SmallVec<[ElementId; 32]>,
     pub(crate) text_style_stack: Vec<TextStyleRefinement>,
+    accessibility_parent_stack: Vec<accesskit::NodeId>,
     pub(crate) rendered_entity_stack: Vec<EntityId>,
     pub(crate) element_offset_stack: Vec<Point<Pixels>>,
     pub(crate) element_opacity: f32,
@@ -1155,6 +1172,8 @@ impl Window {
         let needs_present = Rc::new(Cell::new(false));
         let next_frame_callbacks: Rc<RefCell<Vec<FrameCallback>>> = Default::default();
         let input_rate_tracker = Rc::new(RefCell::new(InputRateTracker::default()));
+        let input_latency = Rc::new(RefCell::new(None));
+        let drew_since_frame_request = Rc::new(Cell::new(false));
         let last_frame_time = Rc::new(Cell::new(None));
 
         platform_window
@@ -1184,7 +1203,10 @@ impl Window {
             let needs_present = needs_present.clone();
             let next_frame_callbacks = next_frame_callbacks.clone();
             let input_rate_tracker = input_rate_tracker.clone();
+            let drew_since_frame_request = drew_since_frame_request.clone();
             move |request_frame_options| {
+                drew_since_frame_request.set(false);
+
                 let thermal_state = cx.update(|cx| cx.thermal_state());
 
                 if thermal_state == ThermalState::Serious || thermal_state == ThermalState::Critical
@@ -1388,6 +1410,10 @@ impl Window {
             sprite_atlas,
             text_system,
             text_rendering_mode: cx.text_rendering_mode.clone(),
+            presentation_mode: cx.presentation_mode.clone(),
+            input_latency,
+            drew_since_frame_request,
+            low_latency_frame: None,
             rem_size: px(16.),
             rem_size_override_stack: SmallVec::new(),
             viewport_size: content_size,
@@ -1395,6 +1421,7 @@ impl Window {
             root: None,
             element_id_stack: SmallVec::default(),
             text_style_stack: Vec::new(),
+            accessibility_parent_stack: Vec::new(),
             rendered_entity_stack: Vec::new(),
             element_offset_stack: Vec::new(),
             content_mask_stack: Vec::new(),
@@ -1901,12 +1928,22 @@ impl Window {
     /// Renders the current frame's scene to a texture and returns the pixel data as an RGBA image.
     /// This does not present the frame to screen - useful for visual testing where we want
     /// to capture what would be rendered without displaying it or requiring the window to be visible.
-    #[cfg(any(test, feature = "test-support"))]
     pub fn render_to_image(&self) -> anyhow::Result<image::RgbaImage> {
         self.platform_window
             .render_to_image(&self.rendered_frame.scene)
     }
 
+    /// Captures the contents of the window as of the last frame, at device resolution.
+    pub fn capture_screenshot(&self) -> anyhow::Result<image::RgbaImage> {
+        self.render_to_image()
+    }
+
+    /// Captures the part of the window within `bounds`, such as the bounds of an element
+    /// recorded while it was painted, at device resolution.
+    pub fn capture_element(&self, bounds: Bounds<Pixels>) -> anyhow::Result<image::RgbaImage> {
+        crop_screenshot(&self.capture_screenshot()?, bounds, self.scale_factor())
+    }
+
     /// Set the content size of the window.
     pub fn resize(&mut self, size: Size<Pixels>) {
         self.platform_window.resize(size);
@@ -1965,6 +2002,39 @@ impl Window {
         self.platform_window.show_window_menu(position)
     }
 
+    /// Moves the window to the bottom of the stacking order, behind other windows (X11)
+    pub fn lower_window(&self) {
+        self.platform_window.lower()
+    }
+
+    /// Sets whether the window stays above all other windows, if the platform supports it (X11)
+    ///
+    /// Wayland has no protocol for clients to do this, so it's left to the compositor there.
+    /// Check [`WindowControls::keep_above`] to find out whether this is supported.
+    pub fn set_keep_above(&self, keep_above: bool) {
+        self.platform_window.set_keep_above(keep_above)
+    }
+
+    /// Returns whether the window is being kept above all other windows (X11)
+    pub fn is_keep_above(&self) -> bool {
+        self.platform_window.is_keep_above()
+    }
+
+    /// Asks the window manager to draw the user's attention to this window, typically by
+    /// flashing its taskbar entry, unless it is already active (X11 and Wayland)
+    pub fn request_attention(&self) {
+        if !self.is_window_active() {
+            self.platform_window.request_attention()
+        }
+    }
+
+    /// Moves and resizes the window to cover part of its display, if the platform supports it (X11)
+    ///
+    /// Check [`WindowControls::snap`] to find out whether this is supported.
+    pub fn snap_window(&self, layout: WindowSnapLayout) {
+        self.platform_window.snap(layout)
+    }
+
     /// Handle window movement for Linux and macOS.
     /// Tells the compositor to take control of window movement (Wayland and X11)
     ///
@@ -2155,6 +2225,61 @@ impl Window {
 
     fn complete_frame(&self) {
         self.platform_window.completed_frame();
+        if let Some(input_latency) = self.input_latency.borrow_mut().as_mut() {
+            input_latency.frame_completed(Instant::now());
+        }
+    }
+
+    /// Starts or stops measuring how long input takes to show up on screen,
+    /// discarding any earlier measurements.
+    pub fn set_measures_input_latency(&mut self, measures: bool) {
+        if !measures {
+            self.platform_window.on_frame_presented(None);
+            self.input_latency.replace(None);
+            return;
+        }
+        self.input_latency.replace(Some(InputLatencyTracker::new(
+            self.platform_window.reports_frame_presentation(),
+        )));
+        self.platform_window.on_frame_presented(Some(Box::new({
+            let input_latency = self.input_latency.clone();
+            move |presented_at| {
+                if let Some(input_latency) = input_latency.borrow_mut().as_mut() {
+                    input_latency.frame_presented(presented_at);
+                }
+            }
+        })));
+    }
+
+    /// Percentiles of how long recent input took to show up on screen, or
+    /// `None` before any input was drawn or while it isn't measured.
+    pub fn input_latency(&self) -> Option<InputLatencyStats> {
+        self.input_latency.borrow().as_ref()?.stats()
+    }
+
+    /// In [`PresentationMode::LowLatency`], draws the window once the input
+    /// events that are currently queued were handled, without waiting for the
+    /// platform to ask for a frame.
+    fn schedule_low_latency_frame(&mut self, cx: &App) {
+        if self.low_latency_frame.is_some() {
+            return;
+        }
+        self.low_latency_frame = Some(self.spawn(cx, async move |cx| {
+            cx.update(|window, cx| {
+                window.low_latency_frame = None;
+                // Drawing more than once between frame requests could block
+                // on the swap chain, adding the latency this mode avoids.
+                if !window.invalidator.is_dirty() || window.drew_since_frame_request.get() {
+                    return;
+                }
+                let arena_clear_needed = window.draw(cx);
+                window.present();
+                arena_clear_needed.clear();
+                window.complete_frame();
+                window.drew_since_frame_request.set(true);
+            })
+            .log_err();
+        }));
     }
 
     /// Produces a new frame and assigns it to `rendered_frame`. To actually show
@@ -2169,6 +2294,9 @@ impl Window {
         cx.entities.clear_accessed();
         debug_assert!(self.rendered_entity_stack.is_empty());
         self.invalidator.set_dirty(false);
+        if let Some(input_latency) = self.input_latency.borrow_mut().as_mut() {
+            input_latency.frame_drawn();
+        }
         self.requested_autoscroll = None;
 
         // Restore the previously-used input handler.
@@ -2195,6 +2323,16 @@ impl Window {
         let previous_focus_path = self.rendered_frame.focus_path();
         let previous_window_active = self.rendered_frame.window_active;
         mem::swap(&mut self.rendered_frame, &mut self.next_frame);
+        if self.rendered_frame.accessibility_nodes != self.next_frame.accessibility_nodes
+            || self.rendered_frame.focus != self.next_frame.focus
+        {
+            let focus = self.rendered_frame.focus;
+            let scale_factor = self.scale_factor();
+            let accessibility_nodes = &self.rendered_frame.accessibility_nodes;
+            self.platform_window.update_accessibility_tree(&mut || {
+                accessibility_tree_update(accessibility_nodes, focus, scale_factor)
+            });
+        }
         self.next_frame.clear();
         let current_focus_path = self.rendered_frame.focus_path();
         let current_window_active = self.rendered_frame.window_active;
@@ -2547,6 +2685,7 @@ impl Window {
             mouse_listeners_index: self.next_frame.mouse_listeners.len(),
             input_handlers_index: self.next_frame.input_handlers.len(),
             cursor_styles_index: self.next_frame.cursor_styles.len(),
+            accessibility_nodes_index: self.next_frame.accessibility_nodes.len(),
             accessed_element_states_index: self.next_frame.accessed_element_states.len(),
             tab_handle_index: self.next_frame.tab_stops.paint_index(),
             line_layout_index: self.text_system.layout_index(),
@@ -2560,6 +2699,23 @@ impl Window {
                 .iter()
                 .cloned(),
         );
+        // Nodes that were children of the reused range's parent node become
+        // children of the node that now encloses the range.
+        let accessibility_nodes = &self.rendered_frame.accessibility_nodes
+            [range.start.accessibility_nodes_index..range.end.accessibility_nodes_index];
+        if let Some(first_node) = accessibility_nodes.first() {
+            let previous_parent = first_node.parent;
+            let parent = self.accessibility_parent();
+            self.next_frame
+                .accessibility_nodes
+                .extend(accessibility_nodes.iter().map(|record| {
+                    let mut record = record.clone();
+                    if record.parent == previous_parent {
+                        record.parent = parent;
+                    }
+                    record
+                }));
+        }
         self.next_frame.input_handlers.extend(
             self.rendered_frame.input_handlers
                 [range.start.input_handlers_index..range.end.input_handlers_index]
@@ -2645,6 +2801,34 @@ impl Window {
         id
     }
 
+    /// Inserts `node` into the window's accessibility tree as a child of the
+    /// enclosing element's node, and calls `f` with it as the parent of the
+    /// nodes inserted from within. This method should only be called as part
+    /// of the paint phase of element drawing.
+    pub fn with_accessibility_node<R>(
+        &mut self,
+        id: accesskit::NodeId,
+        node: accesskit::Node,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.invalidator.debug_assert_paint();
+        let parent = self.accessibility_parent();
+        self.next_frame
+            .accessibility_nodes
+            .push(AccessibilityNodeRecord { id, parent, node });
+        self.accessibility_parent_stack.push(id);
+        let result = f(self);
+        self.accessibility_parent_stack.pop();
+        result
+    }
+
+    fn accessibility_parent(&self) -> accesskit::NodeId {
+        self.accessibility_parent_stack
+            .last()
+            .copied()
+            .unwrap_or(ROOT_ACCESSIBILITY_NODE_ID)
+    }
+
     /// Invoke the given function with the given content mask after intersecting it
     /// with the current mask. This method should only be called during element drawing.
     // This function is called in a highly recursive manner in editor
@@ -3878,6 +4062,12 @@ impl Window {
     /// Dispatch a mouse or keyboard event on the window.
     #[profiling::function]
     pub fn dispatch_event(&mut self, event: PlatformInput, cx: &mut App) -> DispatchEventResult {
+        let received_at = Instant::now();
+        // Typed text is inserted through the input handler once the event was
+        // dispatched, so key presses count as input even when they didn't
+        // invalidate the window yet.
+        let is_key_down = matches!(event, PlatformInput::KeyDown(_));
+
         // Track whether this input was keyboard-based for focus-visible styling
         self.last_input_modality = match &event {
             PlatformInput::KeyDown(_) | PlatformInput::ModifiersChanged(_) => {
@@ -3927,6 +4117,16 @@ impl Window {
                 self.modifiers = scroll_wheel.modifiers;
                 PlatformInput::ScrollWheel(scroll_wheel)
             }
+            PlatformInput::Pinch(pinch) => {
+                self.mouse_position = pinch.position;
+                self.modifiers = pinch.modifiers;
+                PlatformInput::Pinch(pinch)
+            }
+            PlatformInput::Swipe(swipe) => {
+                self.mouse_position = swipe.position;
+                self.modifiers = swipe.modifiers;
+                PlatformInput::Swipe(swipe)
+            }
             // Translate dragging and dropping of external files from the operating system
             // to internal drag and drop events.
             PlatformInput::FileDrop(file_drop) => match file_drop {
@@ -3981,6 +4181,16 @@ impl Window {
         if self.invalidator.is_dirty() {
             self.input_rate_tracker.borrow_mut().record_input();
         }
+        if (self.invalidator.is_dirty() || is_key_down)
+            && let Some(input_latency) = self.input_latency.borrow_mut().as_mut()
+        {
+            input_latency.input_received(received_at);
+        }
+        // Views notified by the handlers only invalidate the window once the
+        // effects are flushed, so the scheduled frame checks whether to draw.
+        if self.presentation_mode.get() == PresentationMode::LowLatency {
+            self.schedule_low_latency_frame(cx);
+        }
 
         DispatchEventResult {
             propagate: cx.propagate_event,
@@ -5494,3 +5704,84 @@ pub fn outline(
         border_style,
     }
 }
+
+fn crop_screenshot(
+    screenshot: &image::RgbaImage,
+    bounds: Bounds<Pixels>,
+    scale_factor: f32,
+) -> Result<image::RgbaImage> {
+    let screenshot_bounds = Bounds::new(
+        Point::default(),
+        size(
+            DevicePixels::from(screenshot.width() as i32),
+            DevicePixels::from(screenshot.height() as i32),
+        ),
+    );
+    let scaled_bounds = bounds.scale(scale_factor);
+    let element_bounds = Bounds::from_corners(
+        point(
+            DevicePixels::from(scaled_bounds.origin.x.0.floor() as i32),
+            DevicePixels::from(scaled_bounds.origin.y.0.floor() as i32),
+        ),
+        point(
+            DevicePixels::from(scaled_bounds.bottom_right().x.0.ceil() as i32),
+            DevicePixels::from(scaled_bounds.bottom_right().y.0.ceil() as i32),
+        ),
+    )
+    .intersect(&screenshot_bounds);
+    anyhow::ensure!(
+        !element_bounds.is_empty(),
+        "the element is outside of the window"
+    );
+    Ok(image::imageops::crop_imm(
+        screenshot,
+        element_bounds.origin.x.0 as u32,
+        element_bounds.origin.y.0 as u32,
+        element_bounds.size.width.0 as u32,
+        element_bounds.size.height.0 as u32,
+    )
+    .to_image())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Image;
+
+    #[test]
+    fn test_crop_screenshot() {
+        let screenshot =
+            image::RgbaImage::from_fn(200, 100, |x, y| image::Rgba([x as u8, y as u8, 0, 255]));
+
+        let cropped = crop_screenshot(
+            &screenshot,
+            Bounds::new(point(px(10.5), px(20.)), size(px(30.), px(10.))),
+            2.,
+        )
+        .unwrap();
+        assert_eq!(cropped.dimensions(), (60, 20));
+        assert_eq!(cropped.get_pixel(0, 0), &image::Rgba([21, 40, 0, 255]));
+
+        let clipped = crop_screenshot(
+            &screenshot,
+            Bounds::new(point(px(90.), px(40.)), size(px(150.), px(80.))),
+            1.,
+        )
+        .unwrap();
+        assert_eq!(clipped.dimensions(), (110, 60));
+
+        assert!(
+            crop_screenshot(
+                &screenshot,
+                Bounds::new(point(px(300.), px(0.)), size(px(10.), px(10.))),
+                1.,
+            )
+            .is_err()
+        );
+
+        let png = Image::from_rgba(&cropped).unwrap();
+        assert_eq!(png.format, crate::ImageFormat::Png);
+        let decoded = image::load_from_memory(&png.bytes).unwrap().into_rgba8();
+        assert_eq!(decoded, cropped);
+    }
+}
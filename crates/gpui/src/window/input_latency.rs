@@ -0,0 +1,156 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// How many of the most recent latency measurements the statistics cover.
+const MAX_SAMPLES: usize = 500;
+
+/// How many completed frames may await a presentation time. Platforms report
+/// presentation shortly after each frame, so a longer queue means reports
+/// stopped arriving.
+const MAX_FRAMES_AWAITING_PRESENTATION: usize = 16;
+
+/// Percentiles of how long input took to show up on screen, measured from
+/// when a window received an input event to when the first frame drawn after
+/// it was presented, or submitted on platforms that don't report presentation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InputLatencyStats {
+    /// The median latency.
+    pub p50: Duration,
+    /// The latency that 90% of the measurements were below.
+    pub p90: Duration,
+    /// The latency that 99% of the measurements were below.
+    pub p99: Duration,
+    /// How many measurements the percentiles were computed from.
+    pub sample_count: usize,
+}
+
+#[derive(Default)]
+pub(crate) struct InputLatencyTracker {
+    reports_presentation: bool,
+    /// When the earliest input not yet drawn was received.
+    pending_input: Option<Instant>,
+    /// When the earliest input included in the frame being drawn was received.
+    drawn_input: Option<Instant>,
+    /// For each completed frame not yet presented, when the earliest input it
+    /// included was received.
+    awaiting_presentation: VecDeque<Option<Instant>>,
+    samples: VecDeque<Duration>,
+}
+
+impl InputLatencyTracker {
+    pub fn new(reports_presentation: bool) -> Self {
+        Self {
+            reports_presentation,
+            ..Default::default()
+        }
+    }
+
+    pub fn input_received(&mut self, received_at: Instant) {
+        self.pending_input.get_or_insert(received_at);
+    }
+
+    pub fn frame_drawn(&mut self) {
+        if let Some(pending_input) = self.pending_input.take() {
+            self.drawn_input.get_or_insert(pending_input);
+        }
+    }
+
+    pub fn frame_completed(&mut self, completed_at: Instant) {
+        let drawn_input = self.drawn_input.take();
+        if self.reports_presentation {
+            if self.awaiting_presentation.len() == MAX_FRAMES_AWAITING_PRESENTATION {
+                self.awaiting_presentation.pop_front();
+            }
+            self.awaiting_presentation.push_back(drawn_input);
+        } else if let Some(drawn_input) = drawn_input {
+            self.record(completed_at.saturating_duration_since(drawn_input));
+        }
+    }
+
+    pub fn frame_presented(&mut self, presented_at: Option<Instant>) {
+        if let Some(Some(drawn_input)) = self.awaiting_presentation.pop_front()
+            && let Some(presented_at) = presented_at
+        {
+            self.record(presented_at.saturating_duration_since(drawn_input));
+        }
+    }
+
+    pub fn stats(&self) -> Option<InputLatencyStats> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut samples = self.samples.iter().copied().collect::<Vec<_>>();
+        samples.sort_unstable();
+        let percentile = |percent: usize| samples[(samples.len() - 1) * percent / 100];
+        Some(InputLatencyStats {
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            sample_count: samples.len(),
+        })
+    }
+
+    fn record(&mut self, latency: Duration) {
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_is_measured_to_presentation() {
+        let start = Instant::now();
+        let mut tracker = InputLatencyTracker::new(true);
+
+        tracker.input_received(start);
+        tracker.input_received(start + Duration::from_millis(2));
+        tracker.frame_drawn();
+        tracker.frame_completed(start + Duration::from_millis(5));
+        // A frame without new input doesn't produce a measurement.
+        tracker.frame_drawn();
+        tracker.frame_completed(start + Duration::from_millis(20));
+        assert_eq!(tracker.stats(), None);
+
+        tracker.frame_presented(Some(start + Duration::from_millis(16)));
+        tracker.frame_presented(Some(start + Duration::from_millis(33)));
+        let stats = tracker.stats().expect("the first frame carried input");
+        assert_eq!(stats.sample_count, 1);
+        assert_eq!(stats.p50, Duration::from_millis(16));
+    }
+
+    #[test]
+    fn test_discarded_frames_are_skipped() {
+        let start = Instant::now();
+        let mut tracker = InputLatencyTracker::new(true);
+
+        tracker.input_received(start);
+        tracker.frame_drawn();
+        tracker.frame_completed(start);
+        tracker.frame_presented(None);
+        assert_eq!(tracker.stats(), None);
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let start = Instant::now();
+        let mut tracker = InputLatencyTracker::new(false);
+        for latency in 1..=100 {
+            tracker.input_received(start);
+            tracker.frame_drawn();
+            tracker.frame_completed(start + Duration::from_millis(latency));
+        }
+
+        let stats = tracker.stats().expect("every frame carried input");
+        assert_eq!(stats.sample_count, 100);
+        assert_eq!(stats.p50, Duration::from_millis(50));
+        assert_eq!(stats.p90, Duration::from_millis(90));
+        assert_eq!(stats.p99, Duration::from_millis(99));
+    }
+}
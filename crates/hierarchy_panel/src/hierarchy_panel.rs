@@ -0,0 +1,1000 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{
+    Bias, Editor, SelectionEffects, ShowCallHierarchy, ShowTypeHierarchy, scroll::Autoscroll,
+};
+use gpui::{
+    Action, App, AsyncWindowContext, ClickEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    Pixels, Task, WeakEntity, actions, px,
+};
+use language::Buffer;
+use menu::{Confirm, SelectNext, SelectPrevious};
+use project::{Fs, HierarchyItem, Project, lsp_store::SymbolLocation};
+use serde::{Deserialize, Serialize};
+use settings::{RegisterSetting, Settings};
+use ui::{CommonAnimationExt, IconButton, IconName, Label, ListItem, Tab, Tooltip, prelude::*};
+use util::{ResultExt, TryFutureExt, post_inc};
+use workspace::{
+    Toast, Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::NotificationId,
+};
+
+const HIERARCHY_PANEL_KEY: &str = "HierarchyPanel";
+
+actions!(
+    hierarchy_panel,
+    [
+        /// Toggles focus on the hierarchy panel.
+        ToggleFocus,
+        /// Switches the hierarchy between the callers and the callees of its
+        /// root, or between its supertypes and subtypes.
+        ToggleHierarchyDirection,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, window, cx| {
+                workspace.toggle_panel_focus::<HierarchyPanel>(window, cx);
+            })
+            .register_action(|workspace, _: &ShowCallHierarchy, window, cx| {
+                show_hierarchy_at_cursor(workspace, HierarchyKind::Call, window, cx);
+            })
+            .register_action(|workspace, _: &ShowTypeHierarchy, window, cx| {
+                show_hierarchy_at_cursor(workspace, HierarchyKind::Type, window, cx);
+            });
+    })
+    .detach();
+}
+
+fn show_hierarchy_at_cursor(
+    workspace: &mut Workspace,
+    kind: HierarchyKind,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some((buffer, position)) = editor.update(cx, |editor, cx| {
+        let head = editor.selections.newest_anchor().head();
+        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    }) else {
+        return;
+    };
+    let items = workspace.project().update(cx, |project, cx| match kind {
+        HierarchyKind::Call => project.prepare_call_hierarchy(buffer.clone(), position, cx),
+        HierarchyKind::Type => project.prepare_type_hierarchy(buffer.clone(), position, cx),
+    });
+    cx.spawn_in(window, async move |workspace, cx| {
+        let items = items.await?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            let Some(root) = items.into_iter().next() else {
+                let message = match kind {
+                    HierarchyKind::Call => "No call hierarchy for the symbol at the cursor",
+                    HierarchyKind::Type => "No type hierarchy for the symbol at the cursor",
+                };
+                workspace.show_toast(
+                    Toast::new(NotificationId::unique::<HierarchyPanel>(), message),
+                    cx,
+                );
+                return;
+            };
+            if let Some(panel) = workspace.focus_panel::<HierarchyPanel>(window, cx) {
+                panel.update(cx, |panel, cx| {
+                    let direction = panel
+                        .hierarchy
+                        .as_ref()
+                        .map(|hierarchy| hierarchy.direction)
+                        .filter(|direction| direction.kind() == kind)
+                        .unwrap_or(match kind {
+                            HierarchyKind::Call => HierarchyDirection::IncomingCalls,
+                            HierarchyKind::Type => HierarchyDirection::Supertypes,
+                        });
+                    panel.show_hierarchy(buffer, root, direction, cx);
+                });
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+#[derive(Debug, RegisterSetting)]
+pub struct HierarchyPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+impl Settings for HierarchyPanelSettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let panel = content.hierarchy_panel.as_ref().unwrap();
+        Self {
+            button: panel.button.unwrap(),
+            dock: panel.dock.unwrap().into(),
+            default_width: panel.default_width.map(px).unwrap(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedHierarchyPanel {
+    width: Option<Pixels>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HierarchyKind {
+    Call,
+    Type,
+}
+
+/// What the tree lists under each item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HierarchyDirection {
+    IncomingCalls,
+    OutgoingCalls,
+    Supertypes,
+    Subtypes,
+}
+
+impl HierarchyDirection {
+    fn kind(self) -> HierarchyKind {
+        match self {
+            Self::IncomingCalls | Self::OutgoingCalls => HierarchyKind::Call,
+            Self::Supertypes | Self::Subtypes => HierarchyKind::Type,
+        }
+    }
+
+    fn reversed(self) -> Self {
+        match self {
+            Self::IncomingCalls => Self::OutgoingCalls,
+            Self::OutgoingCalls => Self::IncomingCalls,
+            Self::Supertypes => Self::Subtypes,
+            Self::Subtypes => Self::Supertypes,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::IncomingCalls => "Incoming Calls",
+            Self::OutgoingCalls => "Outgoing Calls",
+            Self::Supertypes => "Supertypes",
+            Self::Subtypes => "Subtypes",
+        }
+    }
+}
+
+enum EntryState {
+    Collapsed,
+    Loading(Task<()>),
+    Expanded,
+}
+
+struct HierarchyEntry {
+    id: usize,
+    item: HierarchyItem,
+    depth: usize,
+    state: EntryState,
+}
+
+/// The tree shown in the panel, flattened in display order. The children of
+/// an entry are only fetched when it's expanded.
+struct Hierarchy {
+    /// A buffer the language server that reported the items is open in, which
+    /// the requests for more of them go through.
+    buffer: Entity<Buffer>,
+    direction: HierarchyDirection,
+    entries: Vec<HierarchyEntry>,
+}
+
+pub struct HierarchyPanel {
+    fs: Arc<dyn Fs>,
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    hierarchy: Option<Hierarchy>,
+    selected_entry: Option<usize>,
+    next_entry_id: usize,
+    pending_serialization: Task<Option<()>>,
+}
+
+impl HierarchyPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        let serialized_panel = cx
+            .background_spawn(async move { KEY_VALUE_STORE.read_kvp(HIERARCHY_PANEL_KEY) })
+            .await
+            .context("loading hierarchy panel")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedHierarchyPanel>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        workspace.update(&mut cx, |workspace, cx| {
+            let panel = Self::new(workspace, cx);
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|width| width.round());
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let project = workspace.project().clone();
+        let workspace_handle = workspace.weak_handle();
+        cx.new(|cx| Self {
+            fs,
+            workspace: workspace_handle,
+            project,
+            focus_handle: cx.focus_handle(),
+            width: None,
+            hierarchy: None,
+            selected_entry: None,
+            next_entry_id: 0,
+            pending_serialization: Task::ready(None),
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        HIERARCHY_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedHierarchyPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn show_hierarchy(
+        &mut self,
+        buffer: Entity<Buffer>,
+        root: HierarchyItem,
+        direction: HierarchyDirection,
+        cx: &mut Context<Self>,
+    ) {
+        let root = HierarchyEntry {
+            id: post_inc(&mut self.next_entry_id),
+            item: root,
+            depth: 0,
+            state: EntryState::Collapsed,
+        };
+        self.hierarchy = Some(Hierarchy {
+            buffer,
+            direction,
+            entries: vec![root],
+        });
+        self.selected_entry = Some(0);
+        self.toggle_entry(0, cx);
+    }
+
+    fn toggle_direction(
+        &mut self,
+        _: &ToggleHierarchyDirection,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(hierarchy) = &self.hierarchy else {
+            return;
+        };
+        let Some(root) = hierarchy.entries.first() else {
+            return;
+        };
+        let buffer = hierarchy.buffer.clone();
+        let root = root.item.clone();
+        let direction = hierarchy.direction.reversed();
+        self.show_hierarchy(buffer, root, direction, cx);
+    }
+
+    fn reroot(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(hierarchy) = &self.hierarchy else {
+            return;
+        };
+        let Some(entry) = hierarchy.entries.get(index) else {
+            return;
+        };
+        let buffer = hierarchy.buffer.clone();
+        let root = entry.item.clone();
+        let direction = hierarchy.direction;
+        self.show_hierarchy(buffer, root, direction, cx);
+    }
+
+    fn toggle_entry(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(hierarchy) = self.hierarchy.as_mut() else {
+            return;
+        };
+        let direction = hierarchy.direction;
+        let buffer = hierarchy.buffer.clone();
+        let Some(entry) = hierarchy.entries.get_mut(index) else {
+            return;
+        };
+        match entry.state {
+            EntryState::Loading(_) => {}
+            EntryState::Expanded => {
+                entry.state = EntryState::Collapsed;
+                let depth = entry.depth;
+                let descendant_count = hierarchy.entries[index + 1..]
+                    .iter()
+                    .take_while(|descendant| descendant.depth > depth)
+                    .count();
+                hierarchy
+                    .entries
+                    .drain(index + 1..index + 1 + descendant_count);
+                if let Some(selected_entry) = self.selected_entry.as_mut()
+                    && *selected_entry > index
+                {
+                    *selected_entry = selected_entry.saturating_sub(descendant_count).max(index);
+                }
+            }
+            EntryState::Collapsed => {
+                let entry_id = entry.id;
+                let item = entry.item.clone();
+                let children = self.project.update(cx, |project, cx| match direction {
+                    HierarchyDirection::IncomingCalls => project.incoming_calls(buffer, item, cx),
+                    HierarchyDirection::OutgoingCalls => project.outgoing_calls(buffer, item, cx),
+                    HierarchyDirection::Supertypes => project.supertypes(buffer, item, cx),
+                    HierarchyDirection::Subtypes => project.subtypes(buffer, item, cx),
+                });
+                entry.state = EntryState::Loading(cx.spawn(async move |this, cx| {
+                    let children = children.await.log_err().unwrap_or_default();
+                    this.update(cx, |this, cx| this.insert_children(entry_id, children, cx))
+                        .ok();
+                }));
+            }
+        }
+        cx.notify();
+    }
+
+    fn insert_children(
+        &mut self,
+        entry_id: usize,
+        children: Vec<HierarchyItem>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(hierarchy) = self.hierarchy.as_mut() else {
+            return;
+        };
+        let Some(index) = hierarchy
+            .entries
+            .iter()
+            .position(|entry| entry.id == entry_id)
+        else {
+            return;
+        };
+        let entry = &mut hierarchy.entries[index];
+        entry.state = EntryState::Expanded;
+        let depth = entry.depth + 1;
+        let children = children
+            .into_iter()
+            .map(|item| HierarchyEntry {
+                id: post_inc(&mut self.next_entry_id),
+                item,
+                depth,
+                state: EntryState::Collapsed,
+            })
+            .collect::<Vec<_>>();
+        let child_count = children.len();
+        hierarchy.entries.splice(index + 1..index + 1, children);
+        if let Some(selected_entry) = self.selected_entry.as_mut()
+            && *selected_entry > index
+        {
+            *selected_entry += child_count;
+        }
+        cx.notify();
+    }
+
+    fn open_entry(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(symbol) = self
+            .hierarchy
+            .as_ref()
+            .and_then(|hierarchy| hierarchy.entries.get(index))
+            .map(|entry| entry.item.symbol.clone())
+        else {
+            return;
+        };
+        let buffer = self.project.update(cx, |project, cx| {
+            project.open_buffer_for_symbol(&symbol, cx)
+        });
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let buffer = buffer.await?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                let position = buffer
+                    .read(cx)
+                    .clip_point_utf16(symbol.range.start, Bias::Left);
+                let pane = workspace.active_pane().clone();
+                let editor = workspace
+                    .open_project_item::<Editor>(pane, buffer, true, true, true, true, window, cx);
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(
+                        SelectionEffects::scroll(Autoscroll::center()),
+                        window,
+                        cx,
+                        |selections| selections.select_ranges([position..position]),
+                    );
+                });
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let entry_count = self
+            .hierarchy
+            .as_ref()
+            .map_or(0, |hierarchy| hierarchy.entries.len());
+        if entry_count > 0 {
+            self.selected_entry = Some(self.selected_entry.map_or(0, |selected_entry| {
+                (selected_entry + 1).min(entry_count - 1)
+            }));
+            cx.notify();
+        }
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_entry) = self.selected_entry.as_mut() {
+            *selected_entry = selected_entry.saturating_sub(1);
+            cx.notify();
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_entry) = self.selected_entry {
+            self.open_entry(selected_entry, window, cx);
+        }
+    }
+
+    fn render_entry(
+        &self,
+        index: usize,
+        entry: &HierarchyEntry,
+        has_children: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let symbol = &entry.item.symbol;
+        let file_name = match &symbol.path {
+            SymbolLocation::InProject(project_path) => {
+                project_path.path.file_name().map(str::to_string)
+            }
+            SymbolLocation::OutsideProject { abs_path, .. } => abs_path
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy().into_owned()),
+        }
+        .unwrap_or_default();
+        let location = format!("{file_name}:{}", symbol.range.start.0.row + 1);
+        let toggle = match entry.state {
+            EntryState::Collapsed => Some(false),
+            EntryState::Loading(_) => Some(true),
+            EntryState::Expanded => has_children.then_some(true),
+        };
+        let end_slot = if let EntryState::Loading(_) = entry.state {
+            Icon::new(IconName::ArrowCircle)
+                .size(IconSize::Small)
+                .color(Color::Muted)
+                .with_rotate_animation(2)
+                .into_any_element()
+        } else {
+            Label::new(location)
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+                .into_any_element()
+        };
+
+        ListItem::new(("hierarchy-entry", entry.id))
+            .indent_level(entry.depth)
+            .indent_step_size(px(12.))
+            .toggle(toggle)
+            .toggle_state(self.selected_entry == Some(index))
+            .on_toggle(cx.listener(move |this, _, _, cx| this.toggle_entry(index, cx)))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .overflow_hidden()
+                    .child(Label::new(symbol.name.clone()).truncate())
+                    .children(entry.item.detail.clone().map(|detail| {
+                        Label::new(detail)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate()
+                    })),
+            )
+            .end_slot(end_slot)
+            .end_hover_slot(
+                IconButton::new(("reroot-hierarchy", entry.id), IconName::Crosshair)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Show Hierarchy From Here"))
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        cx.stop_propagation();
+                        this.reroot(index, cx);
+                    })),
+            )
+            .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                this.selected_entry = Some(index);
+                if event.click_count() > 1 {
+                    this.open_entry(index, window, cx);
+                }
+                cx.notify();
+            }))
+    }
+}
+
+impl Render for HierarchyPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let direction = self.hierarchy.as_ref().map(|hierarchy| hierarchy.direction);
+        let title = direction.map_or("Hierarchy", HierarchyDirection::title);
+        v_flex()
+            .key_context("HierarchyPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::toggle_direction))
+            .size_full()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .px_2()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new(title))
+                    .when_some(direction, |this, direction| {
+                        let tooltip = match direction.kind() {
+                            HierarchyKind::Call => "Toggle Incoming and Outgoing Calls",
+                            HierarchyKind::Type => "Toggle Supertypes and Subtypes",
+                        };
+                        this.child(
+                            IconButton::new("toggle-hierarchy-direction", IconName::ArrowUpRight)
+                                .icon_size(IconSize::Small)
+                                .tooltip(Tooltip::text(tooltip))
+                                .on_click(|_, window, cx| {
+                                    window
+                                        .dispatch_action(ToggleHierarchyDirection.boxed_clone(), cx)
+                                }),
+                        )
+                    }),
+            )
+            .map(|this| {
+                let Some(hierarchy) = &self.hierarchy else {
+                    return this.child(
+                        v_flex()
+                            .flex_1()
+                            .justify_center()
+                            .items_center()
+                            .gap_1()
+                            .p_4()
+                            .child(Label::new("No hierarchy"))
+                            .child(
+                                Label::new(
+                                    "Show the call or type hierarchy of a symbol from the editor",
+                                )
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                            ),
+                    );
+                };
+                this.child(
+                    v_flex()
+                        .id("hierarchy-entries")
+                        .flex_1()
+                        .overflow_y_scroll()
+                        .children(hierarchy.entries.iter().enumerate().map(|(index, entry)| {
+                            let has_children = hierarchy
+                                .entries
+                                .get(index + 1)
+                                .is_some_and(|next_entry| next_entry.depth > entry.depth);
+                            self.render_entry(index, entry, has_children, cx)
+                        })),
+                )
+            })
+    }
+}
+
+impl Focusable for HierarchyPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for HierarchyPanel {}
+
+impl Panel for HierarchyPanel {
+    fn persistent_name() -> &'static str {
+        "HierarchyPanel"
+    }
+
+    fn panel_key() -> &'static str {
+        HIERARCHY_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        HierarchyPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.hierarchy_panel.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| HierarchyPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        HierarchyPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::ListTree)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Hierarchy Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        11
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use futures::StreamExt as _;
+    use gpui::{TestAppContext, VisualTestContext, WindowHandle};
+    use language::{FakeLspAdapter, rust_lang};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::{MultiWorkspace, OpenOptions, OpenVisible};
+
+    use super::*;
+
+    #[gpui::test]
+    async fn test_call_hierarchy_panel(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let root = path!("/root");
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            root,
+            json!({
+                "lib.rs": "fn a() { b() }\nfn b() { c() }\nfn c() {}\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [Path::new(root)], cx).await;
+        let language_registry = project.read_with(cx, |project, _| {
+            project.languages().add(rust_lang());
+            project.languages().clone()
+        });
+        let mut fake_language_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                    ..lsp::ServerCapabilities::default()
+                },
+                ..FakeLspAdapter::default()
+            },
+        );
+
+        let (window, workspace) = add_hierarchy_panel(&project, cx).await;
+        let cx = &mut VisualTestContext::from_window(window.into(), cx);
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_abs_path(
+                    PathBuf::from(path!("/root/lib.rs")),
+                    OpenOptions {
+                        visible: Some(OpenVisible::All),
+                        ..OpenOptions::default()
+                    },
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        let fake_language_server = fake_language_servers.next().await.unwrap();
+        cx.run_until_parked();
+
+        fake_language_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>(
+            |params, _| async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(1, 3)
+                );
+                Ok(Some(vec![call_hierarchy_item("b", 1)]))
+            },
+        );
+        fake_language_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(
+            |params, _| async move {
+                assert_eq!(params.item.name, "b");
+                Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                    from: call_hierarchy_item("a", 0),
+                    from_ranges: vec![lsp::Range::new(
+                        lsp::Position::new(0, 9),
+                        lsp::Position::new(0, 10),
+                    )],
+                }]))
+            },
+        );
+        fake_language_server.set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>(
+            |params, _| async move {
+                let callees = match params.item.name.as_str() {
+                    "b" => vec![lsp::CallHierarchyOutgoingCall {
+                        to: call_hierarchy_item("c", 2),
+                        from_ranges: vec![lsp::Range::new(
+                            lsp::Position::new(1, 9),
+                            lsp::Position::new(1, 10),
+                        )],
+                    }],
+                    _ => Vec::new(),
+                };
+                Ok(Some(callees))
+            },
+        );
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |selections| {
+                selections.select_ranges([language::Point::new(1, 3)..language::Point::new(1, 3)])
+            });
+        });
+        cx.dispatch_action(ShowCallHierarchy);
+        cx.run_until_parked();
+
+        let panel = workspace.update_in(cx, |workspace, _, cx| {
+            workspace.panel::<HierarchyPanel>(cx).unwrap()
+        });
+        panel.update(cx, |panel, _| {
+            assert_eq!(displayed_entries(panel), [("b", 0), ("a", 1)]);
+        });
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.toggle_direction(&ToggleHierarchyDirection, window, cx)
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(displayed_entries(panel), [("b", 0), ("c", 1)]);
+        });
+
+        // Collapsing the root hides its callees, and expanding it fetches them again.
+        panel.update(cx, |panel, cx| panel.toggle_entry(0, cx));
+        panel.update(cx, |panel, _| {
+            assert_eq!(displayed_entries(panel), [("b", 0)]);
+        });
+        panel.update(cx, |panel, cx| panel.toggle_entry(0, cx));
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(displayed_entries(panel), [("b", 0), ("c", 1)]);
+        });
+
+        panel.update_in(cx, |panel, window, cx| panel.open_entry(1, window, cx));
+        cx.run_until_parked();
+        editor.update(cx, |editor, cx| {
+            let selection = editor
+                .selections
+                .newest::<language::Point>(&editor.display_snapshot(cx));
+            assert_eq!(selection.head(), language::Point::new(2, 3));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_type_hierarchy_panel(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let root = path!("/root");
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            root,
+            json!({
+                "lib.rs": "struct A;\nstruct B;\nstruct C;\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [Path::new(root)], cx).await;
+        let language_registry = project.read_with(cx, |project, _| {
+            project.languages().add(rust_lang());
+            project.languages().clone()
+        });
+        let mut capabilities = lsp::ServerCapabilities::default();
+        lsp::set_type_hierarchy_provider(&mut capabilities, Some(json!(true)));
+        let mut fake_language_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities,
+                ..FakeLspAdapter::default()
+            },
+        );
+
+        let (window, workspace) = add_hierarchy_panel(&project, cx).await;
+        let cx = &mut VisualTestContext::from_window(window.into(), cx);
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_abs_path(
+                    PathBuf::from(path!("/root/lib.rs")),
+                    OpenOptions {
+                        visible: Some(OpenVisible::All),
+                        ..OpenOptions::default()
+                    },
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        let fake_language_server = fake_language_servers.next().await.unwrap();
+        cx.run_until_parked();
+
+        fake_language_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(
+            |params, _| async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(1, 7)
+                );
+                Ok(Some(vec![type_hierarchy_item("B", 1)]))
+            },
+        );
+        fake_language_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+            |params, _| async move {
+                assert_eq!(params.item.name, "B");
+                Ok(Some(vec![type_hierarchy_item("A", 0)]))
+            },
+        );
+        fake_language_server.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>(
+            |params, _| async move {
+                assert_eq!(params.item.name, "B");
+                Ok(Some(vec![type_hierarchy_item("C", 2)]))
+            },
+        );
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |selections| {
+                selections.select_ranges([language::Point::new(1, 7)..language::Point::new(1, 7)])
+            });
+        });
+        cx.dispatch_action(ShowTypeHierarchy);
+        cx.run_until_parked();
+
+        let panel = workspace.update_in(cx, |workspace, _, cx| {
+            workspace.panel::<HierarchyPanel>(cx).unwrap()
+        });
+        panel.update(cx, |panel, _| {
+            assert_eq!(displayed_entries(panel), [("B", 0), ("A", 1)]);
+        });
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.toggle_direction(&ToggleHierarchyDirection, window, cx)
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(displayed_entries(panel), [("B", 0), ("C", 1)]);
+        });
+
+        panel.update_in(cx, |panel, window, cx| panel.open_entry(1, window, cx));
+        cx.run_until_parked();
+        editor.update(cx, |editor, cx| {
+            let selection = editor
+                .selections
+                .newest::<language::Point>(&editor.display_snapshot(cx));
+            assert_eq!(selection.head(), language::Point::new(2, 7));
+        });
+    }
+
+    fn call_hierarchy_item(name: &str, line: u32) -> lsp::CallHierarchyItem {
+        let range = lsp::Range::new(lsp::Position::new(line, 3), lsp::Position::new(line, 4));
+        lsp::CallHierarchyItem {
+            name: name.to_string(),
+            kind: lsp::SymbolKind::FUNCTION,
+            tags: None,
+            detail: None,
+            uri: lsp::Uri::from_file_path(path!("/root/lib.rs")).unwrap(),
+            range,
+            selection_range: range,
+            data: None,
+        }
+    }
+
+    fn type_hierarchy_item(name: &str, line: u32) -> lsp::TypeHierarchyItem {
+        let range = lsp::Range::new(lsp::Position::new(line, 7), lsp::Position::new(line, 8));
+        lsp::TypeHierarchyItem {
+            name: name.to_string(),
+            kind: lsp::SymbolKind::STRUCT,
+            tags: None,
+            detail: None,
+            uri: lsp::Uri::from_file_path(path!("/root/lib.rs")).unwrap(),
+            range,
+            selection_range: range,
+            data: None,
+        }
+    }
+
+    fn displayed_entries(panel: &HierarchyPanel) -> Vec<(&str, usize)> {
+        panel
+            .hierarchy
+            .iter()
+            .flat_map(|hierarchy| &hierarchy.entries)
+            .map(|entry| (entry.item.symbol.name.as_str(), entry.depth))
+            .collect()
+    }
+
+    async fn add_hierarchy_panel(
+        project: &Entity<Project>,
+        cx: &mut TestAppContext,
+    ) -> (WindowHandle<MultiWorkspace>, Entity<Workspace>) {
+        let window =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window
+            .read_with(cx, |multi_workspace, _| multi_workspace.workspace().clone())
+            .unwrap();
+
+        let workspace_weak = workspace.downgrade();
+        let panel = window
+            .update(cx, |_, window, cx| {
+                cx.spawn_in(window, async move |_, cx| {
+                    HierarchyPanel::load(workspace_weak, cx.clone()).await
+                })
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        window
+            .update(cx, |_, window, cx| {
+                workspace.update(cx, |workspace, cx| workspace.add_panel(panel, window, cx));
+            })
+            .unwrap();
+        (window, workspace)
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            super::init(cx);
+        });
+    }
+}
@@ -1039,9 +1039,28 @@ impl KeymapEditor {
         conflict: Option<ConflictOrigin>,
         cx: &mut Context<Self>,
     ) -> IconButton {
-        if self.filter_state != FilterState::Conflicts
-            && let Some(conflict) = conflict
-        {
+        if self.filter_state == FilterState::Conflicts && conflict.is_some() {
+            let is_user_binding = self
+                .matches
+                .get(index)
+                .and_then(|candidate| self.keybindings.get(candidate.candidate_id))
+                .and_then(ProcessedBinding::keybind_source)
+                == Some(KeybindSource::User);
+            let meta = if is_user_binding {
+                "Removes this binding from your keymap."
+            } else {
+                "Binds its keystrokes to zed::NoAction in your keymap."
+            };
+            base_button_style(index, IconName::Trash)
+                .tooltip(move |_window, cx| {
+                    Tooltip::with_meta("Disable Keybinding", Some(&DeleteBinding), meta, cx)
+                })
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.select_index(index, None, window, cx);
+                    this.delete_binding(&DeleteBinding, window, cx);
+                    cx.stop_propagation();
+                }))
+        } else if let Some(conflict) = conflict {
             if conflict.is_user_keybind_conflict() {
                 base_button_style(index, IconName::Warning)
                     .icon_color(Color::Warning)
@@ -2126,6 +2145,20 @@ impl Render for KeymapEditor {
                                                     }.map(|source| format!("This keybinding is overridden by the '{}' binding from {}.", binding.action().humanized_name, source))
                                                 }).unwrap_or_else(|| "This binding is overridden.".to_string());
 
+                                                row.tooltip(Tooltip::text(context))},
+                                        )
+                                        .when_some(
+                                            conflict.filter(|conflict| {
+                                                !this.context_menu_deployed() &&
+                                                conflict.is_user_keybind_conflict()
+                                            }),
+                                            |row, conflict| {
+                                                let context = this
+                                                    .keybindings
+                                                    .get(conflict.index)
+                                                    .map(|binding| format!("This keybinding conflicts with the '{}' binding in your keymap. The one defined later in your keymap takes precedence.", binding.action().humanized_name))
+                                                    .unwrap_or_else(|| "This keybinding conflicts with another binding in your keymap.".to_string());
+
                                                 row.tooltip(Tooltip::text(context))},
                                         ),
                                 )
@@ -2262,6 +2295,7 @@ struct KeybindingEditorModal {
     keymap_editor: Entity<KeymapEditor>,
     workspace: WeakEntity<Workspace>,
     focus_state: KeybindingEditorModalFocusState,
+    _keystrokes_subscription: Subscription,
 }
 
 impl ModalView for KeybindingEditorModal {}
@@ -2287,10 +2321,13 @@ impl KeybindingEditorModal {
         workspace: WeakEntity<Workspace>,
         fs: Arc<dyn Fs>,
         window: &mut Window,
-        cx: &mut App,
+        cx: &mut Context<Self>,
     ) -> Self {
         let keybind_editor = cx
             .new(|cx| KeystrokeInput::new(editing_keybind.keystrokes().map(Vec::from), window, cx));
+        let keystrokes_subscription = cx.subscribe(&keybind_editor, |this, _, _, cx| {
+            this.update_conflict_warning(cx);
+        });
 
         let context_editor: Entity<InputField> = cx.new(|cx| {
             let input = InputField::new(window, cx, "Keybinding Context")
@@ -2417,9 +2454,88 @@ impl KeybindingEditorModal {
             keymap_editor,
             workspace,
             focus_state,
+            _keystrokes_subscription: keystrokes_subscription,
+        }
+    }
+
+    /// Shows whether the keystrokes being recorded conflict with other
+    /// bindings as they change, rather than only when saving.
+    fn update_conflict_warning(&mut self, cx: &mut Context<Self>) {
+        let warning = self
+            .validate_keystrokes(cx)
+            .ok()
+            .zip(self.validate_context(cx).ok())
+            .and_then(|(mut keystrokes, context)| {
+                keystrokes.iter_mut().for_each(|ks| ks.remove_key_char());
+                self.conflict_warning(
+                    &ActionMapping {
+                        keystrokes: Rc::from(keystrokes.as_slice()),
+                        context: context.map(SharedString::from),
+                    },
+                    cx,
+                )
+            });
+        match warning {
+            Some(warning) => {
+                if self.error.as_ref() != Some(&warning) {
+                    self.error = Some(warning);
+                    cx.notify();
+                }
+            }
+            None => {
+                if self
+                    .error
+                    .as_ref()
+                    .is_some_and(|error| error.severity == Severity::Warning)
+                {
+                    self.error = None;
+                    cx.notify();
+                }
+            }
         }
     }
 
+    fn conflict_warning(&self, action_mapping: &ActionMapping, cx: &App) -> Option<InputError> {
+        let KeybindConflict {
+            first_conflict_index,
+            remaining_conflict_amount,
+        } = self
+            .keymap_editor
+            .read(cx)
+            .keybinding_conflict_state
+            .conflicting_indices_for_mapping(
+                action_mapping,
+                self.creating.not().then_some(self.editing_keybind_idx),
+            )?;
+        let conflicting_action_name = self
+            .keymap_editor
+            .read(cx)
+            .keybindings
+            .get(first_conflict_index)
+            .map(|keybind| keybind.action().name);
+
+        let warning_message = match conflicting_action_name {
+            Some(name) => {
+                if remaining_conflict_amount > 0 {
+                    format!(
+                        "Your keybind would conflict with the \"{}\" action and {} other bindings",
+                        name, remaining_conflict_amount
+                    )
+                } else {
+                    format!("Your keybind would conflict with the \"{}\" action", name)
+                }
+            }
+            None => {
+                log::info!(
+                    "Could not find action in keybindings with index {}",
+                    first_conflict_index
+                );
+                "Your keybind would conflict with other actions".to_string()
+            }
+        };
+        Some(InputError::warning(warning_message))
+    }
+
     fn add_action_arguments_input(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let Some(action_editor) = &self.action_editor else {
             return;
@@ -2584,54 +2700,11 @@ impl KeybindingEditorModal {
             context: new_context.map(SharedString::from),
         };
 
-        let conflicting_indices = self
-            .keymap_editor
-            .read(cx)
-            .keybinding_conflict_state
-            .conflicting_indices_for_mapping(
-                &action_mapping,
-                self.creating.not().then_some(self.editing_keybind_idx),
-            );
-
-        conflicting_indices.map(|KeybindConflict {
-            first_conflict_index,
-            remaining_conflict_amount,
-        }|
+        if let Some(warning) = self.conflict_warning(&action_mapping, cx)
+            && self.error.as_ref() != Some(&warning)
         {
-            let conflicting_action_name = self
-                .keymap_editor
-                .read(cx)
-                .keybindings
-                .get(first_conflict_index)
-                .map(|keybind| keybind.action().name);
-
-            let warning_message = match conflicting_action_name {
-                Some(name) => {
-                     if remaining_conflict_amount > 0 {
-                        format!(
-                            "Your keybind would conflict with the \"{}\" action and {} other bindings",
-                            name, remaining_conflict_amount
-                        )
-                    } else {
-                        format!("Your keybind would conflict with the \"{}\" action", name)
-                    }
-                }
-                None => {
-                    log::info!(
-                        "Could not find action in keybindings with index {}",
-                        first_conflict_index
-                    );
-                    "Your keybind would conflict with other actions".to_string()
-                }
-            };
-
-            let warning = InputError::warning(warning_message);
-            if self.error.as_ref().is_some_and(|old_error| *old_error == warning) {
-                Ok(())
-           } else {
-                Err(warning)
-            }
-        }).unwrap_or(Ok(()))?;
+            return Err(warning);
+        }
 
         let create = self.creating;
         let keyboard_mapper = cx.keyboard_mapper().clone();
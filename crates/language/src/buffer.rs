@@ -139,6 +139,9 @@ pub struct Buffer {
     tree_sitter_data: Arc<TreeSitterData>,
     encoding: &'static Encoding,
     has_bom: bool,
+    /// Whether the buffer's file was over the large-file mode threshold when opened.
+    large_file: bool,
+    syntax_disabled: bool,
     reload_with_encoding_txns: HashMap<TransactionId, (&'static Encoding, bool)>,
 }
 
@@ -150,6 +153,9 @@ pub struct TreeSitterData {
 
 const MAX_ROWS_IN_A_CHUNK: u32 = 50;
 
+/// How many rows after a `region` marker are searched for its `endregion`.
+const MAX_REGION_MARKER_SCAN_ROWS: u32 = 10_000;
+
 impl TreeSitterData {
     fn clear(&mut self, snapshot: &text::BufferSnapshot) {
         self.chunks = RowChunks::new(&snapshot, MAX_ROWS_IN_A_CHUNK);
@@ -178,6 +184,13 @@ pub enum ParseStatus {
     Parsing,
 }
 
+/// A comment marking where a foldable region starts or ends.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum RegionMarker {
+    Start,
+    End,
+}
+
 struct BufferBranchState {
     base_buffer: Entity<Buffer>,
     merged_operations: Vec<Lamport>,
@@ -1156,6 +1169,8 @@ impl Buffer {
             _subscriptions: Vec::new(),
             encoding: encoding_rs::UTF_8,
             has_bom: false,
+            large_file: false,
+            syntax_disabled: false,
             reload_with_encoding_txns: HashMap::default(),
         }
     }
@@ -1470,6 +1485,29 @@ impl Buffer {
         self.has_bom = has_bom;
     }
 
+    /// Returns whether the buffer was opened in large-file mode.
+    pub fn is_large_file(&self) -> bool {
+        self.large_file
+    }
+
+    /// Sets whether the buffer was opened in large-file mode.
+    pub fn set_large_file(&mut self, large_file: bool) {
+        self.large_file = large_file;
+    }
+
+    /// Returns whether the buffer's text is left unparsed, even with a language.
+    pub fn is_syntax_disabled(&self) -> bool {
+        self.syntax_disabled
+    }
+
+    /// Sets whether to leave the buffer's text unparsed, even with a language.
+    pub fn set_syntax_disabled(&mut self, syntax_disabled: bool, cx: &mut Context<Self>) {
+        if self.syntax_disabled != syntax_disabled {
+            self.syntax_disabled = syntax_disabled;
+            self.reparse(cx, false);
+        }
+    }
+
     /// Assign a language to the buffer.
     pub fn set_language_async(&mut self, language: Option<Arc<Language>>, cx: &mut Context<Self>) {
         self.set_language_(language, cfg!(any(test, feature = "test-support")), cx);
@@ -1822,7 +1860,7 @@ impl Buffer {
         if self.text.version() != *self.tree_sitter_data.version() {
             Self::invalidate_tree_sitter_data(&mut self.tree_sitter_data, self.text.snapshot());
         }
-        if self.reparse.is_some() {
+        if self.reparse.is_some() || self.syntax_disabled {
             return;
         }
         let language = if let Some(language) = self.language.clone() {
@@ -3775,6 +3813,67 @@ impl BufferSnapshot {
         }
     }
 
+    /// Returns the parts of the given range covered by the given highlight
+    /// captures, or by the captures nested under them, sorted and merged.
+    pub fn ranges_for_highlight_captures<T: ToOffset>(
+        &self,
+        range: Range<T>,
+        capture_names: &[String],
+    ) -> Vec<Range<usize>> {
+        let range = range.start.to_offset(self)..range.end.to_offset(self);
+        let mut captures = self.syntax.captures(range.clone(), &self.text, |grammar| {
+            grammar
+                .highlights_config
+                .as_ref()
+                .map(|config| &config.query)
+        });
+        let matching_capture_indices = captures
+            .grammars()
+            .iter()
+            .map(|grammar| {
+                grammar
+                    .highlights_config
+                    .as_ref()
+                    .map(|config| {
+                        config
+                            .query
+                            .capture_names()
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, name)| {
+                                capture_names.iter().any(|capture_name| {
+                                    name.strip_prefix(capture_name.as_str())
+                                        .is_some_and(|rest| {
+                                            rest.is_empty() || rest.starts_with('.')
+                                        })
+                                })
+                            })
+                            .map(|(index, _)| index as u32)
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+
+        let mut ranges = Vec::<Range<usize>>::new();
+        while let Some(capture) = captures.peek() {
+            if matching_capture_indices[capture.grammar_index].contains(&capture.index) {
+                let node_range = capture.node.byte_range();
+                ranges.push(node_range.start.max(range.start)..node_range.end.min(range.end));
+            }
+            captures.advance();
+        }
+        ranges.sort_unstable_by_key(|range| range.start);
+        let mut merged_ranges = Vec::<Range<usize>>::with_capacity(ranges.len());
+        for range in ranges {
+            match merged_ranges.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged_ranges.push(range),
+            }
+        }
+        merged_ranges
+    }
+
     /// Iterates over every [`SyntaxLayer`] in the buffer.
     pub fn syntax_layers(&self) -> impl Iterator<Item = SyntaxLayer<'_>> + '_ {
         self.syntax_layers_for_range(0..self.len(), true)
@@ -4543,6 +4642,25 @@ impl BufferSnapshot {
         self.syntax.matches(range, self, query)
     }
 
+    /// Whether `range` is within a string or a comment of any syntax layer, so
+    /// that brackets a grammar parses inside them, such as those of injected
+    /// languages, aren't colorized.
+    fn is_range_in_string_or_comment(&self, range: &Range<usize>) -> bool {
+        // Both ends are checked, as inclusive overrides also contain the
+        // offsets right before and after them.
+        [range.start, range.end].into_iter().all(|offset| {
+            self.syntax
+                .layers_for_range(offset..offset, &self.text, false)
+                .any(|layer| {
+                    let scope = LanguageScope {
+                        language: layer.language.clone(),
+                        override_id: layer.override_id(offset, &self.text),
+                    };
+                    matches!(scope.override_name(), Some("string" | "comment"))
+                })
+        })
+    }
+
     /// Finds all [`RowChunks`] applicable to the given range, then returns all bracket pairs that intersect with those chunks.
     /// Hence, may return more bracket pairs than the range contains.
     ///
@@ -4732,7 +4850,8 @@ impl BufferSnapshot {
                     // We need to  colorize `<Element/>` bracket pairs, so cannot make this check stricter.
                     let should_color = !rainbow_exclude
                         && (bracket_match.open_range.len() == 1
-                            || bracket_match.close_range.len() == 1);
+                            || bracket_match.close_range.len() == 1)
+                        && !self.is_range_in_string_or_comment(&bracket_match.open_range);
                     if should_color {
                         opens.push(bracket_match.open_range.clone());
                         color_pairs.push((
@@ -4993,6 +5112,193 @@ impl BufferSnapshot {
         result
     }
 
+    /// Returns the ranges of the names of an element's opening and closing
+    /// tags when `position` is within one of them, for languages whose tags are
+    /// described by their `jsx_tag_auto_close` configuration.
+    ///
+    /// Returns nothing when the two names differ, as the tags then don't
+    /// match.
+    pub fn tag_name_ranges_at<T: ToOffset>(&self, position: T) -> Vec<Range<usize>> {
+        let offset = position.to_offset(self);
+        maybe!({
+            let layer = self.smallest_syntax_layer_containing(offset..offset)?;
+            let config = layer.language.config().jsx_tag_auto_close.as_ref()?;
+            let is_tag_name = |node: &tree_sitter::Node| {
+                node.kind() == config.tag_name_node_name
+                    || config
+                        .tag_name_node_name_alternates
+                        .iter()
+                        .any(|alternate| alternate == node.kind())
+            };
+
+            let mut tag = layer.node().descendant_for_byte_range(offset, offset)?;
+            while tag.kind() != config.open_tag_node_name
+                && tag.kind() != config.close_tag_node_name
+            {
+                tag = tag.parent()?;
+            }
+            let name = tag.named_child(0).filter(is_tag_name)?.byte_range();
+            if !name.contains_inclusive(&(offset..offset)) {
+                return None;
+            }
+
+            let element = tag
+                .parent()
+                .filter(|element| element.kind() == config.jsx_element_node_name)?;
+            let open_tag = element
+                .named_child(0)
+                .filter(|node| node.kind() == config.open_tag_node_name)?;
+            let close_tag = element
+                .named_children(&mut element.walk())
+                .last()
+                .filter(|node| node.kind() == config.close_tag_node_name)?;
+            let open_name = open_tag.named_child(0).filter(is_tag_name)?.byte_range();
+            let close_name = close_tag.named_child(0).filter(is_tag_name)?.byte_range();
+            let open_text = self.text_for_range(open_name.clone()).collect::<String>();
+            let close_text = self.text_for_range(close_name.clone()).collect::<String>();
+            (open_text == close_text).then(|| vec![open_name, close_name])
+        })
+        .unwrap_or_default()
+    }
+
+    /// Returns the range hidden by folding the fold that starts at `row`.
+    ///
+    /// A comment with a `region` marker, like `// region` or `#region`, is
+    /// folded up to its matching `endregion` marker. Otherwise, the largest
+    /// `@fold` capture of the language's folds query that starts at `row` is
+    /// folded, except for its last line when that line only closes it.
+    pub fn fold_range_for_row(&self, row: u32) -> Option<Range<Point>> {
+        let start = Point::new(row, self.line_len(row));
+        let line_start = Point::new(row, self.line_indent_for_row(row).raw_len());
+        let scope = self.language_scope_at(line_start);
+        let comment_prefixes = scope
+            .iter()
+            .flat_map(|scope| {
+                scope
+                    .line_comment_prefixes()
+                    .iter()
+                    .map(|prefix| prefix.trim_end())
+                    .chain(
+                        scope
+                            .block_comment()
+                            .map(|comment| comment.start.trim_end()),
+                    )
+            })
+            .filter(|prefix| !prefix.is_empty())
+            .collect::<Vec<_>>();
+        if let Some(marker) = self.region_marker(row, &comment_prefixes) {
+            if marker == RegionMarker::End {
+                return None;
+            }
+            let mut depth = 0_usize;
+            let last_row = self
+                .max_point()
+                .row
+                .min(row.saturating_add(MAX_REGION_MARKER_SCAN_ROWS));
+            for next_row in row + 1..=last_row {
+                match self.region_marker(next_row, &comment_prefixes) {
+                    Some(RegionMarker::Start) => depth += 1,
+                    Some(RegionMarker::End) if depth == 0 => {
+                        return Some(start..Point::new(next_row, self.line_len(next_row)));
+                    }
+                    Some(RegionMarker::End) => depth -= 1,
+                    None => {}
+                }
+            }
+            return None;
+        }
+
+        let row_range = Point::new(row, 0).to_offset(self)..start.to_offset(self);
+        let mut matches = self
+            .syntax
+            .matches(row_range.clone(), &self.text, |grammar| {
+                grammar.folds_config.as_ref().map(|config| &config.query)
+            });
+        let configs = matches
+            .grammars()
+            .iter()
+            .map(|grammar| grammar.folds_config.as_ref())
+            .collect::<Vec<_>>();
+        let mut end: Option<Point> = None;
+        while let Some(mat) = matches.peek() {
+            if let Some(config) = configs[mat.grammar_index] {
+                for capture in mat.captures {
+                    if capture.index != config.fold_capture_ix
+                        || !row_range.contains(&capture.node.start_byte())
+                    {
+                        continue;
+                    }
+                    let node_end = self.offset_to_point(capture.node.end_byte());
+                    let last_line_start = Point::new(
+                        node_end.row,
+                        self.line_indent_for_row(node_end.row).raw_len(),
+                    );
+                    let closes_on_own_line = capture
+                        .node
+                        .children(&mut capture.node.walk())
+                        .last()
+                        .is_some_and(|last_child| {
+                            self.offset_to_point(last_child.start_byte()) == last_line_start
+                        });
+                    let fold_end = if closes_on_own_line {
+                        let row_before = node_end.row.saturating_sub(1);
+                        Point::new(row_before, self.line_len(row_before))
+                    } else {
+                        node_end
+                    };
+                    if fold_end.row > row && end.is_none_or(|end| fold_end > end) {
+                        end = Some(fold_end);
+                    }
+                }
+            }
+            matches.advance();
+        }
+        Some(start..end?)
+    }
+
+    /// Whether the folds of `row` come from the language's folds query, rather than from
+    /// indentation.
+    pub fn folds_by_syntax(&self, row: u32) -> bool {
+        let position = Point::new(row, self.line_indent_for_row(row).raw_len());
+        self.smallest_syntax_layer_containing(position..position)
+            .and_then(|layer| layer.language.grammar())
+            .is_some_and(|grammar| grammar.folds_config.is_some())
+    }
+
+    /// The region marker on `row`, which is only recognized in a comment
+    /// starting with one of `comment_prefixes` or with `#`.
+    fn region_marker(&self, row: u32, comment_prefixes: &[&str]) -> Option<RegionMarker> {
+        let line_start = Point::new(row, self.line_indent_for_row(row).raw_len());
+        let mut marker_start = comment_prefixes
+            .iter()
+            .find(|prefix| self.contains_str_at(line_start, prefix))
+            .map(|prefix| {
+                let after_prefix = Point::new(row, line_start.column + prefix.len() as u32);
+                let whitespace_len = self
+                    .chars_at(after_prefix)
+                    .take_while(|char| *char == ' ' || *char == '\t')
+                    .count();
+                Point::new(row, after_prefix.column + whitespace_len as u32)
+            })
+            .or_else(|| self.contains_str_at(line_start, "#").then_some(line_start))?;
+        if self.contains_str_at(marker_start, "#") {
+            marker_start.column += 1;
+        }
+        let (marker, keyword) = if self.contains_str_at(marker_start, "region") {
+            (RegionMarker::Start, "region")
+        } else if self.contains_str_at(marker_start, "endregion") {
+            (RegionMarker::End, "endregion")
+        } else {
+            return None;
+        };
+        let keyword_end = Point::new(row, marker_start.column + keyword.len() as u32);
+        let ends_word = self
+            .chars_at(keyword_end)
+            .next()
+            .is_none_or(|char| !char.is_alphanumeric());
+        ends_word.then_some(marker)
+    }
+
     /// Returns anchor ranges for any matches of the redaction query.
     /// The buffer can be associated with multiple languages, and the redaction query associated with each
     /// will be run on the relevant section of the buffer.
@@ -1,5 +1,5 @@
 use super::*;
-use crate::Buffer;
+use crate::{Buffer, JsxTagAutoCloseConfig};
 use clock::ReplicaId;
 use collections::BTreeMap;
 use futures::FutureExt as _;
@@ -1397,6 +1397,91 @@ fn test_enclosing_bracket_ranges_where_brackets_are_not_outermost_children(cx: &
     );
 }
 
+#[gpui::test]
+fn test_tag_name_ranges_at(cx: &mut App) {
+    let language = Language::new(
+        LanguageConfig {
+            name: "TSX".into(),
+            jsx_tag_auto_close: Some(JsxTagAutoCloseConfig {
+                open_tag_node_name: "jsx_opening_element".into(),
+                close_tag_node_name: "jsx_closing_element".into(),
+                jsx_element_node_name: "jsx_element".into(),
+                tag_name_node_name: "identifier".into(),
+                tag_name_node_name_alternates: vec!["member_expression".into()],
+                erroneous_close_tag_node_name: None,
+                erroneous_close_tag_name_node_name: None,
+            }),
+            ..Default::default()
+        },
+        Some(tree_sitter_typescript::LANGUAGE_TSX.into()),
+    );
+    let text = "let a = <div><Foo.Bar /><Foo.Bar></Foo.Bar></div>;";
+    let buffer = Buffer::local(text, cx).with_language(Arc::new(language), cx);
+    let snapshot = buffer.snapshot();
+
+    let div = text.find("div").unwrap();
+    let closing_div = text.rfind("div").unwrap();
+    let foo = text.rfind("<Foo.Bar>").unwrap() + 1;
+    let closing_foo = text.rfind("Foo.Bar").unwrap();
+    assert_eq!(
+        snapshot.tag_name_ranges_at(div + 1),
+        [div..div + 3, closing_div..closing_div + 3]
+    );
+    assert_eq!(
+        snapshot.tag_name_ranges_at(closing_foo + 7),
+        [foo..foo + 7, closing_foo..closing_foo + 7]
+    );
+    assert!(snapshot.tag_name_ranges_at(0).is_empty());
+    assert!(
+        snapshot
+            .tag_name_ranges_at(text.find("Foo").unwrap())
+            .is_empty()
+    );
+}
+
+#[gpui::test]
+fn test_fold_range_for_row(cx: &mut App) {
+    let language = Language::new(
+        LanguageConfig {
+            name: "Rust".into(),
+            line_comments: vec!["// ".into()],
+            ..Default::default()
+        },
+        Some(tree_sitter_rust::LANGUAGE.into()),
+    )
+    .with_folds_query("[(block) (field_declaration_list)] @fold")
+    .unwrap();
+    let text = indoc! {"
+        // region: helpers
+        fn a() {
+            let x = 1;
+        }
+        // endregion
+        struct S { a: u8,
+            b: u8 }
+        fn b() {}
+    "};
+    let buffer = Buffer::local(text, cx).with_language(Arc::new(language), cx);
+    let snapshot = buffer.snapshot();
+
+    assert_eq!(
+        snapshot.fold_range_for_row(0),
+        Some(Point::new(0, 18)..Point::new(4, 12))
+    );
+    assert_eq!(
+        snapshot.fold_range_for_row(1),
+        Some(Point::new(1, 8)..Point::new(2, 14))
+    );
+    assert_eq!(snapshot.fold_range_for_row(2), None);
+    assert_eq!(snapshot.fold_range_for_row(4), None);
+    assert_eq!(
+        snapshot.fold_range_for_row(5),
+        Some(Point::new(5, 17)..Point::new(6, 11))
+    );
+    assert_eq!(snapshot.fold_range_for_row(7), None);
+    assert!(snapshot.folds_by_syntax(1));
+}
+
 #[gpui::test]
 fn test_range_for_syntax_ancestor(cx: &mut App) {
     cx.new(|cx| {
@@ -2489,6 +2574,66 @@ fn test_insert_empty_line(cx: &mut App) {
     });
 }
 
+#[gpui::test]
+fn test_brackets_in_strings_are_not_colorized(cx: &mut App) {
+    init_settings(cx, |_| {});
+
+    cx.new(|cx| {
+        let language_registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
+        language_registry.add(Arc::new(
+            json_lang()
+                .with_brackets_query(r#"("[" @open "]" @close)"#)
+                .unwrap(),
+        ));
+        let language = Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                ..Default::default()
+            },
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        )
+        .with_brackets_query(
+            r#"
+                ("(" @open ")" @close)
+                ("[" @open "]" @close)
+            "#,
+        )
+        .unwrap()
+        .with_override_query(
+            r#"
+                (string_literal) @string
+            "#,
+        )
+        .unwrap()
+        .with_injection_query(
+            r#"
+                ((string_content) @injection.content
+                    (#set! injection.language "Json"))
+            "#,
+        )
+        .unwrap();
+
+        let text = r#"let a = ([1], "[2]");"#;
+        let mut buffer = Buffer::local(text, cx);
+        buffer.set_language_registry(language_registry);
+        buffer.set_language(Some(Arc::new(language)), cx);
+
+        // The brackets of the JSON injected into the string are matched, but
+        // neither colorized nor counted towards the depth of the others.
+        let mut brackets = buffer
+            .snapshot()
+            .fetch_bracket_ranges(0..text.len(), None)
+            .into_values()
+            .flatten()
+            .map(|pair| (pair.open_range.start, pair.color_index))
+            .collect::<Vec<_>>();
+        brackets.sort();
+        assert_eq!(brackets, [(8, Some(0)), (9, Some(1)), (15, None)]);
+
+        buffer
+    });
+}
+
 #[gpui::test]
 fn test_language_scope_at_with_javascript(cx: &mut App) {
     init_settings(cx, |_| {});
@@ -583,6 +583,12 @@ pub trait LspAdapter: 'static + Send + Sync + DynLspInstaller {
         false
     }
 
+    /// True for language servers defined only by their settings, which are
+    /// only used for the languages the settings list.
+    fn is_custom(&self) -> bool {
+        false
+    }
+
     /// Called when a user responds to a ShowMessageRequest from this language server.
     /// This allows adapters to intercept preference selections (like "Always" or "Never")
     /// for settings that should be persisted to Zed's settings file.
@@ -1361,6 +1367,7 @@ pub struct Grammar {
     pub(crate) override_config: Option<OverrideConfig>,
     pub(crate) debug_variables_config: Option<DebugVariablesConfig>,
     pub(crate) imports_config: Option<ImportsConfig>,
+    pub(crate) folds_config: Option<FoldsConfig>,
     pub(crate) highlight_map: Mutex<HighlightMap>,
 }
 
@@ -1515,6 +1522,11 @@ pub struct ImportsConfig {
     pub alias_ix: Option<u32>,
 }
 
+struct FoldsConfig {
+    query: Query,
+    fold_capture_ix: u32,
+}
+
 impl Language {
     pub fn new(config: LanguageConfig, ts_language: Option<tree_sitter::Language>) -> Self {
         Self::new_with_id(LanguageId::new(), config, ts_language)
@@ -1547,6 +1559,7 @@ impl Language {
                     error_query: Query::new(&ts_language, "(ERROR) @error").ok(),
                     debug_variables_config: None,
                     imports_config: None,
+                    folds_config: None,
                     ts_language,
                     highlight_map: Default::default(),
                 })
@@ -1628,6 +1641,11 @@ impl Language {
                 .with_imports_query(query.as_ref())
                 .context("Error loading imports query")?;
         }
+        if let Some(query) = queries.folds {
+            self = self
+                .with_folds_query(query.as_ref())
+                .context("Error loading folds query")?;
+        }
         Ok(self)
     }
 
@@ -1842,6 +1860,24 @@ impl Language {
         Ok(self)
     }
 
+    pub fn with_folds_query(mut self, source: &str) -> Result<Self> {
+        let query = Query::new(&self.expect_grammar()?.ts_language, source)?;
+        let mut fold_capture_ix = 0;
+        if populate_capture_indices(
+            &query,
+            &self.config.name,
+            "folds",
+            &[],
+            &mut [Capture::Required("fold", &mut fold_capture_ix)],
+        ) {
+            self.grammar_mut()?.folds_config = Some(FoldsConfig {
+                query,
+                fold_capture_ix,
+            });
+        }
+        Ok(self)
+    }
+
     pub fn with_indents_query(mut self, source: &str) -> Result<Self> {
         let query = Query::new(&self.expect_grammar()?.ts_language, source)?;
         let mut indent_capture_ix = 0;
@@ -2917,6 +2953,40 @@ mod tests {
         assert!(languages.language_for_name("Unknown").await.is_err());
     }
 
+    #[gpui::test]
+    async fn test_user_folds_query(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+        let user_languages_dir = util::test::TempTree::new(serde_json::json!({
+            "Rust": {
+                "folds.scm": "(block) @fold",
+            },
+            "JSON": {
+                "folds.scm": "(not_a_node) @fold",
+            },
+        }));
+        let mut languages = LanguageRegistry::test(cx.executor());
+        languages.set_user_languages_dir(user_languages_dir.path());
+        let languages = Arc::new(languages);
+        languages.register_native_grammars([
+            ("json", tree_sitter_json::LANGUAGE),
+            ("rust", tree_sitter_rust::LANGUAGE),
+        ]);
+        for (name, grammar) in [("JSON", "json"), ("Rust", "rust")] {
+            languages.register_test_language(LanguageConfig {
+                name: name.into(),
+                grammar: Some(grammar.into()),
+                ..Default::default()
+            });
+        }
+
+        let rust = languages.language_for_name("Rust").await.unwrap();
+        assert!(rust.grammar().unwrap().folds_config.is_some());
+
+        // An invalid query doesn't keep the language from loading.
+        let json = languages.language_for_name("JSON").await.unwrap();
+        assert!(json.grammar().unwrap().folds_config.is_none());
+    }
+
     #[gpui::test]
     async fn test_completion_label_omits_duplicate_data() {
         let regular_completion_item_1 = lsp::CompletionItem {
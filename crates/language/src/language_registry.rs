@@ -109,6 +109,9 @@ impl From<LanguageName> for String {
 pub struct LanguageRegistry {
     state: RwLock<LanguageRegistryState>,
     language_server_download_dir: Option<Arc<Path>>,
+    /// Where users override the queries of languages, in a directory named
+    /// after each language.
+    user_languages_dir: Option<Arc<Path>>,
     executor: BackgroundExecutor,
     lsp_binary_status_tx: ServerStatusSender,
 }
@@ -235,6 +238,7 @@ pub const QUERY_FILENAME_PREFIXES: &[(
     ("debugger", |q| &mut q.debugger),
     ("textobjects", |q| &mut q.text_objects),
     ("imports", |q| &mut q.imports),
+    ("folds", |q| &mut q.folds),
 ];
 
 /// Tree-sitter language queries for a given language.
@@ -251,6 +255,7 @@ pub struct LanguageQueries {
     pub text_objects: Option<Cow<'static, str>>,
     pub debugger: Option<Cow<'static, str>>,
     pub imports: Option<Cow<'static, str>>,
+    pub folds: Option<Cow<'static, str>>,
 }
 
 #[derive(Clone, Default)]
@@ -288,6 +293,7 @@ impl LanguageRegistry {
                 fake_server_entries: Default::default(),
             }),
             language_server_download_dir: None,
+            user_languages_dir: None,
             lsp_binary_status_tx: Default::default(),
             executor,
         };
@@ -645,6 +651,10 @@ impl LanguageRegistry {
         self.language_server_download_dir = Some(path.into());
     }
 
+    pub fn set_user_languages_dir(&mut self, path: impl Into<Arc<Path>>) {
+        self.user_languages_dir = Some(path.into());
+    }
+
     pub fn language_for_name(
         self: &Arc<Self>,
         name: &str,
@@ -968,11 +978,16 @@ impl LanguageRegistry {
                 self.executor
                     .spawn(async move {
                         let language = async {
-                            let loaded_language = (language_load)()?;
+                            let mut loaded_language = (language_load)()?;
                             if let Some(grammar) = loaded_language.config.grammar.clone() {
-                                let grammar = Some(this.get_or_load_grammar(grammar).await?);
+                                let grammar = this.get_or_load_grammar(grammar).await?;
+                                if let Some(folds_query) =
+                                    this.load_user_folds_query(&name, &grammar).await
+                                {
+                                    loaded_language.queries.folds = Some(folds_query.into());
+                                }
 
-                                Language::new_with_id(id, loaded_language.config, grammar)
+                                Language::new_with_id(id, loaded_language.config, Some(grammar))
                                     .with_context_provider(loaded_language.context_provider)
                                     .with_toolchain_lister(loaded_language.toolchain_provider)
                                     .with_manifest(loaded_language.manifest_name)
@@ -1041,6 +1056,26 @@ impl LanguageRegistry {
         self.load_language(&language)
     }
 
+    /// Reads the user's `folds.scm` for the language named `name`, which
+    /// replaces the language's own folds query. Invalid queries are ignored,
+    /// so that they don't keep the language from loading.
+    async fn load_user_folds_query(
+        &self,
+        name: &LanguageName,
+        grammar: &tree_sitter::Language,
+    ) -> Option<String> {
+        let path = self
+            .user_languages_dir
+            .as_ref()?
+            .join(name.as_ref())
+            .join("folds.scm");
+        let query = smol::fs::read_to_string(&path).await.ok()?;
+        tree_sitter::Query::new(grammar, &query)
+            .with_context(|| format!("invalid folds query in {path:?}"))
+            .log_err()?;
+        Some(query)
+    }
+
     fn get_or_load_grammar(
         self: &Arc<Self>,
         name: Arc<str>,
@@ -7,13 +7,13 @@ use ec4rs::{
     property::{FinalNewline, IndentSize, IndentStyle, MaxLineLen, TabWidth, TrimTrailingWs},
 };
 use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
-use gpui::{App, Modifiers, SharedString};
+use gpui::{App, FontFeatures, FontWeight, Modifiers, Pixels, SharedString};
 use itertools::{Either, Itertools};
-use settings::{DocumentFoldingRanges, DocumentSymbols, IntoGpui, SemanticTokens};
+use settings::{CodeLens, DocumentFoldingRanges, DocumentSymbols, IntoGpui, SemanticTokens};
 
 pub use settings::{
-    CompletionSettingsContent, EditPredictionProvider, EditPredictionsMode, FormatOnSave,
-    Formatter, FormatterList, InlayHintKind, LanguageSettingsContent, LspInsertMode,
+    AutosaveSetting, CompletionSettingsContent, EditPredictionProvider, EditPredictionsMode,
+    FormatOnSave, Formatter, FormatterList, InlayHintKind, LanguageSettingsContent, LspInsertMode,
     RewrapBehavior, ShowWhitespaceSetting, SoftWrap, WordsCompletionMode,
 };
 use settings::{RegisterSetting, Settings, SettingsLocation, SettingsStore};
@@ -81,10 +81,27 @@ pub struct LanguageSettings {
     pub show_wrap_guides: bool,
     /// Character counts at which to show wrap guides (vertical rulers) in the editor.
     pub wrap_guides: Vec<usize>,
+    /// Whether to show the minimap in editors for this language, when the
+    /// minimap is enabled.
+    pub show_minimap: bool,
+    /// The font family to use for buffers of this language, if it differs
+    /// from the buffer font.
+    pub buffer_font_family: Option<SharedString>,
+    /// The font size to use for buffers of this language, if it differs from
+    /// the buffer font size.
+    pub buffer_font_size: Option<Pixels>,
+    /// The font weight to use for buffers of this language, if it differs
+    /// from the buffer font weight.
+    pub buffer_font_weight: Option<FontWeight>,
+    /// The OpenType features to use for buffers of this language, if they
+    /// differ from those of the buffer font.
+    pub buffer_font_features: Option<FontFeatures>,
     /// Indent guide related settings.
     pub indent_guides: IndentGuideSettings,
     /// Whether or not to perform a buffer format before saving.
     pub format_on_save: FormatOnSave,
+    /// When to automatically save edited buffers.
+    pub autosave: AutosaveSetting,
     /// Whether or not to remove any trailing whitespace from lines of a buffer
     /// before saving it.
     pub remove_trailing_whitespace_on_save: bool,
@@ -106,6 +123,9 @@ pub struct LanguageSettings {
     /// - `"!<language_server_id>"` - A language server ID prefixed with a `!` will be disabled.
     /// - `"..."` - A placeholder to refer to the **rest** of the registered language servers for this language.
     pub language_servers: Vec<String>,
+    /// Language server names by LSP request method, asked before the other
+    /// servers for requests that go to a single server.
+    pub language_server_priorities: HashMap<String, Vec<String>>,
     /// Controls how semantic tokens from language servers are used for syntax highlighting.
     pub semantic_tokens: SemanticTokens,
     /// Controls whether folding ranges from language servers are used instead of
@@ -113,6 +133,8 @@ pub struct LanguageSettings {
     pub document_folding_ranges: DocumentFoldingRanges,
     /// Controls the source of document symbols used for outlines and breadcrumbs.
     pub document_symbols: DocumentSymbols,
+    /// Controls whether code lenses are shown above symbols.
+    pub code_lens: CodeLens,
     /// Controls where the `editor::Rewrap` action is allowed for this language.
     ///
     /// Note: This setting has no effect in Vim mode, as rewrap is already
@@ -151,6 +173,14 @@ pub struct LanguageSettings {
     pub always_treat_brackets_as_autoclosed: bool,
     /// Which code actions to run on save
     pub code_actions_on_format: HashMap<String, bool>,
+    /// Which code actions to run, in order, whenever a buffer is saved
+    pub code_actions_on_save: Vec<String>,
+    /// How long to wait for each code action run on save, in milliseconds
+    pub code_actions_on_save_timeout_ms: u64,
+    /// Whether to underline misspelled words
+    pub spell_check: bool,
+    /// The highlight captures whose text is spell checked, or none to check everything
+    pub spell_check_captures: Vec<String>,
     /// Whether to perform linked edits
     pub linked_edits: bool,
     /// Task configuration for this language.
@@ -201,6 +231,12 @@ pub struct CompletionSettings {
     ///
     /// Default: "replace_suffix"
     pub lsp_insert_mode: LspInsertMode,
+    /// Whether accepting an LSP completion may also apply the additional
+    /// edits the language server attaches to it, which usually add a missing
+    /// import.
+    ///
+    /// Default: true
+    pub auto_import: bool,
 }
 
 /// The settings for indent guides.
@@ -335,6 +371,11 @@ pub struct InlayHintSettings {
     ///
     /// Default: true
     pub show_other_hints: bool,
+    /// Whether chaining hints should be shown, or `None` to show them along
+    /// with the other type hints.
+    ///
+    /// Default: None
+    pub show_chaining_hints: Option<bool>,
     /// Whether to show a background for inlay hints.
     ///
     /// If set to `true`, the background will use the `hint.background` color
@@ -375,6 +416,9 @@ impl InlayHintSettings {
         if self.show_other_hints {
             kinds.insert(None);
         }
+        if self.show_chaining_hints.unwrap_or(self.show_type_hints) {
+            kinds.insert(Some(InlayHintKind::Chaining));
+        }
         kinds
     }
 }
@@ -574,6 +618,11 @@ impl settings::Settings for AllLanguageSettings {
                 preferred_line_length: settings.preferred_line_length.unwrap(),
                 show_wrap_guides: settings.show_wrap_guides.unwrap(),
                 wrap_guides: settings.wrap_guides.unwrap(),
+                show_minimap: settings.show_minimap.unwrap(),
+                buffer_font_family: settings.buffer_font_family.map(IntoGpui::into_gpui),
+                buffer_font_size: settings.buffer_font_size.map(IntoGpui::into_gpui),
+                buffer_font_weight: settings.buffer_font_weight.map(IntoGpui::into_gpui),
+                buffer_font_features: settings.buffer_font_features.map(IntoGpui::into_gpui),
                 indent_guides: IndentGuideSettings {
                     enabled: indent_guides.enabled.unwrap(),
                     line_width: indent_guides.line_width.unwrap(),
@@ -582,6 +631,7 @@ impl settings::Settings for AllLanguageSettings {
                     background_coloring: indent_guides.background_coloring.unwrap(),
                 },
                 format_on_save: settings.format_on_save.unwrap(),
+                autosave: settings.autosave.unwrap(),
                 remove_trailing_whitespace_on_save: settings
                     .remove_trailing_whitespace_on_save
                     .unwrap(),
@@ -596,9 +646,11 @@ impl settings::Settings for AllLanguageSettings {
                 jsx_tag_auto_close: settings.jsx_tag_auto_close.unwrap().enabled.unwrap(),
                 enable_language_server: settings.enable_language_server.unwrap(),
                 language_servers: settings.language_servers.unwrap(),
+                language_server_priorities: settings.language_server_priorities.unwrap(),
                 semantic_tokens: settings.semantic_tokens.unwrap(),
                 document_folding_ranges: settings.document_folding_ranges.unwrap(),
                 document_symbols: settings.document_symbols.unwrap(),
+                code_lens: settings.code_lens.unwrap(),
                 allow_rewrap: settings.allow_rewrap.unwrap(),
                 show_edit_predictions: settings.show_edit_predictions.unwrap(),
                 edit_predictions_disabled_in: settings.edit_predictions_disabled_in.unwrap(),
@@ -616,6 +668,7 @@ impl settings::Settings for AllLanguageSettings {
                     show_type_hints: inlay_hints.show_type_hints.unwrap(),
                     show_parameter_hints: inlay_hints.show_parameter_hints.unwrap(),
                     show_other_hints: inlay_hints.show_other_hints.unwrap(),
+                    show_chaining_hints: inlay_hints.show_chaining_hints,
                     show_background: inlay_hints.show_background.unwrap(),
                     edit_debounce_ms: inlay_hints.edit_debounce_ms.unwrap(),
                     scroll_debounce_ms: inlay_hints.scroll_debounce_ms.unwrap(),
@@ -632,6 +685,10 @@ impl settings::Settings for AllLanguageSettings {
                     .always_treat_brackets_as_autoclosed
                     .unwrap(),
                 code_actions_on_format: settings.code_actions_on_format.unwrap(),
+                code_actions_on_save: settings.code_actions_on_save.unwrap(),
+                code_actions_on_save_timeout_ms: settings.code_actions_on_save_timeout_ms.unwrap(),
+                spell_check: settings.spell_check.unwrap(),
+                spell_check_captures: settings.spell_check_captures.unwrap(),
                 linked_edits: settings.linked_edits.unwrap(),
                 tasks: LanguageTaskSettings {
                     variables: tasks.variables.unwrap_or_default(),
@@ -647,6 +704,7 @@ impl settings::Settings for AllLanguageSettings {
                     lsp: completions.lsp.unwrap(),
                     lsp_fetch_timeout_ms: completions.lsp_fetch_timeout_ms.unwrap(),
                     lsp_insert_mode: completions.lsp_insert_mode.unwrap(),
+                    auto_import: completions.auto_import.unwrap(),
                 },
                 debuggers: settings.debuggers.unwrap(),
                 word_diff_enabled: settings.word_diff_enabled.unwrap(),
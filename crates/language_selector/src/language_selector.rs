@@ -153,7 +153,13 @@ impl LanguageSelectorDelegate {
         {
             label.push_str(" (current)");
             let icon = need_icon
-                .then(|| self.language_icon(&buffer_language.config().matcher, cx))
+                .then(|| {
+                    self.language_icon(
+                        &buffer_language.name(),
+                        &buffer_language.config().matcher,
+                        cx,
+                    )
+                })
                 .flatten();
             (label, icon)
         } else {
@@ -163,7 +169,7 @@ impl LanguageSelectorDelegate {
                     self.language_registry
                         .available_language_for_name(language_name.as_ref())
                         .and_then(|available_language| {
-                            self.language_icon(available_language.matcher(), cx)
+                            self.language_icon(&language_name, available_language.matcher(), cx)
                         })
                 })
                 .flatten();
@@ -171,11 +177,19 @@ impl LanguageSelectorDelegate {
         }
     }
 
-    fn language_icon(&self, matcher: &LanguageMatcher, cx: &App) -> Option<Icon> {
-        matcher
-            .path_suffixes
-            .iter()
-            .find_map(|extension| file_icons::FileIcons::get_icon(Path::new(extension), cx))
+    fn language_icon(
+        &self,
+        language_name: &LanguageName,
+        matcher: &LanguageMatcher,
+        cx: &App,
+    ) -> Option<Icon> {
+        file_icons::FileIcons::get_language_icon(language_name.as_ref(), cx)
+            .or_else(|| {
+                matcher
+                    .path_suffixes
+                    .iter()
+                    .find_map(|extension| file_icons::FileIcons::get_icon(Path::new(extension), cx))
+            })
             .map(Icon::from_path)
             .map(|icon| icon.color(Color::Muted))
     }
@@ -0,0 +1,704 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use client::proto;
+use collections::{HashMap, HashSet};
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{
+    Action, App, AsyncWindowContext, Entity, EventEmitter, FocusHandle, Focusable, Pixels,
+    Subscription, Task, WeakEntity, actions, px,
+};
+use language::{BinaryStatus, ServerHealth};
+use lsp::{LanguageServerId, LanguageServerName, LanguageServerSelector};
+use project::{
+    Fs, LspStore, LspStoreEvent, Project, WorktreeId,
+    lsp_store::{LanguageServerStatus, log_store::GlobalLogStore},
+};
+use serde::{Deserialize, Serialize};
+use settings::{RegisterSetting, Settings, SettingsStore};
+use ui::{IconButton, IconName, Indicator, Label, ListItem, Tab, Tooltip, prelude::*};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+use crate::{
+    lsp_button::{
+        PROCESS_USAGE_CACHE_DURATION, ProcessUsageCache, binary_status_from_proto, format_memory,
+        server_health_from_proto,
+    },
+    lsp_log_view,
+};
+
+const LANGUAGE_SERVERS_PANEL_KEY: &str = "LanguageServersPanel";
+
+actions!(
+    language_servers_panel,
+    [
+        /// Toggles focus on the language servers panel.
+        ToggleFocus,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<LanguageServersPanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Debug, RegisterSetting)]
+pub struct LanguageServersPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+impl Settings for LanguageServersPanelSettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let panel = content.language_servers_panel.as_ref().unwrap();
+        Self {
+            button: panel.button.unwrap(),
+            dock: panel.dock.unwrap().into(),
+            default_width: panel.default_width.map(px).unwrap(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedLanguageServersPanel {
+    width: Option<Pixels>,
+}
+
+/// The language servers of one worktree, or of none for the ones serving
+/// files outside of the project.
+struct ServerGroup {
+    title: SharedString,
+    servers: Vec<LanguageServerId>,
+}
+
+pub struct LanguageServersPanel {
+    fs: Arc<dyn Fs>,
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    lsp_store: Entity<LspStore>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    health: HashMap<LanguageServerId, (ServerHealth, Option<SharedString>)>,
+    binary_statuses: HashMap<LanguageServerName, BinaryStatus>,
+    process_usage_cache: ProcessUsageCache,
+    /// Re-renders the panel while it's open, so that the resources used by
+    /// the servers stay current.
+    usage_refresh: Option<Task<()>>,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl LanguageServersPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        let serialized_panel = cx
+            .background_spawn(async move { KEY_VALUE_STORE.read_kvp(LANGUAGE_SERVERS_PANEL_KEY) })
+            .await
+            .context("loading language servers panel")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedLanguageServersPanel>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        workspace.update(&mut cx, |workspace, cx| {
+            let panel = Self::new(workspace, cx);
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|width| width.round());
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let project = workspace.project().clone();
+        let lsp_store = project.read(cx).lsp_store();
+        let workspace_handle = workspace.weak_handle();
+        cx.new(|cx| {
+            let subscriptions = vec![
+                cx.subscribe(&lsp_store, Self::on_lsp_store_event),
+                cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+            ];
+            Self {
+                fs,
+                workspace: workspace_handle,
+                project,
+                lsp_store,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                health: HashMap::default(),
+                binary_statuses: HashMap::default(),
+                process_usage_cache: ProcessUsageCache::new(),
+                usage_refresh: None,
+                pending_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            }
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        LANGUAGE_SERVERS_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedLanguageServersPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn on_lsp_store_event(
+        &mut self,
+        _: Entity<LspStore>,
+        event: &LspStoreEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            LspStoreEvent::LanguageServerAdded(..) => cx.notify(),
+            LspStoreEvent::LanguageServerRemoved(server_id) => {
+                self.health.remove(server_id);
+                cx.notify();
+            }
+            LspStoreEvent::LanguageServerUpdate {
+                language_server_id,
+                name,
+                message: proto::update_language_server::Variant::StatusUpdate(status_update),
+            } => {
+                let message = status_update.message.clone().map(SharedString::from);
+                if let Some(binary_status) = binary_status_from_proto(status_update) {
+                    if let Some(name) = name {
+                        self.binary_statuses.insert(name.clone(), binary_status);
+                    }
+                } else if let Some(health) = server_health_from_proto(status_update) {
+                    self.health.insert(*language_server_id, (health, message));
+                }
+                cx.notify();
+            }
+            LspStoreEvent::LanguageServerUpdate {
+                message:
+                    proto::update_language_server::Variant::WorkStart(_)
+                    | proto::update_language_server::Variant::WorkEnd(_),
+                ..
+            } => cx.notify(),
+            _ => {}
+        }
+    }
+
+    fn server_groups(&self, cx: &App) -> Vec<ServerGroup> {
+        let project = self.project.read(cx);
+        let mut servers_by_worktree = HashMap::<Option<WorktreeId>, Vec<_>>::default();
+        for (server_id, status) in self.lsp_store.read(cx).language_server_statuses() {
+            let worktree_id = status
+                .worktree
+                .filter(|worktree_id| project.worktree_for_id(*worktree_id, cx).is_some());
+            servers_by_worktree
+                .entry(worktree_id)
+                .or_default()
+                .push((status.name.clone(), server_id));
+        }
+
+        let mut groups = project
+            .worktrees(cx)
+            .filter_map(|worktree| {
+                let worktree = worktree.read(cx);
+                let servers = servers_by_worktree.remove(&Some(worktree.id()))?;
+                Some((
+                    SharedString::from(worktree.root_name_str().to_string()),
+                    servers,
+                ))
+            })
+            .collect::<Vec<_>>();
+        if let Some(servers) = servers_by_worktree.remove(&None) {
+            groups.push(("Outside the Project".into(), servers));
+        }
+        groups
+            .into_iter()
+            .map(|(title, mut servers)| {
+                servers.sort();
+                ServerGroup {
+                    title,
+                    servers: servers
+                        .into_iter()
+                        .map(|(_, server_id)| server_id)
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+
+    fn restart_server(&mut self, name: LanguageServerName, cx: &mut Context<Self>) {
+        let buffers = self
+            .project
+            .read(cx)
+            .buffer_store()
+            .read(cx)
+            .buffers()
+            .collect();
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.restart_language_servers_for_buffers(
+                buffers,
+                HashSet::from_iter([LanguageServerSelector::Name(name)]),
+                cx,
+            );
+        });
+    }
+
+    fn stop_server(&mut self, server_id: LanguageServerId, cx: &mut Context<Self>) {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store
+                .stop_language_servers_for_buffers(
+                    Vec::new(),
+                    HashSet::from_iter([LanguageServerSelector::Id(server_id)]),
+                    cx,
+                )
+                .detach_and_log_err(cx);
+        });
+    }
+
+    fn open_server_log(
+        &mut self,
+        server_id: LanguageServerId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(log_store) = cx
+            .try_global::<GlobalLogStore>()
+            .map(|store| store.0.clone())
+        else {
+            return;
+        };
+        lsp_log_view::open_server_trace(
+            &log_store,
+            self.workspace.clone(),
+            LanguageServerSelector::Id(server_id),
+            window,
+            cx,
+        );
+    }
+
+    fn status_summary(
+        &self,
+        status: &LanguageServerStatus,
+        server_id: LanguageServerId,
+    ) -> (Color, String) {
+        let binary_status = self
+            .binary_statuses
+            .get(&status.name)
+            .and_then(|binary_status| match binary_status {
+                BinaryStatus::None => None,
+                BinaryStatus::CheckingForUpdate
+                | BinaryStatus::Downloading
+                | BinaryStatus::Starting => Some((Color::Modified, "Starting…".to_string())),
+                BinaryStatus::Stopping => Some((Color::Disabled, "Stopping…".to_string())),
+                BinaryStatus::Stopped => Some((Color::Disabled, "Stopped".to_string())),
+                BinaryStatus::Failed { error } => Some((Color::Error, error.clone())),
+            });
+        binary_status.unwrap_or_else(|| match self.health.get(&server_id) {
+            Some((ServerHealth::Warning, message)) => (
+                Color::Warning,
+                message
+                    .as_ref()
+                    .map_or("Warning".to_string(), |message| message.to_string()),
+            ),
+            Some((ServerHealth::Error, message)) => (
+                Color::Error,
+                message
+                    .as_ref()
+                    .map_or("Error".to_string(), |message| message.to_string()),
+            ),
+            Some((ServerHealth::Ok, _)) | None => (Color::Success, "Running".to_string()),
+        })
+    }
+
+    fn render_server(
+        &mut self,
+        server_id: LanguageServerId,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let lsp_store = self.lsp_store.read(cx);
+        let status = lsp_store
+            .language_server_statuses()
+            .find_map(|(id, status)| {
+                (id == server_id).then(|| {
+                    (
+                        status.name.clone(),
+                        status.server_version.clone(),
+                        status.process_id,
+                        status.pending_work.len(),
+                        self.status_summary(status, server_id),
+                    )
+                })
+            });
+        let (name, version, process_id, pending_work, (status_color, status_label)) = status?;
+        let pending_requests = lsp_store
+            .language_server_for_id(server_id)
+            .map(|server| server.pending_request_count());
+
+        let mut details = vec![status_label];
+        if let Some(process_id) = process_id {
+            let usage = self.process_usage_cache.get_usage(process_id);
+            details.push(format_memory(usage.memory));
+            details.push(format!("{:.1}% CPU", usage.cpu));
+        }
+        match pending_requests {
+            Some(1) => details.push("1 pending request".to_string()),
+            Some(count) if count > 1 => details.push(format!("{count} pending requests")),
+            _ => {}
+        }
+        if pending_work > 0 {
+            details.push(format!("{pending_work} in progress"));
+        }
+
+        Some(
+            ListItem::new(("language-server", server_id.0))
+                .start_slot(Indicator::dot().color(status_color))
+                .child(
+                    v_flex()
+                        .overflow_hidden()
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(Label::new(name.0.clone()).truncate())
+                                .children(version.map(|version| {
+                                    Label::new(format!("v{version}"))
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted)
+                                })),
+                        )
+                        .child(
+                            Label::new(details.join(" • "))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        ),
+                )
+                .end_slot(
+                    h_flex()
+                        .gap_0p5()
+                        .child(
+                            IconButton::new(("restart-server", server_id.0), IconName::RotateCw)
+                                .icon_size(IconSize::Small)
+                                .tooltip(Tooltip::text("Restart Server"))
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.restart_server(name.clone(), cx)
+                                })),
+                        )
+                        .child(
+                            IconButton::new(("stop-server", server_id.0), IconName::Stop)
+                                .icon_size(IconSize::Small)
+                                .tooltip(Tooltip::text("Stop Server"))
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.stop_server(server_id, cx)
+                                })),
+                        )
+                        .child(
+                            IconButton::new(
+                                ("open-server-log", server_id.0),
+                                IconName::FileTextOutlined,
+                            )
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Open Log"))
+                            .on_click(cx.listener(
+                                move |this, _, window, cx| {
+                                    this.open_server_log(server_id, window, cx)
+                                },
+                            )),
+                        ),
+                ),
+        )
+    }
+}
+
+impl Render for LanguageServersPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let groups = self.server_groups(cx);
+        v_flex()
+            .key_context("LanguageServersPanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(
+                h_flex()
+                    .px_2()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new("Language Servers")),
+            )
+            .map(|this| {
+                if groups.is_empty() {
+                    return this.child(
+                        v_flex()
+                            .flex_1()
+                            .justify_center()
+                            .items_center()
+                            .p_4()
+                            .child(Label::new("No language servers running")),
+                    );
+                }
+                let mut entries = v_flex().id("language-servers").flex_1().overflow_y_scroll();
+                for group in groups {
+                    entries = entries.child(
+                        h_flex().px_2().pt_2().pb_1().child(
+                            Label::new(group.title)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                    );
+                    for server_id in group.servers {
+                        entries = entries.children(self.render_server(server_id, cx));
+                    }
+                }
+                this.child(entries)
+            })
+    }
+}
+
+impl Focusable for LanguageServersPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for LanguageServersPanel {}
+
+impl Panel for LanguageServersPanel {
+    fn persistent_name() -> &'static str {
+        "LanguageServersPanel"
+    }
+
+    fn panel_key() -> &'static str {
+        LANGUAGE_SERVERS_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        LanguageServersPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.language_servers_panel.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| LanguageServersPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.usage_refresh = active.then(|| {
+            cx.spawn(async move |this, cx| {
+                loop {
+                    cx.background_executor()
+                        .timer(PROCESS_USAGE_CACHE_DURATION)
+                        .await;
+                    if this.update(cx, |_, cx| cx.notify()).is_err() {
+                        break;
+                    }
+                }
+            })
+        });
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        LanguageServersPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::BoltOutlined)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Language Servers Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        12
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{StreamExt, channel::oneshot};
+    use gpui::{TestAppContext, VisualTestContext, WindowHandle};
+    use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher, tree_sitter_rust};
+    use lsp::DEFAULT_LSP_REQUEST_TIMEOUT;
+    use project::FakeFs;
+    use serde_json::json;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    use super::*;
+
+    #[gpui::test]
+    async fn test_language_servers_panel(cx: &mut TestAppContext) {
+        zlog::init_test();
+
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/the-root"),
+            json!({
+                "test.rs": "",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/the-root").as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        )));
+        let mut fake_rust_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                name: "the-rust-language-server",
+                ..Default::default()
+            },
+        );
+
+        let _rust_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer_with_lsp(path!("/the-root/test.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let language_server = fake_rust_servers.next().await.unwrap();
+        let server_id = language_server.server.server_id();
+
+        let (window, panel) = add_language_servers_panel(&project, cx).await;
+        let cx = &mut VisualTestContext::from_window(window.into(), cx);
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            assert_eq!(
+                server_groups(panel, cx),
+                [("the-root".to_string(), vec![server_id])]
+            );
+        });
+
+        // Requests count as pending until the server responds to them.
+        let (respond_tx, respond_rx) = oneshot::channel::<()>();
+        let mut respond_rx = Some(respond_rx);
+        language_server.set_request_handler::<lsp::request::HoverRequest, _, _>(move |_, _| {
+            let respond_rx = respond_rx.take();
+            async move {
+                if let Some(respond_rx) = respond_rx {
+                    respond_rx.await.ok();
+                }
+                Ok(None)
+            }
+        });
+        let hover = language_server
+            .server
+            .request::<lsp::request::HoverRequest>(
+                lsp::HoverParams {
+                    text_document_position_params: lsp::TextDocumentPositionParams::new(
+                        lsp::TextDocumentIdentifier::new(
+                            lsp::Uri::from_file_path(path!("/the-root/test.rs")).unwrap(),
+                        ),
+                        lsp::Position::new(0, 0),
+                    ),
+                    work_done_progress_params: Default::default(),
+                },
+                DEFAULT_LSP_REQUEST_TIMEOUT,
+            );
+        cx.run_until_parked();
+        assert_eq!(language_server.server.pending_request_count(), 1);
+
+        respond_tx.send(()).unwrap();
+        hover.await.into_response().unwrap();
+        assert_eq!(language_server.server.pending_request_count(), 0);
+
+        panel.update(cx, |panel, cx| panel.stop_server(server_id, cx));
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            assert!(server_groups(panel, cx).is_empty());
+        });
+    }
+
+    fn server_groups(
+        panel: &LanguageServersPanel,
+        cx: &App,
+    ) -> Vec<(String, Vec<LanguageServerId>)> {
+        panel
+            .server_groups(cx)
+            .into_iter()
+            .map(|group| (group.title.to_string(), group.servers))
+            .collect()
+    }
+
+    async fn add_language_servers_panel(
+        project: &Entity<Project>,
+        cx: &mut TestAppContext,
+    ) -> (WindowHandle<MultiWorkspace>, Entity<LanguageServersPanel>) {
+        let window =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window
+            .read_with(cx, |multi_workspace, _| multi_workspace.workspace().clone())
+            .unwrap();
+        let workspace_weak = workspace.downgrade();
+        let panel = window
+            .update(cx, |_, window, cx| {
+                cx.spawn_in(window, async move |_, cx| {
+                    LanguageServersPanel::load(workspace_weak, cx.clone()).await
+                })
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        (window, panel)
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            release_channel::init(semver::Version::new(0, 0, 0), cx);
+        });
+    }
+}
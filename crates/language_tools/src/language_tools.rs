@@ -1,5 +1,6 @@
 mod highlights_tree_view;
 mod key_context_view;
+pub mod language_servers_panel;
 pub mod lsp_button;
 pub mod lsp_log_view;
 mod syntax_tree_view;
@@ -10,6 +11,7 @@ mod lsp_log_view_tests;
 use gpui::{App, AppContext, Entity};
 
 pub use highlights_tree_view::{HighlightsTreeToolbarItemView, HighlightsTreeView};
+pub use language_servers_panel::LanguageServersPanel;
 pub use lsp_log_view::LspLogView;
 pub use syntax_tree_view::{SyntaxTreeToolbarItemView, SyntaxTreeView};
 use ui::{Context, Window};
@@ -20,6 +22,7 @@ pub fn init(cx: &mut App) {
     lsp_log_view::init(false, cx);
     syntax_tree_view::init(cx);
     key_context_view::init(cx);
+    language_servers_panel::init(cx);
 }
 
 fn get_or_create_tool<T>(
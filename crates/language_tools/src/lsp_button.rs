@@ -52,7 +52,7 @@ struct LanguageServerState {
     lsp_store: WeakEntity<LspStore>,
     active_editor: Option<ActiveEditor>,
     language_servers: LanguageServers,
-    process_memory_cache: Rc<RefCell<ProcessMemoryCache>>,
+    process_usage_cache: Rc<RefCell<ProcessUsageCache>>,
 }
 
 impl std::fmt::Debug for LanguageServerState {
@@ -67,39 +67,51 @@ impl std::fmt::Debug for LanguageServerState {
     }
 }
 
-const PROCESS_MEMORY_CACHE_DURATION: Duration = Duration::from_secs(5);
+pub(crate) const PROCESS_USAGE_CACHE_DURATION: Duration = Duration::from_secs(5);
 
-struct ProcessMemoryCache {
+/// The resources used by a language server process and all of its descendants.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ProcessUsage {
+    pub memory: u64,
+    /// The percentage of a single CPU core used since the previous refresh.
+    pub cpu: f32,
+}
+
+pub(crate) struct ProcessUsageCache {
     system: System,
-    memory_usage: HashMap<u32, u64>,
+    usage: HashMap<u32, ProcessUsage>,
     last_refresh: Option<Instant>,
 }
 
-impl ProcessMemoryCache {
-    fn new() -> Self {
+impl ProcessUsageCache {
+    pub(crate) fn new() -> Self {
         Self {
             system: System::new(),
-            memory_usage: HashMap::new(),
+            usage: HashMap::new(),
             last_refresh: None,
         }
     }
 
-    fn get_memory_usage(&mut self, process_id: u32) -> u64 {
+    pub(crate) fn get_usage(&mut self, process_id: u32) -> ProcessUsage {
         let cache_expired = self
             .last_refresh
-            .map(|last| last.elapsed() >= PROCESS_MEMORY_CACHE_DURATION)
+            .map(|last| last.elapsed() >= PROCESS_USAGE_CACHE_DURATION)
             .unwrap_or(true);
 
         if cache_expired {
-            let refresh_kind = RefreshKind::nothing()
-                .with_processes(ProcessRefreshKind::nothing().without_tasks().with_memory());
+            let refresh_kind = RefreshKind::nothing().with_processes(
+                ProcessRefreshKind::nothing()
+                    .without_tasks()
+                    .with_memory()
+                    .with_cpu(),
+            );
             self.system.refresh_specifics(refresh_kind);
-            self.memory_usage.clear();
+            self.usage.clear();
             self.last_refresh = Some(Instant::now());
         }
 
-        if let Some(&memory) = self.memory_usage.get(&process_id) {
-            return memory;
+        if let Some(&usage) = self.usage.get(&process_id) {
+            return usage;
         }
 
         let root_pid = Pid::from_u32(process_id);
@@ -111,16 +123,20 @@ impl ProcessMemoryCache {
             .filter_map(|(&pid, process)| Some((pid, process.parent()?)))
             .collect();
 
-        let total_memory = self
+        let total_usage = self
             .system
             .processes()
             .iter()
             .filter(|(pid, _)| self.is_descendant_of(**pid, root_pid, &parent_map))
-            .map(|(_, process)| process.memory())
-            .sum();
+            .fold(ProcessUsage::default(), |usage, (_, process)| {
+                ProcessUsage {
+                    memory: usage.memory + process.memory(),
+                    cpu: usage.cpu + process.cpu_usage(),
+                }
+            });
 
-        self.memory_usage.insert(process_id, total_memory);
-        total_memory
+        self.usage.insert(process_id, total_usage);
+        total_usage
     }
 
     fn is_descendant_of(&self, pid: Pid, root_pid: Pid, parent_map: &HashMap<Pid, Pid>) -> bool {
@@ -240,7 +256,7 @@ impl LanguageServerState {
             })
             .unwrap_or_default();
 
-        let process_memory_cache = self.process_memory_cache.clone();
+        let process_usage_cache = self.process_usage_cache.clone();
 
         let mut first_button_encountered = false;
         for item in &self.items {
@@ -356,7 +372,7 @@ impl LanguageServerState {
                     let lsp_store = self.lsp_store.clone();
                     let state = cx.entity().downgrade();
                     let can_stop = submenu_server_info.can_stop();
-                    let process_memory_cache = process_memory_cache.clone();
+                    let process_usage_cache = process_usage_cache.clone();
 
                     move |menu, _window, _cx| {
                         let mut submenu = menu;
@@ -550,21 +566,12 @@ impl LanguageServerState {
                             let binary_path = binary_path.clone();
                             let server_version = server_version.clone();
                             let truncated_message = truncated_message.clone();
-                            let process_memory_cache = process_memory_cache.clone();
+                            let process_usage_cache = process_usage_cache.clone();
                             move |_, _| {
-                                let memory_usage = process_id.map(|pid| {
-                                    process_memory_cache.borrow_mut().get_memory_usage(pid)
-                                });
-
-                                let memory_label = memory_usage.map(|bytes| {
-                                    if bytes >= 1024 * 1024 * 1024 {
-                                        format!(
-                                            "{:.1} GB",
-                                            bytes as f64 / (1024.0 * 1024.0 * 1024.0)
-                                        )
-                                    } else {
-                                        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
-                                    }
+                                let memory_label = process_id.map(|pid| {
+                                    format_memory(
+                                        process_usage_cache.borrow_mut().get_usage(pid).memory,
+                                    )
                                 });
 
                                 let metadata_label =
@@ -641,6 +648,48 @@ impl LanguageServerState {
     }
 }
 
+pub(crate) fn format_memory(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 * 1024 {
+        format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// The binary status carried by a language server status update, if any.
+pub(crate) fn binary_status_from_proto(
+    status_update: &proto::StatusUpdate,
+) -> Option<BinaryStatus> {
+    let Some(proto::status_update::Status::Binary(binary_status)) = &status_update.status else {
+        return None;
+    };
+    Some(match proto::ServerBinaryStatus::from_i32(*binary_status)? {
+        proto::ServerBinaryStatus::None => BinaryStatus::None,
+        proto::ServerBinaryStatus::CheckingForUpdate => BinaryStatus::CheckingForUpdate,
+        proto::ServerBinaryStatus::Downloading => BinaryStatus::Downloading,
+        proto::ServerBinaryStatus::Starting => BinaryStatus::Starting,
+        proto::ServerBinaryStatus::Stopping => BinaryStatus::Stopping,
+        proto::ServerBinaryStatus::Stopped => BinaryStatus::Stopped,
+        proto::ServerBinaryStatus::Failed => BinaryStatus::Failed {
+            error: status_update.message.clone()?,
+        },
+    })
+}
+
+/// The health carried by a language server status update, if any.
+pub(crate) fn server_health_from_proto(
+    status_update: &proto::StatusUpdate,
+) -> Option<ServerHealth> {
+    let Some(proto::status_update::Status::Health(health)) = &status_update.status else {
+        return None;
+    };
+    Some(match proto::ServerHealth::from_i32(*health)? {
+        proto::ServerHealth::Ok => ServerHealth::Ok,
+        proto::ServerHealth::Warning => ServerHealth::Warning,
+        proto::ServerHealth::Error => ServerHealth::Error,
+    })
+}
+
 impl LanguageServers {
     fn update_binary_status(
         &mut self,
@@ -828,7 +877,7 @@ impl LspButton {
             lsp_store: lsp_store.downgrade(),
             active_editor: None,
             language_servers,
-            process_memory_cache: Rc::new(RefCell::new(ProcessMemoryCache::new())),
+            process_usage_cache: Rc::new(RefCell::new(ProcessUsageCache::new())),
         });
 
         let mut lsp_button = Self {
@@ -869,59 +918,31 @@ impl LspButton {
                 language_server_id,
                 name,
                 message: proto::update_language_server::Variant::StatusUpdate(status_update),
-            } => match &status_update.status {
-                Some(proto::status_update::Status::Binary(binary_status)) => {
+            } => {
+                if let Some(binary_status) = binary_status_from_proto(status_update) {
                     let Some(name) = name.as_ref() else {
                         return;
                     };
-                    if let Some(binary_status) = proto::ServerBinaryStatus::from_i32(*binary_status)
-                    {
-                        let binary_status = match binary_status {
-                            proto::ServerBinaryStatus::None => BinaryStatus::None,
-                            proto::ServerBinaryStatus::CheckingForUpdate => {
-                                BinaryStatus::CheckingForUpdate
-                            }
-                            proto::ServerBinaryStatus::Downloading => BinaryStatus::Downloading,
-                            proto::ServerBinaryStatus::Starting => BinaryStatus::Starting,
-                            proto::ServerBinaryStatus::Stopping => BinaryStatus::Stopping,
-                            proto::ServerBinaryStatus::Stopped => BinaryStatus::Stopped,
-                            proto::ServerBinaryStatus::Failed => {
-                                let Some(error) = status_update.message.clone() else {
-                                    return;
-                                };
-                                BinaryStatus::Failed { error }
-                            }
-                        };
-                        self.server_state.update(cx, |state, _| {
-                            state.language_servers.update_binary_status(
-                                binary_status,
-                                status_update.message.as_deref(),
-                                name.clone(),
-                            );
-                        });
-                        updated = true;
-                    };
-                }
-                Some(proto::status_update::Status::Health(health_status)) => {
-                    if let Some(health) = proto::ServerHealth::from_i32(*health_status) {
-                        let health = match health {
-                            proto::ServerHealth::Ok => ServerHealth::Ok,
-                            proto::ServerHealth::Warning => ServerHealth::Warning,
-                            proto::ServerHealth::Error => ServerHealth::Error,
-                        };
-                        self.server_state.update(cx, |state, _| {
-                            state.language_servers.update_server_health(
-                                *language_server_id,
-                                health,
-                                status_update.message.as_deref(),
-                                name.clone(),
-                            );
-                        });
-                        updated = true;
-                    }
+                    self.server_state.update(cx, |state, _| {
+                        state.language_servers.update_binary_status(
+                            binary_status,
+                            status_update.message.as_deref(),
+                            name.clone(),
+                        );
+                    });
+                    updated = true;
+                } else if let Some(health) = server_health_from_proto(status_update) {
+                    self.server_state.update(cx, |state, _| {
+                        state.language_servers.update_server_health(
+                            *language_server_id,
+                            health,
+                            status_update.message.as_deref(),
+                            name.clone(),
+                        );
+                    });
+                    updated = true;
                 }
-                None => {}
-            },
+            }
             LspStoreEvent::LanguageServerUpdate {
                 language_server_id,
                 name,
@@ -0,0 +1,300 @@
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, Focusable, Render, Task, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, SaveLayoutAs, SwitchLayout, Workspace};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &SaveLayoutAs, window, cx| {
+                toggle(workspace, Mode::Save, window, cx);
+            })
+            .register_action(|workspace, _: &SwitchLayout, window, cx| {
+                toggle(workspace, Mode::Switch, window, cx);
+            });
+    })
+    .detach();
+}
+
+fn toggle(workspace: &mut Workspace, mode: Mode, window: &mut Window, cx: &mut Context<Workspace>) {
+    let layout_names = workspace.saved_layout_names();
+    let weak_workspace = cx.entity().downgrade();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        let delegate = LayoutSelectorDelegate::new(
+            cx.entity().downgrade(),
+            weak_workspace,
+            mode,
+            layout_names,
+        );
+        LayoutSelector::new(delegate, window, cx)
+    });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Save,
+    Switch,
+}
+
+pub struct LayoutSelector {
+    picker: Entity<Picker<LayoutSelectorDelegate>>,
+}
+
+impl ModalView for LayoutSelector {}
+
+impl EventEmitter<DismissEvent> for LayoutSelector {}
+
+impl Focusable for LayoutSelector {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for LayoutSelector {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(22.)).child(self.picker.clone())
+    }
+}
+
+impl LayoutSelector {
+    fn new(delegate: LayoutSelectorDelegate, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+pub struct LayoutSelectorDelegate {
+    selector: WeakEntity<LayoutSelector>,
+    workspace: WeakEntity<Workspace>,
+    mode: Mode,
+    layout_names: Vec<String>,
+    matches: Vec<StringMatch>,
+    /// In save mode, the typed name when no layout is saved under it yet.
+    new_layout_name: Option<String>,
+    selected_index: usize,
+}
+
+impl LayoutSelectorDelegate {
+    fn new(
+        selector: WeakEntity<LayoutSelector>,
+        workspace: WeakEntity<Workspace>,
+        mode: Mode,
+        layout_names: Vec<String>,
+    ) -> Self {
+        let matches = layout_names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| StringMatch {
+                candidate_id: index,
+                score: 0.0,
+                positions: Vec::new(),
+                string: name.clone(),
+            })
+            .collect();
+        Self {
+            selector,
+            workspace,
+            mode,
+            layout_names,
+            matches,
+            new_layout_name: None,
+            selected_index: 0,
+        }
+    }
+
+    /// The name of the layout at the given index, counting the new layout
+    /// entry that comes first when there is one.
+    fn layout_name(&self, index: usize) -> Option<&str> {
+        match (&self.new_layout_name, index) {
+            (Some(name), 0) => Some(name),
+            (Some(_), index) => Some(&self.matches.get(index - 1)?.string),
+            (None, index) => Some(&self.matches.get(index)?.string),
+        }
+    }
+
+    fn delete_layout(&mut self, name: String, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(task) = self
+            .workspace
+            .update(cx, |workspace, cx| {
+                workspace.delete_layout(name.clone(), cx)
+            })
+            .ok()
+        else {
+            return;
+        };
+        cx.spawn_in(window, async move |picker, cx| {
+            task.await?;
+            picker.update_in(cx, |picker, window, cx| {
+                picker
+                    .delegate
+                    .layout_names
+                    .retain(|layout_name| layout_name != &name);
+                picker.refresh(window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+impl PickerDelegate for LayoutSelectorDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _: &mut Window, _: &mut App) -> std::sync::Arc<str> {
+        match self.mode {
+            Mode::Save => "Name the layout…".into(),
+            Mode::Switch => "Switch to a layout…".into(),
+        }
+    }
+
+    fn no_matches_text(&self, _: &mut Window, _: &mut App) -> Option<SharedString> {
+        match self.mode {
+            Mode::Save => Some("Type a name to save the layout".into()),
+            Mode::Switch => Some("No saved layouts".into()),
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len() + usize::from(self.new_layout_name.is_some())
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, index: usize, _: &mut Window, _: &mut Context<Picker<Self>>) {
+        self.selected_index = index;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .layout_names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| StringMatchCandidate::new(id, name))
+            .collect::<Vec<_>>();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let query = query.trim().to_string();
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, _| {
+                let delegate = &mut this.delegate;
+                delegate.new_layout_name = (delegate.mode == Mode::Save
+                    && !query.is_empty()
+                    && !delegate.layout_names.contains(&query))
+                .then_some(query);
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.match_count().saturating_sub(1));
+            })
+            .ok();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(name) = self.layout_name(self.selected_index).map(str::to_string) else {
+            return;
+        };
+        let mode = self.mode;
+        self.workspace
+            .update(cx, |workspace, cx| match mode {
+                Mode::Save => workspace
+                    .save_layout(name, window, cx)
+                    .detach_and_log_err(cx),
+                Mode::Switch => {
+                    if let Err(error) = workspace.restore_layout(&name, window, cx) {
+                        workspace.show_error(&error, cx);
+                    }
+                }
+            })
+            .log_err();
+        self.selector.update(cx, |_, cx| cx.emit(DismissEvent)).ok();
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.selector.update(cx, |_, cx| cx.emit(DismissEvent)).ok();
+    }
+
+    fn render_match(
+        &self,
+        index: usize,
+        selected: bool,
+        _: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let list_item = ListItem::new(index)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+
+        if let Some(new_layout_name) = self.new_layout_name.as_ref().filter(|_| index == 0) {
+            return Some(
+                list_item
+                    .start_slot(Icon::new(IconName::Plus).color(Color::Muted))
+                    .child(Label::new(format!("Save as \"{new_layout_name}\""))),
+            );
+        }
+
+        let layout_match = self
+            .matches
+            .get(index - usize::from(self.new_layout_name.is_some()))?;
+        let name = layout_match.string.clone();
+        Some(
+            list_item
+                .child(HighlightedLabel::new(
+                    layout_match.string.clone(),
+                    layout_match.positions.clone(),
+                ))
+                .when(self.mode == Mode::Save, |list_item| {
+                    list_item.end_slot(
+                        Label::new("Overwrite")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                })
+                .when(self.mode == Mode::Switch, |list_item| {
+                    list_item.end_hover_slot(
+                        IconButton::new(("delete-layout", index), IconName::Trash)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Delete Layout"))
+                            .on_click(cx.listener(move |picker, _, window, cx| {
+                                cx.stop_propagation();
+                                picker.delegate.delete_layout(name.clone(), window, cx);
+                            })),
+                    )
+                }),
+        )
+    }
+}
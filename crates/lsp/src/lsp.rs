@@ -341,6 +341,53 @@ pub struct AdapterServerCapabilities {
     pub code_action_kinds: Option<Vec<CodeActionKind>>,
 }
 
+/// `lsp-types` has no field for the `typeHierarchyProvider` server capability,
+/// so it is kept among the experimental capabilities, under the same name.
+const TYPE_HIERARCHY_PROVIDER: &str = "typeHierarchyProvider";
+
+/// Whether the server advertised `typeHierarchyProvider`, on initialization
+/// or through a dynamic registration.
+pub fn supports_type_hierarchy(capabilities: &ServerCapabilities) -> bool {
+    capabilities
+        .experimental
+        .as_ref()
+        .and_then(|experimental| experimental.get(TYPE_HIERARCHY_PROVIDER))
+        .is_some_and(|provider| match provider {
+            Value::Bool(supported) => *supported,
+            Value::Object(_) => true,
+            _ => false,
+        })
+}
+
+/// Records the `typeHierarchyProvider` capability, or its removal when `None`.
+pub fn set_type_hierarchy_provider(capabilities: &mut ServerCapabilities, provider: Option<Value>) {
+    match provider {
+        Some(provider) => {
+            let experimental = capabilities
+                .experimental
+                .get_or_insert_with(|| Value::Object(Default::default()));
+            if let Value::Object(experimental) = experimental {
+                experimental.insert(TYPE_HIERARCHY_PROVIDER.to_string(), provider);
+            }
+        }
+        None => {
+            if let Some(Value::Object(experimental)) = capabilities.experimental.as_mut() {
+                experimental.remove(TYPE_HIERARCHY_PROVIDER);
+            }
+        }
+    }
+}
+
+/// The `initialize` request, with a reply that is kept untyped to read the
+/// capabilities `lsp-types` doesn't model.
+enum RawInitialize {}
+
+impl request::Request for RawInitialize {
+    type Params = InitializeParams;
+    type Result = Value;
+    const METHOD: &'static str = request::Initialize::METHOD;
+}
+
 // See the VSCode docs [1] and the LSP Spec [2]
 //
 // [1]: https://code.visualstudio.com/api/language-extensions/semantic-highlight-guide#standard-token-types-and-modifiers
@@ -1000,6 +1047,15 @@ impl LanguageServer {
                     color_provider: Some(DocumentColorClientCapabilities {
                         dynamic_registration: Some(true),
                     }),
+                    call_hierarchy: Some(DynamicRegistrationClientCapabilities {
+                        dynamic_registration: Some(true),
+                    }),
+                    type_hierarchy: Some(DynamicRegistrationClientCapabilities {
+                        dynamic_registration: Some(true),
+                    }),
+                    inline_value: Some(InlineValueClientCapabilities {
+                        dynamic_registration: None,
+                    }),
                     folding_range: Some(FoldingRangeClientCapabilities {
                         dynamic_registration: Some(true),
                         line_folding_only: Some(false),
@@ -1057,7 +1113,7 @@ impl LanguageServer {
     ) -> Task<Result<Arc<Self>>> {
         cx.background_spawn(async move {
             let response = self
-                .request::<request::Initialize>(params, timeout)
+                .request::<RawInitialize>(params, timeout)
                 .await
                 .into_response()
                 .with_context(|| {
@@ -1067,6 +1123,14 @@ impl LanguageServer {
                         self.server_id()
                     )
                 })?;
+            let type_hierarchy_provider = response
+                .pointer("/capabilities/typeHierarchyProvider")
+                .cloned();
+            let mut response = serde_json::from_value::<InitializeResult>(response)
+                .with_context(|| format!("parsing the initialize response of {}", self.name()))?;
+            if let Some(provider) = type_hierarchy_provider {
+                set_type_hierarchy_provider(&mut response.capabilities, Some(provider));
+            }
             if let Some(info) = response.server_info {
                 self.version = info.version.map(SharedString::from);
                 self.process_name = info.name.into();
@@ -1346,6 +1410,15 @@ impl LanguageServer {
         self.server.lock().as_ref().map(|child| child.id())
     }
 
+    /// Get the number of requests sent to the running language server that
+    /// it hasn't responded to yet.
+    pub fn pending_request_count(&self) -> usize {
+        self.response_handlers
+            .lock()
+            .as_ref()
+            .map_or(0, |handlers| handlers.len())
+    }
+
     /// Get the binary information of the running language server.
     pub fn binary(&self) -> &LanguageServerBinary {
         &self.binary
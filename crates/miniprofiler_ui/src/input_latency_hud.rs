@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use gpui::{InputLatencyStats, Task};
+use workspace::{
+    StatusItemView,
+    item::ItemHandle,
+    ui::{Tooltip, prelude::*},
+};
+
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A status bar readout of how long input takes to show up on screen, for
+/// tuning the `presentation_mode` setting.
+#[derive(Default)]
+pub struct InputLatencyHud {
+    refresh: Option<Task<()>>,
+}
+
+impl InputLatencyHud {
+    pub fn toggle(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let measures = self.refresh.take().is_none();
+        window.set_measures_input_latency(measures);
+        if measures {
+            self.refresh = Some(cx.spawn(async move |this, cx| {
+                loop {
+                    cx.background_executor().timer(REFRESH_INTERVAL).await;
+                    if this.update(cx, |_, cx| cx.notify()).is_err() {
+                        break;
+                    }
+                }
+            }));
+        }
+        cx.notify();
+    }
+}
+
+impl Render for InputLatencyHud {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.refresh.is_none() {
+            return div().into_any_element();
+        }
+
+        let text = match window.input_latency() {
+            Some(InputLatencyStats { p50, p90, p99, .. }) => format!(
+                "p50 {} · p90 {} · p99 {}",
+                format_latency(p50),
+                format_latency(p90),
+                format_latency(p99)
+            ),
+            None => "No input measured yet".to_string(),
+        };
+        h_flex()
+            .id("input-latency-hud")
+            .child(
+                Label::new(text)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .tooltip(Tooltip::text(
+                "Time from input to the screen, over recent frames",
+            ))
+            .into_any_element()
+    }
+}
+
+fn format_latency(latency: Duration) -> String {
+    format!("{:.1}ms", latency.as_secs_f64() * 1000.)
+}
+
+impl StatusItemView for InputLatencyHud {
+    fn set_active_pane_item(
+        &mut self,
+        _active_pane_item: Option<&dyn ItemHandle>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+}
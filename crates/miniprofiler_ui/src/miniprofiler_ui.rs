@@ -1,3 +1,5 @@
+mod input_latency_hud;
+
 use std::{
     ops::Range,
     path::PathBuf,
@@ -21,6 +23,8 @@ use workspace::{
 };
 use zed_actions::OpenPerformanceProfiler;
 
+pub use input_latency_hud::InputLatencyHud;
+
 pub fn init(startup_time: Instant, cx: &mut App) {
     cx.observe_new(move |workspace: &mut workspace::Workspace, _, cx| {
         let workspace_handle = cx.entity().downgrade();
@@ -33,6 +33,15 @@ pub fn init(cx: &mut App) {
             toggle(editor, &Default::default(), window, cx);
         })
         .ok();
+    zed_actions::outline::TOGGLE_OUTLINE_IN_SCOPE
+        .set(|view, symbol_index, window, cx| {
+            let Ok(editor) = view.downcast::<Editor>() else {
+                return;
+            };
+
+            toggle_in_scope(editor, symbol_index, window, cx);
+        })
+        .ok();
 }
 
 pub fn toggle(
@@ -76,6 +85,55 @@ pub fn toggle(
         .detach();
 }
 
+/// Shows the outline of the symbols within the breadcrumb symbol at the given index.
+pub fn toggle_in_scope(
+    editor: Entity<Editor>,
+    symbol_index: usize,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let Some(workspace) = editor.read(cx).workspace() else {
+        return;
+    };
+    let Some(scope) = editor
+        .read(cx)
+        .outline_symbols_at_cursor()
+        .get(symbol_index)
+        .map(|symbol| symbol.range.clone())
+    else {
+        return;
+    };
+    let Some(task) = outline_for_editor(&editor, cx) else {
+        return;
+    };
+    window
+        .spawn(cx, async move |cx| {
+            let items = task.await;
+            cx.update(|window, cx| {
+                let buffer = editor.read(cx).buffer().read(cx).snapshot(cx);
+                let scope = scope.to_offset(&buffer);
+                let items = items
+                    .into_iter()
+                    .filter(|item| {
+                        let range = item.range.to_offset(&buffer);
+                        scope.start <= range.start && range.end <= scope.end
+                    })
+                    .collect::<Vec<_>>();
+                if items.is_empty() {
+                    return;
+                }
+                let outline = Outline::new(items);
+                workspace.update(cx, |workspace, cx| {
+                    workspace.toggle_modal(window, cx, |window, cx| {
+                        OutlineView::new(outline, editor, window, cx)
+                    });
+                });
+            })
+            .ok();
+        })
+        .detach();
+}
+
 fn outline_for_editor(
     editor: &Entity<Editor>,
     cx: &mut App,
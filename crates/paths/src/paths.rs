@@ -304,12 +304,27 @@ pub fn themes_dir() -> &'static PathBuf {
     THEMES_DIR.get_or_init(|| config_dir().join("themes"))
 }
 
+/// Returns the path to the icon themes directory.
+///
+/// This is where icon themes that are not provided by extensions are stored,
+/// along with the icons they refer to.
+pub fn icon_themes_dir() -> &'static PathBuf {
+    static ICON_THEMES_DIR: OnceLock<PathBuf> = OnceLock::new();
+    ICON_THEMES_DIR.get_or_init(|| config_dir().join("icon_themes"))
+}
+
 /// Returns the path to the snippets directory.
 pub fn snippets_dir() -> &'static PathBuf {
     static SNIPPETS_DIR: OnceLock<PathBuf> = OnceLock::new();
     SNIPPETS_DIR.get_or_init(|| config_dir().join("snippets"))
 }
 
+/// Returns the path to the spell checking dictionaries directory.
+pub fn dictionaries_dir() -> &'static PathBuf {
+    static DICTIONARIES_DIR: OnceLock<PathBuf> = OnceLock::new();
+    DICTIONARIES_DIR.get_or_init(|| config_dir().join("dictionaries"))
+}
+
 // Returns old path to contexts directory.
 // Fallback
 fn text_threads_dir_fallback() -> &'static PathBuf {
@@ -397,6 +412,13 @@ pub fn languages_dir() -> &'static PathBuf {
     LANGUAGES_DIR.get_or_init(|| data_dir().join("languages"))
 }
 
+/// Returns the path to the directory where users override the queries of
+/// languages, like their `folds.scm`.
+pub fn user_languages_dir() -> &'static PathBuf {
+    static USER_LANGUAGES_DIR: OnceLock<PathBuf> = OnceLock::new();
+    USER_LANGUAGES_DIR.get_or_init(|| config_dir().join("languages"))
+}
+
 /// Returns the path to the debug adapters directory
 ///
 /// This is where debug adapters are downloaded to for DAPs that are built-in to Zed.
@@ -437,6 +459,13 @@ pub fn remote_servers_dir() -> &'static PathBuf {
     REMOTE_SERVERS_DIR.get_or_init(|| data_dir().join("remote_servers"))
 }
 
+/// Returns the path to the directory of the hooks that interactive terminal
+/// shells source for their shell integration.
+pub fn shell_integration_dir() -> &'static PathBuf {
+    static SHELL_INTEGRATION_DIR: OnceLock<PathBuf> = OnceLock::new();
+    SHELL_INTEGRATION_DIR.get_or_init(|| data_dir().join("shell_integration"))
+}
+
 /// Returns the path to the directory where the devcontainer CLI is installed.
 pub fn devcontainer_dir() -> &'static PathBuf {
     static DEVCONTAINER_DIR: OnceLock<PathBuf> = OnceLock::new();
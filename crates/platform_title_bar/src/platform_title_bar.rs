@@ -1,3 +1,4 @@
+mod platform_title_bar_settings;
 mod platforms;
 mod system_window_tabs;
 
@@ -7,18 +8,19 @@ use gpui::{
     MouseButton, ParentElement, StatefulInteractiveElement, Styled, Window, WindowControlArea, div,
     px,
 };
+use settings::{
+    FullscreenWindowControls, Settings, TitleBarDoubleClickAction, TitleBarMiddleClickAction,
+};
 use smallvec::SmallVec;
 use std::mem;
-use ui::{
-    prelude::*,
-    utils::{TRAFFIC_LIGHT_PADDING, platform_title_bar_height},
-};
+use ui::{prelude::*, utils::TRAFFIC_LIGHT_PADDING};
 
 use crate::{
     platforms::{platform_linux, platform_windows},
     system_window_tabs::SystemWindowTabs,
 };
 
+pub use platform_title_bar_settings::{PlatformTitleBarSettings, title_bar_height};
 pub use system_window_tabs::{
     DraggedWindowTab, MergeAllWindows, MoveTabToNewWindow, ShowNextWindowTab, ShowPreviousWindowTab,
 };
@@ -31,6 +33,7 @@ pub struct PlatformTitleBar {
     system_window_tabs: Entity<SystemWindowTabs>,
     workspace_sidebar_open: bool,
     sidebar_has_notifications: bool,
+    zen_mode: bool,
 }
 
 impl PlatformTitleBar {
@@ -46,6 +49,7 @@ impl PlatformTitleBar {
             system_window_tabs,
             workspace_sidebar_open: false,
             sidebar_has_notifications: false,
+            zen_mode: false,
         }
     }
 
@@ -94,19 +98,54 @@ impl PlatformTitleBar {
         cx.notify();
     }
 
+    /// Zen mode collapses the window controls like fullscreen does.
+    pub fn set_zen_mode(&mut self, zen_mode: bool) {
+        self.zen_mode = zen_mode;
+    }
+
     pub fn is_multi_workspace_enabled(cx: &App) -> bool {
         cx.has_flag::<AgentV2FeatureFlag>()
     }
 }
 
+/// Whether the window controls are shown, and whether the reveal strip stands
+/// in for them while they're collapsed in fullscreen or zen mode.
+fn window_controls_visibility(
+    collapsed: bool,
+    fullscreen_controls: FullscreenWindowControls,
+    platform_style: PlatformStyle,
+    decorations: Decorations,
+) -> (bool, bool) {
+    if !collapsed {
+        return (true, false);
+    }
+    match fullscreen_controls {
+        FullscreenWindowControls::Visible => (true, false),
+        FullscreenWindowControls::Reveal => (
+            false,
+            platform_style == PlatformStyle::Linux
+                && matches!(decorations, Decorations::Client { .. }),
+        ),
+        FullscreenWindowControls::Hidden => (false, false),
+    }
+}
+
 impl Render for PlatformTitleBar {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let supported_controls = window.window_controls();
         let decorations = window.window_decorations();
-        let height = platform_title_bar_height(window);
+        let height = title_bar_height(window, cx);
         let titlebar_color = self.title_bar_color(window, cx);
         let close_action = Box::new(workspace::CloseWindow);
         let children = mem::take(&mut self.children);
+        let platform_style = self.platform_style;
+        let settings = *PlatformTitleBarSettings::get_global(cx);
+        let (show_window_controls, show_reveal_strip) = window_controls_visibility(
+            window.is_fullscreen() || self.zen_mode,
+            settings.fullscreen_controls,
+            platform_style,
+            decorations,
+        );
 
         let is_multiworkspace_sidebar_open =
             PlatformTitleBar::is_multi_workspace_enabled(cx) && self.is_workspace_sidebar_open();
@@ -141,17 +180,21 @@ impl Render for PlatformTitleBar {
             .map(|this| {
                 // Note: On Windows the title bar behavior is handled by the platform implementation.
                 this.id(self.id.clone())
-                    .when(self.platform_style == PlatformStyle::Mac, |this| {
-                        this.on_click(|event, window, _| {
+                    .when(platform_style != PlatformStyle::Windows, |this| {
+                        this.on_click(move |event, window, _| {
                             if event.click_count() == 2 {
-                                window.titlebar_double_click();
+                                perform_double_click_action(
+                                    settings.double_click_action,
+                                    platform_style,
+                                    window,
+                                );
                             }
                         })
                     })
-                    .when(self.platform_style == PlatformStyle::Linux, |this| {
-                        this.on_click(|event, window, _| {
-                            if event.click_count() == 2 {
-                                window.zoom_window();
+                    .when(platform_style == PlatformStyle::Linux, |this| {
+                        this.on_aux_click(move |event, window, _| {
+                            if event.is_middle_click() {
+                                perform_middle_click_action(settings.middle_click_action, window);
                             }
                         })
                     })
@@ -196,7 +239,7 @@ impl Render for PlatformTitleBar {
                     .w_full()
                     .children(children),
             )
-            .when(!window.is_fullscreen(), |title_bar| {
+            .when(show_window_controls, |title_bar| {
                 match self.platform_style {
                     PlatformStyle::Mac => title_bar,
                     PlatformStyle::Linux => {
@@ -217,6 +260,15 @@ impl Render for PlatformTitleBar {
                         title_bar.child(platform_windows::WindowsWindowControls::new(height))
                     }
                 }
+            })
+            .when(show_reveal_strip, |title_bar| {
+                title_bar
+                    .relative()
+                    .child(platform_linux::LinuxWindowControlsRevealStrip::new(
+                        Box::new(workspace::CloseWindow),
+                        height,
+                        titlebar_color,
+                    ))
             });
 
         v_flex()
@@ -226,8 +278,96 @@ impl Render for PlatformTitleBar {
     }
 }
 
+fn perform_double_click_action(
+    action: TitleBarDoubleClickAction,
+    platform_style: PlatformStyle,
+    window: &mut Window,
+) {
+    match action {
+        TitleBarDoubleClickAction::PlatformDefault => {
+            if platform_style == PlatformStyle::Mac {
+                window.titlebar_double_click();
+            } else {
+                window.zoom_window();
+            }
+        }
+        TitleBarDoubleClickAction::Maximize => window.zoom_window(),
+        TitleBarDoubleClickAction::Minimize => window.minimize_window(),
+        TitleBarDoubleClickAction::None => {}
+    }
+}
+
+fn perform_middle_click_action(action: TitleBarMiddleClickAction, window: &mut Window) {
+    match action {
+        TitleBarMiddleClickAction::Lower => window.lower_window(),
+        TitleBarMiddleClickAction::Minimize => window.minimize_window(),
+        TitleBarMiddleClickAction::Maximize => window.zoom_window(),
+        TitleBarMiddleClickAction::None => {}
+    }
+}
+
 impl ParentElement for PlatformTitleBar {
     fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
         self.children.extend(elements)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::Tiling;
+
+    use super::*;
+
+    #[test]
+    fn test_window_controls_collapse_in_fullscreen_and_zen_mode() {
+        let client_decorations = Decorations::Client {
+            tiling: Tiling::default(),
+        };
+        assert_eq!(
+            window_controls_visibility(
+                false,
+                FullscreenWindowControls::Reveal,
+                PlatformStyle::Linux,
+                client_decorations,
+            ),
+            (true, false)
+        );
+        assert_eq!(
+            window_controls_visibility(
+                true,
+                FullscreenWindowControls::Reveal,
+                PlatformStyle::Linux,
+                client_decorations,
+            ),
+            (false, true)
+        );
+        // Without client-side decorations the platform draws the controls.
+        assert_eq!(
+            window_controls_visibility(
+                true,
+                FullscreenWindowControls::Reveal,
+                PlatformStyle::Linux,
+                Decorations::Server,
+            ),
+            (false, false)
+        );
+        assert_eq!(
+            window_controls_visibility(
+                true,
+                FullscreenWindowControls::Visible,
+                PlatformStyle::Linux,
+                client_decorations,
+            ),
+            (true, false)
+        );
+        assert_eq!(
+            window_controls_visibility(
+                true,
+                FullscreenWindowControls::Hidden,
+                PlatformStyle::Linux,
+                client_decorations,
+            ),
+            (false, false)
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use gpui::{App, Pixels, Window, px};
+use settings::{
+    FullscreenWindowControls, RegisterSetting, Settings, SettingsContent, TitleBarControlStyle,
+    TitleBarDoubleClickAction, TitleBarMiddleClickAction,
+};
+use theme::{ThemeSettings, UiDensity};
+
+#[derive(Copy, Clone, Debug, RegisterSetting)]
+pub struct PlatformTitleBarSettings {
+    pub double_click_action: TitleBarDoubleClickAction,
+    pub middle_click_action: TitleBarMiddleClickAction,
+    pub fullscreen_controls: FullscreenWindowControls,
+    pub height: Option<Pixels>,
+    pub control_size: Pixels,
+    pub control_style: TitleBarControlStyle,
+}
+
+impl Settings for PlatformTitleBarSettings {
+    fn from_settings(s: &SettingsContent) -> Self {
+        let content = s.title_bar.clone().unwrap();
+        PlatformTitleBarSettings {
+            double_click_action: content.double_click_action.unwrap(),
+            middle_click_action: content.middle_click_action.unwrap(),
+            fullscreen_controls: content.fullscreen_controls.unwrap(),
+            height: content.height.map(px),
+            control_size: px(content.control_size.unwrap()),
+            control_style: content.control_style.unwrap(),
+        }
+    }
+}
+
+/// Returns the height of the title bar, honoring the `title_bar.height` setting
+/// and otherwise adjusting the platform's height to the UI density.
+pub fn title_bar_height(window: &Window, cx: &App) -> Pixels {
+    PlatformTitleBarSettings::get_global(cx)
+        .height
+        .unwrap_or_else(|| {
+            let density_adjustment = match ThemeSettings::get_global(cx).ui_density {
+                UiDensity::Compact => px(-4.),
+                UiDensity::Default => px(0.),
+                UiDensity::Comfortable => px(4.),
+            };
+            ui::utils::platform_title_bar_height(window) + density_adjustment
+        })
+}
@@ -1,5 +1,12 @@
-use gpui::{Action, Hsla, MouseButton, prelude::*, svg};
-use ui::prelude::*;
+use gpui::{
+    Action, Bounds, Corner, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Hsla,
+    MouseButton, PathBuilder, Pixels, Point, Spring, Stateful, WindowSnapLayout, anchored, canvas,
+    deferred, point, prelude::*, px, size, svg,
+};
+use settings::{Settings, TitleBarControlStyle};
+use ui::{Tooltip, prelude::*, tooltip_container};
+
+use crate::PlatformTitleBarSettings;
 
 #[derive(IntoElement)]
 pub struct LinuxWindowControls {
@@ -18,34 +25,295 @@ impl RenderOnce for LinuxWindowControls {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         h_flex()
             .id("generic-window-controls")
+            .tab_group()
             .px_3()
             .gap_3()
             .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
-            .child(WindowControl::new(
-                "minimize",
-                WindowControlType::Minimize,
-                cx,
-            ))
-            .child(WindowControl::new(
-                "maximize-or-restore",
-                if window.is_maximized() {
-                    WindowControlType::Restore
+            .when(window.window_controls().keep_above, |this| {
+                this.child(
+                    WindowControl::new(
+                        "keep-above",
+                        if window.is_keep_above() {
+                            WindowControlType::Unpin
+                        } else {
+                            WindowControlType::Pin
+                        },
+                        cx,
+                    )
+                    .tab_index(0),
+                )
+            })
+            .child(WindowControl::new("minimize", WindowControlType::Minimize, cx).tab_index(1))
+            .child(
+                WindowControl::new(
+                    "maximize-or-restore",
+                    if window.is_maximized() {
+                        WindowControlType::Restore
+                    } else {
+                        WindowControlType::Maximize
+                    },
+                    cx,
+                )
+                .tab_index(2),
+            )
+            .child(
+                WindowControl::new_close(
+                    "close",
+                    WindowControlType::Close,
+                    self.close_window_action,
+                    cx,
+                )
+                .tab_index(3),
+            )
+    }
+}
+
+const DEFAULT_CONTROL_SIZE: Pixels = px(16.);
+/// The gap between a control's icon and the edge of its hit target.
+const CONTROL_PADDING: Pixels = px(4.);
+
+/// How much of the top edge stays hoverable while the controls are collapsed.
+const REVEAL_STRIP_HEIGHT: Pixels = px(4.);
+const REVEAL_STRIP_WIDTH: Pixels = px(120.);
+
+/// The window controls shown while the window is fullscreen: a thin strip
+/// along the top-right edge that expands into the full controls on hover.
+#[derive(IntoElement)]
+pub struct LinuxWindowControlsRevealStrip {
+    close_window_action: Box<dyn Action>,
+    height: Pixels,
+    background: Hsla,
+}
+
+impl LinuxWindowControlsRevealStrip {
+    pub fn new(close_window_action: Box<dyn Action>, height: Pixels, background: Hsla) -> Self {
+        Self {
+            close_window_action,
+            height,
+            background,
+        }
+    }
+}
+
+impl RenderOnce for LinuxWindowControlsRevealStrip {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let revealed = window.use_keyed_state("window-controls-reveal-strip", cx, |_, _| false);
+        let is_revealed = *revealed.read(cx);
+
+        h_flex()
+            .id("window-controls-reveal-strip")
+            .absolute()
+            .top_0()
+            .right_0()
+            .justify_end()
+            .on_hover(move |hovered, _, cx| {
+                revealed.update(cx, |revealed, cx| {
+                    *revealed = *hovered;
+                    cx.notify();
+                });
+            })
+            .map(|this| {
+                if is_revealed {
+                    this.h(self.height)
+                        .bg(self.background)
+                        .rounded_bl_md()
+                        .child(LinuxWindowControls::new(self.close_window_action))
                 } else {
-                    WindowControlType::Maximize
-                },
-                cx,
-            ))
-            .child(WindowControl::new_close(
-                "close",
-                WindowControlType::Close,
-                self.close_window_action,
-                cx,
-            ))
+                    this.h(REVEAL_STRIP_HEIGHT).w(REVEAL_STRIP_WIDTH)
+                }
+            })
+    }
+}
+
+const SNAP_LAYOUTS: [WindowSnapLayout; 6] = [
+    WindowSnapLayout::LeftHalf,
+    WindowSnapLayout::RightHalf,
+    WindowSnapLayout::TopLeftQuarter,
+    WindowSnapLayout::TopRightQuarter,
+    WindowSnapLayout::BottomLeftQuarter,
+    WindowSnapLayout::BottomRightQuarter,
+];
+
+/// The layouts offered by the snap layout menu, where `None` maximizes the
+/// window, which is only offered while it isn't maximized.
+fn snap_options(is_maximized: bool) -> impl Iterator<Item = Option<WindowSnapLayout>> {
+    SNAP_LAYOUTS
+        .into_iter()
+        .map(Some)
+        .chain((!is_maximized).then_some(None))
+}
+
+fn apply_snap_option(option: Option<WindowSnapLayout>, window: &Window) {
+    match option {
+        Some(layout) => window.snap_window(layout),
+        None => window.zoom_window(),
+    }
+}
+
+/// The option selected after moving forward or backward from `selected`,
+/// wrapping around at either end.
+fn cycle_selection(selected: Option<usize>, count: usize, forward: bool) -> usize {
+    match (selected, forward) {
+        (None, true) => 0,
+        (None, false) => count - 1,
+        (Some(index), true) => (index + 1) % count,
+        (Some(index), false) => (index + count - 1) % count,
+    }
+}
+
+/// The popover offering the layouts the window can be snapped to. It's shown
+/// while hovering the maximize control, and opened from it with a right click
+/// or the down key, in which case it takes focus so it can be used from the
+/// keyboard.
+struct SnapLayoutMenu {
+    focus_handle: FocusHandle,
+    selected_index: Option<usize>,
+    /// The control to focus again when the menu is dismissed from the keyboard.
+    return_focus: Option<FocusHandle>,
+}
+
+impl SnapLayoutMenu {
+    fn new(return_focus: Option<FocusHandle>, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            selected_index: return_focus.is_some().then_some(0),
+            return_focus,
+        }
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        let count = snap_options(window.is_maximized()).count();
+        self.selected_index = Some(cycle_selection(self.selected_index, count, true));
+        cx.notify();
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let count = snap_options(window.is_maximized()).count();
+        self.selected_index = Some(cycle_selection(self.selected_index, count, false));
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let option = self
+            .selected_index
+            .and_then(|index| snap_options(window.is_maximized()).nth(index));
+        if let Some(option) = option {
+            apply_snap_option(option, window);
+        }
+        self.dismiss(window, cx);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        self.dismiss(window, cx);
+    }
+
+    fn dismiss(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(return_focus) = self.return_focus.as_ref() {
+            window.focus(return_focus, cx);
+        }
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for SnapLayoutMenu {}
+
+impl Focusable for SnapLayoutMenu {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for SnapLayoutMenu {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_maximized = window.is_maximized();
+
+        tooltip_container(cx, |this, cx| {
+            this.child(
+                h_flex()
+                    .key_context("menu")
+                    .track_focus(&self.focus_handle)
+                    .on_action(cx.listener(Self::select_next))
+                    .on_action(cx.listener(Self::select_previous))
+                    .on_action(cx.listener(|this, _: &menu::SelectChild, window, cx| {
+                        this.select_next(&menu::SelectNext, window, cx)
+                    }))
+                    .on_action(cx.listener(|this, _: &menu::SelectParent, window, cx| {
+                        this.select_previous(&menu::SelectPrevious, window, cx)
+                    }))
+                    .on_action(cx.listener(Self::confirm))
+                    .on_action(cx.listener(Self::cancel))
+                    .py_1()
+                    .gap_1()
+                    .children(
+                        snap_options(is_maximized)
+                            .enumerate()
+                            .map(|(index, option)| {
+                                let region = option.map_or_else(
+                                    || Bounds::new(point(0., 0.), size(1., 1.)),
+                                    |layout| layout.region(),
+                                );
+                                snap_layout_preview(
+                                    ("snap-layout", index),
+                                    region,
+                                    self.selected_index == Some(index),
+                                    cx,
+                                )
+                                .on_click(cx.listener(
+                                    move |this, _, window, cx| {
+                                        apply_snap_option(option, window);
+                                        this.dismiss(window, cx);
+                                    },
+                                ))
+                            }),
+                    ),
+            )
+        })
     }
 }
 
+/// A miniature of the display with the region a layout covers filled in.
+fn snap_layout_preview(
+    id: impl Into<ElementId>,
+    region: Bounds<f32>,
+    selected: bool,
+    cx: &App,
+) -> Stateful<Div> {
+    let colors = cx.theme().colors();
+
+    div()
+        .id(id)
+        .relative()
+        .w(px(36.))
+        .h(px(24.))
+        .rounded_sm()
+        .border_1()
+        .border_color(if selected {
+            colors.border_focused
+        } else {
+            colors.border
+        })
+        .cursor_pointer()
+        .hover(|this| this.border_color(colors.border_focused))
+        .child(
+            div()
+                .absolute()
+                .left(relative(region.origin.x))
+                .top(relative(region.origin.y))
+                .w(relative(region.size.width))
+                .h(relative(region.size.height))
+                .bg(colors.element_selected),
+        )
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub enum WindowControlType {
+    Pin,
+    Unpin,
     Minimize,
     Restore,
     Maximize,
@@ -59,12 +327,26 @@ impl WindowControlType {
     /// icon name based on the platform.
     pub fn icon(&self) -> IconName {
         match self {
+            WindowControlType::Pin => IconName::Pin,
+            WindowControlType::Unpin => IconName::Unpin,
             WindowControlType::Minimize => IconName::GenericMinimize,
             WindowControlType::Restore => IconName::GenericRestore,
             WindowControlType::Maximize => IconName::GenericMaximize,
             WindowControlType::Close => IconName::GenericClose,
         }
     }
+
+    /// Returns the name of the action the control performs, shown in its tooltip.
+    pub fn title(&self) -> &'static str {
+        match self {
+            WindowControlType::Pin => "Keep on Top",
+            WindowControlType::Unpin => "Stop Keeping on Top",
+            WindowControlType::Minimize => "Minimize",
+            WindowControlType::Restore => "Restore",
+            WindowControlType::Maximize => "Maximize",
+            WindowControlType::Close => "Close",
+        }
+    }
 }
 
 #[allow(unused)]
@@ -73,6 +355,7 @@ pub struct WindowControlStyle {
     background_hover: Hsla,
     icon: Hsla,
     icon_hover: Hsla,
+    focus_ring: Hsla,
 }
 
 impl WindowControlStyle {
@@ -84,6 +367,7 @@ impl WindowControlStyle {
             background_hover: colors.ghost_element_hover,
             icon: colors.icon,
             icon_hover: colors.icon_muted,
+            focus_ring: colors.border_focused,
         }
     }
 
@@ -114,6 +398,119 @@ impl WindowControlStyle {
         self.icon_hover = color.into();
         self
     }
+
+    #[allow(unused)]
+    /// Sets the color of the ring drawn around the control when it is focused from the keyboard.
+    pub fn focus_ring(mut self, color: impl Into<Hsla>) -> Self {
+        self.focus_ring = color.into();
+        self
+    }
+}
+
+/// Per-control state that persists across frames, so a control keeps
+/// keyboard focus while the title bar re-renders.
+pub struct WindowControlState {
+    focus_handle: FocusHandle,
+    hovered: bool,
+    /// Whether each icon this control showed could be loaded, so that the
+    /// assets are only looked up once rather than on every render.
+    loaded_icons: Vec<(WindowControlType, bool)>,
+    /// The snap layout menu, while it's open from a right click or the keyboard.
+    snap_menu: Option<Entity<SnapLayoutMenu>>,
+}
+
+impl WindowControlState {
+    fn new(cx: &mut App) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            hovered: false,
+            loaded_icons: Vec::new(),
+            snap_menu: None,
+        }
+    }
+
+    fn open_snap_menu(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let return_focus = self.focus_handle.clone();
+        let menu = cx.new(|cx| SnapLayoutMenu::new(Some(return_focus), cx));
+        window.focus(&menu.focus_handle(cx), cx);
+        cx.subscribe(&menu, |state, _, _: &DismissEvent, cx| {
+            state.snap_menu = None;
+            cx.notify();
+        })
+        .detach();
+        self.snap_menu = Some(menu);
+        cx.notify();
+    }
+
+    fn icon_loads(&mut self, icon: WindowControlType, cx: &App) -> bool {
+        if let Some((_, loads)) = self.loaded_icons.iter().find(|(loaded, _)| *loaded == icon) {
+            return *loads;
+        }
+        let loads = matches!(cx.asset_source().load(&icon.icon().path()), Ok(Some(_)));
+        self.loaded_icons.push((icon, loads));
+        loads
+    }
+}
+
+/// Draws a window control glyph with paths rather than an icon asset, for
+/// the `symbolic` control style and for when an icon fails to load.
+fn symbolic_glyph(control: WindowControlType, size: Pixels, color: Hsla) -> impl IntoElement {
+    canvas(
+        |_, _, _| {},
+        move |bounds, _, window, _| paint_symbolic_glyph(control, bounds, color, window),
+    )
+    .flex_none()
+    .size(size)
+}
+
+fn paint_symbolic_glyph(
+    control: WindowControlType,
+    bounds: Bounds<Pixels>,
+    color: Hsla,
+    window: &mut Window,
+) {
+    // Glyphs are laid out on a 16x16 grid and scaled to the control size.
+    let scale = f32::from(bounds.size.width) / 16.;
+    let at =
+        |x: f32, y: f32| -> Point<Pixels> { bounds.origin + point(px(x * scale), px(y * scale)) };
+
+    let mut builder = PathBuilder::stroke(px(1.5 * scale));
+    match control {
+        WindowControlType::Minimize => {
+            builder.move_to(at(4., 8.));
+            builder.line_to(at(12., 8.));
+        }
+        WindowControlType::Maximize => {
+            builder.add_polygon(&[at(4., 4.), at(12., 4.), at(12., 12.), at(4., 12.)], true);
+        }
+        WindowControlType::Restore => {
+            builder.add_polygon(&[at(4., 6.), at(10., 6.), at(10., 12.), at(4., 12.)], true);
+            builder.move_to(at(6., 6.));
+            builder.line_to(at(6., 4.));
+            builder.line_to(at(12., 4.));
+            builder.line_to(at(12., 10.));
+            builder.line_to(at(10., 10.));
+        }
+        WindowControlType::Close => {
+            builder.move_to(at(4., 4.));
+            builder.line_to(at(12., 12.));
+            builder.move_to(at(12., 4.));
+            builder.line_to(at(4., 12.));
+        }
+        WindowControlType::Pin | WindowControlType::Unpin => {
+            builder.add_polygon(&[at(6., 3.), at(10., 3.), at(11., 9.), at(5., 9.)], true);
+            builder.move_to(at(8., 9.));
+            builder.line_to(at(8., 13.));
+            if control == WindowControlType::Unpin {
+                builder.move_to(at(3., 3.));
+                builder.line_to(at(13., 13.));
+            }
+        }
+    }
+
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, color);
+    }
 }
 
 #[derive(IntoElement)]
@@ -122,17 +519,24 @@ pub struct WindowControl {
     icon: WindowControlType,
     style: WindowControlStyle,
     close_action: Option<Box<dyn Action>>,
+    tab_index: Option<isize>,
+    size: Pixels,
+    control_style: TitleBarControlStyle,
 }
 
 impl WindowControl {
     pub fn new(id: impl Into<ElementId>, icon: WindowControlType, cx: &mut App) -> Self {
         let style = WindowControlStyle::default(cx);
+        let settings = PlatformTitleBarSettings::get_global(cx);
 
         Self {
             id: id.into(),
             icon,
             style,
             close_action: None,
+            tab_index: None,
+            size: settings.control_size,
+            control_style: settings.control_style,
         }
     }
 
@@ -143,12 +547,16 @@ impl WindowControl {
         cx: &mut App,
     ) -> Self {
         let style = WindowControlStyle::default(cx);
+        let settings = PlatformTitleBarSettings::get_global(cx);
 
         Self {
             id: id.into(),
             icon,
             style,
             close_action: Some(close_action.boxed_clone()),
+            tab_index: None,
+            size: settings.control_size,
+            control_style: settings.control_style,
         }
     }
 
@@ -163,35 +571,153 @@ impl WindowControl {
             icon,
             style,
             close_action: None,
+            tab_index: None,
+            size: DEFAULT_CONTROL_SIZE,
+            control_style: TitleBarControlStyle::default(),
         }
     }
+
+    /// Sets the position of this control in the keyboard navigation order
+    /// of its surrounding tab group.
+    pub fn tab_index(mut self, tab_index: isize) -> Self {
+        self.tab_index = Some(tab_index);
+        self
+    }
 }
 
 impl RenderOnce for WindowControl {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
-        let icon = svg()
-            .size_4()
-            .flex_none()
-            .path(self.icon.icon().path())
-            .text_color(self.style.icon)
-            .group_hover("", |this| this.text_color(self.style.icon_hover));
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state =
+            window.use_keyed_state(self.id.clone(), cx, |_, cx| WindowControlState::new(cx));
+        let focus_handle = state.read(cx).focus_handle.clone();
+        let hovered = state.read(cx).hovered;
+        let snap_menu = state.read(cx).snap_menu.clone();
+        let supports_snap = window.window_controls().snap
+            && matches!(
+                self.icon,
+                WindowControlType::Maximize | WindowControlType::Restore
+            );
+        let background = window.use_animated_value(
+            (self.id.clone(), "background"),
+            self.style.background,
+            if hovered {
+                self.style.background_hover
+            } else {
+                self.style.background
+            },
+            Spring::snappy(),
+        );
+
+        let icon_loads = self.control_style == TitleBarControlStyle::Icons
+            && state.update(cx, |state, cx| state.icon_loads(self.icon, cx));
+        let icon = if icon_loads {
+            svg()
+                .size(self.size)
+                .flex_none()
+                .path(self.icon.icon().path())
+                .text_color(self.style.icon)
+                .group_hover("", |this| this.text_color(self.style.icon_hover))
+                .into_any_element()
+        } else {
+            let color = if hovered {
+                self.style.icon_hover
+            } else {
+                self.style.icon
+            };
+            symbolic_glyph(self.icon, self.size, color).into_any_element()
+        };
 
         h_flex()
             .id(self.id)
+            .track_focus(&focus_handle)
+            .accessibility_role(gpui::accesskit::Role::Button)
+            .accessibility_label(self.icon.title())
+            .when_some(self.tab_index, |this, tab_index| this.tab_index(tab_index))
             .group("")
             .cursor_pointer()
             .justify_center()
             .content_center()
             .rounded_2xl()
-            .w_5()
-            .h_5()
-            .hover(|this| this.bg(self.style.background_hover))
+            .size(self.size + CONTROL_PADDING)
+            .border_1()
+            .border_color(gpui::transparent_black())
+            .focus_visible(|this| this.border_color(self.style.focus_ring))
+            .bg(background)
             .active(|this| this.bg(self.style.background_hover))
+            .on_hover({
+                let state = state.clone();
+                move |hovered, _, cx| {
+                    state.update(cx, |state, cx| {
+                        state.hovered = *hovered;
+                        cx.notify();
+                    });
+                }
+            })
             .child(icon)
+            .map(|this| {
+                if let Some(close_action) = self.close_action.as_ref() {
+                    this.tooltip(Tooltip::for_action_title(
+                        self.icon.title(),
+                        close_action.as_ref(),
+                    ))
+                } else if supports_snap {
+                    this.when(snap_menu.is_none(), |this| {
+                        this.hoverable_tooltip(|_, cx| {
+                            cx.new(|cx| SnapLayoutMenu::new(None, cx)).into()
+                        })
+                    })
+                } else {
+                    this.tooltip(Tooltip::text(self.icon.title()))
+                }
+            })
+            // Clicking with the mouse shouldn't pull focus away from the
+            // editor; the controls only take focus via keyboard navigation.
+            .on_mouse_down(MouseButton::Left, |_, window, _| window.prevent_default())
             .on_mouse_move(|_, _, cx| cx.stop_propagation())
+            .when(supports_snap, |this| {
+                this.on_mouse_down(MouseButton::Right, {
+                    let state = state.clone();
+                    move |_, window, cx| {
+                        window.prevent_default();
+                        cx.stop_propagation();
+                        state.update(cx, |state, cx| state.open_snap_menu(window, cx));
+                    }
+                })
+                .on_key_down(move |event, window, cx| {
+                    if event.keystroke.key == "down" && !event.keystroke.modifiers.modified() {
+                        cx.stop_propagation();
+                        state.update(cx, |state, cx| state.open_snap_menu(window, cx));
+                    }
+                })
+            })
+            .when_some(snap_menu, |this, snap_menu| {
+                this.child(
+                    div().absolute().top_full().right_0().child(
+                        deferred(
+                            anchored()
+                                .anchor(Corner::TopRight)
+                                .snap_to_window_with_margin(px(8.))
+                                .child(
+                                    div()
+                                        .occlude()
+                                        .on_mouse_down_out({
+                                            let snap_menu = snap_menu.clone();
+                                            move |_, _, cx| {
+                                                snap_menu.update(cx, |_, cx| cx.emit(DismissEvent))
+                                            }
+                                        })
+                                        .child(snap_menu),
+                                ),
+                        )
+                        .with_priority(1),
+                    ),
+                )
+            })
             .on_click(move |_, window, cx| {
                 cx.stop_propagation();
                 match self.icon {
+                    WindowControlType::Pin => window.set_keep_above(true),
+                    WindowControlType::Unpin => window.set_keep_above(false),
                     WindowControlType::Minimize => window.minimize_window(),
                     WindowControlType::Restore => window.zoom_window(),
                     WindowControlType::Maximize => window.zoom_window(),
@@ -206,3 +732,22 @@ impl RenderOnce for WindowControl {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snap_layout_menu_selection() {
+        assert_eq!(snap_options(false).count(), SNAP_LAYOUTS.len() + 1);
+        assert_eq!(snap_options(false).last(), Some(None));
+        assert!(snap_options(true).all(|option| option.is_some()));
+
+        let count = snap_options(false).count();
+        assert_eq!(cycle_selection(None, count, true), 0);
+        assert_eq!(cycle_selection(None, count, false), count - 1);
+        assert_eq!(cycle_selection(Some(0), count, true), 1);
+        assert_eq!(cycle_selection(Some(count - 1), count, true), 0);
+        assert_eq!(cycle_selection(Some(0), count, false), count - 1);
+    }
+}
@@ -0,0 +1,291 @@
+//! Line bookmarks, which mark places in files to come back to.
+//!
+//! Bookmarks belong to the workspace rather than to any editor, so that every
+//! editor showing a file shows its bookmarks, and the workspace keeps them
+//! between sessions.
+use anyhow::Result;
+use collections::BTreeMap;
+use gpui::{App, AppContext as _, Context, Entity, EventEmitter, SharedString, Subscription, Task};
+use language::{Buffer, BufferEvent, BufferSnapshot};
+use std::{ops::Range, path::Path, sync::Arc};
+use text::{Point, ToPoint as _};
+
+use crate::{ProjectPath, buffer_store::BufferStore, worktree_store::WorktreeStore};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bookmark {
+    pub position: text::Anchor,
+    pub label: Option<SharedString>,
+}
+
+/// A bookmark as the workspace stores it, by the row it is on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerializedBookmark {
+    pub row: u32,
+    pub label: Option<SharedString>,
+}
+
+struct BookmarksInFile {
+    buffer: Entity<Buffer>,
+    bookmarks: Vec<Bookmark>,
+    _subscription: Subscription,
+}
+
+impl BookmarksInFile {
+    fn new(buffer: Entity<Buffer>, cx: &mut Context<BookmarkStore>) -> Self {
+        let subscription = cx.subscribe(&buffer, |bookmark_store, buffer, event, cx| {
+            if let BufferEvent::FileHandleChanged = event {
+                bookmark_store.on_file_handle_changed(buffer, cx);
+            }
+        });
+        Self {
+            buffer,
+            bookmarks: Vec::new(),
+            _subscription: subscription,
+        }
+    }
+
+    fn rows(&self, cx: &App) -> Vec<SerializedBookmark> {
+        let snapshot = self.buffer.read(cx).snapshot();
+        let mut rows = self
+            .bookmarks
+            .iter()
+            .map(|bookmark| SerializedBookmark {
+                row: bookmark.position.to_point(&snapshot).row,
+                label: bookmark.label.clone(),
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by_key(|bookmark| bookmark.row);
+        // Edits can move several bookmarks onto the same row.
+        rows.dedup_by_key(|bookmark| bookmark.row);
+        rows
+    }
+}
+
+pub struct BookmarkStore {
+    worktree_store: Entity<WorktreeStore>,
+    buffer_store: Entity<BufferStore>,
+    bookmarks: BTreeMap<Arc<Path>, BookmarksInFile>,
+}
+
+pub enum BookmarkStoreEvent {
+    BookmarksUpdated,
+}
+
+impl EventEmitter<BookmarkStoreEvent> for BookmarkStore {}
+
+impl BookmarkStore {
+    pub fn new(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
+        Self {
+            worktree_store,
+            buffer_store,
+            bookmarks: BTreeMap::new(),
+        }
+    }
+
+    pub fn abs_path_from_buffer(buffer: &Entity<Buffer>, cx: &App) -> Option<Arc<Path>> {
+        worktree::File::from_dyn(buffer.read(cx).file())
+            .map(|file| file.worktree.read(cx).absolutize(&file.path))
+            .map(Arc::<Path>::from)
+    }
+
+    /// Removes the bookmarks on the row of `position`, or adds one there if
+    /// there were none.
+    pub fn toggle_bookmark(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: text::Anchor,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(abs_path) = Self::abs_path_from_buffer(&buffer, cx) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).snapshot();
+        let row = position.to_point(&snapshot).row;
+        let bookmarks_in_file = self
+            .bookmarks
+            .entry(abs_path.clone())
+            .or_insert_with(|| BookmarksInFile::new(buffer, cx));
+
+        let bookmark_count = bookmarks_in_file.bookmarks.len();
+        bookmarks_in_file
+            .bookmarks
+            .retain(|bookmark| bookmark.position.to_point(&snapshot).row != row);
+        if bookmarks_in_file.bookmarks.len() == bookmark_count {
+            bookmarks_in_file.bookmarks.push(Bookmark {
+                position: snapshot.anchor_before(Point::new(row, 0)),
+                label: None,
+            });
+        } else if bookmarks_in_file.bookmarks.is_empty() {
+            self.bookmarks.remove(&abs_path);
+        }
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated);
+        cx.notify();
+    }
+
+    pub fn set_bookmark_label(
+        &mut self,
+        abs_path: &Arc<Path>,
+        row: u32,
+        label: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bookmarks_in_file) = self.bookmarks.get_mut(abs_path) else {
+            return;
+        };
+        let snapshot = bookmarks_in_file.buffer.read(cx).snapshot();
+        let label = label.filter(|label| !label.trim().is_empty());
+        for bookmark in &mut bookmarks_in_file.bookmarks {
+            if bookmark.position.to_point(&snapshot).row == row {
+                bookmark.label = label.clone();
+            }
+        }
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated);
+        cx.notify();
+    }
+
+    pub fn remove_bookmark(&mut self, abs_path: &Arc<Path>, row: u32, cx: &mut Context<Self>) {
+        let Some(bookmarks_in_file) = self.bookmarks.get_mut(abs_path) else {
+            return;
+        };
+        let snapshot = bookmarks_in_file.buffer.read(cx).snapshot();
+        bookmarks_in_file
+            .bookmarks
+            .retain(|bookmark| bookmark.position.to_point(&snapshot).row != row);
+        if bookmarks_in_file.bookmarks.is_empty() {
+            self.bookmarks.remove(abs_path);
+        }
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated);
+        cx.notify();
+    }
+
+    pub fn clear_bookmarks(&mut self, cx: &mut Context<Self>) {
+        self.bookmarks.clear();
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated);
+        cx.notify();
+    }
+
+    /// The bookmarks in `buffer`, optionally only those within `range`.
+    pub fn bookmarks<'a>(
+        &'a self,
+        buffer: &'a Entity<Buffer>,
+        range: Option<Range<text::Anchor>>,
+        buffer_snapshot: &'a BufferSnapshot,
+        cx: &App,
+    ) -> impl Iterator<Item = &'a Bookmark> + 'a {
+        Self::abs_path_from_buffer(buffer, cx)
+            .and_then(|abs_path| self.bookmarks.get(&abs_path))
+            .into_iter()
+            .flat_map(move |bookmarks_in_file| {
+                let range = range.clone();
+                bookmarks_in_file.bookmarks.iter().filter(move |bookmark| {
+                    range.as_ref().is_none_or(|range| {
+                        bookmark.position.cmp(&range.start, buffer_snapshot).is_ge()
+                            && bookmark.position.cmp(&range.end, buffer_snapshot).is_le()
+                    })
+                })
+            })
+    }
+
+    /// The project path of the file with bookmarks at `abs_path`.
+    pub fn project_path(&self, abs_path: &Arc<Path>, cx: &App) -> Option<ProjectPath> {
+        let buffer = self.bookmarks.get(abs_path)?.buffer.read(cx);
+        let file = buffer.file()?;
+        Some(ProjectPath {
+            worktree_id: file.worktree_id(cx),
+            path: file.path().clone(),
+        })
+    }
+
+    /// Every bookmark, by the file it is in, sorted by row.
+    pub fn all_bookmarks(&self, cx: &App) -> BTreeMap<Arc<Path>, Vec<SerializedBookmark>> {
+        self.bookmarks
+            .iter()
+            .map(|(abs_path, bookmarks_in_file)| (abs_path.clone(), bookmarks_in_file.rows(cx)))
+            .filter(|(_, bookmarks)| !bookmarks.is_empty())
+            .collect()
+    }
+
+    pub fn with_serialized_bookmarks(
+        &self,
+        bookmarks: BTreeMap<Arc<Path>, Vec<SerializedBookmark>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let worktree_store = self.worktree_store.downgrade();
+        let buffer_store = self.buffer_store.downgrade();
+        cx.spawn(async move |this, cx| {
+            let mut new_bookmarks = BTreeMap::default();
+            for (abs_path, serialized_bookmarks) in bookmarks {
+                let Some(project_path) = worktree_store.read_with(cx, |worktree_store, cx| {
+                    worktree_store
+                        .find_worktree(&abs_path, cx)
+                        .map(|(worktree, path)| ProjectPath {
+                            worktree_id: worktree.read(cx).id(),
+                            path,
+                        })
+                })?
+                else {
+                    log::debug!("Skipping bookmarks outside of the project: {abs_path:?}");
+                    continue;
+                };
+                let Ok(buffer) = buffer_store
+                    .update(cx, |buffer_store, cx| {
+                        buffer_store.open_buffer(project_path, cx)
+                    })?
+                    .await
+                else {
+                    log::debug!("Skipping bookmarks in a file that can't be opened: {abs_path:?}");
+                    continue;
+                };
+                let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+                let mut bookmarks_in_file =
+                    this.update(cx, |_, cx| BookmarksInFile::new(buffer, cx))?;
+                let max_row = snapshot.max_point().row;
+                bookmarks_in_file.bookmarks = serialized_bookmarks
+                    .into_iter()
+                    .filter(|bookmark| bookmark.row <= max_row)
+                    .map(|bookmark| Bookmark {
+                        position: snapshot.anchor_before(Point::new(bookmark.row, 0)),
+                        label: bookmark.label,
+                    })
+                    .collect();
+                if !bookmarks_in_file.bookmarks.is_empty() {
+                    new_bookmarks.insert(abs_path, bookmarks_in_file);
+                }
+            }
+            this.update(cx, |this, cx| {
+                this.bookmarks = new_bookmarks;
+                cx.notify();
+            })
+        })
+    }
+
+    fn on_file_handle_changed(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
+        let entity_id = buffer.entity_id();
+        let Some(old_path) = self
+            .bookmarks
+            .iter()
+            .find(|(_, bookmarks_in_file)| bookmarks_in_file.buffer.entity_id() == entity_id)
+            .map(|(abs_path, _)| abs_path.clone())
+        else {
+            return;
+        };
+
+        if buffer
+            .read(cx)
+            .file()
+            .is_none_or(|file| file.disk_state().is_deleted())
+        {
+            self.bookmarks.remove(&old_path);
+        } else if let Some(new_path) = Self::abs_path_from_buffer(&buffer, cx)
+            && new_path != old_path
+            && let Some(bookmarks_in_file) = self.bookmarks.remove(&old_path)
+        {
+            self.bookmarks.insert(new_path, bookmarks_in_file);
+        } else {
+            return;
+        }
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated);
+        cx.notify();
+    }
+}
@@ -10,6 +10,7 @@ use futures::{Future, FutureExt as _, channel::oneshot, future::Shared};
 use gpui::{
     App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Subscription, Task, WeakEntity,
 };
+use itertools::Either;
 use language::{
     Buffer, BufferEvent, Capability, DiskState, File as _, Language, Operation,
     proto::{
@@ -22,7 +23,7 @@ use rpc::{
     proto::{self, PeerId},
 };
 
-use settings::Settings;
+use settings::{Settings, SettingsLocation};
 use std::{io, sync::Arc, time::Instant};
 use text::{BufferId, ReplicaId};
 use util::{ResultExt as _, TryFutureExt, debug_panic, maybe, rel_path::RelPath};
@@ -642,23 +643,65 @@ impl LocalBufferStore {
         worktree: Entity<Worktree>,
         cx: &mut Context<BufferStore>,
     ) -> Task<Result<Entity<Buffer>>> {
-        let load_file = worktree.update(cx, |worktree, cx| worktree.load_file(path.as_ref(), cx));
+        let large_file_mode = WorktreeSettings::get(
+            Some(SettingsLocation {
+                worktree_id: worktree.read(cx).id(),
+                path: &path,
+            }),
+            cx,
+        )
+        .large_file_mode;
+        let is_large_file = worktree
+            .read(cx)
+            .entry_for_path(&path)
+            .is_some_and(|entry| entry.size >= large_file_mode.threshold);
+        let load_file = worktree.update(cx, |worktree, cx| {
+            if is_large_file {
+                Either::Right(worktree.load_large_file(path.as_ref(), cx))
+            } else {
+                Either::Left(worktree.load_file(path.as_ref(), cx))
+            }
+        });
         cx.spawn(async move |this, cx| {
             let path = path.clone();
-            let buffer = match load_file.await {
-                Ok(loaded) => {
+            let loaded = match load_file {
+                Either::Left(load_file) => load_file.await.map(|loaded| {
+                    let text = Either::Left(loaded.text);
+                    (loaded.file, loaded.encoding, loaded.has_bom, text)
+                }),
+                Either::Right(load_large_file) => load_large_file.await.map(|loaded| {
+                    let text = Either::Right((loaded.line_ending, loaded.text));
+                    (loaded.file, loaded.encoding, loaded.has_bom, text)
+                }),
+            };
+            let buffer = match loaded {
+                Ok((file, encoding, has_bom, text)) => {
                     let reservation = cx.reserve_entity::<Buffer>();
                     let buffer_id = BufferId::from(reservation.entity_id().as_non_zero_u64());
                     let text_buffer = cx
                         .background_spawn(async move {
-                            text::Buffer::new(ReplicaId::LOCAL, buffer_id, loaded.text)
+                            match text {
+                                Either::Left(text) => {
+                                    text::Buffer::new(ReplicaId::LOCAL, buffer_id, text)
+                                }
+                                Either::Right((line_ending, text)) => text::Buffer::new_normalized(
+                                    ReplicaId::LOCAL,
+                                    buffer_id,
+                                    line_ending,
+                                    text,
+                                ),
+                            }
                         })
                         .await;
-                    cx.insert_entity(reservation, |_| {
+                    cx.insert_entity(reservation, |cx| {
                         let mut buffer =
-                            Buffer::build(text_buffer, Some(loaded.file), Capability::ReadWrite);
-                        buffer.set_encoding(loaded.encoding);
-                        buffer.set_has_bom(loaded.has_bom);
+                            Buffer::build(text_buffer, Some(file), Capability::ReadWrite);
+                        buffer.set_encoding(encoding);
+                        buffer.set_has_bom(has_bom);
+                        if is_large_file {
+                            buffer.set_large_file(true);
+                            buffer.set_syntax_disabled(!large_file_mode.syntax_highlighting, cx);
+                        }
                         buffer
                     })
                 }
@@ -716,6 +716,19 @@ impl DapStore {
                             });
                         };
                     }
+                    VariableLookupKind::Text => {
+                        inlay_hints.push(InlayHint {
+                            position,
+                            label: InlayHintLabel::String(
+                                inline_value_location.variable_name.clone(),
+                            ),
+                            kind: Some(InlayHintKind::Type),
+                            padding_left: true,
+                            padding_right: false,
+                            tooltip: None,
+                            resolve_state: ResolveState::Resolved,
+                        });
+                    }
                 };
             }
 
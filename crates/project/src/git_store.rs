@@ -32,7 +32,7 @@ use git::{
         Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, FetchOptions,
         GitRepository, GitRepositoryCheckpoint, GraphCommitData, InitialGraphCommitData, LogOrder,
         LogSource, PushOptions, Remote, RemoteCommandOutput, RepoPath, ResetMode,
-        UpstreamTrackingStatus, Worktree as GitWorktree,
+        UpstreamTrackingStatus, Worktree as GitWorktree, is_binary_content,
     },
     stash::{GitStash, StashEntry},
     status::{
@@ -2220,6 +2220,7 @@ impl GitStore {
                     CommitOptions {
                         amend: options.amend,
                         signoff: options.signoff,
+                        gpg_sign: options.gpg_sign,
                     },
                     askpass,
                     cx,
@@ -4314,6 +4315,57 @@ impl Repository {
         })
     }
 
+    /// Loads the changes that the next commit would include, as the HEAD and
+    /// index versions of every file with staged changes.
+    pub fn load_staged_changes(&mut self) -> oneshot::Receiver<Result<CommitDiff>> {
+        let staged_paths = self
+            .cached_status()
+            .filter(|entry| entry.status.staging().has_staged())
+            .map(|entry| (entry.repo_path, entry.status))
+            .collect::<Vec<_>>();
+        self.send_job(None, move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    let mut files = Vec::with_capacity(staged_paths.len());
+                    for (path, status) in staged_paths {
+                        let old_text = backend.load_committed_text(path.clone()).await;
+                        let new_text = backend.load_index_text(path.clone()).await;
+                        let index_status = match status {
+                            FileStatus::Tracked(status) => status.index_status,
+                            _ => StatusCode::Modified,
+                        };
+                        // Blobs that aren't valid UTF-8 load as no text at
+                        // all, even though they exist on that side.
+                        let is_binary = old_text.is_none() && index_status != StatusCode::Added
+                            || new_text.is_none() && index_status != StatusCode::Deleted
+                            || [&old_text, &new_text]
+                                .into_iter()
+                                .flatten()
+                                .any(|text| is_binary_content(text.as_bytes()));
+                        let (old_text, new_text) = if is_binary {
+                            (
+                                (index_status != StatusCode::Added).then(String::new),
+                                (index_status != StatusCode::Deleted).then(String::new),
+                            )
+                        } else {
+                            (old_text, new_text)
+                        };
+                        files.push(CommitFile {
+                            path,
+                            old_text,
+                            new_text,
+                            is_binary,
+                        });
+                    }
+                    Ok(CommitDiff { files })
+                }
+                RepositoryState::Remote(_) => {
+                    anyhow::bail!("Reviewing staged changes is not supported for collab yet")
+                }
+            }
+        })
+    }
+
     pub fn file_history(
         &mut self,
         path: RepoPath,
@@ -5078,6 +5130,7 @@ impl Repository {
                             options: Some(proto::commit::CommitOptions {
                                 amend: options.amend,
                                 signoff: options.signoff,
+                                gpg_sign: options.gpg_sign,
                             }),
                             askpass_id,
                         })
@@ -1069,6 +1069,23 @@ fn language_server_for_buffer(
         .context("no language server found for buffer")
 }
 
+/// Whether `position` ends a line whose code continues on the next line
+/// with a method call, like the hints rust-analyzer shows for method chains.
+fn ends_chain_line(buffer: &Buffer, position: PointUtf16) -> bool {
+    let mut following = buffer
+        .chars_at(position)
+        .skip_while(|char| *char == ' ' || *char == '\t');
+    if following.next() != Some('\n') {
+        return false;
+    }
+    let mut next_line = following.skip_while(|char| char.is_whitespace());
+    match next_line.next() {
+        Some('.') => true,
+        Some('?') => next_line.next() == Some('.'),
+        _ => false,
+    }
+}
+
 pub async fn location_links_from_proto(
     proto_links: Vec<proto::LocationLink>,
     lsp_store: Entity<LspStore>,
@@ -2996,22 +3013,30 @@ impl InlayHints {
             _ => None,
         });
 
-        let position = buffer_handle.read_with(cx, |buffer, _| {
+        let padding_left = if force_no_type_left_padding && kind == Some(InlayHintKind::Type) {
+            false
+        } else {
+            lsp_hint.padding_left.unwrap_or(false)
+        };
+        let (kind, position) = buffer_handle.read_with(cx, |buffer, _| {
             let position = buffer.clip_point_utf16(point_from_lsp(lsp_hint.position), Bias::Left);
-            if kind == Some(InlayHintKind::Parameter) {
+            // Language servers report chaining hints as type hints, so they are told apart by
+            // where they are placed.
+            let kind = if kind == Some(InlayHintKind::Type) && ends_chain_line(buffer, position) {
+                Some(InlayHintKind::Chaining)
+            } else {
+                kind
+            };
+            let position = if kind == Some(InlayHintKind::Parameter) {
                 buffer.anchor_before(position)
             } else {
                 buffer.anchor_after(position)
-            }
+            };
+            (kind, position)
         });
         let label = Self::lsp_inlay_label_to_project(lsp_hint.label, server_id)
             .await
             .context("lsp to project inlay hint conversion")?;
-        let padding_left = if force_no_type_left_padding && kind == Some(InlayHintKind::Type) {
-            false
-        } else {
-            lsp_hint.padding_left.unwrap_or(false)
-        };
 
         Ok(InlayHint {
             position,
@@ -3270,7 +3295,7 @@ impl InlayHints {
         lsp::InlayHint {
             position: point_to_lsp(hint.position.to_point_utf16(snapshot)),
             kind: hint.kind.map(|kind| match kind {
-                InlayHintKind::Type => lsp::InlayHintKind::TYPE,
+                InlayHintKind::Type | InlayHintKind::Chaining => lsp::InlayHintKind::TYPE,
                 InlayHintKind::Parameter => lsp::InlayHintKind::PARAMETER,
             }),
             text_edits: None,
@@ -5026,3 +5051,45 @@ fn process_full_diagnostics_report(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::{AppContext as _, TestAppContext};
+
+    use super::*;
+
+    #[gpui::test]
+    fn test_ends_chain_line(cx: &mut TestAppContext) {
+        let buffer = cx.new(|cx| {
+            Buffer::local(
+                concat!(
+                    "let names = people\n",
+                    "    .iter()  \n",
+                    "    ?.name\n",
+                    "    .collect();\n",
+                    "let count = names.len();\n",
+                    "let first = names\n",
+                    "    ?\n",
+                ),
+                cx,
+            )
+        });
+        buffer.read_with(cx, |buffer, _| {
+            let row_ends_chain =
+                |row| ends_chain_line(buffer, PointUtf16::new(row, buffer.line_len(row)));
+            assert!(row_ends_chain(0), "the next line calls a method");
+            assert!(
+                row_ends_chain(1),
+                "trailing whitespace and `?.` continue the chain"
+            );
+            assert!(row_ends_chain(2));
+            assert!(!row_ends_chain(3), "the next line starts a new statement");
+            assert!(!row_ends_chain(5), "a lone `?` doesn't call a method");
+            assert!(!row_ends_chain(6), "the last line has no next line");
+            assert!(
+                !ends_chain_line(PointUtf16::new(0, 4)),
+                "hints within a line don't end it"
+            );
+        });
+    }
+}
@@ -14,10 +14,13 @@ mod code_lens;
 mod document_colors;
 mod document_symbols;
 mod folding_ranges;
+pub mod hierarchy;
 mod inlay_hints;
+pub mod inline_values;
 pub mod json_language_server_ext;
 pub mod log_store;
 pub mod lsp_ext_command;
+pub mod rename_preview;
 pub mod rust_analyzer_ext;
 mod semantic_tokens;
 pub mod vue_language_server_ext;
@@ -64,8 +67,8 @@ use futures::{
 };
 use globset::{Glob, GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
 use gpui::{
-    App, AppContext, AsyncApp, Context, Entity, EventEmitter, PromptLevel, SharedString,
-    Subscription, Task, WeakEntity,
+    App, AppContext, AsyncApp, Context, Entity, EventEmitter, FutureExt as _, PromptLevel,
+    SharedString, Subscription, Task, WeakEntity,
 };
 use http_client::HttpClient;
 use itertools::Itertools as _;
@@ -461,6 +464,8 @@ impl LocalLspStore {
                 }
             });
         let update_binary_status = wait_until_worktree_trust.is_none();
+        let reports_workspace_folders =
+            settings.workspace_folders.unwrap_or_default() == settings::LspWorkspaceFolders::All;
 
         let binary = self.get_language_server_binary(
             worktree_abs_path.clone(),
@@ -507,7 +512,7 @@ impl LocalLspStore {
                     binary,
                     &worktree_abs_path,
                     code_action_kinds,
-                    Some(pending_workspace_folders),
+                    reports_workspace_folders.then_some(pending_workspace_folders),
                     cx,
                 )
             }
@@ -1091,6 +1096,7 @@ impl LocalLspStore {
                     let mut cx = cx.clone();
                     async move {
                         this.update(&mut cx, |this, cx| {
+                            this.invalidate_code_lens();
                             cx.emit(LspStoreEvent::RefreshCodeLens);
                             this.downstream_client.as_ref().map(|(client, project_id)| {
                                 client.send(proto::RefreshCodeLens {
@@ -1651,6 +1657,18 @@ impl LocalLspStore {
             })?;
         }
 
+        // `code_actions_on_save` run on every save, even when the buffer
+        // isn't otherwise formatted, ahead of all other formatters
+        let code_actions_on_save_formatters = match trigger {
+            FormatTrigger::Save => settings
+                .code_actions_on_save
+                .iter()
+                .cloned()
+                .map(Formatter::CodeAction)
+                .collect::<Vec<_>>(),
+            FormatTrigger::Manual => Vec::new(),
+        };
+
         // Formatter for `code_actions_on_format` that runs before
         // the rest of the formatters
         let mut code_actions_on_format_formatters = None;
@@ -1684,9 +1702,9 @@ impl LocalLspStore {
             }
         };
 
-        let formatters = code_actions_on_format_formatters
+        let formatters = code_actions_on_save_formatters
             .iter()
-            .flatten()
+            .chain(code_actions_on_format_formatters.iter().flatten())
             .chain(formatters);
 
         for formatter in formatters {
@@ -1850,6 +1868,13 @@ impl LocalLspStore {
                     let code_action_kind: CodeActionKind = code_action_name.clone().into();
                     zlog::trace!(logger => "Attempting to resolve code actions {:?}", &code_action_kind);
 
+                    let code_action_timeout = match trigger {
+                        FormatTrigger::Save => {
+                            Duration::from_millis(settings.code_actions_on_save_timeout_ms)
+                        }
+                        FormatTrigger::Manual => request_timeout,
+                    };
+                    let executor = cx.background_executor().clone();
                     let mut actions_and_servers = Vec::new();
 
                     for (index, (_, language_server)) in adapters_and_servers.iter().enumerate() {
@@ -1860,7 +1885,10 @@ impl LocalLspStore {
                             &buffer.handle,
                             cx,
                         )
+                        .with_timeout(code_action_timeout, &executor)
                         .await
+                        .map_err(anyhow::Error::from)
+                        .and_then(|actions| actions)
                         .with_context(|| {
                             format!(
                                 "Failed to resolve code action {:?} with language server {}",
@@ -1908,7 +1936,7 @@ impl LocalLspStore {
                         zlog::trace!(logger => "Executing {}", describe_code_action(&action));
 
                         if let Err(err) =
-                            Self::try_resolve_code_action(server, &mut action, request_timeout)
+                            Self::try_resolve_code_action(server, &mut action, code_action_timeout)
                                 .await
                         {
                             zlog::error!(
@@ -2698,6 +2726,18 @@ impl LocalLspStore {
         if !file.is_local() {
             return;
         }
+        if buffer.is_large_file() {
+            let settings = WorktreeSettings::get(
+                Some(SettingsLocation {
+                    worktree_id: file.worktree_id(cx),
+                    path: file.path(),
+                }),
+                cx,
+            );
+            if !settings.large_file_mode.language_servers {
+                return;
+            }
+        }
 
         let abs_path = file.abs_path(cx);
         let Some(uri) = file_path_to_lsp_url(&abs_path).log_err() else {
@@ -3221,15 +3261,11 @@ impl LocalLspStore {
         })
     }
 
-    pub(crate) async fn deserialize_workspace_edit(
-        this: Entity<LspStore>,
+    /// The operations of a workspace edit, in order, whether it lists them
+    /// or only the edits of each document.
+    pub(crate) fn workspace_edit_operations(
         edit: lsp::WorkspaceEdit,
-        push_to_history: bool,
-        language_server: Arc<LanguageServer>,
-        cx: &mut AsyncApp,
-    ) -> Result<ProjectTransaction> {
-        let fs = this.read_with(cx, |this, _| this.as_local().unwrap().fs.clone());
-
+    ) -> Vec<lsp::DocumentChangeOperation> {
         let mut operations = Vec::new();
         if let Some(document_changes) = edit.document_changes {
             match document_changes {
@@ -3249,9 +3285,20 @@ impl LocalLspStore {
                 })
             }));
         }
+        operations
+    }
+
+    pub(crate) async fn deserialize_workspace_edit(
+        this: Entity<LspStore>,
+        edit: lsp::WorkspaceEdit,
+        push_to_history: bool,
+        language_server: Arc<LanguageServer>,
+        cx: &mut AsyncApp,
+    ) -> Result<ProjectTransaction> {
+        let fs = this.read_with(cx, |this, _| this.as_local().unwrap().fs.clone());
 
         let mut project_transaction = ProjectTransaction::default();
-        for operation in operations {
+        for operation in Self::workspace_edit_operations(edit) {
             match operation {
                 lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Create(op)) => {
                     let abs_path = op
@@ -4042,7 +4089,7 @@ struct CoreSymbol {
     pub container_name: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum SymbolLocation {
     InProject(ProjectPath),
     OutsideProject {
@@ -4082,6 +4129,7 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_refresh_inlay_hints);
         client.add_entity_request_handler(Self::handle_refresh_semantic_tokens);
         client.add_entity_request_handler(Self::handle_refresh_code_lens);
+        client.add_entity_request_handler(Self::handle_resolve_code_lens);
         client.add_entity_request_handler(Self::handle_on_type_formatting);
         client.add_entity_request_handler(Self::handle_apply_additional_edits_for_completion);
         client.add_entity_request_handler(Self::handle_register_buffer_with_language_servers);
@@ -4093,6 +4141,20 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
+        client.add_entity_request_handler(
+            Self::handle_lsp_command::<hierarchy::PrepareCallHierarchy>,
+        );
+        client.add_entity_request_handler(Self::handle_lsp_command::<hierarchy::GetIncomingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<hierarchy::GetOutgoingCalls>);
+        client.add_entity_request_handler(
+            Self::handle_lsp_command::<hierarchy::PrepareTypeHierarchy>,
+        );
+        client.add_entity_request_handler(Self::handle_lsp_command::<hierarchy::GetSupertypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<hierarchy::GetSubtypes>);
+        client
+            .add_entity_request_handler(Self::handle_lsp_command::<inline_values::GetInlineValues>);
+        client
+            .add_entity_request_handler(Self::handle_lsp_command::<rename_preview::GetRenameEdits>);
 
         client.add_entity_request_handler(Self::handle_lsp_ext_cancel_flycheck);
         client.add_entity_request_handler(Self::handle_lsp_ext_run_flycheck);
@@ -4951,11 +5013,26 @@ impl LspStore {
 
         let Some(language_server) = buffer.update(cx, |buffer, cx| match server {
             LanguageServerToQuery::FirstCapable => self.as_local().and_then(|local| {
+                let priorities = language_settings(
+                    buffer.language().map(|language| language.name()),
+                    buffer.file(),
+                    cx,
+                )
+                .language_server_priorities
+                .get(<R::LspRequest as lsp::request::Request>::METHOD)
+                .cloned()
+                .unwrap_or_default();
                 local
                     .language_servers_for_buffer(buffer, cx)
-                    .find(|(_, server)| {
+                    .filter(|(_, server)| {
                         request.check_capabilities(server.adapter_server_capabilities())
                     })
+                    .min_by_key(|(adapter, _)| {
+                        priorities
+                            .iter()
+                            .position(|name| name.as_str() == adapter.name.0.as_ref())
+                            .unwrap_or(priorities.len())
+                    })
                     .map(|(_, server)| server.clone())
             }),
             LanguageServerToQuery::Other(id) => self
@@ -5501,6 +5578,20 @@ impl LspStore {
         cx: &mut Context<Self>,
     ) -> Task<Result<Vec<Range<Anchor>>>> {
         let snapshot = buffer.read(cx).snapshot();
+        let linked_edits_enabled = maybe!({
+            let language = buffer.read(cx).language_at(position)?;
+            Some(language_settings(Some(language.name()), buffer.read(cx).file(), cx).linked_edits)
+        }) == Some(true);
+        if !linked_edits_enabled {
+            return Task::ready(Ok(Vec::new()));
+        }
+
+        // Servers without linked editing support fall back to the tags of the syntax tree.
+        let tag_name_ranges = snapshot
+            .tag_name_ranges_at(position)
+            .into_iter()
+            .map(|range| snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end))
+            .collect::<Vec<_>>();
         let scope = snapshot.language_scope_at(position);
         let Some(server_id) = self
             .as_local()
@@ -5526,25 +5617,24 @@ impl LspStore {
                     .is_some()
                     .then_some(LanguageServerToQuery::FirstCapable)
             })
-            .filter(|_| {
-                maybe!({
-                    let language = buffer.read(cx).language_at(position)?;
-                    Some(
-                        language_settings(Some(language.name()), buffer.read(cx).file(), cx)
-                            .linked_edits,
-                    )
-                }) == Some(true)
-            })
         else {
-            return Task::ready(Ok(Vec::new()));
+            return Task::ready(Ok(tag_name_ranges));
         };
 
-        self.request_lsp(
+        let request = self.request_lsp(
             buffer.clone(),
             server_id,
             LinkedEditingRange { position },
             cx,
-        )
+        );
+        cx.background_spawn(async move {
+            let ranges = request.await?;
+            Ok(if ranges.is_empty() {
+                tag_name_ranges
+            } else {
+                ranges
+            })
+        })
     }
 
     fn apply_on_type_formatting(
@@ -7688,6 +7778,16 @@ impl LspStore {
                     .await;
                 }
 
+                // Several servers of a language, like a language's own server and a
+                // linter's, may report the same symbol.
+                let mut seen_symbols = HashSet::default();
+                symbols.retain(|symbol| {
+                    seen_symbols.insert((
+                        symbol.path.clone(),
+                        symbol.range.start,
+                        symbol.name.clone(),
+                    ))
+                });
                 Ok(symbols)
             })
         } else {
@@ -12540,6 +12640,24 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/prepareCallHierarchy" => {
+                    let options = parse_register_capabilities(reg)?;
+                    let provider = match options {
+                        OneOf::Left(value) => lsp::CallHierarchyServerCapability::Simple(value),
+                        OneOf::Right(caps) => caps,
+                    };
+                    server.update_capabilities(|capabilities| {
+                        capabilities.call_hierarchy_provider = Some(provider);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/prepareTypeHierarchy" => {
+                    let provider = reg.register_options.unwrap_or(Value::Bool(true));
+                    server.update_capabilities(|capabilities| {
+                        lsp::set_type_hierarchy_provider(capabilities, Some(provider));
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 _ => log::warn!("unhandled capability registration: {reg:?}"),
             }
         }
@@ -12743,6 +12861,18 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/prepareCallHierarchy" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.call_hierarchy_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/prepareTypeHierarchy" => {
+                    server.update_capabilities(|capabilities| {
+                        lsp::set_type_hierarchy_provider(capabilities, None);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 _ => log::warn!("unhandled capability unregistration: {unreg:?}"),
             }
         }
@@ -13977,6 +14107,101 @@ impl LspAdapter for SshLspAdapter {
     }
 }
 
+/// A language server defined entirely by its `lsp` settings, for servers
+/// that no extension or built-in support provides.
+///
+/// The server is launched with `binary`, so it is never downloaded.
+pub(crate) struct CustomLspAdapter {
+    name: LanguageServerName,
+}
+
+impl CustomLspAdapter {
+    pub(crate) fn new(name: LanguageServerName) -> Self {
+        Self { name }
+    }
+}
+
+impl LspInstaller for CustomLspAdapter {
+    type BinaryVersion = ();
+
+    async fn cached_server_binary(
+        &self,
+        _: PathBuf,
+        _: &dyn LspAdapterDelegate,
+    ) -> Option<LanguageServerBinary> {
+        None
+    }
+
+    async fn fetch_latest_server_version(
+        &self,
+        _: &dyn LspAdapterDelegate,
+        _: bool,
+        _: &mut AsyncApp,
+    ) -> Result<()> {
+        anyhow::bail!(
+            "no `binary.path` is set for custom language server {}",
+            self.name
+        )
+    }
+
+    async fn fetch_server_binary(
+        &self,
+        _: (),
+        _: PathBuf,
+        _: &dyn LspAdapterDelegate,
+    ) -> Result<LanguageServerBinary> {
+        anyhow::bail!("custom language server {} can't be downloaded", self.name)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspAdapter for CustomLspAdapter {
+    fn name(&self) -> LanguageServerName {
+        self.name.clone()
+    }
+
+    async fn workspace_configuration(
+        self: Arc<Self>,
+        delegate: &Arc<dyn LspAdapterDelegate>,
+        _: Option<Toolchain>,
+        _: Option<Uri>,
+        cx: &mut AsyncApp,
+    ) -> Result<serde_json::Value> {
+        Ok(cx
+            .update(|cx| {
+                language_server_settings(delegate.as_ref(), &self.name, cx)
+                    .and_then(|settings| settings.settings.clone())
+            })
+            .unwrap_or_else(|| serde_json::json!({})))
+    }
+
+    fn is_custom(&self) -> bool {
+        true
+    }
+}
+
+/// The custom language servers the `lsp` settings start for the language.
+pub(crate) fn custom_language_servers_for(
+    location: SettingsLocation,
+    language_name: &LanguageName,
+    cx: &App,
+) -> Vec<LanguageServerName> {
+    let mut names = ProjectSettings::get(Some(location), cx)
+        .lsp
+        .iter()
+        .filter(|(_, settings)| {
+            settings.languages.as_ref().is_some_and(|languages| {
+                languages
+                    .iter()
+                    .any(|language| language.as_str() == language_name.as_ref())
+            })
+        })
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
 pub fn language_server_settings<'a>(
     delegate: &'a dyn LspAdapterDelegate,
     language: &LanguageServerName,
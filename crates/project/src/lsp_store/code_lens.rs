@@ -17,6 +17,7 @@ use std::time::Duration;
 use crate::{
     CodeAction, LspStore, LspStoreEvent,
     lsp_command::{GetCodeLens, LspCommand as _},
+    lsp_store::LocalLspStore,
     project_settings::ProjectSettings,
 };
 
@@ -202,6 +203,65 @@ impl LspStore {
         }
     }
 
+    /// Fills in the command of a lens that language servers sent without one,
+    /// to only compute it for the lenses that are shown.
+    pub fn resolve_code_lens(
+        &self,
+        buffer: &Entity<Buffer>,
+        mut lens_action: CodeAction,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<CodeAction>> {
+        if lens_action.resolved {
+            return Task::ready(Ok(lens_action));
+        }
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            let request = proto::ResolveCodeLens {
+                project_id,
+                buffer_id: buffer.read(cx).remote_id().into(),
+                lens_action: Some(Self::serialize_code_action(&lens_action)),
+            };
+            cx.background_spawn(async move {
+                let response = upstream_client
+                    .request(request)
+                    .await
+                    .context("code lens proto resolve request")?;
+                Self::deserialize_code_action(
+                    response
+                        .lens_action
+                        .context("missing the resolved code lens")?,
+                )
+            })
+        } else {
+            let Some((lang_server, request_timeout)) = buffer.update(cx, |buffer, cx| {
+                let request_timeout = ProjectSettings::get_global(cx)
+                    .global_lsp_settings
+                    .get_request_timeout();
+                self.language_server_for_local_buffer(buffer, lens_action.server_id, cx)
+                    .map(|(_, server)| (server.clone(), request_timeout))
+            }) else {
+                return Task::ready(Ok(lens_action));
+            };
+            cx.spawn(async move |_, _| {
+                LocalLspStore::try_resolve_code_action(
+                    &lang_server,
+                    &mut lens_action,
+                    request_timeout,
+                )
+                .await
+                .context("code lens resolve LSP request")?;
+                Ok(lens_action)
+            })
+        }
+    }
+
+    /// Drops the fetched lenses of every buffer, so that they are fetched
+    /// again after a language server asked for a refresh.
+    pub(super) fn invalidate_code_lens(&mut self) {
+        for lsp_data in self.lsp_data.values_mut() {
+            lsp_data.code_lens = None;
+        }
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn forget_code_lens_task(&mut self, buffer_id: text::BufferId) -> Option<CodeLensTask> {
         Some(
@@ -220,9 +280,32 @@ impl LspStore {
         _: TypedEnvelope<proto::RefreshCodeLens>,
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
-        this.update(&mut cx, |_, cx| {
+        this.update(&mut cx, |this, cx| {
+            this.invalidate_code_lens();
             cx.emit(LspStoreEvent::RefreshCodeLens);
         });
         Ok(proto::Ack {})
     }
+
+    pub(super) async fn handle_resolve_code_lens(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::ResolveCodeLens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::ResolveCodeLensResponse> {
+        let lens_action = Self::deserialize_code_action(
+            envelope
+                .payload
+                .lens_action
+                .context("missing the code lens to resolve")?,
+        )?;
+        let resolve_task = this.update(&mut cx, |this, cx| {
+            let buffer_id = text::BufferId::new(envelope.payload.buffer_id)?;
+            let buffer = this.buffer_store.read(cx).get_existing(buffer_id)?;
+            anyhow::Ok(this.resolve_code_lens(&buffer, lens_action, cx))
+        })?;
+        let lens_action = resolve_task.await?;
+        Ok(proto::ResolveCodeLensResponse {
+            lens_action: Some(Self::serialize_code_action(&lens_action)),
+        })
+    }
 }
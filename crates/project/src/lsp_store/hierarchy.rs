@@ -0,0 +1,791 @@
+//! Call and type hierarchies reported by language servers: the items at a
+//! position, and the items calling or called by each of them, or their
+//! supertypes and subtypes.
+
+use std::{path::Path, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use gpui::{App, AsyncApp, Entity};
+use language::{
+    Buffer, CodeLabel, PointUtf16, ToPointUtf16,
+    proto::{deserialize_anchor, deserialize_version, serialize_anchor, serialize_version},
+    range_from_lsp,
+};
+use lsp::{AdapterServerCapabilities, LanguageServer, LanguageServerId};
+use rpc::proto::{self, PeerId};
+use text::BufferId;
+use util::ResultExt as _;
+
+use crate::{
+    ProjectPath, Symbol,
+    lsp_command::{LspCommand, make_lsp_text_document_position},
+    lsp_store::{LspStore, SymbolLocation},
+};
+
+/// A symbol in a call or type hierarchy.
+#[derive(Clone, Debug)]
+pub struct HierarchyItem {
+    pub symbol: Symbol,
+    pub detail: Option<String>,
+    /// The item as the language server reported it, sent back to the server
+    /// when asking for the item's neighbours in the hierarchy.
+    lsp_item: serde_json::Value,
+}
+
+/// The items of the call and type hierarchies of the protocol, which only
+/// differ by name.
+trait LspHierarchyItem: serde::Serialize {
+    fn name(&self) -> &str;
+    fn kind(&self) -> lsp::SymbolKind;
+    fn detail(&self) -> Option<&str>;
+    fn uri(&self) -> &lsp::Uri;
+    fn selection_range(&self) -> lsp::Range;
+}
+
+macro_rules! impl_lsp_hierarchy_item {
+    ($($item:ty),*) => {
+        $(
+            impl LspHierarchyItem for $item {
+                fn name(&self) -> &str {
+                    &self.name
+                }
+
+                fn kind(&self) -> lsp::SymbolKind {
+                    self.kind
+                }
+
+                fn detail(&self) -> Option<&str> {
+                    self.detail.as_deref()
+                }
+
+                fn uri(&self) -> &lsp::Uri {
+                    &self.uri
+                }
+
+                fn selection_range(&self) -> lsp::Range {
+                    self.selection_range
+                }
+            }
+        )*
+    };
+}
+
+impl_lsp_hierarchy_item!(lsp::CallHierarchyItem, lsp::TypeHierarchyItem);
+
+impl HierarchyItem {
+    fn from_lsp(
+        lsp_item: impl LspHierarchyItem,
+        language_server_id: LanguageServerId,
+        lsp_store: &LspStore,
+        buffer: &Buffer,
+        cx: &App,
+    ) -> Option<Self> {
+        let serialized_item = serde_json::to_value(&lsp_item).log_err()?;
+        let abs_path = lsp_item.uri().to_file_path().ok()?;
+        let path = match lsp_store
+            .worktree_store
+            .read(cx)
+            .find_worktree(&abs_path, cx)
+        {
+            Some((worktree, path)) => SymbolLocation::InProject(ProjectPath {
+                worktree_id: worktree.read(cx).id(),
+                path,
+            }),
+            None => SymbolLocation::OutsideProject {
+                signature: lsp_store.symbol_signature(&abs_path),
+                abs_path: abs_path.into(),
+            },
+        };
+        let language_server_name = lsp_store
+            .language_server_adapter_for_id(language_server_id)?
+            .name
+            .clone();
+        Some(Self {
+            symbol: Symbol {
+                language_server_name,
+                source_worktree_id: buffer.file()?.worktree_id(cx),
+                source_language_server_id: language_server_id,
+                path,
+                label: CodeLabel::plain(lsp_item.name().to_string(), None),
+                name: lsp_item.name().to_string(),
+                kind: lsp_item.kind(),
+                range: range_from_lsp(lsp_item.selection_range()),
+                container_name: None,
+            },
+            detail: lsp_item.detail().map(str::to_string),
+            lsp_item: serialized_item,
+        })
+    }
+
+    fn to_lsp<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_value(self.lsp_item.clone()).context("deserializing hierarchy item")
+    }
+
+    fn to_proto(&self) -> proto::HierarchyItem {
+        proto::HierarchyItem {
+            symbol: Some(LspStore::serialize_symbol(&self.symbol)),
+            detail: self.detail.clone(),
+            lsp_item: serde_json::to_vec(&self.lsp_item)
+                .log_err()
+                .unwrap_or_default(),
+        }
+    }
+
+    fn from_proto(item: proto::HierarchyItem) -> Result<Self> {
+        let symbol = LspStore::deserialize_symbol(item.symbol.context("invalid symbol")?)?;
+        Ok(Self {
+            symbol: Symbol {
+                language_server_name: symbol.language_server_name,
+                source_worktree_id: symbol.source_worktree_id,
+                source_language_server_id: symbol.source_language_server_id,
+                path: symbol.path,
+                label: CodeLabel::plain(symbol.name.clone(), None),
+                name: symbol.name,
+                kind: symbol.kind,
+                range: symbol.range,
+                container_name: symbol.container_name,
+            },
+            detail: item.detail,
+            lsp_item: serde_json::from_slice(&item.lsp_item)
+                .context("deserializing hierarchy item")?,
+        })
+    }
+}
+
+fn items_from_lsp(
+    lsp_items: impl IntoIterator<Item = impl LspHierarchyItem>,
+    language_server_id: LanguageServerId,
+    lsp_store: &Entity<LspStore>,
+    buffer: &Entity<Buffer>,
+    cx: &AsyncApp,
+) -> Vec<HierarchyItem> {
+    lsp_store.read_with(cx, |lsp_store, cx| {
+        let buffer = buffer.read(cx);
+        lsp_items
+            .into_iter()
+            .filter_map(|lsp_item| {
+                HierarchyItem::from_lsp(lsp_item, language_server_id, lsp_store, buffer, cx)
+            })
+            .collect()
+    })
+}
+
+fn items_from_proto(items: Vec<proto::HierarchyItem>) -> Result<Vec<HierarchyItem>> {
+    items.into_iter().map(HierarchyItem::from_proto).collect()
+}
+
+fn items_to_proto(items: &[HierarchyItem]) -> Vec<proto::HierarchyItem> {
+    items.iter().map(HierarchyItem::to_proto).collect()
+}
+
+fn supports_call_hierarchy(capabilities: AdapterServerCapabilities) -> bool {
+    capabilities
+        .server_capabilities
+        .call_hierarchy_provider
+        .is_some_and(|capability| match capability {
+            lsp::CallHierarchyServerCapability::Simple(supported) => supported,
+            lsp::CallHierarchyServerCapability::Options(_) => true,
+        })
+}
+
+fn supports_type_hierarchy(capabilities: AdapterServerCapabilities) -> bool {
+    lsp::supports_type_hierarchy(&capabilities.server_capabilities)
+}
+
+#[derive(Debug)]
+pub struct PrepareCallHierarchy {
+    pub position: PointUtf16,
+}
+
+#[derive(Debug)]
+pub struct GetIncomingCalls {
+    pub item: HierarchyItem,
+}
+
+#[derive(Debug)]
+pub struct GetOutgoingCalls {
+    pub item: HierarchyItem,
+}
+
+#[derive(Debug)]
+pub struct PrepareTypeHierarchy {
+    pub position: PointUtf16,
+}
+
+#[derive(Debug)]
+pub struct GetSupertypes {
+    pub item: HierarchyItem,
+}
+
+#[derive(Debug)]
+pub struct GetSubtypes {
+    pub item: HierarchyItem,
+}
+
+#[async_trait(?Send)]
+impl LspCommand for PrepareCallHierarchy {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::CallHierarchyPrepare;
+    type ProtoRequest = proto::PrepareCallHierarchy;
+
+    fn display_name(&self) -> &str {
+        "Prepare call hierarchy"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        supports_call_hierarchy(capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyPrepareParams> {
+        Ok(lsp::CallHierarchyPrepareParams {
+            text_document_position_params: make_lsp_text_document_position(path, self.position)?,
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::CallHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        Ok(items_from_lsp(
+            message.unwrap_or_default(),
+            server_id,
+            &lsp_store,
+            &buffer,
+            &cx,
+        ))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::PrepareCallHierarchy {
+        proto::PrepareCallHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(serialize_anchor(&buffer.anchor_before(self.position))),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::PrepareCallHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })
+            .await?;
+        Ok(Self {
+            position: buffer.read_with(&cx, |buffer, _| position.to_point_utf16(buffer)),
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::PrepareCallHierarchyResponse {
+        proto::PrepareCallHierarchyResponse {
+            items: items_to_proto(&response),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::PrepareCallHierarchyResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        items_from_proto(message.items)
+    }
+
+    fn buffer_id_from_proto(message: &proto::PrepareCallHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetIncomingCalls {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::CallHierarchyIncomingCalls;
+    type ProtoRequest = proto::GetIncomingCalls;
+
+    fn display_name(&self) -> &str {
+        "Get incoming calls"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        supports_call_hierarchy(capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyIncomingCallsParams> {
+        Ok(lsp::CallHierarchyIncomingCallsParams {
+            item: self.item.to_lsp()?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::CallHierarchyIncomingCall>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        Ok(items_from_lsp(
+            message
+                .unwrap_or_default()
+                .into_iter()
+                .map(|call| call.from),
+            server_id,
+            &lsp_store,
+            &buffer,
+            &cx,
+        ))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetIncomingCalls {
+        proto::GetIncomingCalls {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            item: Some(self.item.to_proto()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetIncomingCalls,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: HierarchyItem::from_proto(message.item.context("invalid item")?)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetIncomingCallsResponse {
+        proto::GetIncomingCallsResponse {
+            items: items_to_proto(&response),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetIncomingCallsResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        items_from_proto(message.items)
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetIncomingCalls) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetOutgoingCalls {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::CallHierarchyOutgoingCalls;
+    type ProtoRequest = proto::GetOutgoingCalls;
+
+    fn display_name(&self) -> &str {
+        "Get outgoing calls"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        supports_call_hierarchy(capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyOutgoingCallsParams> {
+        Ok(lsp::CallHierarchyOutgoingCallsParams {
+            item: self.item.to_lsp()?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::CallHierarchyOutgoingCall>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        Ok(items_from_lsp(
+            message.unwrap_or_default().into_iter().map(|call| call.to),
+            server_id,
+            &lsp_store,
+            &buffer,
+            &cx,
+        ))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetOutgoingCalls {
+        proto::GetOutgoingCalls {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            item: Some(self.item.to_proto()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetOutgoingCalls,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: HierarchyItem::from_proto(message.item.context("invalid item")?)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetOutgoingCallsResponse {
+        proto::GetOutgoingCallsResponse {
+            items: items_to_proto(&response),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetOutgoingCallsResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        items_from_proto(message.items)
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetOutgoingCalls) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for PrepareTypeHierarchy {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchyPrepare;
+    type ProtoRequest = proto::PrepareTypeHierarchy;
+
+    fn display_name(&self) -> &str {
+        "Prepare type hierarchy"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        supports_type_hierarchy(capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchyPrepareParams> {
+        Ok(lsp::TypeHierarchyPrepareParams {
+            text_document_position_params: make_lsp_text_document_position(path, self.position)?,
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        Ok(items_from_lsp(
+            message.unwrap_or_default(),
+            server_id,
+            &lsp_store,
+            &buffer,
+            &cx,
+        ))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::PrepareTypeHierarchy {
+        proto::PrepareTypeHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(serialize_anchor(&buffer.anchor_before(self.position))),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::PrepareTypeHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })
+            .await?;
+        Ok(Self {
+            position: buffer.read_with(&cx, |buffer, _| position.to_point_utf16(buffer)),
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::PrepareTypeHierarchyResponse {
+        proto::PrepareTypeHierarchyResponse {
+            items: items_to_proto(&response),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::PrepareTypeHierarchyResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        items_from_proto(message.items)
+    }
+
+    fn buffer_id_from_proto(message: &proto::PrepareTypeHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSupertypes {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchySupertypes;
+    type ProtoRequest = proto::GetSupertypes;
+
+    fn display_name(&self) -> &str {
+        "Get supertypes"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        supports_type_hierarchy(capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchySupertypesParams> {
+        Ok(lsp::TypeHierarchySupertypesParams {
+            item: self.item.to_lsp()?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        Ok(items_from_lsp(
+            message.unwrap_or_default(),
+            server_id,
+            &lsp_store,
+            &buffer,
+            &cx,
+        ))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSupertypes {
+        proto::GetSupertypes {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            item: Some(self.item.to_proto()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSupertypes,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: HierarchyItem::from_proto(message.item.context("invalid item")?)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSupertypesResponse {
+        proto::GetSupertypesResponse {
+            items: items_to_proto(&response),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSupertypesResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        items_from_proto(message.items)
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSupertypes) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSubtypes {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchySubtypes;
+    type ProtoRequest = proto::GetSubtypes;
+
+    fn display_name(&self) -> &str {
+        "Get subtypes"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        supports_type_hierarchy(capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchySubtypesParams> {
+        Ok(lsp::TypeHierarchySubtypesParams {
+            item: self.item.to_lsp()?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        Ok(items_from_lsp(
+            message.unwrap_or_default(),
+            server_id,
+            &lsp_store,
+            &buffer,
+            &cx,
+        ))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSubtypes {
+        proto::GetSubtypes {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            item: Some(self.item.to_proto()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSubtypes,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: HierarchyItem::from_proto(message.item.context("invalid item")?)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSubtypesResponse {
+        proto::GetSubtypesResponse {
+            items: items_to_proto(&response),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSubtypesResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        items_from_proto(message.items)
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSubtypes) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
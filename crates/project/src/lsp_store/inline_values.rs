@@ -0,0 +1,212 @@
+//! Values shown at the end of lines while stopped in a debugger, as reported
+//! by language servers through `textDocument/inlineValue`.
+
+use std::{ops::Range, path::Path, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use dap::inline_value::{InlineValueLocation, VariableLookupKind, VariableScope};
+use gpui::{App, AsyncApp, Entity};
+use language::{
+    Anchor, Bias, Buffer, PointUtf16, ToPointUtf16,
+    proto::{deserialize_anchor, deserialize_version, serialize_anchor, serialize_version},
+    range_from_lsp, range_to_lsp,
+};
+use lsp::{AdapterServerCapabilities, LanguageServer, LanguageServerId, OneOf};
+use rpc::proto::{self, PeerId};
+use text::BufferId;
+
+use crate::{
+    lsp_command::{LspCommand, make_text_document_identifier},
+    lsp_store::LspStore,
+};
+
+/// Asks for the inline values of a range of a buffer, for the stack frame the
+/// debugger is stopped in at the end of the range.
+///
+/// Responds with `None` when no language server of the buffer provides
+/// inline values, so that they can be found another way.
+#[derive(Debug)]
+pub struct GetInlineValues {
+    pub range: Range<Anchor>,
+    pub frame_id: u64,
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetInlineValues {
+    type Response = Option<Vec<InlineValueLocation>>;
+    type LspRequest = lsp::request::InlineValueRequest;
+    type ProtoRequest = proto::GetInlineValues;
+
+    fn display_name(&self) -> &str {
+        "Get inline values"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .inline_value_provider
+            .is_some_and(|provider| !matches!(provider, OneOf::Left(false)))
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        buffer: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::InlineValueParams> {
+        let range = self.range.to_point_utf16(buffer);
+        let stopped_location = PointUtf16::new(range.end.row, 0)..range.end;
+        Ok(lsp::InlineValueParams {
+            text_document: make_text_document_identifier(path)?,
+            range: range_to_lsp(range)?,
+            context: lsp::InlineValueContext {
+                frame_id: i32::try_from(self.frame_id).context("stack frame id out of range")?,
+                stopped_location: range_to_lsp(stopped_location)?,
+            },
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::InlineValue>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Option<Vec<InlineValueLocation>>> {
+        let snapshot = buffer.read_with(&cx, |buffer, _| buffer.snapshot());
+        let locations = message
+            .unwrap_or_default()
+            .into_iter()
+            .map(|inline_value| {
+                let (lsp_range, lookup, text) = match inline_value {
+                    lsp::InlineValue::Text(value) => {
+                        (value.range, VariableLookupKind::Text, Some(value.text))
+                    }
+                    lsp::InlineValue::VariableLookup(lookup) => (
+                        lookup.range,
+                        VariableLookupKind::Variable,
+                        lookup.variable_name,
+                    ),
+                    lsp::InlineValue::EvaluatableExpression(expression) => (
+                        expression.range,
+                        VariableLookupKind::Expression,
+                        expression.expression,
+                    ),
+                };
+                let range = range_from_lsp(lsp_range);
+                let start = snapshot.clip_point_utf16(range.start, Bias::Left);
+                let end = snapshot.clip_point_utf16(range.end, Bias::Left);
+                let variable_name =
+                    text.unwrap_or_else(|| snapshot.text_for_range(start..end).collect());
+                let end = snapshot.point_utf16_to_point(end);
+                InlineValueLocation {
+                    variable_name,
+                    scope: VariableScope::Local,
+                    lookup,
+                    row: end.row as usize,
+                    column: end.column as usize,
+                }
+            })
+            .collect();
+        Ok(Some(locations))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetInlineValues {
+        proto::GetInlineValues {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            start: Some(serialize_anchor(&self.range.start)),
+            end: Some(serialize_anchor(&self.range.end)),
+            frame_id: self.frame_id,
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetInlineValues,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let start = message
+            .start
+            .and_then(deserialize_anchor)
+            .context("invalid start")?;
+        let end = message
+            .end
+            .and_then(deserialize_anchor)
+            .context("invalid end")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })
+            .await?;
+        Ok(Self {
+            range: start..end,
+            frame_id: message.frame_id,
+        })
+    }
+
+    fn response_to_proto(
+        response: Option<Vec<InlineValueLocation>>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetInlineValuesResponse {
+        proto::GetInlineValuesResponse {
+            provided: response.is_some(),
+            values: response
+                .unwrap_or_default()
+                .into_iter()
+                .map(|location| proto::InlineValue {
+                    row: location.row as u32,
+                    column: location.column as u32,
+                    text: location.variable_name,
+                    kind: match location.lookup {
+                        VariableLookupKind::Variable => proto::inline_value::Kind::Variable,
+                        VariableLookupKind::Expression => proto::inline_value::Kind::Expression,
+                        VariableLookupKind::Text => proto::inline_value::Kind::Text,
+                    }
+                    .into(),
+                })
+                .collect(),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetInlineValuesResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Option<Vec<InlineValueLocation>>> {
+        if !message.provided {
+            return Ok(None);
+        }
+        let locations = message
+            .values
+            .into_iter()
+            .map(|value| InlineValueLocation {
+                lookup: match value.kind() {
+                    proto::inline_value::Kind::Variable => VariableLookupKind::Variable,
+                    proto::inline_value::Kind::Expression => VariableLookupKind::Expression,
+                    proto::inline_value::Kind::Text => VariableLookupKind::Text,
+                },
+                variable_name: value.text,
+                scope: VariableScope::Local,
+                row: value.row as usize,
+                column: value.column as usize,
+            })
+            .collect();
+        Ok(Some(locations))
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetInlineValues) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
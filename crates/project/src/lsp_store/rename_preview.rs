@@ -0,0 +1,256 @@
+//! The edits of a rename, computed without applying them so that they can be
+//! reviewed first.
+
+use std::{ops::Range, path::Path, sync::Arc};
+
+use anyhow::{Context as _, Result, anyhow};
+use async_trait::async_trait;
+use gpui::{App, AsyncApp, Entity, Task};
+use language::{
+    Anchor, Buffer, PointUtf16, ToPointUtf16,
+    proto::{deserialize_anchor, deserialize_version, serialize_anchor, serialize_version},
+};
+use lsp::{AdapterServerCapabilities, Edit, LanguageServer, LanguageServerId, OneOf};
+use rpc::proto::{self, PeerId};
+use snippet::Snippet;
+use text::BufferId;
+use util::ResultExt as _;
+
+use crate::{
+    lsp_command::{LspCommand, make_lsp_text_document_position},
+    lsp_store::{LocalLspStore, LspStore},
+};
+
+/// The edits a rename makes to one buffer.
+#[derive(Clone, Debug)]
+pub struct RenameBufferEdits {
+    pub buffer: Entity<Buffer>,
+    pub edits: Vec<(Range<Anchor>, Arc<str>)>,
+}
+
+/// Asks for the edits of renaming the symbol at a position, like
+/// `PerformRename`, but leaves the buffers untouched.
+///
+/// Responds with `None` when the rename also creates, renames or deletes
+/// files, which can't be previewed, or when no language server can rename.
+#[derive(Debug)]
+pub(crate) struct GetRenameEdits {
+    pub position: PointUtf16,
+    pub new_name: String,
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetRenameEdits {
+    type Response = Option<Vec<RenameBufferEdits>>;
+    type LspRequest = lsp::request::Rename;
+    type ProtoRequest = proto::GetRenameEdits;
+
+    fn display_name(&self) -> &str {
+        "Get rename edits"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .rename_provider
+            .is_some_and(|capability| !matches!(capability, OneOf::Left(false)))
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::RenameParams> {
+        Ok(lsp::RenameParams {
+            text_document_position: make_lsp_text_document_position(path, self.position)?,
+            new_name: self.new_name.clone(),
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<lsp::WorkspaceEdit>,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Option<Vec<RenameBufferEdits>>> {
+        let Some(edit) = message else {
+            return Ok(Some(Vec::new()));
+        };
+
+        let mut buffer_edits = Vec::<RenameBufferEdits>::new();
+        for operation in LocalLspStore::workspace_edit_operations(edit) {
+            let lsp::DocumentChangeOperation::Edit(operation) = operation else {
+                return Ok(None);
+            };
+            let buffer = lsp_store
+                .update(&mut cx, |lsp_store, cx| {
+                    lsp_store.open_local_buffer_via_lsp(
+                        operation.text_document.uri.clone(),
+                        server_id,
+                        cx,
+                    )
+                })
+                .await?;
+            let lsp_edits = operation
+                .edits
+                .into_iter()
+                .filter_map(|edit| match edit {
+                    Edit::Plain(edit) => Some(edit),
+                    Edit::Annotated(edit) => Some(edit.text_edit),
+                    Edit::Snippet(edit) => {
+                        Snippet::parse(&edit.snippet.value)
+                            .log_err()
+                            .map(|snippet| lsp::TextEdit {
+                                range: edit.range,
+                                new_text: snippet.text,
+                            })
+                    }
+                })
+                .collect::<Vec<_>>();
+            let edits = lsp_store
+                .update(&mut cx, |lsp_store, cx| {
+                    let Some(local) = lsp_store.as_local_mut() else {
+                        return Task::ready(Err(anyhow!("rename edits are only computed locally")));
+                    };
+                    local.edits_from_lsp(
+                        &buffer,
+                        lsp_edits,
+                        server_id,
+                        operation.text_document.version,
+                        cx,
+                    )
+                })
+                .await?;
+
+            match buffer_edits
+                .iter_mut()
+                .find(|buffer_edits| buffer_edits.buffer == buffer)
+            {
+                Some(buffer_edits) => buffer_edits.edits.extend(edits),
+                None => buffer_edits.push(RenameBufferEdits { buffer, edits }),
+            }
+        }
+        Ok(Some(buffer_edits))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetRenameEdits {
+        proto::GetRenameEdits {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(serialize_anchor(&buffer.anchor_before(self.position))),
+            new_name: self.new_name.clone(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetRenameEdits,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })
+            .await?;
+        Ok(Self {
+            position: buffer.read_with(&cx, |buffer, _| position.to_point_utf16(buffer)),
+            new_name: message.new_name,
+        })
+    }
+
+    fn response_to_proto(
+        response: Option<Vec<RenameBufferEdits>>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetRenameEditsResponse {
+        proto::GetRenameEditsResponse {
+            previewable: response.is_some(),
+            buffers: response
+                .unwrap_or_default()
+                .into_iter()
+                .map(|buffer_edits| {
+                    lsp_store
+                        .buffer_store()
+                        .update(cx, |buffer_store, cx| {
+                            buffer_store.create_buffer_for_peer(&buffer_edits.buffer, peer_id, cx)
+                        })
+                        .detach_and_log_err(cx);
+                    proto::RenameBufferEdits {
+                        buffer_id: buffer_edits.buffer.read(cx).remote_id().into(),
+                        edits: buffer_edits
+                            .edits
+                            .into_iter()
+                            .map(|(range, new_text)| proto::RenameEdit {
+                                start: Some(serialize_anchor(&range.start)),
+                                end: Some(serialize_anchor(&range.end)),
+                                new_text: new_text.to_string(),
+                            })
+                            .collect(),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetRenameEditsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Option<Vec<RenameBufferEdits>>> {
+        if !message.previewable {
+            return Ok(None);
+        }
+        let mut buffer_edits = Vec::new();
+        for proto_buffer_edits in message.buffers {
+            let buffer_id = BufferId::new(proto_buffer_edits.buffer_id)?;
+            let buffer = lsp_store
+                .update(&mut cx, |lsp_store, cx| {
+                    lsp_store.wait_for_remote_buffer(buffer_id, cx)
+                })
+                .await?;
+            let mut edits = Vec::new();
+            for edit in proto_buffer_edits.edits {
+                let start = edit
+                    .start
+                    .and_then(deserialize_anchor)
+                    .context("missing edit start")?;
+                let end = edit
+                    .end
+                    .and_then(deserialize_anchor)
+                    .context("missing edit end")?;
+                edits.push((start..end, Arc::from(edit.new_text)));
+            }
+            buffer
+                .update(&mut cx, |buffer, _| {
+                    buffer.wait_for_anchors(
+                        edits
+                            .iter()
+                            .flat_map(|(range, _)| [range.start, range.end])
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .await?;
+            buffer_edits.push(RenameBufferEdits { buffer, edits });
+        }
+        Ok(Some(buffer_edits))
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetRenameEdits) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
@@ -250,11 +250,27 @@ impl LanguageServerTree {
         if !settings.enable_language_server {
             return Default::default();
         }
-        let available_lsp_adapters = self.languages.lsp_adapters(language_name);
-        let available_language_servers = available_lsp_adapters
+        let custom_language_servers =
+            crate::lsp_store::custom_language_servers_for(settings_location, language_name, cx);
+        // Custom language servers stay registered for a language after their
+        // settings stop listing it, so they're left out then.
+        let available_lsp_adapters = self
+            .languages
+            .lsp_adapters(language_name)
+            .into_iter()
+            .filter(|adapter| {
+                !adapter.adapter.is_custom() || custom_language_servers.contains(&adapter.name)
+            })
+            .collect::<Vec<_>>();
+        let mut available_language_servers = available_lsp_adapters
             .iter()
             .map(|lsp_adapter| lsp_adapter.name.clone())
             .collect::<Vec<_>>();
+        for name in &custom_language_servers {
+            if !available_language_servers.contains(name) {
+                available_language_servers.push(name.clone());
+            }
+        }
 
         let desired_language_servers =
             settings.customized_language_servers(&available_language_servers);
@@ -272,6 +288,28 @@ impl LanguageServerTree {
                     self.languages
                         .register_lsp_adapter(language_name.clone(), adapter.adapter.clone());
                     Some(adapter)
+                } else if let Some(adapter) = self
+                    .languages
+                    .adapter_for_name(&desired_adapter)
+                    .filter(|_| custom_language_servers.contains(&desired_adapter))
+                {
+                    self.languages
+                        .register_lsp_adapter(language_name.clone(), adapter.adapter.clone());
+                    Some(adapter)
+                } else if crate::lsp_store::language_server_settings_for(
+                    settings_location,
+                    &desired_adapter,
+                    cx,
+                )
+                .and_then(|settings| settings.binary.as_ref())
+                .is_some_and(|binary| binary.path.is_some())
+                {
+                    let adapter = CachedLspAdapter::new(Arc::new(
+                        crate::lsp_store::CustomLspAdapter::new(desired_adapter.clone()),
+                    ));
+                    self.languages
+                        .register_lsp_adapter(language_name.clone(), adapter.adapter.clone());
+                    Some(adapter)
                 } else {
                     None
                 }?;
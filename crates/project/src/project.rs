@@ -1,5 +1,6 @@
 pub mod agent_registry_store;
 pub mod agent_server_store;
+pub mod bookmark_store;
 pub mod buffer_store;
 pub mod color_extractor;
 pub mod connection_manager;
@@ -37,7 +38,16 @@ use itertools::Either;
 
 use crate::{
     git_store::GitStore,
-    lsp_store::{SymbolLocation, log_store::LogKind},
+    lsp_store::{
+        SymbolLocation,
+        hierarchy::{
+            GetIncomingCalls, GetOutgoingCalls, GetSubtypes, GetSupertypes, PrepareCallHierarchy,
+            PrepareTypeHierarchy,
+        },
+        inline_values::GetInlineValues,
+        log_store::LogKind,
+        rename_preview::GetRenameEdits,
+    },
     project_search::SearchResultsHandle,
     trusted_worktrees::{PathTrust, RemoteHostLocation, TrustedWorktrees},
     worktree_store::WorktreeIdCounter,
@@ -54,6 +64,7 @@ pub use manifest_tree::ManifestTree;
 pub use project_search::{Search, SearchResults};
 
 use anyhow::{Context as _, Result, anyhow};
+use bookmark_store::BookmarkStore;
 use buffer_store::{BufferStore, BufferStoreEvent};
 use client::{
     Client, Collaborator, PendingEntitySubscription, ProjectId, TypedEnvelope, UserStore, proto,
@@ -158,7 +169,8 @@ pub use buffer_store::ProjectTransaction;
 pub use lsp_store::{
     DiagnosticSummary, InvalidationStrategy, LanguageServerLogType, LanguageServerProgress,
     LanguageServerPromptRequest, LanguageServerStatus, LanguageServerToQuery, LspStore,
-    LspStoreEvent, ProgressToken, SERVER_PROGRESS_THROTTLE_TIMEOUT,
+    LspStoreEvent, ProgressToken, SERVER_PROGRESS_THROTTLE_TIMEOUT, hierarchy::HierarchyItem,
+    rename_preview::RenameBufferEdits,
 };
 pub use toolchain_store::{ToolchainStore, Toolchains};
 const MAX_PROJECT_SEARCH_HISTORY_SIZE: usize = 500;
@@ -211,6 +223,7 @@ pub struct Project {
     agent_server_store: Entity<AgentServerStore>,
 
     breakpoint_store: Entity<BreakpointStore>,
+    bookmark_store: Entity<BookmarkStore>,
     collab_client: Arc<client::Client>,
     join_project_response_message_id: u32,
     task_store: Entity<TaskStore>,
@@ -1196,6 +1209,8 @@ impl Project {
 
             let breakpoint_store =
                 cx.new(|_| BreakpointStore::local(worktree_store.clone(), buffer_store.clone()));
+            let bookmark_store =
+                cx.new(|_| BookmarkStore::new(worktree_store.clone(), buffer_store.clone()));
 
             let dap_store = cx.new(|cx| {
                 DapStore::new_local(
@@ -1312,6 +1327,7 @@ impl Project {
                 fs,
                 remote_client: None,
                 breakpoint_store,
+                bookmark_store,
                 dap_store,
                 agent_server_store,
 
@@ -1475,6 +1491,8 @@ impl Project {
                     worktree_store.clone(),
                 )
             });
+            let bookmark_store =
+                cx.new(|_| BookmarkStore::new(worktree_store.clone(), buffer_store.clone()));
 
             let dap_store = cx.new(|cx| {
                 DapStore::new_remote(
@@ -1513,6 +1531,7 @@ impl Project {
                 lsp_store,
                 context_server_store,
                 breakpoint_store,
+                bookmark_store,
                 dap_store,
                 join_project_response_message_id: 0,
                 client_state: ProjectClientState::Local,
@@ -1697,6 +1716,8 @@ impl Project {
                 worktree_store.clone(),
             )
         });
+        let bookmark_store =
+            cx.new(|_| BookmarkStore::new(worktree_store.clone(), buffer_store.clone()));
         let dap_store = cx.new(|cx| {
             DapStore::new_collab(
                 remote_id,
@@ -1822,6 +1843,7 @@ impl Project {
                     replica_id,
                 },
                 breakpoint_store: breakpoint_store.clone(),
+                bookmark_store,
                 dap_store: dap_store.clone(),
                 git_store: git_store.clone(),
                 agent_server_store,
@@ -2054,6 +2076,10 @@ impl Project {
         self.breakpoint_store.clone()
     }
 
+    pub fn bookmark_store(&self) -> Entity<BookmarkStore> {
+        self.bookmark_store.clone()
+    }
+
     pub fn active_debug_session(&self, cx: &App) -> Option<(Entity<Session>, ActiveStackFrame)> {
         let active_position = self.breakpoint_store.read(cx).active_position()?;
         let session = self
@@ -4243,6 +4269,17 @@ impl Project {
         })
     }
 
+    pub fn resolve_code_lens(
+        &self,
+        buffer: &Entity<Buffer>,
+        lens_action: CodeAction,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<CodeAction>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.resolve_code_lens(buffer, lens_action, cx)
+        })
+    }
+
     pub fn apply_code_action(
         &self,
         buffer_handle: Entity<Buffer>,
@@ -4267,6 +4304,108 @@ impl Project {
         })
     }
 
+    /// The call hierarchy items at the position, usually the function or
+    /// method whose name is there.
+    pub fn prepare_call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer,
+            LanguageServerToQuery::FirstCapable,
+            PrepareCallHierarchy { position },
+            cx,
+        )
+    }
+
+    /// The items calling the given one, asked of the language server that
+    /// reported it through a buffer it was opened in.
+    pub fn incoming_calls(
+        &mut self,
+        buffer: Entity<Buffer>,
+        item: HierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        let server_id = item.symbol.source_language_server_id;
+        self.request_lsp(
+            buffer,
+            LanguageServerToQuery::Other(server_id),
+            GetIncomingCalls { item },
+            cx,
+        )
+    }
+
+    /// The items called by the given one, asked of the language server that
+    /// reported it through a buffer it was opened in.
+    pub fn outgoing_calls(
+        &mut self,
+        buffer: Entity<Buffer>,
+        item: HierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        let server_id = item.symbol.source_language_server_id;
+        self.request_lsp(
+            buffer,
+            LanguageServerToQuery::Other(server_id),
+            GetOutgoingCalls { item },
+            cx,
+        )
+    }
+
+    /// The type hierarchy items at the position, usually the type whose name
+    /// is there.
+    pub fn prepare_type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer,
+            LanguageServerToQuery::FirstCapable,
+            PrepareTypeHierarchy { position },
+            cx,
+        )
+    }
+
+    /// The direct supertypes of the given item, asked of the language server
+    /// that reported it through a buffer it was opened in.
+    pub fn supertypes(
+        &mut self,
+        buffer: Entity<Buffer>,
+        item: HierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        let server_id = item.symbol.source_language_server_id;
+        self.request_lsp(
+            buffer,
+            LanguageServerToQuery::Other(server_id),
+            GetSupertypes { item },
+            cx,
+        )
+    }
+
+    /// The direct subtypes of the given item, asked of the language server
+    /// that reported it through a buffer it was opened in.
+    pub fn subtypes(
+        &mut self,
+        buffer: Entity<Buffer>,
+        item: HierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        let server_id = item.symbol.source_language_server_id;
+        self.request_lsp(
+            buffer,
+            LanguageServerToQuery::Other(server_id),
+            GetSubtypes { item },
+            cx,
+        )
+    }
+
     pub fn prepare_rename<T: ToPointUtf16>(
         &mut self,
         buffer: Entity<Buffer>,
@@ -4303,6 +4442,25 @@ impl Project {
         )
     }
 
+    /// The edits of renaming the symbol at the position, without applying
+    /// them. `None` when the rename can't be previewed, because it also
+    /// creates, renames or deletes files.
+    pub fn rename_edits<T: ToPointUtf16>(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: T,
+        new_name: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<Vec<RenameBufferEdits>>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer,
+            LanguageServerToQuery::FirstCapable,
+            GetRenameEdits { position, new_name },
+            cx,
+        )
+    }
+
     pub fn on_type_format<T: ToPointUtf16>(
         &mut self,
         buffer: Entity<Buffer>,
@@ -4325,18 +4483,31 @@ impl Project {
         cx: &mut Context<Self>,
     ) -> Task<anyhow::Result<Vec<InlayHint>>> {
         let snapshot = buffer_handle.read(cx).snapshot();
-
-        let captures =
-            snapshot.debug_variables_query(Anchor::min_for_buffer(snapshot.remote_id())..range.end);
-
-        let row = snapshot
-            .summary_for_anchor::<text::PointUtf16>(&range.end)
-            .row as usize;
-
-        let inline_value_locations = provide_inline_values(captures, &snapshot, row);
-
         let stack_frame_id = active_stack_frame.stack_frame_id;
+        let lsp_inline_values = self.request_lsp(
+            buffer_handle.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetInlineValues {
+                range: range.clone(),
+                frame_id: stack_frame_id,
+            },
+            cx,
+        );
+
         cx.spawn(async move |this, cx| {
+            let inline_value_locations = match lsp_inline_values.await.log_err().flatten() {
+                Some(inline_value_locations) => inline_value_locations,
+                None => {
+                    let captures = snapshot.debug_variables_query(
+                        Anchor::min_for_buffer(snapshot.remote_id())..range.end,
+                    );
+                    let row = snapshot
+                        .summary_for_anchor::<text::PointUtf16>(&range.end)
+                        .row as usize;
+                    provide_inline_values(captures, &snapshot, row)
+                }
+            };
+
             this.update(cx, |project, cx| {
                 project.dap_store().update(cx, |dap_store, cx| {
                     dap_store.resolve_inline_value_locations(
@@ -5777,6 +5948,38 @@ impl Project {
             .any(|capabilities| capabilities.semantic_tokens_provider.is_some())
     }
 
+    pub fn any_language_server_supports_document_highlights(
+        &self,
+        buffer: &Buffer,
+        cx: &mut App,
+    ) -> bool {
+        let Some(language) = buffer.language().cloned() else {
+            return false;
+        };
+        let lsp_store = self.lsp_store.read(cx);
+        let relevant_language_servers = lsp_store
+            .languages
+            .lsp_adapters(&language.name())
+            .into_iter()
+            .map(|lsp_adapter| lsp_adapter.name())
+            .collect::<HashSet<_>>();
+        lsp_store
+            .language_server_statuses()
+            .filter_map(|(server_id, server_status)| {
+                relevant_language_servers
+                    .contains(&server_status.name)
+                    .then_some(server_id)
+            })
+            .filter_map(|server_id| lsp_store.lsp_server_capabilities.get(&server_id))
+            .any(
+                |capabilities| match capabilities.document_highlight_provider {
+                    Some(lsp::OneOf::Left(supported)) => supported,
+                    Some(lsp::OneOf::Right(_)) => true,
+                    None => false,
+                },
+            )
+    }
+
     pub fn language_server_id_for_name(
         &self,
         buffer: &Buffer,
@@ -290,7 +290,18 @@ impl Project {
         cwd: Option<PathBuf>,
         cx: &mut Context<Self>,
     ) -> Task<Result<Entity<Terminal>>> {
-        self.create_terminal_shell_internal(cwd, false, cx)
+        self.create_terminal_shell_internal(cwd, None, false, cx)
+    }
+
+    /// Creates a terminal with the shell and environment of the named terminal
+    /// profile, or of the default profile when no name is given.
+    pub fn create_terminal_shell_with_profile(
+        &mut self,
+        cwd: Option<PathBuf>,
+        profile: Option<String>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Terminal>>> {
+        self.create_terminal_shell_internal(cwd, profile, false, cx)
     }
 
     /// Creates a local terminal even if the project is remote.
@@ -307,7 +318,7 @@ impl Project {
             // Local project: use project directory like normal terminals
             self.active_project_directory(cx).map(|p| p.to_path_buf())
         };
-        self.create_terminal_shell_internal(working_directory, true, cx)
+        self.create_terminal_shell_internal(working_directory, None, true, cx)
     }
 
     /// Internal method for creating terminal shells.
@@ -316,6 +327,7 @@ impl Project {
     fn create_terminal_shell_internal(
         &mut self,
         cwd: Option<PathBuf>,
+        profile: Option<String>,
         force_local: bool,
         cx: &mut Context<Self>,
     ) -> Task<Result<Entity<Terminal>>> {
@@ -332,6 +344,10 @@ impl Project {
             });
         }
         let settings = TerminalSettings::get(settings_location, cx).clone();
+        let profile = settings
+            .profile(profile.as_deref())
+            .map(|(name, _)| name.to_string());
+        let settings = settings.with_profile(profile.as_deref());
         let detect_venv = settings.detect_venv.as_option().is_some();
         let local_path = if is_via_remote { None } else { path.clone() };
 
@@ -425,7 +441,11 @@ impl Project {
                 })??
                 .await?;
             project.update(cx, move |this, cx| {
-                let terminal_handle = cx.new(|cx| builder.subscribe(cx));
+                let terminal_handle = cx.new(|cx| {
+                    let mut terminal = builder.subscribe(cx);
+                    terminal.set_profile(profile);
+                    terminal
+                });
 
                 this.terminals
                     .local_handles
@@ -468,10 +488,15 @@ impl Project {
         };
 
         let builder = terminal.read(cx).clone_builder(cx, local_path);
+        let profile = terminal.read(cx).profile().map(str::to_string);
         cx.spawn(async |project, cx| {
             let terminal = builder.await?;
             project.update(cx, |project, cx| {
-                let terminal_handle = cx.new(|cx| terminal.subscribe(cx));
+                let terminal_handle = cx.new(|cx| {
+                    let mut terminal = terminal.subscribe(cx);
+                    terminal.set_profile(profile);
+                    terminal
+                });
 
                 project
                     .terminals
@@ -2011,6 +2011,64 @@ async fn test_language_server_relative_path(cx: &mut gpui::TestAppContext) {
     assert_eq!(lsp_path.to_string_lossy(), path!("lsp_on_path.exe"));
 }
 
+#[gpui::test]
+async fn test_custom_language_server(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let settings_json_contents = json!({
+        "lsp": {
+            "my_custom_lsp": {
+                "languages": ["Rust"],
+                "binary": {
+                    "path": path!("/usr/bin/my_custom_lsp"),
+                    "arguments": ["--stdio"],
+                }
+            }
+        },
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/the-root"),
+        json!({
+            ".zed": {
+                "settings.json": settings_json_contents.to_string(),
+            },
+            "src": {
+                "main.rs": "",
+            }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/the-root").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+
+    // Only the server is faked: no adapter is registered for it.
+    let mut my_custom_lsp = language_registry.register_fake_lsp_server(
+        LanguageServerName::new_static("my_custom_lsp"),
+        lsp::ServerCapabilities::default(),
+        None,
+    );
+
+    cx.run_until_parked();
+
+    project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/the-root/src/main.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let binary = my_custom_lsp.next().await.unwrap().binary;
+    assert_eq!(
+        binary.path.to_string_lossy(),
+        path!("/usr/bin/my_custom_lsp")
+    );
+    assert_eq!(binary.arguments, ["--stdio"]);
+}
+
 #[gpui::test]
 async fn test_language_server_tilde_path(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4158,6 +4216,484 @@ async fn test_definition(cx: &mut gpui::TestAppContext) {
     }
 }
 
+fn hierarchy_item<T: serde::de::DeserializeOwned>(name: &str, line: u32) -> T {
+    let range = lsp::Range::new(lsp::Position::new(line, 3), lsp::Position::new(line, 4));
+    serde_json::from_value(json!({
+        "name": name,
+        "kind": lsp::SymbolKind::FUNCTION,
+        "uri": lsp::Uri::from_file_path(path!("/dir/a.rs")).unwrap(),
+        "range": range,
+        "selectionRange": range,
+    }))
+    .unwrap()
+}
+
+fn hierarchy_item_names(items: &[HierarchyItem]) -> Vec<&str> {
+    items.iter().map(|item| item.symbol.name.as_str()).collect()
+}
+
+#[gpui::test]
+async fn test_call_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() { b() }\nfn b() { c() }\nfn c() {}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>(
+        |params, _| async move {
+            assert_eq!(
+                params.text_document_position_params.position,
+                lsp::Position::new(1, 3)
+            );
+            Ok(Some(vec![hierarchy_item("b", 1)]))
+        },
+    );
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_call_hierarchy(buffer.clone(), PointUtf16::new(1, 3), cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(hierarchy_item_names(&items), ["b"]);
+    let b = items[0].clone();
+    assert_eq!(b.symbol.range.start, Unclipped(PointUtf16::new(1, 3)));
+
+    fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(
+        |params, _| async move {
+            assert_eq!(params.item.name, "b");
+            Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                from: hierarchy_item("a", 0),
+                from_ranges: vec![lsp::Range::new(
+                    lsp::Position::new(0, 9),
+                    lsp::Position::new(0, 10),
+                )],
+            }]))
+        },
+    );
+    let callers = project
+        .update(cx, |project, cx| {
+            project.incoming_calls(buffer.clone(), b.clone(), cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(hierarchy_item_names(&callers), ["a"]);
+
+    fake_server.set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>(
+        |params, _| async move {
+            assert_eq!(params.item.name, "b");
+            Ok(Some(vec![lsp::CallHierarchyOutgoingCall {
+                to: hierarchy_item("c", 2),
+                from_ranges: vec![lsp::Range::new(
+                    lsp::Position::new(1, 9),
+                    lsp::Position::new(1, 10),
+                )],
+            }]))
+        },
+    );
+    let callees = project
+        .update(cx, |project, cx| {
+            project.outgoing_calls(buffer.clone(), b, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(hierarchy_item_names(&callees), ["c"]);
+}
+
+#[gpui::test]
+async fn test_type_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() {}\nfn b() {}\nfn c() {}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    // Servers that don't advertise type hierarchies are not asked for them.
+    fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(
+        |_, _| async move { panic!("the server doesn't support type hierarchies") },
+    );
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(buffer.clone(), PointUtf16::new(1, 3), cx)
+        })
+        .await
+        .unwrap();
+    assert!(items.is_empty());
+
+    fake_server
+        .request::<lsp::request::RegisterCapability>(
+            lsp::RegistrationParams {
+                registrations: vec![lsp::Registration {
+                    id: Default::default(),
+                    method: "textDocument/prepareTypeHierarchy".to_string(),
+                    register_options: None,
+                }],
+            },
+            DEFAULT_LSP_REQUEST_TIMEOUT,
+        )
+        .await
+        .into_response()
+        .unwrap();
+    cx.executor().run_until_parked();
+
+    fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(
+        |_, _| async move { Ok(Some(vec![hierarchy_item("b", 1)])) },
+    );
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(buffer.clone(), PointUtf16::new(1, 3), cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(hierarchy_item_names(&items), ["b"]);
+    let b = items[0].clone();
+
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+        |params, _| async move {
+            assert_eq!(params.item.name, "b");
+            Ok(Some(vec![hierarchy_item("a", 0)]))
+        },
+    );
+    let supertypes = project
+        .update(cx, |project, cx| {
+            project.supertypes(buffer.clone(), b.clone(), cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(hierarchy_item_names(&supertypes), ["a"]);
+
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>(
+        |params, _| async move {
+            assert_eq!(params.item.name, "b");
+            Ok(Some(vec![hierarchy_item("c", 2)]))
+        },
+    );
+    let subtypes = project
+        .update(cx, |project, cx| project.subtypes(buffer.clone(), b, cx))
+        .await
+        .unwrap();
+    assert_eq!(hierarchy_item_names(&subtypes), ["c"]);
+}
+
+#[gpui::test]
+async fn test_inline_values(cx: &mut gpui::TestAppContext) {
+    use dap::inline_value::{InlineValueLocation, VariableLookupKind, VariableScope};
+    use project::{lsp_command::LspCommand as _, lsp_store::inline_values::GetInlineValues};
+
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn main() {\n    let count = 1;\n    let total = count + 1;\n}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                inline_value_provider: Some(lsp::OneOf::Left(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    fake_server.set_request_handler::<lsp::request::InlineValueRequest, _, _>(
+        |params, _| async move {
+            assert_eq!(params.context.frame_id, 7);
+            assert_eq!(
+                params.context.stopped_location,
+                lsp::Range::new(lsp::Position::new(2, 0), lsp::Position::new(2, 26))
+            );
+            Ok(Some(vec![
+                lsp::InlineValue::VariableLookup(lsp::InlineValueVariableLookup {
+                    range: lsp::Range::new(lsp::Position::new(1, 8), lsp::Position::new(1, 13)),
+                    variable_name: None,
+                    case_sensitive_lookup: true,
+                }),
+                lsp::InlineValue::Text(lsp::InlineValueText {
+                    range: lsp::Range::new(lsp::Position::new(2, 8), lsp::Position::new(2, 13)),
+                    text: "total = 2".to_string(),
+                }),
+            ]))
+        },
+    );
+    let range = buffer.read_with(cx, |buffer, _| {
+        buffer.anchor_before(Point::new(0, 0))..buffer.anchor_after(Point::new(2, 26))
+    });
+    let request = GetInlineValues {
+        range: range.clone(),
+        frame_id: 7,
+    };
+    let locations = project
+        .update(cx, |project, cx| {
+            project.request_lsp(
+                buffer.clone(),
+                LanguageServerToQuery::FirstCapable,
+                request,
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    let expected_locations = vec![
+        InlineValueLocation {
+            variable_name: "count".to_string(),
+            scope: VariableScope::Local,
+            lookup: VariableLookupKind::Variable,
+            row: 1,
+            column: 13,
+        },
+        InlineValueLocation {
+            variable_name: "total = 2".to_string(),
+            scope: VariableScope::Local,
+            lookup: VariableLookupKind::Text,
+            row: 2,
+            column: 13,
+        },
+    ];
+    assert_eq!(locations, Some(expected_locations.clone()));
+
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+    let request = GetInlineValues {
+        range: range.clone(),
+        frame_id: 7,
+    };
+    let message = buffer.read_with(cx, |buffer, _| request.to_proto(1, buffer));
+    let request =
+        GetInlineValues::from_proto(message, lsp_store.clone(), buffer.clone(), cx.to_async())
+            .await
+            .unwrap();
+    assert_eq!(request.range, range);
+    assert_eq!(request.frame_id, 7);
+
+    for locations in [Some(expected_locations), None] {
+        let response = lsp_store.update(cx, |lsp_store, cx| {
+            GetInlineValues::response_to_proto(
+                locations.clone(),
+                lsp_store,
+                rpc::proto::PeerId::default(),
+                &clock::Global::default(),
+                cx,
+            )
+        });
+        let request = GetInlineValues {
+            range: range.clone(),
+            frame_id: 7,
+        };
+        let round_tripped = request
+            .response_from_proto(response, lsp_store.clone(), buffer.clone(), cx.to_async())
+            .await
+            .unwrap();
+        assert_eq!(round_tripped, locations);
+    }
+}
+
+#[gpui::test]
+async fn test_code_actions_on_save(cx: &mut gpui::TestAppContext) {
+    use project::lsp_store::{FormatTrigger, LspFormatTarget};
+
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/dir"), json!({ "a.rs": "fn a() {}\n" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings.languages_mut().insert(
+                    "Rust".into(),
+                    LanguageSettingsContent {
+                        format_on_save: Some(settings::FormatOnSave::Off),
+                        formatter: Some(settings::FormatterList::Vec(Vec::new())),
+                        code_actions_on_save: Some(vec![
+                            "source.first".to_string(),
+                            "source.second".to_string(),
+                        ]),
+                        code_actions_on_save_timeout_ms: Some(1000),
+                        ..Default::default()
+                    },
+                );
+            });
+        })
+    });
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    // Each action inserts its number at the start of the file, so the text
+    // shows the order they ran in.
+    let requested_kinds = Arc::new(Mutex::new(Vec::new()));
+    let slow_kind = Arc::new(Mutex::new(None::<String>));
+    fake_server.set_request_handler::<lsp::request::CodeActionRequest, _, _>({
+        let requested_kinds = requested_kinds.clone();
+        let slow_kind = slow_kind.clone();
+        move |params, _| {
+            let kind = params.context.only.unwrap()[0].as_str().to_string();
+            requested_kinds.lock().push(kind.clone());
+            let is_slow = slow_kind.lock().as_ref() == Some(&kind);
+            async move {
+                if is_slow {
+                    future::pending::<()>().await;
+                }
+                let number = if kind == "source.first" { "1" } else { "2" };
+                Ok(Some(vec![lsp::CodeActionOrCommand::CodeAction(
+                    lsp::CodeAction {
+                        title: kind.clone(),
+                        kind: Some(kind.into()),
+                        edit: Some(lsp::WorkspaceEdit {
+                            changes: Some(
+                                [(
+                                    params.text_document.uri,
+                                    vec![lsp::TextEdit::new(
+                                        lsp::Range::new(
+                                            lsp::Position::new(0, 0),
+                                            lsp::Position::new(0, 0),
+                                        ),
+                                        number.to_string(),
+                                    )],
+                                )]
+                                .into_iter()
+                                .collect(),
+                            ),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
+                )]))
+            }
+        }
+    });
+    let format = |trigger: FormatTrigger, cx: &mut gpui::TestAppContext| {
+        project.update(cx, |project, cx| {
+            project.format(
+                HashSet::from_iter([buffer.clone()]),
+                LspFormatTarget::Buffers,
+                true,
+                trigger,
+                cx,
+            )
+        })
+    };
+
+    // They run in order on save, even though formatting on save is off.
+    format(FormatTrigger::Save, cx).await.unwrap();
+    assert_eq!(
+        requested_kinds.lock().drain(..).collect::<Vec<_>>(),
+        ["source.first", "source.second"]
+    );
+    assert_eq!(
+        buffer.read_with(cx, |buffer, _| buffer.text()),
+        "21fn a() {}\n"
+    );
+
+    // They don't run when formatting manually.
+    format(FormatTrigger::Manual, cx).await.unwrap();
+    assert!(requested_kinds.lock().is_empty());
+    assert_eq!(
+        buffer.read_with(cx, |buffer, _| buffer.text()),
+        "21fn a() {}\n"
+    );
+
+    // An action the server takes too long to provide is skipped.
+    slow_kind.lock().replace("source.first".to_string());
+    let save = format(FormatTrigger::Save, cx);
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(Duration::from_millis(1000));
+    save.await.unwrap();
+    assert_eq!(
+        requested_kinds.lock().drain(..).collect::<Vec<_>>(),
+        ["source.first", "source.second"]
+    );
+    assert_eq!(
+        buffer.read_with(cx, |buffer, _| buffer.text()),
+        "221fn a() {}\n"
+    );
+}
+
 #[gpui::test]
 async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -6324,6 +6860,223 @@ async fn test_rename(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_rename_edits(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "one.rs": "const ONE: usize = 1;",
+            "two.rs": "const TWO: usize = one::ONE + one::ONE;"
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                rename_provider: Some(lsp::OneOf::Left(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/one.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    let response = project.update(cx, |project, cx| {
+        project.rename_edits(buffer.clone(), 7, "THREE".to_string(), cx)
+    });
+    fake_server
+        .set_request_handler::<lsp::request::Rename, _, _>(|params, _| async move {
+            assert_eq!(params.new_name, "THREE");
+            Ok(Some(lsp::WorkspaceEdit {
+                changes: Some(
+                    [
+                        (
+                            lsp::Uri::from_file_path(path!("/dir/one.rs")).unwrap(),
+                            vec![lsp::TextEdit::new(
+                                lsp::Range::new(lsp::Position::new(0, 6), lsp::Position::new(0, 9)),
+                                "THREE".to_string(),
+                            )],
+                        ),
+                        (
+                            lsp::Uri::from_file_path(path!("/dir/two.rs")).unwrap(),
+                            vec![
+                                lsp::TextEdit::new(
+                                    lsp::Range::new(
+                                        lsp::Position::new(0, 24),
+                                        lsp::Position::new(0, 27),
+                                    ),
+                                    "THREE".to_string(),
+                                ),
+                                lsp::TextEdit::new(
+                                    lsp::Range::new(
+                                        lsp::Position::new(0, 35),
+                                        lsp::Position::new(0, 38),
+                                    ),
+                                    "THREE".to_string(),
+                                ),
+                            ],
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+                ..Default::default()
+            }))
+        })
+        .next()
+        .await
+        .unwrap();
+    let buffer_edits = response.await.unwrap().expect("the rename is previewable");
+
+    // The edits are resolved in each buffer, but not applied yet.
+    let mut edits_by_path = buffer_edits
+        .iter()
+        .map(|buffer_edits| {
+            buffer_edits.buffer.read_with(cx, |buffer, cx| {
+                let path = buffer.file().unwrap().full_path(cx);
+                let edits = buffer_edits
+                    .edits
+                    .iter()
+                    .map(|(range, new_text)| (range.to_offset(buffer), new_text.to_string()))
+                    .collect::<Vec<_>>();
+                (path, buffer.text(), edits)
+            })
+        })
+        .collect::<Vec<_>>();
+    edits_by_path.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        edits_by_path,
+        [
+            (
+                PathBuf::from(path!("dir/one.rs")),
+                "const ONE: usize = 1;".to_string(),
+                vec![(6..9, "THREE".to_string())],
+            ),
+            (
+                PathBuf::from(path!("dir/two.rs")),
+                "const TWO: usize = one::ONE + one::ONE;".to_string(),
+                vec![(24..27, "THREE".to_string()), (35..38, "THREE".to_string())],
+            ),
+        ]
+    );
+
+    // Renames that also move files can't be previewed.
+    let response = project.update(cx, |project, cx| {
+        project.rename_edits(buffer.clone(), 7, "THREE".to_string(), cx)
+    });
+    fake_server
+        .set_request_handler::<lsp::request::Rename, _, _>(|_, _| async move {
+            Ok(Some(lsp::WorkspaceEdit {
+                document_changes: Some(lsp::DocumentChanges::Operations(vec![
+                    lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Rename(lsp::RenameFile {
+                        old_uri: lsp::Uri::from_file_path(path!("/dir/one.rs")).unwrap(),
+                        new_uri: lsp::Uri::from_file_path(path!("/dir/three.rs")).unwrap(),
+                        options: None,
+                        annotation_id: None,
+                    })),
+                ])),
+                ..Default::default()
+            }))
+        })
+        .next()
+        .await
+        .unwrap();
+    assert!(response.await.unwrap().is_none());
+}
+
+#[gpui::test]
+async fn test_language_server_priorities(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/dir"), json!({ "a.tsx": "a" })).await;
+
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings.languages_mut().insert(
+                    "tsx".into(),
+                    LanguageSettingsContent {
+                        language_server_priorities: Some(HashMap::from_iter([(
+                            "textDocument/rename".to_string(),
+                            vec!["ESLintServer".to_string()],
+                        )])),
+                        ..Default::default()
+                    },
+                );
+            });
+        })
+    });
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(tsx_lang());
+    let rename_capabilities = lsp::ServerCapabilities {
+        rename_provider: Some(lsp::OneOf::Left(true)),
+        ..lsp::ServerCapabilities::default()
+    };
+    let mut typescript_servers = language_registry.register_fake_lsp(
+        "tsx",
+        FakeLspAdapter {
+            name: "TypeScriptServer",
+            capabilities: rename_capabilities.clone(),
+            ..FakeLspAdapter::default()
+        },
+    );
+    let mut eslint_servers = language_registry.register_fake_lsp(
+        "tsx",
+        FakeLspAdapter {
+            name: "ESLintServer",
+            capabilities: rename_capabilities,
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.tsx"), cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    let typescript_server = typescript_servers.next().await.unwrap();
+    let eslint_server = eslint_servers.next().await.unwrap();
+
+    let mut typescript_renames = typescript_server
+        .set_request_handler::<lsp::request::Rename, _, _>(|_, _| async move { Ok(None) });
+    let mut eslint_renames = eslint_server
+        .set_request_handler::<lsp::request::Rename, _, _>(|_, _| async move { Ok(None) });
+
+    let response = project.update(cx, |project, cx| {
+        project.perform_rename(buffer.clone(), 0, "b".to_string(), cx)
+    });
+    eslint_renames.next().await.unwrap();
+    response.await.unwrap();
+    cx.executor().run_until_parked();
+    assert!(
+        typescript_renames.try_next().is_err(),
+        "the server without priority should not be asked to rename"
+    );
+}
+
 #[gpui::test]
 async fn test_search(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -69,8 +69,8 @@ use util::{
     rel_path::{RelPath, RelPathBuf},
 };
 use workspace::{
-    DraggedSelection, OpenInTerminal, OpenOptions, OpenVisible, PreviewTabsSettings, SelectedEntry,
-    SplitDirection, Workspace,
+    DraggedSelection, ItemSettings, OpenInTerminal, OpenOptions, OpenVisible, PreviewTabsSettings,
+    SelectedEntry, SplitDirection, Workspace,
     dock::{DockPosition, Panel, PanelEvent},
     notifications::{DetachAndPromptErr, NotifyResultExt, NotifyTaskExt},
 };
@@ -248,6 +248,7 @@ struct EntryDetails {
     is_cut: bool,
     sticky: Option<StickyDetails>,
     filename_text_color: Color,
+    tint: Option<Hsla>,
     diagnostic_severity: Option<DiagnosticSeverity>,
     git_status: GitSummary,
     is_private: bool,
@@ -4126,6 +4127,51 @@ impl ProjectPanel {
         });
     }
 
+    /// Folders dropped below the entries are added to the project, while
+    /// files are copied into the last worktree.
+    fn drop_external_paths_on_background(
+        &mut self,
+        paths: &[PathBuf],
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let project = self.project.read(cx);
+        let fs = project.is_local().then(|| project.fs().clone());
+        let paths = paths.to_vec();
+        cx.spawn_in(window, async move |this, cx| {
+            let mut folders = Vec::new();
+            let mut files = Vec::new();
+            for path in paths {
+                let is_dir = match &fs {
+                    Some(fs) => fs.is_dir(&path).await,
+                    None => false,
+                };
+                if is_dir {
+                    folders.push(path);
+                } else {
+                    files.push(path);
+                }
+            }
+
+            this.update_in(cx, |this, window, cx| {
+                for folder in folders {
+                    this.project
+                        .update(cx, |project, cx| {
+                            project.find_or_create_worktree(folder, true, cx)
+                        })
+                        .detach_and_log_err(cx);
+                }
+
+                if !files.is_empty()
+                    && let Some(entry_id) = this.state.last_worktree_root_id
+                {
+                    this.drop_external_files(&files, entry_id, window, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn drop_external_files(
         &mut self,
         paths: &[PathBuf],
@@ -5067,6 +5113,8 @@ impl ProjectPanel {
 
         let bg_color = if is_marked {
             item_colors.marked
+        } else if let Some(tint) = details.tint {
+            item_colors.default.blend(tint.opacity(0.2))
         } else {
             item_colors.default
         };
@@ -5909,6 +5957,7 @@ impl ProjectPanel {
 
         let filename_text_color =
             entry_git_aware_label_color(git_status, entry.is_ignored, is_marked);
+        let tint = ItemSettings::get_global(cx).file_tint(root_name.as_unix_str(), &entry.path);
 
         let is_cut = self
             .clipboard
@@ -5930,6 +5979,7 @@ impl ProjectPanel {
             is_cut,
             sticky,
             filename_text_color,
+            tint,
             diagnostic_severity,
             git_status,
             is_private: entry.is_private,
@@ -6646,14 +6696,11 @@ impl Render for ProjectPanel {
                                     move |this, external_paths: &ExternalPaths, window, cx| {
                                         this.drag_target_entry = None;
                                         this.hover_scroll_task.take();
-                                        if let Some(entry_id) = this.state.last_worktree_root_id {
-                                            this.drop_external_files(
-                                                external_paths.paths(),
-                                                entry_id,
-                                                window,
-                                                cx,
-                                            );
-                                        }
+                                        this.drop_external_paths_on_background(
+                                            external_paths.paths(),
+                                            window,
+                                            cx,
+                                        );
                                         cx.stop_propagation();
                                     },
                                 ))
@@ -2310,6 +2310,53 @@ async fn test_auto_open_on_drop_when_disabled(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_drop_external_folder_on_background_adds_worktree(cx: &mut gpui::TestAppContext) {
+    init_test_with_editor(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/root"), json!({})).await;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let external_folder = temp_dir.path().join("library");
+    std::fs::create_dir(&external_folder).unwrap();
+    std::fs::write(external_folder.join("lib.rs"), "// library").unwrap();
+    let external_file = temp_dir.path().join("notes.md");
+    std::fs::write(&external_file, "notes").unwrap();
+    fs.insert_tree_from_real_fs(temp_dir.path(), temp_dir.path())
+        .await;
+
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    let window = cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = window
+        .read_with(cx, |mw, _| mw.workspace().clone())
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(window.into(), cx);
+    let panel = workspace.update_in(cx, ProjectPanel::new);
+    cx.run_until_parked();
+
+    panel.update_in(cx, |panel, window, cx| {
+        panel.drop_external_paths_on_background(
+            &[external_folder.clone(), external_file.clone()],
+            window,
+            cx,
+        );
+    });
+    cx.executor().run_until_parked();
+
+    project.read_with(cx, |project, cx| {
+        let worktree_names = project
+            .visible_worktrees(cx)
+            .map(|worktree| worktree.read(cx).root_name_str().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(worktree_names, ["root", "library"]);
+    });
+    assert!(
+        find_project_entry(&panel, "root/notes.md", cx).is_some(),
+        "Dropped files should be copied into the last worktree"
+    );
+}
+
 #[gpui::test]
 async fn test_create_duplicate_items(cx: &mut gpui::TestAppContext) {
     init_test_with_editor(cx);
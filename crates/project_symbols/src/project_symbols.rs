@@ -4,6 +4,7 @@ use gpui::{
     App, Context, DismissEvent, Entity, HighlightStyle, ParentElement, StyledText, Task, TextStyle,
     WeakEntity, Window, relative, rems,
 };
+use lsp::SymbolKind;
 use ordered_float::OrderedFloat;
 use picker::{Picker, PickerDelegate};
 use project::{Project, Symbol, lsp_store::SymbolLocation};
@@ -36,6 +37,45 @@ pub fn init(cx: &mut App) {
 
 pub type ProjectSymbols = Entity<Picker<ProjectSymbolsDelegate>>;
 
+/// The prefixes, like `#fn `, that narrow a query to symbols of some kinds.
+const KIND_FILTERS: &[(&str, &[SymbolKind])] = &[
+    (
+        "fn",
+        &[
+            SymbolKind::FUNCTION,
+            SymbolKind::METHOD,
+            SymbolKind::CONSTRUCTOR,
+        ],
+    ),
+    ("struct", &[SymbolKind::STRUCT]),
+    ("class", &[SymbolKind::CLASS]),
+    ("enum", &[SymbolKind::ENUM]),
+    ("trait", &[SymbolKind::INTERFACE]),
+    ("interface", &[SymbolKind::INTERFACE]),
+    ("const", &[SymbolKind::CONSTANT]),
+    ("var", &[SymbolKind::VARIABLE]),
+    ("field", &[SymbolKind::FIELD, SymbolKind::PROPERTY]),
+    (
+        "mod",
+        &[
+            SymbolKind::MODULE,
+            SymbolKind::NAMESPACE,
+            SymbolKind::PACKAGE,
+        ],
+    ),
+];
+
+/// Splits a kind filter prefix, like `#struct `, off the query.
+fn parse_kind_filter(query: &str) -> (Option<&'static [SymbolKind]>, &str) {
+    if let Some(filtered_query) = query.strip_prefix('#')
+        && let Some((prefix, filtered_query)) = filtered_query.split_once(' ')
+        && let Some((_, kinds)) = KIND_FILTERS.iter().find(|(name, _)| *name == prefix)
+    {
+        return (Some(kinds), filtered_query.trim_start());
+    }
+    (None, query)
+}
+
 pub struct ProjectSymbolsDelegate {
     workspace: WeakEntity<Workspace>,
     project: Entity<Project>,
@@ -108,7 +148,7 @@ impl ProjectSymbolsDelegate {
 impl PickerDelegate for ProjectSymbolsDelegate {
     type ListItem = ListItem;
     fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
-        "Search project symbols...".into()
+        "Search project symbols, or prefix with #fn, #struct, … to filter by kind".into()
     }
 
     fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
@@ -179,6 +219,8 @@ impl PickerDelegate for ProjectSymbolsDelegate {
         window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) -> Task<()> {
+        let (kinds, query) = parse_kind_filter(&query);
+        let query = query.to_owned();
         // Try to support rust-analyzer's path based symbols feature which
         // allows to search by rust path syntax, in that case we only want to
         // filter names by the last segment
@@ -193,7 +235,12 @@ impl PickerDelegate for ProjectSymbolsDelegate {
             .project
             .update(cx, |project, cx| project.symbols(&query, cx));
         cx.spawn_in(window, async move |this, cx| {
-            let symbols = symbols.await.log_err();
+            let symbols = symbols.await.log_err().map(|symbols| {
+                symbols
+                    .into_iter()
+                    .filter(|symbol| kinds.is_none_or(|kinds| kinds.contains(&symbol.kind)))
+                    .collect::<Vec<_>>()
+            });
             if let Some(symbols) = symbols {
                 this.update_in(cx, |this, window, cx| {
                     let delegate = &mut this.delegate;
@@ -255,6 +302,7 @@ impl PickerDelegate for ProjectSymbolsDelegate {
             } => abs_path.to_string_lossy(),
         };
         let label = symbol.label.text.clone();
+        let container_name = symbol.container_name.clone();
         let line_number = symbol.range.start.0.row + 1;
         let path = path.into_owned();
 
@@ -289,9 +337,24 @@ impl PickerDelegate for ProjectSymbolsDelegate {
                 .toggle_state(selected)
                 .child(
                     v_flex()
-                        .child(LabelLike::new().child(
-                            StyledText::new(label).with_default_highlights(&text_style, highlights),
-                        ))
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    LabelLike::new().child(
+                                        StyledText::new(label)
+                                            .with_default_highlights(&text_style, highlights),
+                                    ),
+                                )
+                                .when_some(container_name, |this, container_name| {
+                                    this.child(
+                                        Label::new(container_name)
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted)
+                                            .truncate(),
+                                    )
+                                }),
+                        )
                         .child(
                             h_flex()
                                 .child(Label::new(path).size(LabelSize::Small).color(Color::Muted))
@@ -473,6 +536,18 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_parse_kind_filter() {
+        assert_eq!(
+            parse_kind_filter("#struct  Foo"),
+            (Some(&[SymbolKind::STRUCT][..]), "Foo")
+        );
+        assert_eq!(parse_kind_filter("#fn ").1, "");
+        assert_eq!(parse_kind_filter("#fn").1, "#fn");
+        assert_eq!(parse_kind_filter("#unknown foo"), (None, "#unknown foo"));
+        assert_eq!(parse_kind_filter("foo::bar"), (None, "foo::bar"));
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let store = SettingsStore::test(cx);
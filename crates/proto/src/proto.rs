@@ -234,6 +234,24 @@ messages!(
     (RefreshCodeLens, Background),
     (GetCodeLens, Background),
     (GetCodeLensResponse, Background),
+    (ResolveCodeLens, Background),
+    (ResolveCodeLensResponse, Background),
+    (PrepareCallHierarchy, Background),
+    (PrepareCallHierarchyResponse, Background),
+    (GetIncomingCalls, Background),
+    (GetIncomingCallsResponse, Background),
+    (GetOutgoingCalls, Background),
+    (GetOutgoingCallsResponse, Background),
+    (PrepareTypeHierarchy, Background),
+    (PrepareTypeHierarchyResponse, Background),
+    (GetSupertypes, Background),
+    (GetSupertypesResponse, Background),
+    (GetSubtypes, Background),
+    (GetSubtypesResponse, Background),
+    (GetInlineValues, Background),
+    (GetInlineValuesResponse, Background),
+    (GetRenameEdits, Background),
+    (GetRenameEditsResponse, Background),
     (RespondToChannelInvite, Foreground),
     (RespondToContactRequest, Foreground),
     (RestartLanguageServers, Foreground),
@@ -451,9 +469,18 @@ request_messages!(
         ResolveCompletionDocumentationResponse
     ),
     (ResolveInlayHint, ResolveInlayHintResponse),
+    (ResolveCodeLens, ResolveCodeLensResponse),
     (GetDocumentColor, GetDocumentColorResponse),
     (GetFoldingRanges, GetFoldingRangesResponse),
     (GetColorPresentation, GetColorPresentationResponse),
+    (PrepareCallHierarchy, PrepareCallHierarchyResponse),
+    (GetIncomingCalls, GetIncomingCallsResponse),
+    (GetOutgoingCalls, GetOutgoingCallsResponse),
+    (PrepareTypeHierarchy, PrepareTypeHierarchyResponse),
+    (GetSupertypes, GetSupertypesResponse),
+    (GetSubtypes, GetSubtypesResponse),
+    (GetInlineValues, GetInlineValuesResponse),
+    (GetRenameEdits, GetRenameEditsResponse),
     (RespondToChannelInvite, Ack),
     (RespondToContactRequest, Ack),
     (SaveBuffer, BufferSaved),
@@ -611,9 +638,15 @@ entity_messages!(
     GetDocumentHighlights,
     GetDocumentSymbols,
     GetHover,
+    GetIncomingCalls,
+    GetInlineValues,
+    GetOutgoingCalls,
     GetProjectSymbols,
     GetReferences,
+    GetRenameEdits,
     GetSignatureHelp,
+    GetSubtypes,
+    GetSupertypes,
     OpenUnstagedDiff,
     OpenUncommittedDiff,
     GetTypeDefinition,
@@ -635,13 +668,16 @@ entity_messages!(
     OpenBufferForSymbol,
     OpenCommitMessageBuffer,
     PerformRename,
+    PrepareCallHierarchy,
     PrepareRename,
+    PrepareTypeHierarchy,
     RefreshInlayHints,
     RefreshSemanticTokens,
     RefreshCodeLens,
     ReloadBuffers,
     RemoveProjectCollaborator,
     RenameProjectEntry,
+    ResolveCodeLens,
     ResolveCompletionDocumentation,
     ResolveInlayHint,
     SaveBuffer,
@@ -9,7 +9,8 @@ use std::{
     sync::Arc,
 };
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, Utc};
+use db::kvp::KEY_VALUE_STORE;
 
 use fs::Fs;
 
@@ -52,6 +53,8 @@ use zed_actions::{OpenDevContainer, OpenRecent, OpenRemote};
 
 actions!(recent_projects, [ToggleActionsMenu]);
 
+const PINNED_RECENT_PROJECTS_KEY: &str = "pinned_recent_projects";
+
 #[derive(Clone, Debug)]
 pub struct RecentProjectEntry {
     pub name: SharedString,
@@ -211,6 +214,58 @@ fn get_branch_for_worktree(
     None
 }
 
+fn read_pinned_workspace_ids() -> Vec<WorkspaceId> {
+    KEY_VALUE_STORE
+        .read_kvp(PINNED_RECENT_PROJECTS_KEY)
+        .log_err()
+        .flatten()
+        .and_then(|pinned| serde_json::from_str(&pinned).log_err())
+        .unwrap_or_default()
+}
+
+fn write_pinned_workspace_ids(pinned_workspace_ids: Vec<WorkspaceId>, cx: &App) {
+    db::write_and_log(cx, move || async move {
+        let pinned = serde_json::to_string(&pinned_workspace_ids)?;
+        KEY_VALUE_STORE
+            .write_kvp(PINNED_RECENT_PROJECTS_KEY.to_string(), pinned)
+            .await
+    });
+}
+
+/// Whether every term is contained in one of the segments of the project's paths.
+fn matches_path_segments(paths: &PathList, terms: &[&str]) -> bool {
+    terms.iter().all(|term| {
+        let term = term.to_lowercase();
+        paths.paths().iter().any(|path| {
+            path.components().any(|component| {
+                component
+                    .as_os_str()
+                    .to_string_lossy()
+                    .to_lowercase()
+                    .contains(&term)
+            })
+        })
+    })
+}
+
+fn format_last_opened(timestamp: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now.signed_duration_since(timestamp);
+    if elapsed.num_minutes() < 1 {
+        "Just now".to_string()
+    } else if elapsed.num_hours() < 1 {
+        format!("{}m ago", elapsed.num_minutes())
+    } else if elapsed.num_days() < 1 {
+        format!("{}h ago", elapsed.num_hours())
+    } else if elapsed.num_days() < 7 {
+        format!("{}d ago", elapsed.num_days())
+    } else {
+        timestamp
+            .with_timezone(&Local)
+            .format("%b %-d, %Y")
+            .to_string()
+    }
+}
+
 pub fn init(cx: &mut App) {
     #[cfg(target_os = "windows")]
     cx.on_action(|open_wsl: &zed_actions::wsl_actions::OpenFolderInWsl, cx| {
@@ -633,6 +688,8 @@ pub struct RecentProjectsDelegate {
         PathList,
         DateTime<Utc>,
     )>,
+    /// Recent projects listed above all others, in the order they were pinned.
+    pinned_workspace_ids: Vec<WorkspaceId>,
     filtered_entries: Vec<ProjectPickerEntry>,
     selected_index: usize,
     render_paths: bool,
@@ -660,6 +717,7 @@ impl RecentProjectsDelegate {
             workspace,
             open_folders,
             workspaces: Vec::new(),
+            pinned_workspace_ids: read_pinned_workspace_ids(),
             filtered_entries: Vec::new(),
             selected_index: 0,
             create_new_window,
@@ -793,11 +851,24 @@ impl PickerDelegate for RecentProjectsDelegate {
             ))
         };
 
+        // Several space-separated terms each narrow the projects down to those
+        // with a path segment containing them, as paths rarely contain spaces.
+        let query_terms = query.split_whitespace().collect::<Vec<_>>();
+        let path_segment_terms = if query_terms.len() > 1 {
+            query_terms.as_slice()
+        } else {
+            &[]
+        };
+        let recent_query = query_terms.concat();
+
         let recent_candidates: Vec<_> = self
             .workspaces
             .iter()
             .enumerate()
-            .filter(|(_, (id, _, paths, _))| self.is_valid_recent_candidate(*id, paths, cx))
+            .filter(|(_, (id, _, paths, _))| {
+                self.is_valid_recent_candidate(*id, paths, cx)
+                    && matches_path_segments(paths, path_segment_terms)
+            })
             .map(|(id, (_, _, paths, _))| {
                 let combined_string = paths
                     .ordered_paths()
@@ -810,7 +881,7 @@ impl PickerDelegate for RecentProjectsDelegate {
 
         let mut recent_matches = smol::block_on(fuzzy::match_strings(
             &recent_candidates,
-            query,
+            &recent_query,
             smart_case,
             true,
             100,
@@ -850,22 +921,39 @@ impl PickerDelegate for RecentProjectsDelegate {
         };
 
         if has_recent_to_show {
-            entries.push(ProjectPickerEntry::Header("Recent Projects".into()));
-
-            if is_empty_query {
-                for (id, (workspace_id, _, paths, _)) in self.workspaces.iter().enumerate() {
-                    if self.is_valid_recent_candidate(*workspace_id, paths, cx) {
-                        entries.push(ProjectPickerEntry::RecentProject(StringMatch {
-                            candidate_id: id,
-                            score: 0.0,
-                            positions: Vec::new(),
-                            string: String::new(),
-                        }));
-                    }
-                }
+            let recent_matches = if is_empty_query {
+                recent_candidates
+                    .iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        score: 0.0,
+                        positions: Vec::new(),
+                        string: String::new(),
+                    })
+                    .collect()
             } else {
-                for m in recent_matches {
-                    entries.push(ProjectPickerEntry::RecentProject(m));
+                recent_matches
+            };
+            let (mut pinned_matches, unpinned_matches): (Vec<_>, Vec<_>) =
+                recent_matches.into_iter().partition(|recent_match| {
+                    self.is_pinned(self.workspaces[recent_match.candidate_id].0)
+                });
+            if is_empty_query {
+                pinned_matches.sort_by_key(|pinned_match| {
+                    let workspace_id = self.workspaces[pinned_match.candidate_id].0;
+                    self.pinned_workspace_ids
+                        .iter()
+                        .position(|pinned_id| *pinned_id == workspace_id)
+                });
+            }
+
+            for (title, matches) in [
+                ("Pinned Projects", pinned_matches),
+                ("Recent Projects", unpinned_matches),
+            ] {
+                if !matches.is_empty() {
+                    entries.push(ProjectPickerEntry::Header(title.into()));
+                    entries.extend(matches.into_iter().map(ProjectPickerEntry::RecentProject));
                 }
             }
         }
@@ -1110,7 +1198,10 @@ impl PickerDelegate for RecentProjectsDelegate {
             }
             ProjectPickerEntry::RecentProject(hit) => {
                 let popover_style = matches!(self.style, ProjectPickerStyle::Popover);
-                let (_, location, paths, _) = self.workspaces.get(hit.candidate_id)?;
+                let (workspace_id, location, paths, timestamp) =
+                    self.workspaces.get(hit.candidate_id)?;
+                let is_pinned = self.is_pinned(*workspace_id);
+                let last_opened = format_last_opened(*timestamp, Utc::now());
                 let is_local = matches!(location, SerializedWorkspaceLocation::Local);
                 let paths_to_add = paths.paths().to_vec();
                 let tooltip_path: SharedString = paths
@@ -1149,6 +1240,23 @@ impl PickerDelegate for RecentProjectsDelegate {
 
                 let secondary_actions = h_flex()
                     .gap_px()
+                    .child(
+                        IconButton::new("toggle_pinned", IconName::Pin)
+                            .icon_size(IconSize::Small)
+                            .toggle_state(is_pinned)
+                            .tooltip(Tooltip::text(if is_pinned {
+                                "Unpin Project"
+                            } else {
+                                "Pin Project"
+                            }))
+                            .on_click(cx.listener(move |this, _event, window, cx| {
+                                cx.stop_propagation();
+                                window.prevent_default();
+                                this.delegate.toggle_pinned(ix, cx);
+                                this.delegate.reset_selected_match_index = false;
+                                this.update_matches(this.query(cx), window, cx);
+                            })),
+                    )
                     .when(is_local, |this| {
                         this.child(
                             IconButton::new("add_to_workspace", IconName::Plus)
@@ -1233,7 +1341,23 @@ impl PickerDelegate for RecentProjectsDelegate {
                             if self.selected_index == ix {
                                 el.end_slot(secondary_actions)
                             } else {
-                                el.end_hover_slot(secondary_actions)
+                                el.end_slot(
+                                    h_flex()
+                                        .gap_1()
+                                        .when(is_pinned, |this| {
+                                            this.child(
+                                                Icon::new(IconName::Pin)
+                                                    .size(IconSize::XSmall)
+                                                    .color(Color::Muted),
+                                            )
+                                        })
+                                        .child(
+                                            Label::new(last_opened)
+                                                .size(LabelSize::Small)
+                                                .color(Color::Muted),
+                                        ),
+                                )
+                                .end_hover_slot(secondary_actions)
                             }
                         })
                         .into_any_element(),
@@ -1486,7 +1610,7 @@ impl RecentProjectsDelegate {
     }
 
     fn delete_recent_project(
-        &self,
+        &mut self,
         ix: usize,
         window: &mut Window,
         cx: &mut Context<Picker<Self>>,
@@ -1496,6 +1620,11 @@ impl RecentProjectsDelegate {
         {
             let (workspace_id, _, _, _) = &self.workspaces[selected_match.candidate_id];
             let workspace_id = *workspace_id;
+            if self.is_pinned(workspace_id) {
+                self.pinned_workspace_ids
+                    .retain(|pinned_id| *pinned_id != workspace_id);
+                write_pinned_workspace_ids(self.pinned_workspace_ids.clone(), cx);
+            }
             let fs = self
                 .workspace
                 .upgrade()
@@ -1530,6 +1659,28 @@ impl RecentProjectsDelegate {
         }
     }
 
+    fn toggle_pinned(&mut self, ix: usize, cx: &mut Context<Picker<Self>>) {
+        let Some(ProjectPickerEntry::RecentProject(selected_match)) = self.filtered_entries.get(ix)
+        else {
+            return;
+        };
+        let Some((workspace_id, _, _, _)) = self.workspaces.get(selected_match.candidate_id) else {
+            return;
+        };
+        let workspace_id = *workspace_id;
+        if self.is_pinned(workspace_id) {
+            self.pinned_workspace_ids
+                .retain(|pinned_id| *pinned_id != workspace_id);
+        } else {
+            self.pinned_workspace_ids.push(workspace_id);
+        }
+        write_pinned_workspace_ids(self.pinned_workspace_ids.clone(), cx);
+    }
+
+    fn is_pinned(&self, workspace_id: WorkspaceId) -> bool {
+        self.pinned_workspace_ids.contains(&workspace_id)
+    }
+
     fn is_current_workspace(
         &self,
         workspace_id: WorkspaceId,
@@ -1729,6 +1880,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_matches_path_segments() {
+        let paths = PathList::new(&[path!("/code/zed"), path!("/notes/Work")]);
+        assert!(matches_path_segments(&paths, &[]));
+        assert!(matches_path_segments(&paths, &["code", "ze"]));
+        assert!(matches_path_segments(&paths, &["zed", "work"]));
+        assert!(!matches_path_segments(&paths, &["code", "crates"]));
+    }
+
+    #[test]
+    fn test_format_last_opened() {
+        let now = Utc::now();
+        assert_eq!(format_last_opened(now, now), "Just now");
+        assert_eq!(
+            format_last_opened(now - chrono::Duration::minutes(5), now),
+            "5m ago"
+        );
+        assert_eq!(
+            format_last_opened(now - chrono::Duration::hours(3), now),
+            "3h ago"
+        );
+        assert_eq!(
+            format_last_opened(now - chrono::Duration::days(2), now),
+            "2d ago"
+        );
+    }
+
     fn open_recent_projects(
         multi_workspace: &WindowHandle<MultiWorkspace>,
         cx: &mut TestAppContext,
@@ -1253,7 +1253,7 @@ impl ProjectSearchView {
         let project = self.entity.read(cx).project.clone();
 
         let can_autosave = self.results_editor.can_autosave(cx);
-        let autosave_setting = self.results_editor.workspace_settings(cx).autosave;
+        let autosave_setting = self.results_editor.autosave_setting(cx);
 
         let will_autosave = can_autosave && autosave_setting.should_save_on_close();
 
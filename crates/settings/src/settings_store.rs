@@ -1121,7 +1121,13 @@ impl SettingsStore {
         replace_subschema::<ThemeName>(&mut generator, || {
             json_schema!({
                 "type": "string",
-                "enum": params.theme_names,
+                "anyOf": [
+                    { "enum": params.theme_names },
+                    {
+                        "pattern": "\\.json$",
+                        "description": "The absolute path to a theme file, which is reloaded whenever it changes."
+                    }
+                ]
             })
         });
 
@@ -177,6 +177,7 @@ impl VsCodeSettings {
             audio: None,
             auto_update: None,
             base_keymap: Some(BaseKeymapContent::VSCode),
+            bookmarks_panel: None,
             calls: None,
             collaboration_panel: None,
             debugger: None,
@@ -191,9 +192,11 @@ impl VsCodeSettings {
                 ..GlobalLspSettingsContent::default()
             }),
             helix_mode: None,
+            hierarchy_panel: None,
             image_viewer: None,
             journal: None,
             language_models: None,
+            language_servers_panel: None,
             line_indicator_format: None,
             log: None,
             message_editor: None,
@@ -208,6 +211,7 @@ impl VsCodeSettings {
             repl: None,
             server_url: None,
             session: None,
+            spelling: self.spelling_settings_content(),
             status_bar: self.status_bar_settings_content(),
             tab_bar: self.tab_bar_settings_content(),
             tabs: self.item_settings_content(),
@@ -215,6 +219,7 @@ impl VsCodeSettings {
             terminal: self.terminal_settings_content(),
             theme: Box::new(self.theme_settings_content()),
             title_bar: None,
+            tray: None,
             vim: None,
             vim_mode: None,
             workspace: self.workspace_settings_content(),
@@ -299,6 +304,7 @@ impl VsCodeSettings {
                 },
             ),
             selection_highlight: self.read_bool("editor.selectionHighlight"),
+            selection_highlight_min_length: None,
             show_signature_help_after_edits: self.read_bool("editor.parameterHints.enabled"),
             snippet_sort_order: None,
             toolbar: None,
@@ -313,6 +319,7 @@ impl VsCodeSettings {
     fn sticky_scroll_content(&self) -> Option<StickyScrollContent> {
         skip_default(StickyScrollContent {
             enabled: self.read_bool("editor.stickyScroll.enabled"),
+            max_lines: self.read_u32("editor.stickyScroll.maxLineCount"),
         })
     }
 
@@ -326,6 +333,7 @@ impl VsCodeSettings {
             min_line_number_digits: None,
             runnables: None,
             breakpoints: None,
+            bookmarks: None,
             folds: self.read_enum("editor.showFoldingControls", |s| match s {
                 "always" | "mouseover" => Some(true),
                 "never" => Some(false),
@@ -520,7 +528,38 @@ impl VsCodeSettings {
             always_treat_brackets_as_autoclosed: None,
             auto_indent: None,
             auto_indent_on_paste: self.read_bool("editor.formatOnPaste"),
+            autosave: self.read_enum("files.autoSave", |s| match s {
+                "off" => Some(AutosaveSetting::Off),
+                "afterDelay" => Some(AutosaveSetting::AfterDelay {
+                    milliseconds: self
+                        .read_value("files.autoSaveDelay")
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(1000)
+                        .into(),
+                }),
+                "onFocusChange" => Some(AutosaveSetting::OnFocusChange),
+                "onWindowChange" => Some(AutosaveSetting::OnWindowChange),
+                _ => None,
+            }),
+            buffer_font_family: None,
+            buffer_font_features: None,
+            buffer_font_size: None,
+            buffer_font_weight: None,
             code_actions_on_format: None,
+            code_actions_on_save: self
+                .read_value("editor.codeActionsOnSave")
+                .and_then(|v| v.as_object())
+                .map(|actions| {
+                    actions
+                        .iter()
+                        .filter(|(_, when)| {
+                            when.as_bool() == Some(true)
+                                || matches!(when.as_str(), Some("explicit" | "always"))
+                        })
+                        .map(|(kind, _)| kind.clone())
+                        .collect()
+                }),
+            code_actions_on_save_timeout_ms: None,
             completions: skip_default(CompletionSettingsContent {
                 words: self.read_bool("editor.suggest.showWords").map(|b| {
                     if b {
@@ -554,6 +593,7 @@ impl VsCodeSettings {
             inlay_hints: None,
             jsx_tag_auto_close: None,
             language_servers: None,
+            language_server_priorities: None,
             semantic_tokens: self
                 .read_bool("editor.semanticHighlighting.enabled")
                 .map(|enabled| {
@@ -565,6 +605,9 @@ impl VsCodeSettings {
                 }),
             document_folding_ranges: None,
             document_symbols: None,
+            code_lens: self
+                .read_bool("editor.codeLens")
+                .map(|enabled| if enabled { CodeLens::On } else { CodeLens::Off }),
             linked_edits: self.read_bool("editor.linkedEditing"),
             preferred_line_length: self.read_u32("editor.wordWrapColumn"),
             prettier: None,
@@ -583,6 +626,7 @@ impl VsCodeSettings {
                 })
             }),
             show_wrap_guides: None,
+            show_minimap: None,
             soft_wrap: self.read_enum("editor.wordWrap", |s| match s {
                 "on" => Some(SoftWrap::EditorWidth),
                 "wordWrapColumn" => Some(SoftWrap::PreferLine),
@@ -590,6 +634,8 @@ impl VsCodeSettings {
                 "off" => Some(SoftWrap::None),
                 _ => None,
             }),
+            spell_check: None,
+            spell_check_captures: None,
             tab_size: self
                 .read_u32("editor.tabSize")
                 .and_then(|n| NonZeroU32::new(n)),
@@ -734,6 +780,7 @@ impl VsCodeSettings {
                         ShowCloseButton::Hidden
                     }
                 }),
+            color_rules: None,
         })
     }
 
@@ -763,6 +810,27 @@ impl VsCodeSettings {
                 .read_str("workbench.editor.editorActionsLocation")
                 .and_then(|str| if str == "hidden" { Some(false) } else { None }),
             show_pinned_tabs_in_separate_row: None,
+            show_pinned_tabs_as_icons: self.read_enum("workbench.editor.pinnedTabSizing", |s| {
+                match s {
+                    "compact" => Some(true),
+                    "normal" | "shrink" => Some(false),
+                    _ => None,
+                }
+            }),
+            placement: None,
+        })
+    }
+
+    fn spelling_settings_content(&self) -> Option<SpellingSettingsContent> {
+        let words = self.read_value("cSpell.words")?.as_array()?;
+        Some(SpellingSettingsContent {
+            dictionaries: None,
+            words: Some(
+                words
+                    .iter()
+                    .filter_map(|word| word.as_str().map(str::to_owned))
+                    .collect(),
+            ),
         })
     }
 
@@ -834,6 +902,9 @@ impl VsCodeSettings {
         let (font_family, font_fallbacks) = self.read_fonts("terminal.integrated.fontFamily");
         skip_default(TerminalSettingsContent {
             alternate_scroll: None,
+            bell: self
+                .read_bool("terminal.integrated.enableVisualBell")
+                .and_then(|enabled| enabled.then_some(TerminalBell::Visual)),
             blinking: self
                 .read_bool("terminal.integrated.cursorBlinking")
                 .map(|b| {
@@ -861,16 +932,22 @@ impl VsCodeSettings {
                 .read_f32("terminal.integrated.fontSize")
                 .map(FontSize::from),
             font_weight: None,
+            inline_images: None,
             keep_selection_on_copy: None,
             line_height: self
                 .read_f32("terminal.integrated.lineHeight")
                 .map(|lh| TerminalLineHeight::Custom(lh)),
+            max_inline_image_lines: None,
             max_scroll_history_lines: self.read_usize("terminal.integrated.scrollback"),
+            persist_scrollback: self.read_bool("terminal.integrated.enablePersistentSessions"),
             minimum_contrast: None,
             option_as_meta: self.read_bool("terminal.integrated.macOptionIsMeta"),
             project: self.project_terminal_settings_content(),
             scrollbar: None,
             scroll_multiplier: None,
+            notify_on_command_finish: None,
+            send_to_terminal: None,
+            shell_integration: self.read_bool("terminal.integrated.shellIntegration.enabled"),
             toolbar: None,
         })
     }
@@ -905,6 +982,8 @@ impl VsCodeSettings {
             detect_venv: None,
             path_hyperlink_regexes: None,
             path_hyperlink_timeout_ms: None,
+            profiles: None,
+            default_profile: None,
         }
     }
 
@@ -928,6 +1007,7 @@ impl VsCodeSettings {
             icon_theme: None,
             ui_density: None,
             unnecessary_code_fade: None,
+            window_background_appearance: None,
             experimental_theme_overrides: None,
             theme_overrides: Default::default(),
         }
@@ -937,19 +1017,7 @@ impl VsCodeSettings {
         WorkspaceSettingsContent {
             active_pane_modifiers: self.active_pane_modifiers(),
             text_rendering_mode: None,
-            autosave: self.read_enum("files.autoSave", |s| match s {
-                "off" => Some(AutosaveSetting::Off),
-                "afterDelay" => Some(AutosaveSetting::AfterDelay {
-                    milliseconds: self
-                        .read_value("files.autoSaveDelay")
-                        .and_then(|v| v.as_u64())
-                        .unwrap_or(1000)
-                        .into(),
-                }),
-                "onFocusChange" => Some(AutosaveSetting::OnFocusChange),
-                "onWindowChange" => Some(AutosaveSetting::OnWindowChange),
-                _ => None,
-            }),
+            presentation_mode: None,
             bottom_dock_layout: None,
             centered_layout: None,
             close_on_file_delete: None,
@@ -961,6 +1029,7 @@ impl VsCodeSettings {
                 _ => None,
             }),
             drop_target_size: None,
+            global_hotkeys: Default::default(),
             // workbench.editor.limit contains "enabled", "value", and "perEditorGroup"
             // our semantics match if those are set to true, some N, and true respectively.
             // we'll ignore "perEditorGroup" for now since we only support a global max
@@ -988,6 +1057,12 @@ impl VsCodeSettings {
                     CloseWindowWhenNoItems::KeepWindowOpen
                 }
             }),
+            zen_mode: self.read_bool("zenMode.fullScreen").map(|fullscreen| {
+                ZenModeSettingsContent {
+                    max_width: None,
+                    fullscreen: Some(fullscreen),
+                }
+            }),
             zoomed_padding: None,
         }
     }
@@ -1044,6 +1119,7 @@ impl VsCodeSettings {
                         .collect::<Vec<_>>()
                 })
                 .filter(|r| !r.is_empty()),
+            large_file_mode: None,
         }
     }
 }
@@ -17,6 +17,17 @@ pub struct EditorSettingsContent {
     ///
     /// Default: true
     pub cursor_blink: Option<bool>,
+    /// How long the cursor stays visible, and then hidden, in each blink, in
+    /// the editor and the terminal. Intervals shorter than 100 milliseconds
+    /// are treated as 100.
+    ///
+    /// Default: 500
+    pub cursor_blink_interval: Option<DelayMs>,
+    /// Whether the cursor glides to its new position when it moves, instead
+    /// of jumping there, in the editor and the terminal.
+    ///
+    /// Default: false
+    pub smooth_caret: Option<bool>,
     /// Cursor shape for the default editor.
     /// Can be "bar", "block", "underline", or "hollow".
     ///
@@ -38,6 +49,11 @@ pub struct EditorSettingsContent {
     ///
     /// Default: true
     pub selection_highlight: Option<bool>,
+    /// The minimum number of characters the selected text, or the word under
+    /// the cursor, must have for its occurrences to be highlighted.
+    ///
+    /// Default: 1
+    pub selection_highlight_min_length: Option<usize>,
     /// Whether the text selection should have rounded corners.
     ///
     /// Default: true
@@ -355,6 +371,29 @@ pub struct StickyScrollContent {
     ///
     /// Default: false
     pub enabled: Option<bool>,
+    /// The maximum number of enclosing scopes to stick to the top of the
+    /// editor. The outermost scopes are shown first.
+    ///
+    /// Default: 5
+    pub max_lines: Option<u32>,
+}
+
+/// Spell checking related settings
+#[with_fallible_options]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct SpellingSettingsContent {
+    /// The names of the Hunspell dictionaries to check spelling with, such as
+    /// `en_US`. Each is made of a `.dic` file and an optional `.aff` file,
+    /// looked up in the `dictionaries` directory of the config directory and
+    /// in the system's Hunspell directories.
+    ///
+    /// Default: ["en_US"]
+    pub dictionaries: Option<Vec<String>>,
+
+    /// Words to accept in addition to those of the dictionaries.
+    ///
+    /// Default: []
+    pub words: Option<Vec<String>>,
 }
 
 /// Minimap related settings
@@ -427,6 +466,10 @@ pub struct GutterContent {
     ///
     /// Default: true
     pub breakpoints: Option<bool>,
+    /// Whether to show bookmarks in the gutter.
+    ///
+    /// Default: true
+    pub bookmarks: Option<bool>,
     /// Whether to show fold buttons in the gutter.
     ///
     /// Default: true
@@ -6,7 +6,10 @@ use serde::{Deserialize, Serialize, de::Error as _};
 use settings_macros::{MergeFrom, with_fallible_options};
 use std::sync::Arc;
 
-use crate::{DocumentFoldingRanges, DocumentSymbols, ExtendingVec, SemanticTokens, merge_from};
+use crate::{
+    AutosaveSetting, CodeLens, DocumentFoldingRanges, DocumentSymbols, ExtendingVec,
+    FontFamilyName, FontFeaturesContent, FontSize, FontWeightContent, SemanticTokens, merge_from,
+};
 
 /// The state of the modifier keys at some point in time
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, MergeFrom)]
@@ -379,12 +382,35 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: []
     pub wrap_guides: Option<Vec<usize>>,
+    /// Whether to show the minimap in editors for this language, when the
+    /// minimap is enabled.
+    ///
+    /// Default: true
+    pub show_minimap: Option<bool>,
+    /// The font family to use for buffers of this language, instead of
+    /// `buffer_font_family`.
+    pub buffer_font_family: Option<FontFamilyName>,
+    /// The font size to use for buffers of this language, instead of
+    /// `buffer_font_size`.
+    pub buffer_font_size: Option<FontSize>,
+    /// The font weight to use for buffers of this language, instead of
+    /// `buffer_font_weight`.
+    pub buffer_font_weight: Option<FontWeightContent>,
+    /// The OpenType features to use for buffers of this language, instead of
+    /// `buffer_font_features`. For example, `{ "calt": false }` disables
+    /// ligatures.
+    pub buffer_font_features: Option<FontFeaturesContent>,
     /// Indent guide related settings.
     pub indent_guides: Option<IndentGuideSettingsContent>,
     /// Whether or not to perform a buffer format before saving.
     ///
     /// Default: on
     pub format_on_save: Option<FormatOnSave>,
+    /// When to automatically save edited buffers. Untitled buffers are never
+    /// saved automatically.
+    ///
+    /// Default: off
+    pub autosave: Option<AutosaveSetting>,
     /// Whether or not to remove any trailing whitespace from lines of a buffer
     /// before saving it.
     ///
@@ -420,6 +446,14 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: ["..."]
     pub language_servers: Option<Vec<String>>,
+    /// Overrides the order of `language_servers` for some LSP requests, by
+    /// request method. When several servers can answer requests that go to a
+    /// single server, like `textDocument/rename`, the servers listed are asked
+    /// first, in order. Requests answered by every server, like completions and
+    /// code actions, are unaffected.
+    ///
+    /// Default: {}
+    pub language_server_priorities: Option<HashMap<String, Vec<String>>>,
     /// Controls how semantic tokens from language servers are used for syntax highlighting.
     ///
     /// Options:
@@ -446,6 +480,15 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: "off"
     pub document_symbols: Option<DocumentSymbols>,
+    /// Controls whether code lenses from language servers, and from other
+    /// providers like test runners, are shown above the symbols they belong to.
+    ///
+    /// Options:
+    /// - "off": Do not show code lenses (default).
+    /// - "on": Show code lenses above symbols.
+    ///
+    /// Default: "off"
+    pub code_lens: Option<CodeLens>,
     /// Controls where the `editor::Rewrap` action is allowed for this language.
     ///
     /// Note: This setting has no effect in Vim mode, as rewrap is already
@@ -513,6 +556,28 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: {} (or {"source.organizeImports": true} for Go).
     pub code_actions_on_format: Option<HashMap<String, bool>>,
+    /// Kinds of code actions to run, in order, whenever a buffer is saved.
+    /// Unlike `code_actions_on_format`, these run even if formatting on save
+    /// is off, and before any other formatting.
+    ///
+    /// Default: []
+    pub code_actions_on_save: Option<Vec<String>>,
+    /// How long to wait for a language server to provide and resolve each
+    /// code action run on save, in milliseconds, before skipping it.
+    ///
+    /// Default: 10000
+    pub code_actions_on_save_timeout_ms: Option<u64>,
+    /// Whether to underline misspelled words, using the dictionaries of the
+    /// `spelling` settings.
+    ///
+    /// Default: false
+    pub spell_check: Option<bool>,
+    /// The syntax highlighting captures whose text is spell checked, such as
+    /// `comment` or `string`. A capture also covers the captures nested under
+    /// it, like `comment.doc`. An empty list checks the whole buffer.
+    ///
+    /// Default: ["comment", "string"]
+    pub spell_check_captures: Option<Vec<String>>,
     /// Whether to perform linked edits of associated ranges, if the language server supports it.
     /// For example, when editing opening <html> tag, the contents of the closing </html> tag will be edited as well.
     ///
@@ -655,6 +720,14 @@ pub struct InlayHintSettingsContent {
     ///
     /// Default: true
     pub show_other_hints: Option<bool>,
+    /// Whether chaining hints should be shown.
+    ///
+    /// Chaining hints are the type hints shown at the end of each line of
+    /// a method chain that spans several lines. When unset, they are shown
+    /// whenever type hints are.
+    ///
+    /// Default: null
+    pub show_chaining_hints: Option<bool>,
     /// Whether to show a background for inlay hints.
     ///
     /// If set to `true`, the background will use the `hint.background` color
@@ -689,6 +762,8 @@ pub enum InlayHintKind {
     Type,
     /// An inlay hint for a parameter.
     Parameter,
+    /// An inlay hint for the type of an expression ending a line of a method chain.
+    Chaining,
 }
 
 impl InlayHintKind {
@@ -700,6 +775,7 @@ impl InlayHintKind {
         match name {
             "type" => Some(InlayHintKind::Type),
             "parameter" => Some(InlayHintKind::Parameter),
+            "chaining" => Some(InlayHintKind::Chaining),
             _ => None,
         }
     }
@@ -709,6 +785,7 @@ impl InlayHintKind {
         match self {
             InlayHintKind::Type => "type",
             InlayHintKind::Parameter => "parameter",
+            InlayHintKind::Chaining => "chaining",
         }
     }
 }
@@ -741,6 +818,12 @@ pub struct CompletionSettingsContent {
     ///
     /// Default: "replace_suffix"
     pub lsp_insert_mode: Option<LspInsertMode>,
+    /// Whether accepting an LSP completion may also apply the additional
+    /// edits the language server attaches to it, which usually add a missing
+    /// import.
+    ///
+    /// Default: true
+    pub auto_import: Option<bool>,
 }
 
 #[derive(
@@ -138,6 +138,33 @@ pub struct WorktreeSettingsContent {
     /// external dependencies that should not be modified directly.
     /// Default: []
     pub read_only_files: Option<Vec<String>>,
+
+    /// How to open files too large to edit comfortably.
+    pub large_file_mode: Option<LargeFileModeSettingsContent>,
+}
+
+/// Files over `threshold` bytes are opened in large-file mode, which turns off
+/// the features below unless they're enabled here.
+#[with_fallible_options]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, MergeFrom)]
+pub struct LargeFileModeSettingsContent {
+    /// The size in bytes from which files are opened in large-file mode.
+    ///
+    /// Default: 20971520 (20 MiB)
+    pub threshold: Option<u64>,
+    /// Whether to parse large files with tree-sitter for syntax highlighting,
+    /// outlines and bracket matching.
+    ///
+    /// Default: false
+    pub syntax_highlighting: Option<bool>,
+    /// Whether to start language servers for large files.
+    ///
+    /// Default: false
+    pub language_servers: Option<bool>,
+    /// Whether to soft wrap large files.
+    ///
+    /// Default: false
+    pub soft_wrap: Option<bool>,
 }
 
 #[with_fallible_options]
@@ -163,6 +190,18 @@ pub struct LspSettings {
     #[serde(default = "default_true")]
     pub enable_lsp_tasks: bool,
     pub fetch: Option<FetchSettings>,
+    /// The languages to start this language server for, in addition to those
+    /// an extension or Zed registers it for.
+    ///
+    /// Together with `binary.path`, this defines a language server that
+    /// nothing else provides, such as a build of the server kept in the project.
+    ///
+    /// Default: null
+    pub languages: Option<Vec<String>>,
+    /// Which folders the language server is told it works on.
+    ///
+    /// Default: all
+    pub workspace_folders: Option<LspWorkspaceFolders>,
 }
 
 impl Default for LspSettings {
@@ -173,10 +212,26 @@ impl Default for LspSettings {
             settings: None,
             enable_lsp_tasks: true,
             fetch: None,
+            languages: None,
+            workspace_folders: None,
         }
     }
 }
 
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, MergeFrom,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LspWorkspaceFolders {
+    /// Every folder of the worktree the language server is used for, adding
+    /// folders as its files are opened.
+    #[default]
+    All,
+    /// Only the folder the language server was started for, for servers
+    /// that don't support multiple workspace folders.
+    Root,
+}
+
 #[with_fallible_options]
 #[derive(
     Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema, MergeFrom, Hash,
@@ -125,6 +125,9 @@ pub struct SettingsContent {
     /// Default: VSCode
     pub base_keymap: Option<BaseKeymapContent>,
 
+    /// Configuration for the bookmarks panel.
+    pub bookmarks_panel: Option<PanelSettingsContent>,
+
     /// Configuration for the collab panel visual settings.
     pub collaboration_panel: Option<PanelSettingsContent>,
 
@@ -139,6 +142,12 @@ pub struct SettingsContent {
     /// Common language server settings.
     pub global_lsp_settings: Option<GlobalLspSettingsContent>,
 
+    /// Configuration for the hierarchy panel.
+    pub hierarchy_panel: Option<PanelSettingsContent>,
+
+    /// Configuration for the language servers panel.
+    pub language_servers_panel: Option<PanelSettingsContent>,
+
     /// The settings for the image viewer.
     pub image_viewer: Option<ImageViewerSettingsContent>,
 
@@ -181,6 +190,9 @@ pub struct SettingsContent {
 
     /// Configuration for session-related features
     pub session: Option<SessionSettingsContent>,
+
+    /// Configuration of the dictionaries used to spell check.
+    pub spelling: Option<SpellingSettingsContent>,
     /// Control what info is collected by Zed.
     pub telemetry: Option<TelemetrySettingsContent>,
 
@@ -189,6 +201,9 @@ pub struct SettingsContent {
 
     pub title_bar: Option<TitleBarSettingsContent>,
 
+    /// Configuration of the system tray icon (Linux only).
+    pub tray: Option<TraySettingsContent>,
+
     /// Whether or not to enable Vim mode.
     ///
     /// Default: false
@@ -339,6 +354,11 @@ pub struct TitleBarSettingsContent {
     ///
     /// Default: true
     pub show_project_items: Option<bool>,
+    /// How the project and branch names are shortened when they don't fit
+    /// in the title bar.
+    ///
+    /// Default: end
+    pub name_truncation: Option<TitleBarNameTruncation>,
     /// Whether to show the sign in button in the title bar.
     ///
     /// Default: true
@@ -351,6 +371,175 @@ pub struct TitleBarSettingsContent {
     ///
     /// Default: false
     pub show_menus: Option<bool>,
+    /// What to do when the title bar is double-clicked.
+    ///
+    /// Default: platform_default
+    pub double_click_action: Option<TitleBarDoubleClickAction>,
+    /// What to do when the title bar is middle-clicked.
+    ///
+    /// Default: lower
+    pub middle_click_action: Option<TitleBarMiddleClickAction>,
+    /// How the window controls behave while the window is fullscreen or in
+    /// zen mode.
+    ///
+    /// Default: reveal
+    pub fullscreen_controls: Option<FullscreenWindowControls>,
+    /// The height of the title bar in pixels. When unset, the platform's
+    /// default height is used.
+    ///
+    /// Default: null
+    pub height: Option<f32>,
+    /// The size of the window control icons in pixels. Their hit targets
+    /// grow along with them.
+    ///
+    /// Default: 16
+    pub control_size: Option<f32>,
+    /// How the window control glyphs are drawn.
+    ///
+    /// Default: icons
+    pub control_style: Option<TitleBarControlStyle>,
+    /// A template for the window title. Supports the `{project}`, `{file}`,
+    /// `{path}`, `{dirty}`, `{host}`, `{branch}` and `{collab}` variables, and
+    /// `{separator}`, which only renders between two non-empty parts.
+    /// When unset, the title is composed from the project and file names.
+    ///
+    /// Default: null
+    pub window_title: Option<String>,
+}
+
+/// The action performed when double-clicking the title bar.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TitleBarDoubleClickAction {
+    /// Follow the platform convention: the system preference on macOS, maximize elsewhere.
+    #[default]
+    PlatformDefault,
+    /// Toggle between the maximized and restored window size.
+    Maximize,
+    /// Minimize the window.
+    Minimize,
+    /// Do nothing.
+    None,
+}
+
+/// How the client-side window controls are shown while the window is fullscreen.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum FullscreenWindowControls {
+    /// Collapse the controls into a strip at the top edge that expands on hover.
+    #[default]
+    Reveal,
+    /// Keep showing the controls in the title bar.
+    Visible,
+    /// Don't show the controls.
+    Hidden,
+}
+
+/// The action performed when middle-clicking the title bar.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TitleBarMiddleClickAction {
+    /// Move the window behind all other windows (X11 only).
+    #[default]
+    Lower,
+    /// Minimize the window.
+    Minimize,
+    /// Toggle between the maximized and restored window size.
+    Maximize,
+    /// Do nothing.
+    None,
+}
+
+/// How long project and branch names are shortened in the title bar.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TitleBarNameTruncation {
+    /// Remove characters from the end of the name.
+    #[default]
+    End,
+    /// Remove characters from the start of the name.
+    Start,
+    /// Remove characters from the middle of the name.
+    Middle,
+    /// Abbreviate the leading `/`-separated components of the name to their
+    /// first character before removing characters from its end.
+    DirectoriesFirst,
+}
+
+/// How the client-side window control glyphs are drawn.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TitleBarControlStyle {
+    /// Use the icon theme's window control icons.
+    #[default]
+    Icons,
+    /// Use simple glyphs tinted with the theme's icon colors.
+    Symbolic,
 }
 
 /// Configuration of audio in Zed.
@@ -439,6 +628,17 @@ impl From<Option<String>> for AudioOutputDeviceName {
     }
 }
 
+/// Configuration of the system tray icon.
+#[with_fallible_options]
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, PartialEq, Eq)]
+pub struct TraySettingsContent {
+    /// Whether to show an icon in the system tray with quick actions for
+    /// opening windows and quitting Zed.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+}
+
 /// Control what info is collected by Zed.
 #[with_fallible_options]
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, MergeFrom)]
@@ -33,6 +33,8 @@ pub struct ProjectTerminalSettingsContent {
     /// Default: [
     ///   // Python-style diagnostics
     ///   "File \"(?<path>[^\"]+)\", line (?<line>[0-9]+)",
+    ///   // Shell script errors
+    ///   "^(?<link>(?<path>[^ :]+): line (?<line>[0-9]+)):",
     ///   // Common path syntax with optional line, column, description, trailing punctuation, or
     ///   // surrounding symbols or quotes
     ///   [
@@ -63,6 +65,40 @@ pub struct ProjectTerminalSettingsContent {
     ///
     /// Default: 1
     pub path_hyperlink_timeout_ms: Option<u64>,
+    /// Named terminal configurations that can be picked when opening a new
+    /// terminal. Settings a profile leaves out are taken from the terminal
+    /// settings.
+    ///
+    /// Default: {}
+    pub profiles: Option<HashMap<String, TerminalProfileContent>>,
+    /// The name of the profile that new terminals use unless another one is
+    /// picked. When unset, new terminals use the terminal settings as is.
+    ///
+    /// Default: null
+    pub default_profile: Option<String>,
+}
+
+/// A named terminal configuration.
+#[with_fallible_options]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
+pub struct TerminalProfileContent {
+    /// What shell to run, optionally with arguments.
+    ///
+    /// Default: the `shell` terminal setting
+    pub shell: Option<Shell>,
+    /// Key-value pairs added to the terminal's environment, on top of the
+    /// `env` terminal setting.
+    ///
+    /// Default: {}
+    pub env: Option<HashMap<String, String>>,
+    /// What working directory to launch the terminal in.
+    ///
+    /// Default: the `working_directory` terminal setting
+    pub working_directory: Option<WorkingDirectory>,
+    /// The name of the icon shown in the terminal's tab, such as "server".
+    ///
+    /// Default: "terminal"
+    pub icon: Option<String>,
 }
 
 #[with_fallible_options]
@@ -104,6 +140,10 @@ pub struct TerminalSettingsContent {
     ///
     /// Default: terminal_controlled
     pub blinking: Option<TerminalBlink>,
+    /// What to do when a program in the terminal rings the bell.
+    ///
+    /// Default: visual
+    pub bell: Option<TerminalBell>,
     /// Sets whether Alternate Scroll mode (code: ?1007) is active by default.
     /// Alternate Scroll mode converts mouse scroll events into up / down key
     /// presses when in the alternate screen (e.g. when running applications
@@ -124,6 +164,21 @@ pub struct TerminalSettingsContent {
     ///
     /// Default: true
     pub keep_selection_on_copy: Option<bool>,
+    /// Whether to load hooks into bash, zsh and fish that mark where each
+    /// prompt starts and how each command exited, so that the terminal can
+    /// jump between prompts, mark failed commands in the scrollbar and rerun
+    /// the last command.
+    ///
+    /// Default: true
+    pub shell_integration: Option<bool>,
+    /// How text from `editor: send selection to terminal` and
+    /// `editor: send buffer to terminal` is written to the terminal.
+    pub send_to_terminal: Option<SendToTerminalSettingsContent>,
+    /// Whether to show a notification when a command finishes in a terminal
+    /// that isn't focused. Requires the shell integration.
+    ///
+    /// Default: false
+    pub notify_on_command_finish: Option<bool>,
     /// Whether to show the terminal button in the status bar.
     ///
     /// Default: true
@@ -147,6 +202,22 @@ pub struct TerminalSettingsContent {
     ///
     /// Default: 10_000
     pub max_scroll_history_lines: Option<usize>,
+    /// Whether to save the scrollback of terminals with the workspace, so that
+    /// it's shown again when the workspace is reopened. Colors and styles
+    /// aren't kept.
+    ///
+    /// Default: false
+    pub persist_scrollback: Option<bool>,
+    /// Whether to show images that programs print with the Sixel or iTerm2
+    /// inline image protocols. New terminals pick up this change.
+    ///
+    /// Default: true
+    pub inline_images: Option<bool>,
+    /// The maximum height of an inline image, in lines. Bigger images are
+    /// scaled down to fit.
+    ///
+    /// Default: 40
+    pub max_inline_image_lines: Option<u32>,
     /// The multiplier for scrolling with the mouse wheel.
     ///
     /// Default: 1.0
@@ -236,6 +307,24 @@ pub enum WorkingDirectory {
     Always { directory: String },
 }
 
+#[with_fallible_options]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq, Default)]
+pub struct SendToTerminalSettingsContent {
+    /// Whether to wrap the text in bracketed paste sequences when the program
+    /// running in the terminal supports them, so that it's treated as pasted
+    /// rather than typed, e.g. without being indented again.
+    ///
+    /// Default: true
+    pub bracketed_paste: Option<bool>,
+    /// How many line breaks to send after the text, by the name of the
+    /// language it was sent from. Languages that aren't listed get one, which
+    /// runs the text. Some REPLs, like Python's, need an empty line to finish
+    /// an indented block.
+    ///
+    /// Default: {"Python": 2}
+    pub trailing_newlines: Option<HashMap<String, usize>>,
+}
+
 #[with_fallible_options]
 #[derive(
     Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq, Default,
@@ -329,6 +418,32 @@ pub enum CursorShapeContent {
     Hollow,
 }
 
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminalBell {
+    /// Briefly flash the terminal.
+    Visual,
+    /// Play a sound.
+    Sound,
+    /// Ask the window manager to draw attention to Zed's window, when it isn't
+    /// active.
+    Attention,
+    /// Ignore the bell.
+    None,
+}
+
 #[derive(
     Copy,
     Clone,
@@ -154,16 +154,21 @@ pub struct ThemeSettingsContent {
     /// The name of the icon theme to use.
     pub icon_theme: Option<IconThemeSelection>,
 
-    /// UNSTABLE: Expect many elements to be broken.
+    /// The density of the UI, which scales paddings and the heights of rows,
+    /// tabs and the title bar.
     ///
-    // Controls the density of the UI.
-    #[serde(rename = "unstable.ui_density")]
+    /// Default: default
+    #[serde(alias = "unstable.ui_density")]
     pub ui_density: Option<UiDensity>,
 
     /// How much to fade out unused code.
     #[schemars(range(min = 0.0, max = 0.9))]
     pub unnecessary_code_fade: Option<CodeFade>,
 
+    /// Whether the window is opaque, or shows the desktop behind it either as
+    /// is or blurred. Overrides the theme's `background.appearance` when set.
+    pub window_background_appearance: Option<WindowBackgroundContent>,
+
     /// EXPERIMENTAL: Overrides for the current theme.
     ///
     /// These values will override the ones on the current theme specified in `theme`.
@@ -172,7 +177,9 @@ pub struct ThemeSettingsContent {
 
     /// Overrides per theme
     ///
-    /// These values will override the ones on the specified theme
+    /// These values will override the ones on the specified theme. The keys
+    /// `light` and `dark` apply to every theme of that appearance, before the
+    /// overrides of the theme's own name.
     #[serde(default)]
     pub theme_overrides: HashMap<String, ThemeStyleContent>,
 }
@@ -348,7 +355,6 @@ pub enum ThemeAppearanceMode {
 }
 
 /// Specifies the density of the UI.
-/// Note: This setting is still experimental. See [this tracking issue](https://github.com/zed-industries/zed/issues/18078)
 #[derive(
     Debug,
     Default,
@@ -363,6 +369,8 @@ pub enum ThemeAppearanceMode {
     Deserialize,
     JsonSchema,
     MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
 )]
 #[serde(rename_all = "snake_case")]
 pub enum UiDensity {
@@ -814,6 +822,18 @@ pub struct ThemeColorsContent {
     #[serde(rename = "editor.invisible")]
     pub editor_invisible: Option<String>,
 
+    /// Text Color. Used to mark tabs in the editor.
+    ///
+    /// Falls back to `editor.invisible` when not set.
+    #[serde(rename = "editor.invisible.tab")]
+    pub editor_invisible_tab: Option<String>,
+
+    /// Text Color. Used to mark spaces in the editor.
+    ///
+    /// Falls back to `editor.invisible` when not set.
+    #[serde(rename = "editor.invisible.space")]
+    pub editor_invisible_space: Option<String>,
+
     #[serde(rename = "editor.wrap_guide")]
     pub editor_wrap_guide: Option<String>,
 
@@ -19,6 +19,10 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: platform_default
     pub text_rendering_mode: Option<TextRenderingMode>,
+    /// When to draw windows in response to input.
+    ///
+    /// Default: vsync
+    pub presentation_mode: Option<PresentationMode>,
     /// Layout mode for the bottom dock
     ///
     /// Default: contained
@@ -33,6 +37,8 @@ pub struct WorkspaceSettingsContent {
     pub pane_split_direction_vertical: Option<PaneSplitDirectionVertical>,
     /// Centered layout related settings.
     pub centered_layout: Option<CenteredLayoutSettings>,
+    /// Zen mode related settings.
+    pub zen_mode: Option<ZenModeSettingsContent>,
     /// Whether or not to prompt the user to confirm before closing the application.
     ///
     /// Default: false
@@ -41,10 +47,6 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: true
     pub show_call_status_icon: Option<bool>,
-    /// When to automatically save edited buffers.
-    ///
-    /// Default: off
-    pub autosave: Option<AutosaveSetting>,
     /// Controls previous session restoration in freshly launched Zed instance.
     /// Values: empty_tab, last_workspace, last_session, launchpad
     /// Default: last_session
@@ -122,6 +124,12 @@ pub struct WorkspaceSettingsContent {
     /// What draws window decorations/titlebar, the client application (Zed) or display server
     /// Default: client
     pub window_decorations: Option<WindowDecorations>,
+    /// Keystrokes that trigger an action system-wide, even while Zed isn't
+    /// focused, such as `{"ctrl-alt-z": "focus_last_workspace"}` (Linux only).
+    ///
+    /// Default: {}
+    #[serde(default)]
+    pub global_hotkeys: HashMap<String, GlobalHotkeyAction>,
 }
 
 #[with_fallible_options]
@@ -152,6 +160,25 @@ pub struct ItemSettingsContent {
     ///
     /// Default: false
     pub show_close_button: Option<ShowCloseButton>,
+    /// Rules tinting the tabs and project panel entries of matching files.
+    /// The first rule matching a file applies.
+    ///
+    /// Default: []
+    pub color_rules: Option<Vec<TabColorRule>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, MergeFrom)]
+pub struct TabColorRule {
+    /// The name of the worktree the file has to belong to.
+    /// Files of any worktree match when not set.
+    pub worktree: Option<String>,
+    /// Globs matched against the file's path within its worktree,
+    /// such as `**/*_test.rs` or `generated/**`.
+    /// Files at any path match when empty.
+    #[serde(default)]
+    pub globs: Vec<String>,
+    /// The tint, as a hex color such as `#22c55e`.
+    pub color: String,
 }
 
 #[with_fallible_options]
@@ -342,6 +369,31 @@ pub enum WindowDecorations {
     Server,
 }
 
+/// What a global hotkey does when pressed.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    Debug,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum GlobalHotkeyAction {
+    /// Bring the most recently used workspace window to the front, opening a
+    /// new window if there are none.
+    #[default]
+    FocusLastWorkspace,
+    /// Open a new window with an empty buffer for jotting something down.
+    QuickCapture,
+}
+
 #[derive(
     Copy,
     Clone,
@@ -424,6 +476,38 @@ pub struct TabBarSettingsContent {
     ///
     /// Default: false
     pub show_pinned_tabs_in_separate_row: Option<bool>,
+    /// Whether or not to shrink pinned tabs to their icon.
+    /// Pinned tabs of items without an icon keep showing their title.
+    ///
+    /// Default: true
+    pub show_pinned_tabs_as_icons: Option<bool>,
+    /// Where to place the tab bar of the editor panes.
+    ///
+    /// Default: top
+    pub placement: Option<TabBarPlacement>,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TabBarPlacement {
+    /// Show the tabs in a row above the pane's content.
+    #[default]
+    Top,
+    /// Show the tabs in a list to the left of the pane's content.
+    Left,
 }
 
 #[with_fallible_options]
@@ -574,6 +658,26 @@ pub struct CenteredLayoutSettings {
     ///
     /// Default: 0.2
     pub right_padding: Option<CenteredPaddingSettings>,
+    /// The maximum width of the central pane when the centered layout is used,
+    /// in characters of the buffer font. On wide screens, the paddings grow
+    /// beyond their relative width to keep the pane this narrow.
+    ///
+    /// Default: null
+    pub max_width: Option<u32>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Default)]
+#[with_fallible_options]
+pub struct ZenModeSettingsContent {
+    /// The maximum width of the centered pane in zen mode, in characters of
+    /// the buffer font.
+    ///
+    /// Default: 100
+    pub max_width: Option<u32>,
+    /// Whether entering zen mode also makes the window fullscreen.
+    ///
+    /// Default: false
+    pub fullscreen: Option<bool>,
 }
 
 #[derive(
@@ -623,6 +727,31 @@ pub enum TextRenderingMode {
     Grayscale,
 }
 
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    Debug,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PresentationMode {
+    /// Draw at most once per display refresh.
+    #[default]
+    Vsync,
+    /// Also draw as soon as input changes what's on screen, without waiting
+    /// for the next display refresh. Lowers the latency of typing and
+    /// scrolling at the cost of some extra frames.
+    LowLatency,
+}
+
 impl OnLastWindowClosed {
     pub fn is_quit_app(&self) -> bool {
         match self {
@@ -899,3 +1028,33 @@ impl DocumentSymbols {
         self == &Self::On
     }
 }
+
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeLens {
+    /// Do not show code lenses (default).
+    #[default]
+    Off,
+    /// Show code lenses, like reference counts or buttons to run tests, above the symbols they belong to.
+    On,
+}
+
+impl CodeLens {
+    /// Returns true if code lenses should be fetched and shown.
+    pub fn enabled(&self) -> bool {
+        self == &Self::On
+    }
+}
@@ -380,7 +380,7 @@ fn general_page() -> SettingsPage {
 }
 
 fn appearance_page() -> SettingsPage {
-    fn theme_section() -> [SettingsPageItem; 3] {
+    fn theme_section() -> [SettingsPageItem; 4] {
         [
             SettingsPageItem::SectionHeader("Theme"),
             SettingsPageItem::DynamicItem(DynamicItem {
@@ -721,6 +721,19 @@ fn appearance_page() -> SettingsPage {
                     }
                 }).collect(),
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "UI Density",
+                description: "How dense the UI is, scaling paddings and the heights of rows, tabs and the title bar.",
+                field: Box::new(SettingField {
+                    json_path: Some("ui_density"),
+                    pick: |settings_content| settings_content.theme.ui_density.as_ref(),
+                    write: |settings_content, value| {
+                        settings_content.theme.ui_density = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
         ]
     }
 
@@ -1035,7 +1048,7 @@ fn appearance_page() -> SettingsPage {
         ]
     }
 
-    fn cursor_section() -> [SettingsPageItem; 5] {
+    fn cursor_section() -> [SettingsPageItem; 7] {
         [
             SettingsPageItem::SectionHeader("Cursor"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -1064,6 +1077,32 @@ fn appearance_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Cursor Blink Interval",
+                description: "How long the cursor stays visible, and then hidden, in each blink (in milliseconds).",
+                field: Box::new(SettingField {
+                    json_path: Some("cursor_blink_interval"),
+                    pick: |settings_content| settings_content.editor.cursor_blink_interval.as_ref(),
+                    write: |settings_content, value| {
+                        settings_content.editor.cursor_blink_interval = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Smooth Caret",
+                description: "Whether the cursor glides to its new position instead of jumping there.",
+                field: Box::new(SettingField {
+                    json_path: Some("smooth_caret"),
+                    pick: |settings_content| settings_content.editor.smooth_caret.as_ref(),
+                    write: |settings_content, value| {
+                        settings_content.editor.smooth_caret = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
             SettingsPageItem::SettingItem(SettingItem {
                 title: "Cursor Shape",
                 description: "Cursor shape for the editor.",
@@ -1093,7 +1132,7 @@ fn appearance_page() -> SettingsPage {
         ]
     }
 
-    fn highlighting_section() -> [SettingsPageItem; 6] {
+    fn highlighting_section() -> [SettingsPageItem; 7] {
         [
             SettingsPageItem::SectionHeader("Highlighting"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -1137,6 +1176,24 @@ fn appearance_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Selection Highlight Min Length",
+                description: "Minimum length of the text to highlight the occurrences of.",
+                field: Box::new(SettingField {
+                    json_path: Some("selection_highlight_min_length"),
+                    pick: |settings_content| {
+                        settings_content
+                            .editor
+                            .selection_highlight_min_length
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content.editor.selection_highlight_min_length = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
             SettingsPageItem::SettingItem(SettingItem {
                 title: "Rounded Selection",
                 description: "Whether the text selection should have rounded corners.",
@@ -1342,7 +1399,7 @@ fn editor_page() -> SettingsPage {
             SettingsPageItem::SectionHeader("Auto Save"),
             SettingsPageItem::DynamicItem(DynamicItem {
                 discriminant: SettingItem {
-                    files: USER,
+                    files: USER | PROJECT,
                     title: "Auto Save Mode",
                     description: "When to auto save buffer changes.",
                     field: Box::new(SettingField {
@@ -1350,7 +1407,9 @@ fn editor_page() -> SettingsPage {
                         pick: |settings_content| {
                             Some(
                                 &dynamic_variants::<settings::AutosaveSetting>()[settings_content
-                                    .workspace
+                                    .project
+                                    .all_languages
+                                    .defaults
                                     .autosave
                                     .as_ref()?
                                     .discriminant()
@@ -1359,11 +1418,13 @@ fn editor_page() -> SettingsPage {
                         },
                         write: |settings_content, value| {
                             let Some(value) = value else {
-                                settings_content.workspace.autosave = None;
+                                settings_content.project.all_languages.defaults.autosave = None;
                                 return;
                             };
                             let settings_value = settings_content
-                                .workspace
+                                .project
+                                .all_languages
+                                .defaults
                                 .autosave
                                 .get_or_insert_with(|| settings::AutosaveSetting::Off);
                             *settings_value = match value {
@@ -1391,20 +1452,30 @@ fn editor_page() -> SettingsPage {
                     metadata: None,
                 },
                 pick_discriminant: |settings_content| {
-                    Some(settings_content.workspace.autosave.as_ref()?.discriminant() as usize)
+                    Some(
+                        settings_content
+                            .project
+                            .all_languages
+                            .defaults
+                            .autosave
+                            .as_ref()?
+                            .discriminant() as usize,
+                    )
                 },
                 fields: dynamic_variants::<settings::AutosaveSetting>()
                     .into_iter()
                     .map(|variant| match variant {
                         settings::AutosaveSettingDiscriminants::Off => vec![],
                         settings::AutosaveSettingDiscriminants::AfterDelay => vec![SettingItem {
-                            files: USER,
+                            files: USER | PROJECT,
                             title: "Delay (milliseconds)",
                             description: "Save after inactivity period (in milliseconds).",
                             field: Box::new(SettingField {
                                 json_path: Some("autosave.after_delay.milliseconds"),
                                 pick: |settings_content| match settings_content
-                                    .workspace
+                                    .project
+                                    .all_languages
+                                    .defaults
                                     .autosave
                                     .as_ref()
                                 {
@@ -1415,10 +1486,17 @@ fn editor_page() -> SettingsPage {
                                 },
                                 write: |settings_content, value| {
                                     let Some(value) = value else {
-                                        settings_content.workspace.autosave = None;
+                                        settings_content.project.all_languages.defaults.autosave =
+                                            None;
                                         return;
                                     };
-                                    match settings_content.workspace.autosave.as_mut() {
+                                    match settings_content
+                                        .project
+                                        .all_languages
+                                        .defaults
+                                        .autosave
+                                        .as_mut()
+                                    {
                                         Some(settings::AutosaveSetting::AfterDelay {
                                             milliseconds,
                                         }) => *milliseconds = value,
@@ -1561,7 +1639,7 @@ fn editor_page() -> SettingsPage {
         ]
     }
 
-    fn scrolling_section() -> [SettingsPageItem; 8] {
+    fn scrolling_section() -> [SettingsPageItem; 9] {
         [
             SettingsPageItem::SectionHeader("Scrolling"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -1673,6 +1751,30 @@ fn editor_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Sticky Scroll Max Lines",
+                description: "The maximum number of enclosing scopes to stick to the top of the editor",
+                field: Box::new(SettingField {
+                    json_path: Some("sticky_scroll.max_lines"),
+                    pick: |settings_content| {
+                        settings_content
+                            .editor
+                            .sticky_scroll
+                            .as_ref()?
+                            .max_lines
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .editor
+                            .sticky_scroll
+                            .get_or_insert_default()
+                            .max_lines = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
         ]
     }
 
@@ -1811,7 +1913,7 @@ fn editor_page() -> SettingsPage {
         ]
     }
 
-    fn gutter_section() -> [SettingsPageItem; 8] {
+    fn gutter_section() -> [SettingsPageItem; 9] {
         [
             SettingsPageItem::SectionHeader("Gutter"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -1896,6 +1998,29 @@ fn editor_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Show Bookmarks",
+                description: "Show bookmarks in the gutter.",
+                field: Box::new(SettingField {
+                    json_path: Some("gutter.bookmarks"),
+                    pick: |settings_content| {
+                        settings_content
+                            .editor
+                            .gutter
+                            .as_ref()
+                            .and_then(|gutter| gutter.bookmarks.as_ref())
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .editor
+                            .gutter
+                            .get_or_insert_default()
+                            .bookmarks = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
             SettingsPageItem::SettingItem(SettingItem {
                 title: "Show Folds",
                 description: "Show code folding controls in the gutter.",
@@ -3521,7 +3646,7 @@ fn window_and_layout_page() -> SettingsPage {
         ]
     }
 
-    fn title_bar_section() -> [SettingsPageItem; 9] {
+    fn title_bar_section() -> [SettingsPageItem; 14] {
         [
             SettingsPageItem::SectionHeader("Title Bar"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -3590,6 +3715,28 @@ fn window_and_layout_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Name Truncation",
+                description: "How long project and branch names are shortened in the titlebar.",
+                field: Box::new(SettingField {
+                    json_path: Some("title_bar.name_truncation"),
+                    pick: |settings_content| {
+                        settings_content
+                            .title_bar
+                            .as_ref()?
+                            .name_truncation
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .title_bar
+                            .get_or_insert_default()
+                            .name_truncation = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
             SettingsPageItem::SettingItem(SettingItem {
                 title: "Show Onboarding Banner",
                 description: "Show banners announcing new features in the titlebar.",
@@ -3688,10 +3835,94 @@ fn window_and_layout_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Double Click Action",
+                description: "What to do when double-clicking the titlebar.",
+                field: Box::new(SettingField {
+                    json_path: Some("title_bar.double_click_action"),
+                    pick: |settings_content| {
+                        settings_content
+                            .title_bar
+                            .as_ref()?
+                            .double_click_action
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .title_bar
+                            .get_or_insert_default()
+                            .double_click_action = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Middle Click Action",
+                description: "What to do when middle-clicking the titlebar.",
+                field: Box::new(SettingField {
+                    json_path: Some("title_bar.middle_click_action"),
+                    pick: |settings_content| {
+                        settings_content
+                            .title_bar
+                            .as_ref()?
+                            .middle_click_action
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .title_bar
+                            .get_or_insert_default()
+                            .middle_click_action = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Fullscreen Controls",
+                description: "How the window controls behave while the window is fullscreen or in zen mode.",
+                field: Box::new(SettingField {
+                    json_path: Some("title_bar.fullscreen_controls"),
+                    pick: |settings_content| {
+                        settings_content
+                            .title_bar
+                            .as_ref()?
+                            .fullscreen_controls
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .title_bar
+                            .get_or_insert_default()
+                            .fullscreen_controls = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Window Control Style",
+                description: "How the window control glyphs are drawn.",
+                field: Box::new(SettingField {
+                    json_path: Some("title_bar.control_style"),
+                    pick: |settings_content| {
+                        settings_content.title_bar.as_ref()?.control_style.as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .title_bar
+                            .get_or_insert_default()
+                            .control_style = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
         ]
     }
 
-    fn tab_bar_section() -> [SettingsPageItem; 9] {
+    fn tab_bar_section() -> [SettingsPageItem; 11] {
         [
             SettingsPageItem::SectionHeader("Tab Bar"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -3832,6 +4063,41 @@ fn window_and_layout_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Pinned Tabs As Icons",
+                description: "Shrink pinned tabs to their icon.",
+                field: Box::new(SettingField {
+                    json_path: Some("tab_bar.show_pinned_tabs_as_icons"),
+                    pick: |settings_content| {
+                        settings_content
+                            .tab_bar
+                            .as_ref()?
+                            .show_pinned_tabs_as_icons
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .tab_bar
+                            .get_or_insert_default()
+                            .show_pinned_tabs_as_icons = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Tab Bar Placement",
+                description: "Show the tabs above the pane's content or in a list to its left.",
+                field: Box::new(SettingField {
+                    json_path: Some("tab_bar.placement"),
+                    pick: |settings_content| settings_content.tab_bar.as_ref()?.placement.as_ref(),
+                    write: |settings_content, value| {
+                        settings_content.tab_bar.get_or_insert_default().placement = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
         ]
     }
 
@@ -4051,7 +4317,7 @@ fn window_and_layout_page() -> SettingsPage {
         ]
     }
 
-    fn layout_section() -> [SettingsPageItem; 4] {
+    fn layout_section() -> [SettingsPageItem; 7] {
         [
             SettingsPageItem::SectionHeader("Layout"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -4115,6 +4381,78 @@ fn window_and_layout_page() -> SettingsPage {
                 }),
                 metadata: None,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                files: USER,
+                title: "Centered Layout Max Width",
+                description: "Maximum width of the central pane for centered layout, in characters.",
+                field: Box::new(SettingField {
+                    json_path: Some("centered_layout.max_width"),
+                    pick: |settings_content| {
+                        settings_content
+                            .workspace
+                            .centered_layout
+                            .as_ref()?
+                            .max_width
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .workspace
+                            .centered_layout
+                            .get_or_insert_default()
+                            .max_width = value;
+                    },
+                }),
+                metadata: None,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                files: USER,
+                title: "Zen Mode Max Width",
+                description: "Maximum width of the centered pane in zen mode, in characters.",
+                field: Box::new(SettingField {
+                    json_path: Some("zen_mode.max_width"),
+                    pick: |settings_content| {
+                        settings_content
+                            .workspace
+                            .zen_mode
+                            .as_ref()?
+                            .max_width
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .workspace
+                            .zen_mode
+                            .get_or_insert_default()
+                            .max_width = value;
+                    },
+                }),
+                metadata: None,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                files: USER,
+                title: "Zen Mode Fullscreen",
+                description: "Whether entering zen mode also makes the window fullscreen.",
+                field: Box::new(SettingField {
+                    json_path: Some("zen_mode.fullscreen"),
+                    pick: |settings_content| {
+                        settings_content
+                            .workspace
+                            .zen_mode
+                            .as_ref()?
+                            .fullscreen
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .workspace
+                            .zen_mode
+                            .get_or_insert_default()
+                            .fullscreen = value;
+                    },
+                }),
+                metadata: None,
+            }),
         ]
     }
 
@@ -5368,6 +5706,206 @@ fn panels_page() -> SettingsPage {
         ]
     }
 
+    fn bookmarks_panel_section() -> [SettingsPageItem; 4] {
+        [
+            SettingsPageItem::SectionHeader("Bookmarks Panel"),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Bookmarks Panel Button",
+                description: "Show the bookmarks panel button in the status bar.",
+                field: Box::new(SettingField {
+                    json_path: Some("bookmarks_panel.button"),
+                    pick: |settings_content| {
+                        settings_content.bookmarks_panel.as_ref()?.button.as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .bookmarks_panel
+                            .get_or_insert_default()
+                            .button = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Bookmarks Panel Dock",
+                description: "Where to dock the bookmarks panel.",
+                field: Box::new(SettingField {
+                    json_path: Some("bookmarks_panel.dock"),
+                    pick: |settings_content| {
+                        settings_content.bookmarks_panel.as_ref()?.dock.as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .bookmarks_panel
+                            .get_or_insert_default()
+                            .dock = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Bookmarks Panel Default Width",
+                description: "Default width of the bookmarks panel in pixels.",
+                field: Box::new(SettingField {
+                    json_path: Some("bookmarks_panel.default_width"),
+                    pick: |settings_content| {
+                        settings_content
+                            .bookmarks_panel
+                            .as_ref()?
+                            .default_width
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .bookmarks_panel
+                            .get_or_insert_default()
+                            .default_width = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+        ]
+    }
+
+    fn hierarchy_panel_section() -> [SettingsPageItem; 4] {
+        [
+            SettingsPageItem::SectionHeader("Hierarchy Panel"),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Hierarchy Panel Button",
+                description: "Show the hierarchy panel button in the status bar.",
+                field: Box::new(SettingField {
+                    json_path: Some("hierarchy_panel.button"),
+                    pick: |settings_content| {
+                        settings_content.hierarchy_panel.as_ref()?.button.as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .hierarchy_panel
+                            .get_or_insert_default()
+                            .button = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Hierarchy Panel Dock",
+                description: "Where to dock the hierarchy panel.",
+                field: Box::new(SettingField {
+                    json_path: Some("hierarchy_panel.dock"),
+                    pick: |settings_content| {
+                        settings_content.hierarchy_panel.as_ref()?.dock.as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .hierarchy_panel
+                            .get_or_insert_default()
+                            .dock = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Hierarchy Panel Default Width",
+                description: "Default width of the hierarchy panel in pixels.",
+                field: Box::new(SettingField {
+                    json_path: Some("hierarchy_panel.default_width"),
+                    pick: |settings_content| {
+                        settings_content
+                            .hierarchy_panel
+                            .as_ref()?
+                            .default_width
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .hierarchy_panel
+                            .get_or_insert_default()
+                            .default_width = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+        ]
+    }
+
+    fn language_servers_panel_section() -> [SettingsPageItem; 4] {
+        [
+            SettingsPageItem::SectionHeader("Language Servers Panel"),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Language Servers Panel Button",
+                description: "Show the language servers panel button in the status bar.",
+                field: Box::new(SettingField {
+                    json_path: Some("language_servers_panel.button"),
+                    pick: |settings_content| {
+                        settings_content
+                            .language_servers_panel
+                            .as_ref()?
+                            .button
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .language_servers_panel
+                            .get_or_insert_default()
+                            .button = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Language Servers Panel Dock",
+                description: "Where to dock the language servers panel.",
+                field: Box::new(SettingField {
+                    json_path: Some("language_servers_panel.dock"),
+                    pick: |settings_content| {
+                        settings_content
+                            .language_servers_panel
+                            .as_ref()?
+                            .dock
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .language_servers_panel
+                            .get_or_insert_default()
+                            .dock = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Language Servers Panel Default Width",
+                description: "Default width of the language servers panel in pixels.",
+                field: Box::new(SettingField {
+                    json_path: Some("language_servers_panel.default_width"),
+                    pick: |settings_content| {
+                        settings_content
+                            .language_servers_panel
+                            .as_ref()?
+                            .default_width
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .language_servers_panel
+                            .get_or_insert_default()
+                            .default_width = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+        ]
+    }
+
     fn agent_panel_section() -> [SettingsPageItem; 5] {
         [
             SettingsPageItem::SectionHeader("Agent Panel"),
@@ -5444,6 +5982,9 @@ fn panels_page() -> SettingsPage {
             debugger_panel_section(),
             notification_panel_section(),
             collaboration_panel_section(),
+            bookmarks_panel_section(),
+            hierarchy_panel_section(),
+            language_servers_panel_section(),
             agent_panel_section(),
         ],
     }
@@ -6093,6 +6634,19 @@ fn terminal_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Bell",
+                description: "What to do when a program in the terminal rings the bell.",
+                field: Box::new(SettingField {
+                    json_path: Some("terminal.bell"),
+                    pick: |settings_content| settings_content.terminal.as_ref()?.bell.as_ref(),
+                    write: |settings_content, value| {
+                        settings_content.terminal.get_or_insert_default().bell = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
             SettingsPageItem::SettingItem(SettingItem {
                 title: "Alternate Scroll",
                 description: "Whether alternate scroll mode is active by default (converts mouse scroll to arrow keys in apps like Vim).",
@@ -7674,7 +8228,7 @@ fn language_settings_data() -> Box<[SettingsPageItem]> {
         ]
     }
 
-    fn formatting_section() -> [SettingsPageItem; 7] {
+    fn formatting_section() -> [SettingsPageItem; 9] {
         [
             SettingsPageItem::SectionHeader("Formatting"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -7812,6 +8366,51 @@ fn language_settings_data() -> Box<[SettingsPageItem]> {
                 metadata: None,
                 files: USER | PROJECT,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Code Actions On Save",
+                description: "Code actions to run, in order, whenever a file is saved.",
+                field: Box::new(
+                    SettingField {
+                        json_path: Some("languages.$(language).code_actions_on_save"),
+                        pick: |settings_content| {
+                            language_settings_field(settings_content, |language| {
+                                language.code_actions_on_save.as_ref()
+                            })
+                        },
+                        write: |settings_content, value| {
+                            language_settings_field_mut(
+                                settings_content,
+                                value,
+                                |language, value| {
+                                    language.code_actions_on_save = value;
+                                },
+                            )
+                        },
+                    }
+                    .unimplemented(),
+                ),
+                metadata: None,
+                files: USER | PROJECT,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Code Actions On Save Timeout (milliseconds)",
+                description: "How long to wait for each code action run on save before skipping it.",
+                field: Box::new(SettingField {
+                    json_path: Some("languages.$(language).code_actions_on_save_timeout_ms"),
+                    pick: |settings_content| {
+                        language_settings_field(settings_content, |language| {
+                            language.code_actions_on_save_timeout_ms.as_ref()
+                        })
+                    },
+                    write: |settings_content, value| {
+                        language_settings_field_mut(settings_content, value, |language, value| {
+                            language.code_actions_on_save_timeout_ms = value;
+                        })
+                    },
+                }),
+                metadata: None,
+                files: USER | PROJECT,
+            }),
         ]
     }
 
@@ -8090,7 +8689,7 @@ fn language_settings_data() -> Box<[SettingsPageItem]> {
         ]
     }
 
-    fn inlay_hints_section() -> [SettingsPageItem; 10] {
+    fn inlay_hints_section() -> [SettingsPageItem; 11] {
         [
             SettingsPageItem::SectionHeader("Inlay Hints"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -8197,6 +8796,32 @@ fn language_settings_data() -> Box<[SettingsPageItem]> {
                 metadata: None,
                 files: USER | PROJECT,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Show Chaining Hints",
+                description: "Whether type hints at the end of each line of a method chain should be shown. Falls back to showing them with the other type hints.",
+                field: Box::new(SettingField {
+                    json_path: Some("languages.$(language).inlay_hints.show_chaining_hints"),
+                    pick: |settings_content| {
+                        language_settings_field(settings_content, |language| {
+                            let inlay_hints = language.inlay_hints.as_ref()?;
+                            inlay_hints
+                                .show_chaining_hints
+                                .as_ref()
+                                .or(inlay_hints.show_type_hints.as_ref())
+                        })
+                    },
+                    write: |settings_content, value| {
+                        language_settings_field_mut(settings_content, value, |language, value| {
+                            language
+                                .inlay_hints
+                                .get_or_insert_default()
+                                .show_chaining_hints = value;
+                        })
+                    },
+                }),
+                metadata: None,
+                files: USER | PROJECT,
+            }),
             SettingsPageItem::SettingItem(SettingItem {
                 title: "Show Background",
                 description: "Show a background for inlay hints.",
@@ -8368,7 +8993,7 @@ fn language_settings_data() -> Box<[SettingsPageItem]> {
         ]
     }
 
-    fn miscellaneous_section() -> [SettingsPageItem; 6] {
+    fn miscellaneous_section() -> [SettingsPageItem; 7] {
         [
             SettingsPageItem::SectionHeader("Miscellaneous"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -8467,6 +9092,25 @@ fn language_settings_data() -> Box<[SettingsPageItem]> {
                 metadata: None,
                 files: USER | PROJECT,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Show Minimap",
+                description: "Whether to show the minimap for this language, when the minimap is enabled.",
+                field: Box::new(SettingField {
+                    json_path: Some("languages.$(language).show_minimap"),
+                    pick: |settings_content| {
+                        language_settings_field(settings_content, |language| {
+                            language.show_minimap.as_ref()
+                        })
+                    },
+                    write: |settings_content, value| {
+                        language_settings_field_mut(settings_content, value, |language, value| {
+                            language.show_minimap = value;
+                        })
+                    },
+                }),
+                metadata: None,
+                files: USER | PROJECT,
+            }),
         ]
     }
 
@@ -8579,7 +9223,7 @@ fn language_settings_data() -> Box<[SettingsPageItem]> {
 /// LanguageSettings items that should be included in the "Languages & Tools" page
 /// not the "Editor" page
 fn non_editor_language_settings_data() -> Box<[SettingsPageItem]> {
-    fn lsp_section() -> [SettingsPageItem; 8] {
+    fn lsp_section() -> [SettingsPageItem; 9] {
         [
             SettingsPageItem::SectionHeader("LSP"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -8734,10 +9378,29 @@ fn non_editor_language_settings_data() -> Box<[SettingsPageItem]> {
                 metadata: None,
                 files: USER | PROJECT,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Code Lens",
+                description: "When enabled, show code lenses like reference counts or buttons to run tests above symbols.",
+                field: Box::new(SettingField {
+                    json_path: Some("languages.$(language).code_lens"),
+                    pick: |settings_content| {
+                        language_settings_field(settings_content, |language| {
+                            language.code_lens.as_ref()
+                        })
+                    },
+                    write: |settings_content, value| {
+                        language_settings_field_mut(settings_content, value, |language, value| {
+                            language.code_lens = value;
+                        })
+                    },
+                }),
+                metadata: None,
+                files: USER | PROJECT,
+            }),
         ]
     }
 
-    fn lsp_completions_section() -> [SettingsPageItem; 4] {
+    fn lsp_completions_section() -> [SettingsPageItem; 5] {
         [
             SettingsPageItem::SectionHeader("LSP Completions"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -8800,6 +9463,25 @@ fn non_editor_language_settings_data() -> Box<[SettingsPageItem]> {
                 metadata: None,
                 files: USER | PROJECT,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Auto Import",
+                description: "Whether accepting a completion may also add the import it needs.",
+                field: Box::new(SettingField {
+                    json_path: Some("languages.$(language).completions.auto_import"),
+                    pick: |settings_content| {
+                        language_settings_field(settings_content, |language| {
+                            language.completions.as_ref()?.auto_import.as_ref()
+                        })
+                    },
+                    write: |settings_content, value| {
+                        language_settings_field_mut(settings_content, value, |language, value| {
+                            language.completions.get_or_insert_default().auto_import = value;
+                        })
+                    },
+                }),
+                metadata: None,
+                files: USER | PROJECT,
+            }),
         ]
     }
 
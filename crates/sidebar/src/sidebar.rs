@@ -1065,7 +1065,7 @@ fn read_thread_title_map() -> Option<HashMap<String, String>> {
 
 impl Render for Sidebar {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let titlebar_height = ui::utils::platform_title_bar_height(window);
+        let titlebar_height = platform_title_bar::title_bar_height(window, cx);
         let ui_font = theme::setup_ui_font(window, cx);
         let is_focused = self.focus_handle(cx).is_focused(window);
 
@@ -1,4 +1,5 @@
 use anyhow::{Context as _, Result};
+use regex::{Captures, Regex, RegexBuilder};
 use smallvec::SmallVec;
 use std::{collections::BTreeMap, ops::Range};
 
@@ -14,11 +15,22 @@ pub struct TabStop {
     pub choices: Option<Vec<String>>,
 }
 
+/// Gives the value of a snippet variable, like `TM_FILENAME`, or `None` when
+/// the variable is unknown.
+pub type ResolveVariable<'a> = &'a dyn Fn(&str) -> Option<String>;
+
 impl Snippet {
     pub fn parse(source: &str) -> Result<Self> {
+        Self::parse_with_variables(source, &|_| None)
+    }
+
+    /// Parses a snippet, replacing its variables with the values that
+    /// `resolve_variable` gives for them. Variables that are unknown or empty
+    /// are replaced with their default, or with their name when they have none.
+    pub fn parse_with_variables(source: &str, resolve_variable: ResolveVariable) -> Result<Self> {
         let mut text = String::with_capacity(source.len());
         let mut tabstops = BTreeMap::new();
-        parse_snippet(source, false, &mut text, &mut tabstops)
+        parse_snippet(source, false, &mut text, &mut tabstops, resolve_variable)
             .context("failed to parse snippet")?;
 
         let len = text.len() as isize;
@@ -47,12 +59,19 @@ fn parse_snippet<'a>(
     nested: bool,
     text: &mut String,
     tabstops: &mut BTreeMap<usize, TabStop>,
+    resolve_variable: ResolveVariable,
 ) -> Result<&'a str> {
     loop {
         match source.chars().next() {
             None => return Ok(""),
             Some('$') => {
-                source = parse_tabstop(&source[1..], text, tabstops)?;
+                let rest = &source[1..];
+                let name_start = rest.strip_prefix('{').unwrap_or(rest);
+                source = if name_start.starts_with(is_variable_name_start) {
+                    parse_variable(rest, text, tabstops, resolve_variable)?
+                } else {
+                    parse_tabstop(rest, text, tabstops, resolve_variable)?
+                };
             }
             Some('\\') => {
                 // As specified in the LSP spec (`Grammar` section),
@@ -93,6 +112,7 @@ fn parse_tabstop<'a>(
     mut source: &'a str,
     text: &mut String,
     tabstops: &mut BTreeMap<usize, TabStop>,
+    resolve_variable: ResolveVariable,
 ) -> Result<&'a str> {
     let tabstop_start = text.len();
     let tabstop_index;
@@ -108,7 +128,11 @@ fn parse_tabstop<'a>(
         }
 
         if source.starts_with(':') {
-            source = parse_snippet(&source[1..], true, text, tabstops)?;
+            source = parse_snippet(&source[1..], true, text, tabstops, resolve_variable)?;
+        } else if source.starts_with('/') {
+            // A transformation of a tabstop applies to the text typed into
+            // it, which its mirrors don't follow, so it's left out.
+            (_, source) = parse_transform(&source[1..])?;
         }
 
         if source.starts_with('}') {
@@ -133,6 +157,274 @@ fn parse_tabstop<'a>(
     Ok(source)
 }
 
+fn is_variable_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn parse_variable<'a>(
+    mut source: &'a str,
+    text: &mut String,
+    tabstops: &mut BTreeMap<usize, TabStop>,
+    resolve_variable: ResolveVariable,
+) -> Result<&'a str> {
+    let braced = source.starts_with('{');
+    if braced {
+        source = &source[1..];
+    }
+    let name_len = source
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(source.len());
+    let (name, rest) = source.split_at(name_len);
+    source = rest;
+    let value = resolve_variable(name).filter(|value| !value.is_empty());
+
+    if !braced {
+        text.push_str(value.as_deref().unwrap_or(name));
+        return Ok(source);
+    }
+
+    if let Some(default) = source.strip_prefix(':') {
+        match value {
+            Some(value) => {
+                text.push_str(&value);
+                // The default is parsed only to find where it ends.
+                source = parse_snippet(
+                    default,
+                    true,
+                    &mut String::new(),
+                    &mut BTreeMap::new(),
+                    resolve_variable,
+                )?;
+            }
+            None => source = parse_snippet(default, true, text, tabstops, resolve_variable)?,
+        }
+    } else if let Some(transform) = source.strip_prefix('/') {
+        let (transform, rest) = parse_transform(transform)?;
+        text.push_str(&transform.apply(value.as_deref().unwrap_or_default()));
+        source = rest;
+    } else {
+        text.push_str(value.as_deref().unwrap_or(name));
+    }
+
+    source.strip_prefix('}').context("expected a closing brace")
+}
+
+/// A `/regex/format/flags` transformation, applied to the value of a variable.
+struct Transform {
+    regex: Regex,
+    global: bool,
+    format: Vec<FormatItem>,
+}
+
+enum FormatItem {
+    Text(String),
+    Group { index: usize, kind: GroupFormat },
+}
+
+enum GroupFormat {
+    Plain,
+    Upcase,
+    Downcase,
+    Capitalize,
+    CamelCase,
+    PascalCase,
+    Conditional { if_text: String, else_text: String },
+    OrElse(String),
+}
+
+impl Transform {
+    fn apply(&self, value: &str) -> String {
+        let limit = if self.global { 0 } else { 1 };
+        self.regex
+            .replacen(value, limit, |captures: &Captures| self.format(captures))
+            .into_owned()
+    }
+
+    fn format(&self, captures: &Captures) -> String {
+        let mut formatted = String::new();
+        for item in &self.format {
+            match item {
+                FormatItem::Text(text) => formatted.push_str(text),
+                FormatItem::Group { index, kind } => {
+                    let group = captures.get(*index).map_or("", |group| group.as_str());
+                    match kind {
+                        GroupFormat::Plain => formatted.push_str(group),
+                        GroupFormat::Upcase => formatted.push_str(&group.to_uppercase()),
+                        GroupFormat::Downcase => formatted.push_str(&group.to_lowercase()),
+                        GroupFormat::Capitalize => {
+                            let mut chars = group.chars();
+                            if let Some(first) = chars.next() {
+                                formatted.extend(first.to_uppercase());
+                                formatted.push_str(chars.as_str());
+                            }
+                        }
+                        GroupFormat::CamelCase | GroupFormat::PascalCase => {
+                            for (word_index, word) in group
+                                .split(|c: char| !c.is_alphanumeric())
+                                .filter(|word| !word.is_empty())
+                                .enumerate()
+                            {
+                                let mut chars = word.chars();
+                                if let Some(first) = chars.next() {
+                                    if word_index == 0 && matches!(kind, GroupFormat::CamelCase) {
+                                        formatted.extend(first.to_lowercase());
+                                    } else {
+                                        formatted.extend(first.to_uppercase());
+                                    }
+                                    formatted.push_str(&chars.as_str().to_lowercase());
+                                }
+                            }
+                        }
+                        GroupFormat::Conditional { if_text, else_text } => {
+                            if group.is_empty() {
+                                formatted.push_str(else_text);
+                            } else {
+                                formatted.push_str(if_text);
+                            }
+                        }
+                        GroupFormat::OrElse(else_text) => {
+                            if group.is_empty() {
+                                formatted.push_str(else_text);
+                            } else {
+                                formatted.push_str(group);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        formatted
+    }
+}
+
+fn parse_transform(source: &str) -> Result<(Transform, &str)> {
+    let (pattern, source) = split_transform_part(source, false)?;
+    let (format, source) = split_transform_part(source, true)?;
+    let flags_len = source.find('}').context("expected a closing brace")?;
+    let (flags, source) = source.split_at(flags_len);
+
+    let regex = RegexBuilder::new(&pattern)
+        .case_insensitive(flags.contains('i'))
+        .multi_line(flags.contains('m'))
+        .dot_matches_new_line(flags.contains('s'))
+        .build()
+        .context("invalid transformation regex")?;
+    let transform = Transform {
+        regex,
+        global: flags.contains('g'),
+        format: parse_format(&format)?,
+    };
+    Ok((transform, source))
+}
+
+/// Splits `source` at the first `/` that isn't escaped with a backslash.
+/// Regex patterns keep their other escapes, since they have their own meaning
+/// there, while formats skip over their `${...}` groups, which can contain a
+/// `/` themselves.
+fn split_transform_part(source: &str, is_format: bool) -> Result<(String, &str)> {
+    let mut part = String::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '/' => return Ok((part, &source[index + 1..])),
+            '\\' => {
+                if let Some((_, escaped)) = chars.next() {
+                    if !is_format && escaped != '/' {
+                        part.push('\\');
+                    }
+                    part.push(escaped);
+                }
+            }
+            '$' if is_format && chars.peek().is_some_and(|(_, next)| *next == '{') => {
+                part.push(c);
+                for (_, c) in chars.by_ref() {
+                    part.push(c);
+                    if c == '}' {
+                        break;
+                    }
+                }
+            }
+            _ => part.push(c),
+        }
+    }
+    anyhow::bail!("expected a closing '/'")
+}
+
+fn parse_format(mut source: &str) -> Result<Vec<FormatItem>> {
+    let mut items = Vec::new();
+    let mut text = String::new();
+    while let Some(c) = source.chars().next() {
+        if c != '$' {
+            text.push(c);
+            source = &source[c.len_utf8()..];
+            continue;
+        }
+
+        source = &source[1..];
+        let braced = source.starts_with('{');
+        if braced {
+            source = &source[1..];
+        }
+        let Ok((index, rest)) = parse_int(source) else {
+            text.push('$');
+            if braced {
+                text.push('{');
+            }
+            continue;
+        };
+        source = rest;
+
+        let mut kind = GroupFormat::Plain;
+        if braced {
+            let end = source.find('}').context("expected a closing brace")?;
+            let (modifier, rest) = source.split_at(end);
+            source = &rest[1..];
+            kind = parse_group_format(modifier)?;
+        }
+
+        if !text.is_empty() {
+            items.push(FormatItem::Text(std::mem::take(&mut text)));
+        }
+        items.push(FormatItem::Group { index, kind });
+    }
+    if !text.is_empty() {
+        items.push(FormatItem::Text(text));
+    }
+    Ok(items)
+}
+
+fn parse_group_format(modifier: &str) -> Result<GroupFormat> {
+    let Some(modifier) = modifier.strip_prefix(':') else {
+        anyhow::ensure!(modifier.is_empty(), "expected ':' after a format group");
+        return Ok(GroupFormat::Plain);
+    };
+    let kind = match modifier {
+        "/upcase" => GroupFormat::Upcase,
+        "/downcase" => GroupFormat::Downcase,
+        "/capitalize" => GroupFormat::Capitalize,
+        "/camelcase" => GroupFormat::CamelCase,
+        "/pascalcase" => GroupFormat::PascalCase,
+        _ => {
+            if let Some(if_text) = modifier.strip_prefix('+') {
+                GroupFormat::Conditional {
+                    if_text: if_text.to_string(),
+                    else_text: String::new(),
+                }
+            } else if let Some(conditional) = modifier.strip_prefix('?') {
+                let (if_text, else_text) = conditional.split_once(':').unwrap_or((conditional, ""));
+                GroupFormat::Conditional {
+                    if_text: if_text.to_string(),
+                    else_text: else_text.to_string(),
+                }
+            } else {
+                let else_text = modifier.strip_prefix('-').unwrap_or(modifier);
+                GroupFormat::OrElse(else_text.to_string())
+            }
+        }
+    };
+    Ok(kind)
+}
+
 fn parse_int(source: &str) -> Result<(usize, &str)> {
     let len = source
         .find(|c: char| !c.is_ascii_digit())
@@ -324,6 +616,74 @@ mod tests {
         assert_eq!(tabstops(&snippet), &[vec![4..4], vec![7..7]]);
     }
 
+    #[test]
+    fn test_snippet_with_variables() {
+        let resolve_variable = |name: &str| match name {
+            "TM_FILENAME" => Some("main.rs".to_string()),
+            "TM_SELECTED_TEXT" => Some(String::new()),
+            _ => None,
+        };
+
+        let snippet =
+            Snippet::parse_with_variables("// $TM_FILENAME: ${TM_FILENAME}$1", &resolve_variable)
+                .unwrap();
+        assert_eq!(snippet.text, "// main.rs: main.rs");
+        assert_eq!(tabstops(&snippet), &[vec![19..19]]);
+
+        // Empty and unknown variables are replaced with their default, or
+        // with their name when they have none.
+        let snippet = Snippet::parse_with_variables(
+            "${TM_SELECTED_TEXT:${1:value}} $UNKNOWN ${TM_FILENAME:${2:unused}}",
+            &resolve_variable,
+        )
+        .unwrap();
+        assert_eq!(snippet.text, "value UNKNOWN main.rs");
+        assert_eq!(tabstops(&snippet), &[vec![0..5], vec![21..21]]);
+
+        // Without a resolver, every variable is unknown.
+        let snippet = Snippet::parse("${TM_FILENAME:untitled}").unwrap();
+        assert_eq!(snippet.text, "untitled");
+    }
+
+    #[test]
+    fn test_snippet_with_variable_transformations() {
+        let resolve_variable = |name: &str| match name {
+            "TM_FILENAME" => Some("snippet_parser.rs".to_string()),
+            _ => None,
+        };
+        let transform = |source: &str| {
+            Snippet::parse_with_variables(source, &resolve_variable)
+                .unwrap()
+                .text
+        };
+
+        assert_eq!(transform(r"${TM_FILENAME/(.*)\..+$/$1/}"), "snippet_parser");
+        assert_eq!(
+            transform(r"${TM_FILENAME/(.*)\..+$/${1:/pascalcase}/}"),
+            "SnippetParser"
+        );
+        assert_eq!(
+            transform(r"${TM_FILENAME/(.*)\..+$/${1:/camelcase}/}"),
+            "snippetParser"
+        );
+        assert_eq!(
+            transform(r"${TM_FILENAME/(.*)\..+$/${1:/upcase}/}"),
+            "SNIPPET_PARSER"
+        );
+        assert_eq!(transform(r"${TM_FILENAME/_/-/g}"), "snippet-parser.rs");
+        assert_eq!(transform(r"${TM_FILENAME/S/-/i}"), "-nippet_parser.rs");
+        assert_eq!(
+            transform(r"${TM_FILENAME/(test)?.*/${1:?test:code}/}"),
+            "code"
+        );
+        assert_eq!(transform(r"${TM_FILENAME/(test)?.*/${1:-none}/}"), "none");
+
+        // Transformations of tabstops are left out.
+        let snippet = Snippet::parse("$1 ${1/(.*)/${1:/upcase}/}").unwrap();
+        assert_eq!(snippet.text, " ");
+        assert_eq!(tabstops(&snippet), &[vec![0..0, 1..1], vec![1..1]]);
+    }
+
     fn tabstops(snippet: &Snippet) -> Vec<Vec<Range<isize>>> {
         snippet.tabstops.iter().map(|t| t.ranges.to_vec()).collect()
     }
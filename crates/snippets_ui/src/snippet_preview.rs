@@ -0,0 +1,231 @@
+use std::path::PathBuf;
+
+use editor::{Editor, EditorEvent};
+use gpui::{
+    App, Context, Entity, EventEmitter, FocusHandle, Focusable, HighlightStyle, IntoElement,
+    ParentElement, Render, StyledText, Subscription, Window,
+};
+use settings::Settings as _;
+use snippet::Snippet;
+use snippet_provider::format::VsSnippetsFile;
+use theme::ThemeSettings;
+use ui::prelude::*;
+use workspace::item::{Item, ItemEvent};
+
+/// Shows how each snippet of a snippets file expands, and why the ones that
+/// don't parse are invalid, updating as the file is edited.
+pub struct SnippetPreview {
+    editor: Entity<Editor>,
+    focus_handle: FocusHandle,
+    previews: Result<Vec<SnippetExpansion>, String>,
+    _editor_subscription: Subscription,
+}
+
+enum SnippetExpansion {
+    Valid {
+        name: String,
+        prefixes: Vec<String>,
+        description: Option<String>,
+        snippet: Snippet,
+    },
+    Invalid(String),
+}
+
+impl SnippetPreview {
+    pub fn new(editor: Entity<Editor>, cx: &mut Context<Self>) -> Self {
+        let editor_subscription = cx.subscribe(&editor, |this, _, event: &EditorEvent, cx| {
+            if let EditorEvent::Edited { .. } = event {
+                this.update_previews(cx);
+            }
+        });
+        let mut this = Self {
+            editor,
+            focus_handle: cx.focus_handle(),
+            previews: Ok(Vec::new()),
+            _editor_subscription: editor_subscription,
+        };
+        this.update_previews(cx);
+        this
+    }
+
+    pub fn editor(&self) -> &Entity<Editor> {
+        &self.editor
+    }
+
+    fn update_previews(&mut self, cx: &mut Context<Self>) {
+        let buffer = self.editor.read(cx).buffer().read(cx);
+        let contents = buffer.snapshot(cx).text();
+        let source = buffer
+            .as_singleton()
+            .and_then(|buffer| buffer.read(cx).file().map(|file| file.full_path(cx)))
+            .unwrap_or_else(|| PathBuf::from(buffer.title(cx).into_owned()));
+
+        self.previews = match serde_json_lenient::from_str::<VsSnippetsFile>(&contents) {
+            Ok(snippets_file) => {
+                let mut previews = snippet_provider::file_to_snippets(snippets_file, &source)
+                    .map(|snippet| -> Result<SnippetExpansion, String> {
+                        let snippet = snippet.map_err(|error| format!("{error:#}"))?;
+                        let parsed = Snippet::parse(&snippet.body).map_err(|error| {
+                            format!("Invalid snippet '{}': {error:#}", snippet.name)
+                        })?;
+                        Ok(SnippetExpansion::Valid {
+                            name: snippet.name.clone(),
+                            prefixes: snippet.prefix.clone(),
+                            description: snippet.description.clone(),
+                            snippet: parsed,
+                        })
+                    })
+                    .map(|expansion| expansion.unwrap_or_else(SnippetExpansion::Invalid))
+                    .collect::<Vec<_>>();
+                previews.sort_by(|left, right| left.sort_key().cmp(right.sort_key()));
+                Ok(previews)
+            }
+            Err(_) if contents.trim().is_empty() => Ok(Vec::new()),
+            Err(error) => Err(format!("Invalid snippets file: {error}")),
+        };
+        cx.notify();
+    }
+
+    fn render_expansion(
+        &self,
+        index: usize,
+        expansion: &SnippetExpansion,
+        cx: &App,
+    ) -> impl IntoElement {
+        let colors = cx.theme().colors();
+        let container = v_flex()
+            .id(("snippet-preview", index))
+            .w_full()
+            .gap_1()
+            .p_2()
+            .rounded_md()
+            .border_1()
+            .border_color(colors.border_variant);
+
+        match expansion {
+            SnippetExpansion::Invalid(error) => container.child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Icon::new(IconName::XCircle)
+                            .size(IconSize::Small)
+                            .color(Color::Error),
+                    )
+                    .child(Label::new(error.clone()).color(Color::Error)),
+            ),
+            SnippetExpansion::Valid {
+                name,
+                prefixes,
+                description,
+                snippet,
+            } => {
+                let tabstop_highlight = HighlightStyle {
+                    background_color: Some(colors.editor_document_highlight_read_background),
+                    ..Default::default()
+                };
+                let mut highlights = snippet
+                    .tabstops
+                    .iter()
+                    .flat_map(|tabstop| tabstop.ranges.iter())
+                    .filter(|range| !range.is_empty())
+                    .map(|range| (range.start as usize..range.end as usize, tabstop_highlight))
+                    .collect::<Vec<_>>();
+                highlights.sort_by_key(|(range, _)| range.start);
+                highlights
+                    .dedup_by(|(range, _), (previous_range, _)| range.start < previous_range.end);
+                let font = ThemeSettings::get_global(cx).buffer_font.clone();
+
+                container
+                    .child(
+                        h_flex().gap_2().child(Label::new(name.clone())).child(
+                            Label::new(prefixes.join(", "))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                    )
+                    .when_some(description.clone(), |this, description| {
+                        this.child(
+                            Label::new(description)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .child(
+                        div()
+                            .p_1()
+                            .rounded_sm()
+                            .bg(colors.editor_background)
+                            .font(font)
+                            .text_sm()
+                            .child(
+                                StyledText::new(snippet.text.clone()).with_highlights(highlights),
+                            ),
+                    )
+            }
+        }
+    }
+}
+
+impl SnippetExpansion {
+    fn sort_key(&self) -> &str {
+        match self {
+            SnippetExpansion::Valid { name, .. } => name,
+            SnippetExpansion::Invalid(error) => error,
+        }
+    }
+}
+
+impl Focusable for SnippetPreview {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<()> for SnippetPreview {}
+
+impl Item for SnippetPreview {
+    type Event = ();
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Code))
+    }
+
+    fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
+        let title = self.editor.read(cx).buffer().read(cx).title(cx);
+        format!("Preview {title}").into()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Snippet Preview Opened")
+    }
+
+    fn to_item_events(_event: &Self::Event, _f: &mut dyn FnMut(ItemEvent)) {}
+}
+
+impl Render for SnippetPreview {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match &self.previews {
+            Err(error) => v_flex().child(Label::new(error.clone()).color(Color::Error)),
+            Ok(previews) if previews.is_empty() => v_flex().child(
+                Label::new("Add snippets to the file to preview how they expand.")
+                    .color(Color::Muted),
+            ),
+            Ok(previews) => v_flex().gap_2().children(
+                previews
+                    .iter()
+                    .enumerate()
+                    .map(|(index, expansion)| self.render_expansion(index, expansion, cx)),
+            ),
+        };
+
+        v_flex()
+            .id("SnippetPreview")
+            .key_context("SnippetPreview")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_4()
+            .overflow_y_scroll()
+            .bg(cx.theme().colors().editor_background)
+            .child(content)
+    }
+}
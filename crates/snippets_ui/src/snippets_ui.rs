@@ -1,3 +1,6 @@
+mod snippet_preview;
+
+use editor::Editor;
 use file_icons::FileIcons;
 use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
 use gpui::{
@@ -18,7 +21,11 @@ use std::{
 };
 use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
 use util::ResultExt;
-use workspace::{ModalView, OpenOptions, OpenVisible, Workspace, notifications::NotifyResultExt};
+use workspace::{
+    ModalView, OpenOptions, OpenVisible, SplitDirection, Workspace, notifications::NotifyResultExt,
+};
+
+pub use snippet_preview::SnippetPreview;
 
 #[derive(Eq, Hash, PartialEq)]
 struct ScopeName(Cow<'static, str>);
@@ -97,6 +104,39 @@ fn open_folder(
     cx.open_with_system(snippets_dir().borrow());
 }
 
+/// Opens a preview of the snippets file in `editor` to the side, unless one
+/// is open already.
+fn open_snippet_preview(
+    workspace: &mut Workspace,
+    editor: Entity<Editor>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let pane = workspace
+        .find_pane_in_direction(SplitDirection::Right, cx)
+        .unwrap_or_else(|| {
+            workspace.split_pane(
+                workspace.active_pane().clone(),
+                SplitDirection::Right,
+                window,
+                cx,
+            )
+        });
+    pane.update(cx, |pane, cx| {
+        let existing_preview = pane
+            .items_of_type::<SnippetPreview>()
+            .find(|preview| preview.read(cx).editor() == &editor)
+            .and_then(|preview| pane.index_for_item(&preview));
+        if let Some(existing_preview) = existing_preview {
+            pane.activate_item(existing_preview, false, false, window, cx);
+        } else {
+            let preview = cx.new(|cx| SnippetPreview::new(editor.clone(), cx));
+            pane.add_item(Box::new(preview), false, false, None, window, cx);
+        }
+    });
+    editor.focus_handle(cx).focus(window, cx);
+}
+
 pub struct ScopeSelector {
     picker: Entity<Picker<ScopeSelectorDelegate>>,
 }
@@ -222,17 +262,26 @@ impl PickerDelegate for ScopeSelectorDelegate {
                     workspace.update_in(cx, |workspace, window, cx| {
                         workspace
                             .with_local_workspace(window, cx, |workspace, window, cx| {
-                                workspace
-                                    .open_abs_path(
-                                        snippets_dir().join(scope_file_name.with_extension()),
-                                        OpenOptions {
-                                            visible: Some(OpenVisible::None),
-                                            ..Default::default()
-                                        },
-                                        window,
-                                        cx,
-                                    )
-                                    .detach();
+                                let open_snippets_file = workspace.open_abs_path(
+                                    snippets_dir().join(scope_file_name.with_extension()),
+                                    OpenOptions {
+                                        visible: Some(OpenVisible::None),
+                                        ..Default::default()
+                                    },
+                                    window,
+                                    cx,
+                                );
+                                cx.spawn_in(window, async move |workspace, cx| {
+                                    let Some(editor) =
+                                        open_snippets_file.await?.downcast::<Editor>()
+                                    else {
+                                        return Ok(());
+                                    };
+                                    workspace.update_in(cx, |workspace, window, cx| {
+                                        open_snippet_preview(workspace, editor, window, cx);
+                                    })
+                                })
+                                .detach_and_log_err(cx);
                             })
                             .detach();
                     })
@@ -0,0 +1,409 @@
+//! Hunspell dictionaries: a `.dic` word list whose entries are expanded with
+//! the prefix and suffix rules of an `.aff` file when loaded.
+//!
+//! Only the affix rules themselves and the flags keeping a stem out of the
+//! dictionary are understood. Compounding, rule continuations and
+//! morphological fields are ignored.
+
+use std::ops::Range;
+
+use collections::{HashMap, HashSet};
+
+/// How many suggestions at most to offer for a misspelled word.
+const MAX_SUGGESTIONS: usize = 5;
+
+pub struct Dictionary {
+    words: HashSet<String>,
+    /// The characters tried when suggesting corrections, most common first.
+    try_characters: Vec<char>,
+}
+
+#[derive(Clone, Copy)]
+enum FlagFormat {
+    /// Each character is a flag.
+    Char,
+    /// Each pair of characters is a flag.
+    Long,
+    /// Flags are comma-separated numbers.
+    Numeric,
+}
+
+impl FlagFormat {
+    fn parse(self, flags: &str) -> Vec<String> {
+        match self {
+            Self::Char => flags.chars().map(String::from).collect(),
+            Self::Long => flags
+                .chars()
+                .collect::<Vec<_>>()
+                .chunks(2)
+                .map(|pair| pair.iter().collect())
+                .collect(),
+            Self::Numeric => flags
+                .split(',')
+                .map(|flag| flag.trim().to_string())
+                .filter(|flag| !flag.is_empty())
+                .collect(),
+        }
+    }
+}
+
+enum ConditionPart {
+    Any,
+    Characters {
+        characters: Vec<char>,
+        negated: bool,
+    },
+}
+
+impl ConditionPart {
+    fn parse_all(condition: &str) -> Vec<Self> {
+        if condition == "." {
+            return Vec::new();
+        }
+        let mut parts = Vec::new();
+        let mut chars = condition.chars();
+        while let Some(char) = chars.next() {
+            match char {
+                '.' => parts.push(Self::Any),
+                '[' => {
+                    let mut characters = Vec::new();
+                    let mut negated = false;
+                    for char in chars.by_ref() {
+                        match char {
+                            ']' => break,
+                            '^' if characters.is_empty() && !negated => negated = true,
+                            char => characters.push(char),
+                        }
+                    }
+                    parts.push(Self::Characters {
+                        characters,
+                        negated,
+                    });
+                }
+                char => parts.push(Self::Characters {
+                    characters: vec![char],
+                    negated: false,
+                }),
+            }
+        }
+        parts
+    }
+
+    fn matches(&self, char: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Characters {
+                characters,
+                negated,
+            } => characters.contains(&char) != *negated,
+        }
+    }
+}
+
+struct AffixRule {
+    strip: String,
+    add: String,
+    condition: Vec<ConditionPart>,
+}
+
+struct AffixClass {
+    cross_product: bool,
+    rules: Vec<AffixRule>,
+}
+
+impl AffixRule {
+    fn apply_prefix(&self, word: &str) -> Option<String> {
+        let rest = word.strip_prefix(self.strip.as_str())?;
+        let chars = word.chars().collect::<Vec<_>>();
+        let matches = self.condition.len() <= chars.len()
+            && self
+                .condition
+                .iter()
+                .zip(&chars)
+                .all(|(part, char)| part.matches(*char));
+        matches.then(|| format!("{}{rest}", self.add))
+    }
+
+    fn apply_suffix(&self, word: &str) -> Option<String> {
+        let rest = word.strip_suffix(self.strip.as_str())?;
+        let chars = word.chars().collect::<Vec<_>>();
+        let matches = self.condition.len() <= chars.len()
+            && self
+                .condition
+                .iter()
+                .zip(&chars[chars.len() - self.condition.len()..])
+                .all(|(part, char)| part.matches(*char));
+        matches.then(|| format!("{rest}{}", self.add))
+    }
+}
+
+impl Dictionary {
+    /// Builds a dictionary from the contents of its `.aff` and `.dic` files.
+    pub fn parse(aff: &str, dic: &str) -> Self {
+        let mut flag_format = FlagFormat::Char;
+        let mut try_characters = Vec::new();
+        let mut prefixes = HashMap::<String, AffixClass>::default();
+        let mut suffixes = HashMap::<String, AffixClass>::default();
+        let mut stem_only_flags = HashSet::<String>::default();
+
+        for line in aff.lines() {
+            let mut fields = line.split_whitespace();
+            let Some(directive) = fields.next() else {
+                continue;
+            };
+            match directive {
+                "FLAG" => {
+                    flag_format = match fields.next() {
+                        Some("long") => FlagFormat::Long,
+                        Some("num") => FlagFormat::Numeric,
+                        _ => FlagFormat::Char,
+                    }
+                }
+                "TRY" => {
+                    try_characters = fields.next().unwrap_or_default().chars().collect();
+                }
+                "NEEDAFFIX" | "ONLYINCOMPOUND" | "FORBIDDENWORD" => {
+                    stem_only_flags.extend(fields.next().map(str::to_string));
+                }
+                "PFX" | "SFX" => {
+                    let classes = if directive == "PFX" {
+                        &mut prefixes
+                    } else {
+                        &mut suffixes
+                    };
+                    let Some(flag) = fields.next() else {
+                        continue;
+                    };
+                    let Some(class) = classes.get_mut(flag) else {
+                        classes.insert(
+                            flag.to_string(),
+                            AffixClass {
+                                cross_product: fields.next() == Some("Y"),
+                                rules: Vec::new(),
+                            },
+                        );
+                        continue;
+                    };
+                    let (Some(strip), Some(add)) = (fields.next(), fields.next()) else {
+                        continue;
+                    };
+                    let add = add.split('/').next().unwrap_or_default();
+                    class.rules.push(AffixRule {
+                        strip: if strip == "0" { "" } else { strip }.to_string(),
+                        add: if add == "0" { "" } else { add }.to_string(),
+                        condition: ConditionPart::parse_all(fields.next().unwrap_or(".")),
+                    });
+                }
+                _ => {}
+            }
+        }
+        if try_characters.is_empty() {
+            try_characters = ('a'..='z').collect();
+        }
+
+        let mut words = HashSet::default();
+        let mut lines = dic.lines();
+        if let Some(first_line) = lines.next()
+            && first_line.trim().parse::<usize>().is_err()
+        {
+            lines = dic.lines();
+        }
+        for line in lines {
+            let Some(entry) = line.split_whitespace().next() else {
+                continue;
+            };
+            let (word, flags) = match entry.split_once('/') {
+                Some((word, flags)) => (word, flag_format.parse(flags)),
+                None => (entry, Vec::new()),
+            };
+            if word.is_empty() {
+                continue;
+            }
+            if flags.iter().all(|flag| !stem_only_flags.contains(flag)) {
+                words.insert(word.to_string());
+            }
+
+            let word_prefixes = flags
+                .iter()
+                .filter_map(|flag| prefixes.get(flag))
+                .collect::<Vec<_>>();
+            for prefix in &word_prefixes {
+                words.extend(
+                    prefix
+                        .rules
+                        .iter()
+                        .filter_map(|rule| rule.apply_prefix(word)),
+                );
+            }
+            for suffix in flags.iter().filter_map(|flag| suffixes.get(flag)) {
+                for suffixed in suffix
+                    .rules
+                    .iter()
+                    .filter_map(|rule| rule.apply_suffix(word))
+                {
+                    if suffix.cross_product {
+                        for prefix in word_prefixes.iter().filter(|prefix| prefix.cross_product) {
+                            words.extend(
+                                prefix
+                                    .rules
+                                    .iter()
+                                    .filter_map(|rule| rule.apply_prefix(&suffixed)),
+                            );
+                        }
+                    }
+                    words.insert(suffixed);
+                }
+            }
+        }
+
+        Self {
+            words,
+            try_characters,
+        }
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+
+    pub(crate) fn try_characters(&self) -> &[char] {
+        &self.try_characters
+    }
+}
+
+/// The strings one deletion, transposition, replacement or insertion away
+/// from `word`, in that order.
+pub(crate) fn edits(word: &str, characters: &[char]) -> Vec<String> {
+    let chars = word.chars().collect::<Vec<_>>();
+    let with_range_replaced = |range: Range<usize>, replacement: &[char]| {
+        chars[..range.start]
+            .iter()
+            .chain(replacement)
+            .chain(&chars[range.end..])
+            .collect::<String>()
+    };
+
+    let mut edits = Vec::new();
+    for index in 0..chars.len() {
+        edits.push(with_range_replaced(index..index + 1, &[]));
+    }
+    for index in 0..chars.len().saturating_sub(1) {
+        edits.push(with_range_replaced(
+            index..index + 2,
+            &[chars[index + 1], chars[index]],
+        ));
+    }
+    for index in 0..chars.len() {
+        for character in characters {
+            if *character != chars[index] {
+                edits.push(with_range_replaced(index..index + 1, &[*character]));
+            }
+        }
+    }
+    for index in 0..=chars.len() {
+        for character in characters {
+            edits.push(with_range_replaced(index..index, &[*character]));
+        }
+    }
+    edits
+}
+
+/// Corrections for `word`: the accepted words closest to it, trying those a
+/// single edit away before those two edits away, then its splits into two
+/// accepted words.
+pub(crate) fn suggestions(
+    word: &str,
+    characters: &[char],
+    is_correct: impl Fn(&str) -> bool,
+) -> Vec<String> {
+    let mut suggestions = Vec::new();
+    let mut seen = HashSet::default();
+    let mut push = |suggestion: String, suggestions: &mut Vec<String>| {
+        if suggestions.len() < MAX_SUGGESTIONS
+            && suggestion != word
+            && is_correct(&suggestion)
+            && seen.insert(suggestion.clone())
+        {
+            suggestions.push(suggestion);
+        }
+    };
+
+    let single_edits = edits(word, characters);
+    for edit in &single_edits {
+        push(edit.clone(), &mut suggestions);
+    }
+    if suggestions.is_empty() {
+        for edit in &single_edits {
+            for double_edit in edits(edit, characters) {
+                push(double_edit, &mut suggestions);
+            }
+            if suggestions.len() >= MAX_SUGGESTIONS {
+                break;
+            }
+        }
+    }
+    for (index, _) in word.char_indices().skip(1) {
+        let (first, second) = word.split_at(index);
+        if is_correct(first) && is_correct(second) {
+            push(format!("{first} {second}"), &mut suggestions);
+        }
+    }
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "\
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwz
+
+PFX A Y 1
+PFX A   0     re         .
+
+SFX D Y 4
+SFX D   0     d          e
+SFX D   y     ied        [^aeiou]y
+SFX D   0     ed         [^ey]
+SFX D   0     ed         [aeiou]y
+
+SFX S Y 4
+SFX S   y     ies        [^aeiou]y
+SFX S   0     s          [aeiou]y
+SFX S   0     es         [sxzh]
+SFX S   0     s          [^sxzhy]
+";
+
+    const DIC: &str = "\
+4
+try/ADS
+play/ADS
+move/AD
+cat/S
+";
+
+    #[test]
+    fn test_affix_expansion() {
+        let dictionary = Dictionary::parse(AFF, DIC);
+        for word in [
+            "try", "tried", "retried", "tries", "retry", "play", "played", "replayed", "move",
+            "moved", "removed", "cat", "cats",
+        ] {
+            assert!(dictionary.contains(word), "{word} should be accepted");
+        }
+        for word in ["tryed", "plaied", "moveed", "recat", "moves"] {
+            assert!(!dictionary.contains(word), "{word} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let dictionary = Dictionary::parse(AFF, DIC);
+        let is_correct = |word: &str| dictionary.contains(word);
+        let characters = dictionary.try_characters();
+        assert_eq!(suggestions("cta", characters, is_correct), ["cat"]);
+        assert_eq!(suggestions("plyed", characters, is_correct), ["played"]);
+        assert_eq!(suggestions("catplay", characters, is_correct), ["cat play"]);
+    }
+}
@@ -0,0 +1,487 @@
+//! Spell checking for prose in buffers: comments and strings of code, and the
+//! whole of Markdown and plain text, as configured per language by the
+//! `spell_check_captures` setting.
+//!
+//! Misspellings are underlined in editors, with corrections and adding the
+//! word to the dictionary offered as code actions.
+
+mod dictionary;
+mod words;
+
+use std::{ops::Range, path::PathBuf, rc::Rc, sync::Arc, time::Duration};
+
+use anyhow::{Context as _, Result};
+use collections::HashSet;
+use editor::{CodeActionProvider, Editor, EditorEvent, ExcerptId, HighlightKey};
+use fs::Fs;
+use gpui::{
+    App, AppContext as _, Context, Entity, Global, HighlightStyle, Subscription, Task,
+    UnderlineStyle, WeakEntity, Window, px,
+};
+use language::{Buffer, BufferSnapshot, OffsetRangeExt as _, Point, ToOffset as _};
+use project::{CodeAction, LspAction, ProjectTransaction};
+use serde::{Deserialize, Serialize};
+use settings::{RegisterSetting, Settings, SettingsStore, update_settings_file};
+use theme::ActiveTheme as _;
+use util::ResultExt as _;
+
+use dictionary::Dictionary;
+use words::words;
+
+const SPELL_CHECK_CODE_ACTION_PROVIDER_ID: &str = "spell_check";
+const REFRESH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+pub fn init(cx: &mut App) {
+    cx.set_global(GlobalSpellCheck {
+        spell_check: Arc::new(SpellCheck::default()),
+        dictionary_names: None,
+        _load_dictionaries: Task::ready(()),
+    });
+    update_spell_check(cx);
+    cx.observe_global::<SettingsStore>(update_spell_check)
+        .detach();
+
+    cx.observe_new(|editor: &mut Editor, window, cx| {
+        if let Some(window) = window {
+            register_editor(editor, window, cx);
+        }
+    })
+    .detach();
+}
+
+#[derive(Debug, RegisterSetting)]
+pub struct SpellingSettings {
+    pub dictionaries: Vec<String>,
+    pub words: Vec<String>,
+}
+
+impl Settings for SpellingSettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let spelling = content.spelling.clone().unwrap();
+        Self {
+            dictionaries: spelling.dictionaries.unwrap(),
+            words: spelling.words.unwrap(),
+        }
+    }
+}
+
+/// The dictionaries and accepted words in use, shared with the background
+/// tasks looking for misspellings.
+#[derive(Default)]
+pub struct SpellCheck {
+    dictionaries: Vec<Arc<Dictionary>>,
+    words: HashSet<String>,
+}
+
+impl SpellCheck {
+    pub fn has_dictionaries(&self) -> bool {
+        !self.dictionaries.is_empty()
+    }
+
+    /// Whether the word is accepted as is or, when capitalized, in lowercase.
+    pub fn is_correct(&self, word: &str) -> bool {
+        let word = word.replace('’', "'");
+        let is_known = |word: &str| {
+            self.words.contains(word)
+                || self
+                    .dictionaries
+                    .iter()
+                    .any(|dictionary| dictionary.contains(word))
+        };
+        is_known(&word) || is_known(&word.to_lowercase())
+    }
+
+    /// Corrections for the word, capitalized like it.
+    pub fn suggestions(&self, word: &str) -> Vec<String> {
+        let lowercase_word = word.to_lowercase();
+        let mut characters = Vec::new();
+        for dictionary in &self.dictionaries {
+            for character in dictionary.try_characters() {
+                if !characters.contains(character) {
+                    characters.push(*character);
+                }
+            }
+        }
+        let capitalized = lowercase_word != word;
+        dictionary::suggestions(&lowercase_word, &characters, |suggestion| {
+            self.is_correct(suggestion)
+        })
+        .into_iter()
+        .map(|suggestion| {
+            if !capitalized {
+                return suggestion;
+            }
+            let mut chars = suggestion.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+        .collect()
+    }
+
+    /// Returns the misspelled words of the range, checking only the parts
+    /// covered by the given highlight captures unless there are none.
+    pub fn misspellings(
+        &self,
+        buffer: &BufferSnapshot,
+        range: Range<usize>,
+        captures: &[String],
+    ) -> Vec<Range<usize>> {
+        let ranges = if captures.is_empty() {
+            vec![range]
+        } else {
+            buffer.ranges_for_highlight_captures(range, captures)
+        };
+        let mut misspellings = Vec::new();
+        for range in ranges {
+            let text = buffer.text_for_range(range.clone()).collect::<String>();
+            misspellings.extend(
+                words(&text)
+                    .into_iter()
+                    .filter(|word| !self.is_correct(&text[word.clone()]))
+                    .map(|word| range.start + word.start..range.start + word.end),
+            );
+        }
+        misspellings
+    }
+}
+
+struct GlobalSpellCheck {
+    spell_check: Arc<SpellCheck>,
+    /// The names of the dictionaries loaded or being loaded, which only
+    /// happens once a buffer is spell checked.
+    dictionary_names: Option<Vec<String>>,
+    _load_dictionaries: Task<()>,
+}
+
+impl Global for GlobalSpellCheck {}
+
+fn update_spell_check(cx: &mut App) {
+    let settings = SpellingSettings::get_global(cx);
+    let dictionary_names = settings.dictionaries.clone();
+    let words = settings.words.iter().cloned().collect::<HashSet<_>>();
+    let global = cx.global::<GlobalSpellCheck>();
+    if global
+        .dictionary_names
+        .as_ref()
+        .is_none_or(|names| *names == dictionary_names)
+    {
+        if global.spell_check.words != words {
+            let dictionaries = global.spell_check.dictionaries.clone();
+            cx.global_mut::<GlobalSpellCheck>().spell_check = Arc::new(SpellCheck {
+                dictionaries,
+                words,
+            });
+        }
+        return;
+    }
+
+    let fs = <dyn Fs>::global(cx);
+    let load_dictionaries = cx.spawn({
+        let dictionary_names = dictionary_names.clone();
+        async move |cx| {
+            let mut dictionaries = Vec::new();
+            for name in &dictionary_names {
+                if let Some(dictionary) = load_dictionary(fs.as_ref(), name, cx).await.log_err() {
+                    dictionaries.push(Arc::new(dictionary));
+                }
+            }
+            cx.update_global::<GlobalSpellCheck, _>(|global, cx| {
+                global.spell_check = Arc::new(SpellCheck {
+                    dictionaries,
+                    words: SpellingSettings::get_global(cx)
+                        .words
+                        .iter()
+                        .cloned()
+                        .collect(),
+                });
+            });
+        }
+    });
+    let global = cx.global_mut::<GlobalSpellCheck>();
+    global.dictionary_names = Some(dictionary_names);
+    global._load_dictionaries = load_dictionaries;
+}
+
+fn dictionary_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![paths::dictionaries_dir().clone()];
+    if cfg!(target_os = "macos") {
+        dirs.push(paths::home_dir().join("Library/Spelling"));
+        dirs.push(PathBuf::from("/Library/Spelling"));
+    } else if cfg!(any(target_os = "linux", target_os = "freebsd")) {
+        dirs.extend(
+            [
+                "/usr/share/hunspell",
+                "/usr/share/myspell",
+                "/usr/share/myspell/dicts",
+                "/usr/local/share/hunspell",
+            ]
+            .map(PathBuf::from),
+        );
+    }
+    dirs
+}
+
+async fn load_dictionary(fs: &dyn Fs, name: &str, cx: &gpui::AsyncApp) -> Result<Dictionary> {
+    for dir in dictionary_dirs() {
+        let dic_path = dir.join(format!("{name}.dic"));
+        if !fs.is_file(&dic_path).await {
+            continue;
+        }
+        let dic = fs
+            .load(&dic_path)
+            .await
+            .with_context(|| format!("loading dictionary {dic_path:?}"))?;
+        let aff = fs
+            .load(&dir.join(format!("{name}.aff")))
+            .await
+            .unwrap_or_default();
+        return Ok(cx
+            .background_spawn(async move { Dictionary::parse(&aff, &dic) })
+            .await);
+    }
+    anyhow::bail!("no {name}.dic dictionary found in {:?}", dictionary_dirs())
+}
+
+struct SpellCheckAddon {
+    refresh_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl editor::Addon for SpellCheckAddon {
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn to_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(self)
+    }
+}
+
+fn register_editor(editor: &mut Editor, window: &mut Window, cx: &mut Context<Editor>) {
+    if !editor.mode().is_full() {
+        return;
+    }
+
+    let subscriptions = vec![
+        cx.subscribe(&cx.entity(), |editor, _, event, cx| match event {
+            EditorEvent::BufferEdited
+            | EditorEvent::Reparsed(_)
+            | EditorEvent::ExcerptsAdded { .. }
+            | EditorEvent::ExcerptsExpanded { .. } => refresh_misspellings(editor, cx),
+            _ => {}
+        }),
+        cx.observe_global::<GlobalSpellCheck>(refresh_misspellings),
+        cx.observe_global::<SettingsStore>(refresh_misspellings),
+    ];
+    editor.register_addon(SpellCheckAddon {
+        refresh_task: Task::ready(()),
+        _subscriptions: subscriptions,
+    });
+    editor.add_code_action_provider(
+        Rc::new(SpellCheckCodeActionProvider {
+            editor: cx.entity().downgrade(),
+        }),
+        window,
+        cx,
+    );
+    refresh_misspellings(editor, cx);
+}
+
+fn refresh_misspellings(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let spell_check = cx.global::<GlobalSpellCheck>().spell_check.clone();
+    let multibuffer = editor.buffer().read(cx).snapshot(cx);
+    let excerpts = multibuffer
+        .excerpts()
+        .filter_map(|(excerpt_id, buffer, range)| {
+            let settings = buffer.settings_at(0, cx);
+            settings.spell_check.then(|| {
+                (
+                    excerpt_id,
+                    buffer.clone(),
+                    range.context,
+                    settings.spell_check_captures.clone(),
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    if !excerpts.is_empty() && cx.global::<GlobalSpellCheck>().dictionary_names.is_none() {
+        cx.global_mut::<GlobalSpellCheck>().dictionary_names = Some(Vec::new());
+        update_spell_check(cx);
+    }
+    if excerpts.is_empty() || !spell_check.has_dictionaries() {
+        editor.clear_highlights(HighlightKey::SpellCheck, cx);
+        if let Some(addon) = editor.addon_mut::<SpellCheckAddon>() {
+            addon.refresh_task = Task::ready(());
+        }
+        return;
+    }
+
+    let refresh_task = cx.spawn(async move |editor, cx| {
+        cx.background_executor().timer(REFRESH_DEBOUNCE).await;
+        let misspellings = cx
+            .background_spawn(async move {
+                let mut misspellings = Vec::new();
+                for (excerpt_id, buffer, range, captures) in excerpts {
+                    let range = range.to_offset(&buffer);
+                    for misspelling in spell_check.misspellings(&buffer, range, &captures) {
+                        misspellings.push((
+                            excerpt_id,
+                            buffer.anchor_after(misspelling.start)
+                                ..buffer.anchor_before(misspelling.end),
+                        ));
+                    }
+                }
+                misspellings
+            })
+            .await;
+        editor
+            .update(cx, |editor, cx| {
+                let multibuffer = editor.buffer().read(cx).snapshot(cx);
+                let ranges = misspellings
+                    .into_iter()
+                    .filter_map(|(excerpt_id, range)| {
+                        multibuffer.anchor_range_in_excerpt(excerpt_id, range)
+                    })
+                    .collect();
+                let style = HighlightStyle {
+                    underline: Some(UnderlineStyle {
+                        thickness: px(1.),
+                        color: Some(cx.theme().status().info),
+                        wavy: true,
+                    }),
+                    ..HighlightStyle::default()
+                };
+                editor.highlight_text(HighlightKey::SpellCheck, ranges, style, cx);
+            })
+            .ok();
+    });
+    if let Some(addon) = editor.addon_mut::<SpellCheckAddon>() {
+        addon.refresh_task = refresh_task;
+    }
+}
+
+/// What a spell check code action does, kept in the action's data.
+#[derive(Serialize, Deserialize)]
+enum SpellCheckAction {
+    Replace(String),
+    AddToDictionary(String),
+}
+
+struct SpellCheckCodeActionProvider {
+    editor: WeakEntity<Editor>,
+}
+
+impl CodeActionProvider for SpellCheckCodeActionProvider {
+    fn id(&self) -> Arc<str> {
+        SPELL_CHECK_CODE_ACTION_PROVIDER_ID.into()
+    }
+
+    fn code_actions(
+        &self,
+        buffer: &Entity<Buffer>,
+        range: Range<language::Anchor>,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Vec<CodeAction>>> {
+        let snapshot = buffer.read(cx).snapshot();
+        let settings = snapshot.settings_at(range.start, cx);
+        let spell_check = cx.global::<GlobalSpellCheck>().spell_check.clone();
+        if !settings.spell_check || !spell_check.has_dictionaries() {
+            return Task::ready(Ok(Vec::new()));
+        }
+        let captures = settings.spell_check_captures.clone();
+
+        cx.background_spawn(async move {
+            let offset = range.start.to_offset(&snapshot);
+            let row = snapshot.offset_to_point(offset).row;
+            let line = Point::new(row, 0)..Point::new(row, snapshot.line_len(row));
+            let Some(misspelling) = spell_check
+                .misspellings(&snapshot, line.to_offset(&snapshot), &captures)
+                .into_iter()
+                .find(|misspelling| misspelling.start <= offset && offset <= misspelling.end)
+            else {
+                return Ok(Vec::new());
+            };
+
+            let word = snapshot
+                .text_for_range(misspelling.clone())
+                .collect::<String>();
+            let range =
+                snapshot.anchor_before(misspelling.start)..snapshot.anchor_after(misspelling.end);
+            let code_action = |title: String, action: SpellCheckAction| {
+                anyhow::Ok(CodeAction {
+                    server_id: language::LanguageServerId(0),
+                    range: range.clone(),
+                    lsp_action: LspAction::Action(Box::new(lsp::CodeAction {
+                        title,
+                        kind: Some(lsp::CodeActionKind::QUICKFIX),
+                        data: Some(serde_json::to_value(action)?),
+                        ..lsp::CodeAction::default()
+                    })),
+                    resolved: true,
+                })
+            };
+            let mut actions = spell_check
+                .suggestions(&word)
+                .into_iter()
+                .map(|suggestion| {
+                    code_action(
+                        format!("Change to \"{suggestion}\""),
+                        SpellCheckAction::Replace(suggestion),
+                    )
+                })
+                .collect::<Result<Vec<_>>>()?;
+            actions.push(code_action(
+                format!("Add \"{word}\" to Dictionary"),
+                SpellCheckAction::AddToDictionary(word),
+            )?);
+            Ok(actions)
+        })
+    }
+
+    fn apply_code_action(
+        &self,
+        _: Entity<Buffer>,
+        action: CodeAction,
+        excerpt_id: ExcerptId,
+        _: bool,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<ProjectTransaction>> {
+        let spell_check_action = match &action.lsp_action {
+            LspAction::Action(lsp_action) => lsp_action
+                .data
+                .clone()
+                .and_then(|data| serde_json::from_value::<SpellCheckAction>(data).ok()),
+            _ => None,
+        };
+        let result = match spell_check_action {
+            Some(SpellCheckAction::Replace(replacement)) => self
+                .editor
+                .update(cx, |editor, cx| {
+                    let multibuffer = editor.buffer().read(cx).snapshot(cx);
+                    let range = multibuffer
+                        .anchor_range_in_excerpt(excerpt_id, action.range)
+                        .context("misspelling is no longer in the editor")?;
+                    editor.edit([(range, replacement)], cx);
+                    anyhow::Ok(())
+                })
+                .and_then(|result| result),
+            Some(SpellCheckAction::AddToDictionary(word)) => {
+                update_settings_file(<dyn Fs>::global(cx), cx, move |settings, _| {
+                    settings
+                        .spelling
+                        .get_or_insert_default()
+                        .words
+                        .get_or_insert_default()
+                        .push(word);
+                });
+                Ok(())
+            }
+            None => Err(anyhow::anyhow!("not a spell check code action")),
+        };
+        Task::ready(result.map(|()| ProjectTransaction::default()))
+    }
+}
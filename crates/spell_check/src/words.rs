@@ -0,0 +1,94 @@
+use std::ops::Range;
+
+/// Returns the byte ranges of the words of `text` worth spell checking.
+///
+/// Words are runs of letters and apostrophes. Runs that look like code are
+/// left out: those with digits, underscores or capitals past their first
+/// letter, single letters, and those that are part of a path, a URL, an
+/// escape sequence or a method call.
+pub fn words(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, char)) = chars.next() {
+        if !is_word_char(char) {
+            continue;
+        }
+        let mut end = start + char.len_utf8();
+        while let Some((offset, char)) = chars.next_if(|(_, char)| is_word_char(*char)) {
+            end = offset + char.len_utf8();
+        }
+
+        let token = &text[start..end];
+        let trimmed_start = token.len() - token.trim_start_matches(is_apostrophe).len();
+        let trimmed_end = token.trim_end_matches(is_apostrophe).len();
+        if trimmed_start >= trimmed_end {
+            continue;
+        }
+        let range = start + trimmed_start..start + trimmed_end;
+        if is_prose(text, range.clone()) {
+            words.push(range);
+        }
+    }
+    words
+}
+
+fn is_word_char(char: char) -> bool {
+    char.is_alphanumeric() || char == '_' || is_apostrophe(char)
+}
+
+fn is_apostrophe(char: char) -> bool {
+    char == '\'' || char == '’'
+}
+
+fn is_prose(text: &str, range: Range<usize>) -> bool {
+    let word = &text[range.clone()];
+    let mut chars = word.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    if chars.clone().next().is_none()
+        || !first.is_alphabetic()
+        || chars.any(|char| char.is_numeric() || char == '_' || char.is_uppercase())
+    {
+        return false;
+    }
+
+    let previous = text[..range.start].chars().next_back();
+    let mut following = text[range.end..].chars();
+    let next = following.next();
+    let after_next = following.next();
+    let after_code = matches!(
+        previous,
+        Some('.' | '/' | '\\' | '@' | '#' | '$' | ':' | '%' | '&' | '<' | '`')
+    );
+    let ends_sentence =
+        after_next.is_none_or(|char| char.is_whitespace() || matches!(char, '"' | '\'' | ')'));
+    let before_code = match next {
+        Some('/' | '(' | '`') => true,
+        Some(':' | '.') => !ends_sentence,
+        _ => false,
+    };
+    !after_code && !before_code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_texts(text: &str) -> Vec<&str> {
+        words(text).into_iter().map(|range| &text[range]).collect()
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(
+            word_texts("// Don't 'quote' the teh: see https://example.com/path."),
+            ["Don't", "quote", "the", "teh", "see"]
+        );
+        assert_eq!(
+            word_texts("call foo_bar() or fooBar, not HTTP2 nor main.rs x"),
+            ["call", "or", "not", "nor"]
+        );
+        assert_eq!(word_texts("\"line\\nbreak\""), ["line"]);
+    }
+}
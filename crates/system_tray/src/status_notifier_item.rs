@@ -0,0 +1,309 @@
+//! A tray icon exported over D-Bus with the StatusNotifierItem protocol, along
+//! with its menu, which uses the `com.canonical.dbusmenu` protocol.
+//!
+//! See https://www.freedesktop.org/wiki/Specifications/StatusNotifierItem/
+
+use std::collections::HashMap;
+
+use anyhow::{Context as _, Result};
+use futures::channel::mpsc::UnboundedSender;
+use serde::Serialize;
+use util::ResultExt as _;
+use zbus::{
+    interface,
+    object_server::SignalEmitter,
+    zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Str, Type, Value},
+};
+
+use crate::TrayEvent;
+
+const ITEM_PATH: &str = "/StatusNotifierItem";
+const MENU_PATH: &str = "/MenuBar";
+const ICON_NAME: &str = "zed";
+
+/// Registers the tray icon with the session's StatusNotifierWatcher. The icon
+/// stays visible until the returned connection is dropped.
+pub(crate) async fn connect(
+    app_id: String,
+    title: String,
+    events: UnboundedSender<TrayEvent>,
+) -> Result<zbus::Connection> {
+    let bus_name = format!("org.kde.StatusNotifierItem-{}-1", std::process::id());
+    let item = StatusNotifierItem {
+        id: app_id,
+        title,
+        needs_attention: false,
+        events: events.clone(),
+    };
+    let menu = DbusMenu { events };
+
+    let connection = zbus::connection::Builder::session()?
+        .name(bus_name.as_str())?
+        .serve_at(ITEM_PATH, item)?
+        .serve_at(MENU_PATH, menu)?
+        .build()
+        .await
+        .context("failed to export the tray icon over D-Bus")?;
+
+    connection
+        .call_method(
+            Some("org.kde.StatusNotifierWatcher"),
+            "/StatusNotifierWatcher",
+            Some("org.kde.StatusNotifierWatcher"),
+            "RegisterStatusNotifierItem",
+            &(bus_name.as_str(),),
+        )
+        .await
+        .context("no system tray is available to show the tray icon")?;
+
+    Ok(connection)
+}
+
+/// Switches the icon to its attention state, used while there are unread
+/// notifications.
+pub(crate) async fn set_needs_attention(
+    connection: zbus::Connection,
+    needs_attention: bool,
+) -> Result<()> {
+    let item = connection
+        .object_server()
+        .interface::<_, StatusNotifierItem>(ITEM_PATH)
+        .await?;
+    item.get_mut().await.needs_attention = needs_attention;
+    let status = item.get().await.status();
+    StatusNotifierItem::new_status(item.signal_emitter(), &status).await?;
+    Ok(())
+}
+
+struct StatusNotifierItem {
+    id: String,
+    title: String,
+    needs_attention: bool,
+    events: UnboundedSender<TrayEvent>,
+}
+
+#[interface(name = "org.kde.StatusNotifierItem")]
+impl StatusNotifierItem {
+    fn activate(&self, _x: i32, _y: i32) {
+        self.events.unbounded_send(TrayEvent::Activate).log_err();
+    }
+
+    fn secondary_activate(&self, _x: i32, _y: i32) {}
+
+    fn context_menu(&self, _x: i32, _y: i32) {}
+
+    fn scroll(&self, _delta: i32, _orientation: String) {}
+
+    #[zbus(property)]
+    fn category(&self) -> &str {
+        "ApplicationStatus"
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    #[zbus(property)]
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> String {
+        if self.needs_attention {
+            "NeedsAttention".to_string()
+        } else {
+            "Active".to_string()
+        }
+    }
+
+    #[zbus(property)]
+    fn icon_name(&self) -> &str {
+        ICON_NAME
+    }
+
+    #[zbus(property)]
+    fn attention_icon_name(&self) -> &str {
+        ICON_NAME
+    }
+
+    #[zbus(property)]
+    fn item_is_menu(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn menu(&self) -> OwnedObjectPath {
+        ObjectPath::from_static_str_unchecked(MENU_PATH).into()
+    }
+
+    #[zbus(signal)]
+    async fn new_status(emitter: &SignalEmitter<'_>, status: &str) -> zbus::Result<()>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MenuItem {
+    NewWindow = 1,
+    RecentProjects = 2,
+    Separator = 3,
+    Quit = 4,
+}
+
+impl MenuItem {
+    const ALL: [MenuItem; 4] = [
+        MenuItem::NewWindow,
+        MenuItem::RecentProjects,
+        MenuItem::Separator,
+        MenuItem::Quit,
+    ];
+
+    fn from_id(id: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|item| *item as i32 == id)
+    }
+
+    fn event(&self) -> Option<TrayEvent> {
+        match self {
+            MenuItem::NewWindow => Some(TrayEvent::NewWindow),
+            MenuItem::RecentProjects => Some(TrayEvent::RecentProjects),
+            MenuItem::Separator => None,
+            MenuItem::Quit => Some(TrayEvent::Quit),
+        }
+    }
+
+    fn properties(&self) -> HashMap<String, OwnedValue> {
+        let (name, value) = match self {
+            MenuItem::NewWindow => ("label", "New Window"),
+            MenuItem::RecentProjects => ("label", "Recent Projects…"),
+            MenuItem::Separator => ("type", "separator"),
+            MenuItem::Quit => ("label", "Quit"),
+        };
+        HashMap::from_iter([(name.to_string(), OwnedValue::from(Str::from_static(value)))])
+    }
+}
+
+/// A node of the menu tree returned by `GetLayout`, serialized as `(ia{sv}av)`.
+#[derive(Serialize, Type)]
+struct MenuLayout {
+    id: i32,
+    properties: HashMap<String, OwnedValue>,
+    children: Vec<OwnedValue>,
+}
+
+const ROOT_MENU_ID: i32 = 0;
+
+struct DbusMenu {
+    events: UnboundedSender<TrayEvent>,
+}
+
+#[interface(name = "com.canonical.dbusmenu")]
+impl DbusMenu {
+    fn get_layout(
+        &self,
+        parent_id: i32,
+        _recursion_depth: i32,
+        _property_names: Vec<String>,
+    ) -> zbus::fdo::Result<(u32, MenuLayout)> {
+        // The menu is static, so its layout never needs a new revision.
+        let revision = 1;
+        if parent_id != ROOT_MENU_ID {
+            let item = MenuItem::from_id(parent_id).ok_or_else(|| unknown_item(parent_id))?;
+            return Ok((
+                revision,
+                MenuLayout {
+                    id: parent_id,
+                    properties: item.properties(),
+                    children: Vec::new(),
+                },
+            ));
+        }
+
+        let children = MenuItem::ALL
+            .into_iter()
+            .filter_map(|item| {
+                let layout = (item as i32, item.properties(), Vec::<OwnedValue>::new());
+                OwnedValue::try_from(Value::from(layout)).log_err()
+            })
+            .collect();
+        Ok((
+            revision,
+            MenuLayout {
+                id: ROOT_MENU_ID,
+                properties: HashMap::from_iter([(
+                    "children-display".to_string(),
+                    OwnedValue::from(Str::from_static("submenu")),
+                )]),
+                children,
+            },
+        ))
+    }
+
+    fn get_group_properties(
+        &self,
+        ids: Vec<i32>,
+        _property_names: Vec<String>,
+    ) -> Vec<(i32, HashMap<String, OwnedValue>)> {
+        ids.into_iter()
+            .filter_map(|id| Some((id, MenuItem::from_id(id)?.properties())))
+            .collect()
+    }
+
+    fn get_property(&self, id: i32, name: String) -> zbus::fdo::Result<OwnedValue> {
+        let item = MenuItem::from_id(id).ok_or_else(|| unknown_item(id))?;
+        item.properties()
+            .remove(&name)
+            .ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("unknown property {name}")))
+    }
+
+    fn event(&self, id: i32, event_id: String, _data: OwnedValue, _timestamp: u32) {
+        if event_id == "clicked"
+            && let Some(event) = MenuItem::from_id(id).and_then(|item| item.event())
+        {
+            self.events.unbounded_send(event).log_err();
+        }
+    }
+
+    fn event_group(&self, events: Vec<(i32, String, OwnedValue, u32)>) -> Vec<i32> {
+        let mut unknown_ids = Vec::new();
+        for (id, event_id, data, timestamp) in events {
+            if MenuItem::from_id(id).is_some() {
+                self.event(id, event_id, data, timestamp);
+            } else {
+                unknown_ids.push(id);
+            }
+        }
+        unknown_ids
+    }
+
+    fn about_to_show(&self, _id: i32) -> bool {
+        false
+    }
+
+    fn about_to_show_group(&self, _ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+        (Vec::new(), Vec::new())
+    }
+
+    #[zbus(property)]
+    fn version(&self) -> u32 {
+        3
+    }
+
+    #[zbus(property)]
+    fn text_direction(&self) -> &str {
+        "ltr"
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> &str {
+        "normal"
+    }
+
+    #[zbus(property)]
+    fn icon_theme_path(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+fn unknown_item(id: i32) -> zbus::fdo::Error {
+    zbus::fdo::Error::InvalidArgs(format!("unknown menu item {id}"))
+}
@@ -0,0 +1,163 @@
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+mod status_notifier_item;
+
+use gpui::App;
+use settings::{RegisterSetting, Settings, SettingsContent};
+
+/// An action picked from the system tray icon or its menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayEvent {
+    /// The tray icon itself was clicked.
+    Activate,
+    NewWindow,
+    RecentProjects,
+    Quit,
+}
+
+#[derive(Clone, Copy, Debug, RegisterSetting)]
+pub struct TraySettings {
+    pub enabled: bool,
+}
+
+impl Settings for TraySettings {
+    fn from_settings(content: &SettingsContent) -> Self {
+        let tray = content.tray.clone().unwrap();
+        Self {
+            enabled: tray.enabled.unwrap(),
+        }
+    }
+}
+
+/// Shows a tray icon while the `tray.enabled` setting is on, calling
+/// `on_event` when the user picks one of its actions.
+///
+/// The tray is only implemented on Linux, through the StatusNotifierItem
+/// protocol, and is a no-op elsewhere.
+pub fn init(on_event: impl Fn(TrayEvent, &mut App) + 'static, cx: &mut App) {
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+    linux::init(on_event, cx);
+
+    #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+    {
+        _ = on_event;
+        _ = cx;
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+mod linux {
+    use std::rc::Rc;
+
+    use futures::{StreamExt as _, channel::mpsc};
+    use gpui::{App, AppContext as _, Context, Entity, Global, Subscription, Task};
+    use notifications::NotificationStore;
+    use release_channel::ReleaseChannel;
+    use settings::{Settings as _, SettingsStore};
+
+    use crate::{TrayEvent, TraySettings, status_notifier_item};
+
+    struct GlobalSystemTray(Entity<SystemTray>);
+
+    impl Global for GlobalSystemTray {}
+
+    struct SystemTray {
+        on_event: Rc<dyn Fn(TrayEvent, &mut App)>,
+        connection: Option<zbus::Connection>,
+        running: Option<Task<Option<()>>>,
+        needs_attention: bool,
+        _subscriptions: Vec<Subscription>,
+    }
+
+    pub(crate) fn init(on_event: impl Fn(TrayEvent, &mut App) + 'static, cx: &mut App) {
+        let tray = cx.new(|cx| SystemTray::new(Rc::new(on_event), cx));
+        cx.set_global(GlobalSystemTray(tray));
+    }
+
+    impl SystemTray {
+        fn new(on_event: Rc<dyn Fn(TrayEvent, &mut App)>, cx: &mut Context<Self>) -> Self {
+            let notification_store = NotificationStore::global(cx);
+            let subscriptions = vec![
+                cx.observe_global::<SettingsStore>(|this, cx| this.settings_changed(cx)),
+                cx.observe(&notification_store, |this, notification_store, cx| {
+                    let needs_attention =
+                        notification_store.read(cx).unread_notification_count() > 0;
+                    this.set_needs_attention(needs_attention, cx);
+                }),
+            ];
+
+            let mut this = Self {
+                on_event,
+                connection: None,
+                running: None,
+                needs_attention: false,
+                _subscriptions: subscriptions,
+            };
+            this.settings_changed(cx);
+            this
+        }
+
+        fn settings_changed(&mut self, cx: &mut Context<Self>) {
+            let enabled = TraySettings::get_global(cx).enabled;
+            if enabled && self.running.is_none() {
+                self.start(cx);
+            } else if !enabled {
+                // Dropping the connection releases our bus name, which makes
+                // the tray host remove the icon.
+                self.running = None;
+                self.connection = None;
+            }
+        }
+
+        fn start(&mut self, cx: &mut Context<Self>) {
+            let release_channel = ReleaseChannel::global(cx);
+            let (events_tx, mut events_rx) = mpsc::unbounded();
+            let connect = cx.background_spawn(status_notifier_item::connect(
+                release_channel.app_id().to_string(),
+                release_channel.display_name().to_string(),
+                events_tx,
+            ));
+
+            self.running = Some(cx.spawn(async move |this, cx| {
+                let connection = match connect.await {
+                    Ok(connection) => connection,
+                    Err(error) => {
+                        log::error!("Failed to add the system tray icon: {error:#}");
+                        // Lets the next settings change try again.
+                        this.update(cx, |this, _| this.running = None).ok();
+                        return None;
+                    }
+                };
+                this.update(cx, |this, cx| {
+                    this.connection = Some(connection);
+                    if this.needs_attention {
+                        this.sync_status(cx);
+                    }
+                })
+                .ok()?;
+
+                while let Some(event) = events_rx.next().await {
+                    let on_event = this.read_with(cx, |this, _| this.on_event.clone()).ok()?;
+                    cx.update(|cx| on_event(event, cx));
+                }
+                Some(())
+            }));
+        }
+
+        fn set_needs_attention(&mut self, needs_attention: bool, cx: &mut Context<Self>) {
+            if self.needs_attention != needs_attention {
+                self.needs_attention = needs_attention;
+                self.sync_status(cx);
+            }
+        }
+
+        fn sync_status(&self, cx: &mut Context<Self>) {
+            if let Some(connection) = self.connection.clone() {
+                cx.background_spawn(status_notifier_item::set_needs_attention(
+                    connection,
+                    self.needs_attention,
+                ))
+                .detach_and_log_err(cx);
+            }
+        }
+    }
+}
@@ -0,0 +1,928 @@
+//! Inline images sent by programs with the Sixel or iTerm2 (OSC 1337) image
+//! protocols.
+//!
+//! Alacritty's parser discards both, so they're taken out of the PTY output
+//! before it sees them. Each image is replaced with a space linking to
+//! `zed-image:<id>` through an OSC 8 hyperlink, followed by the line breaks
+//! making room for the image. The image is then drawn wherever that cell is,
+//! so it scrolls with the output, and stops being drawn once the cell is
+//! overwritten or leaves the scrollback.
+
+use std::{collections::VecDeque, io, mem, sync::Arc};
+
+use alacritty_terminal::{
+    event::{OnResize, WindowSize},
+    grid::{Dimensions as _, Grid},
+    index::{Column, Line, Point as AlacPoint},
+    term::cell::{Cell, Hyperlink},
+    tty::{ChildEvent, EventedPty, EventedReadWrite},
+};
+use base64::{
+    Engine as _, alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+};
+use gpui::{RenderImage, Rgba, Size, hsla, size};
+use image::{Frame, ImageReader, Limits, RgbaImage};
+use parking_lot::Mutex;
+use polling::{Event, PollMode, Poller};
+
+use crate::{TerminalBounds, shell_integration::OscScanner};
+
+const IMAGE_URI_PREFIX: &str = "zed-image:";
+
+const ESC: u8 = 0x1b;
+const BEL: u8 = 0x07;
+const CAN: u8 = 0x18;
+const SUB: u8 = 0x1a;
+
+const OSC_1337_FILE: &[u8] = b"1337;File=";
+
+/// Longest Sixel parameter list accepted before the `q` that starts the
+/// image; longer ones belong to other DCS sequences.
+const MAX_SIXEL_PARAMETERS_LEN: usize = 16;
+
+/// Longest encoded image kept, in bytes; longer ones are dropped.
+const MAX_SEQUENCE_LEN: usize = 64 * 1024 * 1024;
+
+/// Widest or tallest decoded image, in pixels.
+const MAX_IMAGE_SIDE: u32 = 8192;
+
+/// Most memory decoding one image may allocate, in bytes: the largest image at
+/// four bytes per pixel.
+const MAX_IMAGE_ALLOC: u64 = MAX_IMAGE_SIDE as u64 * MAX_IMAGE_SIDE as u64 * 4;
+
+/// How many images are kept for the scrollback. Older ones stop being drawn.
+const MAX_KEPT_IMAGES: usize = 256;
+
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Whether the hyperlink only marks where an image is drawn.
+pub(crate) fn is_image_anchor(link: &Hyperlink) -> bool {
+    link.uri().starts_with(IMAGE_URI_PREFIX)
+}
+
+/// Where an image is drawn in the terminal.
+#[derive(Clone)]
+pub struct ImagePlacement {
+    /// The cell the top left corner of the image is in.
+    pub point: AlacPoint,
+    /// The size the image is drawn at, in cells.
+    pub size: Size<f32>,
+    pub image: Arc<RenderImage>,
+}
+
+struct InlineImage {
+    id: u64,
+    size: Size<f32>,
+    image: Arc<RenderImage>,
+}
+
+/// The images of a terminal, shared by the thread reading its PTY and the
+/// terminal itself.
+#[derive(Default)]
+pub(crate) struct InlineImages {
+    enabled: bool,
+    max_lines: u32,
+    bounds: TerminalBounds,
+    images: VecDeque<InlineImage>,
+    evicted: Vec<Arc<RenderImage>>,
+    next_id: u64,
+}
+
+impl InlineImages {
+    pub(crate) fn new(enabled: bool, max_lines: u32) -> Self {
+        Self {
+            enabled,
+            max_lines,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn set_bounds(&mut self, bounds: TerminalBounds) {
+        self.bounds = bounds;
+    }
+
+    /// Takes the images that are no longer drawn, so that their textures can
+    /// be freed.
+    pub(crate) fn take_evicted(&mut self) -> Vec<Arc<RenderImage>> {
+        mem::take(&mut self.evicted)
+    }
+
+    /// Keeps the image, returning its id and how many lines it takes.
+    fn insert(&mut self, image: DecodedImage) -> Option<(u64, u32)> {
+        let cell_width = f32::from(self.bounds.cell_width);
+        let line_height = f32::from(self.bounds.line_height);
+        let columns = self.bounds.num_columns() as f32;
+        if cell_width <= 0. || line_height <= 0. || columns <= 0. || self.max_lines == 0 {
+            return None;
+        }
+
+        let natural_size = size(
+            image.width as f32 / cell_width,
+            image.height as f32 / line_height,
+        );
+        let shown_size = image.requested_size.resolve(
+            natural_size,
+            columns,
+            self.bounds.num_lines() as f32,
+            cell_width,
+            line_height,
+        );
+        if shown_size.width <= 0. || shown_size.height <= 0. {
+            return None;
+        }
+        let scale = (columns / shown_size.width)
+            .min(self.max_lines as f32 / shown_size.height)
+            .min(1.);
+        let shown_size = size(shown_size.width * scale, shown_size.height * scale);
+
+        let buffer = RgbaImage::from_raw(image.width, image.height, image.bgra)?;
+        let id = self.next_id;
+        self.next_id += 1;
+        if self.images.len() == MAX_KEPT_IMAGES
+            && let Some(evicted) = self.images.pop_front()
+        {
+            self.evicted.push(evicted.image);
+        }
+        self.images.push_back(InlineImage {
+            id,
+            size: shown_size,
+            image: Arc::new(RenderImage::new(vec![Frame::new(buffer)])),
+        });
+        Some((id, (shown_size.height.ceil() as u32).max(1)))
+    }
+
+    /// The images whose anchors are on screen, or far enough above it for
+    /// part of the image to still be visible.
+    pub(crate) fn placements(
+        &self,
+        grid: &Grid<Cell>,
+        display_offset: usize,
+    ) -> Vec<ImagePlacement> {
+        if self.images.is_empty() {
+            return Vec::new();
+        }
+
+        let top = -(display_offset as i32);
+        let bottom = top + grid.screen_lines() as i32 - 1;
+        let first = (top - self.max_lines as i32).max(grid.topmost_line().0);
+        let mut placements = Vec::new();
+        for line in first..=bottom {
+            let row = &grid[Line(line)];
+            for column in 0..grid.columns() {
+                let Some(link) = row[Column(column)].hyperlink() else {
+                    continue;
+                };
+                let Some(image) = link
+                    .uri()
+                    .strip_prefix(IMAGE_URI_PREFIX)
+                    .and_then(|id| id.parse::<u64>().ok())
+                    .and_then(|id| self.images.iter().find(|image| image.id == id))
+                else {
+                    continue;
+                };
+                if line as f32 + image.size.height <= top as f32 {
+                    continue;
+                }
+                placements.push(ImagePlacement {
+                    point: AlacPoint::new(Line(line), Column(column)),
+                    size: image.size,
+                    image: image.image.clone(),
+                });
+            }
+        }
+        placements
+    }
+}
+
+/// A PTY whose output has its inline images taken out, and the OSC sequences
+/// the terminal acts on turned into ones Alacritty reports.
+pub(crate) struct InlineImagePty<P> {
+    pty: P,
+    scanner: Option<ImageScanner>,
+    osc_scanner: OscScanner,
+    input: Vec<u8>,
+    scanned: Vec<u8>,
+    output: Vec<u8>,
+    output_start: usize,
+}
+
+impl<P> InlineImagePty<P> {
+    pub(crate) fn new(pty: P, images: Arc<Mutex<InlineImages>>, track_prompts: bool) -> Self {
+        let enabled = images.lock().enabled;
+        Self {
+            pty,
+            scanner: enabled.then(|| ImageScanner::new(images)),
+            osc_scanner: OscScanner::new(track_prompts),
+            input: vec![0; 64 * 1024],
+            scanned: Vec::new(),
+            output: Vec::new(),
+            output_start: 0,
+        }
+    }
+}
+
+impl<P: EventedReadWrite> io::Read for InlineImagePty<P> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Reading on while the sequence being taken out leaves nothing to
+        // return, as returning 0 would mean the PTY was closed.
+        while self.output_start == self.output.len() {
+            self.output.clear();
+            self.output_start = 0;
+            let read = self.pty.reader().read(&mut self.input)?;
+            if read == 0 {
+                return Ok(0);
+            }
+            let mut input = &self.input[..read];
+            if let Some(scanner) = &mut self.scanner {
+                self.scanned.clear();
+                scanner.scan(input, &mut self.scanned);
+                input = &self.scanned;
+            }
+            self.osc_scanner.scan(input, &mut self.output);
+        }
+        let len = buf.len().min(self.output.len() - self.output_start);
+        buf[..len].copy_from_slice(&self.output[self.output_start..][..len]);
+        self.output_start += len;
+        Ok(len)
+    }
+}
+
+impl<P: EventedReadWrite> EventedReadWrite for InlineImagePty<P> {
+    type Reader = Self;
+    type Writer = P::Writer;
+
+    unsafe fn register(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: Event,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        unsafe { self.pty.register(poll, interest, mode) }
+    }
+
+    fn reregister(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: Event,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        self.pty.reregister(poll, interest, mode)
+    }
+
+    fn deregister(&mut self, poll: &Arc<Poller>) -> io::Result<()> {
+        self.pty.deregister(poll)
+    }
+
+    fn reader(&mut self) -> &mut Self {
+        self
+    }
+
+    fn writer(&mut self) -> &mut P::Writer {
+        self.pty.writer()
+    }
+}
+
+impl<P: EventedPty> EventedPty for InlineImagePty<P> {
+    fn next_child_event(&mut self) -> Option<ChildEvent> {
+        self.pty.next_child_event()
+    }
+}
+
+impl<P: OnResize> OnResize for InlineImagePty<P> {
+    fn on_resize(&mut self, window_size: WindowSize) {
+        self.pty.on_resize(window_size);
+    }
+}
+
+enum ImageKind {
+    Sixel,
+    Iterm,
+}
+
+enum ScanState {
+    Ground,
+    Escape,
+    /// After `ESC P`, reading what could be the parameters of a Sixel image.
+    DcsParameters(Vec<u8>),
+    /// After `ESC ]`, reading what could be the start of an iTerm2 image.
+    OscPrefix(usize),
+    Image {
+        kind: ImageKind,
+        data: Vec<u8>,
+        /// Set once the image got too long, dropping the rest of it.
+        discarding: bool,
+        escape: bool,
+    },
+}
+
+/// Takes the images out of a stream of PTY output.
+struct ImageScanner {
+    state: ScanState,
+    images: Arc<Mutex<InlineImages>>,
+}
+
+impl ImageScanner {
+    fn new(images: Arc<Mutex<InlineImages>>) -> Self {
+        Self {
+            state: ScanState::Ground,
+            images,
+        }
+    }
+
+    fn scan(&mut self, mut input: &[u8], output: &mut Vec<u8>) {
+        while !input.is_empty() {
+            if let ScanState::Ground = self.state {
+                let end = input
+                    .iter()
+                    .position(|&byte| byte == ESC)
+                    .unwrap_or(input.len());
+                output.extend_from_slice(&input[..end]);
+                input = &input[end..];
+                if input.is_empty() {
+                    break;
+                }
+            }
+            self.advance(input[0], output);
+            input = &input[1..];
+        }
+    }
+
+    fn advance(&mut self, byte: u8, output: &mut Vec<u8>) {
+        match &mut self.state {
+            ScanState::Ground => {
+                if byte == ESC {
+                    self.state = ScanState::Escape;
+                } else {
+                    output.push(byte);
+                }
+            }
+            ScanState::Escape => match byte {
+                b'P' => self.state = ScanState::DcsParameters(Vec::new()),
+                b']' => self.state = ScanState::OscPrefix(0),
+                _ => {
+                    output.push(ESC);
+                    self.state = ScanState::Ground;
+                    self.advance(byte, output);
+                }
+            },
+            ScanState::DcsParameters(parameters) => {
+                if byte == b'q' {
+                    self.state = ScanState::Image {
+                        kind: ImageKind::Sixel,
+                        data: Vec::new(),
+                        discarding: false,
+                        escape: false,
+                    };
+                } else if (byte.is_ascii_digit() || byte == b';')
+                    && parameters.len() < MAX_SIXEL_PARAMETERS_LEN
+                {
+                    parameters.push(byte);
+                } else {
+                    output.extend_from_slice(&[ESC, b'P']);
+                    output.extend_from_slice(parameters);
+                    self.state = ScanState::Ground;
+                    self.advance(byte, output);
+                }
+            }
+            ScanState::OscPrefix(matched) => {
+                if OSC_1337_FILE.get(*matched) == Some(&byte) {
+                    *matched += 1;
+                    if *matched == OSC_1337_FILE.len() {
+                        self.state = ScanState::Image {
+                            kind: ImageKind::Iterm,
+                            data: Vec::new(),
+                            discarding: false,
+                            escape: false,
+                        };
+                    }
+                } else {
+                    output.extend_from_slice(&[ESC, b']']);
+                    output.extend_from_slice(&OSC_1337_FILE[..*matched]);
+                    self.state = ScanState::Ground;
+                    self.advance(byte, output);
+                }
+            }
+            ScanState::Image {
+                kind,
+                data,
+                discarding,
+                escape,
+            } => {
+                if mem::take(escape) {
+                    self.end_image(output);
+                    // Anything but the `\` of ST starts a new sequence, ending
+                    // the image early.
+                    if byte != b'\\' {
+                        self.advance(ESC, output);
+                        self.advance(byte, output);
+                    }
+                    return;
+                }
+                match byte {
+                    ESC => *escape = true,
+                    BEL if matches!(kind, ImageKind::Iterm) => self.end_image(output),
+                    CAN | SUB => self.state = ScanState::Ground,
+                    _ if *discarding => {}
+                    _ if data.len() == MAX_SEQUENCE_LEN => {
+                        *discarding = true;
+                        *data = Vec::new();
+                    }
+                    _ => data.push(byte),
+                }
+            }
+        }
+    }
+
+    fn end_image(&mut self, output: &mut Vec<u8>) {
+        if let ScanState::Image {
+            kind,
+            data,
+            discarding: false,
+            ..
+        } = mem::replace(&mut self.state, ScanState::Ground)
+        {
+            self.finish_image(kind, &data, output);
+        }
+    }
+
+    fn finish_image(&mut self, kind: ImageKind, data: &[u8], output: &mut Vec<u8>) {
+        let image = match kind {
+            ImageKind::Sixel => decode_sixel(data),
+            ImageKind::Iterm => decode_iterm_image(data),
+        };
+        let Some((id, lines)) = image.and_then(|image| self.images.lock().insert(image)) else {
+            return;
+        };
+        output.extend_from_slice(
+            format!("\x1b]8;;{IMAGE_URI_PREFIX}{id}\x1b\\ \x1b]8;;\x1b\\").as_bytes(),
+        );
+        for _ in 0..lines {
+            output.extend_from_slice(b"\r\n");
+        }
+    }
+}
+
+struct DecodedImage {
+    width: u32,
+    height: u32,
+    bgra: Vec<u8>,
+    requested_size: RequestedSize,
+}
+
+/// A dimension an iTerm2 image asks to be shown with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RequestedDimension {
+    Auto,
+    Cells(f32),
+    Pixels(f32),
+    Percent(f32),
+}
+
+impl RequestedDimension {
+    fn parse(value: &str) -> Option<Self> {
+        if value == "auto" {
+            Some(Self::Auto)
+        } else if let Some(pixels) = value.strip_suffix("px") {
+            pixels.parse().ok().map(Self::Pixels)
+        } else if let Some(percent) = value.strip_suffix('%') {
+            percent.parse().ok().map(Self::Percent)
+        } else {
+            value.parse().ok().map(Self::Cells)
+        }
+    }
+
+    fn in_cells(self, available_cells: f32, cell_size: f32) -> Option<f32> {
+        match self {
+            Self::Auto => None,
+            Self::Cells(cells) => Some(cells),
+            Self::Pixels(pixels) => Some(pixels / cell_size),
+            Self::Percent(percent) => Some(available_cells * percent / 100.),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct RequestedSize {
+    width: RequestedDimension,
+    height: RequestedDimension,
+    preserve_aspect_ratio: bool,
+}
+
+impl Default for RequestedSize {
+    fn default() -> Self {
+        Self {
+            width: RequestedDimension::Auto,
+            height: RequestedDimension::Auto,
+            preserve_aspect_ratio: true,
+        }
+    }
+}
+
+impl RequestedSize {
+    /// The size to show an image at, in cells.
+    fn resolve(
+        &self,
+        natural_size: Size<f32>,
+        columns: f32,
+        lines: f32,
+        cell_width: f32,
+        line_height: f32,
+    ) -> Size<f32> {
+        let width = self.width.in_cells(columns, cell_width);
+        let height = self.height.in_cells(lines, line_height);
+        match (width, height) {
+            (None, None) => natural_size,
+            (Some(width), None) if self.preserve_aspect_ratio => {
+                size(width, natural_size.height * width / natural_size.width)
+            }
+            (None, Some(height)) if self.preserve_aspect_ratio => {
+                size(natural_size.width * height / natural_size.height, height)
+            }
+            (Some(width), Some(height)) if self.preserve_aspect_ratio => {
+                let scale = (width / natural_size.width).min(height / natural_size.height);
+                size(natural_size.width * scale, natural_size.height * scale)
+            }
+            (width, height) => size(
+                width.unwrap_or(natural_size.width),
+                height.unwrap_or(natural_size.height),
+            ),
+        }
+    }
+}
+
+/// Decodes the `args:base64` part of `OSC 1337 ; File=args:base64`.
+fn decode_iterm_image(data: &[u8]) -> Option<DecodedImage> {
+    let separator = data.iter().position(|&byte| byte == b':')?;
+    let arguments = std::str::from_utf8(&data[..separator]).ok()?;
+    let mut inline = false;
+    let mut requested_size = RequestedSize::default();
+    for argument in arguments.split(';') {
+        let Some((key, value)) = argument.split_once('=') else {
+            continue;
+        };
+        match key {
+            "inline" => inline = value == "1",
+            "width" => requested_size.width = RequestedDimension::parse(value)?,
+            "height" => requested_size.height = RequestedDimension::parse(value)?,
+            "preserveAspectRatio" => requested_size.preserve_aspect_ratio = value != "0",
+            _ => {}
+        }
+    }
+    // Files that aren't inline are downloads, which aren't supported.
+    if !inline {
+        return None;
+    }
+
+    let encoded = data[separator + 1..]
+        .iter()
+        .copied()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect::<Vec<_>>();
+    let bytes = BASE64.decode(encoded).ok()?;
+    // The header is read before decoding so that an image claiming huge
+    // dimensions is dropped without allocating for them.
+    let reader = || {
+        ImageReader::new(io::Cursor::new(bytes.as_slice()))
+            .with_guessed_format()
+            .ok()
+    };
+    let (width, height) = reader()?.into_dimensions().ok()?;
+    if width > MAX_IMAGE_SIDE || height > MAX_IMAGE_SIDE {
+        return None;
+    }
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_SIDE);
+    limits.max_image_height = Some(MAX_IMAGE_SIDE);
+    limits.max_alloc = Some(MAX_IMAGE_ALLOC);
+    let mut reader = reader()?;
+    reader.limits(limits);
+    let mut image = reader.decode().ok()?.into_rgba8();
+    for pixel in image.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+    Some(DecodedImage {
+        width: image.width(),
+        height: image.height(),
+        bgra: image.into_raw(),
+        requested_size,
+    })
+}
+
+const TRANSPARENT: [u8; 4] = [0, 0, 0, 0];
+
+/// The VT340 colors, which images use unless they define their own.
+const DEFAULT_SIXEL_COLORS: [[u32; 3]; 16] = [
+    [0, 0, 0],
+    [20, 20, 80],
+    [80, 13, 13],
+    [20, 80, 20],
+    [80, 20, 80],
+    [20, 80, 80],
+    [80, 80, 20],
+    [53, 53, 53],
+    [26, 26, 26],
+    [33, 33, 60],
+    [60, 26, 26],
+    [33, 60, 33],
+    [60, 33, 60],
+    [33, 60, 60],
+    [60, 60, 33],
+    [80, 80, 80],
+];
+
+/// Decodes the data of `DCS parameters q data ST`. The parameters only matter
+/// for printers and for backgrounds, which are always left transparent.
+fn decode_sixel(data: &[u8]) -> Option<DecodedImage> {
+    let mut palette = [[0, 0, 0, 255]; 256];
+    for (register, [red, green, blue]) in DEFAULT_SIXEL_COLORS.iter().enumerate() {
+        palette[register] = bgra_from_percentages(*red, *green, *blue);
+    }
+    let mut color = palette[0];
+    let mut rows: Vec<Vec<[u8; 4]>> = Vec::new();
+    let (mut raster_width, mut raster_height) = (0, 0);
+    let (mut x, mut y) = (0usize, 0usize);
+    let max_side = MAX_IMAGE_SIDE as usize;
+
+    let mut index = 0;
+    while index < data.len() {
+        let byte = data[index];
+        index += 1;
+        let mut repeat = 1;
+        let sixel = match byte {
+            b'"' => {
+                let numbers = parse_sixel_numbers(data, &mut index);
+                if let [_, _, width, height, ..] = numbers[..] {
+                    raster_width = (width as usize).min(max_side);
+                    raster_height = (height as usize).min(max_side);
+                }
+                continue;
+            }
+            b'#' => {
+                let numbers = parse_sixel_numbers(data, &mut index);
+                let Some(&register) = numbers.first() else {
+                    continue;
+                };
+                let register = register as usize % palette.len();
+                match numbers[..] {
+                    [_, 1, hue, lightness, saturation, ..] => {
+                        palette[register] = bgra_from_hls(hue, lightness, saturation);
+                    }
+                    [_, 2, red, green, blue, ..] => {
+                        palette[register] = bgra_from_percentages(red, green, blue);
+                    }
+                    _ => {}
+                }
+                color = palette[register];
+                continue;
+            }
+            b'!' => {
+                repeat = parse_sixel_numbers(data, &mut index)
+                    .first()
+                    .copied()
+                    .unwrap_or(1)
+                    .max(1) as usize;
+                let Some(&sixel) = data.get(index) else {
+                    break;
+                };
+                index += 1;
+                sixel
+            }
+            b'$' => {
+                x = 0;
+                continue;
+            }
+            b'-' => {
+                x = 0;
+                y += 6;
+                continue;
+            }
+            _ => byte,
+        };
+        if !(0x3f..=0x7e).contains(&sixel) {
+            continue;
+        }
+
+        let end = (x + repeat).min(max_side);
+        let bits = sixel - 0x3f;
+        for bit in 0..6 {
+            let row_index = y + bit;
+            if bits & (1 << bit) == 0 || row_index >= max_side || x >= end {
+                continue;
+            }
+            if rows.len() <= row_index {
+                rows.resize_with(row_index + 1, Vec::new);
+            }
+            let row = &mut rows[row_index];
+            if row.len() < end {
+                row.resize(end, TRANSPARENT);
+            }
+            row[x..end].fill(color);
+        }
+        x += repeat;
+    }
+
+    let width = rows
+        .iter()
+        .map(|row| row.len())
+        .max()
+        .unwrap_or(0)
+        .max(raster_width);
+    let height = rows.len().max(raster_height);
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let mut bgra = Vec::with_capacity(width * height * 4);
+    for row_index in 0..height {
+        let row = rows.get(row_index).map_or(&[][..], |row| &row[..]);
+        for pixel in row {
+            bgra.extend_from_slice(pixel);
+        }
+        for _ in row.len()..width {
+            bgra.extend_from_slice(&TRANSPARENT);
+        }
+    }
+    Some(DecodedImage {
+        width: width as u32,
+        height: height as u32,
+        bgra,
+        requested_size: RequestedSize::default(),
+    })
+}
+
+/// Parses numbers separated by `;`, starting at `index` and moving it past
+/// them.
+fn parse_sixel_numbers(data: &[u8], index: &mut usize) -> Vec<u32> {
+    let mut numbers = Vec::new();
+    let mut current: Option<u32> = None;
+    while let Some(&byte) = data.get(*index) {
+        match byte {
+            b'0'..=b'9' => {
+                let digit = (byte - b'0') as u32;
+                current = Some(
+                    current
+                        .unwrap_or(0)
+                        .saturating_mul(10)
+                        .saturating_add(digit),
+                );
+            }
+            b';' => numbers.push(current.take().unwrap_or(0)),
+            _ => break,
+        }
+        *index += 1;
+    }
+    if let Some(current) = current {
+        numbers.push(current);
+    }
+    numbers
+}
+
+fn bgra_from_percentages(red: u32, green: u32, blue: u32) -> [u8; 4] {
+    let channel = |percentage: u32| (percentage.min(100) * 255 / 100) as u8;
+    [channel(blue), channel(green), channel(red), 255]
+}
+
+fn bgra_from_hls(hue: u32, lightness: u32, saturation: u32) -> [u8; 4] {
+    // Sixel hues start at blue rather than at red.
+    let hue = ((hue + 240) % 360) as f32 / 360.;
+    let rgba = Rgba::from(hsla(
+        hue,
+        saturation.min(100) as f32 / 100.,
+        lightness.min(100) as f32 / 100.,
+        1.,
+    ));
+    let channel = |value: f32| (value * 255.).round() as u8;
+    [channel(rgba.b), channel(rgba.g), channel(rgba.r), 255]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::px;
+    use std::io::Cursor;
+
+    fn scanner() -> (ImageScanner, Arc<Mutex<InlineImages>>) {
+        let mut images = InlineImages::new(true, 10);
+        images.set_bounds(TerminalBounds::new(
+            px(10.),
+            px(5.),
+            gpui::Bounds::new(gpui::Point::default(), size(px(100.), px(200.))),
+        ));
+        let images = Arc::new(Mutex::new(images));
+        (ImageScanner::new(images.clone()), images)
+    }
+
+    fn scan_in_chunks(scanner: &mut ImageScanner, input: &[u8], chunk_len: usize) -> Vec<u8> {
+        let mut output = Vec::new();
+        for chunk in input.chunks(chunk_len) {
+            scanner.scan(chunk, &mut output);
+        }
+        output
+    }
+
+    fn png_base64(width: u32, height: u32) -> String {
+        let image = RgbaImage::from_pixel(width, height, image::Rgba([255, 0, 0, 255]));
+        let mut png = Cursor::new(Vec::new());
+        image.write_to(&mut png, image::ImageFormat::Png).unwrap();
+        BASE64.encode(png.into_inner())
+    }
+
+    #[test]
+    fn test_other_sequences_pass_through() {
+        let input = b"plain \x1b[31mred\x1b[0m \x1b]0;title\x07 \x1b]8;;https://zed.dev\x1b\\link\x1b]8;;\x1b\\ \x1bP1$r\x1b\\ \x1b\x1b[m";
+        for chunk_len in [1, 3, input.len()] {
+            let (mut scanner, images) = scanner();
+            assert_eq!(scan_in_chunks(&mut scanner, input, chunk_len), input);
+            assert!(images.lock().images.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_sixel_image() {
+        // Two bands of 6 pixel rows, 4 pixels wide, in a defined red.
+        let input = b"before\x1bPq\"1;1;4;12#1;2;100;0;0#1!4~-!4~\x1b\\after";
+        for chunk_len in [1, 7, input.len()] {
+            let (mut scanner, images) = scanner();
+            let output = scan_in_chunks(&mut scanner, input, chunk_len);
+            let id = images.lock().images.back().unwrap().id;
+            assert_eq!(
+                String::from_utf8(output).unwrap(),
+                format!("before\x1b]8;;zed-image:{id}\x1b\\ \x1b]8;;\x1b\\\r\n\r\nafter")
+            );
+
+            let images = images.lock();
+            let image = images.images.back().unwrap();
+            assert_eq!(image.size, size(4. / 5., 12. / 10.));
+            let frame = &image.image;
+            assert_eq!(frame.size(0).width.0, 4);
+            assert_eq!(frame.size(0).height.0, 12);
+            assert_eq!(&frame.as_bytes(0).unwrap()[..4], &[0, 0, 255, 255]);
+        }
+    }
+
+    #[test]
+    fn test_iterm_image() {
+        let (mut scanner, images) = scanner();
+        let input = format!(
+            "\x1b]1337;File=name=cGxvdA==;inline=1;width=10;height=auto:{}\x07done",
+            png_base64(20, 40)
+        );
+        let output = scan_in_chunks(&mut scanner, input.as_bytes(), 5);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!(
+                "\x1b]8;;zed-image:0\x1b\\ \x1b]8;;\x1b\\{}done",
+                "\r\n".repeat(10)
+            )
+        );
+        // Being 10 columns wide keeps the aspect ratio of the 4x4 cell image.
+        assert_eq!(images.lock().images[0].size, size(10., 10.));
+
+        // Downloads aren't shown, and leave nothing behind.
+        let input = format!("\x1b]1337;File=name=cGxvdA==:{}\x1b\\", png_base64(2, 2));
+        assert!(scan_in_chunks(&mut scanner, input.as_bytes(), 5).is_empty());
+        assert_eq!(images.lock().images.len(), 1);
+    }
+
+    #[test]
+    fn test_oversized_iterm_image_is_dropped() {
+        let ppm = b"P6\n65536 65536\n255\n\xff\x00\x00";
+        let input = format!("inline=1:{}", BASE64.encode(ppm));
+        assert!(decode_iterm_image(input.as_bytes()).is_none());
+
+        let input = format!("inline=1:{}", png_base64(MAX_IMAGE_SIDE + 1, 1));
+        assert!(decode_iterm_image(input.as_bytes()).is_none());
+    }
+
+    #[test]
+    fn test_images_are_capped() {
+        let (mut scanner, images) = scanner();
+        // Shown at its natural size of 80x20 cells, the image would be wider
+        // than the terminal's 20 columns and taller than the cap of 10 lines.
+        let input = format!("\x1b]1337;File=inline=1:{}\x1b\\", png_base64(400, 200));
+        scan_in_chunks(&mut scanner, input.as_bytes(), 64);
+        assert_eq!(images.lock().images[0].size, size(20., 5.));
+    }
+
+    #[test]
+    fn test_requested_size() {
+        let natural_size = size(4., 2.);
+        let resolve = |width, height, preserve_aspect_ratio| {
+            RequestedSize {
+                width,
+                height,
+                preserve_aspect_ratio,
+            }
+            .resolve(natural_size, 80., 20., 8., 16.)
+        };
+        use RequestedDimension::*;
+        assert_eq!(resolve(Auto, Auto, true), size(4., 2.));
+        assert_eq!(resolve(Cells(8.), Auto, true), size(8., 4.));
+        assert_eq!(resolve(Auto, Pixels(64.), true), size(8., 4.));
+        assert_eq!(resolve(Percent(50.), Cells(2.), true), size(4., 2.));
+        assert_eq!(resolve(Percent(50.), Cells(2.), false), size(40., 2.));
+    }
+}
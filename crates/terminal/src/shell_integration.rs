@@ -0,0 +1,443 @@
+//! Hooks sourced into interactive bash, zsh and fish sessions so that the
+//! terminal learns where each prompt starts, how the previous command exited
+//! and what it was.
+//!
+//! The hooks mark prompts and exit codes with the `OSC 133` semantic prompt
+//! sequences, which shells and prompts without these hooks may emit as well.
+//! Alacritty doesn't surface those, so [`OscScanner`] turns them
+//! into title changes carrying [`TITLE_MARKER`] before Alacritty sees them.
+//! The text of each command is reported through such a title directly. Those
+//! titles are consumed by the terminal and never shown.
+
+use std::{
+    mem,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+
+pub(crate) const TITLE_MARKER: &str = "zed-shell-integration";
+
+const ESC: u8 = 0x1b;
+const BEL: u8 = 0x07;
+/// The longest sequence turned into a title, longer than any notification
+/// worth showing.
+const MAX_SEQUENCE_LEN: usize = 4096;
+
+const BASH_SCRIPT: &str = r#"if [ -f "$HOME/.bashrc" ]; then . "$HOME/.bashrc"; fi
+__zed_precmd() {
+    local exit_status=$?
+    local command
+    command=$(HISTTIMEFORMAT= builtin history 1 2>/dev/null)
+    command="${command#"${command%%[![:space:]]*}"}"
+    command="${command#*[[:space:]]}"
+    command="${command#"${command%%[![:space:]]*}"}"
+    builtin printf '\033]2;zed-shell-integration;command;%s\007' "${command//$'\n'/ }"
+    builtin printf '\033]133;D;%s\007' "$exit_status"
+    builtin printf '\033]133;A\007'
+    return $exit_status
+}
+PROMPT_COMMAND="__zed_precmd${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+"#;
+
+const ZSHENV_SCRIPT: &str = r#"if [[ -f "$ZED_USER_ZDOTDIR/.zshenv" ]]; then
+    . "$ZED_USER_ZDOTDIR/.zshenv"
+fi
+"#;
+
+const ZSHRC_SCRIPT: &str = r#"ZDOTDIR="$ZED_USER_ZDOTDIR"
+unset ZED_USER_ZDOTDIR
+if [[ -f "$ZDOTDIR/.zshrc" ]]; then
+    . "$ZDOTDIR/.zshrc"
+fi
+__zed_precmd() {
+    local exit_status=$?
+    builtin printf '\033]2;zed-shell-integration;command;%s\007' "${$(builtin fc -ln -1 2>/dev/null)//$'\n'/ }"
+    builtin printf '\033]133;D;%s\007' "$exit_status"
+    builtin printf '\033]133;A\007'
+}
+autoload -Uz add-zsh-hook
+add-zsh-hook precmd __zed_precmd
+"#;
+
+const FISH_SCRIPT: &str = r#"function __zed_postexec --on-event fish_postexec
+    set -l exit_status $status
+    printf '\033]2;zed-shell-integration;command;%s\007' (string join ' ' -- (string split \n -- $argv[1]))
+    printf '\033]133;D;%s\007' $exit_status
+end
+function __zed_prompt --on-event fish_prompt
+    printf '\033]133;A\007'
+end
+"#;
+
+/// What the shell hooks reported in a marked title.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ShellMark {
+    /// A prompt is about to be drawn at the cursor.
+    Prompt,
+    /// The command that ran before the upcoming prompt exited with this code.
+    /// Reported after the command's text.
+    Exit(i32),
+    /// The text of the command that ran before the upcoming prompt.
+    Command(String),
+    /// A program asked for a desktop notification.
+    Notification { title: String, body: String },
+}
+
+impl ShellMark {
+    /// Parses a title set by the shell hooks, returning `None` for titles
+    /// set by anything else.
+    pub(crate) fn parse(title: &str) -> Option<Self> {
+        let rest = title.strip_prefix(TITLE_MARKER)?.strip_prefix(';')?;
+        let (kind, payload) = rest.split_once(';').unwrap_or((rest, ""));
+        match kind {
+            "prompt" => Some(Self::Prompt),
+            "exit" => payload.trim().parse().ok().map(Self::Exit),
+            "command" => Some(Self::Command(payload.trim().to_string())),
+            "notify" => {
+                let (title, body) = payload.split_once(';').unwrap_or(("", payload));
+                Some(Self::Notification {
+                    title: title.trim().to_string(),
+                    body: body.trim().to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum SequenceKind {
+    SemanticPrompt,
+    /// iTerm2's `OSC 9 ; <body>`.
+    Notification,
+    /// rxvt's `OSC 777 ; notify ; <title> ; <body>`.
+    RxvtNotification,
+}
+
+enum ScanState {
+    Ground,
+    Escape,
+    /// The number of an OSC sequence, up to its `;`.
+    OscNumber(Vec<u8>),
+    Sequence {
+        kind: SequenceKind,
+        data: Vec<u8>,
+        escape: bool,
+    },
+}
+
+/// Turns the OSC sequences that Alacritty drops but the terminal acts on, the
+/// `OSC 133` semantic prompts and the `OSC 9` and `OSC 777` notifications, in
+/// a stream of PTY output into the marked titles that [`ShellMark::parse`]
+/// reads.
+pub(crate) struct OscScanner {
+    state: ScanState,
+    track_prompts: bool,
+}
+
+impl OscScanner {
+    pub(crate) fn new(track_prompts: bool) -> Self {
+        Self {
+            state: ScanState::Ground,
+            track_prompts,
+        }
+    }
+
+    pub(crate) fn scan(&mut self, mut input: &[u8], output: &mut Vec<u8>) {
+        while !input.is_empty() {
+            if let ScanState::Ground = self.state {
+                let end = input
+                    .iter()
+                    .position(|&byte| byte == ESC)
+                    .unwrap_or(input.len());
+                output.extend_from_slice(&input[..end]);
+                input = &input[end..];
+                if input.is_empty() {
+                    break;
+                }
+            }
+            self.advance(input[0], output);
+            input = &input[1..];
+        }
+    }
+
+    fn advance(&mut self, byte: u8, output: &mut Vec<u8>) {
+        match &mut self.state {
+            ScanState::Ground => {
+                if byte == ESC {
+                    self.state = ScanState::Escape;
+                } else {
+                    output.push(byte);
+                }
+            }
+            ScanState::Escape => {
+                if byte == b']' {
+                    self.state = ScanState::OscNumber(Vec::new());
+                } else {
+                    output.push(ESC);
+                    self.state = ScanState::Ground;
+                    self.advance(byte, output);
+                }
+            }
+            ScanState::OscNumber(number) => {
+                let kind = match (byte, number.as_slice()) {
+                    (b'0'..=b'9', _) if number.len() < 3 => {
+                        number.push(byte);
+                        return;
+                    }
+                    (b';', b"133") if self.track_prompts => Some(SequenceKind::SemanticPrompt),
+                    (b';', b"9") => Some(SequenceKind::Notification),
+                    (b';', b"777") => Some(SequenceKind::RxvtNotification),
+                    _ => None,
+                };
+                match kind {
+                    Some(kind) => {
+                        self.state = ScanState::Sequence {
+                            kind,
+                            data: Vec::new(),
+                            escape: false,
+                        };
+                    }
+                    None => {
+                        output.extend_from_slice(&[ESC, b']']);
+                        output.extend_from_slice(number);
+                        self.state = ScanState::Ground;
+                        self.advance(byte, output);
+                    }
+                }
+            }
+            ScanState::Sequence { kind, data, escape } => {
+                let kind = *kind;
+                if mem::take(escape) {
+                    let data = mem::take(data);
+                    self.state = ScanState::Ground;
+                    Self::finish(kind, &data, output);
+                    // Anything but the `\` of ST starts a new sequence.
+                    if byte != b'\\' {
+                        self.advance(ESC, output);
+                        self.advance(byte, output);
+                    }
+                    return;
+                }
+                match byte {
+                    ESC => *escape = true,
+                    BEL => {
+                        let data = mem::take(data);
+                        self.state = ScanState::Ground;
+                        Self::finish(kind, &data, output);
+                    }
+                    // Alacritty drops these sequences anyway, so the rest of
+                    // one that's too long is dropped as well.
+                    _ if data.len() == MAX_SEQUENCE_LEN => {}
+                    _ => data.push(byte),
+                }
+            }
+        }
+    }
+
+    /// Writes the marked title for a sequence, dropping the ones the terminal
+    /// doesn't act on.
+    fn finish(kind: SequenceKind, data: &[u8], output: &mut Vec<u8>) {
+        let Ok(data) = std::str::from_utf8(data) else {
+            return;
+        };
+        let mark = match kind {
+            SequenceKind::SemanticPrompt => {
+                let mut parts = data.split(';');
+                match parts.next() {
+                    Some("A") => "prompt".to_string(),
+                    Some("D") => match parts.next().and_then(|code| code.parse::<i32>().ok()) {
+                        Some(code) => format!("exit;{code}"),
+                        None => return,
+                    },
+                    _ => return,
+                }
+            }
+            // ConEmu uses `OSC 9` with a number for other purposes, like
+            // reporting progress.
+            SequenceKind::Notification
+                if data
+                    .split(';')
+                    .next()
+                    .is_some_and(|part| part.bytes().all(|byte| byte.is_ascii_digit())) =>
+            {
+                return;
+            }
+            SequenceKind::Notification => format!("notify;;{data}"),
+            SequenceKind::RxvtNotification => match data.strip_prefix("notify;") {
+                Some(notification) => format!("notify;{notification}"),
+                None => return,
+            },
+        };
+        output.extend_from_slice(format!("\x1b]2;{TITLE_MARKER};{mark}\x07").as_bytes());
+    }
+}
+
+/// A prompt drawn by a shell with the integration loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PromptMark {
+    /// The line of the prompt, counted from the top of the scrollback.
+    pub line: usize,
+    /// The exit code of the command entered at this prompt, once it finished.
+    pub exit_code: Option<i32>,
+}
+
+impl PromptMark {
+    pub fn failed(&self) -> bool {
+        self.exit_code.is_some_and(|code| code != 0)
+    }
+}
+
+/// Rewrites the program and arguments of an interactive shell so that it
+/// loads the hooks, adding the environment variables they rely on.
+///
+/// Returns `None` for shells without an integration, which are left as is.
+pub(crate) fn inject(
+    program: &str,
+    args: &[String],
+    env: &mut HashMap<String, String>,
+) -> Result<Option<Vec<String>>> {
+    let shell_name = Path::new(program)
+        .file_stem()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    match shell_name {
+        "bash" if args.is_empty() => {
+            let rcfile = write_script("bashrc", BASH_SCRIPT)?;
+            Ok(Some(vec![
+                "--rcfile".to_string(),
+                rcfile.to_string_lossy().into_owned(),
+            ]))
+        }
+        "zsh" if args.is_empty() => {
+            write_script("zsh/.zshenv", ZSHENV_SCRIPT)?;
+            write_script("zsh/.zshrc", ZSHRC_SCRIPT)?;
+            let zdotdir = paths::shell_integration_dir().join("zsh");
+            // The environment of a copied terminal already points at the hooks.
+            let user_zdotdir = [
+                env.get("ZED_USER_ZDOTDIR").cloned(),
+                env.get("ZDOTDIR").cloned(),
+                std::env::var("ZDOTDIR").ok(),
+                env.get("HOME").cloned(),
+                std::env::var("HOME").ok(),
+            ]
+            .into_iter()
+            .flatten()
+            .find(|dir| Path::new(dir) != zdotdir)
+            .unwrap_or_default();
+            env.insert("ZED_USER_ZDOTDIR".to_string(), user_zdotdir);
+            env.insert(
+                "ZDOTDIR".to_string(),
+                zdotdir.to_string_lossy().into_owned(),
+            );
+            Ok(Some(Vec::new()))
+        }
+        "fish" => {
+            let script = write_script("integration.fish", FISH_SCRIPT)?;
+            let mut args = args.to_vec();
+            args.push("--init-command".to_string());
+            args.push(format!("source '{}'", script.to_string_lossy()));
+            Ok(Some(args))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Writes a hook into a directory only the user can access, as every shell
+/// started in the terminal sources it.
+fn write_script(name: &str, contents: &str) -> Result<PathBuf> {
+    let dir = paths::shell_integration_dir();
+    let path = dir.join(name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("creating directory {parent:?}"))?;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt as _;
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+            .with_context(|| format!("restricting permissions of {dir:?}"))?;
+    }
+    if std::fs::read_to_string(&path).ok().as_deref() != Some(contents) {
+        std::fs::write(&path, contents).with_context(|| format!("writing {path:?}"))?;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shell_marks() {
+        assert_eq!(
+            ShellMark::parse("zed-shell-integration;prompt"),
+            Some(ShellMark::Prompt)
+        );
+        assert_eq!(
+            ShellMark::parse("zed-shell-integration;exit;127"),
+            Some(ShellMark::Exit(127))
+        );
+        assert_eq!(
+            ShellMark::parse("zed-shell-integration;command;echo a;b"),
+            Some(ShellMark::Command("echo a;b".to_string()))
+        );
+        assert_eq!(ShellMark::parse("zed-shell-integration;exit;"), None);
+        assert_eq!(ShellMark::parse("vim README.md"), None);
+    }
+
+    #[test]
+    fn test_scan_osc_sequences() {
+        let scan = |track_prompts: bool, input: &str| {
+            let mut scanner = OscScanner::new(track_prompts);
+            let mut output = Vec::new();
+            for byte in input.as_bytes() {
+                scanner.scan(&[*byte], &mut output);
+            }
+            String::from_utf8(output).unwrap()
+        };
+
+        assert_eq!(
+            scan(true, "a\x1b]133;A\x07b\x1b]133;D;2\x1b\\c"),
+            "a\x1b]2;zed-shell-integration;prompt\x07b\x1b]2;zed-shell-integration;exit;2\x07c"
+        );
+        assert_eq!(scan(true, "\x1b]133;B\x07\x1b]133;D\x07"), "");
+        assert_eq!(scan(false, "\x1b]133;A\x07"), "\x1b]133;A\x07");
+        assert_eq!(
+            scan(false, "\x1b]9;Build done; 3 warnings\x07"),
+            "\x1b]2;zed-shell-integration;notify;;Build done; 3 warnings\x07"
+        );
+        assert_eq!(scan(false, "\x1b]9;4;1;50\x07"), "");
+        assert_eq!(
+            scan(false, "\x1b]777;notify;Tests;All passed\x1b\\"),
+            "\x1b]2;zed-shell-integration;notify;Tests;All passed\x07"
+        );
+        assert_eq!(scan(false, "\x1b]777;other\x07"), "");
+        // Other sequences pass through unchanged.
+        for other in [
+            "\x1b]2;title\x07",
+            "\x1b]1337;SetMark\x07",
+            "\x1b]8;;https://zed.dev\x1b\\",
+            "\x1b[1m",
+        ] {
+            assert_eq!(scan(false, other), other);
+        }
+
+        assert_eq!(
+            ShellMark::parse("zed-shell-integration;notify;;Build done; 3 warnings"),
+            Some(ShellMark::Notification {
+                title: String::new(),
+                body: "Build done; 3 warnings".to_string(),
+            })
+        );
+        assert_eq!(
+            ShellMark::parse("zed-shell-integration;notify;Tests;All passed"),
+            Some(ShellMark::Notification {
+                title: "Tests".to_string(),
+                body: "All passed".to_string(),
+            })
+        );
+    }
+}
@@ -2,7 +2,9 @@ pub mod mappings;
 
 pub use alacritty_terminal;
 
+mod inline_images;
 mod pty_info;
+mod shell_integration;
 mod terminal_hyperlinks;
 pub mod terminal_settings;
 
@@ -41,9 +43,14 @@ use mappings::mouse::{
 
 use collections::{HashMap, VecDeque};
 use futures::StreamExt;
+pub use inline_images::ImagePlacement;
+use inline_images::{InlineImagePty, InlineImages};
+use parking_lot::Mutex;
 use pty_info::{ProcessIdGetter, PtyProcessInfo};
 use serde::{Deserialize, Serialize};
 use settings::Settings;
+pub use shell_integration::PromptMark;
+use shell_integration::ShellMark;
 use smol::channel::{Receiver, Sender};
 use task::{HideStrategy, Shell, SpawnInTerminal};
 use terminal_hyperlinks::RegexSearches;
@@ -69,7 +76,7 @@ use thiserror::Error;
 use gpui::{
     App, AppContext as _, BackgroundExecutor, Bounds, ClipboardItem, Context, EventEmitter, Hsla,
     Keystroke, Modifiers, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point,
-    Rgba, ScrollWheelEvent, Size, Task, TouchPhase, Window, actions, black, px,
+    RenderImage, Rgba, ScrollWheelEvent, Size, Task, TouchPhase, Window, actions, black, px,
 };
 
 use crate::mappings::{colors::to_alac_rgb, keys::to_esc_str};
@@ -107,6 +114,12 @@ actions!(
         ToggleViMode,
         /// Selects all text in the terminal.
         SelectAll,
+        /// Scrolls to the prompt above the top of the viewport.
+        ScrollToPreviousPrompt,
+        /// Scrolls to the prompt below the top of the viewport.
+        ScrollToNextPrompt,
+        /// Runs the last command entered at a prompt again.
+        RerunLastCommand,
     ]
 );
 
@@ -137,6 +150,14 @@ pub enum Event {
     Bell,
     Wakeup,
     BlinkChanged(bool),
+    /// A command run at a prompt of a shell with the integration loaded
+    /// exited with this code.
+    CommandFinished(i32),
+    /// A program asked for a desktop notification with `OSC 9` or `OSC 777`.
+    Notification {
+        title: String,
+        body: String,
+    },
     SelectionsChanged,
     NewNavigationTarget(Option<MaybeNavigationTarget>),
     Open(MaybeNavigationTarget),
@@ -388,6 +409,7 @@ impl TerminalBuilder {
             matches: Vec::new(),
 
             selection_head: None,
+            inline_images: Arc::default(),
             breadcrumb_text: String::new(),
             scroll_px: px(0.),
             next_link_id: 0,
@@ -401,6 +423,10 @@ impl TerminalBuilder {
             #[cfg(windows)]
             shell_program: None,
             activation_script: Vec::new(),
+            prompt_marks: Vec::new(),
+            newest_history_line: None,
+            last_command: None,
+            profile: None,
             template: CopyTemplate {
                 shell: Shell::System,
                 env: HashMap::default(),
@@ -442,6 +468,13 @@ impl TerminalBuilder {
     ) -> Task<Result<TerminalBuilder>> {
         let version = release_channel::AppVersion::global(cx);
         let background_executor = cx.background_executor().clone();
+        let terminal_settings = TerminalSettings::get_global(cx);
+        let load_shell_integration =
+            terminal_settings.shell_integration && task.is_none() && !is_remote_terminal;
+        let inline_images = Arc::new(Mutex::new(InlineImages::new(
+            terminal_settings.inline_images,
+            terminal_settings.max_inline_image_lines,
+        )));
         let fut = async move {
             // Remove SHLVL so the spawned shell initializes it to 1, matching
             // the behavior of standalone terminal emulators like iTerm2/Kitty/Alacritty.
@@ -480,7 +513,7 @@ impl TerminalBuilder {
                 }
             }
 
-            let shell_params = match shell.clone() {
+            let mut shell_params = match shell.clone() {
                 Shell::System => {
                     if cfg!(windows) {
                         Some(ShellParams::new(
@@ -499,6 +532,25 @@ impl TerminalBuilder {
                     title_override,
                 } => Some(ShellParams::new(program, Some(args), title_override)),
             };
+            if load_shell_integration {
+                let program = shell_params
+                    .as_ref()
+                    .map_or_else(util::shell::get_system_shell, |params| {
+                        params.program.clone()
+                    });
+                let args = shell_params
+                    .as_ref()
+                    .and_then(|params| params.args.clone())
+                    .unwrap_or_default();
+                match shell_integration::inject(&program, &args, &mut env) {
+                    Ok(Some(args)) => {
+                        let title_override = shell_params.and_then(|params| params.title_override);
+                        shell_params = Some(ShellParams::new(program, Some(args), title_override));
+                    }
+                    Ok(None) => {}
+                    Err(error) => log::error!("Failed to set up shell integration: {error:#}"),
+                }
+            }
             let terminal_title_override =
                 shell_params.as_ref().and_then(|e| e.title_override.clone());
 
@@ -590,7 +642,7 @@ impl TerminalBuilder {
             let event_loop = EventLoop::new(
                 term.clone(),
                 ZedListener(events_tx),
-                pty,
+                InlineImagePty::new(pty, inline_images.clone(), load_shell_integration),
                 pty_options.drain_on_exit,
                 false,
             )
@@ -616,6 +668,7 @@ impl TerminalBuilder {
                 matches: Vec::new(),
 
                 selection_head: None,
+                inline_images: inline_images.clone(),
                 breadcrumb_text: String::new(),
                 scroll_px: px(0.),
                 next_link_id: 0,
@@ -632,6 +685,10 @@ impl TerminalBuilder {
                 #[cfg(windows)]
                 shell_program,
                 activation_script: activation_script.clone(),
+                prompt_marks: Vec::new(),
+                newest_history_line: None,
+                last_command: None,
+                profile: None,
                 template: CopyTemplate {
                     shell,
                     env,
@@ -794,6 +851,7 @@ pub struct TerminalContent {
     pub last_hovered_word: Option<HoveredWord>,
     pub scrolled_to_top: bool,
     pub scrolled_to_bottom: bool,
+    pub images: Vec<ImagePlacement>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -820,6 +878,7 @@ impl Default for TerminalContent {
             last_hovered_word: None,
             scrolled_to_top: false,
             scrolled_to_bottom: false,
+            images: Vec::new(),
         }
     }
 }
@@ -849,6 +908,7 @@ pub struct Terminal {
     pub matches: Vec<RangeInclusive<AlacPoint>>,
     pub last_content: TerminalContent,
     pub selection_head: Option<AlacPoint>,
+    inline_images: Arc<Mutex<InlineImages>>,
 
     pub breadcrumb_text: String,
     title_override: Option<String>,
@@ -866,6 +926,13 @@ pub struct Terminal {
     shell_program: Option<String>,
     template: CopyTemplate,
     activation_script: Vec<String>,
+    prompt_marks: Vec<PromptMark>,
+    /// The size of the scrollback and its newest line, or the top line of the
+    /// screen while it's empty, when the prompt marks were last shifted, to
+    /// find how many lines it dropped since.
+    newest_history_line: Option<(usize, Line, Row<Cell>)>,
+    last_command: Option<String>,
+    profile: Option<String>,
     child_exited: Option<ExitStatus>,
     event_loop_task: Task<Result<(), anyhow::Error>>,
     background_executor: BackgroundExecutor,
@@ -922,6 +989,11 @@ impl Terminal {
     fn process_event(&mut self, event: AlacTermEvent, cx: &mut Context<Self>) {
         match event {
             AlacTermEvent::Title(title) => {
+                if let Some(mark) = ShellMark::parse(&title) {
+                    self.register_shell_mark(mark, cx);
+                    return;
+                }
+
                 // ignore default shell program title change as windows always sends those events
                 // and it would end up showing the shell executable path in breadcrumbs
                 #[cfg(windows)]
@@ -1017,6 +1089,7 @@ impl Terminal {
                 new_bounds.bounds.size.width = cmp::max(new_bounds.cell_width, new_bounds.width());
 
                 self.last_content.terminal_bounds = new_bounds;
+                self.inline_images.lock().set_bounds(new_bounds);
 
                 if let TerminalType::Pty { pty_tx, .. } = &self.terminal_type {
                     pty_tx.0.send(Msg::Resize(new_bounds.into())).ok();
@@ -1032,6 +1105,7 @@ impl Terminal {
             }
             InternalEvent::Clear => {
                 trace!("Clearing");
+                self.prompt_marks.clear();
                 // Clear back buffer
                 term.clear_screen(ClearMode::Saved);
 
@@ -1375,6 +1449,140 @@ impl Terminal {
         self.events.push_back(InternalEvent::Clear)
     }
 
+    fn register_shell_mark(&mut self, mark: ShellMark, cx: &mut Context<Self>) {
+        match mark {
+            ShellMark::Prompt => {
+                let term = self.term.clone();
+                let term = term.lock();
+                self.shift_prompt_marks(&term);
+                let line = term.history_size() + term.grid().cursor.point.line.0.max(0) as usize;
+                // Prompts at or below this one were erased, e.g. by `clear`.
+                self.prompt_marks.retain(|mark| mark.line < line);
+                self.prompt_marks.push(PromptMark {
+                    line,
+                    exit_code: None,
+                });
+                self.shift_prompt_marks(&term);
+            }
+            ShellMark::Exit(exit_code) => {
+                if let Some(mark) = self.prompt_marks.last_mut()
+                    && mark.exit_code.is_none()
+                {
+                    mark.exit_code = Some(exit_code);
+                    cx.emit(Event::CommandFinished(exit_code));
+                }
+            }
+            ShellMark::Command(command) => {
+                if !command.is_empty() {
+                    self.last_command = Some(command);
+                }
+            }
+            ShellMark::Notification { title, body } => {
+                cx.emit(Event::Notification { title, body });
+            }
+        }
+        cx.notify();
+    }
+
+    /// Prompt marks count lines from the top of the scrollback, so once it's
+    /// full and drops its oldest lines, the marks move up by as many lines.
+    fn shift_prompt_marks(&mut self, term: &Term<ZedListener>) {
+        if self.prompt_marks.is_empty() {
+            self.newest_history_line = None;
+            return;
+        }
+        let grid = term.grid();
+        let history_size = grid.history_size();
+        let newest_line = if history_size == 0 { Line(0) } else { Line(-1) };
+        let newest_row = &grid[newest_line];
+        if let Some((previous_history_size, previous_line, previous_row)) =
+            &self.newest_history_line
+        {
+            if *previous_history_size == history_size
+                && previous_line == &newest_line
+                && previous_row == newest_row
+            {
+                return;
+            }
+            if history_size >= self.term_config.scrolling_history {
+                // History lines don't change, so the previous newest line is
+                // as many lines up as were scrolled into the scrollback.
+                let scrolled = (0..=previous_line.0 + history_size as i32)
+                    .find(|&scrolled| &grid[Line(previous_line.0 - scrolled)] == previous_row)
+                    .map(|scrolled| scrolled as usize);
+                match scrolled {
+                    Some(scrolled) => {
+                        let grown = history_size.saturating_sub(*previous_history_size);
+                        let dropped = scrolled.saturating_sub(grown);
+                        self.prompt_marks.retain_mut(|mark| {
+                            mark.line = match mark.line.checked_sub(dropped) {
+                                Some(line) => line,
+                                None => return false,
+                            };
+                            true
+                        });
+                    }
+                    None => self.prompt_marks.clear(),
+                }
+            }
+        }
+        self.newest_history_line = Some((history_size, newest_line, newest_row.clone()));
+    }
+
+    /// The name of the terminal profile this terminal was opened with.
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    pub fn set_profile(&mut self, profile: Option<String>) {
+        self.profile = profile;
+    }
+
+    /// The prompts drawn by a shell with the integration loaded, from the
+    /// oldest to the most recent.
+    pub fn prompt_marks(&self) -> &[PromptMark] {
+        &self.prompt_marks
+    }
+
+    pub fn last_command(&self) -> Option<&str> {
+        self.last_command.as_deref()
+    }
+
+    pub fn rerun_last_command(&mut self) {
+        if let Some(command) = self.last_command.clone() {
+            self.input(format!("{command}\r").into_bytes());
+        }
+    }
+
+    pub fn scroll_to_previous_prompt(&mut self) {
+        self.scroll_to_prompt(true);
+    }
+
+    pub fn scroll_to_next_prompt(&mut self) {
+        self.scroll_to_prompt(false);
+    }
+
+    fn scroll_to_prompt(&mut self, previous: bool) {
+        let top_line = {
+            let term = self.term.lock();
+            term.history_size()
+                .saturating_sub(term.grid().display_offset())
+        };
+        let mark = if previous {
+            self.prompt_marks
+                .iter()
+                .rev()
+                .find(|mark| mark.line < top_line)
+        } else {
+            self.prompt_marks.iter().find(|mark| mark.line > top_line)
+        };
+        if let Some(mark) = mark {
+            let delta = top_line as i32 - mark.line as i32;
+            self.events
+                .push_back(InternalEvent::Scroll(AlacScroll::Delta(delta)));
+        }
+    }
+
     pub fn scroll_line_up(&mut self) {
         self.events
             .push_back(InternalEvent::Scroll(AlacScroll::Delta(1)));
@@ -1594,6 +1802,21 @@ impl Terminal {
         self.input(paste_text.into_bytes());
     }
 
+    /// Writes text from an editor, followed by the given number of line
+    /// breaks, as if it had been typed or pasted.
+    pub fn send_text(&mut self, text: &str, trailing_newlines: usize, bracketed_paste: bool) {
+        let text = text.replace("\r\n", "\n");
+        let text = text.trim_end_matches('\n');
+        let mut input =
+            if bracketed_paste && self.last_content.mode.contains(TermMode::BRACKETED_PASTE) {
+                format!("\x1b[200~{}\x1b[201~", text.replace('\x1b', ""))
+            } else {
+                text.replace('\n', "\r")
+            };
+        input.push_str(&"\r".repeat(trailing_newlines));
+        self.input(input.into_bytes());
+    }
+
     pub fn sync(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let term = self.term.clone();
         let mut terminal = term.lock_unfair();
@@ -1601,11 +1824,23 @@ impl Terminal {
         while let Some(e) = self.events.pop_front() {
             self.process_terminal_event(&e, &mut terminal, window, cx)
         }
+        self.shift_prompt_marks(&terminal);
 
-        self.last_content = Self::make_content(&terminal, &self.last_content);
+        self.last_content =
+            Self::make_content(&terminal, &self.last_content, &self.inline_images.lock());
     }
 
-    fn make_content(term: &Term<ZedListener>, last_content: &TerminalContent) -> TerminalContent {
+    /// Takes the images that are no longer shown, for their textures to be
+    /// freed.
+    pub fn take_evicted_images(&self) -> Vec<Arc<RenderImage>> {
+        self.inline_images.lock().take_evicted()
+    }
+
+    fn make_content(
+        term: &Term<ZedListener>,
+        last_content: &TerminalContent,
+        inline_images: &InlineImages,
+    ) -> TerminalContent {
         let content = term.renderable_content();
 
         // Pre-allocate with estimated size to reduce reallocations
@@ -1635,11 +1870,23 @@ impl Terminal {
             last_hovered_word: last_content.last_hovered_word.clone(),
             scrolled_to_top: content.display_offset == term.history_size(),
             scrolled_to_bottom: content.display_offset == 0,
+            images: inline_images.placements(term.grid(), content.display_offset),
         }
     }
 
     pub fn get_content(&self) -> String {
-        let term = self.term.lock_unfair();
+        Self::content_text(&self.term.lock_unfair())
+    }
+
+    /// Reads the text of the scrollback and the screen on a background thread,
+    /// as it can take a while for a long scrollback.
+    pub fn read_content_in_background(&self) -> Task<String> {
+        let term = self.term.clone();
+        self.background_executor
+            .spawn(async move { Self::content_text(&term.lock()) })
+    }
+
+    fn content_text(term: &Term<ZedListener>) -> String {
         let start = AlacPoint::new(term.topmost_line(), Column(0));
         let end = AlacPoint::new(term.bottommost_line(), term.last_column());
         term.bounds_to_string(start, end)
@@ -1923,9 +2170,8 @@ impl Terminal {
                 }
                 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
                 MouseButton::Middle => {
-                    if let Some(item) = _cx.read_from_primary() {
-                        let text = item.text().unwrap_or_default();
-                        self.input(text.into_bytes());
+                    if let Some(text) = _cx.read_from_primary().and_then(|item| item.text()) {
+                        self.paste(&text);
                     }
                 }
                 _ => {}
@@ -1984,7 +2230,10 @@ impl Terminal {
             if self.selection_phase == SelectionPhase::Ended {
                 let mouse_cell_index =
                     content_index_for_mouse(position, &self.last_content.terminal_bounds);
-                if let Some(link) = self.last_content.cells[mouse_cell_index].hyperlink() {
+                if let Some(link) = self.last_content.cells[mouse_cell_index]
+                    .hyperlink()
+                    .filter(|link| !inline_images::is_image_anchor(link))
+                {
                     cx.open_url(link.uri());
                 } else if e.modifiers.secondary() {
                     self.events
@@ -2617,7 +2866,11 @@ mod tests {
 
         terminal.update(cx, |terminal, _cx| {
             let term_lock = terminal.term.lock();
-            terminal.last_content = Terminal::make_content(&term_lock, &terminal.last_content);
+            terminal.last_content = Terminal::make_content(
+                &term_lock,
+                &terminal.last_content,
+                &terminal.inline_images.lock(),
+            );
             drop(term_lock);
 
             let terminal_bounds = TerminalBounds::new(
@@ -3004,7 +3257,11 @@ mod tests {
         // Get the content by directly accessing the term
         let content = terminal.update(cx, |terminal, _cx| {
             let term = terminal.term.lock_unfair();
-            Terminal::make_content(&term, &terminal.last_content)
+            Terminal::make_content(
+                &term,
+                &terminal.last_content,
+                &terminal.inline_images.lock(),
+            )
         });
 
         // If LF is properly converted to CRLF, each line should start at column 0
@@ -3052,7 +3309,11 @@ mod tests {
         // Get the content by directly accessing the term
         let content = terminal.update(cx, |terminal, _cx| {
             let term = terminal.term.lock_unfair();
-            Terminal::make_content(&term, &terminal.last_content)
+            Terminal::make_content(
+                &term,
+                &terminal.last_content,
+                &terminal.inline_images.lock(),
+            )
         });
 
         let cells = &content.cells;
@@ -3071,6 +3332,170 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_semantic_prompt_navigation(cx: &mut TestAppContext) {
+        let terminal = cx.new(|cx| {
+            TerminalBuilder::new_display_only(
+                CursorShape::default(),
+                AlternateScroll::On,
+                Some(10),
+                0,
+                cx.background_executor(),
+                PathStyle::local(),
+            )
+            .unwrap()
+            .subscribe(cx)
+        });
+        let mut scanner = shell_integration::OscScanner::new(true);
+        let mut feed = |output: &str, cx: &mut TestAppContext| {
+            let mut scanned = Vec::new();
+            scanner.scan(output.as_bytes(), &mut scanned);
+            terminal.update(cx, |terminal, cx| {
+                terminal.write_output(&scanned, cx);
+                let term = terminal.term.clone();
+                terminal.shift_prompt_marks(&term.lock());
+            });
+            cx.run_until_parked();
+        };
+        let scroll = |terminal: &mut Terminal| {
+            let mut term = terminal.term.lock();
+            while let Some(event) = terminal.events.pop_front() {
+                if let InternalEvent::Scroll(scroll) = event {
+                    term.scroll_display(scroll);
+                }
+            }
+            term.grid().display_offset()
+        };
+        let line_text = |terminal: &Terminal, line: usize| {
+            let term = terminal.term.lock();
+            let grid = term.grid();
+            let row = &grid[Line(line as i32 - grid.history_size() as i32)];
+            (0..grid.columns())
+                .map(|column| row[Column(column)].c)
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        };
+
+        feed("\x1b]133;A\x07", cx);
+        feed("$ one\nout\n\x1b]133;D;0\x07\x1b]133;A\x07", cx);
+        // The string terminator of a sequence can come in the next read.
+        feed("$ two\nerr\n\x1b]133;D;1\x1b", cx);
+        feed("\\\x1b]133;A;click_events=1\x1b\\", cx);
+        feed("$ three\n", cx);
+        terminal.read_with(cx, |terminal, _| {
+            assert_eq!(
+                terminal.prompt_marks(),
+                [
+                    PromptMark {
+                        line: 0,
+                        exit_code: Some(0),
+                    },
+                    PromptMark {
+                        line: 2,
+                        exit_code: Some(1),
+                    },
+                    PromptMark {
+                        line: 4,
+                        exit_code: None,
+                    },
+                ]
+            );
+            assert!(terminal.prompt_marks()[1].failed());
+            assert_eq!(line_text(terminal, 2), "$ two");
+        });
+
+        let screen_lines = terminal.read_with(cx, |terminal, _| terminal.viewport_lines());
+        for filler in 0..screen_lines {
+            feed(&format!("filler {filler}\n"), cx);
+        }
+        terminal.update(cx, |terminal, _| {
+            assert_eq!(terminal.term.lock().history_size(), 6);
+            terminal.scroll_to_previous_prompt();
+            assert_eq!(scroll(terminal), 2);
+            terminal.scroll_to_previous_prompt();
+            assert_eq!(scroll(terminal), 4);
+            terminal.scroll_to_next_prompt();
+            assert_eq!(scroll(terminal), 2);
+        });
+
+        // Once the scrollback is full, the marks move up with the lines it
+        // drops, and the marks of dropped lines go away.
+        feed(
+            &(0..6)
+                .map(|filler| format!("more {filler}\n"))
+                .collect::<String>(),
+            cx,
+        );
+        terminal.read_with(cx, |terminal, _| {
+            assert_eq!(terminal.term.lock().history_size(), 10);
+            let lines = terminal
+                .prompt_marks()
+                .iter()
+                .map(|mark| mark.line)
+                .collect::<Vec<_>>();
+            assert_eq!(lines, [0, 2]);
+            assert_eq!(line_text(terminal, 0), "$ two");
+            assert_eq!(line_text(terminal, 2), "$ three");
+        });
+    }
+
+    #[gpui::test]
+    async fn test_notification_sequences(cx: &mut TestAppContext) {
+        let terminal = cx.new(|cx| {
+            TerminalBuilder::new_display_only(
+                CursorShape::default(),
+                AlternateScroll::On,
+                None,
+                0,
+                cx.background_executor(),
+                PathStyle::local(),
+            )
+            .unwrap()
+            .subscribe(cx)
+        });
+        let (event_tx, event_rx) = smol::channel::unbounded::<Event>();
+        cx.update(|cx| {
+            cx.subscribe(&terminal, move |_, event, _| {
+                if let Event::Notification { .. } = event {
+                    event_tx.send_blocking(event.clone()).unwrap();
+                }
+            })
+        })
+        .detach();
+
+        let mut scanned = Vec::new();
+        shell_integration::OscScanner::new(false).scan(
+            b"\x1b]9;Build finished\x07\x1b]777;notify;Tests;2 failed\x1b\\",
+            &mut scanned,
+        );
+        terminal.update(cx, |terminal, cx| terminal.write_output(&scanned, cx));
+        cx.run_until_parked();
+
+        assert_eq!(
+            event_rx.try_recv().unwrap(),
+            Event::Notification {
+                title: String::new(),
+                body: "Build finished".to_string(),
+            }
+        );
+        assert_eq!(
+            event_rx.try_recv().unwrap(),
+            Event::Notification {
+                title: "Tests".to_string(),
+                body: "2 failed".to_string(),
+            }
+        );
+        // The notifications don't show up as the terminal's title.
+        terminal.read_with(cx, |terminal, _| {
+            assert!(
+                !terminal
+                    .breadcrumb_text
+                    .contains(shell_integration::TITLE_MARKER)
+            );
+        });
+    }
+
     #[gpui::test]
     async fn test_write_output_preserves_bare_cr(cx: &mut TestAppContext) {
         let terminal = cx.new(|cx| {
@@ -3094,7 +3519,11 @@ mod tests {
         // Get the content by directly accessing the term
         let content = terminal.update(cx, |terminal, _cx| {
             let term = terminal.term.lock_unfair();
-            Terminal::make_content(&term, &terminal.last_content)
+            Terminal::make_content(
+                &term,
+                &terminal.last_content,
+                &terminal.inline_images.lock(),
+            )
         });
 
         let cells = &content.cells;
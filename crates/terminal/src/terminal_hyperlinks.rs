@@ -18,6 +18,8 @@ use std::{
 use url::Url;
 use util::paths::{PathStyle, UrlExt};
 
+use crate::inline_images::is_image_anchor;
+
 const URL_REGEX: &str = r#"(ipfs:|ipns:|magnet:|mailto:|gemini://|gopher://|https://|http://|news:|file://|git://|ssh:|ftp://)[^\u{0000}-\u{001F}\u{007F}-\u{009F}<>"\s{-}\^⟨⟩`']+"#;
 const WIDE_CHAR_SPACERS: Flags =
     Flags::from_bits(Flags::LEADING_WIDE_CHAR_SPACER.bits() | Flags::WIDE_CHAR_SPACER.bits())
@@ -74,7 +76,10 @@ pub(super) fn find_from_grid_point<T: EventListener>(
     path_style: PathStyle,
 ) -> Option<(String, bool, Match)> {
     let grid = term.grid();
-    let link = grid.index(point).hyperlink();
+    let link = grid
+        .index(point)
+        .hyperlink()
+        .filter(|link| !is_image_anchor(link));
     let found_word = if let Some(ref url) = link {
         let mut min_index = point;
         loop {
@@ -658,6 +663,10 @@ mod tests {
             test_path!("    ‹File \"«/awe👉some.py»\", line «42»›");
             test_path!("    ‹File \"«/awesome.py»👉\", line «42»›: Wat?");
             test_path!("    ‹File \"«/awesome.py»\", line «4👉2»›");
+
+            // Shell scripts
+            test_path!("‹«./bu👉ild.sh»: line «12»›: cool: command not found");
+            test_path!("‹«./build.sh»: line «1👉2»›: cool: command not found");
         }
 
         #[test]
@@ -9,10 +9,11 @@ use serde::{Deserialize, Serialize};
 pub use settings::AlternateScroll;
 
 use settings::{
-    IntoGpui, PathHyperlinkRegex, RegisterSetting, ShowScrollbar, TerminalBlink,
+    IntoGpui, PathHyperlinkRegex, RegisterSetting, ShowScrollbar, TerminalBell, TerminalBlink,
     TerminalDockPosition, TerminalLineHeight, VenvSettings, WorkingDirectory,
     merge_from::MergeFrom,
 };
+use std::collections::BTreeMap;
 use task::Shell;
 use theme::FontFamilyName;
 
@@ -34,22 +35,80 @@ pub struct TerminalSettings {
     pub env: HashMap<String, String>,
     pub cursor_shape: CursorShape,
     pub blinking: TerminalBlink,
+    pub bell: TerminalBell,
     pub alternate_scroll: AlternateScroll,
     pub option_as_meta: bool,
     pub copy_on_select: bool,
     pub keep_selection_on_copy: bool,
+    pub shell_integration: bool,
+    pub send_to_terminal: SendToTerminalSettings,
+    pub notify_on_command_finish: bool,
     pub button: bool,
     pub dock: TerminalDockPosition,
     pub default_width: Pixels,
     pub default_height: Pixels,
     pub detect_venv: VenvSettings,
     pub max_scroll_history_lines: Option<usize>,
+    pub persist_scrollback: bool,
+    pub inline_images: bool,
+    pub max_inline_image_lines: u32,
     pub scroll_multiplier: f32,
     pub toolbar: Toolbar,
     pub scrollbar: ScrollbarSettings,
     pub minimum_contrast: f32,
     pub path_hyperlink_regexes: Vec<String>,
     pub path_hyperlink_timeout_ms: u64,
+    /// The named profiles, with the settings they leave out already filled
+    /// in from the terminal settings.
+    pub profiles: BTreeMap<String, TerminalProfile>,
+    pub default_profile: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct TerminalProfile {
+    pub shell: Shell,
+    pub env: HashMap<String, String>,
+    pub working_directory: WorkingDirectory,
+    pub icon: Option<String>,
+}
+
+impl TerminalSettings {
+    /// The profile with the given name, or the default profile when no name
+    /// is given.
+    pub fn profile(&self, name: Option<&str>) -> Option<(&str, &TerminalProfile)> {
+        let name = name.or(self.default_profile.as_deref())?;
+        self.profiles
+            .get_key_value(name)
+            .map(|(name, profile)| (name.as_str(), profile))
+    }
+
+    /// These settings with the shell, environment and working directory of
+    /// the given profile, or of the default profile when no name is given.
+    pub fn with_profile(mut self, name: Option<&str>) -> Self {
+        if let Some((_, profile)) = self.profile(name) {
+            let profile = profile.clone();
+            self.shell = profile.shell;
+            self.env = profile.env;
+            self.working_directory = profile.working_directory;
+        }
+        self
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct SendToTerminalSettings {
+    pub bracketed_paste: bool,
+    pub trailing_newlines: HashMap<String, usize>,
+}
+
+impl SendToTerminalSettings {
+    /// How many line breaks to send after text from the given language.
+    pub fn trailing_newlines(&self, language_name: Option<&str>) -> usize {
+        language_name
+            .and_then(|language_name| self.trailing_newlines.get(language_name))
+            .copied()
+            .unwrap_or(1)
+    }
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
@@ -82,6 +141,27 @@ impl settings::Settings for TerminalSettings {
         // Note: we allow a subset of "terminal" settings in the project files.
         let mut project_content = user_content.project.clone();
         project_content.merge_from_option(content.project.terminal.as_ref());
+        let profiles = project_content
+            .profiles
+            .unwrap()
+            .into_iter()
+            .map(|(name, profile)| {
+                let mut env = project_content.env.clone().unwrap();
+                env.extend(profile.env.unwrap_or_default());
+                let profile = TerminalProfile {
+                    shell: profile.shell.map_or_else(
+                        || settings_shell_to_task_shell(project_content.shell.clone().unwrap()),
+                        settings_shell_to_task_shell,
+                    ),
+                    env,
+                    working_directory: profile
+                        .working_directory
+                        .unwrap_or_else(|| project_content.working_directory.clone().unwrap()),
+                    icon: profile.icon,
+                };
+                (name, profile)
+            })
+            .collect();
         TerminalSettings {
             shell: settings_shell_to_task_shell(project_content.shell.unwrap()),
             working_directory: project_content.working_directory.unwrap(),
@@ -101,10 +181,20 @@ impl settings::Settings for TerminalSettings {
             env: project_content.env.unwrap(),
             cursor_shape: user_content.cursor_shape.unwrap().into(),
             blinking: user_content.blinking.unwrap(),
+            bell: user_content.bell.unwrap(),
             alternate_scroll: user_content.alternate_scroll.unwrap(),
             option_as_meta: user_content.option_as_meta.unwrap(),
             copy_on_select: user_content.copy_on_select.unwrap(),
             keep_selection_on_copy: user_content.keep_selection_on_copy.unwrap(),
+            shell_integration: user_content.shell_integration.unwrap(),
+            send_to_terminal: {
+                let send_to_terminal = user_content.send_to_terminal.unwrap();
+                SendToTerminalSettings {
+                    bracketed_paste: send_to_terminal.bracketed_paste.unwrap(),
+                    trailing_newlines: send_to_terminal.trailing_newlines.unwrap(),
+                }
+            },
+            notify_on_command_finish: user_content.notify_on_command_finish.unwrap(),
             button: user_content.button.unwrap(),
             dock: user_content.dock.unwrap(),
             default_width: px(user_content.default_width.unwrap()),
@@ -112,6 +202,9 @@ impl settings::Settings for TerminalSettings {
             detect_venv: project_content.detect_venv.unwrap(),
             scroll_multiplier: user_content.scroll_multiplier.unwrap(),
             max_scroll_history_lines: user_content.max_scroll_history_lines,
+            persist_scrollback: user_content.persist_scrollback.unwrap(),
+            inline_images: user_content.inline_images.unwrap(),
+            max_inline_image_lines: user_content.max_inline_image_lines.unwrap(),
             toolbar: Toolbar {
                 breadcrumbs: user_content.toolbar.unwrap().breadcrumbs.unwrap(),
             },
@@ -129,6 +222,8 @@ impl settings::Settings for TerminalSettings {
                 })
                 .collect(),
             path_hyperlink_timeout_ms: project_content.path_hyperlink_timeout_ms.unwrap(),
+            profiles,
+            default_profile: project_content.default_profile,
         }
     }
 }
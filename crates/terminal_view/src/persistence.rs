@@ -422,6 +422,9 @@ impl Domain for TerminalDb {
         sql! (
             ALTER TABLE terminals ADD COLUMN custom_title TEXT;
         ),
+        sql! (
+            ALTER TABLE terminals ADD COLUMN scrollback TEXT;
+        ),
     ];
 }
 
@@ -513,4 +516,79 @@ impl TerminalDb {
             WHERE item_id = ? AND workspace_id = ?
         }
     }
+
+    pub async fn save_scrollback(
+        &self,
+        item_id: ItemId,
+        workspace_id: WorkspaceId,
+        scrollback: Option<String>,
+    ) -> Result<()> {
+        log::debug!("Saving scrollback for item {item_id} in workspace {workspace_id:?}");
+        self.write(move |conn| {
+            let query = "INSERT INTO terminals (item_id, workspace_id, scrollback)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT (workspace_id, item_id) DO UPDATE SET
+                    scrollback = excluded.scrollback";
+            let mut statement = Statement::prepare(conn, query)?;
+            let mut next_index = statement.bind(&item_id, 1)?;
+            next_index = statement.bind(&workspace_id, next_index)?;
+            statement.bind(&scrollback, next_index)?;
+            statement.exec()
+        })
+        .await
+    }
+
+    query! {
+        pub fn get_scrollback(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Option<String>> {
+            SELECT scrollback
+            FROM terminals
+            WHERE item_id = ? AND workspace_id = ?
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context as _;
+
+    use super::*;
+
+    #[gpui::test]
+    async fn test_scrollback_round_trip() {
+        let db = TerminalDb::open_test_db("test_scrollback_round_trip").await;
+        let workspace_id = db
+            .write(|conn| {
+                conn.select_row::<WorkspaceId>(sql!(
+                    INSERT INTO workspaces DEFAULT VALUES RETURNING workspace_id
+                ))?()?
+                .context("no workspace id")
+            })
+            .await
+            .unwrap();
+        let item_id = 1;
+        assert_eq!(db.get_scrollback(item_id, workspace_id).unwrap(), None);
+
+        let scrollback = "$ cargo build\n   Compiling zed\n\nerror: ✗ failed".to_string();
+        db.save_scrollback(item_id, workspace_id, Some(scrollback.clone()))
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get_scrollback(item_id, workspace_id).unwrap(),
+            Some(scrollback.clone())
+        );
+
+        // Saving the terminal's other state keeps its scrollback.
+        db.save_custom_title(item_id, workspace_id, Some("Build".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get_scrollback(item_id, workspace_id).unwrap(),
+            Some(scrollback)
+        );
+
+        db.save_scrollback(item_id, workspace_id, None)
+            .await
+            .unwrap();
+        assert_eq!(db.get_scrollback(item_id, workspace_id).unwrap(), None);
+    }
 }
@@ -1,19 +1,21 @@
-use editor::{CursorLayout, EditorSettings, HighlightedRange, HighlightedRangeLine};
+use editor::{
+    CaretAnimation, CursorLayout, EditorSettings, HighlightedRange, HighlightedRangeLine,
+};
 use gpui::{
-    AbsoluteLength, AnyElement, App, AvailableSpace, Bounds, ContentMask, Context, DispatchPhase,
-    Element, ElementId, Entity, FocusHandle, Font, FontFeatures, FontStyle, FontWeight,
-    GlobalElementId, HighlightStyle, Hitbox, Hsla, InputHandler, InteractiveElement, Interactivity,
-    IntoElement, LayoutId, Length, ModifiersChangedEvent, MouseButton, MouseMoveEvent, Pixels,
-    Point, StatefulInteractiveElement, StrikethroughStyle, Styled, TextRun, TextStyle,
-    UTF16Selection, UnderlineStyle, WeakEntity, WhiteSpace, Window, div, fill, point, px, relative,
-    size,
+    AbsoluteLength, AnyElement, App, AvailableSpace, Bounds, ContentMask, Context, Corners,
+    DispatchPhase, Element, ElementId, Entity, FocusHandle, Font, FontFeatures, FontStyle,
+    FontWeight, GlobalElementId, HighlightStyle, Hitbox, Hsla, InputHandler, InteractiveElement,
+    Interactivity, IntoElement, LayoutId, Length, ModifiersChangedEvent, MouseButton,
+    MouseMoveEvent, Pixels, Point, StatefulInteractiveElement, StrikethroughStyle, Styled, TextRun,
+    TextStyle, UTF16Selection, UnderlineStyle, WeakEntity, WhiteSpace, Window, div, fill, point,
+    px, relative, size,
 };
 use itertools::Itertools;
 use language::CursorShape;
 use settings::Settings;
 use std::time::Instant;
 use terminal::{
-    IndexedCell, Terminal, TerminalBounds, TerminalContent,
+    ImagePlacement, IndexedCell, Terminal, TerminalBounds, TerminalContent,
     alacritty_terminal::{
         grid::Dimensions,
         index::Point as AlacPoint,
@@ -42,6 +44,7 @@ pub struct LayoutState {
     batched_text_runs: Vec<BatchedTextRun>,
     rects: Vec<LayoutRect>,
     relative_highlighted_ranges: Vec<(RangeInclusive<AlacPoint>, Hsla)>,
+    images: Vec<ImagePlacement>,
     cursor: Option<CursorLayout>,
     ime_cursor_bounds: Option<Bounds<Pixels>>,
     background_color: Hsla,
@@ -721,7 +724,12 @@ impl TerminalElement {
                 focus.clone(),
                 true,
                 move |terminal, e, cx| {
-                    terminal.mouse_down(e, cx);
+                    // Programs that capture the mouse still receive middle clicks.
+                    if terminal.mouse_mode(e.modifiers.shift)
+                        || EditorSettings::get_global(cx).middle_click_paste
+                    {
+                        terminal.mouse_down(e, cx);
+                    }
                 },
             ),
         );
@@ -1030,10 +1038,12 @@ impl Element for TerminalElement {
                     cursor_char,
                     selection,
                     cursor,
+                    images,
                     ..
                 } = &self.terminal.read(cx).last_content;
                 let mode = *mode;
                 let display_offset = *display_offset;
+                let images = images.clone();
 
                 // searches, highlights to a single range representations
                 let mut relative_highlighted_ranges = Vec::new();
@@ -1149,11 +1159,49 @@ impl Element for TerminalElement {
                         size: size(cursor_width.ceil(), dimensions.line_height),
                     });
 
+                let smooth_caret = EditorSettings::get_global(cx).smooth_caret;
+                let cursor_bounds = if smooth_caret {
+                    ime_cursor_bounds.map(|bounds| {
+                        // Glide in scrollback coordinates, so that scrolling
+                        // moves the cursor along without animating it.
+                        let scroll_offset =
+                            display_offset as f64 * f64::from(dimensions.line_height);
+                        let target = point(
+                            f64::from(bounds.origin.x),
+                            f64::from(bounds.origin.y) - scroll_offset,
+                        );
+                        let (position, animating) =
+                            self.terminal_view.update(cx, |terminal_view, _| {
+                                CaretAnimation::advance(
+                                    &mut terminal_view.caret_animation,
+                                    target,
+                                    f64::from(dimensions.height()),
+                                    Instant::now(),
+                                )
+                            });
+                        if animating {
+                            window.request_animation_frame();
+                        }
+                        Bounds {
+                            origin: point(
+                                px(position.x as f32),
+                                px((position.y + scroll_offset) as f32),
+                            ),
+                            size: bounds.size,
+                        }
+                    })
+                } else {
+                    self.terminal_view.update(cx, |terminal_view, _| {
+                        terminal_view.caret_animation = None;
+                    });
+                    ime_cursor_bounds
+                };
+
                 let cursor = if let AlacCursorShape::Hidden = cursor.shape {
                     None
                 } else {
                     let focused = self.focused;
-                    ime_cursor_bounds.map(move |bounds| {
+                    cursor_bounds.map(move |bounds| {
                         let (shape, text) = match cursor.shape {
                             AlacCursorShape::Block if !focused => (CursorShape::Hollow, None),
                             AlacCursorShape::Block => (CursorShape::Block, Some(cursor_text)),
@@ -1215,6 +1263,7 @@ impl Element for TerminalElement {
                     dimensions,
                     rects,
                     relative_highlighted_ranges,
+                    images,
                     mode,
                     display_offset,
                     hyperlink_tooltip,
@@ -1330,6 +1379,31 @@ impl Element for TerminalElement {
                     }
                     let text_paint_time = text_paint_start.elapsed();
 
+                    for image in self.terminal.read(cx).take_evicted_images() {
+                        window.drop_image(image).log_err();
+                    }
+                    for placement in &layout.images {
+                        let line = placement.point.line.0 + layout.display_offset as i32;
+                        let image_origin = origin
+                            + point(
+                                placement.point.column.0 as f32 * layout.dimensions.cell_width,
+                                line as f32 * layout.dimensions.line_height,
+                            );
+                        let image_size = size(
+                            layout.dimensions.cell_width * placement.size.width,
+                            layout.dimensions.line_height * placement.size.height,
+                        );
+                        window
+                            .paint_image(
+                                Bounds::new(image_origin, image_size),
+                                Corners::default(),
+                                placement.image.clone(),
+                                0,
+                                false,
+                            )
+                            .log_err();
+                    }
+
                     if let Some(text_to_mark) = &marked_text_cloned
                         && !text_to_mark.is_empty()
                         && let Some(ime_bounds) = layout.ime_cursor_bounds
@@ -5,10 +5,12 @@ use crate::{
     persistence::{
         SerializedItems, SerializedTerminalPanel, deserialize_terminal_panel, serialize_pane_group,
     },
+    profile_working_directory,
 };
 use breadcrumbs::Breadcrumbs;
 use collections::HashMap;
 use db::kvp::KEY_VALUE_STORE;
+use editor::{Bias, Editor, SendBufferToTerminal, SendSelectionToTerminal};
 use futures::{channel::oneshot, future::join_all};
 use gpui::{
     Action, AnyView, App, AsyncApp, AsyncWindowContext, Context, Corner, Entity, EventEmitter,
@@ -16,6 +18,7 @@ use gpui::{
     Task, WeakEntity, Window, actions,
 };
 use itertools::Itertools;
+use language::Point;
 use project::{Fs, Project, ProjectEntryId};
 
 use settings::{Settings, TerminalDockPosition};
@@ -57,6 +60,12 @@ pub fn init(cx: &mut App) {
         |workspace: &mut Workspace, _window, _: &mut Context<Workspace>| {
             workspace.register_action(TerminalPanel::new_terminal);
             workspace.register_action(TerminalPanel::open_terminal);
+            workspace.register_action(|workspace, _: &SendSelectionToTerminal, window, cx| {
+                TerminalPanel::send_editor_text(workspace, false, window, cx);
+            });
+            workspace.register_action(|workspace, _: &SendBufferToTerminal, window, cx| {
+                TerminalPanel::send_editor_text(workspace, true, window, cx);
+            });
             workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
                 if is_enabled_in_workspace(workspace, cx) {
                     workspace.toggle_panel_focus::<TerminalPanel>(window, cx);
@@ -164,12 +173,28 @@ impl TerminalPanel {
                             .with_handle(pane.new_item_context_menu_handle.clone())
                             .menu(move |window, cx| {
                                 let focus_handle = focus_handle.clone();
+                                let profile_names = TerminalSettings::get_global(cx)
+                                    .profiles
+                                    .keys()
+                                    .cloned()
+                                    .collect::<Vec<_>>();
                                 let menu = ContextMenu::build(window, cx, |menu, _, _| {
-                                    menu.context(focus_handle.clone())
-                                        .action(
-                                            "New Terminal",
-                                            workspace::NewTerminal::default().boxed_clone(),
-                                        )
+                                    let menu = menu.context(focus_handle.clone()).action(
+                                        "New Terminal",
+                                        workspace::NewTerminal::default().boxed_clone(),
+                                    );
+                                    profile_names
+                                        .into_iter()
+                                        .fold(menu, |menu, profile_name| {
+                                            menu.action(
+                                                format!("New Terminal: {profile_name}"),
+                                                workspace::NewTerminal {
+                                                    profile: Some(profile_name),
+                                                    ..Default::default()
+                                                }
+                                                .boxed_clone(),
+                                            )
+                                        })
                                         // We want the focus to go back to terminal panel once task modal is dismissed,
                                         // hence we focus that first. Otherwise, we'd end up without a focused element, as
                                         // context menu will be gone the moment we spawn the modal.
@@ -664,8 +689,10 @@ impl TerminalPanel {
                 if action.local {
                     this.add_local_terminal_shell(RevealStrategy::Always, window, cx)
                 } else {
-                    this.add_terminal_shell(
-                        default_working_directory(workspace, cx),
+                    this.add_terminal_shell_internal(
+                        false,
+                        profile_working_directory(workspace, action.profile.as_deref(), cx),
+                        action.profile.clone(),
                         RevealStrategy::Always,
                         window,
                         cx,
@@ -675,6 +702,90 @@ impl TerminalPanel {
             .detach_and_log_err(cx);
     }
 
+    /// Writes text from the active editor to the active terminal of the panel,
+    /// opening a terminal when there is none.
+    fn send_editor_text(
+        workspace: &mut Workspace,
+        whole_buffer: bool,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(terminal_panel) = workspace.panel::<Self>(cx) else {
+            return;
+        };
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+
+        let (text, language_name) = editor.update(cx, |editor, cx| {
+            let display_snapshot = editor.display_snapshot(cx);
+            let snapshot = display_snapshot.buffer_snapshot();
+            if whole_buffer {
+                let language_name = snapshot.language_at(Point::zero());
+                return (
+                    snapshot.text(),
+                    language_name.map(|language| language.name().0.to_string()),
+                );
+            }
+
+            let selections = editor.selections.all::<Point>(&display_snapshot);
+            let language_name = selections
+                .first()
+                .and_then(|selection| snapshot.language_at(selection.head()))
+                .map(|language| language.name().0.to_string());
+            let text = selections
+                .iter()
+                .map(|selection| {
+                    let range = if selection.is_empty() {
+                        let row = selection.head().row;
+                        Point::new(row, 0)
+                            ..snapshot.clip_point(Point::new(row, u32::MAX), Bias::Left)
+                    } else {
+                        selection.start..selection.end
+                    };
+                    snapshot.text_for_range(range).collect::<String>()
+                })
+                .join("\n");
+            (text, language_name)
+        });
+        if text.is_empty() {
+            return;
+        }
+
+        let settings = TerminalSettings::get_global(cx).send_to_terminal.clone();
+        let trailing_newlines = settings.trailing_newlines(language_name.as_deref());
+        let active_terminal = terminal_panel
+            .read(cx)
+            .active_pane
+            .read(cx)
+            .active_item()
+            .and_then(|item| item.downcast::<TerminalView>())
+            .map(|terminal_view| terminal_view.read(cx).terminal().downgrade());
+        let terminal = match active_terminal {
+            Some(terminal) => Task::ready(Ok(terminal)),
+            None => {
+                let working_directory = default_working_directory(workspace, cx);
+                terminal_panel.update(cx, |terminal_panel, cx| {
+                    terminal_panel.add_terminal_shell(
+                        working_directory,
+                        RevealStrategy::NoFocus,
+                        window,
+                        cx,
+                    )
+                })
+            }
+        };
+        workspace.open_panel::<Self>(window, cx);
+
+        cx.spawn(async move |_, cx| {
+            let terminal = terminal.await?;
+            terminal.update(cx, |terminal, _| {
+                terminal.send_text(&text, trailing_newlines, settings.bracketed_paste)
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn terminals_for_task(
         &self,
         label: &str,
@@ -831,7 +942,7 @@ impl TerminalPanel {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Task<Result<WeakEntity<Terminal>>> {
-        self.add_terminal_shell_internal(false, cwd, reveal_strategy, window, cx)
+        self.add_terminal_shell_internal(false, cwd, None, reveal_strategy, window, cx)
     }
 
     fn add_local_terminal_shell(
@@ -840,13 +951,14 @@ impl TerminalPanel {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Task<Result<WeakEntity<Terminal>>> {
-        self.add_terminal_shell_internal(true, None, reveal_strategy, window, cx)
+        self.add_terminal_shell_internal(true, None, None, reveal_strategy, window, cx)
     }
 
     fn add_terminal_shell_internal(
         &mut self,
         force_local: bool,
         cwd: Option<PathBuf>,
+        profile: Option<String>,
         reveal_strategy: RevealStrategy,
         window: &mut Window,
         cx: &mut Context<Self>,
@@ -868,7 +980,9 @@ impl TerminalPanel {
                     .await
             } else {
                 project
-                    .update(cx, |project, cx| project.create_terminal_shell(cwd, cx))
+                    .update(cx, |project, cx| {
+                        project.create_terminal_shell_with_profile(cwd, profile, cx)
+                    })
                     .await
             };
 
@@ -1787,6 +1901,10 @@ impl Panel for TerminalPanel {
     fn activation_priority(&self) -> u32 {
         1
     }
+
+    fn can_detach(&self, _cx: &App) -> bool {
+        true
+    }
 }
 
 struct TerminalProvider(Entity<TerminalPanel>);
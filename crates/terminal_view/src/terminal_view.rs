@@ -6,7 +6,10 @@ pub mod terminal_scrollbar;
 mod terminal_slash_command;
 
 use assistant_slash_command::SlashCommandRegistry;
-use editor::{Editor, EditorSettings, actions::SelectAll, blink_manager::BlinkManager};
+use audio::{Audio, Sound};
+use editor::{
+    CaretAnimation, Editor, EditorSettings, actions::SelectAll, blink_manager::BlinkManager,
+};
 use gpui::{
     Action, AnyElement, App, ClipboardEntry, DismissEvent, Entity, EventEmitter, FocusHandle,
     Focusable, KeyContext, KeyDownEvent, Keystroke, MouseButton, MouseDownEvent, Pixels, Point,
@@ -18,9 +21,11 @@ use persistence::TERMINAL_DB;
 use project::{Project, search::SearchQuery};
 use schemars::JsonSchema;
 use serde::Deserialize;
-use settings::{Settings, SettingsStore, TerminalBlink, WorkingDirectory};
+use settings::{
+    Settings, SettingsLocation, SettingsStore, TerminalBell, TerminalBlink, WorkingDirectory,
+};
 use std::{
-    cmp,
+    cmp, mem,
     ops::{Range, RangeInclusive},
     path::{Path, PathBuf},
     rc::Rc,
@@ -29,9 +34,10 @@ use std::{
 };
 use task::TaskId;
 use terminal::{
-    Clear, Copy, Event, HoveredWord, MaybeNavigationTarget, Paste, ScrollLineDown, ScrollLineUp,
-    ScrollPageDown, ScrollPageUp, ScrollToBottom, ScrollToTop, ShowCharacterPalette, TaskState,
-    TaskStatus, Terminal, TerminalBounds, ToggleViMode,
+    Clear, Copy, Event, HoveredWord, MaybeNavigationTarget, Paste, RerunLastCommand,
+    ScrollLineDown, ScrollLineUp, ScrollPageDown, ScrollPageUp, ScrollToBottom, ScrollToNextPrompt,
+    ScrollToPreviousPrompt, ScrollToTop, ShowCharacterPalette, TaskState, TaskStatus, Terminal,
+    TerminalBounds, ToggleViMode,
     alacritty_terminal::{
         index::Point as AlacPoint,
         term::{TermMode, point_to_viewport, search::RegexSearch},
@@ -48,13 +54,14 @@ use ui::{
     prelude::*,
     scrollbars::{self, GlobalSetting, ScrollbarVisibility},
 };
-use util::ResultExt;
+use util::{ResultExt, rel_path::RelPath};
 use workspace::{
-    CloseActiveItem, NewCenterTerminal, NewTerminal, ToolbarItemLocation, Workspace, WorkspaceId,
-    delete_unloaded_items,
+    CloseActiveItem, NewCenterTerminal, NewTerminal, Toast, ToolbarItemLocation, Workspace,
+    WorkspaceId, delete_unloaded_items,
     item::{
         BreadcrumbText, Item, ItemEvent, SerializableItem, TabContentParams, TabTooltipContent,
     },
+    notifications::NotificationId,
     register_serializable_item,
     searchable::{
         Direction, SearchEvent, SearchOptions, SearchToken, SearchableItem, SearchableItemHandle,
@@ -66,7 +73,12 @@ struct ImeState {
     marked_text: String,
 }
 
-const CURSOR_BLINK_INTERVAL: Duration = Duration::from_millis(500);
+const BELL_FLASH_DURATION: Duration = Duration::from_millis(150);
+/// The whole scrollback is written each time, so output is saved at most this often.
+const SCROLLBACK_SERIALIZATION_INTERVAL: Duration = Duration::from_secs(5);
+
+struct CommandFinishedNotification;
+struct TerminalNotification;
 
 /// Event to transmit the scroll from the element to the view
 #[derive(Clone, Debug, PartialEq)]
@@ -127,12 +139,19 @@ pub struct TerminalView {
     focus_handle: FocusHandle,
     //Currently using iTerm bell, show bell emoji in tab until input is received
     has_bell: bool,
+    bell_flash: bool,
+    bell_flash_task: Task<()>,
     context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
     cursor_shape: CursorShape,
     blink_manager: Entity<BlinkManager>,
+    caret_animation: Option<CaretAnimation>,
     mode: TerminalMode,
     blinking_terminal_enabled: bool,
     needs_serialize: bool,
+    scrollback_serialization_scheduled: bool,
+    /// Whether there was output since the scrollback was last saved.
+    scrollback_changed: bool,
+    scrollback_serialization_task: Task<()>,
     custom_title: Option<String>,
     hover: Option<HoverTarget>,
     hover_tooltip_update: Task<()>,
@@ -249,7 +268,7 @@ impl TerminalView {
 
         let blink_manager = cx.new(|cx| {
             BlinkManager::new(
-                CURSOR_BLINK_INTERVAL,
+                |cx| Duration::from_millis(EditorSettings::get_global(cx).cursor_blink_interval.0),
                 |cx| {
                     !matches!(
                         TerminalSettings::get_global(cx).blinking,
@@ -272,10 +291,13 @@ impl TerminalView {
             workspace: workspace_handle,
             project,
             has_bell: false,
+            bell_flash: false,
+            bell_flash_task: Task::ready(()),
             focus_handle,
             context_menu: None,
             cursor_shape,
             blink_manager,
+            caret_animation: None,
             blinking_terminal_enabled: false,
             hover: None,
             hover_tooltip_update: Task::ready(()),
@@ -286,6 +308,9 @@ impl TerminalView {
             scroll_top: Pixels::ZERO,
             scroll_handle,
             needs_serialize: false,
+            scrollback_serialization_scheduled: false,
+            scrollback_changed: false,
+            scrollback_serialization_task: Task::ready(()),
             custom_title: None,
             ime_state: None,
             self_handle: cx.entity().downgrade(),
@@ -486,6 +511,102 @@ impl TerminalView {
         cx.emit(Event::Wakeup);
     }
 
+    fn schedule_scrollback_serialization(&mut self, cx: &mut Context<Self>) {
+        if self.scrollback_serialization_scheduled
+            || self.workspace_id.is_none()
+            || !TerminalSettings::get_global(cx).persist_scrollback
+            || self.terminal.read(cx).task().is_some()
+        {
+            return;
+        }
+        self.scrollback_serialization_scheduled = true;
+        self.scrollback_serialization_task = cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(SCROLLBACK_SERIALIZATION_INTERVAL)
+                .await;
+            this.update(cx, |this, cx| {
+                this.scrollback_serialization_scheduled = false;
+                this.needs_serialize = true;
+                cx.emit(ItemEvent::UpdateTab);
+            })
+            .ok();
+        });
+    }
+
+    fn ring_bell(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let bell = TerminalSettings::get_global(cx).bell;
+        match bell {
+            TerminalBell::None => return,
+            TerminalBell::Visual => {
+                self.bell_flash = true;
+                self.bell_flash_task = cx.spawn(async move |this, cx| {
+                    cx.background_executor().timer(BELL_FLASH_DURATION).await;
+                    this.update(cx, |this, cx| {
+                        this.bell_flash = false;
+                        cx.notify();
+                    })
+                    .ok();
+                });
+            }
+            TerminalBell::Sound => Audio::play_sound(Sound::TerminalBell, cx),
+            TerminalBell::Attention => window.request_attention(),
+        }
+        self.has_bell = true;
+        cx.emit(Event::Wakeup);
+    }
+
+    fn notify_command_finished(
+        &mut self,
+        exit_code: i32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !TerminalSettings::get_global(cx).notify_on_command_finish
+            || (window.is_window_active() && self.focus_handle.contains_focused(window, cx))
+        {
+            return;
+        }
+        let command = self.terminal.read(cx).last_command().unwrap_or("Command");
+        let message = if exit_code == 0 {
+            format!("`{command}` finished")
+        } else {
+            format!("`{command}` failed with exit code {exit_code}")
+        };
+        let notification_id =
+            NotificationId::composite::<CommandFinishedNotification>(cx.entity_id());
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(Toast::new(notification_id, message), cx);
+            })
+            .ok();
+    }
+
+    /// Shows the notification a program in the terminal asked for, unless the
+    /// terminal is in front of the user already.
+    fn show_notification(
+        &mut self,
+        title: &str,
+        body: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if window.is_window_active() && self.focus_handle.contains_focused(window, cx) {
+            return;
+        }
+        let message = match (title.is_empty(), body.is_empty()) {
+            (true, true) => return,
+            (true, false) => body.to_string(),
+            (false, true) => title.to_string(),
+            (false, false) => format!("{title}: {body}"),
+        };
+        let notification_id = NotificationId::composite::<TerminalNotification>(cx.entity_id());
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(Toast::new(notification_id, message), cx);
+            })
+            .ok();
+    }
+
     pub fn deploy_context_menu(
         &mut self,
         position: Point<Pixels>,
@@ -746,6 +867,33 @@ impl TerminalView {
         cx.notify();
     }
 
+    fn scroll_to_previous_prompt(
+        &mut self,
+        _: &ScrollToPreviousPrompt,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.terminal
+            .update(cx, |term, _| term.scroll_to_previous_prompt());
+        cx.notify();
+    }
+
+    fn scroll_to_next_prompt(
+        &mut self,
+        _: &ScrollToNextPrompt,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.terminal
+            .update(cx, |term, _| term.scroll_to_next_prompt());
+        cx.notify();
+    }
+
+    fn rerun_last_command(&mut self, _: &RerunLastCommand, _: &mut Window, cx: &mut Context<Self>) {
+        self.terminal
+            .update(cx, |term, _| term.rerun_last_command());
+    }
+
     fn toggle_vi_mode(&mut self, _: &ToggleViMode, _: &mut Window, cx: &mut Context<Self>) {
         self.terminal.update(cx, |term, _| term.toggle_vi_mode());
         cx.notify();
@@ -782,6 +930,25 @@ impl TerminalView {
         &self.terminal
     }
 
+    /// The terminal settings of the worktree the terminal's working directory
+    /// is in, which is where the settings of its profile came from.
+    fn worktree_terminal_settings<'a>(&self, cx: &'a App) -> &'a TerminalSettings {
+        let settings_location = self
+            .terminal
+            .read(cx)
+            .working_directory()
+            .zip(self.project.upgrade())
+            .and_then(|(working_directory, project)| {
+                let (worktree, _) = project.read(cx).find_worktree(&working_directory, cx)?;
+                Some(worktree.read(cx).id())
+            })
+            .map(|worktree_id| SettingsLocation {
+                worktree_id,
+                path: RelPath::empty(),
+            });
+        TerminalSettings::get(settings_location, cx)
+    }
+
     pub fn set_block_below_cursor(
         &mut self,
         block: BlockProperties,
@@ -995,11 +1162,18 @@ fn subscribe_for_terminal_events(
                     cx.emit(Event::Wakeup);
                     cx.emit(ItemEvent::UpdateTab);
                     cx.emit(SearchEvent::MatchesInvalidated);
+                    terminal_view.scrollback_changed = true;
+                    terminal_view.schedule_scrollback_serialization(cx);
                 }
 
-                Event::Bell => {
-                    terminal_view.has_bell = true;
-                    cx.emit(Event::Wakeup);
+                Event::Bell => terminal_view.ring_bell(window, cx),
+
+                Event::CommandFinished(exit_code) => {
+                    terminal_view.notify_command_finished(*exit_code, window, cx);
+                }
+
+                Event::Notification { title, body } => {
+                    terminal_view.show_notification(title, body, window, cx);
                 }
 
                 Event::BlinkChanged(blinking) => {
@@ -1094,13 +1268,20 @@ fn subscribe_for_terminal_events(
 
 fn regex_search_for_query(query: &SearchQuery) -> Option<RegexSearch> {
     let str = query.as_str();
+    // Alacritty ignores case unless the pattern has an uppercase letter, so the
+    // case option is spelled out as an inline flag.
+    let case_flag = if query.case_sensitive() {
+        "(?-i)"
+    } else {
+        "(?i)"
+    };
     if query.is_regex() {
         if str == "." {
             return None;
         }
-        RegexSearch::new(str).ok()
+        RegexSearch::new(&format!("{case_flag}{str}")).ok()
     } else {
-        RegexSearch::new(&regex::escape(str)).ok()
+        RegexSearch::new(&format!("{case_flag}{}", regex::escape(str))).ok()
     }
 }
 
@@ -1202,6 +1383,17 @@ impl Render for TerminalView {
         let terminal_view_handle = cx.entity();
 
         let focused = self.focus_handle.is_focused(window);
+        let is_scrollable = self.content_mode(window, cx).is_scrollable();
+        let failed_command_positions = {
+            let terminal = self.terminal.read(cx);
+            let total_lines = terminal.total_lines().max(1) as f32;
+            terminal
+                .prompt_marks()
+                .iter()
+                .filter(|mark| mark.failed())
+                .map(|mark| (mark.line as f32 / total_lines).min(1.))
+                .collect::<Vec<_>>()
+        };
 
         div()
             .id("terminal-view")
@@ -1220,6 +1412,9 @@ impl Render for TerminalView {
             .on_action(cx.listener(TerminalView::scroll_page_down))
             .on_action(cx.listener(TerminalView::scroll_to_top))
             .on_action(cx.listener(TerminalView::scroll_to_bottom))
+            .on_action(cx.listener(TerminalView::scroll_to_previous_prompt))
+            .on_action(cx.listener(TerminalView::scroll_to_next_prompt))
+            .on_action(cx.listener(TerminalView::rerun_last_command))
             .on_action(cx.listener(TerminalView::toggle_vi_mode))
             .on_action(cx.listener(TerminalView::show_character_palette))
             .on_action(cx.listener(TerminalView::select_all))
@@ -1256,7 +1451,7 @@ impl Render for TerminalView {
                         self.block_below_cursor.clone(),
                         self.mode.clone(),
                     ))
-                    .when(self.content_mode(window, cx).is_scrollable(), |div| {
+                    .when(is_scrollable, |div| {
                         div.custom_scrollbars(
                             Scrollbars::for_settings::<TerminalScrollbarSettingsWrapper>()
                                 .show_along(ScrollAxes::Vertical)
@@ -1270,6 +1465,37 @@ impl Render for TerminalView {
                         )
                     }),
             )
+            .when(
+                is_scrollable && !failed_command_positions.is_empty(),
+                |this| {
+                    let error_color = cx.theme().status().error;
+                    this.child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .bottom_0()
+                            .right_0()
+                            .w(px(3.))
+                            .children(failed_command_positions.into_iter().map(|position| {
+                                div()
+                                    .absolute()
+                                    .left_0()
+                                    .right_0()
+                                    .top(relative(position))
+                                    .h(px(2.))
+                                    .bg(error_color)
+                            })),
+                    )
+                },
+            )
+            .when(self.bell_flash, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .bg(cx.theme().colors().text.opacity(0.1)),
+                )
+            })
             .children(self.context_menu.as_ref().map(|(menu, position, _)| {
                 deferred(
                     anchored()
@@ -1337,7 +1563,22 @@ impl Item for TerminalView {
                     }
                 }
             },
-            None => (IconName::Terminal, Color::Muted, None),
+            None => {
+                let profile_icon = terminal.profile().and_then(|profile| {
+                    self.worktree_terminal_settings(cx)
+                        .profiles
+                        .get(profile)?
+                        .icon
+                        .as_deref()?
+                        .parse()
+                        .ok()
+                });
+                (
+                    profile_icon.unwrap_or(IconName::Terminal),
+                    Color::Muted,
+                    None,
+                )
+            }
         };
 
         h_flex()
@@ -1553,8 +1794,7 @@ impl SerializableItem for TerminalView {
         _: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<Task<anyhow::Result<()>>> {
-        let terminal = self.terminal().read(cx);
-        if terminal.task().is_some() {
+        if self.terminal().read(cx).task().is_some() {
             return None;
         }
 
@@ -1563,8 +1803,18 @@ impl SerializableItem for TerminalView {
         }
 
         let workspace_id = self.workspace_id?;
+        let scrollback_changed = mem::take(&mut self.scrollback_changed);
+        let terminal = self.terminal().read(cx);
         let cwd = terminal.working_directory();
         let custom_title = self.custom_title.clone();
+        let scrollback = if !TerminalSettings::get_global(cx).persist_scrollback {
+            Some(Task::ready(None))
+        } else if scrollback_changed {
+            let content = terminal.read_content_in_background();
+            Some(cx.background_spawn(async move { Some(content.await.trim_end().to_string()) }))
+        } else {
+            None
+        };
         self.needs_serialize = false;
 
         Some(cx.background_spawn(async move {
@@ -1576,6 +1826,11 @@ impl SerializableItem for TerminalView {
             TERMINAL_DB
                 .save_custom_title(item_id, workspace_id, custom_title)
                 .await?;
+            if let Some(scrollback) = scrollback {
+                TERMINAL_DB
+                    .save_scrollback(item_id, workspace_id, scrollback.await)
+                    .await?;
+            }
             Ok(())
         }))
     }
@@ -1593,7 +1848,7 @@ impl SerializableItem for TerminalView {
         cx: &mut App,
     ) -> Task<anyhow::Result<Entity<Self>>> {
         window.spawn(cx, async move |cx| {
-            let (cwd, custom_title) = cx
+            let (cwd, custom_title, scrollback) = cx
                 .update(|_window, cx| {
                     let from_db = TERMINAL_DB
                         .get_working_directory(item_id, workspace_id)
@@ -1614,14 +1869,29 @@ impl SerializableItem for TerminalView {
                         .log_err()
                         .flatten()
                         .filter(|title| !title.trim().is_empty());
-                    (cwd, custom_title)
+                    let scrollback = TerminalSettings::get_global(cx)
+                        .persist_scrollback
+                        .then(|| {
+                            TERMINAL_DB
+                                .get_scrollback(item_id, workspace_id)
+                                .log_err()
+                                .flatten()
+                        })
+                        .flatten()
+                        .filter(|scrollback| !scrollback.is_empty());
+                    (cwd, custom_title, scrollback)
                 })
                 .ok()
-                .unwrap_or((None, None));
+                .unwrap_or((None, None, None));
 
             let terminal = project
                 .update(cx, |project, cx| project.create_terminal_shell(cwd, cx))
                 .await?;
+            if let Some(scrollback) = scrollback {
+                terminal.update(cx, |terminal, cx| {
+                    terminal.write_output(format!("{scrollback}\n").as_bytes(), cx)
+                });
+            }
             cx.update(|window, cx| {
                 cx.new(|cx| {
                     let mut view = TerminalView::new(
@@ -1647,7 +1917,7 @@ impl SearchableItem for TerminalView {
 
     fn supported_options(&self) -> SearchOptions {
         SearchOptions {
-            case: false,
+            case: true,
             word: false,
             regex: true,
             replacement: false,
@@ -1796,7 +2066,23 @@ impl SearchableItem for TerminalView {
 /// Gets the working directory for the given workspace, respecting the user's settings.
 /// Falls back to home directory when no project directory is available.
 pub(crate) fn default_working_directory(workspace: &Workspace, cx: &App) -> Option<PathBuf> {
-    let directory = match &TerminalSettings::get_global(cx).working_directory {
+    profile_working_directory(workspace, None, cx)
+}
+
+/// The working directory for a terminal opened with the named profile, or with
+/// the default profile when no name is given.
+pub(crate) fn profile_working_directory(
+    workspace: &Workspace,
+    profile: Option<&str>,
+    cx: &App,
+) -> Option<PathBuf> {
+    let settings = TerminalSettings::get_global(cx);
+    let working_directory = settings
+        .profile(profile)
+        .map_or(&settings.working_directory, |(_, profile)| {
+            &profile.working_directory
+        });
+    let directory = match working_directory {
         WorkingDirectory::CurrentFileDirectory => workspace
             .project()
             .read(cx)
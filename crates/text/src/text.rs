@@ -1394,6 +1394,16 @@ impl Buffer {
         self.history.redo_stack.last()
     }
 
+    /// The versions the buffer was at before each transaction on the undo
+    /// stack, from the oldest transaction to the most recent.
+    pub fn undo_stack_start_versions(&self) -> Vec<clock::Global> {
+        self.history
+            .undo_stack
+            .iter()
+            .map(|entry| entry.transaction.start.clone())
+            .collect()
+    }
+
     pub fn start_transaction(&mut self) -> Option<TransactionId> {
         self.start_transaction_at(Instant::now())
     }
@@ -122,6 +122,8 @@ impl ThemeColors {
             editor_hover_line_number: neutral().light().step_12(),
             editor_active_line_number: neutral().light().step_11(),
             editor_invisible: neutral().light().step_10(),
+            editor_invisible_tab: neutral().light().step_10(),
+            editor_invisible_space: neutral().light().step_10(),
             editor_wrap_guide: neutral().light_alpha().step_7(),
             editor_active_wrap_guide: neutral().light_alpha().step_8(),
             editor_indent_guide: neutral().light_alpha().step_5(),
@@ -267,6 +269,8 @@ impl ThemeColors {
             editor_hover_line_number: neutral().dark_alpha().step_12(),
             editor_active_line_number: neutral().dark_alpha().step_11(),
             editor_invisible: neutral().dark_alpha().step_4(),
+            editor_invisible_tab: neutral().dark_alpha().step_4(),
+            editor_invisible_space: neutral().dark_alpha().step_4(),
             editor_wrap_guide: neutral().dark_alpha().step_4(),
             editor_active_wrap_guide: neutral().dark_alpha().step_4(),
             editor_indent_guide: neutral().dark_alpha().step_4(),
@@ -36,6 +36,11 @@ pub struct IconTheme {
     pub file_stems: HashMap<String, String>,
     /// The mapping of file suffixes to their associated icon keys.
     pub file_suffixes: HashMap<String, String>,
+    /// The mapping of language names to their associated icon keys.
+    ///
+    /// These take precedence over the file stems and suffixes for files
+    /// whose language is known.
+    pub file_languages: HashMap<String, String>,
     /// The mapping of icon keys to icon definitions.
     pub file_icons: HashMap<String, IconDefinition>,
 }
@@ -407,6 +412,7 @@ static DEFAULT_ICON_THEME: LazyLock<Arc<IconTheme>> = LazyLock::new(|| {
         },
         file_stems: icon_keys_by_association(FILE_STEMS_BY_ICON_KEY),
         file_suffixes: icon_keys_by_association(FILE_SUFFIXES_BY_ICON_KEY),
+        file_languages: HashMap::default(),
         file_icons: HashMap::from_iter(FILE_ICONS.iter().map(|(ty, path)| {
             (
                 ty.to_string(),
@@ -29,6 +29,8 @@ pub struct IconThemeContent {
     #[serde(default)]
     pub file_suffixes: HashMap<String, String>,
     #[serde(default)]
+    pub file_languages: HashMap<String, String>,
+    #[serde(default)]
     pub file_icons: HashMap<String, IconDefinitionContent>,
 }
 
@@ -1,5 +1,8 @@
 use std::sync::Arc;
-use std::{fmt::Debug, path::Path};
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{Context as _, Result};
 use collections::HashMap;
@@ -14,7 +17,7 @@ use util::ResultExt;
 use crate::{
     Appearance, AppearanceContent, ChevronIcons, DEFAULT_ICON_THEME_NAME, DirectoryIcons,
     IconDefinition, IconTheme, Theme, ThemeFamily, ThemeFamilyContent, default_icon_theme,
-    read_icon_theme, read_user_theme, refine_theme_family,
+    read_icon_theme, read_theme_file, read_user_theme, refine_theme_family,
 };
 
 /// The metadata for a theme.
@@ -50,6 +53,9 @@ impl Global for GlobalThemeRegistry {}
 struct ThemeRegistryState {
     themes: HashMap<SharedString, Arc<Theme>>,
     icon_themes: HashMap<SharedString, Arc<IconTheme>>,
+    /// The themes loaded from each theme file that the `theme` setting points
+    /// at, kept apart from the named themes so that they never replace one.
+    theme_files: HashMap<PathBuf, Vec<Arc<Theme>>>,
     /// Whether the extensions have been loaded yet.
     extensions_loaded: bool,
 }
@@ -89,6 +95,7 @@ impl ThemeRegistry {
             state: RwLock::new(ThemeRegistryState {
                 themes: HashMap::default(),
                 icon_themes: HashMap::default(),
+                theme_files: HashMap::default(),
                 extensions_loaded: false,
             }),
             assets,
@@ -237,6 +244,48 @@ impl ThemeRegistry {
         Ok(())
     }
 
+    /// Loads the themes of a theme file that the `theme` setting points at,
+    /// replacing those previously loaded from it.
+    ///
+    /// Unlike [`Self::load_user_theme`], this fails on any property that
+    /// doesn't match the theme schema, so that typos surface while the theme
+    /// is being written.
+    pub async fn load_theme_file(&self, theme_path: &Path, fs: Arc<dyn Fs>) -> Result<()> {
+        let theme_family = read_theme_file(theme_path, fs).await?;
+        anyhow::ensure!(
+            !theme_family.themes.is_empty(),
+            "theme file {theme_path:?} defines no themes"
+        );
+
+        let themes = refine_theme_family(theme_family)
+            .themes
+            .into_iter()
+            .map(Arc::new)
+            .collect();
+        self.state
+            .write()
+            .theme_files
+            .insert(theme_path.to_path_buf(), themes);
+
+        Ok(())
+    }
+
+    /// Returns the theme loaded from the given theme file that best matches
+    /// the appearance.
+    pub fn get_from_theme_file(
+        &self,
+        theme_path: &Path,
+        appearance: Appearance,
+    ) -> Option<Arc<Theme>> {
+        let state = self.state.read();
+        let themes = state.theme_files.get(theme_path)?;
+        themes
+            .iter()
+            .find(|theme| theme.appearance == appearance)
+            .or(themes.first())
+            .cloned()
+    }
+
     /// Returns the default icon theme.
     pub fn default_icon_theme(&self) -> Result<Arc<IconTheme>, IconThemeNotFoundError> {
         self.get_icon_theme(DEFAULT_ICON_THEME_NAME)
@@ -273,6 +322,39 @@ impl ThemeRegistry {
             .retain(|name, _| !icon_themes_to_remove.contains(name))
     }
 
+    /// Loads the user icon themes from the specified directory and adds them to
+    /// the registry.
+    ///
+    /// The icon paths of these themes are resolved against the directory itself.
+    pub async fn load_user_icon_themes(
+        &self,
+        icon_themes_path: &Path,
+        fs: Arc<dyn Fs>,
+    ) -> Result<()> {
+        let mut icon_theme_paths = fs
+            .read_dir(icon_themes_path)
+            .await
+            .with_context(|| format!("reading icon themes from {icon_themes_path:?}"))?;
+
+        while let Some(icon_theme_path) = icon_theme_paths.next().await {
+            let Some(icon_theme_path) = icon_theme_path.log_err() else {
+                continue;
+            };
+            if icon_theme_path
+                .extension()
+                .is_none_or(|extension| extension != "json")
+            {
+                continue;
+            }
+
+            self.load_icon_theme(&icon_theme_path, icon_themes_path, fs.clone())
+                .await
+                .log_err();
+        }
+
+        Ok(())
+    }
+
     /// Loads the icon theme from the specified path and adds it to the registry.
     ///
     /// The `icons_root_dir` parameter indicates the root directory from which
@@ -334,6 +416,7 @@ impl ThemeRegistry {
                 },
                 file_stems,
                 file_suffixes,
+                file_languages: icon_theme.file_languages,
                 file_icons: icon_theme
                     .file_icons
                     .into_iter()
@@ -362,3 +445,95 @@ impl Default for ThemeRegistry {
         Self::new(Box::new(()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use serde_json::json;
+    use settings::DEFAULT_DARK_THEME;
+
+    use super::*;
+
+    fn theme_file(theme_names: &[(&str, &str)]) -> serde_json::Value {
+        json!({
+            "name": "Test Family",
+            "author": "Test",
+            "themes": theme_names
+                .iter()
+                .map(|(name, appearance)| {
+                    json!({ "name": name, "appearance": appearance, "style": {} })
+                })
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    #[gpui::test]
+    async fn test_load_theme_file(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        let theme_path = Path::new("/themes/test.json");
+        let registry = ThemeRegistry::new(Box::new(()));
+
+        fs.insert_file(
+            theme_path,
+            theme_file(&[(DEFAULT_DARK_THEME, "dark"), ("Test Light", "light")])
+                .to_string()
+                .into_bytes(),
+        )
+        .await;
+        registry
+            .load_theme_file(theme_path, fs.clone())
+            .await
+            .unwrap();
+        let theme = |appearance| {
+            registry
+                .get_from_theme_file(theme_path, appearance)
+                .map(|theme| theme.name.clone())
+        };
+        assert_eq!(theme(Appearance::Dark), Some(DEFAULT_DARK_THEME.into()));
+        assert_eq!(theme(Appearance::Light), Some("Test Light".into()));
+        assert!(
+            !Arc::ptr_eq(
+                &registry.get(DEFAULT_DARK_THEME).unwrap(),
+                &registry
+                    .get_from_theme_file(theme_path, Appearance::Dark)
+                    .unwrap()
+            ),
+            "A theme file doesn't replace the built-in theme of the same name"
+        );
+
+        // Reloading the file replaces its themes, leaving the built-in one
+        // with a previously loaded name in place.
+        fs.insert_file(
+            theme_path,
+            theme_file(&[("Test Dark", "dark")])
+                .to_string()
+                .into_bytes(),
+        )
+        .await;
+        registry
+            .load_theme_file(theme_path, fs.clone())
+            .await
+            .unwrap();
+        assert_eq!(theme(Appearance::Dark), Some("Test Dark".into()));
+        assert_eq!(theme(Appearance::Light), Some("Test Dark".into()));
+        assert!(registry.get(DEFAULT_DARK_THEME).is_ok());
+        assert!(registry.get("Test Light").is_err());
+
+        // An invalid file leaves the last valid themes loaded.
+        fs.insert_file(
+            theme_path,
+            json!({ "name": "Test Family", "author": "Test", "themes": "Test Dark" })
+                .to_string()
+                .into_bytes(),
+        )
+        .await;
+        assert!(
+            registry
+                .load_theme_file(theme_path, fs.clone())
+                .await
+                .is_err()
+        );
+        assert_eq!(theme(Appearance::Dark), Some("Test Dark".into()));
+    }
+}
@@ -258,6 +258,10 @@ pub fn theme_colors_refinement(
         .editor_document_highlight_read_background
         .as_ref()
         .and_then(|color| try_parse_color(color).ok());
+    let editor_invisible = this
+        .editor_invisible
+        .as_ref()
+        .and_then(|color| try_parse_color(color).ok());
     let scrollbar_thumb_background = this
         .scrollbar_thumb_background
         .as_ref()
@@ -565,10 +569,17 @@ pub fn theme_colors_refinement(
             .editor_active_line_number
             .as_ref()
             .and_then(|color| try_parse_color(color).ok()),
-        editor_invisible: this
-            .editor_invisible
+        editor_invisible,
+        editor_invisible_tab: this
+            .editor_invisible_tab
             .as_ref()
-            .and_then(|color| try_parse_color(color).ok()),
+            .and_then(|color| try_parse_color(color).ok())
+            .or(editor_invisible),
+        editor_invisible_space: this
+            .editor_invisible_space
+            .as_ref()
+            .and_then(|color| try_parse_color(color).ok())
+            .or(editor_invisible),
         editor_wrap_guide: this
             .editor_wrap_guide
             .as_ref()
@@ -839,7 +850,8 @@ pub fn theme_colors_refinement(
     }
 }
 
-pub(crate) fn try_parse_color(color: &str) -> anyhow::Result<Hsla> {
+/// Parses a color the way theme files write them, like `#rrggbbaa`.
+pub fn try_parse_color(color: &str) -> anyhow::Result<Hsla> {
     let rgba = gpui::Rgba::try_from(color)?;
     let rgba = palette::rgb::Srgba::from_components((rgba.r, rgba.g, rgba.b, rgba.a));
     let hsla = palette::Hsla::from_color(rgba);
@@ -5,7 +5,8 @@ use crate::{
 use collections::HashMap;
 use derive_more::{Deref, DerefMut};
 use gpui::{
-    App, Context, Font, FontFallbacks, FontStyle, Global, Pixels, Subscription, Window, px,
+    App, Context, Font, FontFallbacks, FontStyle, Global, Pixels, Subscription, Window,
+    WindowBackgroundAppearance, px,
 };
 use refineable::Refineable;
 use schemars::JsonSchema;
@@ -18,6 +19,10 @@ const MIN_FONT_SIZE: Pixels = px(6.0);
 const MAX_FONT_SIZE: Pixels = px(100.0);
 const MIN_LINE_HEIGHT: f32 = 1.0;
 
+/// The opacity given to opaque theme backgrounds when the window background
+/// is set to show the desktop through it.
+const TRANSLUCENT_BACKGROUND_OPACITY: f32 = 0.8;
+
 #[derive(
     Debug,
     Default,
@@ -34,7 +39,6 @@ const MIN_LINE_HEIGHT: f32 = 1.0;
 )]
 
 /// Specifies the density of the UI.
-/// Note: This setting is still experimental. See [this tracking issue](https://github.com/zed-industries/zed/issues/18078)
 #[serde(rename_all = "snake_case")]
 pub enum UiDensity {
     /// A denser UI with tighter spacing and smaller elements.
@@ -131,12 +135,18 @@ pub struct ThemeSettings {
     /// The current icon theme selection.
     pub icon_theme: IconThemeSelection,
     /// The density of the UI.
-    /// Note: This setting is still experimental. See [this tracking issue](
     pub ui_density: UiDensity,
     /// The amount of fading applied to unnecessary code.
     pub unnecessary_code_fade: f32,
+    /// Overrides the theme's window background appearance.
+    pub window_background_appearance: Option<WindowBackgroundAppearance>,
 }
 
+/// The `theme_overrides` key whose overrides apply to every light theme.
+const APPEARANCE_LIGHT_OVERRIDES_KEY: &str = "light";
+/// The `theme_overrides` key whose overrides apply to every dark theme.
+const APPEARANCE_DARK_OVERRIDES_KEY: &str = "dark";
+
 /// Returns the name of the default theme for the given [`Appearance`].
 pub fn default_theme(appearance: Appearance) -> &'static str {
     match appearance {
@@ -545,9 +555,27 @@ impl ThemeSettings {
             arc_theme = Arc::new(theme);
         }
 
-        if let Some(theme_overrides) = self.theme_overrides.get(arc_theme.name.as_ref()) {
+        let appearance_key = match arc_theme.appearance {
+            Appearance::Light => APPEARANCE_LIGHT_OVERRIDES_KEY,
+            Appearance::Dark => APPEARANCE_DARK_OVERRIDES_KEY,
+        };
+        let theme_name = arc_theme.name.clone();
+        for overrides_key in [appearance_key, theme_name.as_ref()] {
+            if let Some(theme_overrides) = self.theme_overrides.get(overrides_key) {
+                let mut theme = (*arc_theme).clone();
+                ThemeSettings::modify_theme(&mut theme, theme_overrides);
+                arc_theme = Arc::new(theme);
+            }
+        }
+
+        if let Some(window_background_appearance) = self.window_background_appearance
+            && window_background_appearance != arc_theme.styles.window_background_appearance
+        {
             let mut theme = (*arc_theme).clone();
-            ThemeSettings::modify_theme(&mut theme, theme_overrides);
+            theme.styles.window_background_appearance = window_background_appearance;
+            if window_background_appearance != WindowBackgroundAppearance::Opaque {
+                make_backgrounds_translucent(&mut theme);
+            }
             arc_theme = Arc::new(theme);
         }
 
@@ -596,6 +624,31 @@ pub fn adjusted_font_size(size: Pixels, cx: &App) -> Pixels {
     clamp_font_size(adjusted_font_size)
 }
 
+/// Lets the desktop show through the backgrounds that cover the window, for
+/// themes that weren't designed for a transparent window and made them opaque.
+fn make_backgrounds_translucent(theme: &mut Theme) {
+    let colors = &mut theme.styles.colors;
+    for color in [
+        &mut colors.background,
+        &mut colors.surface_background,
+        &mut colors.panel_background,
+        &mut colors.editor_background,
+        &mut colors.editor_gutter_background,
+        &mut colors.tab_bar_background,
+        &mut colors.tab_active_background,
+        &mut colors.tab_inactive_background,
+        &mut colors.toolbar_background,
+        &mut colors.title_bar_background,
+        &mut colors.title_bar_inactive_background,
+        &mut colors.status_bar_background,
+        &mut colors.terminal_background,
+    ] {
+        if color.a >= 1.0 {
+            *color = color.opacity(TRANSLUCENT_BACKGROUND_OPACITY);
+        }
+    }
+}
+
 /// Adjusts the buffer font size.
 pub fn adjust_buffer_font_size(cx: &mut App, f: impl FnOnce(Pixels) -> Pixels) {
     let buffer_font_size = ThemeSettings::get_global(cx).buffer_font_size;
@@ -736,6 +789,9 @@ impl settings::Settings for ThemeSettings {
             icon_theme: icon_theme_selection,
             ui_density: content.ui_density.unwrap_or_default().into(),
             unnecessary_code_fade: content.unnecessary_code_fade.unwrap().0.clamp(0.0, 0.9),
+            window_background_appearance: content
+                .window_background_appearance
+                .map(|appearance| appearance.into_gpui()),
         }
     }
 }
@@ -217,6 +217,10 @@ pub struct ThemeColors {
     ///
     /// Example: spaces, tabs, carriage returns, etc.
     pub editor_invisible: Hsla,
+    /// Text Color. Used to mark tabs in the editor.
+    pub editor_invisible_tab: Hsla,
+    /// Text Color. Used to mark spaces in the editor.
+    pub editor_invisible_space: Hsla,
     pub editor_wrap_guide: Hsla,
     pub editor_active_wrap_guide: Hsla,
     pub editor_indent_guide: Hsla,
@@ -396,6 +400,8 @@ pub enum ThemeColorField {
     EditorLineNumber,
     EditorActiveLineNumber,
     EditorInvisible,
+    EditorInvisibleTab,
+    EditorInvisibleSpace,
     EditorWrapGuide,
     EditorActiveWrapGuide,
     EditorIndentGuide,
@@ -516,6 +522,8 @@ impl ThemeColors {
             ThemeColorField::EditorLineNumber => self.editor_line_number,
             ThemeColorField::EditorActiveLineNumber => self.editor_active_line_number,
             ThemeColorField::EditorInvisible => self.editor_invisible,
+            ThemeColorField::EditorInvisibleTab => self.editor_invisible_tab,
+            ThemeColorField::EditorInvisibleSpace => self.editor_invisible_space,
             ThemeColorField::EditorWrapGuide => self.editor_wrap_guide,
             ThemeColorField::EditorActiveWrapGuide => self.editor_active_wrap_guide,
             ThemeColorField::EditorIndentGuide => self.editor_indent_guide,
@@ -19,14 +19,14 @@ mod schema;
 mod settings;
 mod styles;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use ::settings::DEFAULT_DARK_THEME;
 use ::settings::IntoGpui;
 use ::settings::Settings;
 use ::settings::SettingsStore;
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use fallback_themes::apply_status_color_defaults;
 use fs::Fs;
 use gpui::BorrowAppContext;
@@ -57,6 +57,9 @@ pub use ::settings::{
 pub const CLIENT_SIDE_DECORATION_ROUNDING: Pixels = px(10.0);
 /// Defines window shadow size for platforms that use client side decorations.
 pub const CLIENT_SIDE_DECORATION_SHADOW: Pixels = px(10.0);
+/// Ends the name of a theme's high contrast variant, which is used in its
+/// place while the desktop prefers high contrast.
+pub const HIGH_CONTRAST_THEME_SUFFIX: &str = " High Contrast";
 
 /// The appearance of the theme.
 #[derive(Debug, PartialEq, Clone, Copy, Deserialize)]
@@ -124,6 +127,8 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut App) {
     let theme = GlobalTheme::configured_theme(cx);
     let icon_theme = GlobalTheme::configured_icon_theme(cx);
     cx.set_global(GlobalTheme { theme, icon_theme });
+    cx.on_accessibility_preferences_change(GlobalTheme::reload_theme)
+        .detach();
 
     let settings = ThemeSettings::get_global(cx);
 
@@ -438,6 +443,45 @@ pub async fn read_user_theme(theme_path: &Path, fs: Arc<dyn Fs>) -> Result<Theme
     Ok(theme_family)
 }
 
+/// Asynchronously reads a theme file that the `theme` setting points at,
+/// rejecting any value that doesn't match the theme schema.
+pub async fn read_theme_file(theme_path: &Path, fs: Arc<dyn Fs>) -> Result<ThemeFamilyContent> {
+    let bytes = fs
+        .load_bytes(theme_path)
+        .await
+        .with_context(|| format!("reading theme file {theme_path:?}"))?;
+    let value: serde_json::Value = serde_json_lenient::from_slice(&bytes)
+        .with_context(|| format!("parsing theme file {theme_path:?}"))?;
+
+    let schema = schemars::schema_for!(ThemeFamilyContent);
+    let validator = jsonschema::validator_for(schema.as_value())
+        .map_err(|error| anyhow::anyhow!("invalid theme schema: {error}"))?;
+    let errors = validator
+        .iter_errors(&value)
+        .map(|error| error.to_string())
+        .collect::<Vec<_>>();
+    anyhow::ensure!(
+        errors.is_empty(),
+        "theme file {theme_path:?} doesn't match the theme schema:\n{}",
+        errors.join("\n")
+    );
+
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Returns the path of the theme file that a `theme` setting value points at,
+/// if it is a path to a JSON file rather than the name of a theme.
+pub fn theme_file_path(theme_name: &str) -> Option<PathBuf> {
+    if !theme_name.ends_with(".json") {
+        return None;
+    }
+    let path = match theme_name.strip_prefix("~/") {
+        Some(relative_path) => util::paths::home_dir().join(relative_path),
+        None => PathBuf::from(theme_name),
+    };
+    path.is_absolute().then_some(path)
+}
+
 /// Asynchronously reads the icon theme from the specified path.
 pub async fn read_icon_theme(
     icon_theme_path: &Path,
@@ -464,10 +508,29 @@ impl GlobalTheme {
 
         let theme_name = theme_settings.theme.name(*system_appearance);
 
-        let theme = match themes.get(&theme_name.0) {
+        let theme_path = theme_file_path(&theme_name.0);
+        let theme = match theme_path
+            .as_ref()
+            .and_then(|theme_path| themes.get_from_theme_file(theme_path, *system_appearance))
+        {
+            Some(theme) => Ok(theme),
+            None => cx
+                .accessibility_preferences()
+                .high_contrast
+                .then(|| {
+                    themes
+                        .get(&format!("{}{HIGH_CONTRAST_THEME_SUFFIX}", theme_name.0))
+                        .ok()
+                })
+                .flatten()
+                .map(Ok)
+                .unwrap_or_else(|| themes.get(&theme_name.0)),
+        };
+        let theme = match theme {
             Ok(theme) => theme,
             Err(err) => {
-                if themes.extensions_loaded() {
+                // Theme files report their own errors when they fail to load.
+                if themes.extensions_loaded() && theme_path.is_none() {
                     log::error!("{err}");
                 }
                 themes
@@ -0,0 +1,588 @@
+use std::{ops::Range, path::PathBuf, sync::Arc};
+
+use anyhow::Result;
+use collections::HashMap;
+use editor::{Editor, EditorEvent};
+use fs::Fs;
+use gpui::{
+    App, Entity, EventEmitter, FocusHandle, Focusable, Hsla, PathPromptOptions, Subscription,
+    UniformListScrollHandle, UpdateGlobal, WeakEntity, Window, actions, uniform_list,
+};
+use serde_json::{Value, json};
+use settings::{SettingsStore, ThemeStyleContent, update_settings_file};
+use strum::IntoEnumIterator;
+use theme::{Appearance, ThemeColorField, ThemeSettings, try_parse_color};
+use ui::{Indicator, ListItem, ListItemSpacing, WithScrollbar, prelude::*};
+use util::ResultExt;
+use workspace::{Item, OpenOptions, Workspace};
+
+actions!(
+    theme_selector,
+    [
+        /// Opens an editor for the colors of the active theme.
+        OpenThemeEditor
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &OpenThemeEditor, window, cx| {
+            let fs = workspace.app_state().fs.clone();
+            let weak_workspace = cx.entity().downgrade();
+            let theme_editor = cx.new(|cx| ThemeEditor::new(weak_workspace, fs, window, cx));
+            workspace.add_item_to_active_pane(Box::new(theme_editor), None, true, window, cx)
+        });
+    })
+    .detach();
+}
+
+/// A color of the theme, as named in theme files.
+struct ThemeToken {
+    key: SharedString,
+    field: ThemeColorField,
+}
+
+/// Lists the colors of [`settings::ThemeColorsContent`] that the theme also
+/// resolves, in the order of the theme schema.
+fn theme_tokens() -> Vec<ThemeToken> {
+    let fields = ThemeColorField::iter()
+        .map(|field| (field.as_ref().to_string(), field))
+        .collect::<HashMap<_, _>>();
+    let schema = schemars::schema_for!(settings::ThemeColorsContent);
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|properties| properties.keys())
+        .filter_map(|key| {
+            let field = fields.get(&key.replace('.', "_"))?;
+            Some(ThemeToken {
+                key: key.clone().into(),
+                field: *field,
+            })
+        })
+        .collect()
+}
+
+fn hex_color(color: Hsla) -> String {
+    format!("#{:08x}", u32::from(color.to_rgb()))
+}
+
+/// Turns a theme name into an extension ID, like `one-dark-custom`.
+///
+/// Extension IDs only allow ASCII letters and digits, so names without any
+/// get a generic ID.
+fn extension_id(theme_name: &str) -> String {
+    let slug = theme_name
+        .split(|char: char| !char.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "theme-custom".to_string()
+    } else {
+        format!("{slug}-custom")
+    }
+}
+
+/// Applies the edited colors on top of the overrides a theme already had.
+fn merge_overrides(
+    overrides: Option<&ThemeStyleContent>,
+    edits: &serde_json::Map<String, Value>,
+) -> Result<ThemeStyleContent> {
+    let mut overrides = match overrides {
+        Some(overrides) => serde_json::to_value(overrides)?,
+        None => Value::Object(serde_json::Map::new()),
+    };
+    if let Value::Object(overrides) = &mut overrides {
+        overrides.extend(edits.clone());
+    }
+    Ok(serde_json::from_value(overrides)?)
+}
+
+/// Edits the colors of the theme that was active when it opened.
+///
+/// Every change is previewed by overriding the theme settings in memory. The
+/// changes can be kept as `theme_overrides` in the user settings, or exported
+/// as the scaffold of a theme extension; otherwise they are reverted when the
+/// editor closes.
+pub struct ThemeEditor {
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    theme_name: SharedString,
+    theme_appearance: Appearance,
+    /// The overrides of the edited theme from before the editor changed
+    /// anything.
+    original_overrides: Option<ThemeStyleContent>,
+    tokens: Vec<ThemeToken>,
+    filtered_token_indices: Vec<usize>,
+    selected_token_index: Option<usize>,
+    /// The edited colors, by their key in theme files.
+    edits: serde_json::Map<String, Value>,
+    saved: bool,
+    filter_editor: Entity<Editor>,
+    color_editor: Entity<Editor>,
+    list: UniformListScrollHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ThemeEditor {
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        fs: Arc<dyn Fs>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let theme = cx.theme().clone();
+        let filter_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Filter colors...", window, cx);
+            editor
+        });
+        let color_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("#rrggbbaa", window, cx);
+            editor
+        });
+        let subscriptions = vec![
+            cx.subscribe(&filter_editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::Edited { .. } = event {
+                    this.filter_tokens(cx);
+                }
+            }),
+            cx.subscribe(&color_editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::Edited { .. } = event {
+                    this.color_edited(cx);
+                }
+            }),
+        ];
+        cx.on_release(|this, cx| {
+            if !this.saved && !this.edits.is_empty() {
+                this.set_theme_overrides(this.original_overrides.clone(), cx);
+            }
+        })
+        .detach();
+
+        let tokens = theme_tokens();
+        Self {
+            workspace,
+            fs,
+            theme_name: theme.name.clone(),
+            theme_appearance: theme.appearance,
+            original_overrides: ThemeSettings::get_global(cx)
+                .theme_overrides
+                .get(theme.name.as_ref())
+                .cloned(),
+            filtered_token_indices: (0..tokens.len()).collect(),
+            tokens,
+            selected_token_index: None,
+            edits: serde_json::Map::new(),
+            saved: true,
+            filter_editor,
+            color_editor,
+            list: UniformListScrollHandle::new(),
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn filter_tokens(&mut self, cx: &mut Context<Self>) {
+        let query = self.filter_editor.read(cx).text(cx).trim().to_lowercase();
+        self.filtered_token_indices = self
+            .tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, token)| token.key.contains(&query))
+            .map(|(index, _)| index)
+            .collect();
+        cx.notify();
+    }
+
+    fn select_token(&mut self, token_index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(token) = self.tokens.get(token_index) else {
+            return;
+        };
+        self.selected_token_index = Some(token_index);
+        let color = hex_color(cx.theme().colors().color(token.field));
+        self.color_editor.update(cx, |editor, cx| {
+            editor.set_text(color, window, cx);
+        });
+        window.focus(&self.color_editor.focus_handle(cx), cx);
+        cx.notify();
+    }
+
+    fn color_edited(&mut self, cx: &mut Context<Self>) {
+        let Some(token) = self
+            .selected_token_index
+            .and_then(|index| self.tokens.get(index))
+        else {
+            return;
+        };
+        let text = self.color_editor.read(cx).text(cx).trim().to_string();
+        // Selecting a token fills in its current color, which isn't an edit.
+        if text == hex_color(cx.theme().colors().color(token.field)) {
+            return;
+        }
+        if try_parse_color(&text).is_err() {
+            cx.notify();
+            return;
+        }
+        self.edits
+            .insert(token.key.to_string(), Value::String(text));
+        self.saved = false;
+        self.preview(cx);
+    }
+
+    /// The overrides of the edited theme: those of the user settings, with the
+    /// edits on top.
+    fn overrides(&self) -> Result<ThemeStyleContent> {
+        merge_overrides(self.original_overrides.as_ref(), &self.edits)
+    }
+
+    /// Replaces the overrides of the edited theme in the current theme
+    /// settings, leaving any other change to them in place.
+    fn set_theme_overrides(&self, overrides: Option<ThemeStyleContent>, cx: &mut App) {
+        let mut theme_settings = ThemeSettings::get_global(cx).clone();
+        match overrides {
+            Some(overrides) => {
+                theme_settings
+                    .theme_overrides
+                    .insert(self.theme_name.to_string(), overrides);
+            }
+            None => {
+                theme_settings
+                    .theme_overrides
+                    .remove(self.theme_name.as_ref());
+            }
+        }
+        SettingsStore::update_global(cx, |store, _| store.override_global(theme_settings));
+    }
+
+    fn preview(&mut self, cx: &mut Context<Self>) {
+        let Some(overrides) = self.overrides().log_err() else {
+            return;
+        };
+        self.set_theme_overrides(Some(overrides), cx);
+        cx.notify();
+    }
+
+    fn reset(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.edits.clear();
+        self.saved = true;
+        self.set_theme_overrides(self.original_overrides.clone(), cx);
+        if let Some(token_index) = self.selected_token_index {
+            self.select_token(token_index, window, cx);
+        }
+        cx.notify();
+    }
+
+    fn save_overrides(&mut self, cx: &mut Context<Self>) {
+        let Some(overrides) = self.overrides().log_err() else {
+            return;
+        };
+        let theme_name = self.theme_name.to_string();
+        update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.theme.theme_overrides.insert(theme_name, overrides);
+        });
+        self.saved = true;
+        cx.notify();
+    }
+
+    /// The active theme, edits included, as the content of a theme family
+    /// file.
+    fn theme_family_content(&self, cx: &App) -> Value {
+        let theme = cx.theme();
+        let mut style = self
+            .tokens
+            .iter()
+            .map(|token| {
+                let color = hex_color(theme.colors().color(token.field));
+                (token.key.to_string(), Value::String(color))
+            })
+            .collect::<serde_json::Map<_, _>>();
+        let syntax = theme
+            .syntax()
+            .highlights
+            .iter()
+            .map(|(name, highlight)| {
+                let color = highlight.color.map(hex_color);
+                (name.clone(), json!({ "color": color }))
+            })
+            .collect::<serde_json::Map<_, _>>();
+        style.insert("syntax".to_string(), Value::Object(syntax));
+
+        let name = format!("{} Custom", self.theme_name);
+        let appearance = match self.theme_appearance {
+            Appearance::Light => "light",
+            Appearance::Dark => "dark",
+        };
+        json!({
+            "$schema": "https://zed.dev/schema/themes/v0.2.0.json",
+            "name": name,
+            "author": "",
+            "themes": [{
+                "name": name,
+                "appearance": appearance,
+                "style": style,
+            }],
+        })
+    }
+
+    fn export_extension(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let theme_family = self.theme_family_content(cx);
+        let extension_id = extension_id(&self.theme_name);
+        let manifest = format!(
+            "id = \"{extension_id}\"\n\
+             name = \"{} Custom\"\n\
+             version = \"0.0.1\"\n\
+             schema_version = 1\n\
+             authors = []\n\
+             description = \"A theme based on {}.\"\n\
+             repository = \"\"\n",
+            self.theme_name, self.theme_name
+        );
+        let destination_prompt = cx.prompt_for_paths(PathPromptOptions {
+            files: false,
+            directories: true,
+            multiple: false,
+            prompt: Some("Export Theme Extension".into()),
+        });
+        let fs = self.fs.clone();
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let Some(destination) = destination_prompt.await??.and_then(|mut paths| paths.pop())
+            else {
+                return Ok(());
+            };
+            let extension_dir = destination.join(&extension_id);
+            let themes_dir = extension_dir.join("themes");
+            fs.create_dir(&themes_dir).await?;
+            fs.atomic_write(extension_dir.join("extension.toml"), manifest)
+                .await?;
+            let theme_path: PathBuf = themes_dir.join(format!("{extension_id}.json"));
+            fs.atomic_write(
+                theme_path.clone(),
+                serde_json::to_string_pretty(&theme_family)?,
+            )
+            .await?;
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.open_abs_path(theme_path, OpenOptions::default(), window, cx)
+                })?
+                .await?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn render_tokens(
+        &mut self,
+        range: Range<usize>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<ListItem> {
+        let colors = cx.theme().colors();
+        range
+            .filter_map(|index| {
+                let token_index = *self.filtered_token_indices.get(index)?;
+                let token = self.tokens.get(token_index)?;
+                let color = colors.color(token.field);
+                let edited = self.edits.contains_key(token.key.as_ref());
+                Some(
+                    ListItem::new(token_index)
+                        .spacing(ListItemSpacing::Sparse)
+                        .toggle_state(self.selected_token_index == Some(token_index))
+                        .start_slot(render_swatch(color, cx))
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(Label::new(token.key.clone()))
+                                .when(edited, |this| {
+                                    this.child(Indicator::dot().color(Color::Modified))
+                                }),
+                        )
+                        .end_slot(
+                            Label::new(hex_color(color))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.select_token(token_index, window, cx);
+                        })),
+                )
+            })
+            .collect()
+    }
+
+    fn render_selected_token(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let token = self.tokens.get(self.selected_token_index?)?;
+        let color_text = self.color_editor.read(cx).text(cx);
+        let border_color = if try_parse_color(color_text.trim()).is_ok() {
+            cx.theme().colors().border
+        } else {
+            cx.theme().status().error
+        };
+        Some(
+            h_flex()
+                .gap_2()
+                .child(render_swatch(cx.theme().colors().color(token.field), cx))
+                .child(Label::new(token.key.clone()))
+                .child(
+                    div()
+                        .w(rems(12.))
+                        .px_2()
+                        .py_1()
+                        .border_1()
+                        .border_color(border_color)
+                        .rounded_sm()
+                        .child(self.color_editor.clone()),
+                ),
+        )
+    }
+}
+
+fn render_swatch(color: Hsla, cx: &App) -> impl IntoElement {
+    div()
+        .size_4()
+        .flex_none()
+        .rounded_sm()
+        .border_1()
+        .border_color(cx.theme().colors().border)
+        .bg(color)
+}
+
+impl Render for ThemeEditor {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let has_edits = !self.edits.is_empty();
+        v_flex()
+            .key_context("ThemeEditor")
+            .size_full()
+            .p_4()
+            .gap_3()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .justify_between()
+                    .child(
+                        v_flex()
+                            .child(Headline::new(format!("Editing {}", self.theme_name)))
+                            .child(
+                                Label::new("Changes are previewed as you make them.")
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(Button::new("reset", "Reset").disabled(!has_edits).on_click(
+                                cx.listener(|this, _, window, cx| {
+                                    this.reset(window, cx);
+                                }),
+                            ))
+                            .child(
+                                Button::new("save-overrides", "Save as Overrides")
+                                    .disabled(self.saved)
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.save_overrides(cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("export-extension", "Export as Extension")
+                                    .style(ButtonStyle::Filled)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.export_extension(window, cx);
+                                    })),
+                            ),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .h_8()
+                    .px_2()
+                    .gap_2()
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .rounded_md()
+                    .child(Icon::new(IconName::MagnifyingGlass).color(Color::Muted))
+                    .child(self.filter_editor.clone()),
+            )
+            .children(self.render_selected_token(cx))
+            .child(
+                v_flex().flex_1().overflow_y_hidden().child(
+                    uniform_list(
+                        "theme-editor-tokens",
+                        self.filtered_token_indices.len(),
+                        cx.processor(Self::render_tokens),
+                    )
+                    .flex_grow()
+                    .track_scroll(&self.list),
+                ),
+            )
+            .vertical_scrollbar_for(&self.list, window, cx)
+    }
+}
+
+impl EventEmitter<()> for ThemeEditor {}
+
+impl Focusable for ThemeEditor {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.filter_editor.focus_handle(cx)
+    }
+}
+
+impl Item for ThemeEditor {
+    type Event = ();
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        format!("Edit {}", self.theme_name).into()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Theme Editor Opened")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_id() {
+        assert_eq!(extension_id("One Dark"), "one-dark-custom");
+        assert_eq!(
+            extension_id("Gruvbox Dark (Hard)"),
+            "gruvbox-dark-hard-custom"
+        );
+        assert_eq!(extension_id("Café Noir"), "caf-noir-custom");
+        assert_eq!(extension_id("東京"), "theme-custom");
+    }
+
+    #[test]
+    fn test_merge_overrides() {
+        let edits = json!({ "border": "#ff0000ff", "text": "#00ff00ff" });
+        let Value::Object(edits) = edits else {
+            unreachable!()
+        };
+
+        let overrides = merge_overrides(None, &edits).unwrap();
+        assert_eq!(overrides.colors.border.as_deref(), Some("#ff0000ff"));
+        assert_eq!(overrides.colors.text.as_deref(), Some("#00ff00ff"));
+
+        let mut existing_overrides = ThemeStyleContent::default();
+        existing_overrides.colors.border = Some("#0000ffff".to_string());
+        existing_overrides.colors.background = Some("#ffffffff".to_string());
+        let overrides = merge_overrides(Some(&existing_overrides), &edits).unwrap();
+        assert_eq!(
+            overrides.colors.border.as_deref(),
+            Some("#ff0000ff"),
+            "Edits replace the existing overrides of the same color"
+        );
+        assert_eq!(overrides.colors.background.as_deref(), Some("#ffffffff"));
+        assert_eq!(overrides.colors.text.as_deref(), Some("#00ff00ff"));
+    }
+}
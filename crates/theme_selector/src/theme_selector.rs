@@ -1,4 +1,5 @@
 mod icon_theme_selector;
+mod theme_editor;
 
 use fs::Fs;
 use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
@@ -29,6 +30,7 @@ actions!(
 );
 
 pub fn init(cx: &mut App) {
+    theme_editor::init(cx);
     cx.on_action(|action: &zed_actions::theme_selector::Toggle, cx| {
         let action = action.clone();
         with_active_or_new_workspace(cx, move |workspace, window, cx| {
@@ -3,6 +3,7 @@ pub mod collab;
 mod onboarding_banner;
 mod title_bar_settings;
 mod update_version;
+mod window_title;
 
 #[cfg(feature = "stories")]
 mod stories;
@@ -10,7 +11,7 @@ mod stories;
 use crate::application_menu::{ApplicationMenu, show_menus};
 pub use platform_title_bar::{
     self, DraggedWindowTab, MergeAllWindows, MoveTabToNewWindow, PlatformTitleBar,
-    ShowNextWindowTab, ShowPreviousWindowTab,
+    ShowNextWindowTab, ShowPreviousWindowTab, title_bar_height,
 };
 
 #[cfg(not(target_os = "macos"))]
@@ -31,14 +32,14 @@ use gpui::{
 use onboarding_banner::OnboardingBanner;
 use project::{Project, git_store::GitStoreEvent, trusted_worktrees::TrustedWorktrees};
 use remote::RemoteConnectionOptions;
-use settings::Settings;
 use settings::WorktreeId;
+use settings::{Settings, SettingsStore, TitleBarNameTruncation};
 use std::sync::Arc;
 use theme::ActiveTheme;
 use title_bar_settings::TitleBarSettings;
 use ui::{
     Avatar, ButtonLike, Chip, ContextMenu, IconWithIndicator, Indicator, PopoverMenu,
-    PopoverMenuHandle, TintColor, Tooltip, prelude::*, utils::platform_title_bar_height,
+    PopoverMenuHandle, TintColor, Tooltip, prelude::*,
 };
 use update_version::UpdateVersion;
 use util::ResultExt;
@@ -49,6 +50,7 @@ use workspace::{
 use zed_actions::OpenRemote;
 
 pub use onboarding_banner::restore_banner;
+pub use window_title::{WindowTitleVariables, format_window_title};
 
 #[cfg(feature = "stories")]
 pub use stories::*;
@@ -57,6 +59,27 @@ const MAX_PROJECT_NAME_LENGTH: usize = 40;
 const MAX_BRANCH_NAME_LENGTH: usize = 40;
 const MAX_SHORT_SHA_LENGTH: usize = 8;
 
+fn truncate_name(name: &str, max_chars: usize, truncation: TitleBarNameTruncation) -> String {
+    match truncation {
+        TitleBarNameTruncation::End => util::truncate_and_trailoff(name, max_chars),
+        TitleBarNameTruncation::Start => util::truncate_and_remove_front(name, max_chars),
+        TitleBarNameTruncation::Middle => util::truncate_middle(name, max_chars),
+        TitleBarNameTruncation::DirectoriesFirst => {
+            let mut components = name.split('/').map(str::to_string).collect::<Vec<_>>();
+            let last_index = components.len() - 1;
+            for index in 0..last_index {
+                if components.join("/").chars().count() <= max_chars {
+                    break;
+                }
+                if let Some(first_char) = components[index].chars().next() {
+                    components[index] = first_char.to_string();
+                }
+            }
+            util::truncate_and_trailoff(&components.join("/"), max_chars)
+        }
+    }
+}
+
 actions!(
     collab,
     [
@@ -80,6 +103,7 @@ pub fn init(cx: &mut App) {
         };
         let item = cx.new(|cx| TitleBar::new("title-bar", workspace, window, cx));
         workspace.set_titlebar_item(item.into(), window, cx);
+        workspace.set_window_title_formatter(window_title::window_title, window, cx);
 
         workspace.register_action(|workspace, _: &SimulateUpdateAvailable, _window, cx| {
             if let Some(titlebar) = workspace
@@ -156,7 +180,7 @@ pub struct TitleBar {
 
 impl Render for TitleBar {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let title_bar_settings = *TitleBarSettings::get_global(cx);
+        let title_bar_settings = TitleBarSettings::get_global(cx).clone();
 
         let show_menus = show_menus(cx);
 
@@ -231,6 +255,13 @@ impl Render for TitleBar {
                 .into_any_element(),
         );
 
+        let zen_mode = self
+            .workspace
+            .upgrade()
+            .is_some_and(|workspace| workspace.read(cx).is_zen_mode());
+        self.platform_titlebar
+            .update(cx, |this, _| this.set_zen_mode(zen_mode));
+
         if show_menus {
             self.platform_titlebar.update(cx, |this, _| {
                 this.set_children(
@@ -240,7 +271,7 @@ impl Render for TitleBar {
                 );
             });
 
-            let height = platform_title_bar_height(window);
+            let height = title_bar_height(window, cx);
             let title_bar_color = self.platform_titlebar.update(cx, |platform_titlebar, cx| {
                 platform_titlebar.title_bar_color(window, cx)
             });
@@ -326,6 +357,24 @@ impl TitleBar {
                 _ => {}
             }),
         );
+        // The window title can include the branch and is configured by settings,
+        // neither of which the workspace observes on its own.
+        subscriptions.push(
+            cx.subscribe_in(&git_store, window, |this, _, event, window, cx| {
+                if matches!(
+                    event,
+                    GitStoreEvent::ActiveRepositoryChanged(_)
+                        | GitStoreEvent::RepositoryUpdated(_, _, true)
+                ) {
+                    this.update_window_title(window, cx);
+                }
+            }),
+        );
+        subscriptions.push(
+            cx.observe_global_in::<SettingsStore>(window, |this, window, cx| {
+                this.update_window_title(window, cx)
+            }),
+        );
         subscriptions.push(cx.observe(&user_store, |_a, _, cx| cx.notify()));
         if let Some(trusted_worktrees) = TrustedWorktrees::try_get_global(cx) {
             subscriptions.push(cx.subscribe(&trusted_worktrees, |_, _, _, cx| {
@@ -405,6 +454,14 @@ impl TitleBar {
         }
     }
 
+    fn update_window_title(&self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(workspace) = self.workspace.upgrade() {
+            workspace.update(cx, |workspace, cx| {
+                workspace.update_window_title(window, cx)
+            });
+        }
+    }
+
     fn worktree_count(&self, cx: &App) -> usize {
         self.project.read(cx).visible_worktrees(cx).count()
     }
@@ -723,8 +780,9 @@ impl TitleBar {
 
         let is_project_selected = name.is_some();
 
+        let name_truncation = TitleBarSettings::get_global(cx).name_truncation;
         let display_name = if let Some(ref name) = name {
-            util::truncate_and_trailoff(name, MAX_PROJECT_NAME_LENGTH)
+            truncate_name(name, MAX_PROJECT_NAME_LENGTH, name_truncation)
         } else {
             "Open Recent Project".to_string()
         };
@@ -773,13 +831,14 @@ impl TitleBar {
         let repository = self.get_repository_for_worktree(&effective_worktree, cx)?;
         let workspace = self.workspace.upgrade()?;
 
+        let name_truncation = TitleBarSettings::get_global(cx).name_truncation;
         let (branch_name, icon_info) = {
             let repo = repository.read(cx);
             let branch_name = repo
                 .branch
                 .as_ref()
                 .map(|branch| branch.name())
-                .map(|name| util::truncate_and_trailoff(name, MAX_BRANCH_NAME_LENGTH))
+                .map(|name| truncate_name(name, MAX_BRANCH_NAME_LENGTH, name_truncation))
                 .or_else(|| {
                     repo.head_commit.as_ref().map(|commit| {
                         commit
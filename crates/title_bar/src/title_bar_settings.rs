@@ -1,15 +1,17 @@
-use settings::{RegisterSetting, Settings, SettingsContent};
+use settings::{RegisterSetting, Settings, SettingsContent, TitleBarNameTruncation};
 
-#[derive(Copy, Clone, Debug, RegisterSetting)]
+#[derive(Clone, Debug, RegisterSetting)]
 pub struct TitleBarSettings {
     pub show_branch_icon: bool,
     pub show_onboarding_banner: bool,
     pub show_user_picture: bool,
     pub show_branch_name: bool,
     pub show_project_items: bool,
+    pub name_truncation: TitleBarNameTruncation,
     pub show_sign_in: bool,
     pub show_user_menu: bool,
     pub show_menus: bool,
+    pub window_title: Option<String>,
 }
 
 impl Settings for TitleBarSettings {
@@ -21,9 +23,11 @@ impl Settings for TitleBarSettings {
             show_user_picture: content.show_user_picture.unwrap(),
             show_branch_name: content.show_branch_name.unwrap(),
             show_project_items: content.show_project_items.unwrap(),
+            name_truncation: content.name_truncation.unwrap(),
             show_sign_in: content.show_sign_in.unwrap(),
             show_user_menu: content.show_user_menu.unwrap(),
             show_menus: content.show_menus.unwrap(),
+            window_title: content.window_title,
         }
     }
 }
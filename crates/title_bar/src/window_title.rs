@@ -0,0 +1,208 @@
+use gpui::App;
+use project::{Project, WorktreeSettings};
+use settings::{Settings, SettingsLocation};
+use util::rel_path::RelPath;
+use workspace::Workspace;
+
+use crate::title_bar_settings::TitleBarSettings;
+
+const SEPARATOR_VARIABLE: &str = "{separator}";
+const SEPARATOR: &str = " — ";
+const DIRTY_INDICATOR: &str = "●";
+
+/// The values substituted into the `title_bar.window_title` template.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WindowTitleVariables {
+    /// `{project}`: the names of the visible worktrees.
+    pub project: String,
+    /// `{file}`: the file name of the active item.
+    pub file: String,
+    /// `{path}`: the path of the active item, relative to its worktree.
+    pub path: String,
+    /// `{dirty}`: an indicator shown when the active item has unsaved changes.
+    pub dirty: String,
+    /// `{host}`: the remote host the project is opened on.
+    pub host: String,
+    /// `{branch}`: the git branch of the active repository.
+    pub branch: String,
+    /// `{collab}`: whether the project is shared with (↗) or by (↙) collaborators.
+    pub collab: String,
+}
+
+impl WindowTitleVariables {
+    fn get(&self, name: &str) -> Option<&str> {
+        Some(match name {
+            "project" => &self.project,
+            "file" => &self.file,
+            "path" => &self.path,
+            "dirty" => &self.dirty,
+            "host" => &self.host,
+            "branch" => &self.branch,
+            "collab" => &self.collab,
+            _ => return None,
+        })
+    }
+}
+
+/// Renders a window title template.
+///
+/// `{separator}` only renders when there is text on both sides of it, so that
+/// a template like `{file}{separator}{project}` doesn't leave a dangling
+/// separator when no file is open. Unknown variables are kept verbatim.
+pub fn format_window_title(template: &str, variables: &WindowTitleVariables) -> String {
+    let segments = template
+        .split(SEPARATOR_VARIABLE)
+        .map(|segment| expand_variables(segment, variables))
+        .filter(|segment| !segment.trim().is_empty())
+        .collect::<Vec<_>>();
+    segments.join(SEPARATOR).trim().to_string()
+}
+
+fn expand_variables(segment: &str, variables: &WindowTitleVariables) -> String {
+    let mut result = String::with_capacity(segment.len());
+    let mut rest = segment;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after_brace = &rest[start + 1..];
+        match after_brace
+            .find('}')
+            .and_then(|end| Some((end, variables.get(&after_brace[..end])?)))
+        {
+            Some((end, value)) => {
+                result.push_str(value);
+                rest = &after_brace[end + 1..];
+            }
+            None => {
+                result.push('{');
+                rest = after_brace;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Builds the window title for `workspace` from the user's template, or
+/// returns `None` to fall back to the default title.
+pub(crate) fn window_title(workspace: &Workspace, cx: &App) -> Option<String> {
+    let template = TitleBarSettings::get_global(cx).window_title.clone()?;
+    let variables = window_title_variables(workspace, cx);
+    Some(format_window_title(&template, &variables))
+}
+
+fn window_title_variables(workspace: &Workspace, cx: &App) -> WindowTitleVariables {
+    let project = workspace.project().read(cx);
+    let mut variables = WindowTitleVariables {
+        project: project_name(project, cx),
+        ..Default::default()
+    };
+
+    if let Some(item) = workspace.active_item(cx) {
+        if item.is_dirty(cx) {
+            variables.dirty = DIRTY_INDICATOR.to_string();
+        }
+        if let Some(project_path) = item.project_path(cx) {
+            variables.file = project_path
+                .path
+                .file_name()
+                .map(ToString::to_string)
+                .or_else(|| {
+                    Some(
+                        project
+                            .worktree_for_id(project_path.worktree_id, cx)?
+                            .read(cx)
+                            .root_name_str()
+                            .to_string(),
+                    )
+                })
+                .unwrap_or_default();
+            variables.path = project_path
+                .path
+                .display(project.path_style(cx))
+                .into_owned();
+        }
+    }
+
+    if let Some(options) = project.remote_connection_options(cx) {
+        variables.host = options.display_name();
+    }
+
+    if let Some(repository) = project.active_repository(cx)
+        && let Some(branch) = repository.read(cx).branch.as_ref()
+    {
+        variables.branch = branch.name().to_string();
+    }
+
+    if project.is_via_collab() {
+        variables.collab = "↙".to_string();
+    } else if project.is_shared() {
+        variables.collab = "↗".to_string();
+    }
+
+    variables
+}
+
+fn project_name(project: &Project, cx: &App) -> String {
+    let names = project
+        .visible_worktrees(cx)
+        .map(|worktree| {
+            let worktree = worktree.read(cx);
+            let settings_location = SettingsLocation {
+                worktree_id: worktree.id(),
+                path: RelPath::empty(),
+            };
+            match &WorktreeSettings::get(Some(settings_location), cx).project_name {
+                Some(name) => name.clone(),
+                None => worktree.root_name_str().to_string(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if names.is_empty() {
+        "empty project".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables() -> WindowTitleVariables {
+        WindowTitleVariables {
+            project: "zed".into(),
+            file: "main.rs".into(),
+            path: "src/main.rs".into(),
+            dirty: DIRTY_INDICATOR.into(),
+            branch: "main".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_format_window_title() {
+        assert_eq!(
+            format_window_title("{dirty}{file}{separator}{project} ({branch})", &variables()),
+            "●main.rs — zed (main)"
+        );
+        assert_eq!(
+            format_window_title("{path}{separator}{host}{separator}{project}", &variables()),
+            "src/main.rs — zed"
+        );
+        assert_eq!(
+            format_window_title(
+                "{file}{separator}{project}",
+                &WindowTitleVariables {
+                    project: "zed".into(),
+                    ..Default::default()
+                }
+            ),
+            "zed"
+        );
+        assert_eq!(
+            format_window_title("{unknown} {project} {", &variables()),
+            "{unknown} zed {"
+        );
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
 use gpui::{
     Action, AnyElement, App, Bounds, Corner, DismissEvent, Entity, EventEmitter, FocusHandle,
     Focusable, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, Size,
-    Subscription, anchored, canvas, prelude::*, px,
+    Subscription, accesskit::Role, anchored, canvas, prelude::*, px,
 };
 use menu::{SelectChild, SelectFirst, SelectLast, SelectNext, SelectParent, SelectPrevious};
 use settings::Settings;
@@ -1798,6 +1798,8 @@ impl ContextMenu {
 
         div()
             .id(("context-menu-child", ix))
+            .accessibility_role(Role::MenuItem)
+            .accessibility_label(label.clone())
             .when_some(documentation_aside.clone(), |this, documentation_aside| {
                 this.occlude()
                     .on_hover(cx.listener(move |menu, hovered, _, cx| {
@@ -2118,6 +2120,7 @@ impl Render for ContextMenu {
                 .child(
                     v_flex()
                         .id("context-menu")
+                        .accessibility_role(Role::Menu)
                         .max_h(vh(0.75, window))
                         .flex_shrink_0()
                         .child(menu_bounds_measure)
@@ -4,7 +4,7 @@ use component::{Component, ComponentScope, example_group_with_title, single_exam
 use gpui::{AnyElement, AnyView, ClickEvent, MouseButton, MouseDownEvent, Pixels, px};
 use smallvec::SmallVec;
 
-use crate::{Disclosure, prelude::*};
+use crate::{Disclosure, prelude::*, ui_density};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
 pub enum ListItemSpacing {
@@ -250,10 +250,12 @@ impl RenderOnce for ListItem {
                     .relative()
                     .gap_1()
                     .px(DynamicSpacing::Base06.rems(cx))
-                    .map(|this| match self.spacing {
-                        ListItemSpacing::Dense => this,
-                        ListItemSpacing::ExtraDense => this.py_neg_px(),
-                        ListItemSpacing::Sparse => this.py_1(),
+                    .map(|this| match (self.spacing, ui_density(cx)) {
+                        (ListItemSpacing::Dense, theme::UiDensity::Compact)
+                        | (ListItemSpacing::ExtraDense, _) => this.py_neg_px(),
+                        (ListItemSpacing::Dense, theme::UiDensity::Default) => this,
+                        (ListItemSpacing::Dense, theme::UiDensity::Comfortable) => this.py_0p5(),
+                        (ListItemSpacing::Sparse, _) => this.py(DynamicSpacing::Base04.rems(cx)),
                     })
                     .when(self.inset && !self.disabled, |this| {
                         this
@@ -1,6 +1,6 @@
 use std::cmp::Ordering;
 
-use gpui::{AnyElement, IntoElement, Stateful};
+use gpui::{AnyElement, Hsla, IntoElement, Stateful};
 use smallvec::SmallVec;
 
 use crate::prelude::*;
@@ -35,6 +35,9 @@ pub struct Tab {
     selected: bool,
     position: TabPosition,
     close_side: TabCloseSide,
+    offset: Pixels,
+    vertical: bool,
+    tint: Option<Hsla>,
     start_slot: Option<AnyElement>,
     end_slot: Option<AnyElement>,
     children: SmallVec<[AnyElement; 2]>,
@@ -50,6 +53,9 @@ impl Tab {
             selected: false,
             position: TabPosition::First,
             close_side: TabCloseSide::End,
+            offset: px(0.),
+            vertical: false,
+            tint: None,
             start_slot: None,
             end_slot: None,
             children: SmallVec::new(),
@@ -66,6 +72,27 @@ impl Tab {
         self
     }
 
+    /// Draws the tab shifted horizontally from where it's laid out, such as
+    /// while it slides into a new position.
+    pub fn offset(mut self, offset: Pixels) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Lays the tab out as a row of a vertical tab list, filling its width
+    /// instead of sizing to its content.
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+
+    /// Blends a color into the tab's background, such as to group the tabs
+    /// of related files.
+    pub fn tint(mut self, tint: impl Into<Option<Hsla>>) -> Self {
+        self.tint = tint.into();
+        self
+    }
+
     pub fn start_slot<E: IntoElement>(mut self, element: impl Into<Option<E>>) -> Self {
         self.start_slot = element.into().map(IntoElement::into_any_element);
         self
@@ -141,11 +168,17 @@ impl RenderOnce for Tab {
             }
         };
 
+        let tab_bg = match self.tint {
+            Some(tint) => tab_bg.blend(tint.opacity(0.2)),
+            None => tab_bg,
+        };
+
         self.div
             .h(Tab::container_height(cx))
             .bg(tab_bg)
             .border_color(cx.theme().colors().border)
             .map(|this| match self.position {
+                _ if self.vertical => this.w_full().when(!self.selected, |this| this.border_r_1()),
                 TabPosition::First => {
                     if self.selected {
                         this.pl_px().border_r_1().pb_px()
@@ -164,6 +197,9 @@ impl RenderOnce for Tab {
                 TabPosition::Middle(Ordering::Less) => this.border_l_1().pr_px().border_b_1(),
                 TabPosition::Middle(Ordering::Greater) => this.border_r_1().pl_px().border_b_1(),
             })
+            .when(self.offset != px(0.), |this| {
+                this.relative().left(self.offset)
+            })
             .cursor_pointer()
             .child(
                 h_flex()
@@ -174,7 +210,19 @@ impl RenderOnce for Tab {
                     .gap(DynamicSpacing::Base04.rems(cx))
                     .text_color(text_color)
                     .child(start_slot)
-                    .children(self.children)
+                    .map(|this| {
+                        if self.vertical {
+                            this.w_full().child(
+                                h_flex()
+                                    .flex_1()
+                                    .min_w_0()
+                                    .overflow_hidden()
+                                    .children(self.children),
+                            )
+                        } else {
+                            this.children(self.children)
+                        }
+                    })
                     .child(end_slot),
             )
     }
@@ -103,6 +103,28 @@ pub fn truncate_and_remove_front(s: &str, max_chars: usize) -> String {
     }
 }
 
+/// Removes characters from the middle of the string if its length is greater than `max_chars` and
+/// puts "..." in their place. Returns string unchanged if its length is smaller than max_chars.
+pub fn truncate_middle(s: &str, max_chars: usize) -> String {
+    debug_assert!(max_chars >= 5);
+
+    // If the string's byte length is <= max_chars, walking the string can be skipped since the
+    // number of chars is <= the number of bytes.
+    if s.len() <= max_chars {
+        return s.to_string();
+    }
+    let char_count = s.chars().count();
+    if char_count <= max_chars {
+        return s.to_string();
+    }
+    let prefix_char_length = max_chars.div_ceil(2);
+    let suffix_char_length = max_chars - prefix_char_length;
+    let char_index = |n: usize| s.char_indices().nth(n).map_or(s.len(), |(i, _)| i);
+    let prefix_end = char_index(prefix_char_length);
+    let suffix_start = char_index(char_count - suffix_char_length);
+    format!("{}…{}", &s[..prefix_end], &s[suffix_start..])
+}
+
 /// Takes only `max_lines` from the string and, if there were more than `max_lines-1`, appends a
 /// a newline and "..." to the string, so that `max_lines` are returned.
 /// Returns string unchanged if its length is smaller than max_lines.
@@ -1162,6 +1184,20 @@ mod tests {
         assert_eq!(truncate_and_remove_front("èèèèèè", 5), "…èèèèè");
     }
 
+    #[test]
+    fn test_truncate_middle() {
+        assert_eq!(truncate_middle("", 5), "");
+        assert_eq!(truncate_middle("aaaaaa", 7), "aaaaaa");
+        assert_eq!(truncate_middle("aaaaaa", 6), "aaaaaa");
+        assert_eq!(truncate_middle("abcdef", 5), "abc…ef");
+        assert_eq!(truncate_middle("èèèèèè", 6), "èèèèèè");
+        assert_eq!(truncate_middle("èèèèèè", 5), "èèè…èè");
+        assert_eq!(
+            truncate_middle("feature/long-branch-name", 10),
+            "featu…-name"
+        );
+    }
+
     #[test]
     fn test_numeric_prefix_str_method() {
         let target = "1a";
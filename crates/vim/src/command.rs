@@ -3,7 +3,7 @@ use collections::{HashMap, HashSet};
 use command_palette_hooks::{CommandInterceptItem, CommandInterceptResult};
 use editor::{
     Bias, Editor, EditorSettings, SelectionEffects, ToPoint,
-    actions::{SortLinesCaseInsensitive, SortLinesCaseSensitive},
+    actions::{SortLinesCaseInsensitive, SortLinesCaseSensitive, SortLinesDescending},
     display_map::ToDisplayPoint,
 };
 use futures::AsyncWriteExt as _;
@@ -1213,7 +1213,6 @@ impl VimCommand {
         }
     }
 
-    // TODO: ranges with search queries
     fn parse_range(query: &str) -> (Option<CommandRange>, String) {
         let mut chars = query.chars().peekable();
 
@@ -1299,6 +1298,22 @@ impl VimCommand {
                     offset: Self::parse_offset(chars),
                 })
             }
+            '/' | '?' => {
+                // An unterminated pattern is a search, like `:/foo`, rather
+                // than a range.
+                let mut lookahead = chars.clone();
+                let delimiter = lookahead.next()?;
+                let (pattern, terminated) = parse_vim_pattern(&mut lookahead, delimiter);
+                if !terminated {
+                    return None;
+                }
+                *chars = lookahead;
+                Some(Position::Search {
+                    pattern,
+                    backwards: delimiter == '?',
+                    offset: Self::parse_offset(chars),
+                })
+            }
             _ => None,
         }
     }
@@ -1330,10 +1345,27 @@ impl VimCommand {
 
 #[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq)]
 enum Position {
-    Line { row: u32, offset: i32 },
-    Mark { name: char, offset: i32 },
-    LastLine { offset: i32 },
-    CurrentLine { offset: i32 },
+    Line {
+        row: u32,
+        offset: i32,
+    },
+    Mark {
+        name: char,
+        offset: i32,
+    },
+    LastLine {
+        offset: i32,
+    },
+    CurrentLine {
+        offset: i32,
+    },
+    /// The next line after the cursor matching a pattern, or the previous one
+    /// when searching backwards, wrapping around the buffer.
+    Search {
+        pattern: String,
+        backwards: bool,
+        offset: i32,
+    },
 }
 
 impl Position {
@@ -1387,6 +1419,40 @@ impl Position {
                 .to_point(&snapshot.buffer_snapshot())
                 .row
                 .saturating_add_signed(*offset),
+            Position::Search {
+                pattern,
+                backwards,
+                offset,
+            } => {
+                let regex = Regex::new(pattern)?;
+                let buffer = snapshot.buffer_snapshot();
+                let current_row = editor
+                    .selections
+                    .newest_anchor()
+                    .head()
+                    .to_point(&buffer)
+                    .row;
+                let row_count = buffer.max_row().0 + 1;
+                let Some(row) = (1..=row_count)
+                    .map(|distance| {
+                        if *backwards {
+                            (current_row + row_count - distance) % row_count
+                        } else {
+                            (current_row + distance) % row_count
+                        }
+                    })
+                    .find(|row| {
+                        let line_end = Point::new(*row, buffer.line_len(MultiBufferRow(*row)));
+                        let line = buffer
+                            .text_for_range(Point::new(*row, 0)..line_end)
+                            .collect::<String>();
+                        regex.is_match(&line)
+                    })
+                else {
+                    anyhow::bail!("pattern not found: {pattern}");
+                };
+                row.saturating_add_signed(*offset)
+            }
         };
 
         Ok(MultiBufferRow(target).min(snapshot.buffer_snapshot().max_row()))
@@ -1751,6 +1817,7 @@ fn generate_commands(_: &App) -> Vec<VimCommand> {
             .bang(DeleteMarks::AllLocal)
             .args(|_, args| Some(DeleteMarks::Marks(args).boxed_clone())),
         VimCommand::new(("sor", "t"), SortLinesCaseSensitive)
+            .bang(SortLinesDescending)
             .range(select_range)
             .default_range(CommandRange::buffer()),
         VimCommand::new(("sort i", ""), SortLinesCaseInsensitive)
@@ -2051,6 +2118,35 @@ fn generate_positions(string: &str, query: &str) -> Vec<usize> {
     positions
 }
 
+/// Reads a vim pattern up to its closing delimiter, flipping escaped and
+/// unescaped parens. Returns whether the delimiter was found.
+fn parse_vim_pattern(chars: &mut Peekable<Chars>, delimiter: char) -> (String, bool) {
+    let mut search = String::new();
+    let mut escaped = false;
+
+    for c in chars.by_ref() {
+        if escaped {
+            escaped = false;
+            // unescape escaped parens
+            if c != '(' && c != ')' && c != delimiter {
+                search.push('\\')
+            }
+            search.push(c)
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == delimiter {
+            return (search, true);
+        } else {
+            // escape unescaped parens
+            if c == '(' || c == ')' {
+                search.push('\\')
+            }
+            search.push(c)
+        }
+    }
+    (search, false)
+}
+
 /// Applies a command to all lines matching a pattern.
 #[derive(Debug, PartialEq, Clone, Action)]
 #[action(namespace = vim, no_json, no_register)]
@@ -2097,29 +2193,7 @@ impl OnMatchingLines {
             !c.is_alphanumeric() && *c != '"' && *c != '|' && *c != '\'' && *c != '!'
         })?;
 
-        let mut search = String::new();
-        let mut escaped = false;
-
-        for c in query_chars.by_ref() {
-            if escaped {
-                escaped = false;
-                // unescape escaped parens
-                if c != '(' && c != ')' && c != delimiter {
-                    search.push('\\')
-                }
-                search.push(c)
-            } else if c == '\\' {
-                escaped = true;
-            } else if c == delimiter {
-                break;
-            } else {
-                // escape unescaped parens
-                if c == '(' || c == ')' {
-                    search.push('\\')
-                }
-                search.push(c)
-            }
-        }
+        let (search, _) = parse_vim_pattern(&mut query_chars, delimiter);
 
         Some((query_chars.collect::<String>(), range, search, invert))
     }
@@ -2888,6 +2962,21 @@ mod test {
         cx.shared_state().await.assert_eq("1\nˇ2 3 4\n1");
     }
 
+    #[gpui::test]
+    async fn test_command_search_ranges(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇa\nb\nc\nd\ne", Mode::Normal);
+        cx.simulate_keystrokes(": / c / , / d / d enter");
+        cx.assert_state("a\nb\nˇe", Mode::Normal);
+
+        cx.simulate_keystrokes(": ? a ? + 1 enter");
+        cx.assert_state("a\nˇb\ne", Mode::Normal);
+
+        cx.simulate_keystrokes(": / e / s / e / f enter");
+        cx.assert_state("a\nb\nˇf", Mode::Normal);
+    }
+
     #[gpui::test]
     async fn test_command_visual_replace(cx: &mut TestAppContext) {
         let mut cx = NeovimBackedTestContext::new(cx).await;
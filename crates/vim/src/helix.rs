@@ -45,6 +45,10 @@ actions!(
         /// Removes all but the one selection that was created last.
         /// `Newest` can eventually be `Primary`.
         HelixKeepNewestSelection,
+        /// Removes the selection that was created last, keeping the others.
+        HelixRemoveNewestSelection,
+        /// Shrinks each selection to exclude its leading and trailing whitespace.
+        HelixTrimSelections,
         /// Copies all selections below.
         HelixDuplicateBelow,
         /// Copies all selections above.
@@ -69,6 +73,8 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, Vim::helix_paste);
     Vim::action(editor, cx, Vim::helix_select_regex);
     Vim::action(editor, cx, Vim::helix_keep_newest_selection);
+    Vim::action(editor, cx, Vim::helix_remove_newest_selection);
+    Vim::action(editor, cx, Vim::helix_trim_selections);
     Vim::action(editor, cx, |vim, _: &HelixDuplicateBelow, window, cx| {
         let times = Vim::take_count(cx);
         vim.helix_duplicate_selections_below(times, window, cx);
@@ -743,6 +749,55 @@ impl Vim {
         });
     }
 
+    fn helix_remove_newest_selection(
+        &mut self,
+        _: &HelixRemoveNewestSelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            let display_map = editor.display_snapshot(cx);
+            let mut selections = editor.selections.all::<MultiBufferOffset>(&display_map);
+            if selections.len() < 2 {
+                return;
+            }
+            let newest_id = editor.selections.newest_anchor().id;
+            selections.retain(|selection| selection.id != newest_id);
+            editor.change_selections(Default::default(), window, cx, |s| s.select(selections));
+        });
+    }
+
+    fn helix_trim_selections(
+        &mut self,
+        _: &HelixTrimSelections,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            let display_map = editor.display_snapshot(cx);
+            let buffer = display_map.buffer_snapshot();
+            let mut selections = editor.selections.all::<MultiBufferOffset>(&display_map);
+            for selection in &mut selections {
+                let leading_whitespace: usize = buffer
+                    .chars_at(selection.start)
+                    .take_while(|char| char.is_whitespace())
+                    .map(char::len_utf8)
+                    .sum();
+                let start = (selection.start + leading_whitespace).min(selection.end);
+                let trailing_whitespace: usize = buffer
+                    .reversed_chars_at(selection.end)
+                    .take_while(|char| char.is_whitespace())
+                    .map(char::len_utf8)
+                    .sum();
+                let end = MultiBufferOffset(selection.end.0.saturating_sub(trailing_whitespace))
+                    .max(start);
+                selection.start = start;
+                selection.end = end;
+            }
+            editor.change_selections(Default::default(), window, cx, |s| s.select(selections));
+        });
+    }
+
     fn do_helix_substitute(&mut self, yank: bool, window: &mut Window, cx: &mut Context<Self>) {
         self.update_editor(cx, |vim, editor, cx| {
             editor.set_clip_at_line_ends(false, cx);
@@ -1561,6 +1616,18 @@ mod test {
         // cx.assert_state("ˇstuff one two one", Mode::HelixNormal);
     }
 
+    #[gpui::test]
+    async fn test_helix_remove_and_trim_selections(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("«  oneˇ» « two ˇ» «three  ˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("_");
+        cx.assert_state("  «oneˇ»  «twoˇ»  «threeˇ»  ", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-,");
+        cx.assert_state("  «oneˇ»  «twoˇ»  three  ", Mode::HelixNormal);
+    }
+
     #[gpui::test]
     async fn test_helix_select_next_match(cx: &mut gpui::TestAppContext) {
         let mut cx = VimTestContext::new(cx, true).await;
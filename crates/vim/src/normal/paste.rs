@@ -898,6 +898,34 @@ mod test {
         cx.shared_register('a').await.assert_eq(" over");
     }
 
+    #[gpui::test]
+    async fn test_named_registers_fill_numbered_registers(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+                The quick brown
+                fox jˇumps over
+                the lazy dog"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes("\" a d d \" _ d d \" 1 p");
+        cx.assert_state(
+            indoc! {"
+                The quick brown
+                ˇfox jumps over"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes("\" a p");
+        cx.assert_state(
+            indoc! {"
+                The quick brown
+                fox jumps over
+                ˇfox jumps over"},
+            Mode::Normal,
+        );
+    }
+
     #[gpui::test]
     async fn test_special_registers(cx: &mut gpui::TestAppContext) {
         let mut cx = NeovimBackedTestContext::new(cx).await;
@@ -871,6 +871,23 @@ mod test {
         cx.shared_state().await.assert_eq("aaaaaaabbbˇd");
     }
 
+    #[gpui::test]
+    async fn test_record_replay_command_palette(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇone\ntwo\nthree", Mode::Normal);
+        cx.simulate_keystrokes("q a cmd-shift-p");
+        cx.simulate_input("editor: delete line");
+        cx.simulate_keystrokes("enter");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("q");
+        cx.assert_state("ˇtwo\nthree", Mode::Normal);
+
+        cx.simulate_keystrokes("@ a");
+        cx.run_until_parked();
+        cx.assert_state("ˇthree", Mode::Normal);
+    }
+
     #[gpui::test]
     async fn test_repeat_clear(cx: &mut gpui::TestAppContext) {
         let mut cx = VimTestContext::new(cx, true).await;
@@ -1,12 +1,16 @@
 use crate::command::command_interceptor;
 use crate::motion::MotionKind;
+use crate::normal::paste::Paste;
 use crate::normal::repeat::Replayer;
 use crate::surrounds::SurroundsType;
 use crate::{ToggleMarksView, ToggleRegistersView, UseSystemClipboard, Vim, VimAddon, VimSettings};
 use crate::{motion::Motion, object::Object};
 use anyhow::Result;
 use collections::HashMap;
-use command_palette_hooks::{CommandPaletteFilter, GlobalCommandPaletteInterceptor};
+use command_palette_hooks::{
+    CommandPaletteEvent, CommandPaletteFilter, GlobalCommandPaletteInterceptor,
+    GlobalCommandPaletteObserver,
+};
 use db::{
     sqlez::{domain::Domain, thread_safe_connection::ThreadSafeConnection},
     sqlez_macros::sql,
@@ -109,6 +113,17 @@ pub enum Operator {
         opening: bool,
     },
     DeleteSurrounds,
+    /// Reads the opening tag that `ys` surrounds with, up to its `>`.
+    AddSurroundsTag {
+        target: SurroundsType,
+        tag: String,
+    },
+    /// Reads the opening tag that `cs` replaces a surround with, up to its
+    /// `>`.
+    ChangeSurroundsTag {
+        target: Object,
+        tag: String,
+    },
     Mark,
     Jump {
         line: bool,
@@ -229,6 +244,9 @@ pub struct VimGlobals {
     pub recorded_selection: RecordedSelection,
 
     pub recording_register: Option<char>,
+    /// The length of the recording when the command palette opened, so that
+    /// the keystrokes driving it can be replaced by the action it runs.
+    pub command_palette_recording_start: Option<usize>,
     pub last_recorded_register: Option<char>,
     pub last_replayed_register: Option<char>,
     pub replayer: Option<Replayer>,
@@ -692,6 +710,27 @@ impl MarksState {
 
 impl Global for VimGlobals {}
 
+fn observe_command_palette(event: CommandPaletteEvent<'_>, cx: &mut App) {
+    match event {
+        CommandPaletteEvent::Opened => {
+            let globals = Vim::globals(cx);
+            globals.command_palette_recording_start =
+                globals.recording_register.map(|recording_register| {
+                    globals
+                        .recordings
+                        .get(&recording_register)
+                        .map_or(0, Vec::len)
+                });
+        }
+        CommandPaletteEvent::Confirmed(action) => {
+            let action = action.boxed_clone();
+            // Wait for the keystroke that confirmed the palette to be recorded,
+            // so that it's replaced along with the others.
+            cx.defer(move |cx| Vim::globals(cx).observe_command_palette_action(action));
+        }
+    }
+}
+
 impl VimGlobals {
     pub(crate) fn register(cx: &mut App) {
         cx.set_global(VimGlobals::default());
@@ -728,6 +767,7 @@ impl VimGlobals {
                     filter.show_namespace(Vim::NAMESPACE);
                 });
                 GlobalCommandPaletteInterceptor::set(cx, command_interceptor);
+                GlobalCommandPaletteObserver::set(cx, observe_command_palette);
                 for window in cx.windows() {
                     if let Some(multi_workspace) = window.downcast::<MultiWorkspace>() {
                         multi_workspace
@@ -747,6 +787,7 @@ impl VimGlobals {
                 KeyBinding::set_vim_mode(cx, false);
                 *Vim::globals(cx) = VimGlobals::default();
                 GlobalCommandPaletteInterceptor::clear(cx);
+                GlobalCommandPaletteObserver::clear(cx);
                 CommandPaletteFilter::update_global(cx, |filter, _| {
                     filter.hide_namespace(Vim::NAMESPACE);
                 });
@@ -787,6 +828,9 @@ impl VimGlobals {
                 current.clipboard_selections.take();
                 let yanked = current.clone();
                 self.registers.insert('"', yanked);
+                if !is_yank && lower.is_ascii_lowercase() {
+                    self.shift_numbered_registers(content, kind);
+                }
             } else {
                 match lower {
                     '_' | ':' | '.' | '%' | '#' | '=' | '/' => {}
@@ -807,7 +851,12 @@ impl VimGlobals {
                     }
                     _ => {
                         self.registers.insert('"', content.clone());
-                        self.registers.insert(lower, content);
+                        self.registers.insert(lower, content.clone());
+                        // Like vim, deleting lines into a named register also
+                        // fills the numbered registers.
+                        if !is_yank && lower.is_ascii_lowercase() {
+                            self.shift_numbered_registers(content, kind);
+                        }
                     }
                 }
             }
@@ -831,16 +880,23 @@ impl VimGlobals {
                 if !contains_newline {
                     self.registers.insert('-', content.clone());
                 }
-                if kind.linewise() || contains_newline {
-                    let mut content = content;
-                    for i in '1'..='9' {
-                        if let Some(moved) = self.registers.insert(i, content) {
-                            content = moved;
-                        } else {
-                            break;
-                        }
-                    }
-                }
+                self.shift_numbered_registers(content, kind);
+            }
+        }
+    }
+
+    /// Pushes a delete onto `"1`, moving the older deletes down to `"9`, when
+    /// it spans lines.
+    fn shift_numbered_registers(&mut self, content: Register, kind: MotionKind) {
+        if !kind.linewise() && !content.text.contains('\n') {
+            return;
+        }
+        let mut content = content;
+        for i in '1'..='9' {
+            if let Some(moved) = self.registers.insert(i, content) {
+                content = moved;
+            } else {
+                break;
             }
         }
     }
@@ -928,6 +984,21 @@ impl VimGlobals {
         }
     }
 
+    fn observe_command_palette_action(&mut self, action: Box<dyn Action>) {
+        let Some(start) = self.command_palette_recording_start.take() else {
+            return;
+        };
+        if self.replayer.is_some() {
+            return;
+        }
+        let Some(recording_register) = self.recording_register else {
+            return;
+        };
+        let recording = self.recordings.entry(recording_register).or_default();
+        recording.truncate(start);
+        recording.push(ReplayableAction::Action(action));
+    }
+
     pub fn observe_insertion(&mut self, text: &Arc<str>, range_to_replace: Option<Range<isize>>) {
         if self.ignore_current_insertion {
             self.ignore_current_insertion = false;
@@ -1029,6 +1100,8 @@ impl Operator {
             Operator::AddSurrounds { .. } => "ys",
             Operator::ChangeSurrounds { .. } => "cs",
             Operator::DeleteSurrounds => "ds",
+            Operator::AddSurroundsTag { .. } => "ys",
+            Operator::ChangeSurroundsTag { .. } => "cs",
             Operator::Mark => "m",
             Operator::Jump { line: true } => "'",
             Operator::Jump { line: false } => "`",
@@ -1073,6 +1146,8 @@ impl Operator {
             Operator::Literal {
                 prefix: Some(prefix),
             } => format!("^V{}", make_visible(prefix)),
+            Operator::AddSurroundsTag { tag, .. } => format!("ys<{tag}"),
+            Operator::ChangeSurroundsTag { tag, .. } => format!("cs<{tag}"),
             Operator::AutoIndent => "=".to_string(),
             Operator::ShellCommand => "=".to_string(),
             Operator::HelixMatch => "m".to_string(),
@@ -1108,7 +1183,9 @@ impl Operator {
             | Operator::ChangeSurrounds {
                 target: Some(_), ..
             }
-            | Operator::DeleteSurrounds => true,
+            | Operator::DeleteSurrounds
+            | Operator::AddSurroundsTag { .. }
+            | Operator::ChangeSurroundsTag { .. } => true,
             Operator::Change
             | Operator::Delete
             | Operator::Yank
@@ -1173,6 +1250,8 @@ impl Operator {
             | Operator::Literal { .. }
             | Operator::AddSurrounds { .. }
             | Operator::ChangeSurrounds { .. }
+            | Operator::AddSurroundsTag { .. }
+            | Operator::ChangeSurroundsTag { .. }
             | Operator::Jump { .. }
             | Operator::Register
             | Operator::RecordRegister
@@ -1190,6 +1269,7 @@ struct RegisterMatch {
 pub struct RegistersViewDelegate {
     selected_index: usize,
     matches: Vec<RegisterMatch>,
+    editor: Option<WeakEntity<Editor>>,
 }
 
 impl PickerDelegate for RegistersViewDelegate {
@@ -1221,7 +1301,29 @@ impl PickerDelegate for RegistersViewDelegate {
         Task::ready(())
     }
 
-    fn confirm(&mut self, _: bool, _: &mut Window, _: &mut Context<Picker<Self>>) {}
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(vim) = self
+            .editor
+            .as_ref()
+            .and_then(|editor| editor.upgrade())
+            .and_then(|editor| editor.read(cx).addon::<VimAddon>().cloned())
+            .map(|addon| addon.entity)
+        else {
+            return;
+        };
+        let Some(name) = self
+            .matches
+            .get(self.selected_index)
+            .map(|register_match| register_match.name)
+        else {
+            return;
+        };
+        cx.emit(DismissEvent);
+        vim.update(cx, |vim, cx| {
+            vim.selected_register = Some(name);
+            vim.paste(&Paste::default(), window, cx);
+        });
+    }
 
     fn dismissed(&mut self, _: &mut Window, _: &mut Context<Picker<Self>>) {}
 
@@ -1333,7 +1435,7 @@ impl RegistersView {
                     })
                 }
             }
-            if let Some(editor) = editor {
+            if let Some(editor) = &editor {
                 let register = editor.update(cx, |editor, cx| {
                     globals.read_register(Some('%'), Some(editor), cx)
                 });
@@ -1358,6 +1460,7 @@ impl RegistersView {
         let delegate = RegistersViewDelegate {
             selected_index: 0,
             matches,
+            editor: editor.map(|editor| editor.downgrade()),
         };
 
         Picker::nonsearchable_uniform_list(delegate, window, cx)
@@ -1367,8 +1470,8 @@ impl RegistersView {
 }
 
 enum MarksMatchInfo {
-    Path(Arc<Path>),
-    Title(String),
+    /// A mark in another file, with the text of its line when it's known.
+    File { name: String, line: Option<String> },
     Content {
         line: String,
         highlights: Vec<(Range<usize>, HighlightStyle)>,
@@ -1437,6 +1540,8 @@ impl PickerDelegate for MarksViewDelegate {
         };
         cx.spawn(async move |picker, cx| {
             let mut matches = Vec::new();
+            let mut unopened_marks = Vec::new();
+            let fs = workspace.read_with(cx, |workspace, _| workspace.app_state().fs.clone());
             let _ = workspace.update(cx, |workspace, cx| {
                 let entity_id = cx.entity_id();
                 let Some(editor) = workspace
@@ -1526,26 +1631,37 @@ impl PickerDelegate for MarksViewDelegate {
                                 .and_then(|map| map.get(name))
                                 .and_then(|anchors| anchors.first())
                             {
-                                let Some((info, snapshot)) = workspace
+                                let Some((title, snapshot)) = workspace
                                     .items(cx)
                                     .filter_map(|item| item.act_as::<Editor>(cx))
                                     .map(|entity| entity.read(cx).buffer())
                                     .find(|buffer| buffer.entity_id().eq(entity_id))
                                     .map(|buffer| {
                                         (
-                                            MarksMatchInfo::Title(
-                                                buffer.read(cx).title(cx).to_string(),
-                                            ),
+                                            buffer.read(cx).title(cx).to_string(),
                                             buffer.read(cx).snapshot(cx),
                                         )
                                     })
                                 else {
                                     continue;
                                 };
+                                let position = anchor.to_point(&snapshot);
+                                let line = snapshot
+                                    .text_for_range(
+                                        Point::new(position.row, 0)
+                                            ..Point::new(
+                                                position.row,
+                                                snapshot.line_len(MultiBufferRow(position.row)),
+                                            ),
+                                    )
+                                    .collect::<String>();
                                 matches.push(MarksMatch {
                                     name: name.clone(),
-                                    position: anchor.to_point(&snapshot),
-                                    info,
+                                    position,
+                                    info: MarksMatchInfo::File {
+                                        name: title,
+                                        line: Some(line.trim().to_string()),
+                                    },
                                 });
                             }
                         }
@@ -1556,17 +1672,34 @@ impl PickerDelegate for MarksViewDelegate {
                                 .and_then(|map| map.get(name))
                                 .and_then(|points| points.first())
                             {
-                                let info = MarksMatchInfo::Path(path.clone());
+                                unopened_marks.push((matches.len(), path.clone()));
                                 matches.push(MarksMatch {
                                     name: name.clone(),
                                     position,
-                                    info,
+                                    info: MarksMatchInfo::File {
+                                        name: path.to_string_lossy().into_owned(),
+                                        line: None,
+                                    },
                                 });
                             }
                         }
                     }
                 }
             });
+            // Preview the marks of files that aren't open from their contents on disk.
+            for (match_index, path) in unopened_marks {
+                let Some(text) = fs.load(&path).await.log_err() else {
+                    continue;
+                };
+                if let Some(mark_match) = matches.get_mut(match_index)
+                    && let MarksMatchInfo::File { line, .. } = &mut mark_match.info
+                {
+                    *line = text
+                        .lines()
+                        .nth(mark_match.position.row as usize)
+                        .map(|text| text.trim().to_string());
+                }
+            }
             let _ = picker.update(cx, |picker, cx| {
                 matches.sort_by_key(|a| {
                     (
@@ -1644,20 +1777,23 @@ impl PickerDelegate for MarksViewDelegate {
         }
 
         let (right_output, right_runs): (String, Vec<_>) = match &mark_match.info {
-            MarksMatchInfo::Path(path) => {
-                let s = path.to_string_lossy().into_owned();
-                (
-                    s.clone(),
-                    vec![(0..s.len(), HighlightStyle::color(cx.theme().colors().text))],
-                )
+            MarksMatchInfo::File { name, line } => {
+                let mut output = name.clone();
+                let mut runs = vec![(
+                    0..output.len(),
+                    HighlightStyle::color(cx.theme().colors().text_muted),
+                )];
+                if let Some(line) = line {
+                    output.push_str("  ");
+                    let start = output.len();
+                    output.push_str(line);
+                    runs.push((
+                        start..output.len(),
+                        HighlightStyle::color(cx.theme().colors().text),
+                    ));
+                }
+                (output, runs)
             }
-            MarksMatchInfo::Title(title) => (
-                title.clone(),
-                vec![(
-                    0..title.len(),
-                    HighlightStyle::color(cx.theme().colors().text),
-                )],
-            ),
             MarksMatchInfo::Content { line, highlights } => (line.clone(), highlights.clone()),
         };
 
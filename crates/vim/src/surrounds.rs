@@ -4,11 +4,11 @@ use crate::{
     object::{Object, surrounding_markers},
     state::Mode,
 };
-use editor::{Bias, MultiBufferOffset, movement};
+use editor::{Bias, DisplayPoint, MultiBufferOffset, display_map::DisplaySnapshot, movement};
 use gpui::{Context, Window};
-use language::BracketPair;
+use language::{BracketPair, Selection};
 
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 
 /// A char-based surround pair definition.
 /// Single source of truth for all supported surround pairs.
@@ -92,8 +92,10 @@ impl Vim {
             editor.transact(window, cx, |editor, window, cx| {
                 editor.set_clip_at_line_ends(false, cx);
 
-                let pair = bracket_pair_for_str_vim(&text);
-                let surround = pair.end != surround_alias((*text).as_ref());
+                let tag_pair = tag_bracket_pair(&text);
+                let surround = tag_pair.is_none()
+                    && bracket_pair_for_str_vim(&text).end != surround_alias(&text);
+                let pair = tag_pair.unwrap_or_else(|| bracket_pair_for_str_vim(&text));
                 let display_map = editor.display_snapshot(cx);
                 let display_selections = editor.selections.all_adjusted_display(&display_map);
                 let mut edits = Vec::new();
@@ -179,6 +181,11 @@ impl Vim {
     ) {
         self.stop_recording(cx);
 
+        if text.as_ref() == "t" {
+            self.delete_surrounding_tags(window, cx);
+            return;
+        }
+
         // only legitimate surrounds can be removed
         let Some(first_char) = text.chars().next() else {
             return;
@@ -278,19 +285,23 @@ impl Vim {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if target == Object::Tag {
+            self.change_surrounding_tags(text, window, cx);
+            return;
+        }
         if let Some(will_replace_pair) = self.object_to_bracket_pair(target, cx) {
             self.stop_recording(cx);
             self.update_editor(cx, |_, editor, cx| {
                 editor.transact(window, cx, |editor, window, cx| {
                     editor.set_clip_at_line_ends(false, cx);
 
-                    let pair = bracket_pair_for_str_vim(&text);
+                    let tag_pair = tag_bracket_pair(&text);
 
                     // A single space should be added if the new surround is a
                     // bracket and not a quote (pair.start != pair.end) and if
                     // the bracket used is the opening bracket.
-                    let add_space =
-                        !(pair.start == pair.end) && (pair.end != surround_alias((*text).as_ref()));
+                    let add_space = tag_pair.is_none() && opening_bracket_adds_space(&text);
+                    let pair = tag_pair.unwrap_or_else(|| bracket_pair_for_str_vim(&text));
 
                     // Space should be preserved if either the surrounding
                     // characters being updated are quotes
@@ -425,6 +436,29 @@ impl Vim {
         cx: &mut Context<Self>,
     ) -> bool {
         let mut valid = false;
+        if object == Object::Tag {
+            self.update_editor(cx, |_, editor, cx| {
+                let display_map = editor.display_snapshot(cx);
+                let selections = editor.selections.all_adjusted_display(&display_map);
+                let anchors = selections
+                    .into_iter()
+                    .map(|selection| {
+                        let start = match surrounding_tag_ranges(&display_map, selection.clone()) {
+                            Some((open, _)) => {
+                                valid = true;
+                                open.start
+                            }
+                            None => selection.start.to_offset(&display_map, Bias::Left),
+                        };
+                        start..start
+                    })
+                    .collect::<Vec<_>>();
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_ranges(anchors);
+                });
+            });
+            return valid;
+        }
         if let Some(pair) = self.object_to_bracket_pair(object, cx) {
             self.update_editor(cx, |_, editor, cx| {
                 editor.transact(window, cx, |editor, window, cx| {
@@ -474,6 +508,81 @@ impl Vim {
         valid
     }
 
+    fn delete_surrounding_tags(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_editor(cx, |_, editor, cx| {
+            editor.transact(window, cx, |editor, window, cx| {
+                let display_map = editor.display_snapshot(cx);
+                let selections = editor.selections.all_display(&display_map);
+                let mut edits = Vec::new();
+                let mut anchors = Vec::new();
+
+                for selection in selections {
+                    let start = selection.start.to_offset(&display_map, Bias::Left);
+                    if let Some((open, close)) = surrounding_tag_ranges(&display_map, selection) {
+                        anchors.push(open.start..open.start);
+                        edits.push((open, ""));
+                        edits.push((close, ""));
+                    } else {
+                        anchors.push(start..start);
+                    }
+                }
+
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_ranges(anchors);
+                });
+                edits.sort_by_key(|(range, _)| range.start);
+                edits.dedup_by_key(|(range, _)| range.start);
+                editor.edit(edits, cx);
+            });
+        });
+    }
+
+    fn change_surrounding_tags(
+        &mut self,
+        text: Arc<str>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stop_recording(cx);
+        let tag_pair = tag_bracket_pair(&text);
+        let add_space = tag_pair.is_none() && opening_bracket_adds_space(&text);
+        let pair = tag_pair.unwrap_or_else(|| bracket_pair_for_str_vim(&text));
+        let (open_str, close_str) = if add_space {
+            (format!("{} ", pair.start), format!(" {}", pair.end))
+        } else {
+            (pair.start, pair.end)
+        };
+
+        self.update_editor(cx, |_, editor, cx| {
+            editor.transact(window, cx, |editor, window, cx| {
+                let display_map = editor.display_snapshot(cx);
+                let selections = editor.selections.all_display(&display_map);
+                let mut edits = Vec::new();
+                let mut anchors = Vec::new();
+
+                for selection in selections {
+                    let start = selection.start.to_offset(&display_map, Bias::Left);
+                    if let Some((open, close)) = surrounding_tag_ranges(&display_map, selection) {
+                        let anchor = display_map.buffer_snapshot().anchor_before(open.start);
+                        anchors.push(anchor..anchor);
+                        edits.push((open, open_str.clone()));
+                        edits.push((close, close_str.clone()));
+                    } else {
+                        let anchor = display_map.buffer_snapshot().anchor_before(start);
+                        anchors.push(anchor..anchor);
+                    }
+                }
+
+                edits.sort_by_key(|(range, _)| range.start);
+                edits.dedup_by_key(|(range, _)| range.start);
+                editor.edit(edits, cx);
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_anchor_ranges(anchors);
+                });
+            });
+        });
+    }
+
     fn object_to_bracket_pair(
         &self,
         object: Object,
@@ -547,6 +656,41 @@ impl Vim {
     }
 }
 
+/// The ranges of the opening and closing tags of the element around a
+/// selection.
+fn surrounding_tag_ranges(
+    map: &DisplaySnapshot,
+    selection: Selection<DisplayPoint>,
+) -> Option<(Range<MultiBufferOffset>, Range<MultiBufferOffset>)> {
+    let around = Object::Tag.range(map, selection.clone(), true, None)?;
+    let inner = Object::Tag.range(map, selection, false, None)?;
+    Some((
+        around.start.to_offset(map, Bias::Left)..inner.start.to_offset(map, Bias::Left),
+        inner.end.to_offset(map, Bias::Right)..around.end.to_offset(map, Bias::Right),
+    ))
+}
+
+/// The pair for surrounding with a tag, given its opening tag, like
+/// `<a href="#">`, as read after `t`.
+pub fn tag_bracket_pair(text: &str) -> Option<BracketPair> {
+    let contents = text.strip_prefix('<')?.strip_suffix('>')?;
+    let name = contents.split_whitespace().next()?;
+    Some(BracketPair {
+        start: text.to_string(),
+        end: format!("</{name}>"),
+        close: true,
+        surround: true,
+        newline: false,
+    })
+}
+
+/// Whether surrounding with `text` pads the contents with spaces, as opening
+/// brackets do.
+fn opening_bracket_adds_space(text: &str) -> bool {
+    let pair = bracket_pair_for_str_vim(text);
+    pair.start != pair.end && pair.end != surround_alias(text)
+}
+
 /// Convert an Object to its corresponding SurroundPair.
 fn object_to_surround_pair(object: Object) -> Option<SurroundPair> {
     let open = match object {
@@ -1678,6 +1822,29 @@ mod test {
         );
     }
 
+    #[gpui::test]
+    async fn test_surround_tags(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new_html(cx).await;
+
+        cx.set_state("<div><b>hˇi</b></div>", Mode::Normal);
+        cx.simulate_keystrokes("d s t");
+        cx.assert_state("<div>ˇhi</div>", Mode::Normal);
+
+        cx.set_state("<p>hˇi</p>", Mode::Normal);
+        cx.simulate_keystrokes("y s i w t e m >");
+        cx.assert_state("<p>ˇ<em>hi</em></p>", Mode::Normal);
+
+        cx.set_state("<b>hˇi</b>", Mode::Normal);
+        cx.simulate_keystrokes("c s t t s t r o n g >");
+        cx.assert_state("ˇ<strong>hi</strong>", Mode::Normal);
+
+        cx.simulate_keystrokes("c s t \"");
+        cx.assert_state("ˇ\"hi\"", Mode::Normal);
+
+        cx.simulate_keystrokes("c s \" t b >");
+        cx.assert_state("ˇ<b>hi</b>", Mode::Normal);
+    }
+
     #[test]
     fn test_surround_pair_for_char() {
         use super::{SURROUND_PAIRS, surround_pair_for_char_helix, surround_pair_for_char_vim};
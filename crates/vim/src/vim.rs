@@ -1914,28 +1914,67 @@ impl Vim {
             Some(Operator::Literal { prefix }) => {
                 self.handle_literal_input(prefix.unwrap_or_default(), &text, window, cx)
             }
-            Some(Operator::AddSurrounds { target }) => match self.mode {
-                Mode::Normal => {
-                    if let Some(target) = target {
+            Some(Operator::AddSurrounds { target }) => {
+                let target = match self.mode {
+                    Mode::Normal => target,
+                    Mode::Visual | Mode::VisualLine | Mode::VisualBlock => {
+                        Some(SurroundsType::Selection)
+                    }
+                    _ => {
+                        self.clear_operator(window, cx);
+                        return;
+                    }
+                };
+                if let Some(target) = target {
+                    if text.as_ref() == "t" {
+                        self.pop_operator(window, cx);
+                        let tag = String::new();
+                        self.push_operator(Operator::AddSurroundsTag { target, tag }, window, cx);
+                    } else {
                         self.add_surrounds(text, target, window, cx);
                         self.clear_operator(window, cx);
                     }
                 }
-                Mode::Visual | Mode::VisualLine | Mode::VisualBlock => {
-                    self.add_surrounds(text, SurroundsType::Selection, window, cx);
+            }
+            Some(Operator::AddSurroundsTag { target, mut tag }) => {
+                if text.as_ref() == ">" {
+                    self.add_surrounds(format!("<{tag}>").into(), target, window, cx);
                     self.clear_operator(window, cx);
+                } else {
+                    tag.push_str(&text);
+                    self.pop_operator(window, cx);
+                    self.push_operator(Operator::AddSurroundsTag { target, tag }, window, cx);
                 }
-                _ => self.clear_operator(window, cx),
-            },
+            }
             Some(Operator::ChangeSurrounds { target, opening }) => match self.mode {
                 Mode::Normal => {
                     if let Some(target) = target {
-                        self.change_surrounds(text, target, opening, window, cx);
-                        self.clear_operator(window, cx);
+                        if text.as_ref() == "t" {
+                            self.pop_operator(window, cx);
+                            let tag = String::new();
+                            self.push_operator(
+                                Operator::ChangeSurroundsTag { target, tag },
+                                window,
+                                cx,
+                            );
+                        } else {
+                            self.change_surrounds(text, target, opening, window, cx);
+                            self.clear_operator(window, cx);
+                        }
                     }
                 }
                 _ => self.clear_operator(window, cx),
             },
+            Some(Operator::ChangeSurroundsTag { target, mut tag }) => {
+                if text.as_ref() == ">" {
+                    self.change_surrounds(format!("<{tag}>").into(), target, true, window, cx);
+                    self.clear_operator(window, cx);
+                } else {
+                    tag.push_str(&text);
+                    self.pop_operator(window, cx);
+                    self.push_operator(Operator::ChangeSurroundsTag { target, tag }, window, cx);
+                }
+            }
             Some(Operator::DeleteSurrounds) => match self.mode {
                 Mode::Normal => {
                     self.delete_surrounds(text, window, cx);
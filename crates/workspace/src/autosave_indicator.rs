@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use gpui::{Context, IntoElement, ParentElement, Render, SharedString, Task, Window};
+use ui::prelude::*;
+
+use crate::{ItemHandle, StatusItemView};
+
+/// How long the status bar shows that an item was saved automatically.
+const INDICATOR_DURATION: Duration = Duration::from_secs(2);
+
+/// Shows in the status bar, for a moment, which item autosave saved last.
+#[derive(Default)]
+pub struct AutosaveIndicator {
+    saved_item_title: Option<SharedString>,
+    hide_task: Option<Task<()>>,
+}
+
+impl AutosaveIndicator {
+    pub(crate) fn show_saved(&mut self, title: SharedString, cx: &mut Context<Self>) {
+        self.saved_item_title = Some(title);
+        self.hide_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(INDICATOR_DURATION).await;
+            this.update(cx, |this, cx| {
+                this.saved_item_title = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+}
+
+impl Render for AutosaveIndicator {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .gap_1()
+            .when_some(self.saved_item_title.clone(), |this, title| {
+                this.child(
+                    Icon::new(IconName::Check)
+                        .size(IconSize::XSmall)
+                        .color(Color::Muted),
+                )
+                .child(
+                    Label::new(format!("Auto-saved {title}"))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+    }
+}
+
+impl StatusItemView for AutosaveIndicator {
+    fn set_active_pane_item(
+        &mut self,
+        _active_pane_item: Option<&dyn ItemHandle>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+}
@@ -0,0 +1,143 @@
+//! A window showing a single panel that was moved out of its dock, so that it
+//! can be placed on another screen.
+
+use std::sync::Arc;
+
+use gpui::{AnyWindowHandle, KeystrokeEvent, Subscription, WeakEntity};
+use ui::{Tooltip, prelude::*};
+use util::ResultExt as _;
+
+use crate::dock::{Dock, PanelHandle};
+
+pub struct DetachedPanel {
+    panel: Arc<dyn PanelHandle>,
+    dock: WeakEntity<Dock>,
+    workspace_window: AnyWindowHandle,
+    _subscriptions: [Subscription; 2],
+}
+
+impl DetachedPanel {
+    pub(crate) fn new(
+        panel: Arc<dyn PanelHandle>,
+        dock: WeakEntity<Dock>,
+        workspace_window: AnyWindowHandle,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let this = cx.weak_entity();
+        window.on_window_should_close(cx, move |_, cx| {
+            this.update(cx, |this, cx| this.attach_to_dock(cx)).ok();
+            true
+        });
+        let window_handle = window.window_handle();
+        let workspace_window_closed = cx.on_window_closed(move |cx| {
+            if !cx.windows().contains(&workspace_window) {
+                cx.defer(move |cx| {
+                    window_handle
+                        .update(cx, |_, window, _| window.remove_window())
+                        .ok();
+                });
+            }
+        });
+        let keystrokes = cx.observe_keystrokes(move |this, event, window, cx| {
+            if window.window_handle() == window_handle {
+                this.forward_keystroke(event, cx);
+            }
+        });
+        Self {
+            panel,
+            dock,
+            workspace_window,
+            _subscriptions: [workspace_window_closed, keystrokes],
+        }
+    }
+
+    /// Keystrokes that nothing in this window handled run the workspace's own
+    /// bindings in the workspace's window, which this window isn't part of.
+    fn forward_keystroke(&self, event: &KeystrokeEvent, cx: &mut App) {
+        if event.action.is_some() {
+            return;
+        }
+        let keystroke = event.keystroke.clone();
+        let workspace_window = self.workspace_window;
+        cx.defer(move |cx| {
+            workspace_window
+                .update(cx, |_, window, cx| {
+                    let mut context_stack = window.context_stack();
+                    let Some(workspace_context) = context_stack
+                        .iter()
+                        .position(|context| context.contains("Workspace"))
+                    else {
+                        return;
+                    };
+                    context_stack.truncate(workspace_context + 1);
+                    let (bindings, _) = cx
+                        .key_bindings()
+                        .borrow()
+                        .bindings_for_input(&[keystroke], &context_stack);
+                    if let Some(binding) = bindings.first() {
+                        window.activate_window();
+                        window.dispatch_action(binding.action().boxed_clone(), cx);
+                    }
+                })
+                .log_err();
+        });
+    }
+
+    fn dock_back(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        window.remove_window();
+        self.attach_to_dock(cx);
+    }
+
+    /// Docking needs the workspace's window, which can't be updated while
+    /// this window is.
+    fn attach_to_dock(&self, cx: &mut App) {
+        let dock = self.dock.clone();
+        let panel_id = self.panel.panel_id();
+        let workspace_window = self.workspace_window;
+        cx.defer(move |cx| {
+            workspace_window
+                .update(cx, |_, window, cx| {
+                    dock.update(cx, |dock, cx| dock.attach_panel(panel_id, window, cx))
+                        .ok();
+                })
+                .log_err();
+        });
+    }
+}
+
+impl Render for DetachedPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let ui_font = theme::setup_ui_font(window, cx);
+        let colors = cx.theme().colors();
+        let title = self
+            .panel
+            .icon_tooltip(window, cx)
+            .unwrap_or_else(|| self.panel.persistent_name());
+
+        v_flex()
+            .size_full()
+            .font(ui_font)
+            .text_color(colors.text)
+            .bg(colors.panel_background)
+            .child(
+                h_flex()
+                    .flex_none()
+                    .justify_between()
+                    .px_2()
+                    .py_1()
+                    .border_b_1()
+                    .border_color(colors.border)
+                    .child(Label::new(title).size(LabelSize::Small).color(Color::Muted))
+                    .child(
+                        IconButton::new("dock-back", IconName::Minimize)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Dock Back"))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.dock_back(window, cx);
+                            })),
+                    ),
+            )
+            .child(div().flex_1().min_h_0().child(self.panel.to_any()))
+    }
+}
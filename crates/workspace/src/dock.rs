@@ -1,22 +1,25 @@
-use crate::persistence::model::DockData;
+use crate::detached_panel::DetachedPanel;
+use crate::persistence::model::{DockData, SerializedDetachedPanel};
 use crate::{DraggedDock, Event, ModalLayer, Pane};
 use crate::{Workspace, status_bar::StatusItemView};
 use anyhow::Context as _;
 use client::proto;
 
 use gpui::{
-    Action, AnyView, App, Axis, Context, Corner, Entity, EntityId, EventEmitter, FocusHandle,
-    Focusable, IntoElement, KeyContext, MouseButton, MouseDownEvent, MouseUpEvent, ParentElement,
-    Render, SharedString, StyleRefinement, Styled, Subscription, WeakEntity, Window, deferred, div,
-    px,
+    Action, AnyView, App, Axis, Bounds, Context, Corner, Entity, EntityId, EventEmitter,
+    FocusHandle, Focusable, IntoElement, KeyContext, MouseButton, MouseDownEvent, MouseUpEvent,
+    ParentElement, Render, SharedString, Size, Spring, StyleRefinement, Styled, Subscription,
+    TitlebarOptions, WeakEntity, Window, WindowBounds, WindowHandle, WindowOptions, deferred, div,
+    px, size,
 };
 use settings::SettingsStore;
-use std::sync::Arc;
+use std::{mem, sync::Arc};
 use ui::{ContextMenu, Divider, DividerColor, IconButton, Tooltip, h_flex};
 use ui::{prelude::*, right_click_menu};
 use util::ResultExt as _;
 
 pub(crate) const RESIZE_HANDLE_SIZE: Pixels = px(6.);
+const DETACHED_WINDOW_SIZE: Size<Pixels> = size(px(800.), px(600.));
 
 pub enum PanelEvent {
     ZoomIn,
@@ -59,6 +62,10 @@ pub trait Panel: Focusable + EventEmitter<PanelEvent> + Render + Sized {
     fn enabled(&self, _cx: &App) -> bool {
         true
     }
+    /// Whether the panel can be moved out of its dock into a window of its own.
+    fn can_detach(&self, _cx: &App) -> bool {
+        false
+    }
 }
 
 pub trait PanelHandle: Send + Sync {
@@ -83,6 +90,7 @@ pub trait PanelHandle: Send + Sync {
     fn to_any(&self) -> AnyView;
     fn activation_priority(&self, cx: &App) -> u32;
     fn enabled(&self, cx: &App) -> bool;
+    fn can_detach(&self, cx: &App) -> bool;
     fn move_to_next_position(&self, window: &mut Window, cx: &mut App) {
         let current_position = self.position(window, cx);
         let next_position = [
@@ -187,6 +195,10 @@ where
     fn enabled(&self, cx: &App) -> bool {
         self.read(cx).enabled(cx)
     }
+
+    fn can_detach(&self, cx: &App) -> bool {
+        self.read(cx).can_detach(cx)
+    }
 }
 
 impl From<&dyn PanelHandle> for AnyView {
@@ -205,9 +217,11 @@ pub struct Dock {
     active_panel_index: Option<usize>,
     focus_handle: FocusHandle,
     pub(crate) serialized_dock: Option<DockData>,
+    /// Panels detached in the previous session, detached again once added.
+    pub(crate) serialized_detached_panels: Vec<SerializedDetachedPanel>,
     zoom_layer_open: bool,
     modal_layer: Entity<ModalLayer>,
-    _subscriptions: [Subscription; 2],
+    _subscriptions: [Subscription; 3],
 }
 
 impl Focusable for Dock {
@@ -262,6 +276,7 @@ impl DockPosition {
 
 struct PanelEntry {
     panel: Arc<dyn PanelHandle>,
+    detached_window: Option<WindowHandle<DetachedPanel>>,
     _subscriptions: [Subscription; 3],
 }
 
@@ -292,6 +307,21 @@ impl Dock {
                     dock.zoom_layer_open = is_zoomed;
                 }
             });
+            // The windows would otherwise keep showing panels of a workspace
+            // that is gone.
+            let release_subscription = cx.on_release(|dock: &mut Dock, cx| {
+                for detached_window in dock
+                    .panel_entries
+                    .iter()
+                    .filter_map(|entry| entry.detached_window)
+                {
+                    cx.defer(move |cx| {
+                        detached_window
+                            .update(cx, |_, window, _| window.remove_window())
+                            .ok();
+                    });
+                }
+            });
             Self {
                 position,
                 workspace: workspace.downgrade(),
@@ -299,8 +329,9 @@ impl Dock {
                 active_panel_index: None,
                 is_open: false,
                 focus_handle: focus_handle.clone(),
-                _subscriptions: [focus_subscription, zoom_subscription],
+                _subscriptions: [focus_subscription, zoom_subscription, release_subscription],
                 serialized_dock: None,
+                serialized_detached_panels: Vec::new(),
                 zoom_layer_open: false,
                 modal_layer,
             }
@@ -368,6 +399,10 @@ impl Dock {
             .find_map(|entry| entry.panel.to_any().downcast().ok())
     }
 
+    pub(crate) fn panels(&self) -> impl Iterator<Item = &Arc<dyn PanelHandle>> {
+        self.panel_entries.iter().map(|entry| &entry.panel)
+    }
+
     pub fn panel_index_for_type<T: Panel>(&self) -> Option<usize> {
         self.panel_entries
             .iter()
@@ -599,6 +634,7 @@ impl Dock {
             index,
             PanelEntry {
                 panel: Arc::new(panel.clone()),
+                detached_window: None,
                 _subscriptions: subscriptions,
             },
         );
@@ -615,6 +651,18 @@ impl Dock {
     }
 
     pub fn restore_state(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let detached_panels = mem::take(&mut self.serialized_detached_panels);
+        for detached_panel in detached_panels {
+            match self.panel_index_for_persistent_name(&detached_panel.panel_name, cx) {
+                Some(index) => {
+                    let panel_id = self.panel_entries[index].panel.panel_id();
+                    let bounds = detached_panel.window_bounds.map(|bounds| bounds.0);
+                    self.detach_panel(panel_id, bounds, window, cx);
+                }
+                None => self.serialized_detached_panels.push(detached_panel),
+            }
+        }
+
         if let Some(serialized) = self.serialized_dock.clone() {
             if let Some(active_panel) = serialized.active_panel.filter(|_| serialized.visible)
                 && let Some(idx) = self.panel_index_for_persistent_name(active_panel.as_str(), cx)
@@ -657,7 +705,12 @@ impl Dock {
                 }
             }
 
-            self.panel_entries.remove(panel_ix);
+            let entry = self.panel_entries.remove(panel_ix);
+            if let Some(detached_window) = entry.detached_window {
+                detached_window
+                    .update(cx, |_, window, _| window.remove_window())
+                    .ok();
+            }
             cx.notify();
 
             true
@@ -670,7 +723,125 @@ impl Dock {
         self.panel_entries.len()
     }
 
+    pub fn is_panel_detached(&self, panel_id: EntityId) -> bool {
+        self.panel_entries
+            .iter()
+            .any(|entry| entry.panel.panel_id() == panel_id && entry.detached_window.is_some())
+    }
+
+    /// Moves a panel out of the dock into a window of its own. The panel keeps
+    /// its place in the dock, where its button brings its window forward.
+    pub fn detach_panel(
+        &mut self,
+        panel_id: EntityId,
+        bounds: Option<WindowBounds>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(index) = self
+            .panel_entries
+            .iter()
+            .position(|entry| entry.panel.panel_id() == panel_id)
+        else {
+            return;
+        };
+        let panel = self.panel_entries[index].panel.clone();
+        if self.panel_entries[index].detached_window.is_some() || !panel.can_detach(cx) {
+            return;
+        }
+
+        if self.is_open && self.active_panel_index == Some(index) {
+            self.set_open(false, window, cx);
+        }
+        if panel.is_zoomed(window, cx) {
+            panel.set_zoomed(false, window, cx);
+        }
+
+        let title = panel
+            .icon_tooltip(window, cx)
+            .unwrap_or_else(|| panel.persistent_name());
+        let bounds = bounds.unwrap_or_else(|| WindowBounds::centered(DETACHED_WINDOW_SIZE, cx));
+        let dock = cx.weak_entity();
+        let workspace_window = window.window_handle();
+        let detached_window = cx.open_window(
+            WindowOptions {
+                titlebar: Some(TitlebarOptions {
+                    title: Some(title.into()),
+                    ..Default::default()
+                }),
+                window_bounds: Some(bounds),
+                window_background: cx.theme().window_background_appearance(),
+                ..Default::default()
+            },
+            |window, cx| cx.new(|cx| DetachedPanel::new(panel, dock, workspace_window, window, cx)),
+        );
+        match detached_window {
+            Ok(detached_window) => {
+                self.panel_entries[index].detached_window = Some(detached_window);
+                self.serialize_workspace(window, cx);
+                cx.notify();
+            }
+            Err(error) => log::error!("Failed to open a window for the panel: {error:#}"),
+        }
+    }
+
+    /// Moves a detached panel back into the dock, closing its window.
+    pub fn attach_panel(
+        &mut self,
+        panel_id: EntityId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(index) = self
+            .panel_entries
+            .iter()
+            .position(|entry| entry.panel.panel_id() == panel_id)
+        else {
+            return;
+        };
+        let Some(detached_window) = self.panel_entries[index].detached_window.take() else {
+            return;
+        };
+        // The window is already gone when it was closed by the user.
+        detached_window
+            .update(cx, |_, window, _| window.remove_window())
+            .ok();
+
+        self.activate_panel(index, window, cx);
+        self.set_open(true, window, cx);
+        self.serialize_workspace(window, cx);
+        cx.notify();
+    }
+
+    pub(crate) fn detached_windows(
+        &self,
+    ) -> impl Iterator<Item = (&'static str, WindowHandle<DetachedPanel>)> + '_ {
+        self.panel_entries
+            .iter()
+            .filter_map(|entry| Some((entry.panel.persistent_name(), entry.detached_window?)))
+    }
+
+    fn serialize_workspace(&self, window: &mut Window, cx: &mut App) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.serialize_workspace(window, cx);
+            })
+            .ok();
+    }
+
     pub fn activate_panel(&mut self, panel_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(detached_window) = self
+            .panel_entries
+            .get(panel_ix)
+            .and_then(|entry| entry.detached_window)
+        {
+            cx.defer(move |cx| {
+                detached_window
+                    .update(cx, |_, window, _| window.activate_window())
+                    .ok();
+            });
+        }
+
         if Some(panel_ix) != self.active_panel_index {
             if let Some(active_panel) = self.active_panel_entry() {
                 active_panel.panel.set_active(false, window, cx);
@@ -698,6 +869,7 @@ impl Dock {
     fn visible_entry(&self) -> Option<&PanelEntry> {
         if self.is_open {
             self.active_panel_entry()
+                .filter(|entry| entry.detached_window.is_none())
         } else {
             None
         }
@@ -785,6 +957,9 @@ impl Render for Dock {
         let dispatch_context = Self::dispatch_context();
         if let Some(entry) = self.visible_entry() {
             let size = entry.panel.size(window, cx);
+            // Panels slide in by revealing more of their full size, so resizing
+            // an open panel isn't animated.
+            let revealed = window.use_animated_value("panel-reveal", 0., 1., Spring::gentle());
 
             let position = self.position;
             let create_resize_handle = || {
@@ -849,13 +1024,14 @@ impl Render for Dock {
             div()
                 .key_context(dispatch_context)
                 .track_focus(&self.focus_handle(cx))
+                .on_drop(|_: &DraggedPanel, _, _| {})
                 .flex()
                 .bg(cx.theme().colors().panel_background)
                 .border_color(cx.theme().colors().border)
                 .overflow_hidden()
                 .map(|this| match self.position().axis() {
-                    Axis::Horizontal => this.w(size).h_full().flex_row(),
-                    Axis::Vertical => this.h(size).w_full().flex_col(),
+                    Axis::Horizontal => this.w(size * revealed).h_full().flex_row(),
+                    Axis::Vertical => this.h(size * revealed).w_full().flex_col(),
                 })
                 .map(|this| match self.position() {
                     DockPosition::Left => this.border_r_1(),
@@ -886,6 +1062,39 @@ impl Render for Dock {
     }
 }
 
+/// A panel dragged by its dock button. Dropping it outside of the docks moves
+/// it into a window of its own at the cursor.
+#[derive(Clone)]
+pub(crate) struct DraggedPanel {
+    pub(crate) dock: WeakEntity<Dock>,
+    pub(crate) panel_id: EntityId,
+    pub(crate) icon: IconName,
+}
+
+impl DraggedPanel {
+    pub(crate) fn detach(&self, window: &mut Window, cx: &mut App) {
+        let origin = window.bounds().origin + window.mouse_position();
+        let bounds = WindowBounds::Windowed(Bounds::new(origin, DETACHED_WINDOW_SIZE));
+        self.dock
+            .update(cx, |dock, cx| {
+                dock.detach_panel(self.panel_id, Some(bounds), window, cx)
+            })
+            .ok();
+    }
+}
+
+impl Render for DraggedPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .p_1()
+            .rounded_sm()
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().elevated_surface_background)
+            .child(Icon::new(self.icon).size(IconSize::Small))
+    }
+}
+
 impl PanelButtons {
     pub fn new(dock: Entity<Dock>, cx: &mut Context<Self>) -> Self {
         cx.observe(&dock, |_, _, cx| cx.notify()).detach();
@@ -924,9 +1133,12 @@ impl Render for PanelButtons {
                     .log_err()?;
                 let name = entry.panel.persistent_name();
                 let panel = entry.panel.clone();
+                let detached_window = entry.detached_window;
+                let is_detached = detached_window.is_some();
+                let dock_entity = self.dock.downgrade();
 
-                let is_active_button = Some(i) == active_index && is_open;
-                let (action, tooltip) = if is_active_button {
+                let is_active_button = (Some(i) == active_index && is_open) || is_detached;
+                let (action, tooltip) = if is_active_button && !is_detached {
                     let action = dock.toggle_action();
 
                     let tooltip: SharedString =
@@ -940,6 +1152,12 @@ impl Render for PanelButtons {
                 };
 
                 let focus_handle = dock.focus_handle(cx);
+                let dragged_panel =
+                    (entry.panel.can_detach(cx) && !is_detached).then(|| DraggedPanel {
+                        dock: dock_entity.clone(),
+                        panel_id: entry.panel.panel_id(),
+                        icon,
+                    });
 
                 Some(
                     right_click_menu(name)
@@ -951,6 +1169,16 @@ impl Render for PanelButtons {
                             ];
 
                             ContextMenu::build(window, cx, |mut menu, _, cx| {
+                                if is_detached {
+                                    let panel_id = panel.panel_id();
+                                    let dock = dock_entity.clone();
+                                    return menu.entry("Dock Back", None, move |window, cx| {
+                                        dock.update(cx, |dock, cx| {
+                                            dock.attach_panel(panel_id, window, cx)
+                                        })
+                                        .ok();
+                                    });
+                                }
                                 for position in POSITIONS {
                                     if position != dock_position
                                         && panel.position_is_valid(position, cx)
@@ -965,6 +1193,20 @@ impl Render for PanelButtons {
                                         )
                                     }
                                 }
+                                if panel.can_detach(cx) {
+                                    let panel_id = panel.panel_id();
+                                    let dock = dock_entity.clone();
+                                    menu = menu.separator().entry(
+                                        "Move to New Window",
+                                        None,
+                                        move |window, cx| {
+                                            dock.update(cx, |dock, cx| {
+                                                dock.detach_panel(panel_id, None, window, cx)
+                                            })
+                                            .ok();
+                                        },
+                                    );
+                                }
                                 menu
                             })
                         })
@@ -973,12 +1215,18 @@ impl Render for PanelButtons {
                         .trigger(move |is_active, _window, _cx| {
                             // Include active state in element ID to invalidate the cached
                             // tooltip when panel state changes (e.g., via keyboard shortcut)
-                            IconButton::new((name, is_active_button as u64), icon)
+                            let button = IconButton::new((name, is_active_button as u64), icon)
                                 .icon_size(IconSize::Small)
                                 .toggle_state(is_active_button)
                                 .on_click({
                                     let action = action.boxed_clone();
                                     move |_, window, cx| {
+                                        if let Some(detached_window) = detached_window {
+                                            detached_window
+                                                .update(cx, |_, window, _| window.activate_window())
+                                                .ok();
+                                            return;
+                                        }
                                         window.focus(&focus_handle, cx);
                                         window.dispatch_action(action.boxed_clone(), cx)
                                     }
@@ -987,7 +1235,15 @@ impl Render for PanelButtons {
                                     this.tooltip(move |_window, cx| {
                                         Tooltip::for_action(tooltip.clone(), &*action, cx)
                                     })
-                                })
+                                });
+                            div().id(name).child(button).when_some(
+                                dragged_panel,
+                                |this, dragged_panel| {
+                                    this.on_drag(dragged_panel, |dragged_panel, _, _, cx| {
+                                        cx.new(|_| dragged_panel.clone())
+                                    })
+                                },
+                            )
                         }),
                 )
             })
@@ -997,6 +1253,7 @@ impl Render for PanelButtons {
 
         h_flex()
             .gap_1()
+            .on_drop(|_: &DraggedPanel, _, _| {})
             .when(
                 has_buttons && dock.position == DockPosition::Bottom,
                 |this| this.child(Divider::vertical().color(DividerColor::Border)),
@@ -1031,6 +1288,7 @@ pub mod test {
         pub focus_handle: FocusHandle,
         pub size: Pixels,
         pub activation_priority: u32,
+        pub detachable: bool,
     }
     actions!(test_only, [ToggleTestPanel]);
 
@@ -1045,6 +1303,7 @@ pub mod test {
                 focus_handle: cx.focus_handle(),
                 size: px(300.),
                 activation_priority,
+                detachable: false,
             }
         }
     }
@@ -1112,6 +1371,10 @@ pub mod test {
         fn activation_priority(&self) -> u32 {
             self.activation_priority
         }
+
+        fn can_detach(&self, _cx: &App) -> bool {
+            self.detachable
+        }
     }
 
     impl Focusable for TestPanel {
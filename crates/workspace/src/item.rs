@@ -7,15 +7,15 @@ use crate::{
     searchable::SearchableItemHandle,
     workspace_settings::{AutosaveSetting, WorkspaceSettings},
 };
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use client::{Client, proto};
 use futures::{StreamExt, channel::mpsc};
 use gpui::{
     Action, AnyElement, AnyEntity, AnyView, App, AppContext, Context, Entity, EntityId,
-    EventEmitter, FocusHandle, Focusable, Font, HighlightStyle, Pixels, Point, Render,
+    EventEmitter, FocusHandle, Focusable, Font, HighlightStyle, Hsla, Pixels, Point, Render, Rgba,
     SharedString, Task, WeakEntity, Window,
 };
-use language::Capability;
+use language::{Capability, LanguageName, language_settings::AllLanguageSettings};
 use project::{Project, ProjectEntryId, ProjectPath};
 pub use settings::{
     ActivateOnClose, ClosePosition, RegisterSetting, Settings, SettingsLocation, ShowCloseButton,
@@ -32,7 +32,11 @@ use std::{
     time::Duration,
 };
 use ui::{Color, Icon, IntoElement, Label, LabelCommon};
-use util::ResultExt;
+use util::{
+    ResultExt,
+    paths::{PathMatcher, PathStyle},
+    rel_path::RelPath,
+};
 
 pub const LEADER_UPDATE_THROTTLE: Duration = Duration::from_millis(200);
 
@@ -59,6 +63,53 @@ pub struct ItemSettings {
     pub file_icons: bool,
     pub show_diagnostics: ShowDiagnostics,
     pub show_close_button: ShowCloseButton,
+    pub color_rules: Vec<TabColorRule>,
+}
+
+pub struct TabColorRule {
+    worktree: Option<String>,
+    /// Matches every path when there are no globs.
+    globs: Option<PathMatcher>,
+    color: Hsla,
+}
+
+impl TabColorRule {
+    fn new(rule: &settings::TabColorRule) -> Option<Self> {
+        let color = Rgba::try_from(rule.color.as_str())
+            .with_context(|| format!("Invalid tab color {:?}", rule.color))
+            .log_err()?;
+        let globs = if rule.globs.is_empty() {
+            None
+        } else {
+            Some(
+                PathMatcher::new(&rule.globs, PathStyle::local())
+                    .context("Invalid tab color globs")
+                    .log_err()?,
+            )
+        };
+        Some(Self {
+            worktree: rule.worktree.clone(),
+            globs,
+            color: color.into(),
+        })
+    }
+
+    fn matches(&self, worktree_name: &str, path: &RelPath) -> bool {
+        self.worktree
+            .as_ref()
+            .is_none_or(|worktree| worktree == worktree_name)
+            && self.globs.as_ref().is_none_or(|globs| globs.is_match(path))
+    }
+}
+
+impl ItemSettings {
+    /// The color of the first color rule matching the file, to tint its tab and project panel entry.
+    pub fn file_tint(&self, worktree_name: &str, path: &RelPath) -> Option<Hsla> {
+        self.color_rules
+            .iter()
+            .find(|rule| rule.matches(worktree_name, path))
+            .map(|rule| rule.color)
+    }
 }
 
 #[derive(RegisterSetting)]
@@ -89,6 +140,12 @@ impl Settings for ItemSettings {
             file_icons: tabs.file_icons.unwrap(),
             show_diagnostics: tabs.show_diagnostics.unwrap(),
             show_close_button: tabs.show_close_button.unwrap(),
+            color_rules: tabs
+                .color_rules
+                .iter()
+                .flatten()
+                .filter_map(TabColorRule::new)
+                .collect(),
         }
     }
 }
@@ -242,8 +299,27 @@ pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
     fn buffer_kind(&self, _cx: &App) -> ItemBufferKind {
         ItemBufferKind::None
     }
+    /// The language of the item's contents, whose settings, like autosave,
+    /// apply to the item.
+    fn language_name(&self, _cx: &App) -> Option<LanguageName> {
+        None
+    }
     fn set_nav_history(&mut self, _: ItemNavHistory, _window: &mut Window, _: &mut Context<Self>) {}
 
+    /// Shows the buffer font this much larger or smaller in this item,
+    /// following the font size of the pane it's in.
+    fn set_buffer_font_size_delta(
+        &mut self,
+        _delta: Pixels,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) {
+    }
+
+    /// Hides the decorations around the item's content, like an editor's
+    /// gutter, while the pane it's in is in zen mode.
+    fn set_zen_mode(&mut self, _zen_mode: bool, _window: &mut Window, _: &mut Context<Self>) {}
+
     fn can_split(&self) -> bool {
         false
     }
@@ -493,6 +569,8 @@ pub trait ItemHandle: 'static + Send {
         cx: &mut Context<Workspace>,
     );
     fn deactivated(&self, window: &mut Window, cx: &mut App);
+    fn set_buffer_font_size_delta(&self, delta: Pixels, window: &mut Window, cx: &mut App);
+    fn set_zen_mode(&self, zen_mode: bool, window: &mut Window, cx: &mut App);
     fn on_removed(&self, cx: &mut App);
     fn workspace_deactivated(&self, window: &mut Window, cx: &mut App);
     fn navigate(&self, data: Arc<dyn Any + Send>, window: &mut Window, cx: &mut App) -> bool;
@@ -541,6 +619,7 @@ pub trait ItemHandle: 'static + Send {
     fn pixel_position_of_cursor(&self, cx: &App) -> Option<Point<Pixels>>;
     fn downgrade_item(&self) -> Box<dyn WeakItemHandle>;
     fn workspace_settings<'a>(&self, cx: &'a App) -> &'a WorkspaceSettings;
+    fn autosave_setting(&self, cx: &App) -> AutosaveSetting;
     fn preserve_preview(&self, cx: &App) -> bool;
     fn include_in_nav_history(&self) -> bool;
     fn relay_action(&self, action: Box<dyn Action>, window: &mut Window, cx: &mut App);
@@ -656,6 +735,18 @@ impl<T: Item> ItemHandle for Entity<T> {
         }
     }
 
+    fn autosave_setting(&self, cx: &App) -> AutosaveSetting {
+        let language_name = self.read(cx).language_name(cx);
+        let project_path = self.project_path(cx);
+        let location = project_path.as_ref().map(|project_path| SettingsLocation {
+            worktree_id: project_path.worktree_id,
+            path: &project_path.path,
+        });
+        AllLanguageSettings::get(location, cx)
+            .language(location, language_name.as_ref(), cx)
+            .autosave
+    }
+
     fn project_entry_ids(&self, cx: &App) -> SmallVec<[ProjectEntryId; 3]> {
         let mut result = SmallVec::new();
         self.read(cx).for_each_project_item(cx, &mut |_, item| {
@@ -886,7 +977,7 @@ impl<T: Item> ItemHandle for Entity<T> {
                         }
 
                         ItemEvent::Edit => {
-                            let autosave = item.workspace_settings(cx).autosave;
+                            let autosave = item.autosave_setting(cx);
 
                             if let AutosaveSetting::AfterDelay { milliseconds } = autosave {
                                 let delay = Duration::from_millis(milliseconds.0);
@@ -896,12 +987,7 @@ impl<T: Item> ItemHandle for Entity<T> {
                                     window,
                                     cx,
                                     move |workspace, window, cx| {
-                                        Pane::autosave_item(
-                                            &item,
-                                            workspace.project().clone(),
-                                            window,
-                                            cx,
-                                        )
+                                        workspace.autosave_item(&item, window, cx)
                                     },
                                 );
                             }
@@ -918,7 +1004,7 @@ impl<T: Item> ItemHandle for Entity<T> {
                 window,
                 move |workspace, window, cx| {
                     if let Some(item) = weak_item.upgrade()
-                        && item.workspace_settings(cx).autosave == AutosaveSetting::OnFocusChange
+                        && item.autosave_setting(cx) == AutosaveSetting::OnFocusChange
                     {
                         // Only trigger autosave if focus has truly left the item.
                         // If focus is still within the item's hierarchy (e.g., moved to a context menu),
@@ -929,7 +1015,8 @@ impl<T: Item> ItemHandle for Entity<T> {
                         if !focus_handle.contains_focused(window, cx)
                             && !workspace.has_active_modal(window, cx)
                         {
-                            Pane::autosave_item(&item, workspace.project.clone(), window, cx)
+                            workspace
+                                .autosave_item(&item, window, cx)
                                 .detach_and_log_err(cx);
                         }
                     }
@@ -956,6 +1043,16 @@ impl<T: Item> ItemHandle for Entity<T> {
         self.update(cx, |this, cx| this.deactivated(window, cx));
     }
 
+    fn set_buffer_font_size_delta(&self, delta: Pixels, window: &mut Window, cx: &mut App) {
+        self.update(cx, |this, cx| {
+            this.set_buffer_font_size_delta(delta, window, cx)
+        });
+    }
+
+    fn set_zen_mode(&self, zen_mode: bool, window: &mut Window, cx: &mut App) {
+        self.update(cx, |this, cx| this.set_zen_mode(zen_mode, window, cx));
+    }
+
     fn on_removed(&self, cx: &mut App) {
         self.update(cx, |item, cx| item.on_removed(cx));
     }
@@ -0,0 +1,288 @@
+//! Named arrangements of a workspace's docks and pane splits, saved per project
+//! so that users can switch between them.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context as _, Result};
+use gpui::{App, AppContext as _, Axis, Context, Entity, Task, Window, px};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Member, Pane, PaneAxis, PaneGroup, Workspace, build_serialized_docks,
+    persistence::{self, model::DockStructure},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct WorkspaceLayout {
+    docks: DockStructure,
+    /// The size of every docked panel, by the panel's persistent name.
+    panel_sizes: BTreeMap<String, f32>,
+    center: LayoutMember,
+}
+
+/// The shape of the center pane group. Items aren't part of a layout: the
+/// ones open when it is restored are kept, in the panes that remain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum LayoutMember {
+    Axis {
+        axis: Axis,
+        flexes: Vec<f32>,
+        members: Vec<LayoutMember>,
+    },
+    Pane,
+}
+
+impl LayoutMember {
+    fn new(member: &Member) -> Self {
+        match member {
+            Member::Axis(PaneAxis {
+                axis,
+                members,
+                flexes,
+                ..
+            }) => LayoutMember::Axis {
+                axis: *axis,
+                flexes: flexes.lock().clone(),
+                members: members.iter().map(LayoutMember::new).collect(),
+            },
+            Member::Pane(_) => LayoutMember::Pane,
+        }
+    }
+}
+
+impl Workspace {
+    /// The names of the layouts saved for this workspace, in alphabetical order.
+    pub fn saved_layout_names(&self) -> Vec<String> {
+        let Some(database_id) = self.database_id() else {
+            return Vec::new();
+        };
+        persistence::DB
+            .workspace_layout_names(database_id)
+            .context("Loading workspace layout names")
+            .unwrap_or_else(|error| {
+                log::error!("{error:#}");
+                Vec::new()
+            })
+    }
+
+    /// Saves the current arrangement of the docks and panes under the given
+    /// name, replacing any layout saved with the same name.
+    pub fn save_layout(&self, name: String, window: &mut Window, cx: &mut App) -> Task<Result<()>> {
+        let Some(database_id) = self.database_id() else {
+            return Task::ready(Err(anyhow::anyhow!(
+                "Layouts can't be saved for this workspace"
+            )));
+        };
+
+        let mut panel_sizes = BTreeMap::new();
+        for dock in self.all_docks() {
+            for panel in dock.read(cx).panels() {
+                panel_sizes.insert(
+                    panel.persistent_name().to_string(),
+                    f32::from(panel.size(window, cx)),
+                );
+            }
+        }
+        let layout = WorkspaceLayout {
+            docks: build_serialized_docks(self, window, cx),
+            panel_sizes,
+            center: LayoutMember::new(&self.center.root),
+        };
+
+        cx.background_spawn(async move {
+            let layout = serde_json::to_string(&layout)?;
+            persistence::DB
+                .save_workspace_layout(database_id, name, layout)
+                .await
+        })
+    }
+
+    pub fn delete_layout(&self, name: String, cx: &App) -> Task<Result<()>> {
+        let Some(database_id) = self.database_id() else {
+            return Task::ready(Ok(()));
+        };
+        cx.background_spawn(async move {
+            persistence::DB
+                .delete_workspace_layout(database_id, name)
+                .await
+        })
+    }
+
+    /// Arranges the docks and panes like the layout saved under the given name.
+    pub fn restore_layout(
+        &mut self,
+        name: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let database_id = self
+            .database_id()
+            .context("Layouts can't be restored for this workspace")?;
+        let layout = persistence::DB
+            .workspace_layout(database_id, name)?
+            .with_context(|| format!("No layout named '{name}'"))?;
+        let layout: WorkspaceLayout =
+            serde_json::from_str(&layout).context("Deserializing workspace layout")?;
+
+        self.restore_center_layout(&layout.center, window, cx);
+
+        for (dock, dock_data) in [
+            (self.left_dock.clone(), layout.docks.left),
+            (self.bottom_dock.clone(), layout.docks.bottom),
+            (self.right_dock.clone(), layout.docks.right),
+        ] {
+            dock.update(cx, |dock, cx| {
+                for panel in dock.panels() {
+                    if let Some(size) = layout.panel_sizes.get(panel.persistent_name()) {
+                        panel.set_size(Some(px(*size)), window, cx);
+                    }
+                }
+                dock.serialized_dock = Some(dock_data);
+                dock.restore_state(window, cx);
+            });
+        }
+
+        self.serialize_workspace(window, cx);
+        cx.notify();
+        Ok(())
+    }
+
+    /// Reuses the existing center panes, in order, for the panes of the layout.
+    /// When the layout has fewer panes, the items of the ones left over move to
+    /// its last pane.
+    fn restore_center_layout(
+        &mut self,
+        layout: &LayoutMember,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut existing_panes = self
+            .center
+            .panes()
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>()
+            .into_iter();
+        let root = self.build_layout_member(layout, &mut existing_panes, window, cx);
+        let mut center = PaneGroup::with_root(root);
+        center.set_is_center(true);
+
+        let last_pane = center.last_pane();
+        for pane in existing_panes {
+            let items = pane.read(cx).items().cloned().collect::<Vec<_>>();
+            for item in items {
+                pane.update(cx, |pane, cx| {
+                    pane.remove_item(item.item_id(), false, false, window, cx)
+                });
+                last_pane.update(cx, |last_pane, cx| {
+                    last_pane.add_item(item, false, false, None, window, cx)
+                });
+            }
+            self.force_remove_pane(&pane, &None, window, cx);
+        }
+
+        self.center = center;
+        self.center.mark_positions(cx);
+        if !self.center.panes().contains(&&self.active_pane) {
+            let first_pane = self.center.first_pane();
+            self.set_active_pane(&first_pane, window, cx);
+        }
+    }
+
+    fn build_layout_member(
+        &mut self,
+        layout: &LayoutMember,
+        existing_panes: &mut impl Iterator<Item = Entity<Pane>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Member {
+        match layout {
+            LayoutMember::Pane => Member::Pane(
+                existing_panes
+                    .next()
+                    .unwrap_or_else(|| self.add_pane(window, cx)),
+            ),
+            LayoutMember::Axis {
+                axis,
+                flexes,
+                members,
+            } => {
+                let members = members
+                    .iter()
+                    .map(|member| self.build_layout_member(member, existing_panes, window, cx))
+                    .collect();
+                Member::Axis(PaneAxis::load(*axis, members, Some(flexes.clone())))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SplitDirection, item::test::TestItem};
+    use gpui::TestAppContext;
+    use project::{FakeFs, Project};
+
+    #[gpui::test]
+    async fn test_save_and_restore_layout(cx: &mut TestAppContext) {
+        crate::tests::init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let database_id = persistence::DB.next_id().await.unwrap();
+        workspace.update(cx, |workspace, _| workspace.database_id = Some(database_id));
+
+        let item = cx.new(TestItem::new);
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(item.clone()), None, true, window, cx);
+        });
+
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.save_layout("single".into(), window, cx)
+            })
+            .await
+            .unwrap();
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let pane = workspace.active_pane().clone();
+            workspace.split_pane(pane, SplitDirection::Right, window, cx);
+        });
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.save_layout("split".into(), window, cx)
+            })
+            .await
+            .unwrap();
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert_eq!(workspace.saved_layout_names(), ["single", "split"]);
+            workspace.restore_layout("single", window, cx).unwrap();
+            assert_eq!(workspace.center.panes().len(), 1);
+            assert_eq!(workspace.panes().len(), 1);
+            assert!(
+                workspace
+                    .active_pane()
+                    .read(cx)
+                    .items()
+                    .any(|open_item| { open_item.item_id() == item.entity_id() })
+            );
+
+            workspace.restore_layout("split", window, cx).unwrap();
+            assert_eq!(workspace.center.panes().len(), 2);
+            assert!(workspace.restore_layout("missing", window, cx).is_err());
+        });
+
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace.delete_layout("split".into(), cx)
+            })
+            .await
+            .unwrap();
+        workspace.read_with(cx, |workspace, _| {
+            assert_eq!(workspace.saved_layout_names(), ["single"]);
+        });
+    }
+}
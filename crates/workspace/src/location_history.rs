@@ -0,0 +1,266 @@
+//! A history of the places edited and navigated to, shared by every pane of a
+//! workspace, unlike the back and forward history that each pane keeps.
+
+use std::{collections::VecDeque, sync::Arc};
+
+use gpui::WeakEntity;
+use parking_lot::Mutex;
+use project::ProjectPath;
+
+use crate::{Pane, pane::NavigationEntry};
+
+const MAX_LOCATION_HISTORY_LEN: usize = 100;
+
+/// Locations in the same file at most this many rows apart are merged, so that
+/// typing a few lines doesn't fill the history.
+const MERGED_LOCATION_ROW_DELTA: u32 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LocationKind {
+    Edit,
+    Navigation,
+}
+
+#[derive(Clone)]
+pub struct Location {
+    pub pane: WeakEntity<Pane>,
+    pub entry: NavigationEntry,
+    pub project_path: ProjectPath,
+    pub row: u32,
+    pub kind: LocationKind,
+}
+
+impl Location {
+    fn is_near(&self, project_path: &ProjectPath, row: u32) -> bool {
+        self.project_path == *project_path && self.row.abs_diff(row) <= MERGED_LOCATION_ROW_DELTA
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct LocationHistory(Arc<Mutex<LocationHistoryState>>);
+
+#[derive(Default)]
+struct LocationHistoryState {
+    locations: VecDeque<Location>,
+    /// The index of the location navigated to last, or the number of
+    /// locations when the history hasn't been navigated since the last push.
+    position: usize,
+    navigating: bool,
+}
+
+impl LocationHistory {
+    pub fn push(&self, location: Location) {
+        let mut state = self.0.lock();
+        if state.navigating {
+            return;
+        }
+
+        // Like in a browser, moving on from a location navigated to drops the
+        // locations after it.
+        let retained_len = (state.position + 1).min(state.locations.len());
+        state.locations.truncate(retained_len);
+        let merged = state.locations.back_mut().is_some_and(|last| {
+            let mergeable = last.is_near(&location.project_path, location.row);
+            if mergeable {
+                let kind = if last.kind == LocationKind::Edit {
+                    LocationKind::Edit
+                } else {
+                    location.kind
+                };
+                *last = Location {
+                    kind,
+                    ..location.clone()
+                };
+            }
+            mergeable
+        });
+        if !merged {
+            if state.locations.len() >= MAX_LOCATION_HISTORY_LEN {
+                state.locations.pop_front();
+            }
+            state.locations.push_back(location);
+        }
+        state.position = state.locations.len();
+    }
+
+    /// Moves to the location of `kind` before or after the one navigated to
+    /// last, returning it. Locations that `current` would be merged into are
+    /// skipped, since going to them wouldn't move anywhere.
+    pub fn step(
+        &self,
+        forward: bool,
+        kind: LocationKind,
+        current: Option<(&ProjectPath, u32)>,
+    ) -> Option<Location> {
+        let mut state = self.0.lock();
+        let mut position = state.position;
+        loop {
+            position = if forward {
+                position.checked_add(1)?
+            } else {
+                position.checked_sub(1)?
+            };
+            let location = state.locations.get(position)?;
+            let is_current =
+                current.is_some_and(|(project_path, row)| location.is_near(project_path, row));
+            if location.kind == kind && !is_current {
+                let location = location.clone();
+                state.position = position;
+                return Some(location);
+            }
+        }
+    }
+
+    /// Moves to the location at `index` in [`Self::locations`], returning it.
+    pub fn select(&self, index: usize) -> Option<Location> {
+        let mut state = self.0.lock();
+        let location = state.locations.get(index)?.clone();
+        state.position = index;
+        Some(location)
+    }
+
+    /// Every location, from the oldest to the most recent.
+    pub fn locations(&self) -> Vec<Location> {
+        self.0.lock().locations.iter().cloned().collect()
+    }
+
+    /// The index of the location navigated to last, if the history was
+    /// navigated since the last push.
+    pub fn position(&self) -> Option<usize> {
+        let state = self.0.lock();
+        (state.position < state.locations.len()).then_some(state.position)
+    }
+
+    /// While navigating, moving between items would push the locations left,
+    /// which would drop the locations after the one navigated to.
+    pub(crate) fn set_navigating(&self, navigating: bool) {
+        self.0.lock().navigating = navigating;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::test::TestItem;
+    use gpui::{AppContext as _, Entity, TestAppContext};
+    use project::WorktreeId;
+    use util::rel_path::rel_path;
+
+    fn location(item: &Entity<TestItem>, path: &str, row: u32, kind: LocationKind) -> Location {
+        Location {
+            pane: WeakEntity::new_invalid(),
+            entry: NavigationEntry {
+                item: Arc::new(item.downgrade()),
+                data: None,
+                timestamp: 0,
+                is_preview: false,
+            },
+            project_path: (WorktreeId::from_usize(1), rel_path(path)).into(),
+            row,
+            kind,
+        }
+    }
+
+    fn rows(history: &LocationHistory) -> Vec<(u32, LocationKind)> {
+        history
+            .locations()
+            .iter()
+            .map(|location| (location.row, location.kind))
+            .collect()
+    }
+
+    #[gpui::test]
+    fn test_nearby_locations_are_merged(cx: &mut TestAppContext) {
+        let item = cx.new(TestItem::new);
+        let history = LocationHistory::default();
+
+        history.push(location(&item, "a.rs", 10, LocationKind::Edit));
+        history.push(location(&item, "a.rs", 15, LocationKind::Navigation));
+        history.push(location(&item, "a.rs", 40, LocationKind::Navigation));
+        history.push(location(&item, "b.rs", 40, LocationKind::Edit));
+        assert_eq!(
+            rows(&history),
+            [
+                (15, LocationKind::Edit),
+                (40, LocationKind::Navigation),
+                (40, LocationKind::Edit)
+            ]
+        );
+    }
+
+    #[gpui::test]
+    fn test_stepping_through_locations(cx: &mut TestAppContext) {
+        let item = cx.new(TestItem::new);
+        let history = LocationHistory::default();
+        for row in [0, 100, 200] {
+            history.push(location(&item, "a.rs", row, LocationKind::Edit));
+        }
+
+        // The most recent location is where the cursor already is.
+        let current_path = (WorktreeId::from_usize(1), rel_path("a.rs")).into();
+        let previous = history.step(false, LocationKind::Edit, Some((&current_path, 201)));
+        assert_eq!(previous.map(|location| location.row), Some(100));
+        assert_eq!(
+            history
+                .step(false, LocationKind::Edit, None)
+                .map(|location| location.row),
+            Some(0)
+        );
+        assert!(history.step(false, LocationKind::Edit, None).is_none());
+        assert_eq!(
+            history
+                .step(true, LocationKind::Edit, None)
+                .map(|location| location.row),
+            Some(100)
+        );
+        assert_eq!(history.position(), Some(1));
+
+        // Moving on from a location drops the ones after it.
+        history.push(location(&item, "b.rs", 0, LocationKind::Edit));
+        assert_eq!(
+            rows(&history),
+            [
+                (0, LocationKind::Edit),
+                (100, LocationKind::Edit),
+                (0, LocationKind::Edit)
+            ]
+        );
+        assert_eq!(history.position(), None);
+        assert!(history.step(true, LocationKind::Edit, None).is_none());
+    }
+
+    #[gpui::test]
+    fn test_stepping_skips_other_kinds_of_locations(cx: &mut TestAppContext) {
+        let item = cx.new(TestItem::new);
+        let history = LocationHistory::default();
+        history.push(location(&item, "a.rs", 0, LocationKind::Edit));
+        history.push(location(&item, "a.rs", 100, LocationKind::Navigation));
+        history.push(location(&item, "b.rs", 0, LocationKind::Navigation));
+        history.push(location(&item, "b.rs", 100, LocationKind::Edit));
+
+        assert_eq!(
+            history
+                .step(false, LocationKind::Edit, None)
+                .map(|location| location.row),
+            Some(100)
+        );
+        assert_eq!(history.position(), Some(3));
+        assert_eq!(
+            history
+                .step(false, LocationKind::Edit, None)
+                .map(|location| location.row),
+            Some(0)
+        );
+        assert_eq!(history.position(), Some(0));
+        assert!(history.step(false, LocationKind::Edit, None).is_none());
+        assert_eq!(
+            history
+                .step(true, LocationKind::Edit, None)
+                .map(|location| (
+                    location.project_path.path.as_unix_str().to_string(),
+                    location.row
+                )),
+            Some(("b.rs".to_string(), 100))
+        );
+    }
+}
@@ -8,20 +8,21 @@ use crate::{
         PreviewTabsSettings, ProjectItemKind, SaveOptions, ShowCloseButton, ShowDiagnostics,
         TabContentParams, TabTooltipContent, WeakItemHandle,
     },
+    location_history::{Location, LocationHistory, LocationKind},
     move_item,
     notifications::NotifyResultExt,
     toolbar::Toolbar,
-    workspace_settings::{AutosaveSetting, TabBarSettings, WorkspaceSettings},
+    workspace_settings::{AutosaveSetting, TabBarPlacement, TabBarSettings, WorkspaceSettings},
 };
 use anyhow::Result;
 use collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use futures::{StreamExt, stream::FuturesUnordered};
 use gpui::{
-    Action, AnyElement, App, AsyncWindowContext, ClickEvent, ClipboardItem, Context, Corner, Div,
-    DragMoveEvent, Entity, EntityId, EventEmitter, ExternalPaths, FocusHandle, FocusOutEvent,
-    Focusable, KeyContext, MouseButton, NavigationDirection, Pixels, Point, PromptLevel, Render,
-    ScrollHandle, Subscription, Task, WeakEntity, WeakFocusHandle, Window, actions, anchored,
-    deferred, prelude::*,
+    Action, AnimatedValue, AnyElement, App, AsyncWindowContext, Bounds, ClickEvent, ClipboardItem,
+    Context, Corner, Div, DragMoveEvent, Entity, EntityId, EventEmitter, ExternalPaths,
+    FocusHandle, FocusOutEvent, Focusable, KeyContext, MouseButton, NavigationDirection, Pixels,
+    Point, PromptLevel, Render, ScrollHandle, Spring, Subscription, SwipeEvent, Task, TouchPhase,
+    WeakEntity, WeakFocusHandle, Window, actions, anchored, canvas, deferred, prelude::*,
 };
 use itertools::Itertools;
 use language::{Capability, DiagnosticSeverity};
@@ -273,6 +274,12 @@ actions!(
         TogglePinTab,
         /// Unpins all tabs in the pane.
         UnpinAllTabs,
+        /// Increases the buffer font size in this pane only.
+        IncreaseBufferFontSize,
+        /// Decreases the buffer font size in this pane only.
+        DecreaseBufferFontSize,
+        /// Resets the buffer font size in this pane to the global one.
+        ResetBufferFontSize,
     ]
 );
 
@@ -288,6 +295,11 @@ impl DeploySearch {
 
 const MAX_NAVIGATION_HISTORY_LEN: usize = 1024;
 
+/// How far a multi-finger swipe has to travel across a pane to switch tabs.
+const SWIPE_TAB_DISTANCE: Pixels = px(100.);
+
+const VERTICAL_TAB_BAR_WIDTH: Pixels = px(240.);
+
 pub enum Event {
     AddItem {
         item: Box<dyn ItemHandle>,
@@ -308,6 +320,7 @@ pub enum Event {
     },
     ItemPinned,
     ItemUnpinned,
+    BufferFontSizeChanged,
     JoinAll,
     JoinIntoNext,
     ChangeItemTitle,
@@ -354,6 +367,7 @@ impl fmt::Debug for Event {
                 .finish(),
             Event::ItemPinned => f.write_str("ItemPinned"),
             Event::ItemUnpinned => f.write_str("ItemUnpinned"),
+            Event::BufferFontSizeChanged => f.write_str("BufferFontSizeChanged"),
         }
     }
 }
@@ -390,6 +404,7 @@ pub struct Pane {
     can_toggle_zoom: bool,
     should_display_tab_bar: Rc<dyn Fn(&Window, &mut Context<Pane>) -> bool>,
     should_display_welcome_page: bool,
+    can_show_vertical_tab_bar: bool,
     render_tab_bar_buttons: Rc<
         dyn Fn(
             &mut Pane,
@@ -403,6 +418,13 @@ pub struct Pane {
     use_max_tabs: bool,
     _subscriptions: Vec<Subscription>,
     tab_bar_scroll_handle: ScrollHandle,
+    /// How far tabs that were just reordered are drawn from their new
+    /// position, as they slide into it.
+    tab_move_animations: HashMap<EntityId, AnimatedValue<Pixels>>,
+    /// How far the ongoing multi-finger swipe moved horizontally.
+    swipe_distance: Pixels,
+    /// Where the pane was painted in the window during the last frame.
+    bounds: Bounds<Pixels>,
     /// This is set to true if a user scroll has occurred more recently than a system scroll
     /// We want to suppress certain system scrolls when the user has intentionally scrolled
     suppress_scroll: bool,
@@ -415,6 +437,12 @@ pub struct Pane {
     pub new_item_context_menu_handle: PopoverMenuHandle<ContextMenu>,
     pub split_item_context_menu_handle: PopoverMenuHandle<ContextMenu>,
     pinned_tab_count: usize,
+    /// How much larger or smaller than the global buffer font size the items
+    /// of this pane show it.
+    buffer_font_size_delta: Pixels,
+    /// Whether the tab bar and the decorations of the items are hidden, while
+    /// the workspace is in zen mode.
+    zen_mode: bool,
     diagnostics: HashMap<ProjectPath, DiagnosticSeverity>,
     zoom_out_on_close: bool,
     diagnostic_summary_update: Task<()>,
@@ -451,6 +479,7 @@ struct NavHistoryState {
     pane: WeakEntity<Pane>,
     next_timestamp: Arc<AtomicUsize>,
     preview_item_id: Option<EntityId>,
+    location_history: Option<LocationHistory>,
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -559,9 +588,13 @@ impl Pane {
                 pane: handle,
                 next_timestamp,
                 preview_item_id: None,
+                location_history: None,
             }))),
             toolbar: cx.new(|_| Toolbar::new()),
             tab_bar_scroll_handle: ScrollHandle::new(),
+            tab_move_animations: HashMap::default(),
+            swipe_distance: px(0.),
+            bounds: Bounds::default(),
             suppress_scroll: false,
             drag_split_direction: None,
             workspace,
@@ -572,6 +605,7 @@ impl Pane {
             can_toggle_zoom: true,
             should_display_tab_bar: Rc::new(|_, cx| TabBarSettings::get_global(cx).show),
             should_display_welcome_page: false,
+            can_show_vertical_tab_bar: false,
             render_tab_bar_buttons: Rc::new(default_render_tab_bar_buttons),
             render_tab_bar: Rc::new(Self::render_tab_bar),
             show_tab_bar_buttons: TabBarSettings::get_global(cx).show_tab_bar_buttons,
@@ -585,6 +619,8 @@ impl Pane {
             split_item_context_menu_handle: Default::default(),
             new_item_context_menu_handle: Default::default(),
             pinned_tab_count: 0,
+            buffer_font_size_delta: Pixels::ZERO,
+            zen_mode: false,
             diagnostics: Default::default(),
             zoom_out_on_close: true,
             diagnostic_summary_update: Task::ready(()),
@@ -799,6 +835,22 @@ impl Pane {
         self.should_display_welcome_page = should_display_welcome_page;
     }
 
+    /// Lets the `tab_bar.placement` setting move the pane's tabs to its side.
+    pub fn set_can_show_vertical_tab_bar(&mut self, can_show_vertical_tab_bar: bool) {
+        self.can_show_vertical_tab_bar = can_show_vertical_tab_bar;
+    }
+
+    fn shows_vertical_tab_bar(&self, cx: &App) -> bool {
+        self.can_show_vertical_tab_bar
+            && TabBarSettings::get_global(cx).placement == TabBarPlacement::Left
+    }
+
+    /// Makes the items of the pane record the places they are edited and
+    /// navigated to in `location_history`.
+    pub fn set_location_history(&mut self, location_history: LocationHistory) {
+        self.nav_history.0.lock().location_history = Some(location_history);
+    }
+
     pub fn set_can_split(
         &mut self,
         can_split_predicate: Option<
@@ -1028,6 +1080,67 @@ impl Pane {
         self.pinned_tab_count
     }
 
+    pub fn buffer_font_size_delta(&self) -> Pixels {
+        self.buffer_font_size_delta
+    }
+
+    pub fn set_buffer_font_size_delta(
+        &mut self,
+        delta: Pixels,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let buffer_font_size = ThemeSettings::get_global(cx).buffer_font_size(cx);
+        let delta = theme::clamp_font_size(buffer_font_size + delta) - buffer_font_size;
+        if delta == self.buffer_font_size_delta {
+            return;
+        }
+        self.buffer_font_size_delta = delta;
+        for item in &self.items {
+            item.set_buffer_font_size_delta(delta, window, cx);
+        }
+        cx.emit(Event::BufferFontSizeChanged);
+        cx.notify();
+    }
+
+    pub fn set_zen_mode(&mut self, zen_mode: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if zen_mode == self.zen_mode {
+            return;
+        }
+        self.zen_mode = zen_mode;
+        for item in &self.items {
+            item.set_zen_mode(zen_mode, window, cx);
+        }
+        cx.notify();
+    }
+
+    fn increase_buffer_font_size(
+        &mut self,
+        _: &IncreaseBufferFontSize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_buffer_font_size_delta(self.buffer_font_size_delta + px(1.0), window, cx);
+    }
+
+    fn decrease_buffer_font_size(
+        &mut self,
+        _: &DecreaseBufferFontSize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_buffer_font_size_delta(self.buffer_font_size_delta - px(1.0), window, cx);
+    }
+
+    fn reset_buffer_font_size(
+        &mut self,
+        _: &ResetBufferFontSize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_buffer_font_size_delta(Pixels::ZERO, window, cx);
+    }
+
     pub fn handle_item_edit(&mut self, item_id: EntityId, cx: &App) {
         if let Some(preview_item) = self.preview_item()
             && preview_item.item_id() == item_id
@@ -1263,6 +1376,10 @@ impl Pane {
                 if existing_item_is_active && destination_index.is_none() {
                     insertion_index = existing_item_index;
                 } else {
+                    self.animate_tab_move(
+                        existing_item_index,
+                        insertion_index.min(self.items.len() - 1),
+                    );
                     self.items.remove(existing_item_index);
                     if existing_item_index < self.active_item_index {
                         self.active_item_index -= 1;
@@ -1286,6 +1403,8 @@ impl Pane {
             }
         } else {
             self.items.insert(insertion_index, item.clone());
+            item.set_buffer_font_size_delta(self.buffer_font_size_delta, window, cx);
+            item.set_zen_mode(self.zen_mode, window, cx);
             cx.notify();
 
             if activate {
@@ -1515,6 +1634,23 @@ impl Pane {
         self.activate_item(index, true, true, window, cx);
     }
 
+    /// Switches tabs once three or more fingers swipe far enough across the
+    /// pane, to the previous tab when swiping right.
+    fn handle_swipe(&mut self, event: &SwipeEvent, window: &mut Window, cx: &mut Context<Self>) {
+        match event.touch_phase {
+            TouchPhase::Started => self.swipe_distance = px(0.),
+            TouchPhase::Moved => self.swipe_distance += event.delta.x,
+            TouchPhase::Ended => {
+                let distance = mem::take(&mut self.swipe_distance);
+                if distance > SWIPE_TAB_DISTANCE {
+                    self.activate_previous_item(&ActivatePreviousItem, window, cx);
+                } else if distance < -SWIPE_TAB_DISTANCE {
+                    self.activate_next_item(&ActivateNextItem, window, cx);
+                }
+            }
+        }
+    }
+
     pub fn swap_item_left(
         &mut self,
         _: &SwapItemLeft,
@@ -1526,6 +1662,7 @@ impl Pane {
             return;
         }
 
+        self.animate_tab_move(index, index - 1);
         self.items.swap(index, index - 1);
         self.activate_item(index - 1, true, true, window, cx);
     }
@@ -1541,10 +1678,51 @@ impl Pane {
             return;
         }
 
+        self.animate_tab_move(index, index + 1);
         self.items.swap(index, index + 1);
         self.activate_item(index + 1, true, true, window, cx);
     }
 
+    /// Slides the tabs affected by moving the tab at `from` to `to` from where
+    /// they are now into their new positions. Must be called before the move.
+    fn animate_tab_move(&mut self, from: usize, to: usize) {
+        let pinned_tab_count = self.pinned_tab_count;
+        // Only the unpinned tabs' bounds are known, through the scroll handle.
+        if from == to || from < pinned_tab_count || to < pinned_tab_count {
+            return;
+        }
+        let bounds = |ix: usize| {
+            self.tab_bar_scroll_handle
+                .bounds_for_item(ix - pinned_tab_count)
+        };
+        let (Some(from_bounds), Some(to_bounds)) = (bounds(from), bounds(to)) else {
+            return;
+        };
+
+        let moved_width = from_bounds.size.width;
+        let (moved_offset, shifted_range, shifted_offset) = if from < to {
+            let new_left = to_bounds.right() - moved_width;
+            (from_bounds.left() - new_left, from + 1..=to, moved_width)
+        } else {
+            (
+                from_bounds.left() - to_bounds.left(),
+                to..=from - 1,
+                -moved_width,
+            )
+        };
+
+        let mut offsets = vec![(self.items[from].item_id(), moved_offset)];
+        offsets.extend(shifted_range.map(|ix| (self.items[ix].item_id(), shifted_offset)));
+        for (item_id, offset) in offsets {
+            let animation = self
+                .tab_move_animations
+                .entry(item_id)
+                .or_insert_with(|| AnimatedValue::new(px(0.), Spring::bouncy()));
+            animation.jump_to(animation.value() + offset);
+            animation.animate_to(px(0.));
+        }
+    }
+
     pub fn activate_last_item(
         &mut self,
         _: &ActivateLastItem,
@@ -2326,8 +2504,7 @@ impl Pane {
         } else if is_dirty && (can_save || can_save_as) {
             if save_intent == SaveIntent::Close {
                 let will_autosave = cx.update(|_window, cx| {
-                    item.can_autosave(cx)
-                        && item.workspace_settings(cx).autosave.should_save_on_close()
+                    item.can_autosave(cx) && item.autosave_setting(cx).should_save_on_close()
                 })?;
                 if !will_autosave {
                     let item_id = item.item_id();
@@ -2465,7 +2642,7 @@ impl Pane {
         cx: &mut App,
     ) -> Task<Result<()>> {
         let format = !matches!(
-            item.workspace_settings(cx).autosave,
+            item.autosave_setting(cx),
             AutosaveSetting::AfterDelay { .. }
         );
         if item.can_autosave(cx) {
@@ -2521,6 +2698,11 @@ impl Pane {
         &self.toolbar
     }
 
+    /// Where the pane was painted in the window during the last frame.
+    pub fn bounds(&self) -> Bounds<Pixels> {
+        self.bounds
+    }
+
     pub fn handle_deleted_project_item(
         &mut self,
         entry_id: ProjectEntryId,
@@ -2793,6 +2975,7 @@ impl Pane {
         let is_last_item = ix == self.items.len() - 1;
         let is_pinned = self.is_tab_pinned(ix);
         let position_relative_to_active_item = ix.cmp(&self.active_item_index);
+        let vertical = self.shows_vertical_tab_bar(cx);
 
         let read_only_toggle = |toggleable: bool| {
             IconButton::new("toggle_read_only", IconName::FileLock)
@@ -2821,6 +3004,22 @@ impl Pane {
         };
 
         let has_file_icon = icon.is_some() | decorated_icon.is_some();
+        let show_icon_only =
+            is_pinned && has_file_icon && TabBarSettings::get_global(cx).show_pinned_tabs_as_icons;
+        let tab_tooltip_content = if show_icon_only && tab_tooltip_content.is_none() {
+            Some(TabTooltipContent::Text(item.tab_content_text(detail, cx)))
+        } else {
+            tab_tooltip_content
+        };
+
+        let tint = item.project_path(cx).and_then(|project_path| {
+            let worktree = self
+                .project
+                .upgrade()?
+                .read(cx)
+                .worktree_for_id(project_path.worktree_id, cx)?;
+            settings.file_tint(worktree.read(cx).root_name_str(), &project_path.path)
+        });
 
         let capability = item.capability(cx);
         let tab = Tab::new(ix)
@@ -2836,6 +3035,13 @@ impl Pane {
                 ClosePosition::Right => ui::TabCloseSide::End,
             })
             .toggle_state(is_active)
+            .vertical(vertical)
+            .tint(tint)
+            // Tabs only slide horizontally.
+            .when_some(
+                self.tab_move_animations.get(&item_id).filter(|_| !vertical),
+                |tab, animation| tab.offset(animation.value()),
+            )
             .on_click(cx.listener({
                 let item_handle = item.boxed_clone();
                 move |pane: &mut Self, event: &ClickEvent, window, cx| {
@@ -2884,9 +3090,17 @@ impl Pane {
                     .border_0();
 
                 if ix < dragged_tab.ix {
-                    styled_tab = styled_tab.border_l_2();
+                    styled_tab = if vertical {
+                        styled_tab.border_t_2()
+                    } else {
+                        styled_tab.border_l_2()
+                    };
                 } else if ix > dragged_tab.ix {
-                    styled_tab = styled_tab.border_r_2();
+                    styled_tab = if vertical {
+                        styled_tab.border_b_2()
+                    } else {
+                        styled_tab.border_r_2()
+                    };
                 }
 
                 styled_tab
@@ -2917,7 +3131,9 @@ impl Pane {
             .map(|this| {
                 let end_slot_action: &'static dyn Action;
                 let end_slot_tooltip_text: &'static str;
-                let end_slot = if is_pinned {
+                let end_slot = if show_icon_only {
+                    return this;
+                } else if is_pinned {
                     end_slot_action = &TogglePinTab;
                     end_slot_tooltip_text = "Unpin Tab";
                     IconButton::new("unpin tab", IconName::Pin)
@@ -2980,7 +3196,7 @@ impl Pane {
                     } else {
                         None
                     })
-                    .child(label)
+                    .when(!show_icon_only, |this| this.child(label))
                     .map(|this| match tab_tooltip_content {
                         Some(TabTooltipContent::Text(text)) => {
                             if capability.editable() {
@@ -3350,11 +3566,30 @@ impl Pane {
                 }
             });
 
+        self.tab_move_animations.retain(|_, animation| {
+            animation.advance();
+            animation.is_animating()
+        });
+        if !self.tab_move_animations.is_empty() {
+            window.request_animation_frame();
+        }
+
+        let vertical = self.shows_vertical_tab_bar(cx);
+        let mut details = tab_details(&self.items, window, cx);
+        if vertical {
+            // There's room to show every tab's whole directory.
+            for (detail, item) in details.iter_mut().zip(&self.items) {
+                let directory_depth = item.project_path(cx).map_or(0, |project_path| {
+                    project_path.path.components().count().saturating_sub(1)
+                });
+                *detail = (*detail).max(directory_depth);
+            }
+        }
         let mut tab_items = self
             .items
             .iter()
             .enumerate()
-            .zip(tab_details(&self.items, window, cx))
+            .zip(details)
             .map(|((ix, item), detail)| {
                 self.render_tab(ix, &**item, detail, &focus_handle, window, cx)
                     .into_any_element()
@@ -3377,7 +3612,17 @@ impl Pane {
         let tab_bar_settings = TabBarSettings::get_global(cx);
         let use_separate_rows = tab_bar_settings.show_pinned_tabs_in_separate_row;
 
-        if use_separate_rows && !pinned_tabs.is_empty() && !unpinned_tabs.is_empty() {
+        if vertical {
+            self.render_vertical_tab_bar(
+                pinned_tabs,
+                unpinned_tabs,
+                tab_count,
+                navigate_backward,
+                navigate_forward,
+                window,
+                cx,
+            )
+        } else if use_separate_rows && !pinned_tabs.is_empty() && !unpinned_tabs.is_empty() {
             self.render_two_row_tab_bar(
                 pinned_tabs,
                 unpinned_tabs,
@@ -3509,6 +3754,56 @@ impl Pane {
             .into_any_element()
     }
 
+    fn render_vertical_tab_bar(
+        &mut self,
+        pinned_tabs: Vec<AnyElement>,
+        unpinned_tabs: Vec<AnyElement>,
+        tab_count: usize,
+        navigate_backward: IconButton,
+        navigate_forward: IconButton,
+        window: &mut Window,
+        cx: &mut Context<Pane>,
+    ) -> AnyElement {
+        let buttons = self.configure_tab_bar_start(
+            TabBar::new("vertical_tab_bar_buttons"),
+            navigate_backward,
+            navigate_forward,
+            window,
+            cx,
+        );
+        v_flex()
+            .id("vertical_tab_bar")
+            .debug_selector(|| "vertical_tab_bar".into())
+            .w(VERTICAL_TAB_BAR_WIDTH)
+            .h_full()
+            .flex_none()
+            .bg(cx.theme().colors().tab_bar_background)
+            .child(buttons)
+            .when(!pinned_tabs.is_empty(), |this| {
+                this.child(
+                    v_flex()
+                        .flex_none()
+                        .border_b_1()
+                        .border_color(cx.theme().colors().border)
+                        .children(pinned_tabs),
+                )
+            })
+            .child(
+                v_flex()
+                    .id("unpinned tabs")
+                    .flex_1()
+                    .min_h_0()
+                    .overflow_y_scroll()
+                    .track_scroll(&self.tab_bar_scroll_handle)
+                    .on_scroll_wheel(cx.listener(|this, _, _, _| {
+                        this.suppress_scroll = true;
+                    }))
+                    .children(unpinned_tabs)
+                    .child(self.render_tab_bar_drop_target(tab_count, cx)),
+            )
+            .into_any_element()
+    }
+
     fn render_unpinned_tabs_container(
         &mut self,
         unpinned_tabs: Vec<AnyElement>,
@@ -4200,7 +4495,9 @@ impl Render for Pane {
             .contribute_context(&mut key_context, cx);
 
         let should_display_tab_bar = self.should_display_tab_bar.clone();
-        let display_tab_bar = should_display_tab_bar(window, cx);
+        let display_tab_bar = !self.zen_mode && should_display_tab_bar(window, cx);
+        let display_vertical_tab_bar =
+            display_tab_bar && self.active_item().is_some() && self.shows_vertical_tab_bar(cx);
         let Some(project) = self.project.upgrade() else {
             return div().track_focus(&self.focus_handle(cx));
         };
@@ -4212,6 +4509,8 @@ impl Render for Pane {
             .size_full()
             .flex_none()
             .overflow_hidden()
+            .when(display_vertical_tab_bar, |pane| pane.flex_row())
+            .on_swipe(cx.listener(Self::handle_swipe))
             .on_action(cx.listener(|pane, split: &SplitLeft, window, cx| {
                 pane.split(SplitDirection::Left, split.mode, window, cx)
             }))
@@ -4274,6 +4573,9 @@ impl Render for Pane {
             .on_action(cx.listener(Self::swap_item_right))
             .on_action(cx.listener(Self::toggle_pin_tab))
             .on_action(cx.listener(Self::unpin_all_tabs))
+            .on_action(cx.listener(Self::increase_buffer_font_size))
+            .on_action(cx.listener(Self::decrease_buffer_font_size))
+            .on_action(cx.listener(Self::reset_buffer_font_size))
             .when(PreviewTabsSettings::get_global(cx).enabled, |this| {
                 this.on_action(
                     cx.listener(|pane: &mut Pane, _: &TogglePreviewTab, window, cx| {
@@ -4350,6 +4652,15 @@ impl Render for Pane {
                     cx.propagate();
                 }
             }))
+            .child({
+                let this = cx.entity();
+                canvas(
+                    move |bounds, _, cx| this.update(cx, |this, _| this.bounds = bounds),
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full()
+            })
             .when(self.active_item().is_some() && display_tab_bar, |pane| {
                 pane.child((self.render_tab_bar.clone())(self, window, cx))
             })
@@ -4509,6 +4820,46 @@ impl ItemNavHistory {
         }
     }
 
+    /// Records a place in the item in the workspace's location history.
+    pub fn push_location<D: 'static + Any + Send + Sync>(
+        &self,
+        data: D,
+        project_path: ProjectPath,
+        row: u32,
+        kind: LocationKind,
+    ) {
+        if !self
+            .item
+            .upgrade()
+            .is_some_and(|item| item.include_in_nav_history())
+        {
+            return;
+        }
+        let state = self.history.0.lock();
+        if !matches!(state.mode, NavigationMode::Normal) {
+            return;
+        }
+        let Some(location_history) = state.location_history.clone() else {
+            return;
+        };
+        let pane = state.pane.clone();
+        let is_preview = state.preview_item_id == Some(self.item.id());
+        drop(state);
+
+        location_history.push(Location {
+            pane,
+            entry: NavigationEntry {
+                item: self.item.clone(),
+                data: Some(Arc::new(data)),
+                timestamp: 0, // not used
+                is_preview,
+            },
+            project_path,
+            row,
+            kind,
+        });
+    }
+
     pub fn push_tag(&mut self, origin: Option<NavigationEntry>, target: Option<NavigationEntry>) {
         if let (Some(origin_entry), Some(target_entry)) = (origin, target) {
             self.history.push_tag(origin_entry, target_entry);
@@ -4945,6 +5296,52 @@ mod tests {
         assert_item_labels(&pane, ["A*^!"], cx);
     }
 
+    #[gpui::test]
+    async fn test_buffer_font_size_is_per_pane(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let pane = workspace.read_with(cx, |workspace, _| workspace.active_pane().clone());
+        let other_pane = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.split_pane(pane.clone(), SplitDirection::Right, window, cx)
+        });
+
+        pane.update_in(cx, |pane, window, cx| {
+            pane.increase_buffer_font_size(&IncreaseBufferFontSize, window, cx);
+            pane.increase_buffer_font_size(&IncreaseBufferFontSize, window, cx);
+        });
+        assert_eq!(
+            pane.read_with(cx, |pane, _| pane.buffer_font_size_delta()),
+            px(2.0)
+        );
+        assert_eq!(
+            other_pane.read_with(cx, |pane, _| pane.buffer_font_size_delta()),
+            Pixels::ZERO
+        );
+
+        // The delta can't shrink the font below the minimum font size.
+        pane.update_in(cx, |pane, window, cx| {
+            pane.set_buffer_font_size_delta(px(-1000.0), window, cx);
+        });
+        let buffer_font_size =
+            cx.update(|_, cx| ThemeSettings::get_global(cx).buffer_font_size(cx));
+        assert_eq!(
+            pane.read_with(cx, |pane, _| pane.buffer_font_size_delta()),
+            theme::clamp_font_size(px(0.0)) - buffer_font_size
+        );
+
+        pane.update_in(cx, |pane, window, cx| {
+            pane.reset_buffer_font_size(&ResetBufferFontSize, window, cx);
+        });
+        assert_eq!(
+            pane.read_with(cx, |pane, _| pane.buffer_font_size_delta()),
+            Pixels::ZERO
+        );
+    }
+
     #[gpui::test]
     async fn test_allow_pinning_non_dirty_item_at_max_tabs(cx: &mut TestAppContext) {
         init_test(cx);
@@ -5518,6 +5915,38 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_vertical_tab_bar(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let pane = workspace.read_with(cx, |workspace, _| workspace.active_pane().clone());
+
+        add_labeled_item(&pane, "A", false, cx);
+        add_labeled_item(&pane, "B", false, cx);
+        cx.run_until_parked();
+        assert!(cx.debug_bounds("vertical_tab_bar").is_none());
+
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.tab_bar.get_or_insert_default().placement = Some(TabBarPlacement::Left);
+            });
+        });
+        cx.run_until_parked();
+
+        let tab_bar_bounds = cx
+            .debug_bounds("vertical_tab_bar")
+            .expect("vertical tab bar should be shown");
+        let first_tab_bounds = cx.debug_bounds("TAB-0").unwrap();
+        let second_tab_bounds = cx.debug_bounds("TAB-1").unwrap();
+        assert_eq!(first_tab_bounds.left(), second_tab_bounds.left());
+        assert!(first_tab_bounds.bottom() <= second_tab_bounds.top());
+        assert!(tab_bar_bounds.contains(&second_tab_bounds.center()));
+    }
+
     #[gpui::test]
     async fn test_pinning_active_tab_without_position_change_maintains_focus(
         cx: &mut TestAppContext,
@@ -197,6 +197,52 @@ impl PaneGroup {
         self.mark_positions(cx);
     }
 
+    /// Shrinks every other pane to its minimum size, so that the given pane and
+    /// the splits containing it take up the rest.
+    pub fn maximize(&mut self, pane: &Entity<Pane>, bounds: &Bounds<Pixels>, cx: &mut App) {
+        if let Member::Axis(axis) = &self.root {
+            axis.maximize(pane, bounds);
+        }
+        self.mark_positions(cx);
+    }
+
+    /// The relative sizes of the members of every split, in depth-first order.
+    pub fn pane_sizes(&self) -> Vec<Vec<f32>> {
+        let mut sizes = Vec::new();
+        self.root.collect_pane_sizes(&mut sizes);
+        sizes
+    }
+
+    /// Sets the sizes returned by [`Self::pane_sizes`] back, if the splits
+    /// haven't changed since.
+    pub fn restore_pane_sizes(&mut self, sizes: &[Vec<f32>], cx: &mut App) -> bool {
+        let current_sizes = self.pane_sizes();
+        let same_splits = current_sizes.len() == sizes.len()
+            && current_sizes
+                .iter()
+                .zip(sizes)
+                .all(|(current, restored)| current.len() == restored.len());
+        if !same_splits {
+            return false;
+        }
+        self.root.restore_pane_sizes(&mut sizes.iter());
+        self.mark_positions(cx);
+        true
+    }
+
+    /// Moves every member of the split containing the given pane one position
+    /// forward (right or down), or backward, wrapping around at its end.
+    pub fn rotate(&mut self, pane: &Entity<Pane>, forward: bool, cx: &mut App) -> bool {
+        let rotated = match &mut self.root {
+            Member::Pane(_) => false,
+            Member::Axis(axis) => axis.rotate(pane, forward),
+        };
+        if rotated {
+            self.mark_positions(cx);
+        }
+        rotated
+    }
+
     pub fn swap(&mut self, from: &Entity<Pane>, to: &Entity<Pane>, cx: &mut App) {
         match &mut self.root {
             Member::Pane(_) => {}
@@ -544,6 +590,33 @@ impl Member {
         }
     }
 
+    fn contains(&self, pane: &Entity<Pane>) -> bool {
+        match self {
+            Member::Axis(axis) => axis.members.iter().any(|member| member.contains(pane)),
+            Member::Pane(member_pane) => member_pane == pane,
+        }
+    }
+
+    fn collect_pane_sizes(&self, sizes: &mut Vec<Vec<f32>>) {
+        if let Member::Axis(axis) = self {
+            sizes.push(axis.flexes.lock().clone());
+            for member in &axis.members {
+                member.collect_pane_sizes(sizes);
+            }
+        }
+    }
+
+    fn restore_pane_sizes<'a>(&self, sizes: &mut impl Iterator<Item = &'a Vec<f32>>) {
+        if let Member::Axis(axis) = self {
+            if let Some(axis_sizes) = sizes.next() {
+                *axis.flexes.lock() = axis_sizes.clone();
+            }
+            for member in &axis.members {
+                member.restore_pane_sizes(sizes);
+            }
+        }
+    }
+
     fn collect_panes<'a>(&'a self, panes: &mut Vec<&'a Entity<Pane>>) {
         match self {
             Member::Axis(axis) => {
@@ -700,6 +773,60 @@ impl PaneAxis {
         }
     }
 
+    fn maximize(&self, pane: &Entity<Pane>, bounds: &Bounds<Pixels>) {
+        let Some(index) = self.members.iter().position(|member| member.contains(pane)) else {
+            return;
+        };
+        if let Member::Axis(axis) = &self.members[index] {
+            axis.maximize(pane, bounds);
+        }
+
+        let container_size = self
+            .bounding_boxes
+            .lock()
+            .iter()
+            .filter_map(|bounds| *bounds)
+            .reduce(|acc, bounds| acc.union(&bounds))
+            .unwrap_or(*bounds)
+            .size
+            .along(self.axis);
+        if container_size <= Pixels::ZERO {
+            return;
+        }
+        let min_size = match self.axis {
+            Axis::Horizontal => px(HORIZONTAL_MIN_SIZE),
+            Axis::Vertical => px(VERTICAL_MIN_SIZE),
+        };
+        let member_count = self.members.len() as f32;
+        let min_flex = (member_count * min_size / container_size).min(1.);
+        let mut flexes = vec![min_flex; self.members.len()];
+        flexes[index] = member_count - (member_count - 1.) * min_flex;
+        *self.flexes.lock() = flexes;
+    }
+
+    fn rotate(&mut self, pane: &Entity<Pane>, forward: bool) -> bool {
+        let contains_pane = self
+            .members
+            .iter()
+            .any(|member| matches!(member, Member::Pane(member_pane) if member_pane == pane));
+        if !contains_pane {
+            return self.members.iter_mut().any(|member| match member {
+                Member::Axis(axis) => axis.rotate(pane, forward),
+                Member::Pane(_) => false,
+            });
+        }
+
+        let mut flexes = self.flexes.lock();
+        if forward {
+            self.members.rotate_right(1);
+            flexes.rotate_right(1);
+        } else {
+            self.members.rotate_left(1);
+            flexes.rotate_left(1);
+        }
+        true
+    }
+
     fn reset_pane_sizes(&self) {
         *self.flexes.lock() = vec![1.; self.members.len()];
         for member in self.members.iter() {
@@ -21,6 +21,7 @@ use db::{
 };
 use gpui::{Axis, Bounds, Task, WindowBounds, WindowId, point, size};
 use project::{
+    bookmark_store::SerializedBookmark,
     debugger::breakpoint_store::{BreakpointState, SourceBreakpoint},
     trusted_worktrees::{DbTrustedPaths, RemoteHostLocation},
 };
@@ -41,14 +42,14 @@ use util::{ResultExt, maybe, rel_path::RelPath};
 use uuid::Uuid;
 
 use crate::{
-    WorkspaceId,
+    DockPosition, WorkspaceId,
     path_list::{PathList, SerializedPathList},
     persistence::model::RemoteConnectionKind,
 };
 
 use model::{
-    GroupId, ItemId, PaneId, RemoteConnectionId, SerializedItem, SerializedPane,
-    SerializedPaneGroup, SerializedWorkspace,
+    GroupId, ItemId, PaneId, RemoteConnectionId, SerializedDetachedPanel, SerializedItem,
+    SerializedPane, SerializedPaneGroup, SerializedWorkspace,
 };
 
 use self::model::{DockStructure, SerializedWorkspaceLocation, SessionWorkspace};
@@ -99,6 +100,43 @@ impl sqlez::bindable::Column for SerializedAxis {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct SerializedDockPosition(pub(crate) DockPosition);
+impl sqlez::bindable::StaticColumnCount for SerializedDockPosition {}
+impl sqlez::bindable::Bind for SerializedDockPosition {
+    fn bind(
+        &self,
+        statement: &sqlez::statement::Statement,
+        start_index: i32,
+    ) -> anyhow::Result<i32> {
+        match self.0 {
+            DockPosition::Left => "Left",
+            DockPosition::Bottom => "Bottom",
+            DockPosition::Right => "Right",
+        }
+        .bind(statement, start_index)
+    }
+}
+
+impl sqlez::bindable::Column for SerializedDockPosition {
+    fn column(
+        statement: &mut sqlez::statement::Statement,
+        start_index: i32,
+    ) -> anyhow::Result<(Self, i32)> {
+        String::column(statement, start_index).and_then(|(position_text, next_index)| {
+            Ok((
+                match position_text.as_str() {
+                    "Left" => Self(DockPosition::Left),
+                    "Bottom" => Self(DockPosition::Bottom),
+                    "Right" => Self(DockPosition::Right),
+                    _ => anyhow::bail!("Stored dock position is incorrect"),
+                },
+                next_index,
+            ))
+        })
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
 pub(crate) struct SerializedWindowBounds(pub(crate) WindowBounds);
 
@@ -970,6 +1008,47 @@ impl Domain for WorkspaceDb {
         sql!(
             ALTER TABLE remote_connections ADD COLUMN use_podman BOOLEAN;
         ),
+        sql!(
+            CREATE TABLE bookmarks (
+                workspace_id INTEGER NOT NULL,
+                path TEXT NOT NULL,
+                row INTEGER NOT NULL,
+                label TEXT,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            );
+        ),
+        sql!(
+            ALTER TABLE panes ADD COLUMN buffer_font_size_delta INTEGER DEFAULT 0;
+        ),
+        sql!(
+            CREATE TABLE detached_panels (
+                workspace_id INTEGER NOT NULL,
+                panel_name TEXT NOT NULL,
+                dock TEXT,
+                window_state TEXT,
+                window_x REAL,
+                window_y REAL,
+                window_width REAL,
+                window_height REAL,
+                PRIMARY KEY(workspace_id, panel_name),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            );
+        ),
+        sql!(
+            CREATE TABLE workspace_layouts (
+                workspace_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                layout TEXT NOT NULL,
+                PRIMARY KEY(workspace_id, name),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            );
+        ),
     ];
 
     // Allow recovering from bad migration that was initially shipped to nightly
@@ -1104,6 +1183,8 @@ impl WorkspaceDb {
             display,
             docks,
             session_id: None,
+            bookmarks: self.bookmarks(workspace_id),
+            detached_panels: self.detached_panels(workspace_id),
             breakpoints: self.breakpoints(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
@@ -1194,6 +1275,8 @@ impl WorkspaceDb {
             display,
             docks,
             session_id: None,
+            bookmarks: self.bookmarks(workspace_id),
+            detached_panels: self.detached_panels(workspace_id),
             breakpoints: self.breakpoints(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
@@ -1246,6 +1329,72 @@ impl WorkspaceDb {
         }
     }
 
+    fn bookmarks(&self, workspace_id: WorkspaceId) -> BTreeMap<Arc<Path>, Vec<SerializedBookmark>> {
+        let bookmarks: Result<Vec<(PathBuf, u32, Option<String>)>> = self
+            .select_bound(sql! {
+                SELECT path, row, label
+                FROM bookmarks
+                WHERE workspace_id = ?
+                ORDER BY path, row
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match bookmarks {
+            Ok(bookmarks) => {
+                let mut map: BTreeMap<Arc<Path>, Vec<SerializedBookmark>> = Default::default();
+                for (path, row, label) in bookmarks {
+                    map.entry(path.into())
+                        .or_default()
+                        .push(SerializedBookmark {
+                            row,
+                            label: label.map(Into::into),
+                        });
+                }
+                map
+            }
+            Err(msg) => {
+                log::error!("Bookmarks query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
+    fn detached_panels(&self, workspace_id: WorkspaceId) -> Vec<SerializedDetachedPanel> {
+        let detached_panels: Result<
+            Vec<(
+                String,
+                Option<SerializedDockPosition>,
+                Option<SerializedWindowBounds>,
+            )>,
+        > = self
+            .select_bound(sql! {
+                SELECT panel_name, dock, window_state, window_x, window_y, window_width, window_height
+                FROM detached_panels
+                WHERE workspace_id = ?
+                ORDER BY panel_name
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match detached_panels {
+            // Panels saved before their dock was recorded can't be matched
+            // to a dock, and stay attached.
+            Ok(detached_panels) => detached_panels
+                .into_iter()
+                .filter_map(|(panel_name, dock_position, window_bounds)| {
+                    Some(SerializedDetachedPanel {
+                        panel_name,
+                        dock_position: dock_position?.0,
+                        window_bounds,
+                    })
+                })
+                .collect(),
+            Err(msg) => {
+                log::error!("Detached panels query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     fn user_toolchains(
         &self,
         workspace_id: WorkspaceId,
@@ -1374,6 +1523,52 @@ impl WorkspaceDb {
                     }
                 }
 
+                conn.exec_bound(
+                    sql!(
+                        DELETE FROM bookmarks WHERE workspace_id = ?1;
+                    )
+                )?(workspace.id).context("Clearing old bookmarks")?;
+
+                for (path, bookmarks) in workspace.bookmarks {
+                    for bookmark in bookmarks {
+                        if let Err(err) = conn.exec_bound(sql!(
+                            INSERT INTO bookmarks (workspace_id, path, row, label)
+                            VALUES (?1, ?2, ?3, ?4);))?
+
+                        ((
+                            workspace.id,
+                            path.as_ref(),
+                            bookmark.row,
+                            bookmark.label.as_deref(),
+                        )) {
+                            log::error!("{err}");
+                            continue;
+                        }
+                    }
+                }
+
+                conn.exec_bound(
+                    sql!(
+                        DELETE FROM detached_panels WHERE workspace_id = ?1;
+                    )
+                )?(workspace.id).context("Clearing old detached panels")?;
+
+                for detached_panel in workspace.detached_panels {
+                    if let Err(err) = conn.exec_bound(sql!(
+                        INSERT INTO detached_panels (workspace_id, panel_name, dock, window_state, window_x, window_y, window_width, window_height)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);))?
+
+                    ((
+                        workspace.id,
+                        detached_panel.panel_name.as_str(),
+                        SerializedDockPosition(detached_panel.dock_position),
+                        detached_panel.window_bounds,
+                    )) {
+                        log::error!("{err}");
+                        continue;
+                    }
+                }
+
                 conn.exec_bound(
                     sql!(
                         DELETE FROM user_toolchains WHERE workspace_id = ?1;
@@ -1936,6 +2131,7 @@ impl WorkspaceDb {
                     active: true,
                     children: vec![],
                     pinned_count: 0,
+                    buffer_font_size_delta: 0,
                 })
             }))
     }
@@ -1952,16 +2148,18 @@ impl WorkspaceDb {
             Option<PaneId>,
             Option<bool>,
             Option<usize>,
+            Option<i32>,
             Option<String>,
         );
         self.select_bound::<GroupKey, GroupOrPane>(sql!(
-            SELECT group_id, axis, pane_id, active, pinned_count, flexes
+            SELECT group_id, axis, pane_id, active, pinned_count, buffer_font_size_delta, flexes
                 FROM (SELECT
                         group_id,
                         axis,
                         NULL as pane_id,
                         NULL as active,
                         NULL as pinned_count,
+                        NULL as buffer_font_size_delta,
                         position,
                         parent_group_id,
                         workspace_id,
@@ -1974,6 +2172,7 @@ impl WorkspaceDb {
                         center_panes.pane_id,
                         panes.active as active,
                         pinned_count,
+                        buffer_font_size_delta,
                         position,
                         parent_group_id,
                         panes.workspace_id as workspace_id,
@@ -1984,28 +2183,29 @@ impl WorkspaceDb {
                 ORDER BY position
         ))?((group_id, workspace_id))?
         .into_iter()
-        .map(|(group_id, axis, pane_id, active, pinned_count, flexes)| {
-            let maybe_pane = maybe!({ Some((pane_id?, active?, pinned_count?)) });
-            if let Some((group_id, axis)) = group_id.zip(axis) {
-                let flexes = flexes
-                    .map(|flexes: String| serde_json::from_str::<Vec<f32>>(&flexes))
-                    .transpose()?;
-
-                Ok(SerializedPaneGroup::Group {
-                    axis,
-                    children: self.get_pane_group(workspace_id, Some(group_id))?,
-                    flexes,
-                })
-            } else if let Some((pane_id, active, pinned_count)) = maybe_pane {
-                Ok(SerializedPaneGroup::Pane(SerializedPane::new(
-                    self.get_items(pane_id)?,
-                    active,
-                    pinned_count,
-                )))
-            } else {
-                bail!("Pane Group Child was neither a pane group or a pane");
-            }
-        })
+        .map(
+            |(group_id, axis, pane_id, active, pinned_count, buffer_font_size_delta, flexes)| {
+                let maybe_pane = maybe!({ Some((pane_id?, active?, pinned_count?)) });
+                if let Some((group_id, axis)) = group_id.zip(axis) {
+                    let flexes = flexes
+                        .map(|flexes: String| serde_json::from_str::<Vec<f32>>(&flexes))
+                        .transpose()?;
+
+                    Ok(SerializedPaneGroup::Group {
+                        axis,
+                        children: self.get_pane_group(workspace_id, Some(group_id))?,
+                        flexes,
+                    })
+                } else if let Some((pane_id, active, pinned_count)) = maybe_pane {
+                    Ok(SerializedPaneGroup::Pane(SerializedPane {
+                        buffer_font_size_delta: buffer_font_size_delta.unwrap_or_default(),
+                        ..SerializedPane::new(self.get_items(pane_id)?, active, pinned_count)
+                    }))
+                } else {
+                    bail!("Pane Group Child was neither a pane group or a pane");
+                }
+            },
+        )
         // Filter out panes and pane groups which don't have any children or items
         .filter(|pane_group| match pane_group {
             Ok(SerializedPaneGroup::Group { children, .. }) => !children.is_empty(),
@@ -2075,10 +2275,15 @@ impl WorkspaceDb {
         parent: Option<(GroupId, usize)>,
     ) -> Result<PaneId> {
         let pane_id = conn.select_row_bound::<_, i64>(sql!(
-            INSERT INTO panes(workspace_id, active, pinned_count)
-            VALUES (?, ?, ?)
+            INSERT INTO panes(workspace_id, active, pinned_count, buffer_font_size_delta)
+            VALUES (?, ?, ?, ?)
             RETURNING pane_id
-        ))?((workspace_id, pane.active, pane.pinned_count))?
+        ))?((
+            workspace_id,
+            pane.active,
+            pane.pinned_count,
+            pane.buffer_font_size_delta,
+        ))?
         .context("Could not retrieve inserted pane_id")?;
 
         let (parent_id, order) = parent.unzip();
@@ -2137,6 +2342,37 @@ impl WorkspaceDb {
         }
     }
 
+    query! {
+        pub(crate) fn workspace_layout_names(workspace_id: WorkspaceId) -> Result<Vec<String>> {
+            SELECT name
+            FROM workspace_layouts
+            WHERE workspace_id = ?
+            ORDER BY name
+        }
+    }
+
+    query! {
+        pub(crate) fn workspace_layout(workspace_id: WorkspaceId, name: &str) -> Result<Option<String>> {
+            SELECT layout
+            FROM workspace_layouts
+            WHERE workspace_id = ?1 AND name = ?2
+        }
+    }
+
+    query! {
+        pub(crate) async fn save_workspace_layout(workspace_id: WorkspaceId, name: String, layout: String) -> Result<()> {
+            INSERT OR REPLACE INTO workspace_layouts(workspace_id, name, layout)
+            VALUES (?1, ?2, ?3)
+        }
+    }
+
+    query! {
+        pub(crate) async fn delete_workspace_layout(workspace_id: WorkspaceId, name: String) -> Result<()> {
+            DELETE FROM workspace_layouts
+            WHERE workspace_id = ?1 AND name = ?2
+        }
+    }
+
     query! {
         pub(crate) async fn set_centered_layout(workspace_id: WorkspaceId, centered_layout: bool) -> Result<()> {
             UPDATE workspaces
@@ -2459,6 +2695,100 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_bookmarks() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_bookmarks").await;
+        let id = db.next_id().await.unwrap();
+
+        let path: Arc<Path> = Arc::from(Path::new("/tmp/test.rs"));
+        let bookmarks = vec![
+            SerializedBookmark {
+                row: 3,
+                label: None,
+            },
+            SerializedBookmark {
+                row: 42,
+                label: Some("entry point".into()),
+            },
+        ];
+        let mut workspace = SerializedWorkspace {
+            id,
+            paths: PathList::new(&["/tmp"]),
+            location: SerializedWorkspaceLocation::Local,
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            bookmarks: collections::BTreeMap::from_iter([(path.clone(), bookmarks.clone())]),
+            detached_panels: Default::default(),
+            breakpoints: Default::default(),
+            session_id: None,
+            window_id: None,
+            user_toolchains: Default::default(),
+        };
+
+        db.save_workspace(workspace.clone()).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.bookmarks.get(&path), Some(&bookmarks));
+
+        workspace.bookmarks = Default::default();
+        db.save_workspace(workspace).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert!(loaded.bookmarks.is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_detached_panels() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_detached_panels").await;
+        let id = db.next_id().await.unwrap();
+
+        let detached_panels = vec![
+            SerializedDetachedPanel {
+                panel_name: "AgentPanel".into(),
+                dock_position: DockPosition::Right,
+                window_bounds: None,
+            },
+            SerializedDetachedPanel {
+                panel_name: "TerminalPanel".into(),
+                dock_position: DockPosition::Bottom,
+                window_bounds: Some(SerializedWindowBounds(WindowBounds::Windowed(Bounds {
+                    origin: point(px(100.), px(200.)),
+                    size: size(px(800.), px(600.)),
+                }))),
+            },
+        ];
+        let mut workspace = SerializedWorkspace {
+            id,
+            paths: PathList::new(&["/tmp"]),
+            location: SerializedWorkspaceLocation::Local,
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            bookmarks: Default::default(),
+            detached_panels: detached_panels.clone(),
+            breakpoints: Default::default(),
+            session_id: None,
+            window_id: None,
+            user_toolchains: Default::default(),
+        };
+
+        db.save_workspace(workspace.clone()).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.detached_panels, detached_panels);
+
+        workspace.detached_panels = Default::default();
+        db.save_workspace(workspace).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert!(loaded.detached_panels.is_empty());
+    }
+
     #[gpui::test]
     async fn test_breakpoints() {
         zlog::init_test();
@@ -2517,6 +2847,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: {
                 let mut map = collections::BTreeMap::default();
                 map.insert(
@@ -2672,6 +3004,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: {
                 let mut map = collections::BTreeMap::default();
                 map.insert(
@@ -2720,6 +3054,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: collections::BTreeMap::default(),
             session_id: None,
             window_id: None,
@@ -2818,6 +3154,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
@@ -2833,6 +3171,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
@@ -2937,6 +3277,8 @@ mod tests {
             location: SerializedWorkspaceLocation::Local,
             center_group,
             window_bounds: Default::default(),
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
@@ -2971,6 +3313,8 @@ mod tests {
             location: SerializedWorkspaceLocation::Local,
             center_group: Default::default(),
             window_bounds: Default::default(),
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
@@ -2989,6 +3333,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
@@ -3028,6 +3374,8 @@ mod tests {
             location: SerializedWorkspaceLocation::Local,
             center_group: Default::default(),
             window_bounds: Default::default(),
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
@@ -3069,6 +3417,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
@@ -3084,6 +3434,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
@@ -3099,6 +3451,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
@@ -3114,6 +3468,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
@@ -3140,6 +3496,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
@@ -3152,6 +3510,8 @@ mod tests {
             location: SerializedWorkspaceLocation::Local,
             center_group: Default::default(),
             window_bounds: Default::default(),
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
@@ -3211,6 +3571,8 @@ mod tests {
             window_bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             centered_layout: false,
             session_id: None,
@@ -3254,6 +3616,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             window_id: Some(window_id),
             user_toolchains: Default::default(),
@@ -3366,6 +3730,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             window_id: Some(window_id),
             user_toolchains: Default::default(),
@@ -3725,6 +4091,8 @@ mod tests {
             window_bounds: None,
             display: None,
             docks: Default::default(),
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             centered_layout: false,
             session_id: None,
@@ -3803,6 +4171,8 @@ mod tests {
                 docks: Default::default(),
                 centered_layout: false,
                 session_id: Some("test-session".to_owned()),
+                bookmarks: Default::default(),
+                detached_panels: Default::default(),
                 breakpoints: Default::default(),
                 window_id: Some(*window_id),
                 user_toolchains: Default::default(),
@@ -4144,6 +4514,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             session_id: Some("remove-test-session".to_owned()),
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             window_id: Some(99),
             user_toolchains: Default::default(),
@@ -4226,6 +4598,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             session_id: Some(session_id.to_owned()),
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             window_id: Some(window_id_val),
             user_toolchains: Default::default(),
@@ -4242,6 +4616,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             session_id: Some(session_id.to_owned()),
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             window_id: Some(window_id_val),
             user_toolchains: Default::default(),
@@ -4320,6 +4696,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             session_id: Some("pending-removal-session".to_owned()),
+            bookmarks: Default::default(),
+            detached_panels: Default::default(),
             breakpoints: Default::default(),
             window_id: Some(88),
             user_toolchains: Default::default(),
@@ -1,7 +1,7 @@
 use super::{SerializedAxis, SerializedWindowBounds};
 use crate::{
-    Member, Pane, PaneAxis, SerializableItemRegistry, Workspace, WorkspaceId, item::ItemHandle,
-    path_list::PathList,
+    DockPosition, Member, Pane, PaneAxis, SerializableItemRegistry, Workspace, WorkspaceId,
+    item::ItemHandle, path_list::PathList,
 };
 use anyhow::{Context, Result};
 use async_recursion::async_recursion;
@@ -10,10 +10,12 @@ use db::sqlez::{
     bindable::{Bind, Column, StaticColumnCount},
     statement::Statement,
 };
-use gpui::{AsyncWindowContext, Entity, WeakEntity, WindowId};
+use gpui::{AsyncWindowContext, Entity, WeakEntity, WindowId, px};
 
 use language::{Toolchain, ToolchainScope};
-use project::{Project, debugger::breakpoint_store::SourceBreakpoint};
+use project::{
+    Project, bookmark_store::SerializedBookmark, debugger::breakpoint_store::SourceBreakpoint,
+};
 use remote::RemoteConnectionOptions;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -87,10 +89,21 @@ pub(crate) struct SerializedWorkspace {
     pub(crate) docks: DockStructure,
     pub(crate) session_id: Option<String>,
     pub(crate) breakpoints: BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>,
+    pub(crate) bookmarks: BTreeMap<Arc<Path>, Vec<SerializedBookmark>>,
+    pub(crate) detached_panels: Vec<SerializedDetachedPanel>,
     pub(crate) user_toolchains: BTreeMap<ToolchainScope, IndexSet<Toolchain>>,
     pub(crate) window_id: Option<u64>,
 }
 
+/// A panel shown in its own window rather than in its dock.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct SerializedDetachedPanel {
+    pub(crate) panel_name: String,
+    /// The dock the panel is attached back to.
+    pub(crate) dock_position: DockPosition,
+    pub(crate) window_bounds: Option<SerializedWindowBounds>,
+}
+
 #[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
 pub struct DockStructure {
     pub(crate) left: DockData,
@@ -189,6 +202,7 @@ impl Default for SerializedPaneGroup {
             children: vec![SerializedItem::default()],
             active: false,
             pinned_count: 0,
+            buffer_font_size_delta: 0,
         })
     }
 }
@@ -282,6 +296,8 @@ pub struct SerializedPane {
     pub(crate) active: bool,
     pub(crate) children: Vec<SerializedItem>,
     pub(crate) pinned_count: usize,
+    /// The pane's buffer font size delta, in whole pixels.
+    pub(crate) buffer_font_size_delta: i32,
 }
 
 impl SerializedPane {
@@ -290,6 +306,7 @@ impl SerializedPane {
             children,
             active,
             pinned_count,
+            buffer_font_size_delta: 0,
         }
     }
 
@@ -350,8 +367,16 @@ impl SerializedPane {
                 }
             })?;
         }
-        pane.update(cx, |pane, _| {
-            pane.set_pinned_count(self.pinned_count.min(items.len()));
+        // Pinned items come first, so only the ones that failed to load need to
+        // be taken out of the pinned count.
+        let pinned_count = items
+            .iter()
+            .take(self.pinned_count)
+            .filter(|item| item.is_some())
+            .count();
+        pane.update_in(cx, |pane, window, cx| {
+            pane.set_pinned_count(pinned_count);
+            pane.set_buffer_font_size_delta(px(self.buffer_font_size_delta as f32), window, cx);
         })?;
 
         anyhow::Ok(items)
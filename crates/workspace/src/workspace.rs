@@ -1,7 +1,11 @@
+mod autosave_indicator;
+mod detached_panel;
 pub mod dock;
 pub mod history_manager;
 pub mod invalid_item_view;
 pub mod item;
+mod layouts;
+pub mod location_history;
 mod modal_layer;
 mod multi_workspace;
 pub mod notifications;
@@ -31,13 +35,14 @@ pub use path_list::PathList;
 pub use toast_layer::{ToastAction, ToastLayer, ToastView};
 
 use anyhow::{Context as _, Result, anyhow};
+use autosave_indicator::AutosaveIndicator;
 use call::{ActiveCall, call_settings::CallSettings};
 use client::{
     ChannelId, Client, ErrorExt, Status, TypedEnvelope, UserStore,
     proto::{self, ErrorCode, PanelId, PeerId},
 };
 use collections::{HashMap, HashSet, hash_map};
-use dock::{Dock, DockPosition, PanelButtons, PanelHandle, RESIZE_HANDLE_SIZE};
+use dock::{Dock, DockPosition, DraggedPanel, PanelButtons, PanelHandle, RESIZE_HANDLE_SIZE};
 use futures::{
     Future, FutureExt, StreamExt,
     channel::{
@@ -82,6 +87,7 @@ use postage::stream::Stream;
 use project::{
     DirectoryLister, Project, ProjectEntryId, ProjectPath, ResolvedPath, Worktree, WorktreeId,
     WorktreeSettings,
+    bookmark_store::BookmarkStoreEvent,
     debugger::{breakpoint_store::BreakpointStoreEvent, session::ThreadStatus},
     project_settings::ProjectSettings,
     toolchain_store::ToolchainStoreEvent,
@@ -143,9 +149,13 @@ use zed_actions::{Spawn, feedback::FileBugReport};
 
 use crate::{item::ItemBufferKind, notifications::NotificationId};
 use crate::{
+    location_history::{Location, LocationHistory, LocationKind},
     persistence::{
         SerializedAxis,
-        model::{DockData, DockStructure, SerializedItem, SerializedPane, SerializedPaneGroup},
+        model::{
+            DockData, DockStructure, SerializedDetachedPanel, SerializedItem, SerializedPane,
+            SerializedPaneGroup,
+        },
     },
     security_modal::SecurityModal,
 };
@@ -229,6 +239,9 @@ actions!(
         CloseWindow,
         /// Closes the current project.
         CloseProject,
+        /// Compares the active editor side by side with the active editor of another pane, or
+        /// with the clipboard when no other pane shows an editor.
+        CompareActiveEditors,
         /// Opens the feedback dialog.
         Feedback,
         /// Follows the next collaborator in the session.
@@ -263,12 +276,16 @@ actions!(
         Reload,
         /// Saves the current file with a new name.
         SaveAs,
+        /// Saves the arrangement of the docks and panes as a named layout.
+        SaveLayoutAs,
         /// Saves without formatting.
         SaveWithoutFormat,
         /// Shuts down all debug adapters.
         ShutdownDebugAdapters,
         /// Suppresses the current notification.
         SuppressNotification,
+        /// Arranges the docks and panes like one of the saved layouts.
+        SwitchLayout,
         /// Toggles the bottom dock.
         ToggleBottomDock,
         /// Toggles centered layout mode.
@@ -279,6 +296,9 @@ actions!(
         ToggleLeftDock,
         /// Toggles the right dock.
         ToggleRightDock,
+        /// Toggles zen mode, which hides the docks, tab bar, status bar and
+        /// gutter to leave only the centered editor.
+        ToggleZenMode,
         /// Toggles zoom on the active pane.
         ToggleZoom,
         /// Toggles read-only mode for the active item (if supported by that item).
@@ -435,6 +455,10 @@ pub struct NewTerminal {
     /// If true, creates a local terminal even in remote projects.
     #[serde(default)]
     pub local: bool,
+    /// The name of the terminal profile to open the terminal with, instead of
+    /// the default one.
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
 /// Increases size of a currently focused dock by a given amount of pixels.
@@ -506,6 +530,17 @@ actions!(
         MovePaneUp,
         /// Move the current pane to be at the very bottom.
         MovePaneDown,
+        /// Makes all the panes in the center the same size.
+        EqualizePaneSizes,
+        /// Shrinks the other panes to give the current pane as much room as
+        /// possible, or restores the pane sizes from before.
+        ToggleMaximizePane,
+        /// Moves each pane in the current pane's split one position to the
+        /// right or down, the last one becoming the first.
+        RotatePanesForward,
+        /// Moves each pane in the current pane's split one position to the
+        /// left or up, the first one becoming the last.
+        RotatePanesBackward,
     ]
 );
 
@@ -1201,6 +1236,12 @@ struct DispatchingKeystrokes {
     task: Option<Shared<Task<()>>>,
 }
 
+/// What entering zen mode changed, to put back when leaving it.
+struct ZenMode {
+    open_dock_positions: Vec<DockPosition>,
+    entered_fullscreen: bool,
+}
+
 /// Collects everything project-related for a certain window opened.
 /// In some way, is a counterpart of a window, as the [`WindowHandle`] could be downcast into `Workspace`.
 ///
@@ -1234,6 +1275,7 @@ pub struct Workspace {
     last_leaders_by_pane: HashMap<WeakEntity<Pane>, CollaboratorId>,
     window_edited: bool,
     last_window_title: Option<String>,
+    window_title_formatter: Option<Box<dyn Fn(&Workspace, &App) -> Option<String>>>,
     dirty_items: HashMap<EntityId, Subscription>,
     active_call: Option<(Entity<ActiveCall>, Vec<Subscription>)>,
     leader_updates_tx: mpsc::UnboundedSender<(PeerId, proto::UpdateFollowers)>,
@@ -1247,8 +1289,12 @@ pub struct Workspace {
     _serialize_workspace_task: Option<Task<()>>,
     _schedule_serialize_ssh_paths: Option<Task<()>>,
     pane_history_timestamp: Arc<AtomicUsize>,
+    location_history: LocationHistory,
     bounds: Bounds<Pixels>,
     pub centered_layout: bool,
+    zen_mode: Option<ZenMode>,
+    /// The sizes of the center's splits before the active pane was maximized.
+    maximized_pane_sizes: Option<Vec<Vec<f32>>>,
     bounds_save_task_queued: Option<Task<()>>,
     on_prompt_for_new_path: Option<PromptForNewPath>,
     on_prompt_for_open_path: Option<PromptForOpenPath>,
@@ -1441,6 +1487,16 @@ impl Workspace {
             },
         )
         .detach();
+        cx.subscribe_in(
+            &project.read(cx).bookmark_store(),
+            window,
+            |workspace, _, event, window, cx| match event {
+                BookmarkStoreEvent::BookmarksUpdated => {
+                    workspace.serialize_workspace(window, cx);
+                }
+            },
+        )
+        .detach();
         if let Some(toolchain_store) = project.read(cx).toolchain_store() {
             cx.subscribe_in(
                 &toolchain_store,
@@ -1463,6 +1519,7 @@ impl Workspace {
 
         let weak_handle = cx.entity().downgrade();
         let pane_history_timestamp = Arc::new(AtomicUsize::new(0));
+        let location_history = LocationHistory::default();
 
         let center_pane = cx.new(|cx| {
             let mut center_pane = Pane::new(
@@ -1477,6 +1534,8 @@ impl Workspace {
             );
             center_pane.set_can_split(Some(Arc::new(|_, _, _, _| true)));
             center_pane.set_should_display_welcome_page(true);
+            center_pane.set_can_show_vertical_tab_bar(true);
+            center_pane.set_location_history(location_history.clone());
             center_pane
         });
         cx.subscribe_in(&center_pane, window, Self::handle_pane_event)
@@ -1538,9 +1597,11 @@ impl Workspace {
         let left_dock_buttons = cx.new(|cx| PanelButtons::new(left_dock.clone(), cx));
         let bottom_dock_buttons = cx.new(|cx| PanelButtons::new(bottom_dock.clone(), cx));
         let right_dock_buttons = cx.new(|cx| PanelButtons::new(right_dock.clone(), cx));
+        let autosave_indicator = cx.new(|_| AutosaveIndicator::default());
         let status_bar = cx.new(|cx| {
             let mut status_bar = StatusBar::new(&center_pane.clone(), window, cx);
             status_bar.add_left_item(left_dock_buttons, window, cx);
+            status_bar.add_left_item(autosave_indicator, window, cx);
             status_bar.add_right_item(right_dock_buttons, window, cx);
             status_bar.add_right_item(bottom_dock_buttons, window, cx);
             status_bar
@@ -1661,6 +1722,7 @@ impl Workspace {
             dispatching_keystrokes: Default::default(),
             window_edited: false,
             last_window_title: None,
+            window_title_formatter: None,
             dirty_items: Default::default(),
             active_call,
             database_id: workspace_id,
@@ -1673,10 +1735,13 @@ impl Workspace {
             leader_updates_tx,
             _subscriptions: subscriptions,
             pane_history_timestamp,
+            location_history,
             workspace_actions: Default::default(),
             // This data will be incorrect, but it will be overwritten by the time it needs to be used.
             bounds: Default::default(),
             centered_layout: false,
+            zen_mode: None,
+            maximized_pane_sizes: None,
             bounds_save_task_queued: None,
             on_prompt_for_new_path: None,
             on_prompt_for_open_path: None,
@@ -2366,6 +2431,89 @@ impl Workspace {
         )
     }
 
+    pub fn location_history(&self) -> &LocationHistory {
+        &self.location_history
+    }
+
+    /// Goes to the location of `kind` before or after the one navigated to
+    /// last in the location history, skipping the locations near `current`.
+    pub fn go_to_adjacent_location(
+        &mut self,
+        forward: bool,
+        kind: LocationKind,
+        current: Option<(&ProjectPath, u32)>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<()>> {
+        match self.location_history.step(forward, kind, current) {
+            Some(location) => self.go_to_location(location, window, cx),
+            None => Task::ready(Ok(())),
+        }
+    }
+
+    /// Goes to the location at `index` in the location history.
+    pub fn go_to_location_at(
+        &mut self,
+        index: usize,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<()>> {
+        match self.location_history.select(index) {
+            Some(location) => self.go_to_location(location, window, cx),
+            None => Task::ready(Ok(())),
+        }
+    }
+
+    fn go_to_location(
+        &mut self,
+        location: Location,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<()>> {
+        let pane = location
+            .pane
+            .upgrade()
+            .filter(|pane| self.panes.contains(pane))
+            .unwrap_or_else(|| self.active_pane.clone());
+        let location_history = self.location_history.clone();
+        location_history.set_navigating(true);
+
+        let open_item = match location.entry.item.upgrade().and_then(|item| {
+            let index = pane.read(cx).index_for_item(item.as_ref())?;
+            Some((index, item))
+        }) {
+            Some((index, item)) => {
+                pane.update(cx, |pane, cx| {
+                    pane.activate_item(index, true, true, window, cx);
+                });
+                Task::ready(Ok(item))
+            }
+            // The item was closed since, so the location's file is opened
+            // again instead.
+            None => self.open_path_preview(
+                location.project_path,
+                Some(pane.downgrade()),
+                true,
+                location.entry.is_preview,
+                true,
+                window,
+                cx,
+            ),
+        };
+        cx.spawn_in(window, async move |_, cx| {
+            let result = async {
+                let item = open_item.await?;
+                if let Some(data) = location.entry.data {
+                    cx.update(|window, cx| item.navigate(data, window, cx))?;
+                }
+                anyhow::Ok(())
+            }
+            .await;
+            location_history.set_navigating(false);
+            result
+        })
+    }
+
     pub fn client(&self) -> &Arc<Client> {
         &self.app_state.client
     }
@@ -3806,6 +3954,7 @@ impl Workspace {
     }
 
     fn add_pane(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Entity<Pane> {
+        let zen_mode = self.zen_mode.is_some();
         let pane = cx.new(|cx| {
             let mut pane = Pane::new(
                 self.weak_handle(),
@@ -3818,6 +3967,9 @@ impl Workspace {
                 cx,
             );
             pane.set_can_split(Some(Arc::new(|_, _, _, _| true)));
+            pane.set_can_show_vertical_tab_bar(true);
+            pane.set_location_history(self.location_history.clone());
+            pane.set_zen_mode(zen_mode, window, cx);
             pane
         });
         cx.subscribe_in(&pane, window, Self::handle_pane_event)
@@ -4529,10 +4681,31 @@ impl Workspace {
     }
 
     pub fn reset_pane_sizes(&mut self, cx: &mut Context<Self>) {
+        self.maximized_pane_sizes = None;
         self.center.reset_pane_sizes(cx);
         cx.notify();
     }
 
+    pub fn toggle_maximize_pane(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(pane_sizes) = self.maximized_pane_sizes.take() {
+            self.center.restore_pane_sizes(&pane_sizes, cx);
+        } else if self.center.panes().contains(&&self.active_pane) {
+            self.maximized_pane_sizes = Some(self.center.pane_sizes());
+            self.center.maximize(&self.active_pane, &self.bounds, cx);
+        } else {
+            return;
+        }
+        self.serialize_workspace(window, cx);
+        cx.notify();
+    }
+
+    pub fn rotate_panes(&mut self, forward: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if self.center.rotate(&self.active_pane, forward, cx) {
+            self.serialize_workspace(window, cx);
+            cx.notify();
+        }
+    }
+
     fn handle_pane_focused(
         &mut self,
         pane: Entity<Pane>,
@@ -4706,7 +4879,9 @@ impl Workspace {
                 }
                 cx.notify();
             }
-            pane::Event::ItemPinned | pane::Event::ItemUnpinned => {}
+            pane::Event::ItemPinned
+            | pane::Event::ItemUnpinned
+            | pane::Event::BufferFontSizeChanged => {}
         }
 
         if serialize_workspace {
@@ -5114,7 +5289,40 @@ impl Workspace {
         self.update_window_title(window, cx);
     }
 
-    fn update_window_title(&mut self, window: &mut Window, cx: &mut App) {
+    /// Overrides how the window title is composed. When the formatter returns
+    /// `None`, the default title is used.
+    pub fn set_window_title_formatter(
+        &mut self,
+        formatter: impl Fn(&Workspace, &App) -> Option<String> + 'static,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.window_title_formatter = Some(Box::new(formatter));
+        self.update_window_title(window, cx);
+    }
+
+    pub fn update_window_title(&mut self, window: &mut Window, cx: &mut App) {
+        let title = self
+            .window_title_formatter
+            .as_ref()
+            .and_then(|formatter| formatter(self, cx))
+            .unwrap_or_else(|| self.default_window_title(cx));
+
+        if let Some(last_title) = self.last_window_title.as_ref()
+            && &title == last_title
+        {
+            return;
+        }
+        window.set_window_title(&title);
+        SystemWindowTabController::update_tab_title(
+            cx,
+            window.window_handle().window_id(),
+            SharedString::from(&title),
+        );
+        self.last_window_title = Some(title);
+    }
+
+    fn default_window_title(&self, cx: &App) -> String {
         let project = self.project().read(cx);
         let mut title = String::new();
 
@@ -5163,18 +5371,7 @@ impl Workspace {
             title.push_str(" ↗");
         }
 
-        if let Some(last_title) = self.last_window_title.as_ref()
-            && &title == last_title
-        {
-            return;
-        }
-        window.set_window_title(&title);
-        SystemWindowTabController::update_tab_title(
-            cx,
-            window.window_handle().window_id(),
-            SharedString::from(&title),
-        );
-        self.last_window_title = Some(title);
+        title
     }
 
     fn update_window_edited(&mut self, window: &mut Window, cx: &mut App) {
@@ -5183,6 +5380,10 @@ impl Workspace {
             self.window_edited = is_edited;
             window.set_window_edited(self.window_edited)
         }
+        // A custom title may include the dirty state of the active item.
+        if self.window_title_formatter.is_some() {
+            self.update_window_title(window, cx);
+        }
     }
 
     fn update_item_dirty_state(
@@ -5782,23 +5983,57 @@ impl Workspace {
                     .detach();
             }
         } else {
+            let mut items_to_autosave = Vec::new();
             for pane in &self.panes {
                 pane.update(cx, |pane, cx| {
                     if let Some(item) = pane.active_item() {
                         item.workspace_deactivated(window, cx);
                     }
-                    for item in pane.items() {
-                        if matches!(
-                            item.workspace_settings(cx).autosave,
-                            AutosaveSetting::OnWindowChange | AutosaveSetting::OnFocusChange
-                        ) {
-                            Pane::autosave_item(item.as_ref(), self.project.clone(), window, cx)
-                                .detach_and_log_err(cx);
-                        }
-                    }
+                    items_to_autosave.extend(
+                        pane.items()
+                            .filter(|item| {
+                                matches!(
+                                    item.autosave_setting(cx),
+                                    AutosaveSetting::OnWindowChange
+                                        | AutosaveSetting::OnFocusChange
+                                )
+                            })
+                            .map(|item| item.boxed_clone()),
+                    );
                 });
             }
+            for item in items_to_autosave {
+                self.autosave_item(item.as_ref(), window, cx)
+                    .detach_and_log_err(cx);
+            }
+        }
+    }
+
+    /// Saves `item` if it's dirty and can be saved without asking where to,
+    /// showing in the status bar that it was saved.
+    pub(crate) fn autosave_item(
+        &mut self,
+        item: &dyn ItemHandle,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if !item.can_autosave(cx) {
+            return Task::ready(Ok(()));
         }
+        let title = item.tab_content_text(0, cx);
+        let save = Pane::autosave_item(item, self.project.clone(), window, cx);
+        cx.spawn(async move |workspace, cx| {
+            save.await?;
+            workspace.update(cx, |workspace, cx| {
+                if let Some(indicator) = workspace
+                    .status_bar
+                    .read(cx)
+                    .item_of_type::<AutosaveIndicator>()
+                {
+                    indicator.update(cx, |indicator, cx| indicator.show_saved(title, cx));
+                }
+            })
+        })
     }
 
     pub fn active_call(&self) -> Option<&Entity<ActiveCall>> {
@@ -5918,7 +6153,7 @@ impl Workspace {
             window: &mut Window,
             cx: &mut App,
         ) -> SerializedPane {
-            let (items, active, pinned_count) = {
+            let (items, active, pinned_count, buffer_font_size_delta) = {
                 let pane = pane_handle.read(cx);
                 let active_item_id = pane.active_item().map(|item| item.item_id());
                 (
@@ -5936,10 +6171,14 @@ impl Workspace {
                         .collect::<Vec<_>>(),
                     pane.has_focus(window, cx),
                     pane.pinned_count(),
+                    f32::from(pane.buffer_font_size_delta()).round() as i32,
                 )
             };
 
-            SerializedPane::new(items, active, pinned_count)
+            SerializedPane {
+                buffer_font_size_delta,
+                ..SerializedPane::new(items, active, pinned_count)
+            }
         }
 
         fn build_serialized_pane_group(
@@ -5967,60 +6206,6 @@ impl Workspace {
             }
         }
 
-        fn build_serialized_docks(
-            this: &Workspace,
-            window: &mut Window,
-            cx: &mut App,
-        ) -> DockStructure {
-            let left_dock = this.left_dock.read(cx);
-            let left_visible = left_dock.is_open();
-            let left_active_panel = left_dock
-                .active_panel()
-                .map(|panel| panel.persistent_name().to_string());
-            let left_dock_zoom = left_dock
-                .active_panel()
-                .map(|panel| panel.is_zoomed(window, cx))
-                .unwrap_or(false);
-
-            let right_dock = this.right_dock.read(cx);
-            let right_visible = right_dock.is_open();
-            let right_active_panel = right_dock
-                .active_panel()
-                .map(|panel| panel.persistent_name().to_string());
-            let right_dock_zoom = right_dock
-                .active_panel()
-                .map(|panel| panel.is_zoomed(window, cx))
-                .unwrap_or(false);
-
-            let bottom_dock = this.bottom_dock.read(cx);
-            let bottom_visible = bottom_dock.is_open();
-            let bottom_active_panel = bottom_dock
-                .active_panel()
-                .map(|panel| panel.persistent_name().to_string());
-            let bottom_dock_zoom = bottom_dock
-                .active_panel()
-                .map(|panel| panel.is_zoomed(window, cx))
-                .unwrap_or(false);
-
-            DockStructure {
-                left: DockData {
-                    visible: left_visible,
-                    active_panel: left_active_panel,
-                    zoom: left_dock_zoom,
-                },
-                right: DockData {
-                    visible: right_visible,
-                    active_panel: right_active_panel,
-                    zoom: right_dock_zoom,
-                },
-                bottom: DockData {
-                    visible: bottom_visible,
-                    active_panel: bottom_active_panel,
-                    zoom: bottom_dock_zoom,
-                },
-            }
-        }
-
         match self.workspace_location(cx) {
             WorkspaceLocation::Location(location, paths) => {
                 let breakpoints = self.project.update(cx, |project, cx| {
@@ -6029,6 +6214,12 @@ impl Workspace {
                         .read(cx)
                         .all_source_breakpoints(cx)
                 });
+                let bookmarks = self
+                    .project
+                    .read(cx)
+                    .bookmark_store()
+                    .read(cx)
+                    .all_bookmarks(cx);
                 let user_toolchains = self
                     .project
                     .read(cx)
@@ -6038,6 +6229,29 @@ impl Workspace {
                 let center_group = build_serialized_pane_group(&self.center.root, window, cx);
                 let docks = build_serialized_docks(self, window, cx);
                 let window_bounds = Some(SerializedWindowBounds(window.window_bounds()));
+                let detached_windows = self
+                    .all_docks()
+                    .iter()
+                    .flat_map(|dock| {
+                        let dock = dock.read(cx);
+                        let dock_position = dock.position();
+                        dock.detached_windows()
+                            .map(move |(panel_name, window)| (dock_position, panel_name, window))
+                    })
+                    .collect::<Vec<_>>();
+                let detached_panels = detached_windows
+                    .into_iter()
+                    .map(
+                        |(dock_position, panel_name, detached_window)| SerializedDetachedPanel {
+                            panel_name: panel_name.to_string(),
+                            dock_position,
+                            window_bounds: detached_window
+                                .update(cx, |_, window, _| window.window_bounds())
+                                .ok()
+                                .map(SerializedWindowBounds),
+                        },
+                    )
+                    .collect();
 
                 let serialized_workspace = SerializedWorkspace {
                     id: database_id,
@@ -6050,6 +6264,8 @@ impl Workspace {
                     centered_layout: self.centered_layout,
                     session_id: self.session_id.clone(),
                     breakpoints,
+                    bookmarks,
+                    detached_panels,
                     window_id: Some(window.window_handle().window_id().as_u64()),
                     user_toolchains,
                 };
@@ -6237,6 +6453,7 @@ impl Workspace {
                 }
 
                 let docks = serialized_workspace.docks;
+                let detached_panels = serialized_workspace.detached_panels;
 
                 for (dock, serialized_dock) in [
                     (&mut workspace.right_dock, docks.right),
@@ -6247,6 +6464,11 @@ impl Workspace {
                 {
                     dock.update(cx, |dock, cx| {
                         dock.serialized_dock = Some(serialized_dock.clone());
+                        dock.serialized_detached_panels = detached_panels
+                            .iter()
+                            .filter(|detached_panel| detached_panel.dock_position == dock.position)
+                            .cloned()
+                            .collect();
                         dock.restore_state(window, cx);
                     });
                 }
@@ -6264,6 +6486,14 @@ impl Workspace {
                         })
                 })
                 .await;
+            project
+                .update(cx, |project, cx| {
+                    project.bookmark_store().update(cx, |bookmark_store, cx| {
+                        bookmark_store.with_serialized_bookmarks(serialized_workspace.bookmarks, cx)
+                    })
+                })
+                .await
+                .log_err();
 
             // Clean up all the items that have _not_ been loaded. Our ItemIds aren't stable. That means
             // after loading the items, we might have different items and in order to avoid
@@ -6419,6 +6649,25 @@ impl Workspace {
             .on_action(cx.listener(|workspace, _: &MovePaneDown, _, cx| {
                 workspace.move_pane_to_border(SplitDirection::Down, cx)
             }))
+            .on_action(cx.listener(|workspace, _: &EqualizePaneSizes, window, cx| {
+                workspace.reset_pane_sizes(cx);
+                workspace.serialize_workspace(window, cx);
+            }))
+            .on_action(
+                cx.listener(|workspace, _: &ToggleMaximizePane, window, cx| {
+                    workspace.toggle_maximize_pane(window, cx)
+                }),
+            )
+            .on_action(
+                cx.listener(|workspace, _: &RotatePanesForward, window, cx| {
+                    workspace.rotate_panes(true, window, cx)
+                }),
+            )
+            .on_action(
+                cx.listener(|workspace, _: &RotatePanesBackward, window, cx| {
+                    workspace.rotate_panes(false, window, cx)
+                }),
+            )
             .on_action(cx.listener(|this, _: &ToggleLeftDock, window, cx| {
                 this.toggle_dock(DockPosition::Left, window, cx);
             }))
@@ -6555,6 +6804,7 @@ impl Workspace {
                 },
             ))
             .on_action(cx.listener(Workspace::toggle_centered_layout))
+            .on_action(cx.listener(Workspace::toggle_zen_mode))
             .on_action(cx.listener(
                 |workspace: &mut Workspace, _action: &pane::ActivateNextItem, window, cx| {
                     if let Some(active_dock) = workspace.active_dock(window, cx) {
@@ -6671,6 +6921,11 @@ impl Workspace {
         self.database_id = Some(WorkspaceId(Uuid::new_v4().as_u64_pair().0 as i64));
     }
 
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn set_database_id_for_test(&mut self, id: WorkspaceId) {
+        self.set_database_id(id);
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) fn test_new(
         project: Entity<Project>,
@@ -6784,6 +7039,68 @@ impl Workspace {
         cx.notify();
     }
 
+    pub fn is_zen_mode(&self) -> bool {
+        self.zen_mode.is_some()
+    }
+
+    pub fn toggle_zen_mode(
+        &mut self,
+        _: &ToggleZenMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(zen_mode) = self.zen_mode.take() {
+            for position in zen_mode.open_dock_positions {
+                self.dock_at_position(position)
+                    .update(cx, |dock, cx| dock.set_open(true, window, cx));
+            }
+            if zen_mode.entered_fullscreen && window.is_fullscreen() {
+                window.toggle_fullscreen();
+            }
+        } else {
+            let open_dock_positions = self.get_open_dock_positions(cx);
+            for dock in self.all_docks() {
+                dock.update(cx, |dock, cx| dock.set_open(false, window, cx));
+            }
+            let entered_fullscreen =
+                WorkspaceSettings::get_global(cx).zen_mode.fullscreen && !window.is_fullscreen();
+            if entered_fullscreen {
+                window.toggle_fullscreen();
+            }
+            self.zen_mode = Some(ZenMode {
+                open_dock_positions,
+                entered_fullscreen,
+            });
+        }
+
+        let zen_mode = self.zen_mode.is_some();
+        for pane in self.center.panes() {
+            pane.update(cx, |pane, cx| pane.set_zen_mode(zen_mode, window, cx));
+        }
+        window.focus(&self.active_pane.focus_handle(cx), cx);
+        cx.notify();
+    }
+
+    /// The relative width of the paddings on each side of the center pane that
+    /// limit it to the given number of characters of the buffer font.
+    fn max_width_padding(&self, max_width: u32, cx: &App) -> f32 {
+        let theme_settings = ThemeSettings::get_global(cx);
+        let font_id = cx.text_system().resolve_font(&theme_settings.buffer_font);
+        let Some(em_advance) = cx
+            .text_system()
+            .em_advance(font_id, theme_settings.buffer_font_size(cx))
+            .log_err()
+        else {
+            return 0.;
+        };
+        let max_width = em_advance * max_width as f32;
+        let width = self.bounds.size.width;
+        if width <= max_width {
+            return 0.;
+        }
+        (width - max_width) / width / 2.
+    }
+
     fn adjust_padding(padding: Option<f32>) -> f32 {
         padding
             .unwrap_or(CenteredPaddingSettings::default().0)
@@ -7302,28 +7619,42 @@ impl Render for Workspace {
             }
         }
 
-        let centered_layout = self.centered_layout
-            && self.center.panes().len() == 1
-            && self.active_item(cx).is_some();
+        let single_active_pane = self.center.panes().len() == 1 && self.active_item(cx).is_some();
+        let centered_layout = self.centered_layout && single_active_pane;
+        let zen_mode = self.zen_mode.is_some();
         let render_padding = |size| {
             (size > 0.0).then(|| {
+                let colors = cx.theme().colors();
                 div()
                     .h_full()
                     .w(relative(size))
-                    .bg(cx.theme().colors().editor_background)
-                    .border_color(cx.theme().colors().pane_group_border)
+                    .bg(colors.editor_background)
+                    .border_color(if zen_mode {
+                        colors.editor_background
+                    } else {
+                        colors.pane_group_border
+                    })
             })
         };
-        let paddings = if centered_layout {
+        let paddings = if zen_mode && single_active_pane {
+            let max_width = WorkspaceSettings::get_global(cx).zen_mode.max_width;
+            let padding = self.max_width_padding(max_width, cx);
+            (render_padding(padding), render_padding(padding))
+        } else if centered_layout {
             let settings = WorkspaceSettings::get_global(cx).centered_layout;
-            (
-                render_padding(Self::adjust_padding(
-                    settings.left_padding.map(|padding| padding.0),
-                )),
-                render_padding(Self::adjust_padding(
-                    settings.right_padding.map(|padding| padding.0),
-                )),
-            )
+            let mut left_padding =
+                Self::adjust_padding(settings.left_padding.map(|padding| padding.0));
+            let mut right_padding =
+                Self::adjust_padding(settings.right_padding.map(|padding| padding.0));
+            if let Some(max_width) = settings.max_width {
+                let missing_padding =
+                    2. * self.max_width_padding(max_width, cx) - left_padding - right_padding;
+                if missing_padding > 0. {
+                    left_padding += missing_padding / 2.;
+                    right_padding += missing_padding / 2.;
+                }
+            }
+            (render_padding(left_padding), render_padding(right_padding))
         } else {
             (None, None)
         };
@@ -7376,6 +7707,9 @@ impl Render for Workspace {
                                 .border_t_1()
                                 .border_b_1()
                                 .border_color(colors.border)
+                                .on_drop(|dragged_panel: &DraggedPanel, window, cx| {
+                                    dragged_panel.detach(window, cx)
+                                })
                                 .child({
                                     let this = cx.entity();
                                     canvas(
@@ -7736,7 +8070,7 @@ impl Render for Workspace {
                                 }))
                                 .children(self.render_notifications(window, cx)),
                         )
-                        .when(self.status_bar_visible(cx), |parent| {
+                        .when(self.status_bar_visible(cx) && !zen_mode, |parent| {
                             parent.child(self.status_bar.clone())
                         })
                         .child(self.modal_layer.clone())
@@ -9533,6 +9867,60 @@ fn move_all_items(
     }
 }
 
+pub(crate) fn build_serialized_docks(
+    this: &Workspace,
+    window: &mut Window,
+    cx: &mut App,
+) -> DockStructure {
+    let left_dock = this.left_dock.read(cx);
+    let left_visible = left_dock.is_open();
+    let left_active_panel = left_dock
+        .active_panel()
+        .map(|panel| panel.persistent_name().to_string());
+    let left_dock_zoom = left_dock
+        .active_panel()
+        .map(|panel| panel.is_zoomed(window, cx))
+        .unwrap_or(false);
+
+    let right_dock = this.right_dock.read(cx);
+    let right_visible = right_dock.is_open();
+    let right_active_panel = right_dock
+        .active_panel()
+        .map(|panel| panel.persistent_name().to_string());
+    let right_dock_zoom = right_dock
+        .active_panel()
+        .map(|panel| panel.is_zoomed(window, cx))
+        .unwrap_or(false);
+
+    let bottom_dock = this.bottom_dock.read(cx);
+    let bottom_visible = bottom_dock.is_open();
+    let bottom_active_panel = bottom_dock
+        .active_panel()
+        .map(|panel| panel.persistent_name().to_string());
+    let bottom_dock_zoom = bottom_dock
+        .active_panel()
+        .map(|panel| panel.is_zoomed(window, cx))
+        .unwrap_or(false);
+
+    DockStructure {
+        left: DockData {
+            visible: left_visible,
+            active_panel: left_active_panel,
+            zoom: left_dock_zoom,
+        },
+        right: DockData {
+            visible: right_visible,
+            active_panel: right_active_panel,
+            zoom: right_dock_zoom,
+        },
+        bottom: DockData {
+            visible: bottom_visible,
+            active_panel: bottom_active_panel,
+            zoom: bottom_dock_zoom,
+        },
+    }
+}
+
 pub fn move_item(
     source: &Entity<Pane>,
     destination: &Entity<Pane>,
@@ -10255,7 +10643,8 @@ mod tests {
         item.update(cx, |item, cx| {
             SettingsStore::update_global(cx, |settings, cx| {
                 settings.update_user_settings(cx, |settings| {
-                    settings.workspace.autosave = Some(AutosaveSetting::OnWindowChange);
+                    settings.project.all_languages.defaults.autosave =
+                        Some(AutosaveSetting::OnWindowChange);
                 })
             });
             item.is_dirty = true;
@@ -10275,7 +10664,8 @@ mod tests {
             cx.focus_self(window);
             SettingsStore::update_global(cx, |settings, cx| {
                 settings.update_user_settings(cx, |settings| {
-                    settings.workspace.autosave = Some(AutosaveSetting::OnFocusChange);
+                    settings.project.all_languages.defaults.autosave =
+                        Some(AutosaveSetting::OnFocusChange);
                 })
             });
             item.is_dirty = true;
@@ -10297,9 +10687,10 @@ mod tests {
         item.update(cx, |item, cx| {
             SettingsStore::update_global(cx, |settings, cx| {
                 settings.update_user_settings(cx, |settings| {
-                    settings.workspace.autosave = Some(AutosaveSetting::AfterDelay {
-                        milliseconds: 500.into(),
-                    });
+                    settings.project.all_languages.defaults.autosave =
+                        Some(AutosaveSetting::AfterDelay {
+                            milliseconds: 500.into(),
+                        });
                 })
             });
             item.is_dirty = true;
@@ -10348,7 +10739,8 @@ mod tests {
         item.update(cx, |item, cx| {
             SettingsStore::update_global(cx, |settings, cx| {
                 settings.update_user_settings(cx, |settings| {
-                    settings.workspace.autosave = Some(AutosaveSetting::OnFocusChange);
+                    settings.project.all_languages.defaults.autosave =
+                        Some(AutosaveSetting::OnFocusChange);
                 })
             });
             item.is_dirty = true;
@@ -10445,6 +10837,61 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_drag_panel_into_window(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| {
+                let mut panel = TestPanel::new(DockPosition::Bottom, 100, cx);
+                panel.detachable = true;
+                panel
+            });
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+            panel
+        });
+        let bottom_dock = workspace.read_with(cx, |workspace, _| workspace.bottom_dock().clone());
+        assert!(bottom_dock.read_with(cx, |dock, _| dock.is_open()));
+
+        let dragged_panel = DraggedPanel {
+            dock: bottom_dock.downgrade(),
+            panel_id: panel.entity_id(),
+            icon: IconName::Terminal,
+        };
+        cx.update(|window, cx| dragged_panel.detach(window, cx));
+        cx.run_until_parked();
+        bottom_dock.read_with(cx, |dock, _| {
+            assert!(dock.is_panel_detached(panel.entity_id()));
+            assert!(!dock.is_open());
+        });
+        assert_eq!(cx.update(|_, cx| cx.windows().len()), 2);
+
+        // Dragging a detached panel again doesn't open another window.
+        cx.update(|window, cx| dragged_panel.detach(window, cx));
+        cx.run_until_parked();
+        assert_eq!(cx.update(|_, cx| cx.windows().len()), 2);
+
+        bottom_dock.update_in(cx, |dock, window, cx| {
+            dock.attach_panel(panel.entity_id(), window, cx)
+        });
+        cx.run_until_parked();
+        bottom_dock.read_with(cx, |dock, _| {
+            assert!(!dock.is_panel_detached(panel.entity_id()));
+            assert!(dock.is_open());
+            assert_eq!(
+                dock.active_panel().map(|panel| panel.panel_id()),
+                Some(panel.entity_id())
+            );
+        });
+        assert_eq!(cx.update(|_, cx| cx.windows().len()), 1);
+    }
+
     #[gpui::test]
     async fn test_toggle_docks_and_panels(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -10847,6 +11294,50 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_toggle_zen_mode(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        workspace.update_in(cx, |workspace, window, cx| {
+            let left_dock = workspace.dock_at_position(DockPosition::Left);
+            left_dock.update(cx, |dock, cx| dock.set_open(true, window, cx));
+
+            workspace.toggle_zen_mode(&ToggleZenMode, window, cx);
+            assert!(workspace.is_zen_mode());
+            assert!(
+                workspace
+                    .all_docks()
+                    .iter()
+                    .all(|dock| !dock.read(cx).is_open())
+            );
+
+            workspace.toggle_zen_mode(&ToggleZenMode, window, cx);
+            assert!(!workspace.is_zen_mode());
+            assert!(
+                workspace
+                    .dock_at_position(DockPosition::Left)
+                    .read(cx)
+                    .is_open()
+            );
+            assert!(
+                !workspace
+                    .dock_at_position(DockPosition::Right)
+                    .read(cx)
+                    .is_open()
+            );
+            assert!(
+                !workspace
+                    .dock_at_position(DockPosition::Bottom)
+                    .read(cx)
+                    .is_open()
+            );
+        });
+    }
+
     #[gpui::test]
     async fn test_toggle_all_with_manual_close(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -12468,6 +12959,54 @@ mod tests {
         })
     }
 
+    #[gpui::test]
+    async fn test_rotate_and_maximize_panes(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let pane_a = workspace.read_with(cx, |workspace, _| workspace.active_pane().clone());
+        let pane_b = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.split_pane(pane_a.clone(), SplitDirection::Right, window, cx)
+        });
+        let pane_c = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.split_pane(pane_b.clone(), SplitDirection::Right, window, cx)
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.set_active_pane(&pane_a, window, cx);
+        });
+        cx.run_until_parked();
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.rotate_panes(true, window, cx);
+            assert_eq!(
+                workspace.center.panes(),
+                [&pane_c, &pane_a, &pane_b],
+                "rotating forward moves the last pane first"
+            );
+            workspace.rotate_panes(false, window, cx);
+            assert_eq!(workspace.center.panes(), [&pane_a, &pane_b, &pane_c]);
+        });
+        cx.run_until_parked();
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_maximize_pane(window, cx);
+            let sizes = workspace.center.pane_sizes();
+            assert!(sizes[0][0] > sizes[0][1]);
+            assert_eq!(sizes[0][1], sizes[0][2]);
+
+            workspace.toggle_maximize_pane(window, cx);
+            assert_eq!(workspace.center.pane_sizes(), [vec![1., 1., 1.]]);
+
+            workspace.toggle_maximize_pane(window, cx);
+            workspace.reset_pane_sizes(cx);
+            assert_eq!(workspace.center.pane_sizes(), [vec![1., 1., 1.]]);
+        });
+    }
+
     #[gpui::test]
     async fn test_close_item_in_all_panes(cx: &mut TestAppContext) {
         init_test(cx);
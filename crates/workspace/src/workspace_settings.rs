@@ -6,7 +6,7 @@ use serde::Deserialize;
 pub use settings::{
     AutosaveSetting, BottomDockLayout, EncodingDisplayOptions, InactiveOpacity,
     PaneSplitDirectionHorizontal, PaneSplitDirectionVertical, RegisterSetting,
-    RestoreOnStartupBehavior, Settings,
+    RestoreOnStartupBehavior, Settings, TabBarPlacement,
 };
 
 #[derive(RegisterSetting)]
@@ -16,9 +16,9 @@ pub struct WorkspaceSettings {
     pub pane_split_direction_horizontal: settings::PaneSplitDirectionHorizontal,
     pub pane_split_direction_vertical: settings::PaneSplitDirectionVertical,
     pub centered_layout: settings::CenteredLayoutSettings,
+    pub zen_mode: ZenModeSettings,
     pub confirm_quit: bool,
     pub show_call_status_icon: bool,
-    pub autosave: AutosaveSetting,
     pub restore_on_startup: settings::RestoreOnStartupBehavior,
     pub restore_on_file_reopen: bool,
     pub drop_target_size: f32,
@@ -29,12 +29,14 @@ pub struct WorkspaceSettings {
     pub when_closing_with_no_tabs: settings::CloseWindowWhenNoItems,
     pub on_last_window_closed: settings::OnLastWindowClosed,
     pub text_rendering_mode: settings::TextRenderingMode,
+    pub presentation_mode: settings::PresentationMode,
     pub resize_all_panels_in_dock: Vec<DockPosition>,
     pub close_on_file_delete: bool,
     pub close_panel_on_toggle: bool,
     pub use_system_window_tabs: bool,
     pub zoomed_padding: bool,
     pub window_decorations: settings::WindowDecorations,
+    pub global_hotkeys: HashMap<String, settings::GlobalHotkeyAction>,
 }
 
 #[derive(Copy, Clone, PartialEq, Debug, Default)]
@@ -56,12 +58,22 @@ pub struct ActivePanelModifiers {
     pub inactive_opacity: Option<InactiveOpacity>,
 }
 
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ZenModeSettings {
+    /// The maximum width of the centered pane, in characters of the buffer font.
+    pub max_width: u32,
+    /// Whether entering zen mode also makes the window fullscreen.
+    pub fullscreen: bool,
+}
+
 #[derive(Deserialize, RegisterSetting)]
 pub struct TabBarSettings {
     pub show: bool,
     pub show_nav_history_buttons: bool,
     pub show_tab_bar_buttons: bool,
     pub show_pinned_tabs_in_separate_row: bool,
+    pub show_pinned_tabs_as_icons: bool,
+    pub placement: TabBarPlacement,
 }
 
 impl Settings for WorkspaceSettings {
@@ -88,9 +100,12 @@ impl Settings for WorkspaceSettings {
             pane_split_direction_horizontal: workspace.pane_split_direction_horizontal.unwrap(),
             pane_split_direction_vertical: workspace.pane_split_direction_vertical.unwrap(),
             centered_layout: workspace.centered_layout.unwrap(),
+            zen_mode: ZenModeSettings {
+                max_width: workspace.zen_mode.unwrap().max_width.unwrap(),
+                fullscreen: workspace.zen_mode.unwrap().fullscreen.unwrap(),
+            },
             confirm_quit: workspace.confirm_quit.unwrap(),
             show_call_status_icon: workspace.show_call_status_icon.unwrap(),
-            autosave: workspace.autosave.unwrap(),
             restore_on_startup: workspace.restore_on_startup.unwrap(),
             restore_on_file_reopen: workspace.restore_on_file_reopen.unwrap(),
             drop_target_size: workspace.drop_target_size.unwrap(),
@@ -101,6 +116,7 @@ impl Settings for WorkspaceSettings {
             when_closing_with_no_tabs: workspace.when_closing_with_no_tabs.unwrap(),
             on_last_window_closed: workspace.on_last_window_closed.unwrap(),
             text_rendering_mode: workspace.text_rendering_mode.unwrap(),
+            presentation_mode: workspace.presentation_mode.unwrap(),
             resize_all_panels_in_dock: workspace
                 .resize_all_panels_in_dock
                 .clone()
@@ -113,6 +129,7 @@ impl Settings for WorkspaceSettings {
             use_system_window_tabs: workspace.use_system_window_tabs.unwrap(),
             zoomed_padding: workspace.zoomed_padding.unwrap(),
             window_decorations: workspace.window_decorations.unwrap(),
+            global_hotkeys: workspace.global_hotkeys.clone(),
         }
     }
 }
@@ -125,6 +142,8 @@ impl Settings for TabBarSettings {
             show_nav_history_buttons: tab_bar.show_nav_history_buttons.unwrap(),
             show_tab_bar_buttons: tab_bar.show_tab_bar_buttons.unwrap(),
             show_pinned_tabs_in_separate_row: tab_bar.show_pinned_tabs_in_separate_row.unwrap(),
+            show_pinned_tabs_as_icons: tab_bar.show_pinned_tabs_as_icons.unwrap(),
+            placement: tab_bar.placement.unwrap(),
         }
     }
 }
@@ -6,7 +6,7 @@ use anyhow::{Context as _, Result, anyhow};
 use chardetng::EncodingDetector;
 use clock::ReplicaId;
 use collections::{HashMap, HashSet, VecDeque};
-use encoding_rs::Encoding;
+use encoding_rs::{CoderResult, Encoding};
 use fs::{Fs, MTime, PathEvent, RemoveOptions, Watcher, copy_recursive, read_dir_items};
 use futures::{
     FutureExt as _, Stream, StreamExt,
@@ -24,7 +24,7 @@ use git::{
 };
 use gpui::{
     App, AppContext as _, AsyncApp, BackgroundExecutor, Context, Entity, EventEmitter, Priority,
-    Task,
+    Task, WeakEntity,
 };
 use ignore::IgnoreStack;
 use language::DiskState;
@@ -46,7 +46,7 @@ use smallvec::{SmallVec, smallvec};
 use smol::channel::{self, Sender};
 use std::{
     any::Any,
-    borrow::Borrow as _,
+    borrow::{Borrow as _, Cow},
     cmp::Ordering,
     collections::hash_map,
     convert::TryFrom,
@@ -70,7 +70,7 @@ use util::{
     paths::{PathMatcher, PathStyle, SanitizedPath, home_dir},
     rel_path::RelPath,
 };
-pub use worktree_settings::WorktreeSettings;
+pub use worktree_settings::{LargeFileModeSettings, WorktreeSettings};
 
 use crate::ignore::IgnoreKind;
 
@@ -109,6 +109,16 @@ pub struct LoadedFile {
     pub has_bom: bool,
 }
 
+/// A file loaded in large-file mode, straight into a rope.
+#[derive(Debug)]
+pub struct LoadedLargeFile {
+    pub file: Arc<File>,
+    pub text: Rope,
+    pub line_ending: LineEnding,
+    pub encoding: &'static Encoding,
+    pub has_bom: bool,
+}
+
 pub struct LoadedBinaryFile {
     pub file: Arc<File>,
     pub content: Vec<u8>,
@@ -725,6 +735,21 @@ impl Worktree {
         }
     }
 
+    /// Like [`Self::load_file`], but reads the file into a rope as it goes,
+    /// for files too large to also hold in memory as a string.
+    pub fn load_large_file(
+        &self,
+        path: &RelPath,
+        cx: &Context<Worktree>,
+    ) -> Task<Result<LoadedLargeFile>> {
+        match self {
+            Worktree::Local(this) => this.load_large_file(path, cx),
+            Worktree::Remote(_) => {
+                Task::ready(Err(anyhow!("remote worktrees can't yet load files")))
+            }
+        }
+    }
+
     pub fn load_binary_file(
         &self,
         path: &RelPath,
@@ -1302,33 +1327,9 @@ impl LocalWorktree {
         let worktree = cx.weak_entity();
         cx.background_spawn(async move {
             let content = fs.load_bytes(&abs_path).await?;
-
-            let worktree = worktree.upgrade().context("worktree was dropped")?;
-            let file = match entry.await? {
-                Some(entry) => File::for_entry(entry, worktree),
-                None => {
-                    let metadata = fs
-                        .metadata(&abs_path)
-                        .await
-                        .with_context(|| {
-                            format!("Loading metadata for excluded file {abs_path:?}")
-                        })?
-                        .with_context(|| {
-                            format!("Excluded file {abs_path:?} got removed during loading")
-                        })?;
-                    Arc::new(File {
-                        entry_id: None,
-                        worktree,
-                        path,
-                        disk_state: DiskState::Present {
-                            mtime: metadata.mtime,
-                        },
-                        is_local: true,
-                        is_private,
-                    })
-                }
-            };
-
+            let file =
+                loaded_file_handle(worktree, entry, fs.as_ref(), &abs_path, path, is_private)
+                    .await?;
             Ok(LoadedBinaryFile { file, content })
         })
     }
@@ -1358,36 +1359,39 @@ impl LocalWorktree {
                 }
             }
             let (text, encoding, has_bom) = decode_file_text(fs.as_ref(), &abs_path).await?;
+            let file =
+                loaded_file_handle(this, entry, fs.as_ref(), &abs_path, path, is_private).await?;
+            Ok(LoadedFile {
+                file,
+                text,
+                encoding,
+                has_bom,
+            })
+        })
+    }
 
-            let worktree = this.upgrade().context("worktree was dropped")?;
-            let file = match entry.await? {
-                Some(entry) => File::for_entry(entry, worktree),
-                None => {
-                    let metadata = fs
-                        .metadata(&abs_path)
-                        .await
-                        .with_context(|| {
-                            format!("Loading metadata for excluded file {abs_path:?}")
-                        })?
-                        .with_context(|| {
-                            format!("Excluded file {abs_path:?} got removed during loading")
-                        })?;
-                    Arc::new(File {
-                        entry_id: None,
-                        worktree,
-                        path,
-                        disk_state: DiskState::Present {
-                            mtime: metadata.mtime,
-                        },
-                        is_local: true,
-                        is_private,
-                    })
-                }
-            };
+    #[ztracing::instrument(skip_all)]
+    fn load_large_file(
+        &self,
+        path: &RelPath,
+        cx: &Context<Worktree>,
+    ) -> Task<Result<LoadedLargeFile>> {
+        let path = Arc::from(path);
+        let abs_path = self.absolutize(&path);
+        let fs = self.fs.clone();
+        let entry = self.refresh_entry(path.clone(), None, cx);
+        let is_private = self.is_path_private(path.as_ref());
 
-            Ok(LoadedFile {
+        let this = cx.weak_entity();
+        cx.background_spawn(async move {
+            let (text, line_ending, encoding, has_bom) =
+                decode_large_file_text(fs.as_ref(), &abs_path).await?;
+            let file =
+                loaded_file_handle(this, entry, fs.as_ref(), &abs_path, path, is_private).await?;
+            Ok(LoadedLargeFile {
                 file,
                 text,
+                line_ending,
                 encoding,
                 has_bom,
             })
@@ -5927,8 +5931,47 @@ impl fs::Watcher for NullWatcher {
     }
 }
 
+/// The file loaded at `abs_path`, with the worktree entry `entry` resolves to,
+/// if the file wasn't excluded from the worktree.
+async fn loaded_file_handle(
+    worktree: WeakEntity<Worktree>,
+    entry: Task<Result<Option<Entry>>>,
+    fs: &dyn Fs,
+    abs_path: &Path,
+    path: Arc<RelPath>,
+    is_private: bool,
+) -> Result<Arc<File>> {
+    let worktree = worktree.upgrade().context("worktree was dropped")?;
+    let file = match entry.await? {
+        Some(entry) => File::for_entry(entry, worktree),
+        None => {
+            let metadata = fs
+                .metadata(abs_path)
+                .await
+                .with_context(|| format!("Loading metadata for excluded file {abs_path:?}"))?
+                .with_context(|| {
+                    format!("Excluded file {abs_path:?} got removed during loading")
+                })?;
+            Arc::new(File {
+                entry_id: None,
+                worktree,
+                path,
+                disk_state: DiskState::Present {
+                    mtime: metadata.mtime,
+                },
+                is_local: true,
+                is_private,
+            })
+        }
+    };
+    Ok(file)
+}
+
 const FILE_ANALYSIS_BYTES: usize = 1024;
 
+/// How many bytes of a large file are read at a time.
+const LARGE_FILE_CHUNK_BYTES: usize = 64 * 1024;
+
 async fn decode_file_text(
     fs: &dyn Fs,
     abs_path: &Path,
@@ -5979,6 +6022,173 @@ async fn decode_file_text(
     decode_byte_full(content, bom_encoding, byte_content)
 }
 
+/// Like [`decode_file_text`], but reads the file in chunks and builds the rope
+/// as it goes, so that the text isn't also held in memory as bytes and as a
+/// string. Files that aren't BOM-less UTF-8 are read again once their encoding
+/// is known, and files without a BOM are read once more before that to detect
+/// it.
+async fn decode_large_file_text(
+    fs: &dyn Fs,
+    abs_path: &Path,
+) -> Result<(Rope, LineEnding, &'static Encoding, bool)> {
+    let mut file = fs
+        .open_sync(abs_path)
+        .await
+        .with_context(|| format!("opening file {abs_path:?}"))?;
+
+    let mut text = LargeFileText::default();
+    let mut pending = Vec::with_capacity(LARGE_FILE_CHUNK_BYTES);
+    let mut buf = vec![0u8; LARGE_FILE_CHUNK_BYTES];
+    let mut header = None;
+    loop {
+        let read_len = file
+            .read(&mut buf)
+            .with_context(|| format!("reading bytes of the file {abs_path:?}"))?;
+        let reached_eof = read_len == 0;
+        pending.extend_from_slice(&buf[..read_len]);
+
+        let (bom_encoding, byte_content) = match header {
+            Some(header) => header,
+            None => {
+                if pending.len() < FILE_ANALYSIS_BYTES && !reached_eof {
+                    continue;
+                }
+                *header.insert(decode_byte_header(&pending))
+            }
+        };
+        anyhow::ensure!(
+            byte_content != ByteContent::Binary,
+            "Binary files are not supported"
+        );
+        if bom_encoding.is_some() || byte_content != ByteContent::Unknown {
+            break;
+        }
+
+        let valid_len = match std::str::from_utf8(&pending) {
+            Ok(_) => pending.len(),
+            // A character split between reads is completed by the next one.
+            Err(error) if error.error_len().is_none() && !reached_eof => error.valid_up_to(),
+            Err(_) => break,
+        };
+        let chunk = std::str::from_utf8(&pending[..valid_len])?;
+        // See `decode_byte_full` on why escape characters need the encoding detected.
+        if chunk.contains('\x1b') {
+            break;
+        }
+        let chunk_len = text.push(chunk, reached_eof);
+        pending.drain(..chunk_len);
+
+        if reached_eof {
+            let (text, line_ending) = text.finish();
+            return Ok((text, line_ending, encoding_rs::UTF_8, false));
+        }
+    }
+    drop(text);
+    drop(pending);
+    drop(file);
+
+    let (encoding, has_bom) = match header {
+        Some((Some(encoding), _)) => (encoding, true),
+        Some((None, ByteContent::Utf16Le)) => (encoding_rs::UTF_16LE, false),
+        Some((None, ByteContent::Utf16Be)) => (encoding_rs::UTF_16BE, false),
+        _ => (
+            detect_large_file_encoding(fs, abs_path, &mut buf).await?,
+            false,
+        ),
+    };
+    let mut decoder = if has_bom {
+        encoding.new_decoder_with_bom_removal()
+    } else {
+        encoding.new_decoder()
+    };
+    let mut file = fs
+        .open_sync(abs_path)
+        .await
+        .with_context(|| format!("opening file {abs_path:?}"))?;
+    let mut text = LargeFileText::default();
+    let mut decoded = String::new();
+    loop {
+        let read_len = file
+            .read(&mut buf)
+            .with_context(|| format!("reading bytes of the file {abs_path:?}"))?;
+        let reached_eof = read_len == 0;
+        let mut bytes = &buf[..read_len];
+        loop {
+            decoded.reserve(
+                decoder
+                    .max_utf8_buffer_length(bytes.len())
+                    .unwrap_or(LARGE_FILE_CHUNK_BYTES),
+            );
+            let (result, consumed_len, _) =
+                decoder.decode_to_string(bytes, &mut decoded, reached_eof);
+            bytes = &bytes[consumed_len..];
+            if result == CoderResult::InputEmpty {
+                break;
+            }
+        }
+        let chunk_len = text.push(&decoded, reached_eof);
+        decoded.drain(..chunk_len);
+
+        if reached_eof {
+            let (text, line_ending) = text.finish();
+            return Ok((text, line_ending, encoding, has_bom));
+        }
+    }
+}
+
+/// Detects the encoding of a file without a BOM the way [`decode_byte_full`]
+/// does, feeding the detector one chunk at a time.
+async fn detect_large_file_encoding(
+    fs: &dyn Fs,
+    abs_path: &Path,
+    buf: &mut [u8],
+) -> Result<&'static Encoding> {
+    let mut file = fs
+        .open_sync(abs_path)
+        .await
+        .with_context(|| format!("opening file {abs_path:?}"))?;
+    let mut detector = EncodingDetector::new();
+    loop {
+        let read_len = file
+            .read(buf)
+            .with_context(|| format!("reading bytes of the file {abs_path:?}"))?;
+        let reached_eof = read_len == 0;
+        detector.feed(&buf[..read_len], reached_eof);
+        if reached_eof {
+            return Ok(detector.guess(None, true));
+        }
+    }
+}
+
+/// The text of a large file, collected one decoded chunk at a time.
+#[derive(Default)]
+struct LargeFileText {
+    text: Rope,
+    line_ending: Option<LineEnding>,
+}
+
+impl LargeFileText {
+    /// Appends as much of `chunk` as can be normalized, and returns its length.
+    fn push(&mut self, chunk: &str, reached_eof: bool) -> usize {
+        let mut chunk_len = chunk.len();
+        // Keep a "\r\n" split between reads together, so it's normalized as one line ending.
+        if !reached_eof && chunk.ends_with('\r') {
+            chunk_len -= 1;
+        }
+        let chunk = &chunk[..chunk_len];
+        if self.line_ending.is_none() && (chunk.contains('\n') || reached_eof) {
+            self.line_ending = Some(LineEnding::detect(chunk));
+        }
+        self.text
+            .push(&LineEnding::normalize_cow(Cow::Borrowed(chunk)));
+        chunk_len
+    }
+
+    fn finish(self) -> (Rope, LineEnding) {
+        (self.text, self.line_ending.unwrap_or_default())
+    }
+}
+
 fn decode_byte_header(prefix: &[u8]) -> (Option<&'static Encoding>, ByteContent) {
     if let Some((encoding, _bom_len)) = Encoding::for_bom(prefix) {
         return (Some(encoding), ByteContent::Unknown);
@@ -6043,7 +6253,7 @@ fn decode_byte_full(
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 enum ByteContent {
     Utf16Le,
     Utf16Be,
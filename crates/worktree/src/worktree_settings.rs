@@ -21,6 +21,16 @@ pub struct WorktreeSettings {
     pub private_files: PathMatcher,
     pub hidden_files: PathMatcher,
     pub read_only_files: PathMatcher,
+    pub large_file_mode: LargeFileModeSettings,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LargeFileModeSettings {
+    /// The size in bytes from which files are opened in large-file mode.
+    pub threshold: u64,
+    pub syntax_highlighting: bool,
+    pub language_servers: bool,
+    pub soft_wrap: bool,
 }
 
 impl WorktreeSettings {
@@ -64,6 +74,7 @@ impl Settings for WorktreeSettings {
         let private_files = worktree.private_files.unwrap().0;
         let hidden_files = worktree.hidden_files.unwrap();
         let read_only_files = worktree.read_only_files.unwrap_or_default();
+        let large_file_mode = worktree.large_file_mode.unwrap();
         let parsed_file_scan_inclusions: Vec<String> = file_scan_inclusions
             .iter()
             .flat_map(|glob| {
@@ -97,6 +108,12 @@ impl Settings for WorktreeSettings {
             read_only_files: path_matchers(read_only_files, "read_only_files")
                 .log_err()
                 .unwrap_or_default(),
+            large_file_mode: LargeFileModeSettings {
+                threshold: large_file_mode.threshold.unwrap(),
+                syntax_highlighting: large_file_mode.syntax_highlighting.unwrap(),
+                language_servers: large_file_mode.language_servers.unwrap(),
+                soft_wrap: large_file_mode.soft_wrap.unwrap(),
+            },
         }
     }
 }
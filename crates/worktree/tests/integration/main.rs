@@ -2737,6 +2737,124 @@ async fn test_load_file_encoding(cx: &mut TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_load_large_file(cx: &mut TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+
+    // Large enough to be read in several chunks, with characters and line
+    // endings split between them.
+    let multibyte_text = "こんにちは\r\n".repeat(10_000);
+    let crlf_text = format!("yy{}", "x\r\n".repeat(50_000));
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            "multibyte.txt": multibyte_text,
+            "crlf.txt": crlf_text,
+        }),
+    )
+    .await;
+    fs.write(
+        Path::new(path!("/root/sjis.txt")),
+        &[0x82, 0xb1, 0x82, 0xf1, 0x82, 0xc9, 0x82, 0xbf, 0x82, 0xcd],
+    )
+    .await
+    .unwrap();
+    // Only valid UTF-8 up to its last chunk, so it's detected from all of its bytes.
+    let mut late_sjis_bytes = "a\n".repeat(40_000).into_bytes();
+    late_sjis_bytes
+        .extend_from_slice(&[0x82, 0xb1, 0x82, 0xf1, 0x82, 0xc9, 0x82, 0xbf, 0x82, 0xcd]);
+    fs.write(Path::new(path!("/root/late_sjis.txt")), &late_sjis_bytes)
+        .await
+        .unwrap();
+    // ISO-2022-JP for "こんにちは", after enough ASCII to be read in several chunks.
+    let mut late_escape_bytes = "b\r\n".repeat(40_000).into_bytes();
+    late_escape_bytes.extend_from_slice(b"\x1b$B$3$s$K$A$O\x1b(B");
+    fs.write(
+        Path::new(path!("/root/late_escape.txt")),
+        &late_escape_bytes,
+    )
+    .await
+    .unwrap();
+    let utf16_text = "行\r\n".repeat(40_000);
+    let mut utf16_bytes = vec![0xff, 0xfe];
+    utf16_bytes.extend(utf16_text.encode_utf16().flat_map(u16::to_le_bytes));
+    fs.write(Path::new(path!("/root/utf16.txt")), &utf16_bytes)
+        .await
+        .unwrap();
+
+    let tree = Worktree::local(
+        Path::new(path!("/root")),
+        true,
+        fs,
+        Default::default(),
+        true,
+        WorktreeId::from_proto(0),
+        &mut cx.to_async(),
+    )
+    .await
+    .unwrap();
+    cx.read(|cx| tree.read(cx).as_local().unwrap().scan_complete())
+        .await;
+
+    for (name, expected_text, expected_line_ending, expected_encoding, expected_has_bom) in [
+        (
+            "multibyte.txt",
+            multibyte_text.replace("\r\n", "\n"),
+            text::LineEnding::Windows,
+            encoding_rs::UTF_8,
+            false,
+        ),
+        (
+            "crlf.txt",
+            crlf_text.replace("\r\n", "\n"),
+            text::LineEnding::Windows,
+            encoding_rs::UTF_8,
+            false,
+        ),
+        (
+            "sjis.txt",
+            "こんにちは".to_string(),
+            text::LineEnding::default(),
+            encoding_rs::SHIFT_JIS,
+            false,
+        ),
+        (
+            "late_sjis.txt",
+            format!("{}こんにちは", "a\n".repeat(40_000)),
+            text::LineEnding::Unix,
+            encoding_rs::SHIFT_JIS,
+            false,
+        ),
+        (
+            "late_escape.txt",
+            format!("{}こんにちは", "b\n".repeat(40_000)),
+            text::LineEnding::Windows,
+            encoding_rs::ISO_2022_JP,
+            false,
+        ),
+        (
+            "utf16.txt",
+            utf16_text.replace("\r\n", "\n"),
+            text::LineEnding::Windows,
+            encoding_rs::UTF_16LE,
+            true,
+        ),
+    ] {
+        let loaded = tree
+            .update(cx, |tree, cx| tree.load_large_file(rel_path(name), cx))
+            .await
+            .unwrap();
+        assert_eq!(loaded.text.to_string(), expected_text, "text of {name}");
+        assert_eq!(
+            loaded.line_ending, expected_line_ending,
+            "line ending of {name}"
+        );
+        assert_eq!(loaded.encoding, expected_encoding, "encoding of {name}");
+        assert_eq!(loaded.has_bom, expected_has_bom, "BOM of {name}");
+    }
+}
+
 #[gpui::test]
 async fn test_write_file_encoding(cx: &mut gpui::TestAppContext) {
     init_test(cx);
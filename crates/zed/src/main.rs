@@ -61,7 +61,7 @@ use zed::{
     OpenListener, OpenRequest, RawOpenRequest, app_menus, build_window_options,
     derive_paths_with_position, edit_prediction_registry, handle_cli_connection,
     handle_keymap_file_changes, handle_settings_file_changes, initialize_workspace,
-    open_paths_with_positions,
+    open_paths_with_positions, watch_theme_file,
 };
 
 use crate::zed::{OpenRequestKind, eager_load_active_theme_and_icon_theme};
@@ -472,6 +472,7 @@ fn main() {
         cx.set_http_client(client.http_client());
         let mut languages = LanguageRegistry::new(cx.background_executor().clone());
         languages.set_language_server_download_dir(paths::languages_dir().clone());
+        languages.set_user_languages_dir(paths::user_languages_dir().clone());
         let languages = Arc::new(languages);
         let (mut tx, rx) = watch::channel(None);
         cx.observe_global::<SettingsStore>(move |cx| {
@@ -657,8 +658,11 @@ fn main() {
         project_symbols::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
+        bookmarks_panel::init(cx);
+        hierarchy_panel::init(cx);
         tasks_ui::init(cx);
         snippets_ui::init(cx);
+        spell_check::init(cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
         search::init(cx);
         cx.set_global(workspace::PaneSearchBarCallbacks {
@@ -679,9 +683,18 @@ fn main() {
         toolchain_selector::init(cx);
         theme_selector::init(cx);
         settings_profile_selector::init(cx);
+        layout_selector::init(cx);
         language_tools::init(cx);
         call::init(app_state.client.clone(), app_state.user_store.clone(), cx);
         notifications::init(app_state.client.clone(), app_state.user_store.clone(), cx);
+        system_tray::init(
+            {
+                let app_state = Arc::downgrade(&app_state);
+                move |event, cx| zed::handle_system_tray_event(event, app_state.clone(), cx)
+            },
+            cx,
+        );
+        zed::init_global_hotkeys(Arc::downgrade(&app_state), cx);
         collab_ui::init(&app_state, cx);
         git_ui::init(cx);
         git_graph::init(cx);
@@ -722,6 +735,14 @@ fn main() {
                         }
                     },
                 );
+                cx.set_presentation_mode(
+                    match WorkspaceSettings::get_global(cx).presentation_mode {
+                        settings::PresentationMode::Vsync => gpui::PresentationMode::VSync,
+                        settings::PresentationMode::LowLatency => {
+                            gpui::PresentationMode::LowLatency
+                        }
+                    },
+                );
 
                 let new_host = &client::ClientSettings::get_global(cx).server_url;
                 if &http.base_url() != new_host {
@@ -756,6 +777,9 @@ fn main() {
         let fs = app_state.fs.clone();
         load_user_themes_in_background(fs.clone(), cx);
         watch_themes(fs.clone(), cx);
+        load_user_icon_themes_in_background(fs.clone(), cx);
+        watch_icon_themes(fs.clone(), cx);
+        watch_theme_file(fs.clone(), cx);
         watch_languages(fs.clone(), app_state.languages.clone(), cx);
 
         let menus = app_menus(cx);
@@ -1711,6 +1735,56 @@ fn watch_themes(fs: Arc<dyn fs::Fs>, cx: &mut App) {
     .detach()
 }
 
+/// Spawns a background task to load the user icon themes from the icon themes
+/// directory.
+fn load_user_icon_themes_in_background(fs: Arc<dyn fs::Fs>, cx: &mut App) {
+    cx.spawn(async move |cx| {
+        let icon_themes_dir = paths::icon_themes_dir().as_ref();
+        if !fs.is_dir(icon_themes_dir).await {
+            return anyhow::Ok(());
+        }
+        let theme_registry = cx.update(|cx| ThemeRegistry::global(cx));
+        theme_registry
+            .load_user_icon_themes(icon_themes_dir, fs)
+            .await?;
+        cx.update(GlobalTheme::reload_icon_theme);
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Spawns a background task to watch the icon themes directory for changes.
+fn watch_icon_themes(fs: Arc<dyn fs::Fs>, cx: &mut App) {
+    use std::time::Duration;
+    cx.spawn(async move |cx| {
+        let icon_themes_dir = paths::icon_themes_dir();
+        let (mut events, _) = fs.watch(icon_themes_dir, Duration::from_millis(100)).await;
+
+        while let Some(paths) = events.next().await {
+            for event in paths {
+                if event
+                    .path
+                    .extension()
+                    .is_none_or(|extension| extension != "json")
+                    || fs.metadata(&event.path).await.ok().flatten().is_none()
+                {
+                    continue;
+                }
+                let theme_registry = cx.update(|cx| ThemeRegistry::global(cx));
+                if theme_registry
+                    .load_icon_theme(&event.path, icon_themes_dir, fs.clone())
+                    .await
+                    .log_err()
+                    .is_some()
+                {
+                    cx.update(GlobalTheme::reload_icon_theme);
+                }
+            }
+        }
+    })
+    .detach()
+}
+
 #[cfg(debug_assertions)]
 fn watch_languages(fs: Arc<dyn fs::Fs>, languages: Arc<LanguageRegistry>, cx: &mut App) {
     use std::time::Duration;
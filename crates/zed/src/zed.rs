@@ -18,9 +18,10 @@ use anyhow::Context as _;
 pub use app_menus::*;
 use assets::Assets;
 use audio::{AudioSettings, REPLAY_DURATION};
+use bookmarks_panel::BookmarksPanel;
 use breadcrumbs::Breadcrumbs;
 use client::zed_urls;
-use collections::VecDeque;
+use collections::{HashMap, VecDeque};
 use debugger_ui::debugger_panel::DebugPanel;
 use editor::{Editor, MultiBuffer};
 use extension_host::ExtensionStore;
@@ -33,14 +34,17 @@ use git_ui::commit_view::CommitViewToolbar;
 use git_ui::git_panel::GitPanel;
 use git_ui::project_diff::{BranchDiffToolbar, ProjectDiffToolbar};
 use gpui::{
-    Action, App, AppContext as _, AsyncWindowContext, Context, DismissEvent, Element, Entity,
-    Focusable, KeyBinding, ParentElement, PathPromptOptions, PromptLevel, ReadGlobal, SharedString,
-    Task, TitlebarOptions, UpdateGlobal, WeakEntity, Window, WindowHandle, WindowKind,
-    WindowOptions, actions, image_cache, point, px, retain_all,
+    Action, App, AppContext as _, AsyncApp, AsyncWindowContext, ClipboardItem, Context,
+    DismissEvent, Element, Entity, Focusable, KeyBinding, Keystroke, ParentElement,
+    PathPromptOptions, PromptLevel, ReadGlobal, SharedString, Subscription, Task, TitlebarOptions,
+    UpdateGlobal, WeakEntity, Window, WindowHandle, WindowKind, WindowOptions, actions,
+    image_cache, point, px, retain_all,
 };
+use hierarchy_panel::HierarchyPanel;
 use image_viewer::ImageInfo;
 use language::Capability;
 use language_onboarding::BasedPyrightBanner;
+use language_tools::LanguageServersPanel;
 use language_tools::lsp_button::{self, LspButton};
 use language_tools::lsp_log_view::LspLogToolbarItemView;
 use markdown::{Markdown, MarkdownElement, MarkdownFont, MarkdownStyle};
@@ -63,8 +67,8 @@ use release_channel::{AppCommitSha, AppVersion, ReleaseChannel};
 use rope::Rope;
 use search::project_search::ProjectSearchBar;
 use settings::{
-    BaseKeymap, DEFAULT_KEYMAP_PATH, InvalidSettingsError, KeybindSource, KeymapFile,
-    KeymapFileLoadResult, MigrationStatus, Settings, SettingsStore, VIM_KEYMAP_PATH,
+    BaseKeymap, DEFAULT_KEYMAP_PATH, GlobalHotkeyAction, InvalidSettingsError, KeybindSource,
+    KeymapFile, KeymapFileLoadResult, MigrationStatus, Settings, SettingsStore, VIM_KEYMAP_PATH,
     initial_local_debug_tasks_content, initial_project_settings_content, initial_tasks_content,
     update_settings_file,
 };
@@ -73,8 +77,8 @@ use std::time::Duration;
 use std::{
     borrow::Cow,
     path::{Path, PathBuf},
-    sync::Arc,
     sync::atomic::{self, AtomicBool},
+    sync::{Arc, Weak},
 };
 use terminal_view::terminal_panel::{self, TerminalPanel};
 use theme::{ActiveTheme, GlobalTheme, SystemAppearance, ThemeRegistry, ThemeSettings};
@@ -99,8 +103,8 @@ use workspace::{
 };
 use workspace::{Pane, notifications::DetachAndPromptErr};
 use zed_actions::{
-    OpenAccountSettings, OpenBrowser, OpenDocs, OpenServerSettings, OpenSettingsFile, OpenZedUrl,
-    Quit,
+    CaptureScreenshot, OpenAccountSettings, OpenBrowser, OpenDocs, OpenServerSettings,
+    OpenSettingsFile, OpenZedUrl, Quit,
 };
 
 actions!(
@@ -130,6 +134,11 @@ actions!(
         ResetDatabase,
         /// Shows all hidden windows.
         ShowAll,
+        /// Toggles whether the current window stays above other windows. Only
+        /// supported on X11.
+        ToggleAlwaysOnTop,
+        /// Toggles between window decorations drawn by Zed and by the system.
+        ToggleWindowDecorations,
         /// Toggles fullscreen mode.
         ToggleFullScreen,
         /// Zooms the window.
@@ -312,6 +321,17 @@ fn bind_on_window_closed(cx: &mut App) -> Option<gpui::Subscription> {
     }
 }
 
+fn window_decorations(cx: &App) -> gpui::WindowDecorations {
+    match std::env::var("ZED_WINDOW_DECORATIONS") {
+        Ok(val) if val == "server" => gpui::WindowDecorations::Server,
+        Ok(val) if val == "client" => gpui::WindowDecorations::Client,
+        _ => match WorkspaceSettings::get_global(cx).window_decorations {
+            settings::WindowDecorations::Server => gpui::WindowDecorations::Server,
+            settings::WindowDecorations::Client => gpui::WindowDecorations::Client,
+        },
+    }
+}
+
 pub fn build_window_options(display_uuid: Option<Uuid>, cx: &mut App) -> WindowOptions {
     let display = display_uuid.and_then(|uuid| {
         cx.displays()
@@ -319,14 +339,7 @@ pub fn build_window_options(display_uuid: Option<Uuid>, cx: &mut App) -> WindowO
             .find(|display| display.uuid().ok() == Some(uuid))
     });
     let app_id = ReleaseChannel::global(cx).app_id();
-    let window_decorations = match std::env::var("ZED_WINDOW_DECORATIONS") {
-        Ok(val) if val == "server" => gpui::WindowDecorations::Server,
-        Ok(val) if val == "client" => gpui::WindowDecorations::Client,
-        _ => match WorkspaceSettings::get_global(cx).window_decorations {
-            settings::WindowDecorations::Server => gpui::WindowDecorations::Server,
-            settings::WindowDecorations::Client => gpui::WindowDecorations::Client,
-        },
-    };
+    let window_decorations = window_decorations(cx);
 
     let use_system_window_tabs = WorkspaceSettings::get_global(cx).use_system_window_tabs;
 
@@ -408,6 +421,16 @@ pub fn initialize_workspace(
         #[cfg(not(any(test, target_os = "macos")))]
         initialize_file_watcher(window, cx);
 
+        let mut requested_decorations = window_decorations(cx);
+        cx.observe_global_in::<SettingsStore>(window, move |_, window, cx| {
+            let decorations = window_decorations(cx);
+            if decorations != requested_decorations {
+                requested_decorations = decorations;
+                window.request_decorations(decorations);
+            }
+        })
+        .detach();
+
         if let Some(specs) = window.gpu_specs() {
             log::info!("Using GPU: {:?}", specs);
             show_software_emulation_warning_if_needed(specs.clone(), window, cx);
@@ -470,11 +493,19 @@ pub fn initialize_workspace(
             cx.new(|_| go_to_line::cursor_position::CursorPosition::new(workspace));
         let line_ending_indicator =
             cx.new(|_| line_ending_selector::LineEndingIndicator::default());
+        let input_latency_hud = cx.new(|_| miniprofiler_ui::InputLatencyHud::default());
+        workspace.register_action({
+            let input_latency_hud = input_latency_hud.clone();
+            move |_, _: &zed_actions::dev::ToggleInputLatencyHud, window, cx| {
+                input_latency_hud.update(cx, |hud, cx| hud.toggle(window, cx));
+            }
+        });
         workspace.status_bar().update(cx, |status_bar, cx| {
             status_bar.add_left_item(search_button, window, cx);
             status_bar.add_left_item(lsp_button, window, cx);
             status_bar.add_left_item(diagnostic_summary, window, cx);
             status_bar.add_left_item(activity_indicator, window, cx);
+            status_bar.add_right_item(input_latency_hud, window, cx);
             status_bar.add_right_item(edit_prediction_ui, window, cx);
             status_bar.add_right_item(active_buffer_encoding, window, cx);
             status_bar.add_right_item(active_buffer_language, window, cx);
@@ -624,6 +655,10 @@ fn initialize_panels(
     cx.spawn_in(window, async move |workspace_handle, cx| {
         let project_panel = ProjectPanel::load(workspace_handle.clone(), cx.clone());
         let outline_panel = OutlinePanel::load(workspace_handle.clone(), cx.clone());
+        let bookmarks_panel = BookmarksPanel::load(workspace_handle.clone(), cx.clone());
+        let hierarchy_panel = HierarchyPanel::load(workspace_handle.clone(), cx.clone());
+        let language_servers_panel =
+            LanguageServersPanel::load(workspace_handle.clone(), cx.clone());
         let terminal_panel = TerminalPanel::load(workspace_handle.clone(), cx.clone());
         let git_panel = GitPanel::load(workspace_handle.clone(), cx.clone());
         let channels_panel =
@@ -632,6 +667,8 @@ fn initialize_panels(
             workspace_handle.clone(),
             cx.clone(),
         );
+        let diagnostics_panel =
+            diagnostics::DiagnosticsPanel::load(workspace_handle.clone(), cx.clone());
         let debug_panel = DebugPanel::load(workspace_handle.clone(), cx);
 
         async fn add_panel_when_ready(
@@ -652,10 +689,14 @@ fn initialize_panels(
         futures::join!(
             add_panel_when_ready(project_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(outline_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(bookmarks_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(hierarchy_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(language_servers_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(terminal_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(git_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(channels_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(notification_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(diagnostics_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(debug_panel, workspace_handle.clone(), cx.clone()),
             initialize_agent_panel(workspace_handle, prompt_builder, cx.clone()).map(|r| r.log_err()),
         );
@@ -762,6 +803,22 @@ fn register_actions(
         .register_action(|_, _: &Zoom, window, _| {
             window.zoom_window();
         })
+        .register_action(|_, _: &ToggleAlwaysOnTop, window, _| {
+            window.set_keep_above(!window.is_keep_above());
+        })
+        .register_action({
+            let fs = app_state.fs.clone();
+            move |_, _: &ToggleWindowDecorations, _, cx| {
+                let window_decorations = match WorkspaceSettings::get_global(cx).window_decorations
+                {
+                    settings::WindowDecorations::Client => settings::WindowDecorations::Server,
+                    settings::WindowDecorations::Server => settings::WindowDecorations::Client,
+                };
+                update_settings_file(fs.clone(), cx, move |settings, _| {
+                    settings.workspace.window_decorations = Some(window_decorations);
+                });
+            }
+        })
         .register_action(|_, _: &ToggleFullScreen, window, _| {
             window.toggle_fullscreen();
         })
@@ -1016,6 +1073,30 @@ fn register_actions(
                 workspace.toggle_panel_focus::<OutlinePanel>(window, cx);
             },
         )
+        .register_action(
+            |workspace: &mut Workspace,
+             _: &bookmarks_panel::ToggleFocus,
+             window: &mut Window,
+             cx: &mut Context<Workspace>| {
+                workspace.toggle_panel_focus::<BookmarksPanel>(window, cx);
+            },
+        )
+        .register_action(
+            |workspace: &mut Workspace,
+             _: &hierarchy_panel::ToggleFocus,
+             window: &mut Window,
+             cx: &mut Context<Workspace>| {
+                workspace.toggle_panel_focus::<HierarchyPanel>(window, cx);
+            },
+        )
+        .register_action(
+            |workspace: &mut Workspace,
+             _: &language_tools::language_servers_panel::ToggleFocus,
+             window: &mut Window,
+             cx: &mut Context<Workspace>| {
+                workspace.toggle_panel_focus::<LanguageServersPanel>(window, cx);
+            },
+        )
         .register_action(
             |workspace: &mut Workspace,
              _: &collab_ui::collab_panel::ToggleFocus,
@@ -1128,7 +1209,8 @@ fn register_actions(
         })
         .register_action(|workspace, _: &CaptureRecentAudio, window, cx| {
             capture_recent_audio(workspace, window, cx);
-        });
+        })
+        .register_action(capture_screenshot);
 
     #[cfg(not(target_os = "windows"))]
     workspace.register_action(install_cli);
@@ -1277,6 +1359,157 @@ fn install_cli(
     install_cli::install_cli_binary(window, cx)
 }
 
+pub fn handle_system_tray_event(
+    event: system_tray::TrayEvent,
+    app_state: Weak<AppState>,
+    cx: &mut App,
+) {
+    let workspace_window = cx
+        .windows()
+        .into_iter()
+        .find_map(|window| window.downcast::<MultiWorkspace>());
+    let open_empty_window = |cx: &mut App| {
+        if let Some(app_state) = app_state.upgrade() {
+            open_new(Default::default(), app_state, cx, |_, _, cx| {
+                cx.activate(true)
+            })
+            .detach_and_log_err(cx);
+        }
+    };
+    match event {
+        system_tray::TrayEvent::Activate => match workspace_window {
+            Some(workspace_window) => {
+                workspace_window
+                    .update(cx, |_, window, _| window.activate_window())
+                    .log_err();
+            }
+            None => open_empty_window(cx),
+        },
+        system_tray::TrayEvent::NewWindow => match workspace_window {
+            // Dispatched through a workspace so it opens the same kind of
+            // window as the `workspace::NewWindow` action does.
+            Some(workspace_window) => {
+                workspace_window
+                    .update(cx, |_, window, cx| {
+                        window.dispatch_action(NewWindow.boxed_clone(), cx)
+                    })
+                    .log_err();
+            }
+            None => open_empty_window(cx),
+        },
+        system_tray::TrayEvent::RecentProjects => {
+            let open_recent = zed_actions::OpenRecent {
+                create_new_window: false,
+            };
+            match workspace_window {
+                Some(workspace_window) => {
+                    workspace_window
+                        .update(cx, |_, window, cx| {
+                            window.activate_window();
+                            window.dispatch_action(open_recent.boxed_clone(), cx);
+                        })
+                        .log_err();
+                }
+                None => cx.dispatch_action(&open_recent),
+            }
+        }
+        system_tray::TrayEvent::Quit => cx.dispatch_action(&Quit),
+    }
+}
+
+/// Keeps the hotkeys from the `global_hotkeys` setting registered with the
+/// platform, re-registering them whenever the setting changes.
+pub fn init_global_hotkeys(app_state: Weak<AppState>, cx: &mut App) {
+    let mut global_hotkeys = GlobalHotkeys {
+        app_state,
+        registered: HashMap::default(),
+        subscriptions: Vec::new(),
+    };
+    global_hotkeys.settings_changed(cx);
+    cx.observe_global::<SettingsStore>(move |cx| global_hotkeys.settings_changed(cx))
+        .detach();
+}
+
+struct GlobalHotkeys {
+    app_state: Weak<AppState>,
+    registered: HashMap<String, GlobalHotkeyAction>,
+    subscriptions: Vec<Subscription>,
+}
+
+impl GlobalHotkeys {
+    fn settings_changed(&mut self, cx: &mut App) {
+        let hotkeys = &WorkspaceSettings::get_global(cx).global_hotkeys;
+        if *hotkeys == self.registered {
+            return;
+        }
+        self.registered = hotkeys.clone();
+
+        // Release the previous hotkeys first, in case a keystroke is kept with
+        // a different action.
+        self.subscriptions.clear();
+        for (keystroke, action) in self.registered.clone() {
+            let keystroke = match Keystroke::parse(&keystroke) {
+                Ok(keystroke) => keystroke,
+                Err(error) => {
+                    log::error!("invalid global hotkey: {error}");
+                    continue;
+                }
+            };
+            let app_state = self.app_state.clone();
+            let description = match action {
+                GlobalHotkeyAction::FocusLastWorkspace => "Focus the last Zed window",
+                GlobalHotkeyAction::QuickCapture => "Open a Zed window to jot something down",
+            };
+            match cx.register_global_hotkey(&keystroke, description, move |cx| {
+                handle_global_hotkey(action, app_state.clone(), cx)
+            }) {
+                Ok(subscription) => self.subscriptions.push(subscription),
+                Err(error) => log::error!("{error:#}"),
+            }
+        }
+    }
+}
+
+fn handle_global_hotkey(action: GlobalHotkeyAction, app_state: Weak<AppState>, cx: &mut App) {
+    let Some(app_state) = app_state.upgrade() else {
+        return;
+    };
+    match action {
+        GlobalHotkeyAction::FocusLastWorkspace => {
+            let last_workspace_window = cx
+                .window_stack()
+                .unwrap_or_else(|| cx.windows())
+                .into_iter()
+                .find_map(|window| window.downcast::<MultiWorkspace>());
+            match last_workspace_window {
+                Some(workspace_window) => {
+                    workspace_window
+                        .update(cx, |_, window, _| window.activate_window())
+                        .log_err();
+                }
+                None => {
+                    open_new(Default::default(), app_state, cx, |_, _, cx| {
+                        cx.activate(true)
+                    })
+                    .detach_and_log_err(cx);
+                }
+            }
+        }
+        GlobalHotkeyAction::QuickCapture => {
+            open_new(
+                Default::default(),
+                app_state,
+                cx,
+                |workspace, window, cx| {
+                    cx.activate(true);
+                    Editor::new_file(workspace, &Default::default(), window, cx)
+                },
+            )
+            .detach_and_log_err(cx);
+        }
+    }
+}
+
 static WAITING_QUIT_CONFIRMATION: AtomicBool = AtomicBool::new(false);
 fn quit(_: &Quit, cx: &mut App) {
     if WAITING_QUIT_CONFIRMATION.load(atomic::Ordering::Acquire) {
@@ -1620,6 +1853,61 @@ pub fn handle_settings_file_changes(
     .detach();
 }
 
+/// Loads the theme file that the `theme` setting points at, if any, and
+/// reloads it whenever it changes.
+pub fn watch_theme_file(fs: Arc<dyn Fs>, cx: &mut App) {
+    let mut watched_theme_path = None;
+    let mut _watch_task: Option<Task<()>> = None;
+    let mut update_watched_theme_path = move |cx: &mut App| {
+        let appearance = SystemAppearance::global(cx).0;
+        let theme_name = ThemeSettings::get_global(cx).theme.name(appearance);
+        let theme_path = theme::theme_file_path(&theme_name.0);
+        if theme_path == watched_theme_path {
+            return;
+        }
+        watched_theme_path = theme_path.clone();
+        _watch_task = theme_path.map(|theme_path| {
+            let fs = fs.clone();
+            cx.spawn(async move |cx| {
+                // Editors often save by replacing the file, so the directory is
+                // watched rather than the file itself.
+                let watched_dir = theme_path.parent().unwrap_or(&theme_path);
+                let (mut events, _watcher) =
+                    fs.watch(watched_dir, Duration::from_millis(100)).await;
+                reload_theme_file(&theme_path, fs.clone(), cx).await;
+                while let Some(events) = events.next().await {
+                    if events.iter().any(|event| event.path == theme_path) {
+                        reload_theme_file(&theme_path, fs.clone(), cx).await;
+                    }
+                }
+            })
+        });
+    };
+    update_watched_theme_path(cx);
+    cx.observe_global::<SettingsStore>(update_watched_theme_path)
+        .detach();
+}
+
+async fn reload_theme_file(theme_path: &Path, fs: Arc<dyn Fs>, cx: &mut AsyncApp) {
+    struct ThemeFileErrorNotification;
+    let notification_id = NotificationId::unique::<ThemeFileErrorNotification>();
+
+    let theme_registry = cx.update(|cx| ThemeRegistry::global(cx));
+    let result = theme_registry.load_theme_file(theme_path, fs).await;
+    cx.update(|cx| match result {
+        Ok(()) => {
+            dismiss_app_notification(&notification_id, cx);
+            GlobalTheme::reload_theme(cx);
+        }
+        Err(error) => {
+            log::error!("failed to load theme file: {error:#}");
+            show_app_notification(notification_id, cx, move |cx| {
+                cx.new(|cx| MessageNotification::new(format!("Invalid theme file\n{error:#}"), cx))
+            });
+        }
+    });
+}
+
 pub fn handle_keymap_file_changes(
     mut user_keymap_file_rx: mpsc::UnboundedReceiver<String>,
     user_keymap_watcher: gpui::Task<()>,
@@ -2103,6 +2391,50 @@ fn open_settings_file(
     .detach_and_log_err(cx);
 }
 
+fn capture_screenshot(
+    workspace: &mut Workspace,
+    action: &CaptureScreenshot,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let screenshot = if action.focused_pane {
+        window.capture_element(workspace.active_pane().read(cx).bounds())
+    } else {
+        window.capture_screenshot()
+    };
+    let screenshot = match screenshot {
+        Ok(screenshot) => screenshot,
+        Err(error) => {
+            workspace.show_error(&error.context("failed to capture a screenshot"), cx);
+            return;
+        }
+    };
+
+    cx.spawn(async move |workspace, cx| {
+        let png = cx
+            .background_spawn(async move { gpui::Image::from_rgba(&screenshot) })
+            .await;
+        workspace.update(cx, |workspace, cx| match png {
+            Ok(png) => {
+                cx.write_to_clipboard(ClipboardItem::new_image(&png));
+                struct ScreenshotCopied;
+                workspace.show_toast(
+                    Toast::new(
+                        NotificationId::unique::<ScreenshotCopied>(),
+                        "Copied the screenshot to the clipboard.",
+                    )
+                    .autohide(),
+                    cx,
+                );
+            }
+            Err(error) => {
+                workspace.show_error(&error.context("failed to encode the screenshot"), cx)
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
 fn capture_recent_audio(workspace: &mut Workspace, _: &mut Window, cx: &mut Context<Workspace>) {
     struct CaptureRecentAudioNotification {
         focus_handle: gpui::FocusHandle,
@@ -4801,6 +5133,7 @@ mod tests {
                 "auto_update",
                 "branch_picker",
                 "bedrock",
+                "bookmarks_panel",
                 "branches",
                 "buffer_search",
                 "channel_modal",
@@ -4816,6 +5149,7 @@ mod tests {
                 "debugger",
                 "dev",
                 "diagnostics",
+                "diagnostics_panel",
                 "edit_prediction",
                 "editor",
                 "encoding_selector",
@@ -4827,6 +5161,7 @@ mod tests {
                 "git_panel",
                 "git_picker",
                 "go_to_line",
+                "hierarchy_panel",
                 "highlights_tree_view",
                 "icon_theme_selector",
                 "image_viewer",
@@ -4835,6 +5170,7 @@ mod tests {
                 "keymap_editor",
                 "keystroke_input",
                 "language_selector",
+                "language_servers_panel",
                 "welcome",
                 "line_ending_selector",
                 "lsp_tool",
@@ -5012,6 +5348,8 @@ mod tests {
             git_ui::init(cx);
             project_panel::init(cx);
             outline_panel::init(cx);
+            bookmarks_panel::init(cx);
+            hierarchy_panel::init(cx);
             terminal_view::init(cx);
             copilot_chat::init(
                 app_state.fs.clone(),
@@ -104,6 +104,16 @@ pub struct Extensions {
     pub id: Option<String>,
 }
 
+/// Copies a PNG screenshot of the window to the clipboard.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema, Action)]
+#[action(namespace = zed)]
+#[serde(deny_unknown_fields)]
+pub struct CaptureScreenshot {
+    /// Captures only the focused pane instead of the whole window.
+    #[serde(default)]
+    pub focused_pane: bool,
+}
+
 /// Opens the ACP registry.
 #[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema, Action)]
 #[action(namespace = zed)]
@@ -202,7 +212,10 @@ pub mod dev {
         dev,
         [
             /// Toggles the developer inspector for debugging UI elements.
-            ToggleInspector
+            ToggleInspector,
+            /// Toggles a status bar readout of how long input takes to show up
+            /// on screen.
+            ToggleInputLatencyHud
         ]
     );
 }
@@ -609,6 +622,10 @@ pub mod outline {
     );
     /// A pointer to outline::toggle function, exposed here to sewer the breadcrumbs <-> outline dependency.
     pub static TOGGLE_OUTLINE: OnceLock<fn(AnyView, &mut Window, &mut App)> = OnceLock::new();
+    /// A pointer to outline::toggle_in_scope function, which shows only the symbols within the
+    /// breadcrumb symbol at the given index.
+    pub static TOGGLE_OUTLINE_IN_SCOPE: OnceLock<fn(AnyView, usize, &mut Window, &mut App)> =
+        OnceLock::new();
 }
 
 actions!(